@@ -11,12 +11,14 @@ mod activation;
 mod callable_value;
 mod clamp;
 mod debug;
+mod debugger;
 mod error;
 mod flv;
 mod fscommand;
 pub(crate) mod globals;
 mod object;
 mod object_reference;
+pub mod profiler;
 mod property;
 mod property_map;
 mod runtime;
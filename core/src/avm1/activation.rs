@@ -1,6 +1,6 @@
 use crate::avm1::callable_value::CallableValue;
 use crate::avm1::error::Error;
-use crate::avm1::function::{Avm1Function, ExecutionReason, FunctionObject};
+use crate::avm1::function::{Avm1Function, ExecutionName, ExecutionReason, FunctionObject};
 use crate::avm1::object::{Object, TObject};
 use crate::avm1::property::Attribute;
 use crate::avm1::runtime::skip_actions;
@@ -12,6 +12,7 @@ use crate::display_object::{
     DisplayObject, DisplayObjectContainer, MovieClip, TDisplayObject, TDisplayObjectContainer,
 };
 use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use crate::limits::ScriptTimeoutAction;
 use crate::loader::MovieLoaderVMData;
 use crate::string::{AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfSlice;
@@ -172,6 +173,14 @@ impl<'a> ActivationIdentifier<'a> {
     pub fn depth(&self) -> u16 {
         self.depth
     }
+
+    pub fn parent(&self) -> Option<&'a ActivationIdentifier<'a>> {
+        self.parent
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 /// Represents a single activation of a given AVM1 function or keyframe.
@@ -440,6 +449,33 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
     }
 
+    /// Runs the actions of a function.
+    ///
+    /// If profiling is enabled, the call is timed and recorded under the function's name, or
+    /// under `name` if the function is anonymous.
+    pub fn run_function_actions(
+        &mut self,
+        function: &Avm1Function<'gc>,
+        name: ExecutionName<'gc>,
+    ) -> Result<ReturnType<'gc>, Error<'gc>> {
+        let code = function.data();
+        let Some(profiler) = self.context.avm1.profiler_mut() else {
+            return self.run_actions(code);
+        };
+
+        match function.name().map(ExecutionName::Dynamic).unwrap_or(name) {
+            ExecutionName::Static(name) => profiler.enter(&code.movie, code.start, name),
+            ExecutionName::Dynamic(name) => {
+                profiler.enter(&code.movie, code.start, &name.to_utf8_lossy())
+            }
+        }
+        let result = self.run_actions(code);
+        if let Some(profiler) = self.context.avm1.profiler_mut() {
+            profiler.exit();
+        }
+        result
+    }
+
     /// Run a single action from a given action reader.
     fn do_action<'b>(
         &mut self,
@@ -449,7 +485,15 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         *self.context.actions_since_timeout_check += 1;
         if *self.context.actions_since_timeout_check >= 2000 {
             *self.context.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
+            // AVM1 has no `ScriptTimeoutError`, so the script is aborted right away.
+            let update_start = self.context.update_start;
+            let max_execution_duration = self.context.max_execution_duration;
+            if self
+                .context
+                .script_timeout
+                .check(update_start, max_execution_duration, false)
+                == ScriptTimeoutAction::Abort
+            {
                 return Err(Error::ExecutionTimeout);
             }
         }
@@ -458,6 +502,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             //Executing beyond the end of a function constitutes an implicit return.
             Ok(FrameControl::Return(ReturnType::Implicit))
         } else {
+            if self.context.debugger.is_some() {
+                crate::avm1::debugger::before_action(self, data, reader.get_ref());
+            }
+
             let action = reader.read_action()?;
             avm_debug!(
                 self.context.avm1,
@@ -0,0 +1,222 @@
+//! Lets the script debugger pause and inspect AVM1 code.
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::scope::ScopeClass;
+use crate::avm1::{Object, TObject, Value};
+use crate::debugger::{
+    pause, BreakpointLocation, Debuggee, Literal, NamedValues, StackFrame, StopReason,
+};
+use crate::display_object::TDisplayObject;
+use crate::string::AvmString;
+use crate::tag_utils::SwfSlice;
+
+/// The number of global registers, which are used by code outside of `DefineFunction2`.
+const NUM_GLOBAL_REGISTERS: u8 = 4;
+
+/// Checks whether the debugger wants to pause before running the action at the start of
+/// `remaining`, and pauses if so.
+pub fn before_action(activation: &mut Activation<'_, '_>, data: &SwfSlice, remaining: &[u8]) {
+    let Some(debugger) = activation.context.debugger.as_mut() else {
+        return;
+    };
+    let offset = remaining.as_ptr() as usize - data.movie.data().as_ptr() as usize;
+    let url = data.movie.url();
+
+    let reason = debugger.check(
+        activation.id.depth() as usize,
+        || true,
+        |location| {
+            matches!(location, BreakpointLocation::Action { swf, offset: o }
+                if *o == offset && swf == url)
+        },
+    );
+
+    if let Some(reason) = reason {
+        let mut debugger = activation
+            .context
+            .debugger
+            .take()
+            .expect("Debugger was attached");
+        pause(
+            &mut Avm1Debuggee {
+                activation,
+                url: url.to_string(),
+                offset,
+            },
+            &mut debugger,
+            reason,
+        );
+        *activation.context.debugger = Some(debugger);
+    }
+}
+
+struct Avm1Debuggee<'a, 'b, 'gc> {
+    activation: &'a mut Activation<'b, 'gc>,
+    url: String,
+    offset: usize,
+}
+
+impl<'a, 'b, 'gc> Avm1Debuggee<'a, 'b, 'gc> {
+    fn object_children(&mut self, object: Object<'gc>) -> NamedValues<Value<'gc>> {
+        let activation = &mut *self.activation;
+        object
+            .get_keys(activation, false)
+            .into_iter()
+            .filter_map(|key| {
+                let value = object.get(key, activation).ok()?;
+                Some((key.to_string(), value))
+            })
+            .collect()
+    }
+}
+
+impl<'a, 'b, 'gc> Debuggee for Avm1Debuggee<'a, 'b, 'gc> {
+    type Value = Value<'gc>;
+
+    fn depth(&self) -> usize {
+        self.activation.id.depth() as usize
+    }
+
+    fn stack_trace(&mut self) -> Vec<StackFrame> {
+        let mut frames = Vec::new();
+        let mut id: Option<&ActivationIdentifier> = Some(&self.activation.id);
+        while let Some(current) = id {
+            frames.push(StackFrame {
+                name: current.name().to_string(),
+                file: None,
+                line: None,
+                location: None,
+            });
+            id = current.parent();
+        }
+        if let Some(frame) = frames.first_mut() {
+            frame.location = Some(BreakpointLocation::Action {
+                swf: self.url.clone(),
+                offset: self.offset,
+            });
+        }
+        frames
+    }
+
+    fn scopes(&mut self) -> Vec<(String, NamedValues<Value<'gc>>)> {
+        let mut scopes = Vec::new();
+        let mut scope = Some(self.activation.scope());
+        while let Some(current) = scope {
+            let name = match current.class() {
+                ScopeClass::Global => "Global",
+                ScopeClass::Target => "Target",
+                ScopeClass::Local => "Locals",
+                ScopeClass::With => "With",
+            };
+            let values = self.object_children(current.locals_cell());
+            scopes.push((name.to_string(), values));
+            scope = current.parent();
+        }
+
+        let registers = if self.activation.has_local_register(0) {
+            (0..=u8::MAX)
+                .take_while(|id| self.activation.has_local_register(*id))
+                .map(|id| {
+                    (
+                        format!("register{id}"),
+                        self.activation.current_register(id),
+                    )
+                })
+                .collect()
+        } else {
+            (0..NUM_GLOBAL_REGISTERS)
+                .map(|id| {
+                    (
+                        format!("register{id}"),
+                        self.activation.current_register(id),
+                    )
+                })
+                .collect()
+        };
+        scopes.push(("Registers".to_string(), registers));
+
+        let stack = self
+            .activation
+            .context
+            .avm1
+            .stack()
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (i.to_string(), *value))
+            .collect();
+        scopes.push(("Stack".to_string(), stack));
+
+        scopes
+    }
+
+    fn children(&mut self, value: Value<'gc>) -> NamedValues<Value<'gc>> {
+        match value {
+            Value::Object(_) | Value::MovieClip(_) => {
+                let object = value.coerce_to_object(self.activation);
+                self.object_children(object)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn describe(&mut self, value: Value<'gc>) -> (String, String, bool) {
+        match value {
+            Value::Undefined => ("undefined".to_string(), "undefined".to_string(), false),
+            Value::Null => ("null".to_string(), "null".to_string(), false),
+            Value::Bool(value) => (value.to_string(), "boolean".to_string(), false),
+            Value::Number(value) => (value.to_string(), "number".to_string(), false),
+            Value::String(value) => (format!("{value:?}"), "string".to_string(), false),
+            Value::Object(_) | Value::MovieClip(_) => {
+                let object = value.coerce_to_object(self.activation);
+                if let Some(display_object) = object.as_display_object() {
+                    (
+                        display_object.path().to_string(),
+                        "movieclip".to_string(),
+                        true,
+                    )
+                } else if object.as_executable().is_some() {
+                    ("[type Function]".to_string(), "function".to_string(), true)
+                } else {
+                    ("[object Object]".to_string(), "object".to_string(), true)
+                }
+            }
+        }
+    }
+
+    fn key(&mut self, value: Value<'gc>) -> String {
+        value
+            .coerce_to_string(self.activation)
+            .map(|key| key.to_string())
+            .unwrap_or_default()
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<Value<'gc>> {
+        let name = AvmString::new_utf8(self.activation.context.gc_context, name);
+        self.activation
+            .get_variable(name)
+            .ok()
+            .map(|value| value.into())
+    }
+
+    fn this(&mut self) -> Value<'gc> {
+        self.activation.this_cell()
+    }
+
+    fn member(&mut self, value: Value<'gc>, name: &str) -> Option<Value<'gc>> {
+        let object = value.coerce_to_object(self.activation);
+        let name = AvmString::new_utf8(self.activation.context.gc_context, name);
+        object.get(name, self.activation).ok()
+    }
+
+    fn literal(&mut self, literal: &Literal) -> Value<'gc> {
+        match literal {
+            Literal::Undefined => Value::Undefined,
+            Literal::Null => Value::Null,
+            Literal::Bool(value) => (*value).into(),
+            Literal::Number(value) => (*value).into(),
+            Literal::String(value) => {
+                AvmString::new_utf8(self.activation.context.gc_context, value).into()
+            }
+        }
+    }
+}
@@ -136,6 +136,11 @@ impl<'gc> Avm1Function<'gc> {
         self.name
     }
 
+    /// The clip this function was defined on.
+    pub fn base_clip(&self) -> DisplayObject<'gc> {
+        self.base_clip
+    }
+
     pub fn scope(&self) -> Gc<'gc, Scope<'gc>> {
         self.scope
     }
@@ -316,6 +321,7 @@ impl fmt::Debug for Executable<'_> {
 }
 
 /// Indicates the default name to use for this execution in debug builds.
+#[derive(Clone, Copy)]
 pub enum ExecutionName<'gc> {
     Static(&'static str),
     Dynamic(AvmString<'gc>),
@@ -400,7 +406,7 @@ impl<'gc> Executable<'gc> {
         // The caller is the previous callee.
         let arguments_caller = activation.callee;
 
-        let name = if cfg!(feature = "avm_debug") {
+        let debug_name = if cfg!(feature = "avm_debug") {
             Cow::Owned(af.debug_string_for_call(name, args))
         } else {
             Cow::Borrowed("[Anonymous]")
@@ -418,7 +424,9 @@ impl<'gc> Executable<'gc> {
         let max_recursion_depth = activation.context.avm1.max_recursion_depth();
         let mut frame = Activation::from_action(
             activation.context.reborrow(),
-            activation.id.function(name, reason, max_recursion_depth)?,
+            activation
+                .id
+                .function(debug_name, reason, max_recursion_depth)?,
             swf_version,
             child_scope,
             af.constant_pool,
@@ -453,7 +461,7 @@ impl<'gc> Executable<'gc> {
             }
         }
 
-        Ok(frame.run_actions(af.data.clone())?.value())
+        Ok(frame.run_function_actions(af, name)?.value())
     }
 }
 
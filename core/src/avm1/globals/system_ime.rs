@@ -5,6 +5,7 @@ use crate::avm1::object::Object;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{ScriptObject, Value};
 use crate::context::GcContext;
+use crate::events::ImeEvent;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "ALPHANUMERIC_FULL" => string("ALPHANUMERIC_FULL"; DONT_ENUM | DONT_DELETE | READ_ONLY);
@@ -32,10 +33,13 @@ fn on_ime_composition<'gc>(
 }
 
 fn do_conversion<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text) = activation.context.focus_tracker.get_edit_text() {
+        text.commit_ime_composition(&mut activation.context);
+    }
     Ok(true.into())
 }
 
@@ -48,19 +52,31 @@ fn get_conversion_mode<'gc>(
 }
 
 fn get_enabled<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(false.into())
+    Ok(activation.context.ui.ime_enabled().into())
 }
 
 fn set_composition_string<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(false.into())
+    let Some(text) = activation.context.focus_tracker.get_edit_text() else {
+        return Ok(false.into());
+    };
+    let composition = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+    text.ime(
+        ImeEvent::Preedit(composition, None),
+        &mut activation.context,
+    );
+    Ok(true.into())
 }
 
 fn set_conversion_mode<'gc>(
@@ -72,11 +88,17 @@ fn set_conversion_mode<'gc>(
 }
 
 fn set_enabled<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(false.into())
+    let enabled = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+    let focus_tracker = activation.context.focus_tracker;
+    focus_tracker.set_ime_enabled(enabled, &mut activation.context);
+    Ok(true.into())
 }
 
 pub fn create<'gc>(
@@ -66,6 +66,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "border" => property(tf_getter!(border), tf_setter!(set_border));
     "borderColor" => property(tf_getter!(border_color), tf_setter!(set_border_color));
     "bottomScroll" => property(tf_getter!(bottom_scroll));
+    "condenseWhite" => property(tf_getter!(condense_white), tf_setter!(set_condense_white));
     "embedFonts" => property(tf_getter!(embed_fonts), tf_setter!(set_embed_fonts));
     "filters" => property(tf_getter!(filters), tf_setter!(set_filters); DONT_DELETE | DONT_ENUM | VERSION_8);
     "getDepth" => method(globals::get_depth; DONT_ENUM | DONT_DELETE | READ_ONLY | VERSION_6);
@@ -437,6 +438,23 @@ pub fn set_border<'gc>(
     Ok(())
 }
 
+pub fn condense_white<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.condense_white().into())
+}
+
+pub fn set_condense_white<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let condense_white = value.as_bool(activation.swf_version());
+    this.set_condense_white(activation.context.gc_context, condense_white);
+    Ok(())
+}
+
 pub fn border_color<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
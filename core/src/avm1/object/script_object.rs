@@ -143,6 +143,24 @@ impl<'gc> ScriptObject<'gc> {
             })
             .collect()
     }
+
+    /// Lists the values of all own properties, including hidden ones, and the getters and
+    /// setters of virtual properties.
+    ///
+    /// Doesn't call any getters, thus cannot cause any side-effects.
+    pub fn referenced_values(&self) -> Vec<(AvmString<'gc>, Value<'gc>)> {
+        let mut values = Vec::new();
+        for (name, property) in self.0.read().properties.iter() {
+            values.push((name, property.data()));
+            if let Some(getter) = property.getter() {
+                values.push((name, getter.into()));
+            }
+            if let Some(setter) = property.setter() {
+                values.push((name, setter.into()));
+            }
+        }
+        values
+    }
 }
 
 impl<'gc> TObject<'gc> for ScriptObject<'gc> {
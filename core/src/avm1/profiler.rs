@@ -0,0 +1,265 @@
+//! Opt-in timing of AVM1 function calls.
+
+use crate::profiler::{SpanStats, SpanTracker};
+use crate::tag_utils::SwfMovie;
+use fnv::FnvHashMap;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::Arc;
+use web_time::Duration;
+
+/// The number of frames whose profiles are kept.
+const MAX_FRAMES: usize = 600;
+
+/// The index of a function in `Profiler::functions`.
+pub type FunctionId = usize;
+
+/// An AVM1 function that was called while profiling.
+#[derive(Clone, Debug)]
+pub struct ProfiledFunction {
+    /// The URL of the movie that defined the function.
+    pub url: String,
+
+    /// The offset of the function's actions in the movie.
+    pub offset: usize,
+
+    /// The name of the function, or the name it was called by if it is anonymous.
+    pub name: String,
+}
+
+impl ProfiledFunction {
+    /// A name that identifies this function in flamegraphs.
+    pub fn label(&self) -> String {
+        // Semicolons separate the functions of a stack in the folded format.
+        format!("{} ({}:{})", self.name, self.url, self.offset).replace(';', ":")
+    }
+}
+
+/// The function calls made between two frames.
+#[derive(Clone, Debug, Default)]
+pub struct FrameProfile {
+    /// The number of frames that were profiled before this one.
+    pub frame: u64,
+
+    /// The total time spent in AVM1 functions during this frame.
+    pub time: Duration,
+
+    /// The statistics of every called function.
+    ///
+    /// The total time of recursive calls is only counted once.
+    pub functions: FnvHashMap<FunctionId, SpanStats>,
+}
+
+/// Records how often AVM1 functions are called and how long they run.
+#[derive(Default)]
+pub struct Profiler {
+    functions: Vec<ProfiledFunction>,
+
+    /// The functions defined at every movie and offset, by the address of the movie.
+    function_ids: FnvHashMap<(usize, usize), Vec<FunctionId>>,
+
+    /// The calls that are currently running.
+    calls: SpanTracker<FunctionId>,
+
+    current_frame: FrameProfile,
+
+    /// The most recent frames, oldest first.
+    frames: VecDeque<FrameProfile>,
+
+    /// The statistics of every function over all profiled frames.
+    totals: FnvHashMap<FunctionId, SpanStats>,
+
+    /// The exclusive time spent in every distinct call stack, outermost function first.
+    stacks: FnvHashMap<Vec<FunctionId>, Duration>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn functions(&self) -> &[ProfiledFunction] {
+        &self.functions
+    }
+
+    /// The most recent frames, oldest first.
+    pub fn frames(&self) -> &VecDeque<FrameProfile> {
+        &self.frames
+    }
+
+    pub fn totals(&self) -> &FnvHashMap<FunctionId, SpanStats> {
+        &self.totals
+    }
+
+    /// Discards everything that was recorded, except for the calls that are currently running.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.totals.clear();
+        self.stacks.clear();
+        self.current_frame.functions.clear();
+        self.current_frame.time = Duration::ZERO;
+    }
+
+    /// Records the start of a call to the function whose actions start at `offset` in `movie`.
+    pub fn enter(&mut self, movie: &Arc<SwfMovie>, offset: usize, name: &str) {
+        let candidates = self
+            .function_ids
+            .entry((Arc::as_ptr(movie) as usize, offset))
+            .or_default();
+        let function = match candidates
+            .iter()
+            .find(|id| self.functions[**id].name == name)
+        {
+            Some(id) => *id,
+            None => {
+                let id = self.functions.len();
+                self.functions.push(ProfiledFunction {
+                    url: movie.url().to_string(),
+                    offset,
+                    name: name.to_string(),
+                });
+                candidates.push(id);
+                id
+            }
+        };
+
+        self.calls.begin(function);
+    }
+
+    /// Records the end of the innermost running call.
+    pub fn exit(&mut self) {
+        let Some(call) = self.calls.end() else {
+            return;
+        };
+        let function = call.key;
+        let is_recursive = self.calls.open_keys().any(|f| *f == function);
+        let total = if is_recursive {
+            Duration::ZERO
+        } else {
+            call.duration
+        };
+
+        self.current_frame
+            .functions
+            .entry(function)
+            .or_default()
+            .add_call(total, call.exclusive);
+        self.totals
+            .entry(function)
+            .or_default()
+            .add_call(total, call.exclusive);
+
+        let mut stack: Vec<_> = self.calls.open_keys().copied().collect();
+        stack.push(function);
+        *self.stacks.entry(stack).or_default() += call.exclusive;
+
+        if self.calls.open_keys().next().is_none() {
+            self.current_frame.time += call.duration;
+        }
+    }
+
+    /// Finishes the profile of the current frame.
+    pub fn end_frame(&mut self) {
+        let next_frame = self.current_frame.frame + 1;
+        let frame = std::mem::replace(
+            &mut self.current_frame,
+            FrameProfile {
+                frame: next_frame,
+                ..Default::default()
+            },
+        );
+        if self.frames.len() >= MAX_FRAMES {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Writes the recorded call stacks in the folded format used by flamegraph tools, such as
+    /// `inferno` and `flamegraph.pl`, with the exclusive time of every stack in microseconds.
+    pub fn to_folded_stacks(&self) -> String {
+        let labels: Vec<_> = self.functions.iter().map(|f| f.label()).collect();
+        let mut lines: Vec<_> = self
+            .stacks
+            .iter()
+            .filter(|(_, time)| time.as_micros() > 0)
+            .map(|(stack, time)| {
+                let stack: Vec<_> = stack.iter().map(|id| labels[*id].as_str()).collect();
+                (stack.join(";"), time.as_micros())
+            })
+            .collect();
+        lines.sort();
+
+        let mut output = String::new();
+        for (stack, time) in lines {
+            let _ = writeln!(output, "{stack} {time}");
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_calls_per_frame_and_stack() {
+        let movie = Arc::new(SwfMovie::empty(8));
+        let mut profiler = Profiler::new();
+
+        profiler.enter(&movie, 10, "outer");
+        profiler.enter(&movie, 20, "inner");
+        profiler.exit();
+        profiler.enter(&movie, 20, "inner");
+        profiler.exit();
+        profiler.exit();
+        profiler.end_frame();
+        profiler.enter(&movie, 20, "inner");
+        profiler.exit();
+        profiler.end_frame();
+
+        assert_eq!(profiler.functions().len(), 2);
+        assert_eq!(profiler.functions()[1].name, "inner");
+        assert_eq!(profiler.frames().len(), 2);
+        assert_eq!(profiler.frames()[0].functions[&1].calls, 2);
+        assert_eq!(profiler.frames()[1].functions[&1].calls, 1);
+        assert_eq!(profiler.frames()[1].frame, 1);
+        assert_eq!(profiler.totals()[&0].calls, 1);
+        assert_eq!(profiler.totals()[&1].calls, 3);
+        assert_eq!(profiler.stacks.len(), 3);
+        assert!(profiler.stacks.contains_key(&vec![0, 1]));
+    }
+
+    #[test]
+    fn distinguishes_functions_by_name() {
+        let movie = Arc::new(SwfMovie::empty(8));
+        let mut profiler = Profiler::new();
+
+        profiler.enter(&movie, 10, "onEnterFrame");
+        profiler.exit();
+        profiler.enter(&movie, 10, "onPress");
+        profiler.exit();
+        profiler.enter(&movie, 10, "onEnterFrame");
+        profiler.exit();
+
+        assert_eq!(profiler.functions().len(), 2);
+        assert_eq!(profiler.totals()[&0].calls, 2);
+        assert_eq!(profiler.functions()[1].label(), "onPress (file:///:10)");
+    }
+
+    #[test]
+    fn recursive_calls_are_counted_once_in_total_time() {
+        let movie = Arc::new(SwfMovie::empty(8));
+        let mut profiler = Profiler::new();
+
+        profiler.enter(&movie, 10, "f");
+        profiler.enter(&movie, 10, "f");
+        std::thread::sleep(Duration::from_millis(2));
+        profiler.exit();
+        profiler.exit();
+
+        let stats = profiler.totals()[&0];
+        assert_eq!(stats.calls, 2);
+        assert_eq!(stats.total, profiler.current_frame.time);
+        assert!(stats.exclusive <= stats.total);
+    }
+}
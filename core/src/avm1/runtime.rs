@@ -3,6 +3,7 @@ use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::globals::{as_broadcaster, create_globals};
 use crate::avm1::object::stage_object;
 use crate::avm1::object::TObject;
+use crate::avm1::profiler::Profiler;
 use crate::avm1::property_map::PropertyMap;
 use crate::avm1::scope::Scope;
 use crate::avm1::{scope, Activation, ActivationIdentifier, Error, Object, Value};
@@ -85,6 +86,10 @@ pub struct Avm1<'gc> {
     /// More examples of this are in the movieclip_invalid_get_bounds_X tests.
     use_new_invalid_bounds_value: bool,
 
+    /// The timings of function calls, if profiling is enabled.
+    #[collect(require_static)]
+    profiler: Option<Profiler>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -118,6 +123,7 @@ impl<'gc> Avm1<'gc> {
             #[cfg(feature = "avm_debug")]
             debug_output: false,
             use_new_invalid_bounds_value: false,
+            profiler: None,
         }
     }
 
@@ -329,6 +335,28 @@ impl<'gc> Avm1<'gc> {
         }
     }
 
+    /// Enables or disables the profiling of function calls.
+    ///
+    /// Disabling profiling discards everything that was recorded.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        if enabled != self.profiler.is_some() {
+            self.profiler = enabled.then(Profiler::new);
+        }
+    }
+
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    pub fn profiler_mut(&mut self) -> Option<&mut Profiler> {
+        self.profiler.as_mut()
+    }
+
+    /// The values on the stack, from bottom to top.
+    pub fn stack(&self) -> &[Value<'gc>] {
+        &self.stack
+    }
+
     pub fn stack_len(&self) -> usize {
         self.stack.len()
     }
@@ -495,6 +523,10 @@ impl<'gc> Avm1<'gc> {
             .movie_clip_on_load(context.action_queue);
 
         *context.frame_phase = FramePhase::Idle;
+
+        if let Some(profiler) = context.avm1.profiler_mut() {
+            profiler.end_frame();
+        }
     }
 
     /// Adds a movie clip to the execution list.
@@ -595,6 +627,11 @@ pub fn root_error_handler<'gc>(activation: &mut Activation<'_, 'gc>, error: Erro
         Error::InvalidSwf(swf_error) => {
             tracing::error!("{}: {}", error, swf_error);
         }
+        Error::ExecutionTimeout => {
+            tracing::error!("{}", error);
+            // The player decides whether to halt once the update is over.
+            return;
+        }
         _ => {
             tracing::error!("{}", error);
         }
@@ -7,11 +7,13 @@ use crate::avm2::error::make_error_1107;
 use crate::avm2::function::Executable;
 use crate::avm2::globals::SystemClasses;
 use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::profiler::TraceCategory;
 use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::{GcContext, UpdateContext};
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
+use crate::worker::SharedMemory;
 use crate::PlayerRuntime;
 
 use fnv::FnvHashMap;
@@ -36,6 +38,7 @@ mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+mod debugger;
 mod domain;
 mod dynamic_map;
 mod e4x;
@@ -51,11 +54,15 @@ mod multiname;
 mod namespace;
 pub mod object;
 mod op;
+mod optimize;
 mod parameters;
+pub mod profiler;
 pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+mod register_ir;
+pub mod sampler;
 mod scope;
 mod script;
 #[cfg(feature = "known_stubs")]
@@ -73,6 +80,7 @@ pub use crate::avm2::array::ArrayStorage;
 pub use crate::avm2::call_stack::{CallNode, CallStack};
 #[allow(unused)] // For debug_ui
 pub use crate::avm2::domain::{Domain, DomainPtr};
+pub use crate::avm2::dynamic_map::DynamicKey;
 pub use crate::avm2::error::Error;
 pub use crate::avm2::flv::FlvValueAvm2Ext;
 pub use crate::avm2::globals::flash::ui::context_menu::make_context_menu_state;
@@ -82,7 +90,9 @@ pub use crate::avm2::object::{
     ArrayObject, BitmapDataObject, ClassObject, EventObject, Object, ScriptObject,
     SoundChannelObject, StageObject, TObject,
 };
+pub use crate::avm2::profiler::Profiler;
 pub use crate::avm2::qname::QName;
+pub use crate::avm2::sampler::Sampler;
 pub use crate::avm2::value::Value;
 
 use self::api_version::ApiVersion;
@@ -143,9 +153,12 @@ pub struct Avm2<'gc> {
     pub flash_display_internal: Namespace<'gc>,
     pub flash_utils_internal: Namespace<'gc>,
     pub flash_geom_internal: Namespace<'gc>,
+    pub flash_globalization_internal: Namespace<'gc>,
     pub flash_events_internal: Namespace<'gc>,
     pub flash_text_engine_internal: Namespace<'gc>,
     pub flash_net_internal: Namespace<'gc>,
+    pub flash_system_internal: Namespace<'gc>,
+    pub flash_concurrent_internal: Namespace<'gc>,
 
     #[collect(require_static)]
     native_method_table: &'static [Option<(&'static str, NativeMethodImpl)>],
@@ -177,6 +190,16 @@ pub struct Avm2<'gc> {
     /// strong references around (this matches Flash's behavior).
     orphan_objects: Rc<Vec<DisplayObjectWeak<'gc>>>,
 
+    /// The shareable `ByteArray`s, whose memory has to be kept in sync with other workers.
+    shared_byte_arrays: Vec<WeakObject<'gc>>,
+
+    /// The samples of allocations and calls collected for `flash.sampler`.
+    sampler: Sampler<'gc>,
+
+    /// The timings of method calls and frame processing, if profiling is enabled.
+    #[collect(require_static)]
+    profiler: Option<Profiler>,
+
     /// The api version of our root movie clip. Note - this is used as the
     /// api version for swfs loaded via `Loader`, overriding the api version
     /// specified in the loaded SWF. This is only used for API versioning (hiding
@@ -185,6 +208,24 @@ pub struct Avm2<'gc> {
     #[collect(require_static)]
     pub root_api_version: ApiVersion,
 
+    /// Whether verified method bodies are optimized before they're run.
+    ///
+    /// Disabling this keeps the code as close to the original bytecode as possible, which can
+    /// help with debugging.
+    pub optimizer_enabled: bool,
+
+    /// Whether verified method bodies are compiled to registers and run by the register
+    /// interpreter, rather than by interpreting their ops on the operand stack.
+    pub register_ir_enabled: bool,
+
+    /// If the user has requested it, the AVM may be halted.
+    halted: bool,
+
+    /// The URI set by `default xml namespace`, which E4X uses for unqualified names.
+    ///
+    /// This is scoped to the method that set it, and restored when that method returns.
+    default_xml_namespace: Option<AvmString<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -239,9 +280,12 @@ impl<'gc> Avm2<'gc> {
             flash_display_internal: Namespace::internal("flash.display", context),
             flash_utils_internal: Namespace::internal("flash.utils", context),
             flash_geom_internal: Namespace::internal("flash.geom", context),
+            flash_globalization_internal: Namespace::internal("flash.globalization", context),
             flash_events_internal: Namespace::internal("flash.events", context),
             flash_text_engine_internal: Namespace::internal("flash.text.engine", context),
             flash_net_internal: Namespace::internal("flash.net", context),
+            flash_system_internal: Namespace::internal("flash.system", context),
+            flash_concurrent_internal: Namespace::internal("flash.concurrent", context),
 
             native_method_table: Default::default(),
             native_instance_allocator_table: Default::default(),
@@ -251,9 +295,18 @@ impl<'gc> Avm2<'gc> {
 
             orphan_objects: Default::default(),
 
+            shared_byte_arrays: Vec::new(),
+            sampler: Sampler::new(),
+            profiler: None,
+
             // Set the lowest version for now - this will be overridden when we set our movie
             root_api_version: ApiVersion::AllVersions,
 
+            optimizer_enabled: true,
+            register_ir_enabled: true,
+            halted: false,
+            default_xml_namespace: None,
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
         }
@@ -345,6 +398,16 @@ impl<'gc> Avm2<'gc> {
         }
     }
 
+    /// Iterates over the orphan objects that are still alive and without a parent.
+    pub fn iter_orphan_objs<'a>(
+        &'a self,
+        mc: &'a Mutation<'gc>,
+    ) -> impl Iterator<Item = DisplayObject<'gc>> + 'a {
+        self.orphan_objects
+            .iter()
+            .filter_map(move |orphan| valid_orphan(*orphan, mc))
+    }
+
     pub fn each_orphan_obj(
         context: &mut UpdateContext<'_, 'gc>,
         mut f: impl FnMut(DisplayObject<'gc>, &mut UpdateContext<'_, 'gc>),
@@ -422,6 +485,72 @@ impl<'gc> Avm2<'gc> {
         }
     }
 
+    /// Get the `flash.system.IME` instance that is exposed as `System.ime`.
+    ///
+    /// This is the target of `IMEEvent`s that are not related to a specific
+    /// display object.
+    pub fn ime_object(activation: &mut Activation<'_, 'gc>) -> Option<Object<'gc>> {
+        let ime_class = activation.avm2().classes().ime;
+        ime_class
+            .get_property(
+                &Multiname::new(activation.avm2().flash_system_internal, "_instance"),
+                activation,
+            )
+            .ok()
+            .and_then(|ime| ime.as_object())
+    }
+
+    /// Registers a shareable `ByteArray`, whose memory has to be kept in sync with other workers.
+    pub fn register_shared_byte_array(&mut self, bytearray: Object<'gc>) {
+        self.shared_byte_arrays.push(bytearray.downgrade());
+    }
+
+    /// Finds the `ByteArray` in this worker that uses the given shared memory.
+    pub fn shared_byte_array(
+        &self,
+        mc: &Mutation<'gc>,
+        memory: &Arc<SharedMemory>,
+    ) -> Option<Object<'gc>> {
+        self.shared_byte_arrays
+            .iter()
+            .filter_map(|bytearray| bytearray.upgrade(mc))
+            .find(|bytearray| {
+                bytearray.as_bytearray().map_or(false, |storage| {
+                    storage
+                        .shared_memory()
+                        .map_or(false, |shared| Arc::ptr_eq(shared, memory))
+                })
+            })
+    }
+
+    /// Writes the contents of all shareable `ByteArray`s to their shared memory,
+    /// so that other workers can see them.
+    pub fn flush_shared_memory(&mut self, mc: &Mutation<'gc>) {
+        self.shared_byte_arrays.retain(|bytearray| {
+            let Some(bytearray) = bytearray.upgrade(mc) else {
+                return false;
+            };
+            match bytearray.as_bytearray_mut(mc) {
+                Some(mut storage) if storage.shared_memory().is_some() => {
+                    storage.flush_shared_memory();
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+
+    /// Reads what other workers wrote to the shared memory of all shareable `ByteArray`s.
+    pub fn reload_shared_memory(&mut self, mc: &Mutation<'gc>) {
+        for bytearray in &self.shared_byte_arrays {
+            if let Some(bytearray) = bytearray.upgrade(mc) {
+                if let Some(mut storage) = bytearray.as_bytearray_mut(mc) {
+                    storage.reload_shared_memory();
+                }
+            }
+        }
+    }
+
     /// Add an object to the broadcast list.
     ///
     /// Each broadcastable event contains it's own broadcast list. You must
@@ -578,24 +707,80 @@ impl<'gc> Avm2<'gc> {
     }
 
     /// Pushes an executable on the call stack
-    pub fn push_call(&self, mc: &Mutation<'gc>, calling: &Executable<'gc>) {
-        self.call_stack.write(mc).push(calling)
+    pub fn push_call(&mut self, mc: &Mutation<'gc>, calling: &Executable<'gc>) {
+        self.call_stack.write(mc).push(calling);
+        self.sample_stack();
+        self.profile_call_start();
     }
 
     /// Pushes script initializer (global init) on the call stack
-    pub fn push_global_init(&self, mc: &Mutation<'gc>, script: Script<'gc>) {
-        self.call_stack.write(mc).push_global_init(script)
+    pub fn push_global_init(&mut self, mc: &Mutation<'gc>, script: Script<'gc>) {
+        self.call_stack.write(mc).push_global_init(script);
+        self.sample_stack();
+        self.profile_call_start();
+    }
+
+    fn profile_call_start(&mut self) {
+        if let Some(profiler) = &mut self.profiler {
+            if let Some(call) = self.call_stack.read().iter().last() {
+                profiler.begin(call.name(), TraceCategory::Method);
+            }
+        }
     }
 
     /// Pops an executable off the call stack
-    pub fn pop_call(&self, mc: &Mutation<'gc>) -> Option<CallNode<'gc>> {
-        self.call_stack.write(mc).pop()
+    pub fn pop_call(&mut self, mc: &Mutation<'gc>) -> Option<CallNode<'gc>> {
+        let call = self.call_stack.write(mc).pop();
+        if let (Some(profiler), Some(_)) = (&mut self.profiler, &call) {
+            profiler.end();
+        }
+        call
     }
 
     pub fn call_stack(&self) -> GcCell<'gc, CallStack<'gc>> {
         self.call_stack
     }
 
+    pub fn sampler(&self) -> &Sampler<'gc> {
+        &self.sampler
+    }
+
+    pub fn sampler_mut(&mut self) -> &mut Sampler<'gc> {
+        &mut self.sampler
+    }
+
+    /// Starts or stops profiling method calls and frame processing.
+    ///
+    /// Stopping discards everything that was recorded.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        if enabled != self.profiler.is_some() {
+            self.profiler = enabled.then(Profiler::new);
+        }
+    }
+
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    pub fn profiler_mut(&mut self) -> Option<&mut Profiler> {
+        self.profiler.as_mut()
+    }
+
+    /// Samples the call stack, if `flash.sampler` is sampling and the last sample is old enough.
+    pub fn sample_stack(&mut self) {
+        if self.sampler.is_sampling() {
+            self.sampler.sample_stack(&self.call_stack.read());
+        }
+    }
+
+    /// Samples the allocation of an object, if `flash.sampler` is sampling.
+    pub fn sample_allocation(&mut self, object: Object<'gc>, class: ClassObject<'gc>) {
+        if self.sampler.is_sampling() {
+            self.sampler
+                .sample_allocation(object, class, &self.call_stack.read());
+        }
+    }
+
     #[cold]
     fn stack_overflow(&self) {
         tracing::warn!("Avm2::push: Stack overflow");
@@ -692,6 +877,31 @@ impl<'gc> Avm2<'gc> {
         self.scope_stack.pop();
     }
 
+    /// Halts the AVM, preventing execution of any further methods.
+    ///
+    /// Methods that are currently running are not stopped. Any method that is called from then on
+    /// immediately returns `undefined`.
+    pub fn halt(&mut self) {
+        if !self.halted {
+            self.halted = true;
+            tracing::error!("No more scripts will be executed in this movie.")
+        }
+    }
+
+    /// Whether the AVM has been halted.
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// The URI of the current `default xml namespace`, if one was set.
+    pub fn default_xml_namespace(&self) -> Option<AvmString<'gc>> {
+        self.default_xml_namespace
+    }
+
+    pub fn set_default_xml_namespace(&mut self, uri: Option<AvmString<'gc>>) {
+        self.default_xml_namespace = uri;
+    }
+
     #[cfg(feature = "avm_debug")]
     #[inline]
     pub fn show_debug_output(&self) -> bool {
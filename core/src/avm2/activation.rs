@@ -5,8 +5,8 @@ use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
-    make_error_1127, make_error_1506, make_null_or_undefined_error, make_reference_error,
-    type_error, ReferenceErrorCode,
+    make_error_1127, make_error_1502, make_error_1506, make_null_or_undefined_error,
+    make_reference_error, type_error, ReferenceErrorCode,
 };
 use crate::avm2::method::{BytecodeMethod, Method, ParamConfig};
 use crate::avm2::object::{
@@ -15,6 +15,7 @@ use crate::avm2::object::{
 };
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::op::Op;
+use crate::avm2::register_ir;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
@@ -23,6 +24,7 @@ use crate::avm2::Namespace;
 use crate::avm2::QName;
 use crate::avm2::{Avm2, Error};
 use crate::context::{GcContext, UpdateContext};
+use crate::limits::ScriptTimeoutAction;
 use crate::string::{AvmAtom, AvmString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::{Gc, GcCell};
@@ -565,6 +567,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.local_registers.get_unchecked(id)
     }
 
+    /// The number of local registers of this activation.
+    pub fn local_register_count(&self) -> u32 {
+        self.local_registers.0.len() as u32
+    }
+
     /// Set a local register.
     pub fn set_local_register(&mut self, id: u32, value: impl Into<Value<'gc>>) {
         // Verification guarantees that this is valid
@@ -639,6 +646,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &self.context.avm2.scope_stack[self.scope_depth..]
     }
 
+    /// The values on the operand stack of this activation, from bottom to top.
+    pub fn operand_stack(&self) -> &[Value<'gc>] {
+        &self.context.avm2.stack[self.stack_depth..]
+    }
+
     /// Pushes a value onto the operand stack.
     #[inline]
     pub fn push_stack(&mut self, value: impl Into<Value<'gc>>) {
@@ -801,12 +813,18 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
     ) -> Result<Value<'gc>, Error<'gc>> {
+        if self.avm2().is_halted() {
+            // We've been told to ignore all future execution.
+            return Ok(Value::Undefined);
+        }
+
         if method.verified_info.borrow().is_none() {
             method.verify(self)?;
         }
 
         let verified_info = method.verified_info.borrow();
-        let verified_code = verified_info.as_ref().unwrap().parsed_code.as_slice();
+        let verified_info = verified_info.as_ref().unwrap();
+        let verified_code = verified_info.parsed_code.as_slice();
 
         if verified_code.is_empty() {
             return Ok(Value::Undefined);
@@ -814,15 +832,29 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
         self.ip = 0;
 
-        let val = loop {
-            let result = self.do_next_opcode(method, verified_code);
-            match result {
-                Ok(FrameControl::Return(value)) => break Ok(value),
-                Ok(FrameControl::Continue) => {}
-                Err(e) => break Err(e),
+        // `default xml namespace` only lasts until the method that set it returns.
+        let sets_dxns = method.method().flags.contains(AbcMethodFlags::SET_DXNS);
+        let outer_dxns = self.avm2().default_xml_namespace();
+
+        // The debugger steps through the original ops, so it needs the stack interpreter.
+        let val = match &verified_info.register_code {
+            Some(register_code) if self.context.debugger.is_none() => {
+                register_ir::run(self, method, verified_code, register_code)
             }
+            _ => loop {
+                let result = self.do_next_opcode(method, verified_code);
+                match result {
+                    Ok(FrameControl::Return(value)) => break Ok(value),
+                    Ok(FrameControl::Continue) => {}
+                    Err(e) => break Err(e),
+                }
+            },
         };
 
+        if sets_dxns {
+            self.avm2().set_default_xml_namespace(outer_dxns);
+        }
+
         self.clear_stack();
         self.clear_scope();
         val
@@ -874,6 +906,57 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Err(Error::AvmError(error))
     }
 
+    /// Finds the exception handler for an error thrown by the op at `index`.
+    ///
+    /// If a handler catches the error, the error is pushed onto the operand stack and the index
+    /// of the first op of the handler is returned. Otherwise the error is passed on.
+    pub fn catch_error(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: usize,
+        error: Error<'gc>,
+    ) -> Result<usize, Error<'gc>> {
+        self.ip = index as i32 + 1;
+        self.handle_err(method, error)?;
+        Ok(self.ip as usize)
+    }
+
+    /// Checks whether the script has been running for too long.
+    ///
+    /// The first time the time limit is hit, a catchable `ScriptTimeoutError` is thrown. If the
+    /// script keeps running after that, it is aborted.
+    #[inline(always)]
+    pub fn check_timeout(&mut self) -> Result<(), Error<'gc>> {
+        self.actions_since_timeout_check += 1;
+        if self.actions_since_timeout_check >= 2000 {
+            self.actions_since_timeout_check = 0;
+            return self.check_timeout_slow();
+        }
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn check_timeout_slow(&mut self) -> Result<(), Error<'gc>> {
+        // Long-running loops that don't call any functions still need to show up in the
+        // periodic call stack samples.
+        self.context.avm2.sample_stack();
+
+        let update_start = self.context.update_start;
+        let max_execution_duration = self.context.max_execution_duration;
+        match self
+            .context
+            .script_timeout
+            .check(update_start, max_execution_duration, true)
+        {
+            ScriptTimeoutAction::Continue => Ok(()),
+            ScriptTimeoutAction::ThrowError => Err(make_error_1502(self)),
+            ScriptTimeoutAction::Abort => Err(
+                "A script in this movie has taken too long to execute and has been terminated."
+                    .into(),
+            ),
+        }
+    }
+
     /// Run a single action from a given action reader.
     #[inline(always)]
     fn do_next_opcode(
@@ -881,207 +964,207 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         opcodes: &[Op],
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        self.actions_since_timeout_check += 1;
-        if self.actions_since_timeout_check >= 2000 {
-            self.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
-                return Err(
-                    "A script in this movie has taken too long to execute and has been terminated."
-                        .into(),
-                );
-            }
+        if self.context.debugger.is_some() {
+            let index = self.ip as usize;
+            crate::avm2::debugger::before_op(self, method, opcodes, index);
         }
 
         let op = &opcodes[self.ip as usize];
         self.ip += 1;
         avm_debug!(self.avm2(), "Opcode: {op:?}");
 
-        {
-            let result = match op {
-                Op::PushByte { value } => self.op_push_byte(*value),
-                Op::PushDouble { value } => self.op_push_double(*value),
-                Op::PushFalse => self.op_push_false(),
-                Op::PushInt { value } => self.op_push_int(*value),
-                Op::PushNamespace { value } => self.op_push_namespace(method, *value),
-                Op::PushNaN => self.op_push_nan(),
-                Op::PushNull => self.op_push_null(),
-                Op::PushShort { value } => self.op_push_short(*value),
-                Op::PushString { value } => self.op_push_string(method, *value),
-                Op::PushTrue => self.op_push_true(),
-                Op::PushUint { value } => self.op_push_uint(*value),
-                Op::PushUndefined => self.op_push_undefined(),
-                Op::Pop => self.op_pop(),
-                Op::Dup => self.op_dup(),
-                Op::GetLocal { index } => self.op_get_local(*index),
-                Op::SetLocal { index } => self.op_set_local(*index),
-                Op::Kill { index } => self.op_kill(*index),
-                Op::Call { num_args } => self.op_call(*num_args),
-                Op::CallMethod { index, num_args } => self.op_call_method(*index, *num_args),
-                Op::CallProperty { index, num_args } => {
-                    self.op_call_property(method, *index, *num_args)
-                }
-                Op::CallPropLex { index, num_args } => {
-                    self.op_call_prop_lex(method, *index, *num_args)
-                }
-                Op::CallPropVoid { index, num_args } => {
-                    self.op_call_prop_void(method, *index, *num_args)
-                }
-                Op::CallStatic { index, num_args } => {
-                    self.op_call_static(method, *index, *num_args)
-                }
-                Op::CallSuper { index, num_args } => self.op_call_super(method, *index, *num_args),
-                Op::CallSuperVoid { index, num_args } => {
-                    self.op_call_super_void(method, *index, *num_args)
-                }
-                Op::ReturnValue => self.op_return_value(method),
-                Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, *index),
-                Op::SetProperty { index } => self.op_set_property(method, *index),
-                Op::InitProperty { index } => self.op_init_property(method, *index),
-                Op::DeleteProperty { index } => self.op_delete_property(method, *index),
-                Op::GetSuper { index } => self.op_get_super(method, *index),
-                Op::SetSuper { index } => self.op_set_super(method, *index),
-                Op::In => self.op_in(),
-                Op::PushScope => self.op_push_scope(),
-                Op::NewCatch { index } => self.op_newcatch(method, *index),
-                Op::PushWith => self.op_push_with(),
-                Op::PopScope => self.op_pop_scope(),
-                Op::GetOuterScope { index } => self.op_get_outer_scope(*index),
-                Op::GetScopeObject { index } => self.op_get_scope_object(*index),
-                Op::GetGlobalScope => self.op_get_global_scope(),
-                Op::FindDef { index } => self.op_find_def(method, *index),
-                Op::FindProperty { index } => self.op_find_property(method, *index),
-                Op::FindPropStrict { index } => self.op_find_prop_strict(method, *index),
-                Op::GetLex { index } => self.op_get_lex(method, *index),
-                Op::GetDescendants { index } => self.op_get_descendants(method, *index),
-                Op::GetSlot { index } => self.op_get_slot(*index),
-                Op::SetSlot { index } => self.op_set_slot(*index),
-                Op::GetGlobalSlot { index } => self.op_get_global_slot(*index),
-                Op::SetGlobalSlot { index } => self.op_set_global_slot(*index),
-                Op::Construct { num_args } => self.op_construct(*num_args),
-                Op::ConstructProp { index, num_args } => {
-                    self.op_construct_prop(method, *index, *num_args)
-                }
-                Op::ConstructSuper { num_args } => self.op_construct_super(*num_args),
-                Op::NewActivation => self.op_new_activation(),
-                Op::NewObject { num_args } => self.op_new_object(*num_args),
-                Op::NewFunction { index } => self.op_new_function(method, *index),
-                Op::NewClass { index } => self.op_new_class(method, *index),
-                Op::ApplyType { num_types } => self.op_apply_type(*num_types),
-                Op::NewArray { num_args } => self.op_new_array(*num_args),
-                Op::CoerceA => Ok(FrameControl::Continue),
-                Op::CoerceB => self.op_coerce_b(),
-                Op::CoerceD => self.op_coerce_d(),
-                Op::CoerceI => self.op_coerce_i(),
-                Op::CoerceO => self.op_coerce_o(),
-                Op::CoerceS => self.op_coerce_s(),
-                Op::CoerceU => self.op_coerce_u(),
-                Op::ConvertO => self.op_convert_o(),
-                Op::ConvertS => self.op_convert_s(),
-                Op::Add => self.op_add(),
-                Op::AddI => self.op_add_i(),
-                Op::BitAnd => self.op_bitand(),
-                Op::BitNot => self.op_bitnot(),
-                Op::BitOr => self.op_bitor(),
-                Op::BitXor => self.op_bitxor(),
-                Op::DecLocal { index } => self.op_declocal(*index),
-                Op::DecLocalI { index } => self.op_declocal_i(*index),
-                Op::Decrement => self.op_decrement(),
-                Op::DecrementI => self.op_decrement_i(),
-                Op::Divide => self.op_divide(),
-                Op::IncLocal { index } => self.op_inclocal(*index),
-                Op::IncLocalI { index } => self.op_inclocal_i(*index),
-                Op::Increment => self.op_increment(),
-                Op::IncrementI => self.op_increment_i(),
-                Op::LShift => self.op_lshift(),
-                Op::Modulo => self.op_modulo(),
-                Op::Multiply => self.op_multiply(),
-                Op::MultiplyI => self.op_multiply_i(),
-                Op::Negate => self.op_negate(),
-                Op::NegateI => self.op_negate_i(),
-                Op::RShift => self.op_rshift(),
-                Op::Subtract => self.op_subtract(),
-                Op::SubtractI => self.op_subtract_i(),
-                Op::Swap => self.op_swap(),
-                Op::URShift => self.op_urshift(),
-                Op::Jump { offset } => self.op_jump(*offset),
-                Op::IfTrue { offset } => self.op_if_true(*offset),
-                Op::IfFalse { offset } => self.op_if_false(*offset),
-                Op::IfStrictEq { offset } => self.op_if_strict_eq(*offset),
-                Op::IfStrictNe { offset } => self.op_if_strict_ne(*offset),
-                Op::IfEq { offset } => self.op_if_eq(*offset),
-                Op::IfNe { offset } => self.op_if_ne(*offset),
-                Op::IfGe { offset } => self.op_if_ge(*offset),
-                Op::IfGt { offset } => self.op_if_gt(*offset),
-                Op::IfLe { offset } => self.op_if_le(*offset),
-                Op::IfLt { offset } => self.op_if_lt(*offset),
-                Op::IfNge { offset } => self.op_if_nge(*offset),
-                Op::IfNgt { offset } => self.op_if_ngt(*offset),
-                Op::IfNle { offset } => self.op_if_nle(*offset),
-                Op::IfNlt { offset } => self.op_if_nlt(*offset),
-                Op::StrictEquals => self.op_strict_equals(),
-                Op::Equals => self.op_equals(),
-                Op::GreaterEquals => self.op_greater_equals(),
-                Op::GreaterThan => self.op_greater_than(),
-                Op::LessEquals => self.op_less_equals(),
-                Op::LessThan => self.op_less_than(),
-                Op::Nop => Ok(FrameControl::Continue),
-                Op::Not => self.op_not(),
-                Op::HasNext => self.op_has_next(),
-                Op::HasNext2 {
-                    object_register,
-                    index_register,
-                } => self.op_has_next_2(*object_register, *index_register),
-                Op::NextName => self.op_next_name(),
-                Op::NextValue => self.op_next_value(),
-                Op::IsType { index } => self.op_is_type(method, *index),
-                Op::IsTypeLate => self.op_is_type_late(),
-                Op::AsType { type_name } => self.op_as_type(method, *type_name),
-                Op::AsTypeLate => self.op_as_type_late(),
-                Op::InstanceOf => self.op_instance_of(),
-                Op::Debug {
-                    is_local_register,
-                    register_name,
-                    register,
-                } => self.op_debug(method, *is_local_register, *register_name, *register),
-                Op::DebugFile { file_name } => self.op_debug_file(method, *file_name),
-                Op::DebugLine { line_num } => self.op_debug_line(*line_num),
-                Op::Bkpt => self.op_bkpt(),
-                Op::BkptLine { line_num } => self.op_bkpt_line(*line_num),
-                Op::Timestamp => self.op_timestamp(),
-                Op::TypeOf => self.op_type_of(),
-                Op::EscXAttr => self.op_esc_xattr(),
-                Op::EscXElem => self.op_esc_elem(),
-                Op::LookupSwitch(ref lookup_switch) => {
-                    self.op_lookup_switch(lookup_switch.default_offset, &lookup_switch.case_offsets)
-                }
-                Op::Coerce { index } => self.op_coerce(method, *index),
-                Op::CheckFilter => self.op_check_filter(),
-                Op::Si8 => self.op_si8(),
-                Op::Si16 => self.op_si16(),
-                Op::Si32 => self.op_si32(),
-                Op::Sf32 => self.op_sf32(),
-                Op::Sf64 => self.op_sf64(),
-                Op::Li8 => self.op_li8(),
-                Op::Li16 => self.op_li16(),
-                Op::Li32 => self.op_li32(),
-                Op::Lf32 => self.op_lf32(),
-                Op::Lf64 => self.op_lf64(),
-                Op::Sxi1 => self.op_sxi1(),
-                Op::Sxi8 => self.op_sxi8(),
-                Op::Sxi16 => self.op_sxi16(),
-                Op::Throw => self.op_throw(),
-                _ => {
-                    tracing::info!("Encountered unimplemented AVM2 opcode {:?}", op);
-
-                    return Err("Unknown op".into());
-                }
-            };
+        // A timeout is thrown by the op that was about to run, so that it can be caught.
+        let result = self.check_timeout().and_then(|()| self.do_op(method, op));
+        if let Err(error) = result {
+            return self.handle_err(method, error);
+        }
+        result
+    }
+
+    /// Runs a single op, without looking for a handler of any error it throws.
+    #[inline(always)]
+    pub fn do_op(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        op: &Op,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        match op {
+            Op::PushByte { value } => self.op_push_byte(*value),
+            Op::PushDouble { value } => self.op_push_double(*value),
+            Op::PushFalse => self.op_push_false(),
+            Op::PushInt { value } => self.op_push_int(*value),
+            Op::PushNamespace { value } => self.op_push_namespace(method, *value),
+            Op::PushNaN => self.op_push_nan(),
+            Op::PushNull => self.op_push_null(),
+            Op::PushShort { value } => self.op_push_short(*value),
+            Op::PushString { value } => self.op_push_string(method, *value),
+            Op::PushTrue => self.op_push_true(),
+            Op::PushUint { value } => self.op_push_uint(*value),
+            Op::PushUndefined => self.op_push_undefined(),
+            Op::Pop => self.op_pop(),
+            Op::Dup => self.op_dup(),
+            Op::GetLocal { index } => self.op_get_local(*index),
+            Op::SetLocal { index } => self.op_set_local(*index),
+            Op::Kill { index } => self.op_kill(*index),
+            Op::Call { num_args } => self.op_call(*num_args),
+            Op::CallMethod { index, num_args } => self.op_call_method(*index, *num_args),
+            Op::CallProperty { index, num_args } => {
+                self.op_call_property(method, *index, *num_args)
+            }
+            Op::CallPropLex { index, num_args } => self.op_call_prop_lex(method, *index, *num_args),
+            Op::CallPropVoid { index, num_args } => {
+                self.op_call_prop_void(method, *index, *num_args)
+            }
+            Op::CallStatic { index, num_args } => self.op_call_static(method, *index, *num_args),
+            Op::CallSuper { index, num_args } => self.op_call_super(method, *index, *num_args),
+            Op::CallSuperVoid { index, num_args } => {
+                self.op_call_super_void(method, *index, *num_args)
+            }
+            Op::ReturnValue => self.op_return_value(method),
+            Op::ReturnVoid => self.op_return_void(),
+            Op::GetProperty { index } => self.op_get_property(method, *index),
+            Op::SetProperty { index } => self.op_set_property(method, *index),
+            Op::InitProperty { index } => self.op_init_property(method, *index),
+            Op::DeleteProperty { index } => self.op_delete_property(method, *index),
+            Op::GetSuper { index } => self.op_get_super(method, *index),
+            Op::SetSuper { index } => self.op_set_super(method, *index),
+            Op::In => self.op_in(),
+            Op::PushScope => self.op_push_scope(),
+            Op::NewCatch { index } => self.op_newcatch(method, *index),
+            Op::PushWith => self.op_push_with(),
+            Op::PopScope => self.op_pop_scope(),
+            Op::GetOuterScope { index } => self.op_get_outer_scope(*index),
+            Op::GetScopeObject { index } => self.op_get_scope_object(*index),
+            Op::GetGlobalScope => self.op_get_global_scope(),
+            Op::FindDef { index } => self.op_find_def(method, *index),
+            Op::FindProperty { index } => self.op_find_property(method, *index),
+            Op::FindPropStrict { index } => self.op_find_prop_strict(method, *index),
+            Op::GetLex { index } => self.op_get_lex(method, *index),
+            Op::GetDescendants { index } => self.op_get_descendants(method, *index),
+            Op::GetSlot { index } => self.op_get_slot(*index),
+            Op::SetSlot { index } => self.op_set_slot(*index),
+            Op::GetGlobalSlot { index } => self.op_get_global_slot(*index),
+            Op::SetGlobalSlot { index } => self.op_set_global_slot(*index),
+            Op::Construct { num_args } => self.op_construct(*num_args),
+            Op::ConstructProp { index, num_args } => {
+                self.op_construct_prop(method, *index, *num_args)
+            }
+            Op::ConstructSuper { num_args } => self.op_construct_super(*num_args),
+            Op::NewActivation => self.op_new_activation(),
+            Op::NewObject { num_args } => self.op_new_object(*num_args),
+            Op::NewFunction { index } => self.op_new_function(method, *index),
+            Op::NewClass { index } => self.op_new_class(method, *index),
+            Op::ApplyType { num_types } => self.op_apply_type(*num_types),
+            Op::NewArray { num_args } => self.op_new_array(*num_args),
+            Op::CoerceA => Ok(FrameControl::Continue),
+            Op::CoerceB => self.op_coerce_b(),
+            Op::CoerceD => self.op_coerce_d(),
+            Op::CoerceI => self.op_coerce_i(),
+            Op::CoerceO => self.op_coerce_o(),
+            Op::CoerceS => self.op_coerce_s(),
+            Op::CoerceU => self.op_coerce_u(),
+            Op::ConvertO => self.op_convert_o(),
+            Op::ConvertS => self.op_convert_s(),
+            Op::Add => self.op_add(),
+            Op::AddI => self.op_add_i(),
+            Op::BitAnd => self.op_bitand(),
+            Op::BitNot => self.op_bitnot(),
+            Op::BitOr => self.op_bitor(),
+            Op::BitXor => self.op_bitxor(),
+            Op::DecLocal { index } => self.op_declocal(*index),
+            Op::DecLocalI { index } => self.op_declocal_i(*index),
+            Op::Decrement => self.op_decrement(),
+            Op::DecrementI => self.op_decrement_i(),
+            Op::Divide => self.op_divide(),
+            Op::IncLocal { index } => self.op_inclocal(*index),
+            Op::IncLocalI { index } => self.op_inclocal_i(*index),
+            Op::Increment => self.op_increment(),
+            Op::IncrementI => self.op_increment_i(),
+            Op::LShift => self.op_lshift(),
+            Op::Modulo => self.op_modulo(),
+            Op::Multiply => self.op_multiply(),
+            Op::MultiplyI => self.op_multiply_i(),
+            Op::Negate => self.op_negate(),
+            Op::NegateI => self.op_negate_i(),
+            Op::RShift => self.op_rshift(),
+            Op::Subtract => self.op_subtract(),
+            Op::SubtractI => self.op_subtract_i(),
+            Op::Swap => self.op_swap(),
+            Op::URShift => self.op_urshift(),
+            Op::Jump { offset } => self.op_jump(*offset),
+            Op::IfTrue { offset } => self.op_if_true(*offset),
+            Op::IfFalse { offset } => self.op_if_false(*offset),
+            Op::IfStrictEq { offset } => self.op_if_strict_eq(*offset),
+            Op::IfStrictNe { offset } => self.op_if_strict_ne(*offset),
+            Op::IfEq { offset } => self.op_if_eq(*offset),
+            Op::IfNe { offset } => self.op_if_ne(*offset),
+            Op::IfGe { offset } => self.op_if_ge(*offset),
+            Op::IfGt { offset } => self.op_if_gt(*offset),
+            Op::IfLe { offset } => self.op_if_le(*offset),
+            Op::IfLt { offset } => self.op_if_lt(*offset),
+            Op::IfNge { offset } => self.op_if_nge(*offset),
+            Op::IfNgt { offset } => self.op_if_ngt(*offset),
+            Op::IfNle { offset } => self.op_if_nle(*offset),
+            Op::IfNlt { offset } => self.op_if_nlt(*offset),
+            Op::StrictEquals => self.op_strict_equals(),
+            Op::Equals => self.op_equals(),
+            Op::GreaterEquals => self.op_greater_equals(),
+            Op::GreaterThan => self.op_greater_than(),
+            Op::LessEquals => self.op_less_equals(),
+            Op::LessThan => self.op_less_than(),
+            Op::Nop => Ok(FrameControl::Continue),
+            Op::Not => self.op_not(),
+            Op::HasNext => self.op_has_next(),
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => self.op_has_next_2(*object_register, *index_register),
+            Op::NextName => self.op_next_name(),
+            Op::NextValue => self.op_next_value(),
+            Op::IsType { index } => self.op_is_type(method, *index),
+            Op::IsTypeLate => self.op_is_type_late(),
+            Op::AsType { type_name } => self.op_as_type(method, *type_name),
+            Op::AsTypeLate => self.op_as_type_late(),
+            Op::InstanceOf => self.op_instance_of(),
+            Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => self.op_debug(method, *is_local_register, *register_name, *register),
+            Op::DebugFile { file_name } => self.op_debug_file(method, *file_name),
+            Op::DebugLine { line_num } => self.op_debug_line(*line_num),
+            Op::Bkpt => self.op_bkpt(),
+            Op::BkptLine { line_num } => self.op_bkpt_line(*line_num),
+            Op::Timestamp => self.op_timestamp(),
+            Op::TypeOf => self.op_type_of(),
+            Op::EscXAttr => self.op_esc_xattr(),
+            Op::EscXElem => self.op_esc_elem(),
+            Op::LookupSwitch(ref lookup_switch) => {
+                self.op_lookup_switch(lookup_switch.default_offset, &lookup_switch.case_offsets)
+            }
+            Op::Coerce { index } => self.op_coerce(method, *index),
+            Op::CheckFilter => self.op_check_filter(),
+            Op::Si8 => self.op_si8(),
+            Op::Si16 => self.op_si16(),
+            Op::Si32 => self.op_si32(),
+            Op::Sf32 => self.op_sf32(),
+            Op::Sf64 => self.op_sf64(),
+            Op::Li8 => self.op_li8(),
+            Op::Li16 => self.op_li16(),
+            Op::Li32 => self.op_li32(),
+            Op::Lf32 => self.op_lf32(),
+            Op::Lf64 => self.op_lf64(),
+            Op::Sxi1 => self.op_sxi1(),
+            Op::Sxi8 => self.op_sxi8(),
+            Op::Sxi16 => self.op_sxi16(),
+            Op::Throw => self.op_throw(),
+            Op::Dxns { index } => self.op_dxns(method, *index),
+            Op::DxnsLate => self.op_dxns_late(),
+            _ => {
+                tracing::info!("Encountered unimplemented AVM2 opcode {:?}", op);
 
-            if let Err(error) = result {
-                return self.handle_err(method, error);
+                Err("Unknown op".into())
             }
-            result
         }
     }
 
@@ -1612,7 +1695,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             } else {
                 let qname =
                     QName::from_abc_multiname(method.translation_unit(), vname, &mut self.context)?;
-                ScriptObject::catch_scope(self.context.gc_context, &qname)
+                let scope = ScriptObject::catch_scope(self.context.gc_context, &qname);
+                let object_class = self.avm2().classes().object;
+                self.context.avm2.sample_allocation(scope, object_class);
+                scope
             };
             self.push_stack(so);
         }
@@ -1855,7 +1941,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             activation_class.construct(self, &[])?
         } else {
             // TODO: we might want this to be a proper Object instance, just in case
-            ScriptObject::custom_object(self.context.gc_context, None, None)
+            let instance = ScriptObject::custom_object(self.context.gc_context, None, None);
+            let object_class = self.avm2().classes().object;
+            self.context.avm2.sample_allocation(instance, object_class);
+            instance
         };
 
         self.push_stack(instance);
@@ -2788,6 +2877,30 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    /// Implements `Op::Dxns`
+    fn op_dxns(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<String>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let uri = self.pool_string(&method, index)?;
+        self.avm2().set_default_xml_namespace(Some(uri.into()));
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Implements `Op::DxnsLate`
+    fn op_dxns_late(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let uri = match self.pop_stack() {
+            Value::Object(Object::NamespaceObject(ns)) => ns.namespace().as_uri(),
+            Value::Object(Object::QNameObject(qname)) => qname.uri().unwrap_or_default(),
+            value => value.coerce_to_string(self)?,
+        };
+        self.avm2().set_default_xml_namespace(Some(uri));
+
+        Ok(FrameControl::Continue)
+    }
+
     /// Implements `Op::EscXAttr`
     fn op_esc_xattr(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let s = self.pop_stack().coerce_to_string(self)?;
@@ -2853,7 +2966,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.outer.domain()
     }
 
-    fn domain_memory(&self) -> ByteArrayObject<'gc> {
+    pub fn domain_memory(&self) -> ByteArrayObject<'gc> {
         self.outer.domain().domain_memory()
     }
 
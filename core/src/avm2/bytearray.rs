@@ -2,6 +2,7 @@ use crate::avm2::error::{eof_error, range_error};
 use crate::avm2::Activation;
 use crate::avm2::Error;
 use crate::string::{FromWStr, WStr};
+use crate::worker::SharedMemory;
 use flate2::read::*;
 use flate2::Compression;
 use gc_arena::Collect;
@@ -10,6 +11,7 @@ use std::cmp;
 use std::fmt::{self, Display, Formatter};
 use std::io::prelude::*;
 use std::io::{self, Read, SeekFrom};
+use std::sync::Arc;
 
 #[derive(Clone, Collect, Debug, Copy, PartialEq, Eq)]
 #[collect(no_drop)]
@@ -104,6 +106,10 @@ pub struct ByteArrayStorage {
 
     /// The encoding used when serializing/deserializing using readObject/writeObject
     object_encoding: ObjectEncoding,
+
+    /// The memory shared with other workers, and the version of it that `bytes` is a copy of.
+    #[collect(require_static)]
+    shared: Option<(Arc<SharedMemory>, u64)>,
 }
 
 impl ByteArrayStorage {
@@ -114,6 +120,7 @@ impl ByteArrayStorage {
             position: Cell::new(0),
             endian: Endian::Big,
             object_encoding: ObjectEncoding::Amf3,
+            shared: None,
         }
     }
 
@@ -124,6 +131,51 @@ impl ByteArrayStorage {
             position: Cell::new(0),
             endian: Endian::Big,
             object_encoding: ObjectEncoding::Amf3,
+            shared: None,
+        }
+    }
+
+    /// Create a new ByteArrayStorage using memory shared with other workers
+    pub fn from_shared_memory(memory: Arc<SharedMemory>) -> ByteArrayStorage {
+        ByteArrayStorage {
+            bytes: memory.read(),
+            position: Cell::new(0),
+            endian: Endian::Big,
+            object_encoding: ObjectEncoding::Amf3,
+            shared: Some((memory.clone(), memory.version())),
+        }
+    }
+
+    /// The memory this ByteArray shares with other workers, if it's shareable.
+    pub fn shared_memory(&self) -> Option<&Arc<SharedMemory>> {
+        self.shared.as_ref().map(|(memory, _)| memory)
+    }
+
+    /// Makes this ByteArray share its memory with the workers it's sent to, or stops doing so.
+    pub fn set_shareable(&mut self, shareable: bool) {
+        if !shareable {
+            self.shared = None;
+        } else if self.shared.is_none() {
+            let memory = SharedMemory::new(self.bytes.clone());
+            let version = memory.version();
+            self.shared = Some((memory, version));
+        }
+    }
+
+    /// Writes the contents of this ByteArray to its shared memory.
+    pub fn flush_shared_memory(&mut self) {
+        if let Some((memory, version)) = &mut self.shared {
+            *version = memory.write(&self.bytes);
+        }
+    }
+
+    /// Reads the shared memory of this ByteArray, if another worker changed it.
+    pub fn reload_shared_memory(&mut self) {
+        if let Some((memory, version)) = &mut self.shared {
+            if memory.version() != *version {
+                self.bytes = memory.read();
+                *version = memory.version();
+            }
         }
     }
 
@@ -1,6 +1,7 @@
 use crate::avm2::function::{display_function, Executable};
 use crate::avm2::method::Method;
 use crate::avm2::object::ClassObject;
+use crate::avm2::sampler::SampledFrame;
 use crate::string::WString;
 use gc_arena::Collect;
 
@@ -16,6 +17,43 @@ pub enum CallNode<'gc> {
     },
 }
 
+impl<'gc> CallNode<'gc> {
+    pub fn display(&self, output: &mut WString) {
+        match self {
+            CallNode::GlobalInit(script) => {
+                let name = if let Some(tuint) = script.translation_unit() {
+                    if let Some(name) = tuint.name() {
+                        name.to_utf8_lossy().to_string()
+                    } else {
+                        "<No name>".to_string()
+                    }
+                } else {
+                    "<No translation unit>".to_string()
+                };
+
+                // NOTE: We intentionally diverge from Flash Player's output
+                // here - everything with the [] brackets is extra information
+                // added by Ruffle
+                output.push_utf8(&format!("global$init() [TU={}]", name));
+            }
+            CallNode::Method { method, superclass } => {
+                display_function(output, method, *superclass)
+            }
+        }
+    }
+
+    /// The name of the call, without its parameters.
+    pub fn name(&self) -> String {
+        let mut output = WString::new();
+        self.display(&mut output);
+        let name = output.to_string();
+        match name.split_once('(') {
+            Some((name, _)) => name.to_string(),
+            None => name,
+        }
+    }
+}
+
 #[derive(Collect, Clone)]
 #[collect(no_drop)]
 pub struct CallStack<'gc> {
@@ -45,30 +83,28 @@ impl<'gc> CallStack<'gc> {
     pub fn display(&self, output: &mut WString) {
         for call in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
-            match call {
-                CallNode::GlobalInit(script) => {
-                    let name = if let Some(tuint) = script.translation_unit() {
-                        if let Some(name) = tuint.name() {
-                            name.to_utf8_lossy().to_string()
-                        } else {
-                            "<No name>".to_string()
-                        }
-                    } else {
-                        "<No translation unit>".to_string()
-                    };
-
-                    // NOTE: We intentionally diverge from Flash Player's output
-                    // here - everything with the [] brackets is extra information
-                    // added by Ruffle
-                    output.push_utf8(&format!("global$init() [TU={}]", name));
-                }
-                CallNode::Method { method, superclass } => {
-                    display_function(output, method, *superclass)
-                }
-            }
+            call.display(output);
         }
     }
 
+    /// Iterates over the calls, outermost first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &CallNode<'gc>> {
+        self.stack.iter()
+    }
+
+    /// Takes a sample of the calls for `flash.sampler`, innermost first.
+    pub fn sample(&self) -> Vec<SampledFrame> {
+        self.stack
+            .iter()
+            .rev()
+            .map(|call| SampledFrame { name: call.name() })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
@@ -0,0 +1,344 @@
+//! Lets the script debugger pause and inspect AVM2 code.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::object::TObject;
+use crate::avm2::op::Op;
+use crate::avm2::property::Property;
+use crate::avm2::value::Value;
+use crate::avm2::Multiname;
+use crate::debugger::{
+    pause, BreakpointLocation, Debuggee, Literal, NamedValues, StackFrame, StopReason,
+};
+use crate::string::{AvmString, WString};
+use gc_arena::Gc;
+use std::collections::HashMap;
+
+/// Checks whether the debugger wants to pause before running the next instruction, and pauses
+/// if so.
+pub fn before_op<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    code: &[Op],
+    index: usize,
+) {
+    let Some(debugger) = activation.context.debugger.as_mut() else {
+        return;
+    };
+    let verified_info = method.verified_info.borrow();
+    let Some(offset) = verified_info
+        .as_ref()
+        .and_then(|info| info.byte_offsets.get(index).copied())
+    else {
+        return;
+    };
+    drop(verified_info);
+
+    let call_stack = activation.context.avm2.call_stack();
+    let call_stack = call_stack.read();
+    let mut method_name = None;
+    let reason = debugger.check(
+        call_stack.len(),
+        || {
+            matches!(code[index], Op::DebugLine { .. })
+                || !code.iter().any(|op| matches!(op, Op::DebugLine { .. }))
+        },
+        |location| match location {
+            BreakpointLocation::Method {
+                method: name,
+                offset: breakpoint_offset,
+            } => {
+                *breakpoint_offset == offset
+                    && *method_name.get_or_insert_with(|| {
+                        let mut output = WString::new();
+                        if let Some(call) = call_stack.iter().next_back() {
+                            call.display(&mut output);
+                        }
+                        function_name(&output.to_string())
+                    }) == *name
+            }
+            BreakpointLocation::Line { file, line } => {
+                matches!(code[index], Op::DebugLine { line_num } if line_num == *line)
+                    && source_position(method, code, index)
+                        .0
+                        .is_some_and(|current| same_file(&current, file))
+            }
+            BreakpointLocation::Action { .. } => false,
+        },
+    );
+    drop(call_stack);
+
+    if let Some(reason) = reason {
+        let mut debugger = activation
+            .context
+            .debugger
+            .take()
+            .expect("Debugger was attached");
+        pause(
+            &mut Avm2Debuggee {
+                activation,
+                method,
+                code,
+                index,
+                offset,
+            },
+            &mut debugger,
+            reason,
+        );
+        *activation.context.debugger = Some(debugger);
+    }
+}
+
+/// The name of a call, as displayed in stack traces, without its arguments.
+fn function_name(display: &str) -> String {
+    display.split('(').next().unwrap_or_default().to_string()
+}
+
+/// Compares the file of a breakpoint to a file from debug information, which is given as
+/// `source root;package;File.as` by the Flex compiler. As the source root may differ between
+/// machines, only the path within it needs to match.
+fn same_file(current: &str, breakpoint: &str) -> bool {
+    let normalize = |path: &str| path.replace('\\', "/");
+    let current = normalize(current);
+    let breakpoint = normalize(breakpoint);
+    let relative = match current.split_once(';') {
+        Some((_, relative)) => relative.replace(';', "/"),
+        None => current,
+    };
+    let relative = relative.trim_start_matches('/');
+    breakpoint == relative || breakpoint.ends_with(&format!("/{relative}"))
+}
+
+fn pool_string(method: Gc<'_, BytecodeMethod<'_>>, index: u32) -> String {
+    let abc = method.abc();
+    match index
+        .checked_sub(1)
+        .and_then(|i| abc.constant_pool.strings.get(i as usize))
+    {
+        Some(string) => String::from_utf8_lossy(string).into_owned(),
+        None => String::new(),
+    }
+}
+
+/// Finds the file and line of an instruction from the debug instructions before it.
+fn source_position(
+    method: Gc<'_, BytecodeMethod<'_>>,
+    code: &[Op],
+    index: usize,
+) -> (Option<String>, Option<u32>) {
+    let mut line = None;
+    for op in code[..=index].iter().rev() {
+        match op {
+            Op::DebugLine { line_num } if line.is_none() => line = Some(*line_num),
+            Op::DebugFile { file_name } => return (Some(pool_string(method, file_name.0)), line),
+            _ => {}
+        }
+    }
+    (None, line)
+}
+
+struct Avm2Debuggee<'a, 'b, 'gc> {
+    activation: &'a mut Activation<'b, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    code: &'a [Op],
+    index: usize,
+    offset: u32,
+}
+
+impl<'a, 'b, 'gc> Avm2Debuggee<'a, 'b, 'gc> {
+    /// The local registers, named by the debug information of the method if it has any.
+    fn locals(&self) -> NamedValues<Value<'gc>> {
+        let mut names = HashMap::new();
+        for op in self.code {
+            if let Op::Debug {
+                is_local_register: true,
+                register_name,
+                register,
+            } = op
+            {
+                names.insert(*register as u32, pool_string(self.method, register_name.0));
+            }
+        }
+
+        (0..self.activation.local_register_count())
+            .map(|register| {
+                let name = match names.remove(&register) {
+                    Some(name) => name,
+                    None if register == 0 => "this".to_string(),
+                    None => format!("_local{register}"),
+                };
+                (name, self.activation.local_register(register))
+            })
+            .collect()
+    }
+}
+
+impl<'a, 'b, 'gc> Debuggee for Avm2Debuggee<'a, 'b, 'gc> {
+    type Value = Value<'gc>;
+
+    fn depth(&self) -> usize {
+        self.activation.context.avm2.call_stack().read().len()
+    }
+
+    fn stack_trace(&mut self) -> Vec<StackFrame> {
+        let (file, line) = source_position(self.method, self.code, self.index);
+        let call_stack = self.activation.context.avm2.call_stack();
+        let call_stack = call_stack.read();
+        let mut frames: Vec<_> = call_stack
+            .iter()
+            .rev()
+            .map(|call| {
+                let mut output = WString::new();
+                call.display(&mut output);
+                StackFrame {
+                    name: output.to_string(),
+                    file: None,
+                    line: None,
+                    location: None,
+                }
+            })
+            .collect();
+        if let Some(frame) = frames.first_mut() {
+            frame.location = Some(BreakpointLocation::Method {
+                method: function_name(&frame.name),
+                offset: self.offset,
+            });
+            frame.file = file;
+            frame.line = line;
+        }
+        frames
+    }
+
+    fn scopes(&mut self) -> Vec<(String, NamedValues<Value<'gc>>)> {
+        let stack = self
+            .activation
+            .operand_stack()
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (i.to_string(), *value))
+            .collect();
+
+        let scope_chain = self.activation.create_scopechain();
+        let mut scopes = Vec::new();
+        while let Some(scope) = scope_chain.get(scopes.len()) {
+            scopes.push(scope.values());
+        }
+        let scopes = scopes
+            .into_iter()
+            .rev()
+            .enumerate()
+            .map(|(i, scope)| (i.to_string(), scope.into()))
+            .collect();
+
+        vec![
+            ("Locals".to_string(), self.locals()),
+            ("Scope Chain".to_string(), scopes),
+            ("Operand Stack".to_string(), stack),
+        ]
+    }
+
+    fn children(&mut self, value: Value<'gc>) -> NamedValues<Value<'gc>> {
+        let Value::Object(object) = value else {
+            return Vec::new();
+        };
+        let activation = &mut *self.activation;
+
+        let mut children = Vec::new();
+        if let Some(vtable) = object.vtable() {
+            let mut slots: Vec<_> = vtable
+                .resolved_traits()
+                .iter()
+                .filter_map(|(name, ns, property)| match property {
+                    Property::Slot { slot_id } | Property::ConstSlot { slot_id }
+                        if ns.is_public() =>
+                    {
+                        Some((name.to_string(), *slot_id))
+                    }
+                    _ => None,
+                })
+                .collect();
+            slots.sort();
+            for (name, slot_id) in slots {
+                if let Ok(value) = object.get_slot(slot_id) {
+                    children.push((name, value));
+                }
+            }
+        }
+
+        let mut index = 0;
+        while let Ok(Some(next)) = object.get_next_enumerant(index, activation) {
+            if next == 0 {
+                break;
+            }
+            index = next;
+            let name = object
+                .get_enumerant_name(index, activation)
+                .and_then(|name| name.coerce_to_string(activation));
+            let value = object.get_enumerant_value(index, activation);
+            if let (Ok(name), Ok(value)) = (name, value) {
+                children.push((name.to_string(), value));
+            }
+        }
+
+        children
+    }
+
+    fn describe(&mut self, value: Value<'gc>) -> (String, String, bool) {
+        match value {
+            Value::Undefined => ("undefined".to_string(), "void".to_string(), false),
+            Value::Null => ("null".to_string(), "null".to_string(), false),
+            Value::Bool(value) => (value.to_string(), "Boolean".to_string(), false),
+            Value::Number(value) => (value.to_string(), "Number".to_string(), false),
+            Value::Integer(value) => (value.to_string(), "int".to_string(), false),
+            Value::String(value) => (format!("{value:?}"), "String".to_string(), false),
+            Value::Object(object) => {
+                let class_name = object
+                    .instance_of_class_name(self.activation.context.gc_context)
+                    .to_string();
+                (format!("[object {class_name}]"), class_name, true)
+            }
+        }
+    }
+
+    fn key(&mut self, value: Value<'gc>) -> String {
+        value
+            .coerce_to_string(self.activation)
+            .map(|key| key.to_string())
+            .unwrap_or_default()
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<Value<'gc>> {
+        if let Some((_, value)) = self.locals().into_iter().find(|(local, _)| local == name) {
+            return Some(value);
+        }
+
+        let name = AvmString::new_utf8(self.activation.context.gc_context, name);
+        let multiname = Multiname::new(self.activation.avm2().find_public_namespace(), name);
+        self.activation
+            .resolve_definition(&multiname)
+            .ok()
+            .flatten()
+    }
+
+    fn this(&mut self) -> Value<'gc> {
+        self.activation.local_register(0)
+    }
+
+    fn member(&mut self, value: Value<'gc>, name: &str) -> Option<Value<'gc>> {
+        let object = value.coerce_to_object(self.activation).ok()?;
+        let name = AvmString::new_utf8(self.activation.context.gc_context, name);
+        object.get_public_property(name, self.activation).ok()
+    }
+
+    fn literal(&mut self, literal: &Literal) -> Value<'gc> {
+        match literal {
+            Literal::Undefined => Value::Undefined,
+            Literal::Null => Value::Null,
+            Literal::Bool(value) => (*value).into(),
+            Literal::Number(value) => (*value).into(),
+            Literal::String(value) => {
+                AvmString::new_utf8(self.activation.context.gc_context, value).into()
+            }
+        }
+    }
+}
@@ -6,17 +6,18 @@ use std::{
 use gc_arena::{Collect, GcCell, Mutation};
 use quick_xml::{
     events::{attributes::AttrError as XmlAttrError, BytesStart, Event},
-    name::ResolveResult,
+    name::{PrefixDeclaration, ResolveResult},
     Error as XmlError, NsReader,
 };
 
 use crate::{avm2::TObject, xml::custom_unescape};
 
 use super::{
+    api_version::ApiVersion,
     error::{make_error_1010, make_error_1118, type_error},
-    object::{E4XOrXml, FunctionObject},
+    object::{E4XOrXml, FunctionObject, NamespaceObject},
     string::AvmString,
-    Activation, Error, Multiname, Value,
+    Activation, Error, Multiname, Namespace, Object, Value,
 };
 use crate::string::{WStr, WString};
 
@@ -35,10 +36,60 @@ pub struct E4XNode<'gc>(GcCell<'gc, E4XNodeData<'gc>>);
 #[collect(no_drop)]
 pub struct E4XNodeData<'gc> {
     parent: Option<E4XNode<'gc>>,
-    namespace: Option<AvmString<'gc>>,
+    namespace: Option<E4XNamespace<'gc>>,
     local_name: Option<AvmString<'gc>>,
     kind: E4XNodeKind<'gc>,
     notification: Option<FunctionObject<'gc>>,
+    /// The namespaces declared on this node, [[InScopeNamespaces]] in ECMA-357.
+    declared_namespaces: Vec<E4XNamespace<'gc>>,
+}
+
+/// The URI that the `xml` prefix is always bound to.
+const XML_NAMESPACE_URI: &[u8] = b"http://www.w3.org/XML/1998/namespace";
+
+/// A namespace as stored on an E4X node: a URI, and the prefix it was bound to.
+///
+/// Like the `prefix` of an E4X `Namespace` object, the prefix may be missing
+/// (`undefined`), in which case one is picked when the node is serialized.
+#[derive(Copy, Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct E4XNamespace<'gc> {
+    pub prefix: Option<AvmString<'gc>>,
+    pub uri: AvmString<'gc>,
+}
+
+impl<'gc> E4XNamespace<'gc> {
+    pub fn new_uri(uri: AvmString<'gc>) -> Self {
+        Self { prefix: None, uri }
+    }
+
+    /// The empty namespace, with the empty prefix.
+    pub fn default_namespace() -> Self {
+        Self {
+            prefix: Some(AvmString::default()),
+            uri: AvmString::default(),
+        }
+    }
+
+    /// Read the URI and prefix of a boxed `Namespace` object.
+    pub fn from_namespace_object(namespace: NamespaceObject<'gc>) -> Self {
+        Self {
+            prefix: namespace.prefix(),
+            uri: namespace.namespace().as_uri(),
+        }
+    }
+
+    pub fn as_namespace_object(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let namespace = Namespace::package(
+            self.uri,
+            ApiVersion::AllVersions,
+            &mut activation.context.borrow_gc(),
+        );
+        NamespaceObject::from_ns_and_prefix(activation, namespace, self.prefix)
+    }
 }
 
 impl<'gc> Debug for E4XNodeData<'gc> {
@@ -46,6 +97,7 @@ impl<'gc> Debug for E4XNodeData<'gc> {
         f.debug_struct("E4XNodeData")
             // Don't print the actual parent, to avoid infinite recursion
             .field("parent", &self.parent.is_some())
+            .field("namespace", &self.namespace)
             .field("local_name", &self.local_name)
             .field("kind", &self.kind)
             .finish()
@@ -126,6 +178,7 @@ impl<'gc> E4XNode<'gc> {
                     children: vec![],
                 },
                 notification: None,
+                declared_namespaces: vec![],
             },
         ))
     }
@@ -139,13 +192,14 @@ impl<'gc> E4XNode<'gc> {
                 local_name: None,
                 kind: E4XNodeKind::Text(text),
                 notification: None,
+                declared_namespaces: vec![],
             },
         ))
     }
 
     pub fn element(
         mc: &Mutation<'gc>,
-        namespace: Option<AvmString<'gc>>,
+        namespace: Option<E4XNamespace<'gc>>,
         name: AvmString<'gc>,
         parent: Option<Self>,
     ) -> Self {
@@ -160,6 +214,7 @@ impl<'gc> E4XNode<'gc> {
                     children: vec![],
                 },
                 notification: None,
+                declared_namespaces: vec![],
             },
         ))
     }
@@ -178,6 +233,7 @@ impl<'gc> E4XNode<'gc> {
                 local_name: Some(name),
                 kind: E4XNodeKind::Attribute(value),
                 notification: None,
+                declared_namespaces: vec![],
             },
         ))
     }
@@ -215,6 +271,11 @@ impl<'gc> E4XNode<'gc> {
             return false;
         }
 
+        let uri = |node: &Self| node.namespace().map(|ns| ns.uri);
+        if uri(self) != uri(other) {
+            return false;
+        }
+
         let this = self.0.read();
         let other = other.0.read();
 
@@ -287,6 +348,7 @@ impl<'gc> E4XNode<'gc> {
                 local_name: this.local_name,
                 kind,
                 notification: None,
+                declared_namespaces: this.declared_namespaces.clone(),
             },
         ));
 
@@ -789,6 +851,7 @@ impl<'gc> E4XNode<'gc> {
                             E4XNodeKind::CData(text)
                         },
                         notification: None,
+                        declared_namespaces: vec![],
                     },
                 ));
                 push_childless_node(node, open_tags, top_level, activation)?;
@@ -862,6 +925,7 @@ impl<'gc> E4XNode<'gc> {
                             local_name: None,
                             kind: E4XNodeKind::Comment(text),
                             notification: None,
+                            declared_namespaces: vec![],
                         },
                     ));
 
@@ -901,6 +965,7 @@ impl<'gc> E4XNode<'gc> {
                             local_name: Some(name),
                             kind: E4XNodeKind::ProcessingInstruction(value),
                             notification: None,
+                            declared_namespaces: vec![],
                         },
                     ));
 
@@ -924,23 +989,45 @@ impl<'gc> E4XNode<'gc> {
         bs: &BytesStart<'_>,
         decoder: quick_xml::Decoder,
     ) -> Result<Self, Error<'gc>> {
+        let mc = activation.context.gc_context;
+
+        // A prefix bound to the empty URI (such as with `xmlns=""`) means "no namespace".
+        let make_namespace = |prefix: Option<&[u8]>, uri: &[u8]| {
+            (!uri.is_empty()).then(|| E4XNamespace {
+                prefix: Some(AvmString::new_utf8_bytes(mc, prefix.unwrap_or_default())),
+                uri: AvmString::new_utf8_bytes(mc, uri),
+            })
+        };
+
         let mut attribute_nodes = Vec::new();
+        let mut declared_namespaces = Vec::new();
 
         let attributes: Result<Vec<_>, _> = bs.attributes().collect();
         for attribute in
             attributes.map_err(|e| make_xml_error(activation, XmlError::InvalidAttr(e)))?
         {
+            let value_str = custom_unescape(&attribute.value, decoder)
+                .map_err(|e| make_xml_error(activation, e))?;
+
+            if let Some(binding) = attribute.key.as_namespace_binding() {
+                let prefix = match binding {
+                    PrefixDeclaration::Default => AvmString::default(),
+                    PrefixDeclaration::Named(prefix) => AvmString::new_utf8_bytes(mc, prefix),
+                };
+                declared_namespaces.push(E4XNamespace {
+                    prefix: Some(prefix),
+                    uri: AvmString::new_utf8_bytes(mc, value_str.as_bytes()),
+                });
+                continue;
+            }
+
             let (ns, local_name) = parser.resolve_attribute(attribute.key);
-            let name =
-                AvmString::new_utf8_bytes(activation.context.gc_context, local_name.into_inner());
+            let name = AvmString::new_utf8_bytes(mc, local_name.into_inner());
             let namespace = match ns {
-                ResolveResult::Bound(ns) if ns.into_inner() == b"http://www.w3.org/2000/xmlns/" => {
-                    continue
-                }
-                ResolveResult::Bound(ns) => Some(AvmString::new_utf8_bytes(
-                    activation.context.gc_context,
+                ResolveResult::Bound(ns) => make_namespace(
+                    attribute.key.prefix().map(|prefix| prefix.into_inner()),
                     ns.into_inner(),
-                )),
+                ),
                 ResolveResult::Unknown(ns) => {
                     return Err(Error::AvmError(type_error(
                         activation,
@@ -955,10 +1042,7 @@ impl<'gc> E4XNode<'gc> {
                 ResolveResult::Unbound => None,
             };
 
-            let value_str = custom_unescape(&attribute.value, decoder)
-                .map_err(|e| make_xml_error(activation, e))?;
-            let value =
-                AvmString::new_utf8_bytes(activation.context.gc_context, value_str.as_bytes());
+            let value = AvmString::new_utf8_bytes(mc, value_str.as_bytes());
 
             let attribute_data = E4XNodeData {
                 parent: None,
@@ -966,19 +1050,19 @@ impl<'gc> E4XNode<'gc> {
                 local_name: Some(name),
                 kind: E4XNodeKind::Attribute(value),
                 notification: None,
+                declared_namespaces: vec![],
             };
-            let attribute = E4XNode(GcCell::new(activation.context.gc_context, attribute_data));
+            let attribute = E4XNode(GcCell::new(mc, attribute_data));
             attribute_nodes.push(attribute);
         }
 
         let (ns, local_name) = parser.resolve_element(bs.name());
-        let name =
-            AvmString::new_utf8_bytes(activation.context.gc_context, local_name.into_inner());
+        let name = AvmString::new_utf8_bytes(mc, local_name.into_inner());
         let namespace = match ns {
-            ResolveResult::Bound(ns) => Some(AvmString::new_utf8_bytes(
-                activation.context.gc_context,
+            ResolveResult::Bound(ns) => make_namespace(
+                bs.name().prefix().map(|prefix| prefix.into_inner()),
                 ns.into_inner(),
-            )),
+            ),
             ResolveResult::Unknown(ns) => {
                 return Err(Error::AvmError(type_error(
                     activation,
@@ -990,7 +1074,15 @@ impl<'gc> E4XNode<'gc> {
                     1083,
                 )?))
             }
-            ResolveResult::Unbound => None,
+            // Unqualified elements are placed in the `default xml namespace`, if there is one.
+            ResolveResult::Unbound => activation
+                .avm2()
+                .default_xml_namespace()
+                .filter(|uri| !uri.is_empty())
+                .map(|uri| E4XNamespace {
+                    prefix: Some(AvmString::default()),
+                    uri,
+                }),
         };
 
         let data = E4XNodeData {
@@ -1002,28 +1094,162 @@ impl<'gc> E4XNode<'gc> {
                 children: Vec::new(),
             },
             notification: None,
+            declared_namespaces,
         };
 
-        let result = E4XNode(GcCell::new(activation.context.gc_context, data));
+        let result = E4XNode(GcCell::new(mc, data));
 
-        let mut result_kind = result.kind_mut(activation.context.gc_context);
+        let mut result_kind = result.kind_mut(mc);
         if let E4XNodeKind::Element { attributes, .. } = &mut *result_kind {
             for attribute in attributes {
-                attribute.set_parent(Some(result), activation.context.gc_context);
+                attribute.set_parent(Some(result), mc);
             }
         }
 
         Ok(result)
     }
 
-    pub fn set_namespace(&self, namespace: AvmString<'gc>, mc: &Mutation<'gc>) {
-        self.0.write(mc).namespace = Some(namespace);
+    pub fn set_namespace(&self, namespace: Option<E4XNamespace<'gc>>, mc: &Mutation<'gc>) {
+        self.0.write(mc).namespace = namespace;
     }
 
-    pub fn namespace(&self) -> Option<AvmString<'gc>> {
+    pub fn namespace(&self) -> Option<E4XNamespace<'gc>> {
         self.0.read().namespace
     }
 
+    /// Returns the namespaces declared on this node itself.
+    pub fn declared_namespaces(&self) -> Ref<'_, [E4XNamespace<'gc>]> {
+        Ref::map(self.0.read(), |r| r.declared_namespaces.as_slice())
+    }
+
+    /// Returns every namespace in scope on this node, starting with its own declarations.
+    ///
+    /// When a prefix is declared more than once, the declaration closest to this node wins.
+    pub fn in_scope_namespaces(&self) -> Vec<E4XNamespace<'gc>> {
+        let mut namespaces: Vec<E4XNamespace<'gc>> = Vec::new();
+        for node in self.ancestors() {
+            for namespace in node.declared_namespaces().iter() {
+                if !namespaces.iter().any(|ns| ns.prefix == namespace.prefix) {
+                    namespaces.push(*namespace);
+                }
+            }
+        }
+        namespaces
+    }
+
+    // ECMA-357 9.1.1.13 [[AddInScopeNamespace]] (N)
+    pub fn add_in_scope_namespace(&self, namespace: E4XNamespace<'gc>, mc: &Mutation<'gc>) {
+        // 1. If x.[[Class]] ∈ {"text", "comment", "processing-instruction", "attribute"}, return
+        if !self.is_element() {
+            return;
+        }
+
+        // 2. If N.prefix != undefined
+        let Some(prefix) = namespace.prefix else {
+            return;
+        };
+
+        let mut data = self.0.write(mc);
+
+        // 2.a. If N.prefix == "" and x.[[Name]].uri == "", return
+        if prefix.is_empty() && data.namespace.is_none() {
+            return;
+        }
+
+        // 2.b. Let match be null
+        // 2.c. For each ns in x.[[InScopeNamespaces]]
+        //     2.c.i. If N.prefix == ns.prefix, let match = ns
+        let existing = data
+            .declared_namespaces
+            .iter()
+            .position(|ns| ns.prefix == Some(prefix));
+
+        match existing {
+            // 2.d. If match is not null and match.uri is not equal to N.uri
+            //     2.d.i. Remove match from x.[[InScopeNamespaces]]
+            // 2.e. Let x.[[InScopeNamespaces]] = x.[[InScopeNamespaces]] ∪ { N }
+            Some(index) if data.declared_namespaces[index].uri != namespace.uri => {
+                data.declared_namespaces[index] = namespace;
+            }
+            Some(_) => {}
+            None => data.declared_namespaces.push(namespace),
+        }
+
+        // 2.f. If x.[[Name]].[[Prefix]] == N.prefix
+        //     2.f.i. Let x.[[Name]].prefix = undefined
+        if let Some(own_namespace) = &mut data.namespace {
+            if own_namespace.prefix == Some(prefix) {
+                own_namespace.prefix = None;
+            }
+        }
+
+        // NOTE: Flash Player doesn't implement step 2.g, which would clear the prefix
+        // of attributes using N.prefix.
+    }
+
+    // ECMA-357 13.4.4.31 XML.prototype.removeNamespace (namespace)
+    pub fn remove_namespace(&self, namespace: E4XNamespace<'gc>, mc: &Mutation<'gc>) {
+        // 1. If x.[[Class]] ∈ {"text", "comment", "processing-instruction", "attribute"}, return x
+        let children = {
+            let E4XNodeKind::Element {
+                attributes,
+                children,
+            } = &*self.kind()
+            else {
+                return;
+            };
+
+            let uri_of =
+                |node: &E4XNode<'gc>| node.namespace().map(|ns| ns.uri).unwrap_or_default();
+
+            // 3. Let thisNS be the result of calling [[GetNamespace]] on x.[[Name]] with argument x.[[InScopeNamespaces]]
+            // 4. If (thisNS == ns), return x
+            if uri_of(self) == namespace.uri {
+                return;
+            }
+
+            // 5. For each a in x.[[Attributes]]
+            //     5.a. Let aNS be the result of calling [[GetNamespace]] on a.[[Name]] with argument x.[[InScopeNamespaces]]
+            //     5.b. If (aNS == ns), return x
+            if attributes
+                .iter()
+                .any(|attr| attr.namespace().is_some() && uri_of(attr) == namespace.uri)
+            {
+                return;
+            }
+
+            children.clone()
+        };
+
+        // 6. If ns.prefix == undefined, remove any n ∈ x.[[InScopeNamespaces]] with n.uri == ns.uri
+        // 7. Else, remove any n ∈ x.[[InScopeNamespaces]] with n.uri == ns.uri and n.prefix == ns.prefix
+        self.0.write(mc).declared_namespaces.retain(|ns| {
+            ns.uri != namespace.uri || namespace.prefix.map_or(false, |p| ns.prefix != Some(p))
+        });
+
+        // 8. For each property p of x, if p.[[Class]] = "element", call the removeNamespace method of p with argument ns
+        for child in children {
+            child.remove_namespace(namespace, mc);
+        }
+    }
+
+    // ECMA-357 13.3.5.4 [[GetNamespace]] ( [ InScopeNamespaces ] )
+    /// Returns the namespace of this node's name, bound to a prefix from its scope if possible.
+    pub fn get_namespace(&self) -> E4XNamespace<'gc> {
+        let Some(namespace) = self.namespace() else {
+            return E4XNamespace::default_namespace();
+        };
+
+        if namespace.prefix.is_some() {
+            return namespace;
+        }
+
+        self.in_scope_namespaces()
+            .into_iter()
+            .find(|ns| ns.uri == namespace.uri)
+            .unwrap_or(namespace)
+    }
+
     pub fn set_local_name(&self, name: AvmString<'gc>, mc: &Mutation<'gc>) {
         self.0.write(mc).local_name = Some(name);
     }
@@ -1069,7 +1295,7 @@ impl<'gc> E4XNode<'gc> {
             return true;
         }
 
-        let self_ns = self.namespace().unwrap_or_default();
+        let self_ns = self.namespace().map(|ns| ns.uri).unwrap_or_default();
         // FIXME: For cases where we don't have *any* explicit namespace
         // we just give up and assume we should match the default public namespace.
         if !name.namespace_set().iter().any(|ns| ns.is_namespace()) {
@@ -1222,9 +1448,94 @@ pub fn escape_element_value(s: AvmString) -> WString {
     r
 }
 
-fn to_xml_string_inner(xml: E4XOrXml, buf: &mut WString, pretty: Option<(u32, u32)>) {
-    // FIXME: Namespace support.
+/// Picks the prefix that a name in `namespace` is serialized with, declaring
+/// a new one if necessary (see ECMA-357 10.2.1, steps 11 and 17).
+///
+/// Returns `None` for names without a namespace, which are never prefixed.
+fn namespace_prefix<'gc>(
+    namespace: Option<E4XNamespace<'gc>>,
+    ancestor_namespaces: &[E4XNamespace<'gc>],
+    namespace_declarations: &mut Vec<E4XNamespace<'gc>>,
+    mc: &Mutation<'gc>,
+) -> Option<AvmString<'gc>> {
+    let namespace = namespace?;
+
+    // The `xml` prefix is bound implicitly, and never needs to be declared.
+    if &*namespace.uri == XML_NAMESPACE_URI {
+        return Some(AvmString::new_utf8(mc, "xml"));
+    }
+
+    // Prefer the name's own prefix if it's bound to the right URI, then any prefix bound to it.
+    let in_scope = ancestor_namespaces
+        .iter()
+        .chain(namespace_declarations.iter());
+    let bound = in_scope
+        .clone()
+        .find(|ns| ns.prefix.is_some() && ns.prefix == namespace.prefix && ns.uri == namespace.uri)
+        .or_else(|| {
+            in_scope
+                .clone()
+                .find(|ns| ns.prefix.is_some() && ns.uri == namespace.uri)
+        });
+    if let Some(bound) = bound {
+        return bound.prefix;
+    }
+
+    let is_unused = |prefix: &WStr| {
+        !ancestor_namespaces
+            .iter()
+            .chain(namespace_declarations.iter())
+            .any(|ns| ns.prefix.map_or(false, |p| &*p == prefix))
+    };
+
+    // Otherwise declare the name's own prefix, or make one up.
+    // Like Flash Player, this prefers the empty prefix, even for attributes.
+    let prefix = match namespace.prefix {
+        Some(prefix) if is_unused(&prefix) => prefix,
+        _ if is_unused(WStr::empty()) => AvmString::default(),
+        _ => {
+            let mut generated = *b"aaa";
+            while !is_unused(WStr::from_units(&generated)) {
+                for letter in generated.iter_mut().rev() {
+                    if *letter == b'z' {
+                        *letter = b'a';
+                    } else {
+                        *letter += 1;
+                        break;
+                    }
+                }
+            }
+            AvmString::new_utf8_bytes(mc, &generated)
+        }
+    };
+
+    namespace_declarations.push(E4XNamespace {
+        prefix: Some(prefix),
+        uri: namespace.uri,
+    });
+    Some(prefix)
+}
+
+fn push_qualified_name(buf: &mut WString, prefix: Option<AvmString>, local_name: AvmString) {
+    if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+        buf.push_str(&prefix);
+        buf.push_char(':');
+    }
+    buf.push_str(&local_name);
+}
 
+/// Serializes a node, as in ECMA-357 10.2.1.
+///
+/// `ancestor_namespaces` holds the namespaces declared by the ancestors that were already
+/// serialized, or is `None` for the outermost node. Like in Flash Player, the outermost
+/// element also declares all namespaces it inherits from its parents.
+fn to_xml_string_inner<'gc>(
+    xml: E4XOrXml<'gc>,
+    buf: &mut WString,
+    pretty: Option<(u32, u32)>,
+    ancestor_namespaces: Option<&[E4XNamespace<'gc>]>,
+    mc: &Mutation<'gc>,
+) {
     let node = xml.node();
     let node_kind = node.kind();
 
@@ -1270,13 +1581,45 @@ fn to_xml_string_inner(xml: E4XOrXml, buf: &mut WString, pretty: Option<(u32, u3
         } => (children, attributes),
     };
 
+    let (ancestor_namespaces, namespaces) = match ancestor_namespaces {
+        Some(ancestor_namespaces) => (ancestor_namespaces, node.declared_namespaces().to_vec()),
+        None => (&[][..], node.in_scope_namespaces()),
+    };
+
+    // Only declare the namespaces that aren't already declared by an ancestor.
+    let mut namespace_declarations: Vec<E4XNamespace<'gc>> = namespaces
+        .into_iter()
+        .filter(|ns| {
+            ns.prefix.is_some()
+                && &*ns.uri != XML_NAMESPACE_URI
+                && !ancestor_namespaces
+                    .iter()
+                    .any(|ans| ans.prefix == ns.prefix && ans.uri == ns.uri)
+        })
+        .collect();
+
+    let prefix = namespace_prefix(
+        node.namespace(),
+        ancestor_namespaces,
+        &mut namespace_declarations,
+        mc,
+    );
+    let local_name = node.local_name().unwrap();
+
     buf.push_char('<');
-    buf.push_str(&node.local_name().unwrap());
+    push_qualified_name(buf, prefix, local_name);
 
     for attribute in attributes {
         if let E4XNodeKind::Attribute(value) = &*attribute.kind() {
+            let attribute_prefix = namespace_prefix(
+                attribute.namespace(),
+                ancestor_namespaces,
+                &mut namespace_declarations,
+                mc,
+            );
+
             buf.push_char(' ');
-            buf.push_str(&attribute.local_name().unwrap());
+            push_qualified_name(buf, attribute_prefix, attribute.local_name().unwrap());
             buf.push_char('=');
             buf.push_char('"');
             buf.push_str(&escape_attribute_value(*value));
@@ -1284,6 +1627,17 @@ fn to_xml_string_inner(xml: E4XOrXml, buf: &mut WString, pretty: Option<(u32, u3
         }
     }
 
+    for namespace in &namespace_declarations {
+        buf.push_utf8(" xmlns");
+        if let Some(prefix) = namespace.prefix.filter(|prefix| !prefix.is_empty()) {
+            buf.push_char(':');
+            buf.push_str(&prefix);
+        }
+        buf.push_utf8("=\"");
+        buf.push_str(&escape_attribute_value(namespace.uri));
+        buf.push_char('"');
+    }
+
     if children.is_empty() {
         buf.push_utf8("/>");
         return;
@@ -1302,11 +1656,20 @@ fn to_xml_string_inner(xml: E4XOrXml, buf: &mut WString, pretty: Option<(u32, u3
         None
     };
 
+    let mut child_ancestor_namespaces = ancestor_namespaces.to_vec();
+    child_ancestor_namespaces.extend(namespace_declarations);
+
     for child in children {
         if pretty.is_some() && indent_children {
             buf.push_char('\n');
         }
-        to_xml_string_inner(E4XOrXml::E4X(*child), buf, child_pretty);
+        to_xml_string_inner(
+            E4XOrXml::E4X(*child),
+            buf,
+            child_pretty,
+            Some(&child_ancestor_namespaces),
+            mc,
+        );
     }
 
     if let Some((indent_level, _)) = pretty {
@@ -1319,7 +1682,7 @@ fn to_xml_string_inner(xml: E4XOrXml, buf: &mut WString, pretty: Option<(u32, u3
     }
 
     buf.push_utf8("</");
-    buf.push_str(&node.local_name().unwrap());
+    push_qualified_name(buf, prefix, local_name);
     buf.push_char('>');
 }
 
@@ -1356,7 +1719,7 @@ pub fn to_xml_string<'gc>(
     };
 
     let mut buf = WString::new();
-    to_xml_string_inner(xml, &mut buf, pretty);
+    to_xml_string_inner(xml, &mut buf, pretty, None, activation.gc());
     AvmString::new(activation.context.gc_context, buf)
 }
 
@@ -1374,6 +1737,14 @@ pub fn string_to_multiname<'gc>(
         Multiname::attribute(activation.avm2().public_namespace_base_version, name)
     } else if &*name == b"*" {
         Multiname::any(activation.context.gc_context)
+    } else if let Some(uri) = activation.avm2().default_xml_namespace() {
+        // Unqualified element names are in the `default xml namespace`.
+        let namespace = Namespace::package(
+            uri,
+            ApiVersion::AllVersions,
+            &mut activation.context.borrow_gc(),
+        );
+        Multiname::new(namespace, name)
     } else {
         Multiname::new(activation.avm2().public_namespace_base_version, name)
     }
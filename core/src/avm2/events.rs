@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::error::type_error;
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::profiler::TraceCategory;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
@@ -301,6 +302,19 @@ impl<'gc> DispatchList<'gc> {
             .filter(move |eh| eh.use_capture == use_capture)
             .map(|eh| eh.handler)
     }
+
+    /// Yield every event handler on this dispatch list, along with the event
+    /// it is registered for.
+    pub fn iter_all_event_handlers(
+        &self,
+    ) -> impl '_ + Iterator<Item = (AvmString<'gc>, Object<'gc>)> {
+        self.0.iter().flat_map(|(event, sheaf)| {
+            sheaf
+                .values()
+                .flat_map(|v| v.iter())
+                .map(move |eh| (*event, eh.handler))
+        })
+    }
 }
 
 impl<'gc> Default for DispatchList<'gc> {
@@ -413,6 +427,14 @@ pub fn dispatch_event_to_target<'gc>(
 
     drop(evtmut);
 
+    if handlers.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(profiler) = activation.context.avm2.profiler_mut() {
+        profiler.begin(name.to_string(), TraceCategory::Event);
+    }
+
     for handler in handlers.iter() {
         if event
             .as_event()
@@ -434,6 +456,10 @@ pub fn dispatch_event_to_target<'gc>(
         }
     }
 
+    if let Some(profiler) = activation.context.avm2.profiler_mut() {
+        profiler.end();
+    }
+
     Ok(())
 }
 
@@ -212,6 +212,22 @@ impl<'gc> Executable<'gc> {
         }
     }
 
+    /// The scope this method was defined in.
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        match self {
+            Executable::Native(NativeExecutable { scope, .. }) => *scope,
+            Executable::Action(BytecodeExecutable { scope, .. }) => *scope,
+        }
+    }
+
+    /// The receiver that this method is always called with, if any.
+    pub fn bound_receiver(&self) -> Option<Object<'gc>> {
+        match self {
+            Executable::Native(NativeExecutable { bound_receiver, .. }) => *bound_receiver,
+            Executable::Action(BytecodeExecutable { receiver, .. }) => *receiver,
+        }
+    }
+
     pub fn as_method(&self) -> Method<'gc> {
         match self {
             Executable::Native(nm) => Method::Native(nm.method),
@@ -114,12 +114,20 @@ pub struct SystemClasses<'gc> {
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub imeevent: ClassObject<'gc>,
+    pub ime: ClassObject<'gc>,
+    pub worker: ClassObject<'gc>,
+    pub messagechannel: ClassObject<'gc>,
+    pub mutex: ClassObject<'gc>,
+    pub condition: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
+    pub matrix3d: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub illegaloperationerror: ClassObject<'gc>,
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
@@ -134,6 +142,7 @@ pub struct SystemClasses<'gc> {
     pub verifyerror: ClassObject<'gc>,
     pub ioerror: ClassObject<'gc>,
     pub eoferror: ClassObject<'gc>,
+    pub scripttimeouterror: ClassObject<'gc>,
     pub urierror: ClassObject<'gc>,
     pub securityerror: ClassObject<'gc>,
     pub error: ClassObject<'gc>,
@@ -174,6 +183,8 @@ pub struct SystemClasses<'gc> {
     pub sampledataevent: ClassObject<'gc>,
     pub avm1movie: ClassObject<'gc>,
     pub focusevent: ClassObject<'gc>,
+    pub numberparseresult: ClassObject<'gc>,
+    pub currencyparseresult: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -246,12 +257,20 @@ impl<'gc> SystemClasses<'gc> {
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
+            imeevent: object,
+            ime: object,
+            worker: object,
+            messagechannel: object,
+            mutex: object,
+            condition: object,
             errorevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
             transform: object,
             colortransform: object,
             matrix: object,
+            matrix3d: object,
+            perspectiveprojection: object,
             illegaloperationerror: object,
             eventdispatcher: object,
             rectangle: object,
@@ -266,6 +285,7 @@ impl<'gc> SystemClasses<'gc> {
             verifyerror: object,
             ioerror: object,
             eoferror: object,
+            scripttimeouterror: object,
             urierror: object,
             securityerror: object,
             error: object,
@@ -306,6 +326,8 @@ impl<'gc> SystemClasses<'gc> {
             sampledataevent: object,
             avm1movie: object,
             focusevent: object,
+            numberparseresult: object,
+            currencyparseresult: object,
         }
     }
 }
@@ -788,9 +810,11 @@ fn load_playerglobal<'gc>(
             ),
             ("flash.errors", "IOError", ioerror),
             ("flash.errors", "EOFError", eoferror),
+            ("flash.errors", "ScriptTimeoutError", scripttimeouterror),
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "IMEEvent", imeevent),
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
@@ -805,10 +829,22 @@ fn load_playerglobal<'gc>(
             ("flash.events", "ContextMenuEvent", contextmenuevent),
             ("flash.events", "FocusEvent", focusevent),
             ("flash.geom", "Matrix", matrix),
+            ("flash.geom", "Matrix3D", matrix3d),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
             ("flash.geom", "Point", point),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
             ("flash.geom", "ColorTransform", colortransform),
+            (
+                "flash.globalization",
+                "NumberParseResult",
+                numberparseresult
+            ),
+            (
+                "flash.globalization",
+                "CurrencyParseResult",
+                currencyparseresult
+            ),
             ("flash.media", "SoundChannel", soundchannel),
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
@@ -817,6 +853,11 @@ fn load_playerglobal<'gc>(
             ("flash.net", "FileFilter", filefilter),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.system", "ApplicationDomain", application_domain),
+            ("flash.system", "IME", ime),
+            ("flash.system", "Worker", worker),
+            ("flash.system", "MessageChannel", messagechannel),
+            ("flash.concurrent", "Mutex", mutex),
+            ("flash.concurrent", "Condition", condition),
             ("flash.text", "Font", font),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
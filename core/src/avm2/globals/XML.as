@@ -63,6 +63,9 @@ package {
         }
         AS3 native function inScopeNamespaces():Array;
         AS3 native function namespaceDeclarations():Array;
+        AS3 native function addNamespace(ns:*):XML;
+        AS3 native function removeNamespace(ns:*):XML;
+        AS3 native function setNamespace(ns:*):void;
         AS3 native function localName():Object;
         AS3 native function toXMLString():String;
         AS3 native function child(name:Object):XMLList;
@@ -146,6 +149,21 @@ package {
             return self.AS3::inScopeNamespaces();
         };
 
+        prototype.addNamespace = function(ns:*):XML {
+            var self:XML = this;
+            return self.AS3::addNamespace(ns);
+        };
+
+        prototype.removeNamespace = function(ns:*):XML {
+            var self:XML = this;
+            return self.AS3::removeNamespace(ns);
+        };
+
+        prototype.setNamespace = function(ns:*):void {
+            var self:XML = this;
+            self.AS3::setNamespace(ns);
+        };
+
         prototype.localName = function():Object {
             var self:XML = this;
             return self.AS3::localName();
@@ -1,7 +1,51 @@
 package flash.concurrent {
     public final class Condition {
-        public static const isSupported: Boolean = false;
+        public static const isSupported: Boolean = true;
 
-        public function Condition(mutex: Mutex) {}
+        private static var _conditions: Object = {};
+        private static var _creating: Boolean = false;
+
+        internal var _id: uint;
+        private var _mutex: Mutex;
+
+        public function Condition(mutex: Mutex) {
+            if (_creating) {
+                return;
+            }
+            if (mutex == null) {
+                throw new ArgumentError("Error #1507: Argument mutex cannot be null.", 1507);
+            }
+            this._mutex = mutex;
+            this._id = create(mutex._id);
+            _conditions[this._id] = this;
+        }
+
+        // Returns the one object that represents a condition in this worker.
+        internal static function fromId(id: uint): Condition {
+            var condition: Condition = _conditions[id];
+            if (condition == null) {
+                _creating = true;
+                try {
+                    condition = new Condition(null);
+                } finally {
+                    _creating = false;
+                }
+                condition._id = id;
+                condition._mutex = Mutex.fromId(mutexId(id));
+                _conditions[id] = condition;
+            }
+            return condition;
+        }
+
+        private static native function create(mutexId: uint): uint;
+        private static native function mutexId(id: uint): uint;
+
+        public function get mutex(): Mutex {
+            return this._mutex;
+        }
+
+        public native function notify(): void;
+        public native function notifyAll(): void;
+        public native function wait(timeout: Number = -1): Boolean;
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,39 @@
 package flash.concurrent {
     public final class Mutex {
-        public static const isSupported: Boolean = false;
-        
+        public static const isSupported: Boolean = true;
+
+        private static var _mutexes: Object = {};
+        private static var _creating: Boolean = false;
+
+        internal var _id: uint;
+
         public function Mutex() {
-            throw new Error("Error #1520: Mutex cannot be initialized.", 1520);
+            if (!_creating) {
+                this._id = create();
+                _mutexes[this._id] = this;
+            }
         }
+
+        // Returns the one object that represents a mutex in this worker.
+        internal static function fromId(id: uint): Mutex {
+            var mutex: Mutex = _mutexes[id];
+            if (mutex == null) {
+                _creating = true;
+                try {
+                    mutex = new Mutex();
+                } finally {
+                    _creating = false;
+                }
+                mutex._id = id;
+                _mutexes[id] = mutex;
+            }
+            return mutex;
+        }
+
+        private static native function create(): uint;
+
+        public native function lock(): void;
+        public native function tryLock(): Boolean;
+        public native function unlock(): void;
     }
-}
\ No newline at end of file
+}
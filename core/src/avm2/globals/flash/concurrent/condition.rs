@@ -0,0 +1,102 @@
+//! `flash.concurrent.Condition` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::system::worker::{block, object_id, wait_error};
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::worker::ConditionId;
+use web_time::Duration;
+
+fn condition_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<ConditionId, Error<'gc>> {
+    let namespace = activation.avm2().flash_concurrent_internal;
+    object_id(activation, this, namespace)
+}
+
+/// Implements `Condition.create`
+pub fn create<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mutex = args.get_u32(activation, 0)?;
+    Ok(activation.context.workers.create_condition(mutex).into())
+}
+
+/// Implements `Condition.mutexId`
+pub fn mutex_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = args.get_u32(activation, 0)?;
+    Ok(activation.context.workers.condition_mutex(id).into())
+}
+
+fn notify_workers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    all: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = condition_id(activation, this)?;
+    activation.context.workers.notify(id, all).map_err(|e| {
+        wait_error(
+            activation,
+            e,
+            "Error #1516: Condition cannot notify if associated mutex is not owned.",
+            1516,
+        )
+    })?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.notify`
+pub fn notify<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    notify_workers(activation, this, false)
+}
+
+/// Implements `Condition.notifyAll`
+pub fn notify_all<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    notify_workers(activation, this, true)
+}
+
+/// Implements `Condition.wait`
+pub fn wait<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = condition_id(activation, this)?;
+    let timeout = args.get_f64(activation, 0)?;
+
+    let timeout = if timeout < 0.0 {
+        None
+    } else {
+        Some(Duration::from_millis(timeout as u64))
+    };
+
+    let notified = block(activation, |workers, limit| {
+        workers.wait(id, timeout, limit)
+    })
+    .map_err(|e| {
+        wait_error(
+            activation,
+            e,
+            "Error #1515: Condition cannot wait if associated mutex is not owned.",
+            1515,
+        )
+    })?;
+    Ok(notified.into())
+}
@@ -0,0 +1,73 @@
+//! `flash.concurrent.Mutex` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::illegal_operation_error;
+use crate::avm2::globals::flash::system::worker::{block, object_id, wait_error};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::worker::MutexId;
+
+fn mutex_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<MutexId, Error<'gc>> {
+    let namespace = activation.avm2().flash_concurrent_internal;
+    object_id(activation, this, namespace)
+}
+
+/// Implements `Mutex.create`
+pub fn create<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.workers.create_mutex().into())
+}
+
+/// Implements `Mutex.lock`
+pub fn lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = mutex_id(activation, this)?;
+    if !activation.context.workers.try_lock(id) {
+        block(activation, |workers, limit| workers.lock(id, limit)).map_err(|e| {
+            wait_error(
+                activation,
+                e,
+                "Error #1517: The current worker doesn't own the mutex.",
+                1517,
+            )
+        })?;
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mutex.tryLock`
+pub fn try_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = mutex_id(activation, this)?;
+    Ok(activation.context.workers.try_lock(id).into())
+}
+
+/// Implements `Mutex.unlock`
+pub fn unlock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = mutex_id(activation, this)?;
+    if !activation.context.workers.unlock(id) {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #1517: The current worker doesn't own the mutex.",
+            1517,
+        )?));
+    }
+    Ok(Value::Undefined)
+}
@@ -1,11 +1,13 @@
 package flash.display
 {
     import flash.geom.Matrix;
-    import __ruffle__.stub_method;
 
     // note: no need for an allocator, as it's never constructed from AS
     public final class Graphics
     {
+        // The BitmapData objects used by bitmap fills, returned again by readGraphicsData
+        internal var _bitmaps:Array;
+
         public function Graphics()
         {
             throw new Error("You cannot construct Graphics directly.");
@@ -16,9 +18,7 @@ package flash.display
         public native function beginGradientFill(
             type:String, colors:Array, alphas:Array, ratios:Array, matrix:Matrix = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0
         ): void;
-        public function beginShaderFill(shader:Shader, matrix:Matrix = null):void {
-            stub_method("flash.display.Graphics", "beginShaderFill");
-        }
+        public native function beginShaderFill(shader:Shader, matrix:Matrix = null):void;
         public native function clear(): void;
         public native function curveTo(controlX:Number, controlY:Number, anchorX:Number, anchorY:Number): void;
         public native function drawCircle(x:Number, y:Number, radius:Number): void;
@@ -31,7 +31,6 @@ package flash.display
         ): void;
         public native function lineTo(x:Number, y:Number): void;
         public native function moveTo(x:Number, y:Number): void;
-        //public native function beginShaderFill(shader:Shader, matrix:Matrix = null):void;
         public native function lineGradientStyle(
             type:String, colors:Array, alphas:Array, ratios:Array, matrix:Matrix = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0
         ):void;
@@ -2,17 +2,20 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{argument_error, make_error_2008};
+use crate::avm2::globals::flash::display::shader_job::get_shader_args;
+use crate::avm2::globals::flash::geom::transform::matrix_to_object;
 use crate::avm2::globals::flash::geom::transform::object_to_matrix;
-use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::object::{ArrayObject, Object, TObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
-use crate::avm2::{ArrayStorage, Error};
+use crate::avm2::{ArrayStorage, Error, Multiname};
 use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::drawing::Drawing;
 use crate::string::{AvmString, WStr};
-use ruffle_render::shape_utils::{DrawCommand, GradientType};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::{DrawCommand, DrawPath, GradientType, ShaderFill};
 use std::f64::consts::FRAC_1_SQRT_2;
 use swf::{
     Color, FillStyle, Fixed16, Fixed8, Gradient, GradientInterpolation, GradientRecord,
@@ -49,9 +52,9 @@ pub fn begin_bitmap_fill<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        let bitmap = args
-            .get_object(activation, 0, "bitmap")?
+    if let Some(display_object) = this.as_display_object() {
+        let bitmap_object = args.get_object(activation, 0, "bitmap")?;
+        let bitmap = bitmap_object
             .as_bitmap_data()
             .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
         let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
@@ -76,7 +79,7 @@ pub fn begin_bitmap_fill<'gc>(
             (Twips::TWIPS_PER_PIXEL as i16).into(),
         );
 
-        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+        if let Some(mut draw) = display_object.as_drawing(activation.context.gc_context) {
             let id = draw.add_bitmap(bitmap);
             draw.set_fill_style(Some(FillStyle::Bitmap {
                 id,
@@ -84,6 +87,35 @@ pub fn begin_bitmap_fill<'gc>(
                 is_smoothed,
                 is_repeating,
             }));
+            add_fill_bitmap(activation, this, &draw, bitmap_object)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.beginShaderFill`.
+pub fn begin_shader_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_display_object() {
+        let shader = args.get_object(activation, 0, "shader")?;
+        let (shader, arguments) = get_shader_args(shader, activation)?;
+        let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
+            object_to_matrix(matrix, activation)?
+        } else {
+            // Users can explicitly pass in `null` to mean identity matrix
+            ruffle_render::matrix::Matrix::IDENTITY
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_shader_fill(ShaderFill {
+                shader,
+                arguments,
+                matrix,
+            });
         }
     }
 
@@ -219,10 +251,11 @@ pub fn clear<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+    if let Some(display_object) = this.as_display_object() {
+        if let Some(mut draw) = display_object.as_drawing(activation.context.gc_context) {
             draw.clear()
         }
+        set_fill_bitmaps(activation, this, Value::Null)?;
     }
 
     Ok(Value::Undefined)
@@ -838,21 +871,35 @@ pub fn copy_from<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        let source = args
-            .get_object(activation, 0, "sourceGraphics")?
+    if let Some(display_object) = this.as_display_object() {
+        let source_object = args.get_object(activation, 0, "sourceGraphics")?;
+        let source = source_object
             .as_display_object()
             .expect("Bad sourceGraphics");
 
-        let source = source
-            .as_drawing(activation.context.gc_context)
-            .expect("Missing drawing for sourceGraphics");
+        {
+            let source = source
+                .as_drawing(activation.context.gc_context)
+                .expect("Missing drawing for sourceGraphics");
 
-        let mut target_drawing = this
-            .as_drawing(activation.context.gc_context)
-            .expect("Missing drawing for target");
+            let mut target_drawing = display_object
+                .as_drawing(activation.context.gc_context)
+                .expect("Missing drawing for target");
 
-        target_drawing.copy_from(&source);
+            target_drawing.copy_from(&source);
+        }
+
+        let bitmaps = match fill_bitmaps(activation, source_object)? {
+            Some(bitmaps) => {
+                let storage = bitmaps
+                    .as_array_storage()
+                    .expect("_bitmaps is an Array")
+                    .clone();
+                ArrayObject::from_storage(activation, storage)?.into()
+            }
+            None => Value::Null,
+        };
+        set_fill_bitmaps(activation, this, bitmaps)?;
     }
     Ok(Value::Undefined)
 }
@@ -888,13 +935,150 @@ pub fn draw_path<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Draw a quarter of an ellipse, as one corner of a roundrect.
+///
+/// `direction` gives the quadrant of the ellipse that's drawn, as the sign of
+/// each axis. The corner is drawn from its horizontal end to its vertical end
+/// when `from_horizontal` is set, and the other way around otherwise.
+fn draw_round_rect_corner(
+    draw: &mut Drawing,
+    (center_x, center_y): (f64, f64),
+    radius: f64,
+    (direction_x, direction_y): (f64, f64),
+    from_horizontal: bool,
+) {
+    if radius <= 0.0 {
+        return;
+    }
+
+    let point = |(x, y): (f64, f64)| {
+        Point::from_pixels(
+            center_x + radius * direction_x * x,
+            center_y + radius * direction_y * y,
+        )
+    };
+    let ucp = UNIT_CIRCLE_POINTS;
+    let (first, second) = if from_horizontal {
+        ((ucp[1], ucp[2]), (ucp[3], ucp[4]))
+    } else {
+        ((ucp[3], ucp[2]), (ucp[1], ucp[0]))
+    };
+
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: point(first.0),
+        anchor: point(first.1),
+    });
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: point(second.0),
+        anchor: point(second.1),
+    });
+}
+
+/// Draw a roundrect with a different radius for each corner.
+#[allow(clippy::too_many_arguments)]
+fn draw_round_rect_complex_internal(
+    draw: &mut Drawing,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    top_left: f64,
+    top_right: f64,
+    bottom_left: f64,
+    bottom_right: f64,
+) {
+    // Each radius is limited to half of the smaller side of the rectangle.
+    let max_radius = width.min(height) / 2.0;
+    let clamp = |radius: f64| radius.min(max_radius).max(0.0);
+    let (top_left, top_right) = (clamp(top_left), clamp(top_right));
+    let (bottom_left, bottom_right) = (clamp(bottom_left), clamp(bottom_right));
+
+    let right = x + width;
+    let bottom = y + height;
+
+    // Like `drawRoundRect`, we start from the bottom-right corner.
+    draw.draw_command(DrawCommand::MoveTo(Point::from_pixels(
+        right,
+        bottom - bottom_right,
+    )));
+    draw_round_rect_corner(
+        draw,
+        (right - bottom_right, bottom - bottom_right),
+        bottom_right,
+        (1.0, 1.0),
+        true,
+    );
+
+    draw.draw_command(DrawCommand::LineTo(Point::from_pixels(
+        x + bottom_left,
+        bottom,
+    )));
+    draw_round_rect_corner(
+        draw,
+        (x + bottom_left, bottom - bottom_left),
+        bottom_left,
+        (-1.0, 1.0),
+        false,
+    );
+
+    draw.draw_command(DrawCommand::LineTo(Point::from_pixels(x, y + top_left)));
+    draw_round_rect_corner(
+        draw,
+        (x + top_left, y + top_left),
+        top_left,
+        (-1.0, -1.0),
+        true,
+    );
+
+    draw.draw_command(DrawCommand::LineTo(Point::from_pixels(
+        right - top_right,
+        y,
+    )));
+    draw_round_rect_corner(
+        draw,
+        (right - top_right, y + top_right),
+        top_right,
+        (1.0, -1.0),
+        false,
+    );
+
+    draw.draw_command(DrawCommand::LineTo(Point::from_pixels(
+        right,
+        bottom - bottom_right,
+    )));
+}
+
 /// Implements `Graphics.drawRoundRectComplex`
 pub fn draw_round_rect_complex<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawRoundRectComplex");
+    if let Some(this) = this.as_display_object() {
+        let x = args.get_f64(activation, 0)?;
+        let y = args.get_f64(activation, 1)?;
+        let width = args.get_f64(activation, 2)?;
+        let height = args.get_f64(activation, 3)?;
+        let top_left = args.get_f64(activation, 4)?;
+        let top_right = args.get_f64(activation, 5)?;
+        let bottom_left = args.get_f64(activation, 6)?;
+        let bottom_right = args.get_f64(activation, 7)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw_round_rect_complex_internal(
+                &mut draw,
+                x,
+                y,
+                width,
+                height,
+                top_left,
+                top_right,
+                bottom_left,
+                bottom_right,
+            );
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -1098,13 +1282,13 @@ pub fn draw_graphics_data<'gc>(
     {
         //assert_eq!(vector.value_type(), Some(activation.avm2().classes().igraphicsdata));
 
-        let this = this.as_display_object().expect("Bad this");
+        let display_object = this.as_display_object().expect("Bad this");
 
-        if let Some(mut drawing) = this.as_drawing(activation.context.gc_context) {
+        if let Some(mut drawing) = display_object.as_drawing(activation.context.gc_context) {
             for elem in vector.iter() {
                 let obj = elem.coerce_to_object(activation)?;
 
-                handle_igraphics_data(activation, &mut drawing, &obj)?;
+                handle_igraphics_data(activation, this, &mut drawing, &obj)?;
             }
         };
     }
@@ -1118,9 +1302,9 @@ pub fn line_bitmap_style<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        let bitmap = args
-            .get_object(activation, 0, "bitmap")?
+    if let Some(display_object) = this.as_display_object() {
+        let bitmap_object = args.get_object(activation, 0, "bitmap")?;
+        let bitmap = bitmap_object
             .as_bitmap_data()
             .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
         let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
@@ -1145,7 +1329,7 @@ pub fn line_bitmap_style<'gc>(
             Fixed16::from_f64(bitmap.height as f64),
         );
 
-        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+        if let Some(mut draw) = display_object.as_drawing(activation.context.gc_context) {
             let id = draw.add_bitmap(bitmap);
             draw.set_line_fill_style(FillStyle::Bitmap {
                 id,
@@ -1153,6 +1337,7 @@ pub fn line_bitmap_style<'gc>(
                 is_smoothed,
                 is_repeating,
             });
+            add_fill_bitmap(activation, this, &draw, bitmap_object)?;
         }
     }
 
@@ -1162,15 +1347,503 @@ pub fn line_bitmap_style<'gc>(
 /// Implements `Graphics.readGraphicsData`
 pub fn read_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "readGraphicsData");
+    let recurse = args.get_bool(0);
+
+    let mut graphics_data = Vec::new();
+    if let Some(display_object) = this.as_display_object() {
+        read_display_object_graphics_data(
+            activation,
+            display_object,
+            Some(this),
+            ruffle_render::matrix::Matrix::IDENTITY,
+            recurse,
+            &mut graphics_data,
+        )?;
+    }
+
     let value_type = activation.avm2().classes().igraphicsdata;
-    let new_storage = VectorStorage::new(0, false, Some(value_type), activation);
+    let new_storage = VectorStorage::from_values(graphics_data, false, Some(value_type));
     Ok(VectorObject::from_vector(new_storage, activation)?.into())
 }
 
+/// A path of a `Drawing`, copied out so that the drawing isn't borrowed while
+/// `IGraphicsData` objects are being constructed.
+enum GraphicsDataPath {
+    Fill {
+        style: FillStyle,
+        bitmap: Option<BitmapHandle>,
+        commands: Vec<DrawCommand>,
+    },
+    Stroke {
+        style: LineStyle,
+        bitmap: Option<BitmapHandle>,
+        commands: Vec<DrawCommand>,
+    },
+}
+
+/// Appends the `IGraphicsData` of a display object (and optionally its children)
+/// to `output`, with all coordinates transformed by `matrix`.
+fn read_display_object_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+    graphics: Option<Object<'gc>>,
+    matrix: ruffle_render::matrix::Matrix,
+    recurse: bool,
+    output: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let mut paths = Vec::new();
+    let mut has_shader_fill = false;
+    if let Some(drawing) = display_object.as_drawing(activation.context.gc_context) {
+        let renderer = &mut *activation.context.renderer;
+        let mut bitmap = |style: &FillStyle| match style {
+            FillStyle::Bitmap { id, .. } => drawing.bitmap_handle(*id, &mut *renderer),
+            _ => None,
+        };
+        for path in drawing.paths() {
+            match path {
+                DrawPath::Fill {
+                    style: ruffle_render::shape_utils::FillStyle::Swf(style),
+                    commands,
+                    ..
+                } => paths.push(GraphicsDataPath::Fill {
+                    style: style.clone(),
+                    bitmap: bitmap(style),
+                    commands,
+                }),
+                DrawPath::Fill {
+                    style: ruffle_render::shape_utils::FillStyle::Shader(_),
+                    ..
+                } => has_shader_fill = true,
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => paths.push(GraphicsDataPath::Stroke {
+                    style: style.clone(),
+                    bitmap: bitmap(style.fill_style()),
+                    commands,
+                }),
+            }
+        }
+    }
+
+    if has_shader_fill {
+        avm2_stub_method!(
+            activation,
+            "flash.display.Graphics",
+            "readGraphicsData",
+            "with shader fills"
+        );
+    }
+
+    let graphics = match graphics {
+        Some(graphics) => Some(graphics),
+        None => graphics_object(activation, display_object),
+    };
+
+    let mut is_stroking = false;
+    for path in paths {
+        match path {
+            GraphicsDataPath::Fill {
+                style,
+                bitmap,
+                commands,
+            } => {
+                let Some(fill) =
+                    fill_style_to_object(activation, graphics, &style, bitmap, matrix)?
+                else {
+                    continue;
+                };
+                if is_stroking {
+                    // Strokes and fills are separate paths in a drawing, so make sure
+                    // that the stroke doesn't also apply to this fill.
+                    let no_stroke = activation
+                        .avm2()
+                        .classes()
+                        .graphicsstroke
+                        .construct(activation, &[])?;
+                    output.push(no_stroke.into());
+                    is_stroking = false;
+                }
+                output.push(fill.into());
+                output.push(commands_to_graphics_path(activation, &commands, matrix)?.into());
+                let end_fill = activation
+                    .avm2()
+                    .classes()
+                    .graphicsendfill
+                    .construct(activation, &[])?;
+                output.push(end_fill.into());
+            }
+            GraphicsDataPath::Stroke {
+                style,
+                bitmap,
+                commands,
+            } => {
+                let fill =
+                    fill_style_to_object(activation, graphics, style.fill_style(), bitmap, matrix)?;
+                let stroke = line_style_to_object(activation, &style, fill)?;
+                output.push(stroke.into());
+                output.push(commands_to_graphics_path(activation, &commands, matrix)?.into());
+                is_stroking = true;
+            }
+        }
+    }
+
+    if recurse {
+        if let Some(container) = display_object.as_container() {
+            for child in container.iter_render_list() {
+                let child_matrix = matrix * *child.base().matrix();
+                read_display_object_graphics_data(
+                    activation,
+                    child,
+                    None,
+                    child_matrix,
+                    recurse,
+                    output,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The `Graphics` object of a display object, if it has been created.
+fn graphics_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+) -> Option<Object<'gc>> {
+    let object = display_object.object2().as_object()?;
+    // Only `Shape` and `Sprite` have a `Graphics` object, so a missing property isn't an error.
+    object
+        .get_property(
+            &Multiname::new(activation.avm2().flash_display_internal, "_graphics"),
+            activation,
+        )
+        .ok()?
+        .as_object()
+}
+
+/// The `BitmapData` objects used by bitmap fills of a `Graphics` object.
+fn fill_bitmaps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    Ok(graphics
+        .get_property(
+            &Multiname::new(activation.avm2().flash_display_internal, "_bitmaps"),
+            activation,
+        )?
+        .as_object())
+}
+
+fn set_fill_bitmaps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+    bitmaps: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    graphics.set_property(
+        &Multiname::new(activation.avm2().flash_display_internal, "_bitmaps"),
+        bitmaps,
+        activation,
+    )
+}
+
+/// Remembers a `BitmapData` used by a bitmap fill, so that `readGraphicsData`
+/// can return the same object.
+///
+/// The `BitmapData` objects whose bitmaps aren't used by `drawing` anymore are forgotten.
+fn add_fill_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+    drawing: &Drawing,
+    bitmap: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let bitmaps = match fill_bitmaps(activation, graphics)? {
+        Some(bitmaps) => bitmaps,
+        None => {
+            let bitmaps = ArrayObject::empty(activation)?;
+            set_fill_bitmaps(activation, graphics, bitmaps.into())?;
+            bitmaps
+        }
+    };
+
+    let known: Vec<_> = bitmaps
+        .as_array_storage()
+        .expect("_bitmaps is an Array")
+        .iter()
+        .flatten()
+        .filter_map(|known| known.as_object())
+        .collect();
+    let mut used = Vec::with_capacity(known.len() + 1);
+    for known in known {
+        if Object::ptr_eq(known, bitmap) {
+            continue;
+        }
+        if let Some(bitmap_data) = known.as_bitmap_data() {
+            let handle = bitmap_data
+                .bitmap_handle(activation.context.gc_context, activation.context.renderer);
+            if drawing.used_bitmaps().any(|used| *used == handle) {
+                used.push(known.into());
+            }
+        }
+    }
+    used.push(bitmap.into());
+
+    *bitmaps
+        .as_array_storage_mut(activation.context.gc_context)
+        .expect("_bitmaps is an Array") = ArrayStorage::from_args(&used);
+
+    Ok(())
+}
+
+/// Finds the `BitmapData` object that a bitmap fill was created from.
+fn find_fill_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Option<Object<'gc>>,
+    handle: &BitmapHandle,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    let Some(bitmaps) = graphics
+        .map(|g| fill_bitmaps(activation, g))
+        .transpose()?
+        .flatten()
+    else {
+        return Ok(None);
+    };
+    let bitmaps: Vec<_> = bitmaps
+        .as_array_storage()
+        .expect("_bitmaps is an Array")
+        .iter()
+        .flatten()
+        .filter_map(|bitmap| bitmap.as_object())
+        .collect();
+
+    for bitmap in bitmaps {
+        if let Some(bitmap_data) = bitmap.as_bitmap_data() {
+            let bitmap_handle = bitmap_data
+                .bitmap_handle(activation.context.gc_context, activation.context.renderer);
+            if &bitmap_handle == handle {
+                return Ok(Some(bitmap));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Converts a fill style back into an `IGraphicsFill`, transformed by `matrix`.
+///
+/// Returns `None` for bitmap fills whose `BitmapData` is no longer known.
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Option<Object<'gc>>,
+    style: &FillStyle,
+    bitmap: Option<BitmapHandle>,
+    matrix: ruffle_render::matrix::Matrix,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    let fill = match style {
+        FillStyle::Color(color) => activation.avm2().classes().graphicssolidfill.construct(
+            activation,
+            &[color.to_rgb().into(), (f64::from(color.a) / 255.0).into()],
+        )?,
+        FillStyle::LinearGradient(gradient) => {
+            gradient_to_object(activation, "linear", gradient, 0.0, matrix)?
+        }
+        FillStyle::RadialGradient(gradient) => {
+            gradient_to_object(activation, "radial", gradient, 0.0, matrix)?
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => gradient_to_object(activation, "radial", gradient, focal_point.to_f64(), matrix)?,
+        FillStyle::Bitmap {
+            matrix: bitmap_matrix,
+            is_smoothed,
+            is_repeating,
+            ..
+        } => {
+            let Some(bitmap) = bitmap else {
+                return Ok(None);
+            };
+            let Some(bitmap_data) = find_fill_bitmap(activation, graphics, &bitmap)? else {
+                return Ok(None);
+            };
+            // Bitmap fills are stored scaled from pixels to twips, see `beginBitmapFill`.
+            let scale_matrix = ruffle_render::matrix::Matrix::scale(
+                1.0 / Twips::TWIPS_PER_PIXEL as f32,
+                1.0 / Twips::TWIPS_PER_PIXEL as f32,
+            );
+            let bitmap_matrix =
+                matrix * ruffle_render::matrix::Matrix::from(*bitmap_matrix) * scale_matrix;
+            let bitmap_matrix = matrix_to_object(bitmap_matrix, activation)?;
+            activation.avm2().classes().graphicsbitmapfill.construct(
+                activation,
+                &[
+                    bitmap_data.into(),
+                    bitmap_matrix,
+                    (*is_repeating).into(),
+                    (*is_smoothed).into(),
+                ],
+            )?
+        }
+    };
+
+    Ok(Some(fill))
+}
+
+fn gradient_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gradient_type: &'static str,
+    gradient: &Gradient,
+    focal_point: f64,
+    matrix: ruffle_render::matrix::Matrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let colors = ArrayObject::from_storage(
+        activation,
+        gradient
+            .records
+            .iter()
+            .map(|record| Value::from(record.color.to_rgb()))
+            .collect(),
+    )?;
+    let alphas = ArrayObject::from_storage(
+        activation,
+        gradient
+            .records
+            .iter()
+            .map(|record| Value::from(f64::from(record.color.a) / 255.0))
+            .collect(),
+    )?;
+    let ratios = ArrayObject::from_storage(
+        activation,
+        gradient
+            .records
+            .iter()
+            .map(|record| Value::from(record.ratio))
+            .collect(),
+    )?;
+    let gradient_matrix = matrix * ruffle_render::matrix::Matrix::from(gradient.matrix);
+    let gradient_matrix = matrix_to_object(gradient_matrix, activation)?;
+    let spread_method = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation_method = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    activation.avm2().classes().graphicsgradientfill.construct(
+        activation,
+        &[
+            gradient_type.into(),
+            colors.into(),
+            alphas.into(),
+            ratios.into(),
+            gradient_matrix,
+            spread_method.into(),
+            interpolation_method.into(),
+            focal_point.into(),
+        ],
+    )
+}
+
+/// Converts a line style back into a `GraphicsStroke`.
+fn line_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &LineStyle,
+    fill: Option<Object<'gc>>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+        (true, true) => "normal",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+        (false, false) => "none",
+    };
+    let caps = match style.start_cap() {
+        LineCapStyle::None => "none",
+        LineCapStyle::Round => "round",
+        LineCapStyle::Square => "square",
+    };
+    let (joints, miter_limit) = match style.join_style() {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+    };
+
+    activation.avm2().classes().graphicsstroke.construct(
+        activation,
+        &[
+            style.width().to_pixels().into(),
+            style.is_pixel_hinted().into(),
+            scale_mode.into(),
+            caps.into(),
+            joints.into(),
+            miter_limit.into(),
+            fill.map_or(Value::Null, Value::from),
+        ],
+    )
+}
+
+/// Converts drawing commands back into a `GraphicsPath`, transformed by `matrix`.
+fn commands_to_graphics_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    commands: &[DrawCommand],
+    matrix: ruffle_render::matrix::Matrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let mut path_commands = Vec::with_capacity(commands.len());
+    let mut data = Vec::with_capacity(commands.len() * 2);
+    let mut push_point = |point: Point<Twips>| {
+        let point = matrix * point;
+        data.push(Value::from(point.x.to_pixels()));
+        data.push(Value::from(point.y.to_pixels()));
+    };
+
+    // These values are from `GraphicsPathCommand`.
+    for command in commands {
+        match *command {
+            DrawCommand::MoveTo(point) => {
+                path_commands.push(Value::from(1));
+                push_point(point);
+            }
+            DrawCommand::LineTo(point) => {
+                path_commands.push(Value::from(2));
+                push_point(point);
+            }
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                path_commands.push(Value::from(3));
+                push_point(control);
+                push_point(anchor);
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => {
+                path_commands.push(Value::from(6));
+                push_point(control_a);
+                push_point(control_b);
+                push_point(anchor);
+            }
+        }
+    }
+
+    let int_class = activation.avm2().classes().int;
+    let path_commands = VectorStorage::from_values(path_commands, false, Some(int_class));
+    let path_commands = VectorObject::from_vector(path_commands, activation)?;
+
+    let number_class = activation.avm2().classes().number;
+    let data = VectorStorage::from_values(data, false, Some(number_class));
+    let data = VectorObject::from_vector(data, activation)?;
+
+    activation.avm2().classes().graphicspath.construct(
+        activation,
+        &[path_commands.into(), data.into(), "evenOdd".into()],
+    )
+}
+
 fn read_point<'gc>(
     activation: &mut Activation<'_, 'gc>,
     data: &VectorStorage<'gc>,
@@ -1295,13 +1968,14 @@ fn process_commands<'gc>(
 
 fn handle_igraphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<(), Error<'gc>> {
     let class = obj.instance_of().expect("No class");
 
     if class == activation.avm2().classes().graphicsbitmapfill {
-        let style = handle_bitmap_fill(activation, drawing, obj)?;
+        let style = handle_bitmap_fill(activation, graphics, drawing, obj)?;
         drawing.set_fill_style(Some(style));
     } else if class == activation.avm2().classes().graphicsendfill {
         drawing.set_fill_style(None);
@@ -1355,7 +2029,7 @@ fn handle_igraphics_data<'gc>(
                     .get_public_property("fill", activation)?
                     .coerce_to_object(activation)?;
 
-                handle_igraphics_fill(activation, drawing, &fill)?
+                handle_igraphics_fill(activation, graphics, drawing, &fill)?
             };
 
             let joints = obj
@@ -1525,13 +2199,14 @@ fn handle_graphics_triangle_path<'gc>(
 
 fn handle_igraphics_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<Option<FillStyle>, Error<'gc>> {
     let class = obj.instance_of().expect("No class");
 
     if class == activation.avm2().classes().graphicsbitmapfill {
-        let style = handle_bitmap_fill(activation, drawing, obj)?;
+        let style = handle_bitmap_fill(activation, graphics, drawing, obj)?;
         Ok(Some(style))
     } else if class == activation.avm2().classes().graphicsendfill {
         Ok(None)
@@ -1658,12 +2333,15 @@ fn handle_gradient_fill<'gc>(
 
 fn handle_bitmap_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<FillStyle, Error<'gc>> {
-    let bitmap_data = obj
+    let bitmap_object = obj
         .get_public_property("bitmapData", activation)?
-        .coerce_to_object(activation)?
+        .coerce_to_object(activation)?;
+    add_fill_bitmap(activation, graphics, drawing, bitmap_object)?;
+    let bitmap_data = bitmap_object
         .as_bitmap_data()
         .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
 
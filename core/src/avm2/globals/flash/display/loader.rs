@@ -63,6 +63,19 @@ pub fn load<'gc>(
     let url_request = args.get_object(activation, 0, "request")?;
     let context = args.try_get_object(activation, 1);
 
+    let request = request_from_url_request(activation, url_request)?;
+    load_request(activation, this, request, context)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Start loading the content of a `Loader` from the given request.
+pub fn load_request<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    request: Request,
+    context: Option<Object<'gc>>,
+) -> Result<(), Error<'gc>> {
     // This is a dummy MovieClip, which will get overwritten in `Loader`
     let content = MovieClip::new(
         Arc::new(SwfMovie::empty(activation.context.swf.version())),
@@ -93,8 +106,6 @@ pub fn load<'gc>(
             activation.context.gc_context,
         );
 
-    let request = request_from_url_request(activation, url_request)?;
-
     let url = request.url().to_string();
     let future = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone(),
@@ -111,7 +122,7 @@ pub fn load<'gc>(
     );
     activation.context.navigator.spawn_future(future);
 
-    Ok(Value::Undefined)
+    Ok(())
 }
 
 pub fn request_from_url_request<'gc>(
@@ -1,5 +1,4 @@
 package flash.display3D {
-    import __ruffle__.stub_method;
     import flash.utils.ByteArray;
     
     [Ruffle(InstanceAllocator)]
@@ -7,8 +6,6 @@ package flash.display3D {
         public native function uploadFromByteArray(data:ByteArray, byteArrayOffset:int, startVertex:int, numVertices:int):void
         public native function uploadFromVector(data:Vector.<Number>, startVertex:int, numVertices:int):void
 
-        public function dispose():void {
-            stub_method("flash.display3D.VertexBuffer3D", "dispose");
-        }
+        public native function dispose():void;
     }
 }
\ No newline at end of file
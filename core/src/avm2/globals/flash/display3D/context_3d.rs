@@ -1,4 +1,4 @@
-use crate::avm2::error::{argument_error, error, make_error_2008};
+use crate::avm2::error::{argument_error, error, make_error_2008, make_error_3694};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
 use crate::avm2::TObject;
@@ -7,8 +7,8 @@ use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
 use ruffle_render::backend::Context3DWrapMode;
 use ruffle_render::backend::{
-    BufferUsage, Context3DBlendFactor, Context3DCompareMode, Context3DTextureFormat,
-    Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
+    BufferUsage, Context3DBlendFactor, Context3DCompareMode, Context3DStencilAction,
+    Context3DTextureFormat, Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
 };
 use ruffle_render::backend::{Context3DProfile, Context3DTextureFilter};
 use swf::{Rectangle, Twips};
@@ -19,6 +19,9 @@ pub fn create_index_buffer<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // FIXME - get bufferUsage and pass it through
         let num_indices = args.get_u32(activation, 0)?;
         return context.create_index_buffer(num_indices, activation);
@@ -32,6 +35,9 @@ pub fn create_vertex_buffer<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // FIXME - get bufferUsage and pass it through
         let num_vertices = args.get_u32(activation, 0)?;
         let data_32_per_vertex = args.get_u32(activation, 1)?;
@@ -56,6 +62,9 @@ pub fn configure_back_buffer<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let width = args.get_u32(activation, 0)?;
         let height = args.get_u32(activation, 1)?;
         let anti_alias = args.get_u32(activation, 2)?;
@@ -130,6 +139,9 @@ pub fn set_vertex_buffer_at<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let index = args.get_u32(activation, 0)?;
         let buffer = if matches!(args[1], Value::Null) {
             None
@@ -155,14 +167,17 @@ pub fn set_vertex_buffer_at<'gc>(
                 )?));
             };
 
-            Some((
-                args.get(1)
-                    .unwrap_or(&Value::Undefined)
-                    .coerce_to_object(activation)?
-                    .as_vertex_buffer()
-                    .unwrap(),
-                format,
-            ))
+            let buffer = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation)?
+                .as_vertex_buffer()
+                .unwrap();
+            if buffer.is_disposed() {
+                return Err(make_error_3694(activation));
+            }
+
+            Some((buffer, format))
         };
 
         let buffer_offset = args.get_u32(activation, 2)?;
@@ -178,6 +193,9 @@ pub fn create_program<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         return context.create_program(activation);
     }
     Ok(Value::Undefined)
@@ -189,9 +207,15 @@ pub fn set_program<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let program = args
             .try_get_object(activation, 0)
             .map(|p| p.as_program_3d().unwrap());
+        if program.is_some_and(|p| p.is_disposed()) {
+            return Err(make_error_3694(activation));
+        }
         context.set_program(program);
     }
     Ok(Value::Undefined)
@@ -203,12 +227,18 @@ pub fn draw_triangles<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let index_buffer = args
             .get(0)
             .unwrap_or(&Value::Undefined)
             .coerce_to_object(activation)?
             .as_index_buffer()
             .unwrap();
+        if index_buffer.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
 
         let first_index = args.get_u32(activation, 1)?;
         let num_triangles = args.get_u32(activation, 2)? as i32;
@@ -224,17 +254,23 @@ pub fn present<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         context.present(activation)?;
     }
     Ok(Value::Undefined)
 }
 
 pub fn get_profile<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         return match context.with_context_3d(|context| context.profile()) {
             Context3DProfile::Baseline => Ok("baseline".into()),
             Context3DProfile::BaselineConstrained => Ok("baselineConstrained".into()),
@@ -253,6 +289,9 @@ pub fn set_culling<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let culling = args.get_string(activation, 0)?;
 
         let culling = if &*culling == b"none" {
@@ -279,6 +318,9 @@ pub fn set_program_constants_from_matrix<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let program_type = args.get_string(activation, 0)?;
 
         let is_vertex = if &*program_type == b"vertex" {
@@ -334,6 +376,9 @@ pub fn set_program_constants_from_vector<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let program_type = args.get_string(activation, 0)?;
 
         let program_type = if &*program_type == b"vertex" {
@@ -383,6 +428,9 @@ pub fn clear<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let red = args[0].as_number(activation.context.gc_context)?;
         let green = args[1].as_number(activation.context.gc_context)?;
@@ -402,6 +450,9 @@ pub fn create_texture<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let width = args[0].as_integer(activation.context.gc_context)? as u32;
         let height = args[1].as_integer(activation.context.gc_context)? as u32;
@@ -435,6 +486,9 @@ pub fn create_rectangle_texture<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let width = args[0].as_integer(activation.context.gc_context)? as u32;
         let height = args[1].as_integer(activation.context.gc_context)? as u32;
@@ -471,6 +525,9 @@ pub fn create_cube_texture<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let size = args[0].as_integer(activation.context.gc_context)? as u32;
         let format = args[1].coerce_to_string(activation)?;
@@ -503,6 +560,9 @@ pub fn set_texture_at<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let sampler = args[0].as_integer(activation.context.gc_context)? as u32;
         let mut cube = false;
@@ -518,7 +578,11 @@ pub fn set_texture_at<'gc>(
                     .inner_class_definition(),
                 &mut activation.context,
             );
-            Some(obj.as_texture().unwrap().handle())
+            let texture = obj.as_texture().unwrap();
+            if texture.is_disposed() {
+                return Err(make_error_3694(activation));
+            }
+            Some(texture.handle())
         };
         context.set_texture_at(sampler, texture, cube);
     }
@@ -526,11 +590,14 @@ pub fn set_texture_at<'gc>(
 }
 
 pub fn set_color_mask<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let red = args[0].coerce_to_boolean();
         let green = args[1].coerce_to_boolean();
@@ -547,6 +614,9 @@ pub fn set_depth_test<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let depth_mask = args[0].coerce_to_boolean();
         let pass_compare_mode = args[1].coerce_to_string(activation)?;
@@ -567,6 +637,9 @@ pub fn set_blend_factors<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let source_factor = args[0].coerce_to_string(activation)?;
         let destination_factor = args[1].coerce_to_string(activation)?;
@@ -594,10 +667,16 @@ pub fn set_render_to_texture<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let context = this.as_context_3d().unwrap();
+    if context.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
     let texture = args
         .get_object(activation, 0, "texture")?
         .as_texture()
         .unwrap();
+    if texture.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
     let enable_depth_and_stencil = args.get_bool(1);
     let anti_alias = args.get_u32(activation, 2)?;
     let surface_selector = args.get_u32(activation, 3)?;
@@ -640,11 +719,14 @@ pub fn set_render_to_texture<'gc>(
 }
 
 pub fn set_render_to_back_buffer<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let context = this.as_context_3d().unwrap();
+    if context.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
     context.set_render_to_back_buffer();
     Ok(Value::Undefined)
 }
@@ -655,6 +737,9 @@ pub fn set_sampler_state_at<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(context) = this.as_context_3d() {
+        if context.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         // This is a native method, so all of the arguments have been checked and coerced for us
         let sampler = args[0].as_integer(activation.context.gc_context)? as u32;
         let wrap = args[1].coerce_to_string(activation)?;
@@ -702,6 +787,9 @@ pub fn set_scissor_rectangle<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let context3d = this.as_context_3d().unwrap();
+    if context3d.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
     let rectangle = args.try_get_object(activation, 0);
     let rectangle = if let Some(rectangle) = rectangle {
         let x = rectangle
@@ -730,14 +818,79 @@ pub fn set_scissor_rectangle<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn set_stencil_actions<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let context = this.as_context_3d().unwrap();
+    if context.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
+
+    let triangle_face = args.get_string(activation, 0)?;
+    let compare_mode = args.get_string(activation, 1)?;
+    let action_on_both_pass = args.get_string(activation, 2)?;
+    let action_on_depth_fail = args.get_string(activation, 3)?;
+    let action_on_depth_pass_stencil_fail = args.get_string(activation, 4)?;
+
+    let triangle_face = Context3DTriangleFace::from_wstr(&triangle_face)
+        .ok_or_else(|| make_error_2008(activation, "triangleFace"))?;
+    let compare_mode = Context3DCompareMode::from_wstr(&compare_mode)
+        .ok_or_else(|| make_error_2008(activation, "compareMode"))?;
+    let action_on_both_pass = Context3DStencilAction::from_wstr(&action_on_both_pass)
+        .ok_or_else(|| make_error_2008(activation, "actionOnBothPass"))?;
+    let action_on_depth_fail = Context3DStencilAction::from_wstr(&action_on_depth_fail)
+        .ok_or_else(|| make_error_2008(activation, "actionOnDepthFail"))?;
+    let action_on_depth_pass_stencil_fail =
+        Context3DStencilAction::from_wstr(&action_on_depth_pass_stencil_fail)
+            .ok_or_else(|| make_error_2008(activation, "actionOnDepthPassStencilFail"))?;
+
+    context.set_stencil_actions(
+        triangle_face,
+        compare_mode,
+        action_on_both_pass,
+        action_on_depth_fail,
+        action_on_depth_pass_stencil_fail,
+    );
+    Ok(Value::Undefined)
+}
+
+pub fn set_stencil_reference_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let context = this.as_context_3d().unwrap();
+    if context.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
+
+    // The stencil buffer is 8 bits, so only the low byte of each value is used
+    let reference_value = args.get_u32(activation, 0)? & 0xFF;
+    let read_mask = args.get_u32(activation, 1)? & 0xFF;
+    let write_mask = args.get_u32(activation, 2)? & 0xFF;
+
+    context.set_stencil_reference_value(reference_value, read_mask, write_mask);
+    Ok(Value::Undefined)
+}
+
 pub fn dispose<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display3D.Context3D", "dispose");
-    this.as_context_3d()
-        .unwrap()
+    let context = this.as_context_3d().unwrap();
+    if args.get_bool(0) {
+        avm2_stub_method!(
+            activation,
+            "flash.display3D.Context3D",
+            "dispose",
+            "with recreate"
+        );
+    }
+    context.dispose();
+    context
         .stage3d()
         .set_context3d(None, activation.context.gc_context);
     Ok(Value::Undefined)
@@ -1,3 +1,4 @@
+use crate::avm2::error::make_error_3694;
 use crate::avm2::object::{ClassObject, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
@@ -17,6 +18,9 @@ pub fn upload_from_byte_array<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(index_buffer) = this.as_index_buffer() {
+        if index_buffer.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let byte_array = args.get_object(activation, 0, "byteArray")?;
         let byte_array = byte_array
             .as_bytearray()
@@ -47,6 +51,9 @@ pub fn upload_from_vector<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(index_buffer) = this.as_index_buffer() {
+        if index_buffer.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let vector = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -80,3 +87,14 @@ pub fn upload_from_vector<'gc>(
     }
     Ok(Value::Undefined)
 }
+
+pub fn dispose<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(index_buffer) = this.as_index_buffer() {
+        index_buffer.dispose();
+    }
+    Ok(Value::Undefined)
+}
@@ -1,3 +1,4 @@
+use crate::avm2::error::make_error_3694;
 use crate::avm2::Activation;
 
 use crate::avm2::TObject;
@@ -10,6 +11,9 @@ pub fn upload<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this.as_program_3d() {
+        if this.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let vertex_agal = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -33,3 +37,14 @@ pub fn upload<'gc>(
     }
     Ok(Value::Undefined)
 }
+
+pub fn dispose<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(program) = this.as_program_3d() {
+        program.dispose();
+    }
+    Ok(Value::Undefined)
+}
@@ -1,5 +1,6 @@
 use ruffle_render::backend::Context3DTextureFormat;
 
+use crate::avm2::error::make_error_3694;
 use crate::avm2::globals::flash::display3D::textures::atf_jpegxr::do_compressed_upload;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
@@ -73,6 +74,9 @@ pub fn upload_compressed_texture_from_byte_array<'gc>(
         return Ok(Value::Undefined);
     }
 
+    if texture.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
     do_compressed_upload(activation, texture, data, byte_array_offset, true)?;
     Ok(Value::Undefined)
 }
@@ -83,6 +87,9 @@ pub fn upload_from_bitmap_data<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(texture) = this.as_texture() {
+        if texture.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         if let Some(source) = args[0].coerce_to_object(activation)?.as_bitmap_data() {
             let side = args[1].coerce_to_u32(activation)?;
             let mip_level = args[2].coerce_to_u32(activation)?;
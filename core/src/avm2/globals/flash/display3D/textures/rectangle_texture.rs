@@ -1,3 +1,4 @@
+use crate::avm2::error::make_error_3694;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
 use crate::avm2::TObject;
@@ -25,6 +26,9 @@ pub fn upload_from_bitmap_data<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(texture) = this.as_texture() {
+        if texture.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         if let Some(source) = args[0].coerce_to_object(activation)?.as_bitmap_data() {
             texture.context3d().copy_bitmapdata_to_texture(
                 source.sync(activation.context.renderer),
@@ -3,6 +3,7 @@ use gc_arena::GcCell;
 use ruffle_render::backend::Context3DTextureFormat;
 
 use super::atf_jpegxr::do_compressed_upload;
+use crate::avm2::error::make_error_3694;
 use crate::avm2::object::TextureObject;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
@@ -22,6 +23,9 @@ pub fn do_copy<'gc>(
     side: u32,
     mip_level: u32,
 ) -> Result<(), Error<'gc>> {
+    if texture.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
     if mip_level != 0 {
         avm2_stub_method!(
             activation,
@@ -92,6 +96,9 @@ pub fn upload_compressed_texture_from_byte_array_internal<'gc>(
         return Ok(Value::Undefined);
     }
 
+    if texture.is_disposed() {
+        return Err(make_error_3694(activation));
+    }
     do_compressed_upload(activation, texture, data, byte_array_offset, false)?;
 
     Ok(Value::Undefined)
@@ -117,6 +124,9 @@ pub fn upload_from_bitmap_data<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(texture) = this.as_texture() {
+        if texture.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         if let Some(source) = args[0].coerce_to_object(activation)?.as_bitmap_data() {
             let mip_level = args[1].coerce_to_u32(activation)?;
             if mip_level == 0 {
@@ -1,3 +1,4 @@
+use crate::avm2::error::make_error_3694;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
 use crate::avm2::ClassObject;
@@ -18,6 +19,9 @@ pub fn upload_from_byte_array<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(vertex_buffer) = this.as_vertex_buffer() {
+        if vertex_buffer.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let byte_array = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -55,6 +59,9 @@ pub fn upload_from_vector<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(vertex_buffer) = this.as_vertex_buffer() {
+        if vertex_buffer.is_disposed() {
+            return Err(make_error_3694(activation));
+        }
         let vector = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -84,3 +91,14 @@ pub fn upload_from_vector<'gc>(
     }
     Ok(Value::Undefined)
 }
+
+pub fn dispose<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(vertex_buffer) = this.as_vertex_buffer() {
+        vertex_buffer.dispose();
+    }
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,32 @@
+package flash.events
+{
+    import flash.text.ime.IIMEClient;
+
+    public class IMEEvent extends TextEvent
+    {
+        // Defines the value of the type property of an imeComposition event object.
+        public static const IME_COMPOSITION:String = "imeComposition";
+
+        // To handle IME text input, the receiver must set the imeClient field of the event to an object that implements the IIMEClient interface.
+        public static const IME_START_COMPOSITION:String = "imeStartComposition";
+
+        // Specifies an object that implements the IMEClient interface.
+        public var imeClient:IIMEClient;
+
+        public function IMEEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false, text:String = "", imeClient:IIMEClient = null)
+        {
+            super(type, bubbles, cancelable, text);
+            this.imeClient = imeClient;
+        }
+
+        override public function clone():Event
+        {
+            return new IMEEvent(this.type, this.bubbles, this.cancelable, this.text, this.imeClient);
+        }
+
+        override public function toString():String
+        {
+            return this.formatToString("IMEEvent", "type", "bubbles", "cancelable", "eventPhase", "text");
+        }
+    }
+}
@@ -405,7 +405,38 @@ package flash.geom {
 		}
 
 		public function pointAt(pos:Vector3D, at:Vector3D = null, up:Vector3D = null):void {
-			stub_method("flash.geom.Matrix3D", "pointAt");
+			if (at == null) {
+				at = new Vector3D(0, 0, -1);
+			}
+			if (up == null) {
+				up = new Vector3D(0, -1, 0);
+			}
+
+			var position:Vector3D = this.position;
+			var direction:Vector3D = pos.subtract(position);
+			if (direction.length == 0) {
+				return;
+			}
+
+			var components:Vector.<Vector3D> = decompose();
+			var scale:Vector3D = components[2];
+
+			// Rotate the local frame given by 'at' and 'up' onto the frame pointing at 'pos',
+			// keeping 'up' as close as possible to its original direction.
+			var local:Vector.<Vector3D> = orthonormalFrame(at, up);
+			var target:Vector.<Vector3D> = orthonormalFrame(direction, up);
+			identity();
+			for (var column:int = 0; column < 3; column++) {
+				for (var row:int = 0; row < 3; row++) {
+					var value:Number = 0;
+					for (var i:int = 0; i < 3; i++) {
+						value += component(target[i], row) * component(local[i], column);
+					}
+					this._rawData[column * 4 + row] = value;
+				}
+			}
+			prependScale(scale.x, scale.y, scale.z);
+			appendTranslation(position.x, position.y, position.z);
 		}
 
 		// Based on OpenFL: https://github.com/openfl/openfl/blob/971a4c9e43b5472fd84d73920a2b7c1b3d8d9257/src/openfl/geom/Matrix3D.hx#L1437
@@ -734,6 +765,25 @@ package flash.geom {
 }
 
 import flash.geom.Orientation3D;
+import flash.geom.Vector3D;
+
+// Returns the right, up and forward axes of the frame looking towards 'forward'
+function orthonormalFrame(forward:Vector3D, up:Vector3D):Vector.<Vector3D> {
+	var f:Vector3D = forward.clone();
+	f.normalize();
+	var u:Vector3D = up.subtract(new Vector3D(f.x * up.dotProduct(f), f.y * up.dotProduct(f), f.z * up.dotProduct(f)));
+	if (u.length == 0) {
+		// 'up' is parallel to 'forward', so pick any perpendicular direction
+		u = Math.abs(f.x) < 0.9 ? new Vector3D(0, -f.z, f.y) : new Vector3D(-f.z, 0, f.x);
+	}
+	u.normalize();
+	var r:Vector3D = u.crossProduct(f);
+	return Vector.<Vector3D>([r, u, f]);
+}
+
+function component(v:Vector3D, index:int):Number {
+	return index == 0 ? v.x : (index == 1 ? v.y : v.z);
+}
 
 function checkOrientation(orientationStyle:String) {
 	if (!(orientationStyle == Orientation3D.AXIS_ANGLE || orientationStyle == Orientation3D.EULER_ANGLES || orientationStyle == Orientation3D.QUATERNION)) {
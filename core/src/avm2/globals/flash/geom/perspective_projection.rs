@@ -0,0 +1,60 @@
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::Multiname;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::display_object::PerspectiveProjection;
+
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let projection = PerspectiveProjection::for_stage(activation.context.stage.stage_size());
+    let center = activation.avm2().classes().point.construct(
+        activation,
+        &[
+            projection.projection_center.0.into(),
+            projection.projection_center.1.into(),
+        ],
+    )?;
+    this.set_property(
+        &Multiname::new(activation.avm2().flash_geom_internal, "_projectionCenter"),
+        center.into(),
+        activation,
+    )?;
+    Ok(Value::Undefined)
+}
+
+pub fn get_focal_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let field_of_view = this
+        .get_property(
+            &Multiname::new(activation.avm2().flash_geom_internal, "_fieldOfView"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+    let projection = PerspectiveProjection {
+        field_of_view,
+        projection_center: (0.0, 0.0),
+    };
+    let stage_width = activation.context.stage.stage_size().0;
+    Ok(projection.focal_length(stage_width).into())
+}
+
+pub fn set_focal_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let focal_length = args.get_f64(activation, 0)?;
+    let stage_width = f64::from(activation.context.stage.stage_size().0);
+    let field_of_view = (stage_width / 2.0 / focal_length).atan().to_degrees() * 2.0;
+    this.set_property(
+        &Multiname::new(activation.avm2().flash_geom_internal, "_fieldOfView"),
+        field_of_view.into(),
+        activation,
+    )?;
+    Ok(Value::Undefined)
+}
@@ -1,9 +1,12 @@
+use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{PerspectiveProjection, TDisplayObject};
 use crate::prelude::{DisplayObject, Matrix, Twips};
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
 
@@ -64,7 +67,12 @@ pub fn get_matrix<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let dobj = get_display_object(this, activation)?;
+    // Objects with a 3D transform only expose it through `matrix3D`
+    if dobj.base().transform_3d().is_some() {
+        return Ok(Value::Null);
+    }
+    let matrix = *dobj.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -75,6 +83,7 @@ pub fn set_matrix<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
     let dobj = get_display_object(this, activation)?;
+    dobj.set_matrix3d(activation.context.gc_context, None);
     dobj.set_matrix(activation.context.gc_context, matrix);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
@@ -247,6 +256,199 @@ pub fn object_to_matrix<'gc>(
     Ok(Matrix { a, b, c, d, tx, ty })
 }
 
+pub fn get_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    match dobj.matrix3d(activation.context.gc_context) {
+        Some(matrix) => matrix3d_to_object(matrix, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_matrix3d(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_matrix3d(activation.context.gc_context, matrix);
+    Ok(Value::Undefined)
+}
+
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = dobj.perspective_projection().or_else(|| {
+        // The root always has a projection, centered on the stage
+        dobj.is_root()
+            .then(|| PerspectiveProjection::for_stage(activation.context.stage.stage_size()))
+    });
+    match projection {
+        Some(projection) => perspective_projection_to_object(projection, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_perspective_projection(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
+    Ok(Value::Undefined)
+}
+
+pub fn get_relative_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let relative_to = args
+        .get_object(activation, 0, "relativeTo")?
+        .as_display_object()
+        .expect("relativeTo must be a DisplayObject");
+
+    let gc_context = activation.context.gc_context;
+    let matrix = concatenated_matrix3d(relative_to, gc_context)
+        .inverse()
+        .map(|inverse| inverse * concatenated_matrix3d(dobj, gc_context));
+    match matrix {
+        Some(matrix) => matrix3d_to_object(matrix, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Returns the 3D matrix transforming from the local space of `dobj` to stage space.
+fn concatenated_matrix3d<'gc>(
+    dobj: DisplayObject<'gc>,
+    gc_context: &gc_arena::Mutation<'gc>,
+) -> Matrix3D {
+    let mut matrix = Matrix3D::IDENTITY;
+    let mut node = Some(dobj);
+    while let Some(obj) = node {
+        if obj.as_stage().is_some() {
+            break;
+        }
+        let local = obj
+            .matrix3d(gc_context)
+            .unwrap_or_else(|| Matrix3D::from(*obj.base().matrix()));
+        matrix = local * matrix;
+        node = obj.parent();
+    }
+    matrix
+}
+
+pub fn matrix3d_to_object<'gc>(
+    matrix: Matrix3D,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let storage = VectorStorage::from_values(
+        matrix.raw_data.iter().map(|v| (*v).into()).collect(),
+        false,
+        Some(activation.avm2().classes().number),
+    );
+    let raw_data = VectorObject::from_vector(storage, activation)?;
+    let object = activation
+        .avm2()
+        .classes()
+        .matrix3d
+        .construct(activation, &[raw_data.into()])?;
+    Ok(object.into())
+}
+
+pub fn object_to_matrix3d<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Matrix3D, Error<'gc>> {
+    let raw_data = object
+        .get_public_property("rawData", activation)?
+        .coerce_to_object(activation)?;
+    let raw_data = raw_data
+        .as_vector_storage()
+        .expect("rawData must be a Vector")
+        .iter()
+        .collect::<Vec<_>>();
+
+    let mut matrix = Matrix3D::IDENTITY;
+    for (i, value) in raw_data.into_iter().take(16).enumerate() {
+        matrix.raw_data[i] = value.coerce_to_number(activation)?;
+    }
+    Ok(matrix)
+}
+
+fn perspective_projection_to_object<'gc>(
+    projection: PerspectiveProjection,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = activation
+        .avm2()
+        .classes()
+        .perspectiveprojection
+        .construct(activation, &[])?;
+    let center = activation.avm2().classes().point.construct(
+        activation,
+        &[
+            projection.projection_center.0.into(),
+            projection.projection_center.1.into(),
+        ],
+    )?;
+    object.set_property(
+        &Multiname::new(activation.avm2().flash_geom_internal, "_fieldOfView"),
+        projection.field_of_view.into(),
+        activation,
+    )?;
+    object.set_property(
+        &Multiname::new(activation.avm2().flash_geom_internal, "_projectionCenter"),
+        center.into(),
+        activation,
+    )?;
+    Ok(object.into())
+}
+
+pub fn object_to_perspective_projection<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<PerspectiveProjection, Error<'gc>> {
+    let field_of_view = object
+        .get_property(
+            &Multiname::new(activation.avm2().flash_geom_internal, "_fieldOfView"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+    let center = object
+        .get_property(
+            &Multiname::new(activation.avm2().flash_geom_internal, "_projectionCenter"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let x = center
+        .get_public_property("x", activation)?
+        .coerce_to_number(activation)?;
+    let y = center
+        .get_public_property("y", activation)?
+        .coerce_to_number(activation)?;
+    Ok(PerspectiveProjection {
+        field_of_view,
+        projection_center: (x, y),
+    })
+}
+
 pub fn get_pixel_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
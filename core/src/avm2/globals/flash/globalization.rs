@@ -0,0 +1,177 @@
+//! `flash.globalization` namespace
+
+pub mod currency_formatter;
+pub mod date_time_formatter;
+mod locale_data;
+pub mod locale_id;
+pub mod number_formatter;
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Error, Multiname};
+use crate::string::{AvmString, WStr};
+use locale_data::LocaleData;
+
+/// The locale ID name that stands for the default locale of the user.
+const DEFAULT_LOCALE_ID: &str = "i-default";
+
+/// The status of the last operation of a `flash.globalization` object,
+/// see `flash.globalization.LastOperationStatus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LastOperationStatus {
+    NoError,
+    IllegalArgumentError,
+    ParseError,
+    PatternSyntaxError,
+    UnexpectedToken,
+    UsingDefaultWarning,
+    UsingFallbackWarning,
+}
+
+impl LastOperationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NoError => "noError",
+            Self::IllegalArgumentError => "illegalArgumentError",
+            Self::ParseError => "parseError",
+            Self::PatternSyntaxError => "patternSyntaxError",
+            Self::UnexpectedToken => "unexpectedToken",
+            Self::UsingDefaultWarning => "usingDefaultWarning",
+            Self::UsingFallbackWarning => "usingFallbackWarning",
+        }
+    }
+}
+
+/// Get a `flash.globalization` internal property of an object.
+fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.get_property(
+        &Multiname::new(activation.avm2().flash_globalization_internal, name),
+        activation,
+    )
+}
+
+/// Set a `flash.globalization` internal property of an object.
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+    value: impl Into<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    this.set_property(
+        &Multiname::new(activation.avm2().flash_globalization_internal, name),
+        value.into(),
+        activation,
+    )
+}
+
+/// Get a `flash.globalization` internal string property of an object.
+fn get_internal_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<String, Error<'gc>> {
+    Ok(get_internal(activation, this, name)?
+        .coerce_to_string(activation)?
+        .to_string())
+}
+
+/// Set a `flash.globalization` internal string property of an object.
+fn set_internal_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+    value: &str,
+) -> Result<(), Error<'gc>> {
+    let value = AvmString::new_utf8(activation.context.gc_context, value);
+    set_internal(activation, this, name, value)
+}
+
+fn set_last_operation_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status: LastOperationStatus,
+) -> Result<(), Error<'gc>> {
+    set_internal(activation, this, "_lastOperationStatus", status.as_str())
+}
+
+/// Find the locale data to use for the requested locale ID name.
+///
+/// The default locale of the user is used if no locale of the requested
+/// language is available, which is reported in the returned status.
+fn resolve_locale(
+    activation: &mut Activation<'_, '_>,
+    requested: &WStr,
+) -> (&'static LocaleData, LastOperationStatus) {
+    let default_locale = || {
+        let language = activation.context.ui.language().to_string();
+        locale_data::lookup(&language)
+            .map(|(locale, _)| locale)
+            .unwrap_or_else(locale_data::fallback)
+    };
+
+    let requested = requested.to_utf8_lossy();
+    if requested == DEFAULT_LOCALE_ID {
+        return (default_locale(), LastOperationStatus::NoError);
+    }
+
+    match locale_data::lookup(&requested) {
+        Some((locale, true)) => (locale, LastOperationStatus::NoError),
+        Some((locale, false)) => (locale, LastOperationStatus::UsingFallbackWarning),
+        None => (default_locale(), LastOperationStatus::UsingDefaultWarning),
+    }
+}
+
+/// Set up the locale of a newly constructed formatter, returning the locale data to use.
+fn init_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    requested: &WStr,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let (locale, status) = resolve_locale(activation, requested);
+    set_internal(activation, this, "_actualLocaleIDName", locale.name)?;
+    set_last_operation_status(activation, this, status)?;
+    Ok(locale)
+}
+
+/// The locale data of a formatter, as chosen when it was constructed.
+fn actual_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let name = get_internal_string(activation, this, "_actualLocaleIDName")?;
+    Ok(locale_data::lookup(&name)
+        .map(|(locale, _)| locale)
+        .unwrap_or_else(locale_data::fallback))
+}
+
+/// Create a `Vector.<String>` of the given strings.
+fn string_vector<'gc, 's>(
+    activation: &mut Activation<'_, 'gc>,
+    strings: impl IntoIterator<Item = &'s str>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let values = strings
+        .into_iter()
+        .map(|string| AvmString::new_utf8(activation.context.gc_context, string).into())
+        .collect();
+    let storage =
+        VectorStorage::from_values(values, false, Some(activation.avm2().classes().string));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Implements `getAvailableLocaleIDNames` of the `flash.globalization` formatters.
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    string_vector(
+        activation,
+        locale_data::LOCALES.iter().map(|locale| locale.name),
+    )
+}
@@ -0,0 +1,232 @@
+//! `flash.globalization.CurrencyFormatter` native methods
+
+use super::number_formatter::NumberFormat;
+use super::{
+    get_internal, get_internal_string, init_locale, set_internal, set_internal_string,
+    set_last_operation_status, LastOperationStatus,
+};
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+
+pub use super::get_available_locale_id_names;
+
+/// The formats of `CurrencyFormatter.positiveCurrencyFormat`,
+/// where `¤` stands for the currency and `n` for the number.
+const POSITIVE_CURRENCY_FORMATS: [&str; 4] = ["¤n", "n¤", "¤ n", "n ¤"];
+
+/// The formats of `CurrencyFormatter.negativeCurrencyFormat`,
+/// where `-` additionally stands for the negative symbol.
+const NEGATIVE_CURRENCY_FORMATS: [&str; 16] = [
+    "(¤n)", "-¤n", "¤-n", "¤n-", "(n¤)", "-n¤", "n-¤", "n¤-", "-n ¤", "-¤ n", "n ¤-", "¤ n-",
+    "¤ -n", "n- ¤", "(¤ n)", "(n ¤)",
+];
+
+/// Format an amount of money.
+///
+/// A space is inserted between the currency and the number if the currency
+/// is alphabetic, such as an ISO code.
+fn format_currency(
+    format: &NumberFormat,
+    value: f64,
+    currency: &str,
+    positive_format: u32,
+    negative_format: u32,
+) -> String {
+    let (number, is_negative) = format.format_absolute(value, format.fractional_digits);
+    let pattern = if is_negative {
+        NEGATIVE_CURRENCY_FORMATS
+            .get(negative_format as usize)
+            .unwrap_or(&NEGATIVE_CURRENCY_FORMATS[1])
+    } else {
+        POSITIVE_CURRENCY_FORMATS
+            .get(positive_format as usize)
+            .unwrap_or(&POSITIVE_CURRENCY_FORMATS[0])
+    };
+
+    let is_letter = |c: Option<char>| c.is_some_and(char::is_alphabetic);
+    let mut result = String::new();
+    let mut pattern_chars = pattern.chars().peekable();
+    while let Some(c) = pattern_chars.next() {
+        match c {
+            '¤' => {
+                if result.ends_with(|c: char| c.is_ascii_digit() || c == ')')
+                    && is_letter(currency.chars().next())
+                {
+                    result.push(' ');
+                }
+                result.push_str(currency);
+                if pattern_chars.peek() == Some(&'n') && is_letter(currency.chars().last()) {
+                    result.push(' ');
+                }
+            }
+            'n' => result.push_str(&number),
+            '-' => result.push_str(&format.negative_symbol),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Implements `flash.globalization.CurrencyFormatter`'s `init` method, which is called from the constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let locale = init_locale(activation, this, &requested)?;
+
+    let fractional_digits = locale.currency_fractional_digits as usize;
+    NumberFormat::from_locale(locale, fractional_digits).write(activation, this)?;
+    set_internal_string(
+        activation,
+        this,
+        "_currencyISOCode",
+        locale.currency_iso_code,
+    )?;
+    set_internal_string(activation, this, "_currencySymbol", locale.currency_symbol)?;
+    set_internal(
+        activation,
+        this,
+        "_positiveCurrencyFormat",
+        locale.positive_currency_format,
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_negativeCurrencyFormat",
+        locale.negative_currency_format,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.globalization.CurrencyFormatter.format`
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let with_currency_symbol = args.get_bool(1);
+
+    let format = NumberFormat::read(activation, this)?;
+    let currency = if with_currency_symbol {
+        get_internal_string(activation, this, "_currencySymbol")?
+    } else {
+        get_internal_string(activation, this, "_currencyISOCode")?
+    };
+    let positive_format =
+        get_internal(activation, this, "_positiveCurrencyFormat")?.coerce_to_u32(activation)?;
+    let negative_format =
+        get_internal(activation, this, "_negativeCurrencyFormat")?.coerce_to_u32(activation)?;
+
+    let result = format_currency(&format, value, &currency, positive_format, negative_format);
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, result).into())
+}
+
+/// Implements `flash.globalization.CurrencyFormatter.formattingWithCurrencySymbolIsSafe`
+pub fn formatting_with_currency_symbol_is_safe<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedISOCode")?;
+    let iso_code = get_internal_string(activation, this, "_currencyISOCode")?;
+
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+    Ok(requested
+        .to_utf8_lossy()
+        .eq_ignore_ascii_case(&iso_code)
+        .into())
+}
+
+/// Implements `flash.globalization.CurrencyFormatter.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args
+        .get_string_non_null(activation, 0, "inputString")?
+        .to_utf8_lossy()
+        .into_owned();
+    let format = NumberFormat::read(activation, this)?;
+
+    let (value, currency, status) = match format.parse(&input) {
+        Some(number) => {
+            // Everything around the number that is not whitespace is the currency.
+            let utf16: Vec<u16> = input.encode_utf16().collect();
+            let before = String::from_utf16_lossy(&utf16[..number.start]);
+            let after = String::from_utf16_lossy(&utf16[number.end..]);
+            let currency = format!("{}{}", before.trim(), after.trim());
+            (number.value, currency, LastOperationStatus::NoError)
+        }
+        None => (f64::NAN, String::new(), LastOperationStatus::ParseError),
+    };
+    set_last_operation_status(activation, this, status)?;
+
+    let currency = AvmString::new_utf8(activation.context.gc_context, currency);
+    let result = activation
+        .avm2()
+        .classes()
+        .currencyparseresult
+        .construct(activation, &[value.into(), currency.into()])?;
+    Ok(result.into())
+}
+
+/// Implements `flash.globalization.CurrencyFormatter.setCurrency`
+pub fn set_currency<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let iso_code = args.get_string_non_null(activation, 0, "currencyISOCode")?;
+    let symbol = args.get_string_non_null(activation, 1, "currencySymbol")?;
+
+    set_internal(activation, this, "_currencyISOCode", iso_code)?;
+    set_internal(activation, this, "_currencySymbol", symbol)?;
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+
+    Ok(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::locale_data;
+    use super::super::number_formatter::NumberFormat;
+    use super::format_currency;
+
+    fn format_in(name: &str, value: f64, with_symbol: bool) -> String {
+        let (locale, _) = locale_data::lookup(name).unwrap();
+        let format = NumberFormat::from_locale(locale, locale.currency_fractional_digits as usize);
+        let currency = if with_symbol {
+            locale.currency_symbol
+        } else {
+            locale.currency_iso_code
+        };
+        format_currency(
+            &format,
+            value,
+            currency,
+            locale.positive_currency_format,
+            locale.negative_currency_format,
+        )
+    }
+
+    #[test]
+    fn format_currencies() {
+        assert_eq!(format_in("en-US", 1234.5, true), "$1,234.50");
+        assert_eq!(format_in("en-US", -1234.5, true), "-$1,234.50");
+        assert_eq!(format_in("en-US", 1234.5, false), "USD 1,234.50");
+        assert_eq!(format_in("de-DE", 1234.5, true), "1.234,50 €");
+        assert_eq!(format_in("de-DE", -1234.5, false), "-1.234,50 EUR");
+        assert_eq!(format_in("pt-BR", -3.0, true), "-R$ 3,00");
+        assert_eq!(format_in("ja-JP", 1500.0, true), "￥1,500");
+    }
+}
@@ -0,0 +1,436 @@
+//! `flash.globalization.DateTimeFormatter` native methods
+
+use super::locale_data::LocaleData;
+use super::{
+    actual_locale, get_internal_string, init_locale, set_internal, set_internal_string,
+    set_last_operation_status, string_vector, LastOperationStatus,
+};
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::locale::get_timezone;
+use crate::string::AvmString;
+use chrono::{DateTime, Datelike, FixedOffset, Offset, Timelike, Utc};
+use std::fmt::Write;
+
+pub use super::get_available_locale_id_names;
+
+/// The pattern letters that stand for a date or time field.
+const FIELD_LETTERS: &str = "GyYMLdDFEecQqwWahHKkmsSzZv";
+
+/// A part of a date time pattern such as `EEEE, MMMM d, yyyy`.
+#[derive(Debug, PartialEq, Eq)]
+enum PatternPart {
+    /// A date or time field, given by its letter and how many times it is repeated.
+    Field(char, usize),
+
+    /// Text that is output as-is.
+    Literal(String),
+}
+
+/// Split a date time pattern into its fields and literal text.
+///
+/// Text within single quotes is literal, and two single quotes stand for one.
+/// Returns `None` if the pattern is invalid.
+fn parse_pattern(pattern: &str) -> Option<Vec<PatternPart>> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            if chars.next_if_eq(&'\'').is_some() {
+                literal.push('\'');
+                continue;
+            }
+            loop {
+                match chars.next()? {
+                    '\'' if chars.next_if_eq(&'\'').is_some() => literal.push('\''),
+                    '\'' => break,
+                    c => literal.push(c),
+                }
+            }
+        } else if c.is_ascii_alphabetic() {
+            if !FIELD_LETTERS.contains(c) {
+                return None;
+            }
+            let mut count = 1;
+            while chars.next_if_eq(&c).is_some() {
+                count += 1;
+            }
+            if !literal.is_empty() {
+                parts.push(PatternPart::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(PatternPart::Field(c, count));
+        } else {
+            literal.push(c);
+        }
+    }
+
+    if !literal.is_empty() {
+        parts.push(PatternPart::Literal(literal));
+    }
+    Some(parts)
+}
+
+/// The abbreviated weekday names of the short abbreviation style.
+fn short_weekday_abbreviation(locale: &LocaleData, weekday: usize) -> String {
+    let abbreviation = locale.weekday_abbreviations[weekday];
+    if matches!(locale.script, "Latn" | "Cyrl") {
+        abbreviation.trim_end_matches('.').chars().take(2).collect()
+    } else {
+        abbreviation.to_owned()
+    }
+}
+
+/// Format a date according to a parsed pattern.
+fn format_date_time(
+    parts: &[PatternPart],
+    date: &DateTime<FixedOffset>,
+    locale: &LocaleData,
+) -> String {
+    let mut result = String::new();
+    for part in parts {
+        match part {
+            PatternPart::Literal(text) => result.push_str(text),
+            PatternPart::Field(letter, count) => {
+                format_field(&mut result, *letter, *count, date, locale)
+            }
+        }
+    }
+    result
+}
+
+fn format_field(
+    out: &mut String,
+    letter: char,
+    count: usize,
+    date: &DateTime<FixedOffset>,
+    locale: &LocaleData,
+) {
+    let number = |out: &mut String, value: i64, width: usize| {
+        let _ = write!(out, "{value:0width$}");
+    };
+    let month = date.month0() as usize;
+    let weekday = date.weekday().num_days_from_sunday() as usize;
+    let hour = date.hour() as i64;
+    let first_weekday = locale.first_weekday.rem_euclid(7) as usize;
+
+    match letter {
+        'G' => out.push_str(if date.year() > 0 { "AD" } else { "BC" }),
+        'y' | 'Y' => {
+            let year = if letter == 'Y' {
+                date.iso_week().year()
+            } else {
+                date.year()
+            };
+            if count == 2 {
+                number(out, year.rem_euclid(100).into(), 2);
+            } else {
+                number(out, year.into(), count);
+            }
+        }
+        'M' | 'L' => match count {
+            1 | 2 => number(out, month as i64 + 1, count),
+            3 => out.push_str(locale.month_abbreviations[month]),
+            4 if letter == 'M' => out.push_str(locale.months_format[month]),
+            4 => out.push_str(locale.months[month]),
+            _ => out.extend(locale.months[month].chars().next()),
+        },
+        'd' => number(out, date.day().into(), count),
+        'D' => number(out, date.ordinal().into(), count),
+        'F' => number(out, ((date.day() - 1) / 7 + 1).into(), count),
+        'E' | 'e' | 'c' => match count {
+            1 | 2 if letter != 'E' => {
+                let local_weekday = (weekday + 7 - first_weekday) % 7 + 1;
+                number(out, local_weekday as i64, count);
+            }
+            0..=3 => out.push_str(locale.weekday_abbreviations[weekday]),
+            4 => out.push_str(locale.weekdays[weekday]),
+            _ => out.extend(locale.weekdays[weekday].chars().next()),
+        },
+        'Q' | 'q' => {
+            let quarter = month / 3 + 1;
+            match count {
+                1 | 2 => number(out, quarter as i64, count),
+                _ => {
+                    let _ = write!(out, "Q{quarter}");
+                }
+            }
+        }
+        'w' => number(out, date.iso_week().week().into(), count),
+        'W' => {
+            let day = date.day0() as usize;
+            let first_day_of_month = (weekday + 35 - day) % 7;
+            let offset = (first_day_of_month + 7 - first_weekday) % 7;
+            number(out, ((day + offset) / 7 + 1) as i64, count);
+        }
+        'a' => out.push_str(locale.am_pm[(hour >= 12) as usize]),
+        'h' => number(out, (hour + 11) % 12 + 1, count),
+        'H' => number(out, hour, count),
+        'K' => number(out, hour % 12, count),
+        'k' => number(out, if hour == 0 { 24 } else { hour }, count),
+        'm' => number(out, date.minute().into(), count),
+        's' => number(out, date.second().into(), count),
+        'S' => {
+            let millis = format!("{:03}", date.timestamp_subsec_millis().min(999));
+            out.extend(millis.chars().chain(std::iter::repeat('0')).take(count));
+        }
+        'z' | 'Z' | 'v' => {
+            let offset = date.offset().local_minus_utc();
+            let sign = if offset < 0 { '-' } else { '+' };
+            let (hours, minutes) = (offset.abs() / 3600, offset.abs() / 60 % 60);
+            if letter == 'Z' && count <= 3 {
+                let _ = write!(out, "{sign}{hours:02}{minutes:02}");
+            } else if offset == 0 {
+                out.push_str("GMT");
+            } else {
+                let _ = write!(out, "GMT{sign}{hours:02}:{minutes:02}");
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The index of a date or time style in the patterns of the locale data,
+/// or `None` for `DateTimeStyle.NONE`.
+fn style_index(style: &str) -> Option<Option<usize>> {
+    match style {
+        "long" => Some(Some(0)),
+        "medium" => Some(Some(1)),
+        "short" => Some(Some(2)),
+        "none" => Some(None),
+        _ => None,
+    }
+}
+
+/// Implements `flash.globalization.DateTimeFormatter`'s `init` method, which is called from the constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    init_locale(activation, this, &requested)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.globalization.DateTimeFormatter.setDateTimeStyles`
+pub fn set_date_time_styles<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date_style = args.get_string_non_null(activation, 0, "dateStyle")?;
+    let time_style = args.get_string_non_null(activation, 1, "timeStyle")?;
+
+    let Some(date_index) = style_index(&date_style.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "dateStyle"));
+    };
+    let Some(time_index) = style_index(&time_style.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "timeStyle"));
+    };
+
+    let locale = actual_locale(activation, this)?;
+    let date_pattern = date_index.map(|index| locale.date_patterns[index]);
+    let time_pattern = time_index.map(|index| locale.time_patterns[index]);
+    let pattern = date_pattern
+        .into_iter()
+        .chain(time_pattern)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    set_internal(activation, this, "_dateStyle", date_style)?;
+    set_internal(activation, this, "_timeStyle", time_style)?;
+    set_internal_string(activation, this, "_dateTimePattern", &pattern)?;
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.globalization.DateTimeFormatter.setDateTimePattern`
+pub fn set_date_time_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pattern = args.get_string_non_null(activation, 0, "pattern")?;
+
+    if parse_pattern(&pattern.to_utf8_lossy()).is_none() {
+        set_last_operation_status(activation, this, LastOperationStatus::PatternSyntaxError)?;
+        return Ok(Value::Undefined);
+    }
+
+    set_internal(activation, this, "_dateStyle", "custom")?;
+    set_internal(activation, this, "_timeStyle", "custom")?;
+    set_internal(activation, this, "_dateTimePattern", pattern)?;
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+
+    Ok(Value::Undefined)
+}
+
+fn format_in_timezone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    timezone: FixedOffset,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = args
+        .get_object(activation, 0, "dateTime")?
+        .as_date_object()
+        .and_then(|date| date.date_time());
+    let Some(date) = date else {
+        set_last_operation_status(activation, this, LastOperationStatus::IllegalArgumentError)?;
+        return Ok("".into());
+    };
+
+    let locale = actual_locale(activation, this)?;
+    let pattern = get_internal_string(activation, this, "_dateTimePattern")?;
+    let parts = parse_pattern(&pattern).unwrap_or_default();
+
+    let result = format_date_time(&parts, &date.with_timezone(&timezone), locale);
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, result).into())
+}
+
+/// Implements `flash.globalization.DateTimeFormatter.format`
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_in_timezone(activation, this, args, get_timezone())
+}
+
+/// Implements `flash.globalization.DateTimeFormatter.formatUTC`
+pub fn format_utc<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_in_timezone(activation, this, args, Utc.fix())
+}
+
+/// Implements `flash.globalization.DateTimeFormatter.getFirstWeekday`
+pub fn get_first_weekday<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = actual_locale(activation, this)?;
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+    Ok(locale.first_weekday.into())
+}
+
+/// Implements `flash.globalization.DateTimeFormatter.getMonthNames`
+pub fn get_month_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name_style = args.get_string_non_null(activation, 0, "nameStyle")?;
+    let context = args.get_string_non_null(activation, 1, "context")?;
+    let locale = actual_locale(activation, this)?;
+
+    let names = match (&*name_style.to_utf8_lossy(), &*context.to_utf8_lossy()) {
+        ("full", "format") => locale.months_format,
+        ("full", "standalone") => locale.months,
+        ("longAbbreviation" | "shortAbbreviation", "format" | "standalone") => {
+            locale.month_abbreviations
+        }
+        ("full" | "longAbbreviation" | "shortAbbreviation", _) => {
+            return Err(make_error_2008(activation, "context"));
+        }
+        _ => return Err(make_error_2008(activation, "nameStyle")),
+    };
+
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+    string_vector(activation, names.iter().copied())
+}
+
+/// Implements `flash.globalization.DateTimeFormatter.getWeekdayNames`
+pub fn get_weekday_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name_style = args.get_string_non_null(activation, 0, "nameStyle")?;
+    let context = args.get_string_non_null(activation, 1, "context")?;
+    let locale = actual_locale(activation, this)?;
+
+    if !matches!(&*context.to_utf8_lossy(), "format" | "standalone") {
+        return Err(make_error_2008(activation, "context"));
+    }
+    let names: Vec<String> = match &*name_style.to_utf8_lossy() {
+        "full" => locale
+            .weekdays
+            .iter()
+            .map(|&name| name.to_owned())
+            .collect(),
+        "longAbbreviation" => locale
+            .weekday_abbreviations
+            .iter()
+            .map(|&name| name.to_owned())
+            .collect(),
+        "shortAbbreviation" => (0..7)
+            .map(|weekday| short_weekday_abbreviation(locale, weekday))
+            .collect(),
+        _ => return Err(make_error_2008(activation, "nameStyle")),
+    };
+
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+    string_vector(activation, names.iter().map(String::as_str))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::locale_data;
+    use super::{format_date_time, parse_pattern, PatternPart};
+    use chrono::{FixedOffset, TimeZone};
+
+    fn format(locale: &str, pattern: &str) -> String {
+        let (locale, _) = locale_data::lookup(locale).unwrap();
+        let date = FixedOffset::east_opt(-8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 3, 5, 14, 7, 9)
+            .unwrap();
+        format_date_time(&parse_pattern(pattern).unwrap(), &date, locale)
+    }
+
+    #[test]
+    fn parse_patterns() {
+        assert_eq!(
+            parse_pattern("d 'de' MMMM, ''yy"),
+            Some(vec![
+                PatternPart::Field('d', 1),
+                PatternPart::Literal(" de ".to_owned()),
+                PatternPart::Field('M', 4),
+                PatternPart::Literal(", '".to_owned()),
+                PatternPart::Field('y', 2),
+            ])
+        );
+        assert_eq!(parse_pattern("'unclosed"), None);
+        assert_eq!(parse_pattern("yyyy-MM-dd T"), None);
+    }
+
+    #[test]
+    fn format_dates() {
+        assert_eq!(
+            format("en-US", "EEEE, MMMM d, yyyy h:mm:ss a"),
+            "Tuesday, March 5, 2024 2:07:09 PM"
+        );
+        assert_eq!(format("en-GB", "dd/MM/yy HH:mm"), "05/03/24 14:07");
+        assert_eq!(
+            format("de-DE", "EEEE, d. MMMM yyyy"),
+            "Dienstag, 5. März 2024"
+        );
+        assert_eq!(format("ru-RU", "d MMMM"), "5 марта");
+        assert_eq!(format("ru-RU", "LLLL"), "март");
+        assert_eq!(format("ja-JP", "yyyy'年'M'月'd'日'"), "2024年3月5日");
+        assert_eq!(format("en-US", "QQQ DDD Z zzzz"), "Q1 065 -0800 GMT-08:00");
+    }
+}
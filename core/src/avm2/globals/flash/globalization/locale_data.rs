@@ -0,0 +1,852 @@
+//! A subset of the [CLDR](https://cldr.unicode.org/) locale data,
+//! as needed by the `flash.globalization` formatters.
+
+use super::locale_id::LocaleName;
+
+/// The formatting conventions of a single locale.
+pub struct LocaleData {
+    /// The locale ID name, such as `en-US`.
+    pub name: &'static str,
+
+    /// The script the language of this locale is usually written in.
+    pub script: &'static str,
+
+    /// The code point of the zero digit used by this locale.
+    pub digits_type: u32,
+
+    pub decimal_separator: &'static str,
+    pub grouping_separator: &'static str,
+    pub grouping_pattern: &'static str,
+    pub negative_symbol: &'static str,
+
+    /// The index of the negative number format, see `NumberFormatter.negativeNumberFormat`.
+    pub negative_number_format: u32,
+
+    pub currency_iso_code: &'static str,
+    pub currency_symbol: &'static str,
+    pub currency_fractional_digits: i32,
+
+    /// The index of the positive currency format, see `CurrencyFormatter.positiveCurrencyFormat`.
+    pub positive_currency_format: u32,
+
+    /// The index of the negative currency format, see `CurrencyFormatter.negativeCurrencyFormat`.
+    pub negative_currency_format: u32,
+
+    /// The full month names, as used on their own.
+    pub months: &'static [&'static str; 12],
+
+    /// The full month names, as used within a date.
+    pub months_format: &'static [&'static str; 12],
+
+    pub month_abbreviations: &'static [&'static str; 12],
+    pub weekdays: &'static [&'static str; 7],
+    pub weekday_abbreviations: &'static [&'static str; 7],
+    pub am_pm: [&'static str; 2],
+
+    /// The date patterns of the long, medium and short date styles.
+    pub date_patterns: [&'static str; 3],
+
+    /// The time patterns of the long, medium and short time styles.
+    pub time_patterns: [&'static str; 3],
+
+    /// The first day of the week, where 0 is Sunday.
+    pub first_weekday: i32,
+}
+
+impl LocaleData {
+    /// The language of this locale, such as `en`.
+    pub fn language(&self) -> &'static str {
+        self.name
+            .split_once('-')
+            .map_or(self.name, |(language, _)| language)
+    }
+
+    /// The region of this locale, such as `US`.
+    pub fn region(&self) -> &'static str {
+        self.name.split_once('-').map_or("", |(_, region)| region)
+    }
+}
+
+/// Look up the locale that best matches the given locale ID name.
+///
+/// Returns the locale data, and whether it is an exact match.
+/// A locale of the same language is used if the requested region is not available.
+pub fn lookup(name: &str) -> Option<(&'static LocaleData, bool)> {
+    let name = LocaleName::parse(name)?;
+
+    let same_language = || {
+        LOCALES
+            .iter()
+            .filter(|locale| locale.language().eq_ignore_ascii_case(name.language))
+    };
+
+    if let Some(region) = name.region {
+        if let Some(locale) =
+            same_language().find(|locale| locale.region().eq_ignore_ascii_case(region))
+        {
+            return Some((locale, true));
+        }
+    }
+
+    same_language().next().map(|locale| (locale, false))
+}
+
+/// The locale used when nothing else is available.
+pub fn fallback() -> &'static LocaleData {
+    &LOCALES[0]
+}
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const EN_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const EN_WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const EN_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const DE_MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+const DE_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+];
+const DE_WEEKDAYS: [&str; 7] = [
+    "Sonntag",
+    "Montag",
+    "Dienstag",
+    "Mittwoch",
+    "Donnerstag",
+    "Freitag",
+    "Samstag",
+];
+const DE_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"];
+
+const FR_MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+const FR_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+    "déc.",
+];
+const FR_WEEKDAYS: [&str; 7] = [
+    "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+];
+const FR_WEEKDAY_ABBREVIATIONS: [&str; 7] =
+    ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."];
+
+const ES_MONTHS: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+const ES_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+];
+const ES_WEEKDAYS: [&str; 7] = [
+    "domingo",
+    "lunes",
+    "martes",
+    "miércoles",
+    "jueves",
+    "viernes",
+    "sábado",
+];
+const ES_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"];
+
+const IT_MONTHS: [&str; 12] = [
+    "gennaio",
+    "febbraio",
+    "marzo",
+    "aprile",
+    "maggio",
+    "giugno",
+    "luglio",
+    "agosto",
+    "settembre",
+    "ottobre",
+    "novembre",
+    "dicembre",
+];
+const IT_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+];
+const IT_WEEKDAYS: [&str; 7] = [
+    "domenica",
+    "lunedì",
+    "martedì",
+    "mercoledì",
+    "giovedì",
+    "venerdì",
+    "sabato",
+];
+const IT_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["dom", "lun", "mar", "mer", "gio", "ven", "sab"];
+
+const PT_MONTHS: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
+];
+const PT_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+];
+const PT_WEEKDAYS: [&str; 7] = [
+    "domingo",
+    "segunda-feira",
+    "terça-feira",
+    "quarta-feira",
+    "quinta-feira",
+    "sexta-feira",
+    "sábado",
+];
+const PT_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"];
+
+const NL_MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "maart",
+    "april",
+    "mei",
+    "juni",
+    "juli",
+    "augustus",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+const NL_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+];
+const NL_WEEKDAYS: [&str; 7] = [
+    "zondag",
+    "maandag",
+    "dinsdag",
+    "woensdag",
+    "donderdag",
+    "vrijdag",
+    "zaterdag",
+];
+const NL_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["zo", "ma", "di", "wo", "do", "vr", "za"];
+
+const SV_MONTHS: [&str; 12] = [
+    "januari",
+    "februari",
+    "mars",
+    "april",
+    "maj",
+    "juni",
+    "juli",
+    "augusti",
+    "september",
+    "oktober",
+    "november",
+    "december",
+];
+const SV_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "maj", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+];
+const SV_WEEKDAYS: [&str; 7] = [
+    "söndag", "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag",
+];
+const SV_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["sön", "mån", "tis", "ons", "tors", "fre", "lör"];
+
+const PL_MONTHS: [&str; 12] = [
+    "styczeń",
+    "luty",
+    "marzec",
+    "kwiecień",
+    "maj",
+    "czerwiec",
+    "lipiec",
+    "sierpień",
+    "wrzesień",
+    "październik",
+    "listopad",
+    "grudzień",
+];
+const PL_MONTHS_FORMAT: [&str; 12] = [
+    "stycznia",
+    "lutego",
+    "marca",
+    "kwietnia",
+    "maja",
+    "czerwca",
+    "lipca",
+    "sierpnia",
+    "września",
+    "października",
+    "listopada",
+    "grudnia",
+];
+const PL_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+];
+const PL_WEEKDAYS: [&str; 7] = [
+    "niedziela",
+    "poniedziałek",
+    "wtorek",
+    "środa",
+    "czwartek",
+    "piątek",
+    "sobota",
+];
+const PL_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["niedz.", "pon.", "wt.", "śr.", "czw.", "pt.", "sob."];
+
+const RU_MONTHS: [&str; 12] = [
+    "январь",
+    "февраль",
+    "март",
+    "апрель",
+    "май",
+    "июнь",
+    "июль",
+    "август",
+    "сентябрь",
+    "октябрь",
+    "ноябрь",
+    "декабрь",
+];
+const RU_MONTHS_FORMAT: [&str; 12] = [
+    "января",
+    "февраля",
+    "марта",
+    "апреля",
+    "мая",
+    "июня",
+    "июля",
+    "августа",
+    "сентября",
+    "октября",
+    "ноября",
+    "декабря",
+];
+const RU_MONTH_ABBREVIATIONS: [&str; 12] = [
+    "янв.",
+    "февр.",
+    "март",
+    "апр.",
+    "май",
+    "июнь",
+    "июль",
+    "авг.",
+    "сент.",
+    "окт.",
+    "нояб.",
+    "дек.",
+];
+const RU_WEEKDAYS: [&str; 7] = [
+    "воскресенье",
+    "понедельник",
+    "вторник",
+    "среда",
+    "четверг",
+    "пятница",
+    "суббота",
+];
+const RU_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["вс", "пн", "вт", "ср", "чт", "пт", "сб"];
+
+const NUMBERED_MONTHS: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+const JA_WEEKDAYS: [&str; 7] = [
+    "日曜日",
+    "月曜日",
+    "火曜日",
+    "水曜日",
+    "木曜日",
+    "金曜日",
+    "土曜日",
+];
+const JA_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["日", "月", "火", "水", "木", "金", "土"];
+
+const ZH_MONTHS: [&str; 12] = [
+    "一月",
+    "二月",
+    "三月",
+    "四月",
+    "五月",
+    "六月",
+    "七月",
+    "八月",
+    "九月",
+    "十月",
+    "十一月",
+    "十二月",
+];
+const ZH_WEEKDAYS: [&str; 7] = [
+    "星期日",
+    "星期一",
+    "星期二",
+    "星期三",
+    "星期四",
+    "星期五",
+    "星期六",
+];
+const ZH_WEEKDAY_ABBREVIATIONS: [&str; 7] =
+    ["周日", "周一", "周二", "周三", "周四", "周五", "周六"];
+
+const KO_MONTHS: [&str; 12] = [
+    "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+];
+const KO_WEEKDAYS: [&str; 7] = [
+    "일요일",
+    "월요일",
+    "화요일",
+    "수요일",
+    "목요일",
+    "금요일",
+    "토요일",
+];
+const KO_WEEKDAY_ABBREVIATIONS: [&str; 7] = ["일", "월", "화", "수", "목", "금", "토"];
+
+const AR_MONTHS: [&str; 12] = [
+    "يناير",
+    "فبراير",
+    "مارس",
+    "أبريل",
+    "مايو",
+    "يونيو",
+    "يوليو",
+    "أغسطس",
+    "سبتمبر",
+    "أكتوبر",
+    "نوفمبر",
+    "ديسمبر",
+];
+const AR_WEEKDAYS: [&str; 7] = [
+    "الأحد",
+    "الاثنين",
+    "الثلاثاء",
+    "الأربعاء",
+    "الخميس",
+    "الجمعة",
+    "السبت",
+];
+
+/// All available locales. The first one is used as the last resort fallback.
+pub static LOCALES: &[LocaleData] = &[
+    LocaleData {
+        name: "en-US",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "USD",
+        currency_symbol: "$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: &EN_MONTHS,
+        months_format: &EN_MONTHS,
+        month_abbreviations: &EN_MONTH_ABBREVIATIONS,
+        weekdays: &EN_WEEKDAYS,
+        weekday_abbreviations: &EN_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, MMMM d, yyyy", "MMM d, yyyy", "M/d/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+        first_weekday: 0,
+    },
+    LocaleData {
+        name: "en-GB",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "GBP",
+        currency_symbol: "£",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: &EN_MONTHS,
+        months_format: &EN_MONTHS,
+        month_abbreviations: &EN_MONTH_ABBREVIATIONS,
+        weekdays: &EN_WEEKDAYS,
+        weekday_abbreviations: &EN_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["am", "pm"],
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "de-DE",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &DE_MONTHS,
+        months_format: &DE_MONTHS,
+        month_abbreviations: &DE_MONTH_ABBREVIATIONS,
+        weekdays: &DE_WEEKDAYS,
+        weekday_abbreviations: &DE_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, d. MMMM yyyy", "dd.MM.yyyy", "dd.MM.yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "fr-FR",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &FR_MONTHS,
+        months_format: &FR_MONTHS,
+        month_abbreviations: &FR_MONTH_ABBREVIATIONS,
+        weekdays: &FR_WEEKDAYS,
+        weekday_abbreviations: &FR_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "es-ES",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &ES_MONTHS,
+        months_format: &ES_MONTHS,
+        month_abbreviations: &ES_MONTH_ABBREVIATIONS,
+        weekdays: &ES_WEEKDAYS,
+        weekday_abbreviations: &ES_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["a. m.", "p. m."],
+        date_patterns: ["EEEE, d 'de' MMMM 'de' yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "it-IT",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &IT_MONTHS,
+        months_format: &IT_MONTHS,
+        month_abbreviations: &IT_MONTH_ABBREVIATIONS,
+        weekdays: &IT_WEEKDAYS,
+        weekday_abbreviations: &IT_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "pt-BR",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "BRL",
+        currency_symbol: "R$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        months: &PT_MONTHS,
+        months_format: &PT_MONTHS,
+        month_abbreviations: &PT_MONTH_ABBREVIATIONS,
+        weekdays: &PT_WEEKDAYS,
+        weekday_abbreviations: &PT_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["AM", "PM"],
+        date_patterns: [
+            "EEEE, d 'de' MMMM 'de' yyyy",
+            "d 'de' MMM 'de' yyyy",
+            "dd/MM/yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 0,
+    },
+    LocaleData {
+        name: "nl-NL",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 12,
+        months: &NL_MONTHS,
+        months_format: &NL_MONTHS,
+        month_abbreviations: &NL_MONTH_ABBREVIATIONS,
+        weekdays: &NL_WEEKDAYS,
+        weekday_abbreviations: &NL_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["a.m.", "p.m."],
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd-MM-yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "sv-SE",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "SEK",
+        currency_symbol: "kr",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &SV_MONTHS,
+        months_format: &SV_MONTHS,
+        month_abbreviations: &SV_MONTH_ABBREVIATIONS,
+        weekdays: &SV_WEEKDAYS,
+        weekday_abbreviations: &SV_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["fm", "em"],
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "yyyy-MM-dd"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "pl-PL",
+        script: "Latn",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "PLN",
+        currency_symbol: "zł",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &PL_MONTHS,
+        months_format: &PL_MONTHS_FORMAT,
+        month_abbreviations: &PL_MONTH_ABBREVIATIONS,
+        weekdays: &PL_WEEKDAYS,
+        weekday_abbreviations: &PL_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "ru-RU",
+        script: "Cyrl",
+        digits_type: '0' as u32,
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "RUB",
+        currency_symbol: "₽",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &RU_MONTHS,
+        months_format: &RU_MONTHS_FORMAT,
+        month_abbreviations: &RU_MONTH_ABBREVIATIONS,
+        weekdays: &RU_WEEKDAYS,
+        weekday_abbreviations: &RU_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["AM", "PM"],
+        date_patterns: ["EEEE, d MMMM yyyy 'г'.", "d MMM yyyy 'г'.", "dd.MM.yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "ja-JP",
+        script: "Jpan",
+        digits_type: '0' as u32,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "JPY",
+        currency_symbol: "￥",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: &NUMBERED_MONTHS,
+        months_format: &NUMBERED_MONTHS,
+        month_abbreviations: &NUMBERED_MONTHS,
+        weekdays: &JA_WEEKDAYS,
+        weekday_abbreviations: &JA_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["午前", "午後"],
+        date_patterns: ["yyyy'年'M'月'd'日'", "yyyy/MM/dd", "yyyy/MM/dd"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+        first_weekday: 0,
+    },
+    LocaleData {
+        name: "zh-CN",
+        script: "Hans",
+        digits_type: '0' as u32,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "CNY",
+        currency_symbol: "¥",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: &ZH_MONTHS,
+        months_format: &ZH_MONTHS,
+        month_abbreviations: &NUMBERED_MONTHS,
+        weekdays: &ZH_WEEKDAYS,
+        weekday_abbreviations: &ZH_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["上午", "下午"],
+        date_patterns: ["yyyy'年'M'月'd'日' EEEE", "yyyy'年'M'月'd'日'", "yyyy/M/d"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+        first_weekday: 1,
+    },
+    LocaleData {
+        name: "ko-KR",
+        script: "Kore",
+        digits_type: '0' as u32,
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "KRW",
+        currency_symbol: "₩",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: &KO_MONTHS,
+        months_format: &KO_MONTHS,
+        month_abbreviations: &KO_MONTHS,
+        weekdays: &KO_WEEKDAYS,
+        weekday_abbreviations: &KO_WEEKDAY_ABBREVIATIONS,
+        am_pm: ["오전", "오후"],
+        date_patterns: ["yyyy'년' M'월' d'일' EEEE", "yyyy. M. d.", "yy. M. d."],
+        time_patterns: ["a h:mm:ss", "a h:mm:ss", "a h:mm"],
+        first_weekday: 0,
+    },
+    LocaleData {
+        name: "ar-SA",
+        script: "Arab",
+        digits_type: 0x0660,
+        decimal_separator: "٫",
+        grouping_separator: "٬",
+        grouping_pattern: "3;*",
+        negative_symbol: "\u{61c}-",
+        negative_number_format: 1,
+        currency_iso_code: "SAR",
+        currency_symbol: "ر.س.\u{200f}",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: &AR_MONTHS,
+        months_format: &AR_MONTHS,
+        month_abbreviations: &AR_MONTHS,
+        weekdays: &AR_WEEKDAYS,
+        weekday_abbreviations: &AR_WEEKDAYS,
+        am_pm: ["ص", "م"],
+        date_patterns: ["EEEE، d MMMM yyyy", "dd/MM/yyyy", "d/M/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+        first_weekday: 6,
+    },
+];
@@ -0,0 +1,325 @@
+//! `flash.globalization.LocaleID` native methods
+
+use super::{
+    get_internal_string, locale_data, set_internal_string, set_last_operation_status,
+    string_vector, LastOperationStatus,
+};
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+use std::ops::RangeInclusive;
+
+/// Languages that are written from right to left.
+const RIGHT_TO_LEFT_LANGUAGES: &[&str] = &["ar", "dv", "fa", "he", "ps", "sd", "ug", "ur", "yi"];
+
+/// Scripts that are written from right to left.
+const RIGHT_TO_LEFT_SCRIPTS: &[&str] = &["Arab", "Hebr", "Syrc", "Thaa"];
+
+/// The parts of a locale ID name, such as `zh-Hant-TW@collation=stroke`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LocaleName<'a> {
+    pub language: &'a str,
+    pub script: Option<&'a str>,
+    pub region: Option<&'a str>,
+    pub variants: Vec<&'a str>,
+    pub keywords: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> LocaleName<'a> {
+    /// Parse a locale ID name, accepting both `-` and `_` as separators.
+    pub fn parse(name: &'a str) -> Option<Self> {
+        fn is_alphabetic(subtag: &str, length: RangeInclusive<usize>) -> bool {
+            length.contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+        }
+
+        let (subtags, keywords) = name.split_once('@').unwrap_or((name, ""));
+        let mut subtags = subtags.split(['-', '_']).peekable();
+
+        let language = subtags.next().filter(|s| is_alphabetic(s, 1..=8))?;
+        let script = subtags.next_if(|s| is_alphabetic(s, 4..=4));
+        let region = subtags.next_if(|s| {
+            is_alphabetic(s, 2..=2) || (s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit()))
+        });
+        let variants: Vec<_> = subtags.collect();
+        if variants
+            .iter()
+            .any(|v| v.is_empty() || !v.bytes().all(|b| b.is_ascii_alphanumeric()))
+        {
+            return None;
+        }
+
+        let keywords = keywords
+            .split(';')
+            .filter(|keyword| !keyword.is_empty())
+            .map(|keyword| keyword.split_once('='))
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            language,
+            script,
+            region,
+            variants,
+            keywords,
+        })
+    }
+
+    /// The canonical form of this locale ID name, such as `zh-Hant-TW`.
+    pub fn canonical(&self) -> String {
+        let mut name = self.language.to_ascii_lowercase();
+        if let Some(script) = self.script {
+            name.push('-');
+            name.push_str(&script[..1].to_ascii_uppercase());
+            name.push_str(&script[1..].to_ascii_lowercase());
+        }
+        if let Some(region) = self.region {
+            name.push('-');
+            name.push_str(&region.to_ascii_uppercase());
+        }
+        for variant in &self.variants {
+            name.push('-');
+            name.push_str(variant);
+        }
+        for (i, (key, value)) in self.keywords.iter().enumerate() {
+            name.push(if i == 0 { '@' } else { ';' });
+            name.push_str(key);
+            name.push('=');
+            name.push_str(value);
+        }
+        name
+    }
+
+    /// The script of this locale, inferred from its language if not given explicitly.
+    fn likely_script(&self) -> Option<&'a str> {
+        self.script
+            .or_else(|| locale_data::lookup(self.language).map(|(locale, _)| locale.script))
+    }
+
+    /// The region of this locale, inferred from its language if not given explicitly.
+    fn likely_region(&self) -> Option<&'a str> {
+        self.region
+            .or_else(|| locale_data::lookup(self.language).map(|(locale, _)| locale.region()))
+    }
+
+    fn is_right_to_left(&self) -> bool {
+        match self.likely_script() {
+            Some(script) => RIGHT_TO_LEFT_SCRIPTS
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(script)),
+            None => RIGHT_TO_LEFT_LANGUAGES
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case(self.language)),
+        }
+    }
+}
+
+/// Call `f` with the parsed name of a `LocaleID`, updating its `lastOperationStatus`.
+fn with_locale_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    f: impl FnOnce(&mut Activation<'_, 'gc>, &LocaleName) -> Result<Value<'gc>, Error<'gc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = get_internal_string(activation, this, "_name")?;
+    match LocaleName::parse(&name) {
+        Some(name) => {
+            set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+            f(activation, &name)
+        }
+        None => {
+            set_last_operation_status(activation, this, LastOperationStatus::UnexpectedToken)?;
+            Ok("".into())
+        }
+    }
+}
+
+/// Implements `flash.globalization.LocaleID`'s `init` method, which is called from the constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?.to_string();
+
+    let (name, status) = match LocaleName::parse(&name) {
+        Some(parsed) => (parsed.canonical(), LastOperationStatus::NoError),
+        None => (name, LastOperationStatus::UnexpectedToken),
+    };
+    set_internal_string(activation, this, "_name", &name)?;
+    set_last_operation_status(activation, this, status)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.globalization.LocaleID.getLanguage`
+pub fn get_language<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_name(activation, this, |activation, name| {
+        let language = name.language.to_ascii_lowercase();
+        Ok(AvmString::new_utf8(activation.context.gc_context, language).into())
+    })
+}
+
+/// Implements `flash.globalization.LocaleID.getRegion`
+pub fn get_region<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_name(activation, this, |activation, name| {
+        let region = name
+            .likely_region()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        Ok(AvmString::new_utf8(activation.context.gc_context, region).into())
+    })
+}
+
+/// Implements `flash.globalization.LocaleID.getScript`
+pub fn get_script<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_name(activation, this, |activation, name| {
+        let script = name.likely_script().unwrap_or_default();
+        Ok(AvmString::new_utf8(activation.context.gc_context, script).into())
+    })
+}
+
+/// Implements `flash.globalization.LocaleID.getVariant`
+pub fn get_variant<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    with_locale_name(activation, this, |activation, name| {
+        let variant = name.variants.join("-");
+        Ok(AvmString::new_utf8(activation.context.gc_context, variant).into())
+    })
+}
+
+/// Implements `flash.globalization.LocaleID.getKeysAndValues`
+pub fn get_keys_and_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+
+    with_locale_name(activation, this, |activation, name| {
+        for (key, value) in &name.keywords {
+            let key = AvmString::new_utf8(activation.context.gc_context, *key);
+            let value = AvmString::new_utf8(activation.context.gc_context, *value);
+            object.set_public_property(key, value.into(), activation)?;
+        }
+        Ok(Value::Undefined)
+    })?;
+
+    Ok(object.into())
+}
+
+/// Implements `flash.globalization.LocaleID.isRightToLeft`
+pub fn is_right_to_left<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut right_to_left = false;
+    with_locale_name(activation, this, |_activation, name| {
+        right_to_left = name.is_right_to_left();
+        Ok(Value::Undefined)
+    })?;
+
+    Ok(right_to_left.into())
+}
+
+/// Implements `flash.globalization.LocaleID.determinePreferredLocales`
+pub fn determine_preferred_locales<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let want = args.get_object(activation, 0, "want")?;
+    let want = string_list(activation, want)?;
+    let have = args.get_object(activation, 1, "have")?;
+    let have = string_list(activation, have)?;
+
+    // Prefer the exact matches for each wanted locale, followed by the ones of the same language.
+    let mut preferred: Vec<&str> = Vec::new();
+    for wanted in want.iter().filter_map(|name| LocaleName::parse(name)) {
+        let matches = have.iter().filter_map(|name| {
+            let available = LocaleName::parse(name)?;
+            if !available.language.eq_ignore_ascii_case(wanted.language) {
+                return None;
+            }
+            let is_exact = available.canonical() == wanted.canonical();
+            Some((!is_exact, name.as_str()))
+        });
+        let mut matches: Vec<_> = matches.collect();
+        matches.sort_by_key(|(is_inexact, _)| *is_inexact);
+
+        for (_, name) in matches {
+            if !preferred.contains(&name) {
+                preferred.push(name);
+            }
+        }
+    }
+
+    string_vector(activation, preferred)
+}
+
+/// The strings of a `Vector.<String>`.
+fn string_list<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    vector: Object<'gc>,
+) -> Result<Vec<String>, Error<'gc>> {
+    let values: Vec<_> = vector
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+
+    values
+        .into_iter()
+        .map(|value| Ok(value.coerce_to_string(activation)?.to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocaleName;
+
+    #[test]
+    fn parse_locale_name() {
+        let name = LocaleName::parse("zh_hant-tw@collation=stroke").unwrap();
+        assert_eq!(name.language, "zh");
+        assert_eq!(name.script, Some("hant"));
+        assert_eq!(name.region, Some("tw"));
+        assert_eq!(name.keywords, vec![("collation", "stroke")]);
+        assert_eq!(name.canonical(), "zh-Hant-TW@collation=stroke");
+
+        let name = LocaleName::parse("es-419").unwrap();
+        assert_eq!(name.region, Some("419"));
+
+        assert_eq!(LocaleName::parse("en--US"), None);
+        assert_eq!(LocaleName::parse(""), None);
+    }
+
+    #[test]
+    fn infer_locale_parts() {
+        let name = LocaleName::parse("de").unwrap();
+        assert_eq!(name.likely_region(), Some("DE"));
+        assert_eq!(name.likely_script(), Some("Latn"));
+        assert!(LocaleName::parse("ar").unwrap().is_right_to_left());
+        assert!(LocaleName::parse("he").unwrap().is_right_to_left());
+        assert!(!LocaleName::parse("ja-JP").unwrap().is_right_to_left());
+    }
+}
@@ -0,0 +1,568 @@
+//! `flash.globalization.NumberFormatter` native methods
+
+use super::locale_data::LocaleData;
+use super::{
+    get_internal, get_internal_string, init_locale, set_internal, set_internal_string,
+    set_last_operation_status, LastOperationStatus,
+};
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+
+pub use super::get_available_locale_id_names;
+
+/// The index returned by `parse` when no number was found.
+const NO_INDEX: i32 = 0x7fffffff;
+
+/// The code points of the zero digits listed in `flash.globalization.NationalDigitsType`.
+const NATIONAL_DIGITS_TYPES: [u32; 32] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1B50, 0x1BB0,
+    0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xAA50, 0xFF10, 0x104A0,
+];
+
+/// Whether a `digitsType` is one of the digit sets of `NationalDigitsType`.
+fn is_national_digits_type(digits_type: u32) -> bool {
+    NATIONAL_DIGITS_TYPES.contains(&digits_type)
+}
+
+/// The options of a `NumberFormatter` or `CurrencyFormatter` that affect
+/// how the digits of a number are written.
+#[derive(Clone, Debug)]
+pub struct NumberFormat {
+    pub decimal_separator: String,
+    pub digits_type: u32,
+    pub fractional_digits: usize,
+    pub grouping_pattern: String,
+    pub grouping_separator: String,
+    pub leading_zero: bool,
+    pub negative_symbol: String,
+    pub trailing_zeros: bool,
+    pub use_grouping: bool,
+}
+
+/// A number found in a string by [`NumberFormat::parse`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParsedNumber {
+    pub value: f64,
+
+    /// The index of the first character of the number, including its sign.
+    pub start: usize,
+
+    /// The index after the last character of the number, including its sign.
+    pub end: usize,
+}
+
+impl NumberFormat {
+    /// The most fractional digits that can be formatted.
+    const MAX_FRACTIONAL_DIGITS: usize = 20;
+
+    pub fn from_locale(locale: &LocaleData, fractional_digits: usize) -> Self {
+        Self {
+            decimal_separator: locale.decimal_separator.to_owned(),
+            digits_type: locale.digits_type,
+            fractional_digits,
+            grouping_pattern: locale.grouping_pattern.to_owned(),
+            grouping_separator: locale.grouping_separator.to_owned(),
+            leading_zero: true,
+            negative_symbol: locale.negative_symbol.to_owned(),
+            trailing_zeros: true,
+            use_grouping: true,
+        }
+    }
+
+    /// Read the format from the properties of a formatter.
+    pub fn read<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        this: Object<'gc>,
+    ) -> Result<Self, Error<'gc>> {
+        let fractional_digits = get_internal(activation, this, "_fractionalDigits")?
+            .coerce_to_i32(activation)?
+            .clamp(0, Self::MAX_FRACTIONAL_DIGITS as i32) as usize;
+
+        // An unknown digit set would turn digits into arbitrary characters, so use ASCII instead.
+        let digits_type =
+            get_internal(activation, this, "_digitsType")?.coerce_to_u32(activation)?;
+        let digits_type = if is_national_digits_type(digits_type) {
+            digits_type
+        } else {
+            '0' as u32
+        };
+
+        Ok(Self {
+            decimal_separator: get_internal_string(activation, this, "_decimalSeparator")?,
+            digits_type,
+            fractional_digits,
+            grouping_pattern: get_internal_string(activation, this, "_groupingPattern")?,
+            grouping_separator: get_internal_string(activation, this, "_groupingSeparator")?,
+            leading_zero: get_internal(activation, this, "_leadingZero")?.coerce_to_boolean(),
+            negative_symbol: get_internal_string(activation, this, "_negativeSymbol")?,
+            trailing_zeros: get_internal(activation, this, "_trailingZeros")?.coerce_to_boolean(),
+            use_grouping: get_internal(activation, this, "_useGrouping")?.coerce_to_boolean(),
+        })
+    }
+
+    /// Write the format to the properties of a formatter.
+    pub fn write<'gc>(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        this: Object<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        set_internal_string(
+            activation,
+            this,
+            "_decimalSeparator",
+            &self.decimal_separator,
+        )?;
+        set_internal(activation, this, "_digitsType", self.digits_type)?;
+        set_internal(
+            activation,
+            this,
+            "_fractionalDigits",
+            self.fractional_digits,
+        )?;
+        set_internal_string(activation, this, "_groupingPattern", &self.grouping_pattern)?;
+        set_internal_string(
+            activation,
+            this,
+            "_groupingSeparator",
+            &self.grouping_separator,
+        )?;
+        set_internal(activation, this, "_leadingZero", self.leading_zero)?;
+        set_internal_string(activation, this, "_negativeSymbol", &self.negative_symbol)?;
+        set_internal(activation, this, "_trailingZeros", self.trailing_zeros)?;
+        set_internal(activation, this, "_useGrouping", self.use_grouping)?;
+        Ok(())
+    }
+
+    /// Format the absolute value of a number with the given amount of fractional digits.
+    ///
+    /// Returns the formatted number, and whether the number is negative.
+    /// A number that is rounded to zero is never negative.
+    pub fn format_absolute(&self, value: f64, fractional_digits: usize) -> (String, bool) {
+        if value.is_nan() {
+            return ("NaN".to_owned(), false);
+        }
+        if value.is_infinite() {
+            return ("∞".to_owned(), value < 0.0);
+        }
+
+        let fractional_digits = fractional_digits.min(Self::MAX_FRACTIONAL_DIGITS);
+        let digits = format!("{:.*}", fractional_digits, value.abs());
+        let is_negative = value < 0.0 && digits.bytes().any(|b| matches!(b, b'1'..=b'9'));
+
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        let fraction = if self.trailing_zeros {
+            fraction
+        } else {
+            fraction.trim_end_matches('0')
+        };
+
+        let mut result = String::new();
+        if integer != "0" || self.leading_zero || fraction.is_empty() {
+            result.push_str(&self.group(&self.localize_digits(integer)));
+        }
+        if !fraction.is_empty() {
+            result.push_str(&self.decimal_separator);
+            result.push_str(&self.localize_digits(fraction));
+        }
+
+        (result, is_negative)
+    }
+
+    /// Format a number with the given amount of fractional digits and negative number format.
+    pub fn format(&self, value: f64, fractional_digits: usize, negative_format: u32) -> String {
+        let (number, is_negative) = self.format_absolute(value, fractional_digits);
+        if !is_negative {
+            return number;
+        }
+
+        let symbol = &self.negative_symbol;
+        match negative_format {
+            0 => format!("({number})"),
+            1 => format!("{symbol}{number}"),
+            2 => format!("{symbol} {number}"),
+            3 => format!("{number}{symbol}"),
+            _ => format!("{number} {symbol}"),
+        }
+    }
+
+    /// Insert grouping separators into the integer part of a number.
+    fn group(&self, integer: &str) -> String {
+        let pattern = if self.use_grouping {
+            parse_grouping_pattern(&self.grouping_pattern)
+        } else {
+            None
+        };
+        let Some((sizes, repeat_last)) = pattern else {
+            return integer.to_owned();
+        };
+
+        let digits: Vec<char> = integer.chars().collect();
+        let mut groups = Vec::new();
+        let mut rest = &digits[..];
+        for i in 0.. {
+            let size = match sizes.get(i) {
+                Some(&size) => size,
+                None if repeat_last => sizes[sizes.len() - 1],
+                None => break,
+            };
+            if rest.len() <= size {
+                break;
+            }
+            let (head, tail) = rest.split_at(rest.len() - size);
+            groups.push(tail);
+            rest = head;
+        }
+        groups.push(rest);
+
+        let groups: Vec<String> = groups
+            .iter()
+            .rev()
+            .map(|group| group.iter().collect())
+            .collect();
+        groups.join(&self.grouping_separator)
+    }
+
+    /// Replace ASCII digits with the digits of `digits_type`.
+    fn localize_digits(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) => self
+                    .digits_type
+                    .checked_add(digit)
+                    .and_then(char::from_u32)
+                    .unwrap_or(c),
+                None => c,
+            })
+            .collect()
+    }
+
+    /// The value of a digit, either as ASCII or of `digits_type`.
+    fn digit_value(&self, c: char) -> Option<u32> {
+        c.to_digit(10).or_else(|| {
+            (c as u32)
+                .checked_sub(self.digits_type)
+                .filter(|&digit| digit < 10)
+        })
+    }
+
+    /// Find the first number in a string.
+    ///
+    /// The number may be surrounded by a negative sign in any of the
+    /// negative number formats. The indices of the result are in UTF-16 units.
+    pub fn parse(&self, input: &str) -> Option<ParsedNumber> {
+        let chars: Vec<char> = input.chars().collect();
+        let matches_at = |pos: usize, text: &str| {
+            let len = text.chars().count();
+            len > 0
+                && chars
+                    .get(pos..pos + len)
+                    .is_some_and(|slice| slice.iter().copied().eq(text.chars()))
+        };
+        let is_digit_at = |pos: usize| {
+            chars
+                .get(pos)
+                .is_some_and(|&c| self.digit_value(c).is_some())
+        };
+        let decimal_len = self.decimal_separator.chars().count();
+        let grouping_len = self.grouping_separator.chars().count();
+
+        let start = (0..chars.len()).find(|&pos| {
+            is_digit_at(pos)
+                || (matches_at(pos, &self.decimal_separator) && is_digit_at(pos + decimal_len))
+        })?;
+
+        let mut digits = String::from("0");
+        let mut end = start;
+        let mut has_fraction = false;
+        while end < chars.len() {
+            if let Some(digit) = self.digit_value(chars[end]) {
+                digits.push(char::from_digit(digit, 10).unwrap_or('0'));
+                end += 1;
+            } else if !has_fraction
+                && matches_at(end, &self.decimal_separator)
+                && is_digit_at(end + decimal_len)
+            {
+                digits.push('.');
+                has_fraction = true;
+                end += decimal_len;
+            } else if !has_fraction
+                && matches_at(end, &self.grouping_separator)
+                && is_digit_at(end + grouping_len)
+            {
+                end += grouping_len;
+            } else {
+                break;
+            }
+        }
+        let value: f64 = digits.parse().ok()?;
+
+        // Look for a negative sign, which may be separated from the number by spaces.
+        let mut before = start;
+        while before > 0 && chars[before - 1].is_whitespace() {
+            before -= 1;
+        }
+        let mut after = end;
+        while after < chars.len() && chars[after].is_whitespace() {
+            after += 1;
+        }
+        let signs = [self.negative_symbol.as_str(), "-"];
+        let sign_before = signs
+            .iter()
+            .map(|sign| sign.chars().count())
+            .zip(signs)
+            .find(|&(len, sign)| before >= len && matches_at(before - len, sign))
+            .map(|(len, _)| len);
+        let sign_after = signs.iter().find(|sign| matches_at(after, sign));
+
+        let (start, end, is_negative) = if let Some(len) = sign_before {
+            (before - len, end, true)
+        } else if before > 0 && chars[before - 1] == '(' && chars.get(after) == Some(&')') {
+            (before - 1, after + 1, true)
+        } else if let Some(sign) = sign_after {
+            (start, after + sign.chars().count(), true)
+        } else {
+            (start, end, false)
+        };
+
+        let utf16_index = |pos: usize| chars[..pos].iter().map(|c| c.len_utf16()).sum();
+        Some(ParsedNumber {
+            value: if is_negative { -value } else { value },
+            start: utf16_index(start),
+            end: utf16_index(end),
+        })
+    }
+
+    /// Parse a string that consists of a single number, surrounded by whitespace at most.
+    pub fn parse_whole(&self, input: &str) -> Option<f64> {
+        let number = self.parse(input)?;
+        let utf16: Vec<u16> = input.encode_utf16().collect();
+        let is_blank = |units: &[u16]| {
+            char::decode_utf16(units.iter().copied()).all(|c| c.is_ok_and(char::is_whitespace))
+        };
+
+        (is_blank(&utf16[..number.start]) && is_blank(&utf16[number.end..])).then_some(number.value)
+    }
+}
+
+/// Parse a grouping pattern such as `3;2;*`.
+///
+/// Returns the group sizes from right to left, and whether the last size is repeated.
+fn parse_grouping_pattern(pattern: &str) -> Option<(Vec<usize>, bool)> {
+    let (sizes, repeat_last) = match pattern.strip_suffix(";*") {
+        Some(sizes) => (sizes, true),
+        None => (pattern, false),
+    };
+
+    let sizes = sizes
+        .split(';')
+        .map(|size| size.parse().ok().filter(|&size| size > 0))
+        .collect::<Option<Vec<usize>>>()?;
+    Some((sizes, repeat_last))
+}
+
+/// Implements `flash.globalization.NumberFormatter`'s `init` method, which is called from the constructor.
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let locale = init_locale(activation, this, &requested)?;
+
+    NumberFormat::from_locale(locale, 2).write(activation, this)?;
+    set_internal(
+        activation,
+        this,
+        "_negativeNumberFormat",
+        locale.negative_number_format,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+fn format_with<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    value: f64,
+    is_integer: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = NumberFormat::read(activation, this)?;
+    let negative_format =
+        get_internal(activation, this, "_negativeNumberFormat")?.coerce_to_u32(activation)?;
+    let fractional_digits = if is_integer {
+        0
+    } else {
+        format.fractional_digits
+    };
+
+    let result = format.format(value, fractional_digits, negative_format);
+    set_last_operation_status(activation, this, LastOperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, result).into())
+}
+
+/// Implements `flash.globalization.NumberFormatter.formatInt`
+pub fn format_int<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_i32(activation, 0)?;
+    format_with(activation, this, value.into(), true)
+}
+
+/// Implements `flash.globalization.NumberFormatter.formatUint`
+pub fn format_uint<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_u32(activation, 0)?;
+    format_with(activation, this, value.into(), true)
+}
+
+/// Implements `flash.globalization.NumberFormatter.formatNumber`
+pub fn format_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    format_with(activation, this, value, false)
+}
+
+/// Implements `flash.globalization.NumberFormatter.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string_non_null(activation, 0, "parseString")?;
+    let format = NumberFormat::read(activation, this)?;
+
+    let (value, start, end, status) = match format.parse(&input.to_utf8_lossy()) {
+        Some(number) => (
+            number.value,
+            number.start as i32,
+            number.end as i32,
+            LastOperationStatus::NoError,
+        ),
+        None => (
+            f64::NAN,
+            NO_INDEX,
+            NO_INDEX,
+            LastOperationStatus::ParseError,
+        ),
+    };
+    set_last_operation_status(activation, this, status)?;
+
+    let result = activation
+        .avm2()
+        .classes()
+        .numberparseresult
+        .construct(activation, &[value.into(), start.into(), end.into()])?;
+    Ok(result.into())
+}
+
+/// Implements `flash.globalization.NumberFormatter.parseNumber`
+pub fn parse_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let input = args.get_string_non_null(activation, 0, "parseString")?;
+    let format = NumberFormat::read(activation, this)?;
+
+    let (value, status) = match format.parse_whole(&input.to_utf8_lossy()) {
+        Some(value) => (value, LastOperationStatus::NoError),
+        None => (f64::NAN, LastOperationStatus::ParseError),
+    };
+    set_last_operation_status(activation, this, status)?;
+
+    Ok(value.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::locale_data;
+    use super::{is_national_digits_type, NumberFormat, ParsedNumber};
+
+    fn format_for(name: &str) -> NumberFormat {
+        let (locale, _) = locale_data::lookup(name).unwrap();
+        NumberFormat::from_locale(locale, 2)
+    }
+
+    #[test]
+    fn format_numbers() {
+        let en = format_for("en-US");
+        assert_eq!(en.format(1234567.891, 2, 1), "1,234,567.89");
+        assert_eq!(en.format(-1234.5, 2, 0), "(1,234.50)");
+        assert_eq!(en.format(-0.001, 2, 1), "0.00");
+        assert_eq!(en.format(42.0, 0, 1), "42");
+
+        let de = format_for("de-DE");
+        assert_eq!(de.format(-1234567.891, 2, 1), "-1.234.567,89");
+
+        let ar = format_for("ar-SA");
+        assert_eq!(ar.format(12.5, 1, 1), "١٢٫٥");
+    }
+
+    #[test]
+    fn format_options() {
+        let mut format = format_for("en-US");
+        format.leading_zero = false;
+        format.trailing_zeros = false;
+        assert_eq!(format.format(0.5, 3, 1), ".5");
+        assert_eq!(format.format(2.0, 3, 1), "2");
+
+        format.grouping_pattern = "3;2;*".to_owned();
+        assert_eq!(format.format(123456789.0, 0, 1), "12,34,56,789");
+        format.grouping_pattern = "3".to_owned();
+        assert_eq!(format.format(123456789.0, 0, 1), "123456,789");
+        format.use_grouping = false;
+        assert_eq!(format.format(123456789.0, 0, 1), "123456789");
+    }
+
+    #[test]
+    fn digits_types() {
+        assert!(is_national_digits_type('0' as u32));
+        assert!(is_national_digits_type(0x104A0));
+        assert!(!is_national_digits_type(5));
+        assert!(!is_national_digits_type(u32::MAX));
+
+        let mut format = format_for("en-US");
+        format.digits_type = 0x0966;
+        assert_eq!(format.format(12.0, 0, 1), "१२");
+        assert_eq!(format.parse_whole("१२"), Some(12.0));
+
+        format.digits_type = u32::MAX;
+        assert_eq!(format.format(12.0, 0, 1), "12");
+        assert_eq!(format.parse_whole("12"), Some(12.0));
+    }
+
+    #[test]
+    fn parse_numbers() {
+        let en = format_for("en-US");
+        assert_eq!(
+            en.parse("total: -1,234.5 items"),
+            Some(ParsedNumber {
+                value: -1234.5,
+                start: 7,
+                end: 15,
+            })
+        );
+        assert_eq!(en.parse("(12)").map(|n| n.value), Some(-12.0));
+        assert_eq!(en.parse("12 -").map(|n| n.value), Some(-12.0));
+        assert_eq!(en.parse("no number"), None);
+
+        assert_eq!(en.parse_whole(" 1,000.25 "), Some(1000.25));
+        assert_eq!(en.parse_whole("1,000.25 apples"), None);
+
+        let fr = format_for("fr-FR");
+        assert_eq!(fr.parse_whole("1\u{a0}234,5"), Some(1234.5));
+    }
+}
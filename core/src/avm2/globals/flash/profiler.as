@@ -1,7 +1,3 @@
 package flash.profiler {
-    import __ruffle__.stub_method;
-
-    public function showRedrawRegions(on:Boolean, color:uint = 0xFF0000):void {
-        stub_method("flash.profiler", "showRedrawRegions");
-    }
+    public native function showRedrawRegions(on:Boolean, color:uint = 0xFF0000):void;
 }
@@ -0,0 +1,23 @@
+//! `flash.profiler` namespace
+
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use swf::Color;
+
+/// Implements `flash.profiler.showRedrawRegions`
+pub fn show_redraw_regions<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = if args.get_bool(0) {
+        Some(Color::from_rgb(args.get_u32(activation, 1)?, 255))
+    } else {
+        None
+    };
+    activation
+        .context
+        .stage
+        .set_redraw_regions_color(activation.context.gc_context, color);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,167 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::api_version::ApiVersion;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::sampler::{estimate_size, Sample, SampleKind};
+use crate::avm2::{
+    Activation, ArrayObject, ArrayStorage, ClassObject, Error, Multiname, Namespace, Object, QName,
+    Value,
+};
+use crate::string::AvmString;
+
+pub mod new_object_sample;
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler_mut().start();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler_mut().pause();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler_mut().stop();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.avm2.sampler_mut().clear();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.context.avm2.sampler_mut();
+    sampler.sample_deletions(activation.context.gc_context);
+    Ok((sampler.samples().len() as f64).into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sampler = activation.context.avm2.sampler_mut();
+    sampler.sample_deletions(activation.context.gc_context);
+    let samples = sampler.samples().to_vec();
+
+    // The objects representing the samples shouldn't show up as allocations themselves.
+    let was_sampling = sampler.is_sampling();
+    sampler.pause();
+    let result = samples
+        .into_iter()
+        .map(|sample| make_sample(activation, sample).map(Value::from))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|samples| {
+            ArrayObject::from_storage(activation, ArrayStorage::from_args(&samples))
+        });
+    if was_sampling {
+        activation.context.avm2.sampler_mut().start();
+    }
+
+    Ok(result?.into())
+}
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((estimate_size(args.get_value(0)) as f64).into())
+}
+
+fn sampler_class<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &'static str,
+) -> Result<ClassObject<'gc>, Error<'gc>> {
+    let namespace = Namespace::package(
+        "flash.sampler",
+        ApiVersion::AllVersions,
+        &mut activation.borrow_gc(),
+    );
+    let class = activation
+        .avm2()
+        .playerglobals_domain
+        .get_defined_value(activation, QName::new(namespace, name))?;
+    Ok(class
+        .as_object()
+        .and_then(|class| class.as_class_object())
+        .expect("flash.sampler classes are defined in playerglobal"))
+}
+
+/// Sets a `const` property of one of the sample classes, which can't be assigned to by scripts.
+fn init_public_property<'gc>(
+    object: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    let name = Multiname::new(activation.avm2().public_namespace_vm_internal, name);
+    object.init_property(&name, value, activation)
+}
+
+fn make_sample<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sample: Sample<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let class_name = match sample.kind {
+        SampleKind::Stack => "Sample",
+        SampleKind::NewObject { .. } => "NewObjectSample",
+        SampleKind::DeleteObject { .. } => "DeleteObjectSample",
+    };
+    let object = sampler_class(activation, class_name)?.construct(activation, &[])?;
+
+    let frame_class = sampler_class(activation, "StackFrame")?;
+    let mut frames = Vec::with_capacity(sample.stack.len());
+    for frame in sample.stack {
+        let frame_object = frame_class.construct(activation, &[])?;
+        let name = AvmString::new_utf8(activation.context.gc_context, frame.name);
+        init_public_property(frame_object, "name", name.into(), activation)?;
+        frames.push(frame_object.into());
+    }
+    let stack = ArrayObject::from_storage(activation, ArrayStorage::from_args(&frames))?;
+
+    init_public_property(object, "time", sample.time.into(), activation)?;
+    init_public_property(object, "stack", stack.into(), activation)?;
+    match sample.kind {
+        SampleKind::Stack => {}
+        SampleKind::NewObject { id, class } => {
+            init_public_property(object, "id", (id as f64).into(), activation)?;
+            init_public_property(object, "type", class.into(), activation)?;
+        }
+        SampleKind::DeleteObject { id, size } => {
+            init_public_property(object, "id", (id as f64).into(), activation)?;
+            init_public_property(object, "size", (size as f64).into(), activation)?;
+        }
+    }
+    Ok(object)
+}
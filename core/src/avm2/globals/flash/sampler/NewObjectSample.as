@@ -1,20 +1,11 @@
 package flash.sampler {
-    import __ruffle__.stub_getter;
-
     public final class NewObjectSample extends Sample {
         public const id:Number;
      
         public const type:Class;
 
-        public function get object():* {
-            stub_getter("flash.sampler.NewObjectSample", "object");
-            return {};
-        }
+        public native function get object():*;
 
-        public function get size():Number {
-            stub_getter("flash.sampler.NewObjectSample", "size");
-            return 0;
-        }
+        public native function get size():Number;
     }
 }
-
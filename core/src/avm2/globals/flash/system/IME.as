@@ -6,61 +6,39 @@
 package flash.system
 {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
     import __ruffle__.stub_getter;
     import __ruffle__.stub_setter;
 
     public final class IME extends EventDispatcher
     {
+        // The instance returned by `System.ime`, which receives `imeComposition` events.
+        internal static const _instance: IME = new IME();
+
         // The conversion mode of the current IME.
         public static var _conversionMode: String = "ALPHANUMERIC_HALF";
 
-        // Indicates whether the system IME is enabled (true) or disabled (false).
-        public static var _enabled: Boolean;
-
-        // The isSupported property is set to true if the IME class is available on the current platform, otherwise it is set to false.
-        private static var _isSupported: Boolean;
-
         // Causes the runtime to abandon any composition that is in progress.
-        public static function compositionAbandoned():void
-        {
-            stub_method("flash.system.IME", "compositionAbandoned");
-        }
+        public static native function compositionAbandoned():void;
 
         // Call this method when the selection within the composition has been updated, either interactively or programmatically.
-        public static function compositionSelectionChanged(start:int, end:int):void
-        {
-            stub_method("flash.system.IME", "compositionSelectionChanged");
-        }
+        public static native function compositionSelectionChanged(start:int, end:int):void;
 
         // Instructs the IME to select the first candidate for the current composition string.
-        public static function doConversion():void
-        {
-            stub_method("flash.system.IME", "doConversion");
-        }
+        public static native function doConversion():void;
 
         // Sets the IME composition string.
-        public static function setCompositionString(composition:String):void
-        {
-            stub_method("flash.system.IME", "setCompositionString");
-        }
+        public static native function setCompositionString(composition:String):void;
 
-        public function get isSupported() : Boolean
+        // The isSupported property is set to true if the IME class is available on the current platform, otherwise it is set to false.
+        public static function get isSupported() : Boolean
         {
-            return _isSupported;
+            return true;
         }
 
-        public static function get enabled():Boolean
-        {
-            stub_getter("flash.system.IME", "enabled");
-            return _enabled;
-        }
+        // Indicates whether the system IME is enabled (true) or disabled (false).
+        public static native function get enabled():Boolean;
 
-        public static function set enabled(value:Boolean):void
-        {
-            stub_setter("flash.system.IME", "enabled");
-            _enabled = value;
-        }
+        public static native function set enabled(value:Boolean):void;
 
         public static function get conversionMode():String
         {
@@ -2,8 +2,42 @@ package flash.system {
     import flash.events.EventDispatcher;
 
     public final class MessageChannel extends EventDispatcher {
+        private static var _channels:Object = {};
+        private static var _creating:Boolean = false;
+
+        internal var _id:uint;
+
         public function MessageChannel() {
-            super();
+            if (!_creating) {
+                throw new ArgumentError("Error #2012: MessageChannel$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        // Returns the one object that represents a channel in this worker.
+        internal static function fromId(id:uint):MessageChannel {
+            var channel:MessageChannel = _channels[id];
+            if (channel == null) {
+                _creating = true;
+                try {
+                    channel = new MessageChannel();
+                } finally {
+                    _creating = false;
+                }
+                channel._id = id;
+                _channels[id] = channel;
+            }
+            return channel;
+        }
+
+        public native function get messageAvailable():Boolean;
+        public native function get state():String;
+
+        public native function close():void;
+        public native function receive(blockUntilReceived:Boolean = false):*;
+        public native function send(arg:*, queueLimit:int = -1):void;
+
+        override public function toString():String {
+            return "[object MessageChannel]";
         }
     }
 }
@@ -12,6 +12,10 @@ package flash.system {
 
         public static native function setClipboard(string:String): void;
 
+        public static function get ime(): IME {
+            return IME._instance;
+        }
+
         public static function disposeXML(node:XML):void {
             stub_method("flash.system.System", "disposeXML");
         }
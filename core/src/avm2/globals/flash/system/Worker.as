@@ -1,11 +1,61 @@
 package flash.system {
-    public final class Worker {
+    import flash.events.EventDispatcher;
+
+    public final class Worker extends EventDispatcher {
+        private static var _workers:Object = {};
+        private static var _creating:Boolean = false;
+
+        internal var _id:uint;
+
         public function Worker() {
-            throw new ArgumentError("Error #2012: Worker$ class cannot be instantiated.", 2012);
+            if (!_creating) {
+                throw new ArgumentError("Error #2012: Worker$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        // Returns the one object that represents a worker in this worker.
+        internal static function fromId(id:uint):Worker {
+            var worker:Worker = _workers[id];
+            if (worker == null) {
+                _creating = true;
+                try {
+                    worker = new Worker();
+                } finally {
+                    _creating = false;
+                }
+                worker._id = id;
+                _workers[id] = worker;
+            }
+            return worker;
         }
 
-        public static function get isSupported():Boolean {
-            return false;
+        private static native function currentId():uint;
+
+        public static native function get isSupported():Boolean;
+
+        public static function get current():Worker {
+            return fromId(currentId());
         }
+
+        public function get isPrimordial():Boolean {
+            return this._id == 0;
+        }
+
+        public native function get state():String;
+
+        public function createMessageChannel(receiver:Worker):MessageChannel {
+            if (receiver == null) {
+                throw new TypeError("Error #2007: Parameter receiver must be non-null.", 2007);
+            }
+            return MessageChannel.fromId(this.createChannelId(receiver._id));
+        }
+
+        private native function createChannelId(receiverId:uint):uint;
+
+        public native function getSharedProperty(key:String):*;
+        public native function setSharedProperty(key:String, value:*):void;
+
+        public native function start():void;
+        public native function terminate():Boolean;
     }
 }
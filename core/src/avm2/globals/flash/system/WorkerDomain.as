@@ -1,9 +1,49 @@
 package flash.system {
+    import flash.utils.ByteArray;
+
     public final class WorkerDomain {
-        public static const isSupported: Boolean = false;
+        public static function get isSupported(): Boolean {
+            return Worker.isSupported;
+        }
+
+        private static var _current: WorkerDomain;
+        private static var _creating: Boolean = false;
 
         public function WorkerDomain() {
-            throw new ArgumentError("Error #2012: WorkerDomain$ class cannot be instantiated.", 2012)
+            if (!_creating) {
+                throw new ArgumentError("Error #2012: WorkerDomain$ class cannot be instantiated.", 2012);
+            }
         }
+
+        public static function get current(): WorkerDomain {
+            if (_current == null) {
+                _creating = true;
+                try {
+                    _current = new WorkerDomain();
+                } finally {
+                    _creating = false;
+                }
+            }
+            return _current;
+        }
+
+        public function createWorker(swf: ByteArray, giveAppPrivileges: Boolean = false): Worker {
+            if (swf == null) {
+                throw new TypeError("Error #2007: Parameter swf must be non-null.", 2007);
+            }
+            return Worker.fromId(this.createWorkerId(swf));
+        }
+
+        private native function createWorkerId(swf: ByteArray): uint;
+
+        public function listWorkers(): Vector.<Worker> {
+            var workers: Vector.<Worker> = new Vector.<Worker>();
+            for each (var id: uint in this.listWorkerIds()) {
+                workers.push(Worker.fromId(id));
+            }
+            return workers;
+        }
+
+        private native function listWorkerIds(): Array;
     }
-}
\ No newline at end of file
+}
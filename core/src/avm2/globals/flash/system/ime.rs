@@ -0,0 +1,90 @@
+//! `flash.system.IME` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::events::ImeEvent;
+
+/// Implements `flash.system.IME.enabled`'s getter
+pub fn get_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.ime_enabled().into())
+}
+
+/// Implements `flash.system.IME.enabled`'s setter
+pub fn set_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let enabled = args.get_bool(0);
+    let focus_tracker = activation.context.focus_tracker;
+    focus_tracker.set_ime_enabled(enabled, &mut activation.context);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.IME.setCompositionString`
+pub fn set_composition_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let composition = args.get_string(activation, 0)?;
+
+    if let Some(text) = activation.context.focus_tracker.get_edit_text() {
+        let event = ImeEvent::Preedit(composition.to_string(), None);
+        text.ime(event, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.IME.compositionAbandoned`
+pub fn composition_abandoned<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text) = activation.context.focus_tracker.get_edit_text() {
+        text.abandon_ime_composition(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.IME.compositionSelectionChanged`
+pub fn composition_selection_changed<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let start = args.get_i32(activation, 0)?.max(0) as usize;
+    let end = args.get_i32(activation, 1)?.max(0) as usize;
+
+    if let Some(text) = activation.context.focus_tracker.get_edit_text() {
+        text.set_ime_composition_selection(start, end, activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.IME.doConversion`
+pub fn do_conversion<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // We have no access to the candidates of the platform IME,
+    // so the composition is converted as-is.
+    if let Some(text) = activation.context.focus_tracker.get_edit_text() {
+        text.commit_ime_composition(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
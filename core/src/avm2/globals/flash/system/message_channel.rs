@@ -0,0 +1,85 @@
+//! `flash.system.MessageChannel` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::system::worker::{
+    block, from_shared_value, object_id, to_shared_value, wait_error,
+};
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::worker::ChannelId;
+
+fn channel_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<ChannelId, Error<'gc>> {
+    let namespace = activation.avm2().flash_system_internal;
+    object_id(activation, this, namespace)
+}
+
+/// Implements `MessageChannel.messageAvailable`
+pub fn get_message_available<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    Ok(activation.context.workers.message_available(id).into())
+}
+
+/// Implements `MessageChannel.state`
+pub fn get_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    Ok(activation.context.workers.channel_state(id).as_str().into())
+}
+
+/// Implements `MessageChannel.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    activation.context.workers.close(id);
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.receive`
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    let block_until_received = args.get_bool(0);
+
+    if block_until_received {
+        block(activation, |workers, limit| {
+            workers.wait_for_message(id, limit)
+        })
+        .map_err(|e| wait_error(activation, e, "", 0))?;
+    }
+
+    match activation.context.workers.receive(id) {
+        Some(message) => from_shared_value(activation, message),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `MessageChannel.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = channel_id(activation, this)?;
+    // Queue limits aren't enforced, as receivers are always run after a message is sent.
+    let message = to_shared_value(activation, args.get_value(0))?;
+    activation.context.workers.send(id, message);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,287 @@
+//! `flash.system.Worker` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf::{deserialize_value, serialize_value};
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::{error, illegal_operation_error, make_error_1502};
+use crate::avm2::object::{ByteArrayObject, ClassObject, EventObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error, Multiname, Namespace};
+use crate::context::UpdateContext;
+use crate::worker::{SharedValue, WaitError, WorkerEvent, Workers};
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use std::rc::Rc;
+use web_time::Instant;
+
+/// Reads the id of a worker, message channel, mutex or condition.
+pub fn object_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    namespace: Namespace<'gc>,
+) -> Result<u32, Error<'gc>> {
+    this.get_property(&Multiname::new(namespace, "_id"), activation)?
+        .coerce_to_u32(activation)
+}
+
+/// Returns the object that represents a worker, message channel, mutex or condition in this
+/// worker.
+pub fn object_from_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    class: ClassObject<'gc>,
+    namespace: Namespace<'gc>,
+    id: u32,
+) -> Result<Value<'gc>, Error<'gc>> {
+    class.call_property(
+        &Multiname::new(namespace, "fromId"),
+        &[id.into()],
+        activation,
+    )
+}
+
+/// Converts a value so that it can be sent to another worker.
+pub fn to_shared_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<SharedValue, Error<'gc>> {
+    if let Some(object) = value.as_object() {
+        let system = activation.avm2().flash_system_internal;
+        let concurrent = activation.avm2().flash_concurrent_internal;
+        let classes = activation.avm2().classes();
+        let by_reference: [(ClassObject<'gc>, Namespace<'gc>, fn(u32) -> SharedValue); 4] = [
+            (classes.worker, system, SharedValue::Worker),
+            (classes.messagechannel, system, SharedValue::Channel),
+            (classes.mutex, concurrent, SharedValue::Mutex),
+            (classes.condition, concurrent, SharedValue::Condition),
+        ];
+        for (class, namespace, shared) in by_reference {
+            if object.is_of_type(class.inner_class_definition(), &mut activation.context) {
+                return Ok(shared(object_id(activation, object, namespace)?));
+            }
+        }
+
+        if let Some(mut bytearray) = object.as_bytearray_mut(activation.context.gc_context) {
+            if let Some(memory) = bytearray.shared_memory().cloned() {
+                bytearray.flush_shared_memory();
+                return Ok(SharedValue::ByteArray(memory));
+            }
+        }
+    }
+
+    // AMF values can't be sent to other threads, so they're sent as bytes.
+    let amf = serialize_value(activation, value, AMFVersion::AMF3, &mut Default::default())
+        .unwrap_or(AmfValue::Undefined);
+    let mut lso = Lso::new(vec![Element::new("", Rc::new(amf))], "", AMFVersion::AMF3);
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+    Ok(SharedValue::Amf(bytes))
+}
+
+/// Converts a value that was sent by another worker.
+pub fn from_shared_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: SharedValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let system = activation.avm2().flash_system_internal;
+    let concurrent = activation.avm2().flash_concurrent_internal;
+    let (class, namespace, id) = match value {
+        SharedValue::Amf(bytes) => {
+            let Ok(lso) = flash_lso::read::Reader::default().parse(&bytes) else {
+                return Ok(Value::Undefined);
+            };
+            return match lso.body.first() {
+                Some(element) => deserialize_value(activation, &element.value),
+                None => Ok(Value::Undefined),
+            };
+        }
+        SharedValue::Worker(id) => (activation.avm2().classes().worker, system, id),
+        SharedValue::Channel(id) => (activation.avm2().classes().messagechannel, system, id),
+        SharedValue::Mutex(id) => (activation.avm2().classes().mutex, concurrent, id),
+        SharedValue::Condition(id) => (activation.avm2().classes().condition, concurrent, id),
+        SharedValue::ByteArray(memory) => {
+            let mc = activation.context.gc_context;
+            if let Some(bytearray) = activation.avm2().shared_byte_array(mc, &memory) {
+                return Ok(bytearray.into());
+            }
+            let storage = ByteArrayStorage::from_shared_memory(memory);
+            let bytearray = ByteArrayObject::from_storage(activation, storage)?;
+            activation.avm2().register_shared_byte_array(bytearray);
+            return Ok(bytearray.into());
+        }
+    };
+    object_from_id(activation, class, namespace, id)
+}
+
+/// The time until which the current worker may block.
+///
+/// Other workers are suspended while they block, but the primordial worker can't be, so it may
+/// only block for as long as its script may run.
+pub fn blocking_limit(activation: &Activation<'_, '_>) -> Instant {
+    activation.context.update_start + activation.context.max_execution_duration
+}
+
+/// Turns the failure of a blocking call into an error.
+pub fn wait_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    wait_error: WaitError,
+    not_owner_message: &str,
+    not_owner_code: u32,
+) -> Error<'gc> {
+    let err = match wait_error {
+        WaitError::NotOwner => {
+            illegal_operation_error(activation, not_owner_message, not_owner_code)
+        }
+        WaitError::Deadlock => error(
+            activation,
+            "A blocking call can't continue, as no other worker can run.",
+            0,
+        ),
+        WaitError::Timeout => return make_error_1502(activation),
+        WaitError::Terminated => error(activation, "The worker was terminated.", 0),
+    };
+    match err {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+/// Blocks the current worker, keeping shareable byte arrays in sync with the workers that run in
+/// the meantime.
+pub fn block<'gc, R>(
+    activation: &mut Activation<'_, 'gc>,
+    f: impl FnOnce(&Workers, Instant) -> R,
+) -> R {
+    let limit = blocking_limit(activation);
+    let mc = activation.context.gc_context;
+    activation.avm2().flush_shared_memory(mc);
+    let ret = f(activation.context.workers, limit);
+    activation.avm2().reload_shared_memory(mc);
+    ret
+}
+
+/// Dispatches the events that other workers caused since the last frame.
+pub fn dispatch_events(context: &mut UpdateContext<'_, '_>) {
+    for event in context.workers.take_events() {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        let system = activation.avm2().flash_system_internal;
+        let classes = activation.avm2().classes();
+        let (class, id, event_type) = match event {
+            WorkerEvent::State(id) => (classes.worker, id, "workerState"),
+            WorkerEvent::ChannelMessage(id) => (classes.messagechannel, id, "channelMessage"),
+            WorkerEvent::ChannelState(id) => (classes.messagechannel, id, "channelState"),
+        };
+        let target = match object_from_id(&mut activation, class, system, id) {
+            Ok(Value::Object(target)) => target,
+            Ok(_) => continue,
+            Err(err) => {
+                tracing::error!(
+                    "Encountered AVM2 error when dispatching `{event_type}` event: {err:?}"
+                );
+                continue;
+            }
+        };
+        drop(activation);
+
+        let event = EventObject::bare_default_event(context, event_type);
+        Avm2::dispatch_event(context, event, target);
+    }
+}
+
+/// Implements `Worker.currentId`
+pub fn current_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.workers.id().into())
+}
+
+/// Implements `Worker.isSupported`
+pub fn get_is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Workers::is_supported().into())
+}
+
+/// Implements `Worker.state`
+pub fn get_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = activation.avm2().flash_system_internal;
+    let id = object_id(activation, this, namespace)?;
+    Ok(activation.context.workers.state(id).as_str().into())
+}
+
+/// Implements `Worker.createChannelId`
+pub fn create_channel_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = activation.avm2().flash_system_internal;
+    let sender = object_id(activation, this, namespace)?;
+    let receiver = args.get_u32(activation, 0)?;
+    Ok(activation
+        .context
+        .workers
+        .create_channel(sender, receiver)
+        .into())
+}
+
+/// Implements `Worker.getSharedProperty`
+pub fn get_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = activation.avm2().flash_system_internal;
+    let id = object_id(activation, this, namespace)?;
+    let key = args.get_string(activation, 0)?.to_string();
+    match activation.context.workers.shared_property(id, &key) {
+        Some(value) => from_shared_value(activation, value),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Implements `Worker.setSharedProperty`
+pub fn set_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = activation.avm2().flash_system_internal;
+    let id = object_id(activation, this, namespace)?;
+    let key = args.get_string(activation, 0)?.to_string();
+    let value = to_shared_value(activation, args.get_value(1))?;
+    activation
+        .context
+        .workers
+        .set_shared_property(id, key, value);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.start`
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = activation.avm2().flash_system_internal;
+    let id = object_id(activation, this, namespace)?;
+    activation.context.workers.start(id);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.terminate`
+pub fn terminate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = activation.avm2().flash_system_internal;
+    let id = object_id(activation, this, namespace)?;
+    Ok(activation.context.workers.terminate(id).into())
+}
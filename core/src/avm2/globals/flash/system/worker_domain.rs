@@ -0,0 +1,105 @@
+//! `flash.system.WorkerDomain` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::{argument_error, error, type_error};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::loader::LoadBehavior;
+use crate::tag_utils::SwfMovie;
+use crate::worker::Workers;
+use crate::PlayerBuilder;
+
+/// Implements `WorkerDomain.createWorkerId`
+pub fn create_worker_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let swf = args.get_object(activation, 0, "swf")?;
+    let Some(bytes) = swf.as_bytearray().map(|swf| swf.bytes().to_vec()) else {
+        let class_name = swf.instance_of_class_name(activation.context.gc_context);
+        return Err(Error::AvmError(type_error(
+            activation,
+            &format!(
+                "Error #1034: Type Coercion failed: cannot convert {class_name}@00000000000 to flash.utils.ByteArray."
+            ),
+            1034,
+        )?));
+    };
+
+    if !Workers::is_supported() {
+        return Err(spawn_error(activation));
+    }
+
+    let url = activation.context.swf.url().to_string();
+    let movie = match SwfMovie::from_data(&bytes, url.clone(), Some(url)) {
+        Ok(movie) => movie,
+        Err(e) => {
+            tracing::warn!("Unable to create worker from invalid SWF: {e}");
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2004: One of the parameters is invalid.",
+                2004,
+            )?));
+        }
+    };
+
+    // The player can't be sent to another thread, so it's built on the worker's thread.
+    let player_version = activation.context.player_version;
+    let optimizer = activation.avm2().optimizer_enabled;
+    let register_ir = activation.avm2().register_ir_enabled;
+    let id = activation.context.workers.create_worker();
+    let result = activation.context.workers.spawn(id, move |workers| {
+        let player = PlayerBuilder::new()
+            .with_log(workers.log_backend())
+            .with_workers(workers)
+            .with_player_version(Some(player_version))
+            .with_avm2_optimizer(optimizer)
+            .with_avm2_register_ir(register_ir)
+            .with_load_behavior(LoadBehavior::Blocking)
+            .with_autoplay(true)
+            .with_movie(movie)
+            .build();
+        move || {
+            let mut player = player.lock().unwrap();
+            let dt = 1000.0 / player.frame_rate();
+            player.update_timers(dt);
+            player.run_frame();
+        }
+    });
+    if let Err(e) = result {
+        tracing::error!("Unable to spawn the thread of worker {id}: {e}");
+        return Err(spawn_error(activation));
+    }
+
+    Ok(id.into())
+}
+
+fn spawn_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match error(
+        activation,
+        "Workers can't be started, as threads can't be spawned on this platform.",
+        0,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+/// Implements `WorkerDomain.listWorkerIds`
+pub fn list_worker_ids<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ids: Vec<Value<'gc>> = activation
+        .context
+        .workers
+        .list()
+        .into_iter()
+        .map(Value::from)
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&ids))?.into())
+}
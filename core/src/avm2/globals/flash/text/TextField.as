@@ -133,9 +133,7 @@ package flash.text {
         public native function replaceText(beginIndex:int, endIndex:int, newText:String):void;
         public native function setSelection(beginIndex:int, endIndex:int):void;
 
-        public function insertXMLText(beginIndex:int, endIndex:int, text:String, paste:Boolean = false):void {
-            stub_method("flash.text.TextField", "insertXMLText");
-        }
+        public native function insertXMLText(beginIndex:int, endIndex:int, text:String, paste:Boolean = false):void;
 
         public function getCharIndexAtPoint(x:Number, y:Number):int {
             stub_method("flash.text.TextField", "getCharIndexAtPoint");
@@ -162,10 +160,7 @@ package flash.text {
             return 0;
         }
 
-        public function getImageReference(id:String):DisplayObject {
-            stub_method("flash.text.TextField", "getImageReference");
-            return null;
-        }
+        public native function getImageReference(id:String):DisplayObject;
 
         public function getLineIndexAtPoint(x:Number, y:Number):int {
             stub_method("flash.text.TextField", "getLineIndexAtPoint");
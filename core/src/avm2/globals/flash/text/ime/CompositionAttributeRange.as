@@ -0,0 +1,25 @@
+package flash.text.ime
+{
+    public final class CompositionAttributeRange
+    {
+        // The relative start position from the beginning of the current composition.
+        public var relativeStart:int;
+
+        // The relative end position from the beginning of the current composition.
+        public var relativeEnd:int;
+
+        // A property defining the current clause is being processed by the IME.
+        public var selected:Boolean;
+
+        // A property defining the current clause has been processed by the IME and the clause is waiting to be accepted or confirmed by the user.
+        public var converted:Boolean;
+
+        public function CompositionAttributeRange(relativeStart:int, relativeEnd:int, selected:Boolean, converted:Boolean)
+        {
+            this.relativeStart = relativeStart;
+            this.relativeEnd = relativeEnd;
+            this.selected = selected;
+            this.converted = converted;
+        }
+    }
+}
@@ -49,20 +49,33 @@ pub fn text_field_allocator<'gc>(
 }
 
 pub fn get_always_show_selection<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "alwaysShowSelection");
-    Ok(Value::Bool(false))
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.always_show_selection().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_always_show_selection<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "alwaysShowSelection");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args.get_bool(0);
+        this.set_always_show_selection(activation.context.gc_context, value);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -242,20 +255,33 @@ pub fn set_border_color<'gc>(
 }
 
 pub fn get_condense_white<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "condenseWhite");
-    Ok(Value::Bool(false))
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.condense_white().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_condense_white<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "condenseWhite");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args.get_bool(0);
+        this.set_condense_white(activation.context.gc_context, value);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -774,6 +800,54 @@ pub fn replace_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_image_reference<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let id = args.get_string(activation, 0)?;
+        if let Some(image) = this.image_by_id(&id) {
+            return Ok(image.object2());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+pub fn insert_xml_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let text_length = this.text_length();
+        let begin_index = (args.get_i32(activation, 0)?.max(0) as usize).min(text_length);
+        let end_index = (args.get_i32(activation, 1)?.max(0) as usize).min(text_length);
+        let value = args.get_string_non_null(activation, 2, "text")?;
+        let paste = args.get_bool(3);
+
+        // Text in the rich text clipboard format is parsed like `htmlText`: the
+        // `<flashrichtext>` root element is ignored, like any tag `htmlText` doesn't support.
+
+        this.replace_html_text(
+            begin_index,
+            end_index,
+            &value,
+            paste,
+            &mut activation.context,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_caret_index<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -302,6 +302,36 @@ pub fn set_length<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_shareable<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bytearray) = this.as_bytearray() {
+        return Ok(bytearray.shared_memory().is_some().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_shareable<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let shareable = args.get(0).unwrap_or(&Value::Undefined).coerce_to_boolean();
+    if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+        let was_shareable = bytearray.shared_memory().is_some();
+        bytearray.set_shareable(shareable);
+        drop(bytearray);
+        if shareable && !was_shareable {
+            activation.avm2().register_shared_byte_array(this);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_endian<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
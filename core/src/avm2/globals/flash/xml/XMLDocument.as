@@ -42,23 +42,32 @@ import flash.xml.XMLNodeType;
 
       private function _convertXmlNode(original: XML): XMLNode {
          var nodeType = _convertXmlNodeType(original.nodeKind());
-         // TODO: Use namespace().prefix
          var nodeValue = nodeType == XMLNodeType.ELEMENT_NODE ?
-            original.localName() : original.toString();
+            _qualifiedName(original) : original.toString();
          var result = new XMLNode(nodeType, nodeValue);
          for each (var originalChild in original.children()) {
             result.appendChild(_convertXmlNode(originalChild));
          }
-         var attributeList = original.attributes();
          var attributes = {};
+         if (nodeType == XMLNodeType.ELEMENT_NODE) {
+            // The legacy API exposes namespace declarations as regular attributes.
+            for each (var ns in original.namespaceDeclarations()) {
+               attributes[ns.prefix ? "xmlns:" + ns.prefix : "xmlns"] = ns.uri;
+            }
+         }
+         var attributeList = original.attributes();
          for each (var attribute in attributeList) {
-            // TODO: Use namespace().prefix
-            attributes[attribute.localName()] = attribute.toString();
+            attributes[_qualifiedName(attribute)] = attribute.toString();
          }
          result.attributes = attributes;
          return result;
       }
 
+      private function _qualifiedName(original: XML): String {
+         var prefix = original.namespace().prefix;
+         return prefix ? prefix + ":" + original.localName() : original.localName();
+      }
+
       private function _convertXmlNodeType(kind: String): uint {
          if (kind == "text") {
             return XMLNodeType.TEXT_NODE;
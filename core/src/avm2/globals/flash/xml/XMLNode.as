@@ -1,9 +1,6 @@
 package flash.xml
 {
 
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-
     import flash.xml.XMLNode;
     import flash.xml.XMLNodeType;
 
@@ -132,13 +129,30 @@ package flash.xml
         }
 
         public function getNamespaceForPrefix(prefix: String): String {
-            stub_method("flash.xml.XMLNode", "getNamespaceForPrefix");
-            return "";
+            var attributeName = prefix ? "xmlns:" + prefix : "xmlns";
+            for (var node: XMLNode = this; node != null; node = node.parentNode) {
+                if (node.attributes.hasOwnProperty(attributeName)) {
+                    return node.attributes[attributeName];
+                }
+            }
+            return null;
         }
 
         public function getPrefixForNamespace(ns: String): String {
-            stub_method("flash.xml.XMLNode", "getPrefixForNamespace");
-            return "";
+            for (var node: XMLNode = this; node != null; node = node.parentNode) {
+                for (var key in node.attributes) {
+                    if (node.attributes[key] != ns) {
+                        continue;
+                    }
+                    if (key == "xmlns") {
+                        return "";
+                    }
+                    if (key.indexOf("xmlns:") == 0) {
+                        return key.substring(6);
+                    }
+                }
+            }
+            return null;
         }
 
         public function get localName(): String {
@@ -166,8 +180,10 @@ package flash.xml
         }
 
         public function get namespaceURI(): String {
-            stub_getter("flash.xml.XMLNode", "namespaceURI");
-            return null;
+            if (nodeName == null) {
+                return null;
+            }
+            return getNamespaceForPrefix(prefix);
         }
 
         public function toString(): String {
@@ -160,6 +160,7 @@ include "flash/events/FullScreenEvent.as"
 include "flash/events/GameInputEvent.as"
 include "flash/events/GesturePhase.as"
 include "flash/events/HTTPStatusEvent.as"
+include "flash/events/IMEEvent.as"
 include "flash/events/IOErrorEvent.as"
 include "flash/events/KeyboardEvent.as"
 include "flash/events/NetDataEvent.as"
@@ -348,6 +349,9 @@ include "flash/text/TextInteractionMode.as"
 include "flash/text/TextLineMetrics.as"
 include "flash/text/TextRenderer.as"
 
+include "flash/text/ime/CompositionAttributeRange.as"
+include "flash/text/ime/IIMEClient.as"
+
 include "flash/text/engine/BreakOpportunity.as"
 include "flash/text/engine/CFFHinting.as"
 include "flash/text/engine/ContentElement.as"
@@ -2,6 +2,8 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::e4x::is_xml_name;
+use crate::avm2::error::make_error_1098;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::object::{namespace_allocator, Object, TObject};
 use crate::avm2::value::Value;
@@ -9,7 +11,7 @@ use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
-use crate::{avm2_stub_constructor, avm2_stub_getter};
+use crate::string::AvmString;
 use gc_arena::GcCell;
 
 /// Implements `Namespace`'s instance initializer.
@@ -19,42 +21,102 @@ pub fn instance_init<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this.as_namespace_object() {
-        let uri_value = match args {
-            [_prefix, uri] => {
-                avm2_stub_constructor!(activation, "Namespace", "Namespace prefix not supported");
-                Some(*uri)
-            }
-            [uri] => Some(*uri),
-            _ => None,
-        };
-
         let api_version = activation.avm2().root_api_version;
 
-        let namespace = match uri_value {
-            Some(Value::Object(Object::QNameObject(qname))) => qname
-                .uri()
-                .map(|uri| Namespace::package(uri, api_version, &mut activation.borrow_gc()))
-                .unwrap_or_else(|| Namespace::any(activation.context.gc_context)),
-            Some(val) => Namespace::package(
-                val.coerce_to_string(activation)?,
-                api_version,
-                &mut activation.borrow_gc(),
+        let (namespace, prefix) = match args {
+            // ECMA-357 13.2.2 The Namespace Constructor, steps 3-4
+            [prefix_value, uri_value] => {
+                let namespace = match uri_value {
+                    Value::Object(Object::QNameObject(qname)) => qname
+                        .uri()
+                        .map(|uri| {
+                            Namespace::package(uri, api_version, &mut activation.borrow_gc())
+                        })
+                        .unwrap_or_else(|| Namespace::any(activation.context.gc_context)),
+                    val => Namespace::package(
+                        val.coerce_to_string(activation)?,
+                        api_version,
+                        &mut activation.borrow_gc(),
+                    ),
+                };
+
+                let prefix = if namespace.as_uri().is_empty() {
+                    match prefix_value {
+                        Value::Undefined => Some(AvmString::default()),
+                        val => {
+                            let prefix = val.coerce_to_string(activation)?;
+                            if !prefix.is_empty() {
+                                return Err(make_error_1098(activation, prefix));
+                            }
+                            Some(prefix)
+                        }
+                    }
+                } else {
+                    match prefix_value {
+                        Value::Undefined => None,
+                        val => {
+                            let prefix = val.coerce_to_string(activation)?;
+                            if prefix.is_empty() || is_xml_name(prefix) {
+                                Some(prefix)
+                            } else {
+                                None
+                            }
+                        }
+                    }
+                };
+
+                (namespace, prefix)
+            }
+            // ECMA-357 13.2.2 The Namespace Constructor, step 5
+            [Value::Object(Object::NamespaceObject(ns))] => (ns.namespace(), ns.prefix()),
+            [Value::Object(Object::QNameObject(qname))] => match qname.uri() {
+                Some(uri) => (
+                    Namespace::package(uri, api_version, &mut activation.borrow_gc()),
+                    None,
+                ),
+                None => (Namespace::any(activation.context.gc_context), None),
+            },
+            [val] => {
+                let uri = val.coerce_to_string(activation)?;
+                let prefix = if uri.is_empty() {
+                    Some(AvmString::default())
+                } else {
+                    None
+                };
+
+                (
+                    Namespace::package(uri, api_version, &mut activation.borrow_gc()),
+                    prefix,
+                )
+            }
+            _ => (
+                activation.avm2().public_namespace_base_version,
+                Some(AvmString::default()),
             ),
-            None => activation.avm2().public_namespace_base_version,
         };
 
         this.init_namespace(activation.context.gc_context, namespace);
+        this.set_prefix(activation.context.gc_context, prefix);
     }
     Ok(Value::Undefined)
 }
 
+/// Implements `Namespace` when called as a function (ECMA-357 13.2.1).
 fn class_call<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_constructor!(activation, "Namespace");
-    Err("Namespace constructor is a stub.".into())
+    if let [Value::Object(Object::NamespaceObject(_))] = args {
+        return Ok(args[0]);
+    }
+
+    activation
+        .avm2()
+        .classes()
+        .namespace
+        .construct(activation, args)
+        .map(|ns| ns.into())
 }
 
 /// Implements `Namespace`'s native instance initializer.
@@ -79,13 +141,12 @@ pub fn class_init<'gc>(
 
 /// Implements `Namespace.prefix`'s getter
 pub fn prefix<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if this.as_namespace_object().is_some() {
-        avm2_stub_getter!(activation, "Namespace", "prefix");
-        return Ok("".into());
+    if let Some(o) = this.as_namespace_object() {
+        return Ok(o.prefix().map_or(Value::Undefined, |prefix| prefix.into()));
     }
 
     Ok(Value::Undefined)
@@ -85,7 +85,15 @@ pub fn init<'gc>(
         };
         if &*local != b"*" {
             this.set_local_name(activation.context.gc_context, local);
-            Some(activation.avm2().find_public_namespace())
+            // ECMA-357 13.3.2 step 5: use GetDefaultNamespace()
+            match activation.avm2().default_xml_namespace() {
+                Some(uri) => Some(Namespace::package(
+                    uri,
+                    ApiVersion::AllVersions,
+                    &mut activation.borrow_gc(),
+                )),
+                None => Some(activation.avm2().find_public_namespace()),
+            }
         } else {
             None
         }
@@ -1,15 +1,13 @@
 //! XML builtin and prototype
 
-use crate::avm2::api_version::ApiVersion;
-use crate::avm2::e4x::{name_to_multiname, E4XNode, E4XNodeKind};
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::e4x::{name_to_multiname, E4XNamespace, E4XNode, E4XNodeKind};
 use crate::avm2::error::{make_error_1117, type_error};
 pub use crate::avm2::object::xml_allocator;
-use crate::avm2::object::{
-    E4XOrXml, NamespaceObject, QNameObject, TObject, XmlListObject, XmlObject,
-};
+use crate::avm2::object::{E4XOrXml, QNameObject, TObject, XmlListObject, XmlObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::string::AvmString;
-use crate::avm2::{Activation, ArrayObject, Error, Multiname, Namespace, Object, Value};
+use crate::avm2::{Activation, ArrayObject, Error, Multiname, Object, Value};
 use crate::avm2_stub_method;
 
 fn ill_formed_markup_err<'gc>(
@@ -138,6 +136,7 @@ pub fn set_name<'gc>(
     let xml = this.as_xml_object().unwrap();
     let node = xml.node();
 
+    // 1. If x.[[Class]] ∈ {"text", "comment"}, return
     let is_attribute_or_element = matches!(
         &*node.kind(),
         E4XNodeKind::Attribute(_)
@@ -151,45 +150,78 @@ pub fn set_name<'gc>(
 
     let new_name = args.get_value(0);
 
-    let new_name = if let Some(qname) = new_name.as_object().and_then(|q| q.as_qname_object()) {
-        let has_no_ns = qname.name().is_any_namespace()
-            || (qname.name().namespace_set().len() == 1
-                && qname.name().namespace_set()[0].is_public());
-        if !has_no_ns {
-            avm2_stub_method!(activation, "XML", "setName", "with QName namespaces");
-        }
-        qname.local_name()
-    } else {
-        new_name.coerce_to_string(activation)?
-    };
+    // 2. If (Type(name) is Object) and (name.[[Class]] == "QName") and (name.uri == null)
+    //     2.a. Let name = name.localName
+    // 3. Let n = a new QName created as if by calling the constructor new QName(name)
+    let (namespace, new_name) =
+        if let Some(qname) = new_name.as_object().and_then(|q| q.as_qname_object()) {
+            (qname.uri(), qname.local_name())
+        } else {
+            (
+                activation.avm2().default_xml_namespace(),
+                new_name.coerce_to_string(activation)?,
+            )
+        };
 
     let is_name_valid = crate::avm2::e4x::is_xml_name(new_name);
     if !is_name_valid {
         return Err(make_error_1117(activation, new_name));
     }
 
+    // 4. If x.[[Class]] == "processing-instruction", let n.uri be the empty string
+    let namespace = namespace
+        .filter(|uri| {
+            !uri.is_empty() && !matches!(*node.kind(), E4XNodeKind::ProcessingInstruction(_))
+        })
+        .map(E4XNamespace::new_uri);
+
+    // 5. Let x.[[Name]] = n
     node.set_local_name(new_name, activation.context.gc_context);
+    node.set_namespace(namespace, activation.context.gc_context);
+
+    // 6. - 8. Call [[AddInScopeNamespace]] with a new Namespace(n.prefix, n.uri)
+    // NOTE: Our QNames don't have prefixes, so this never declares anything.
 
     Ok(Value::Undefined)
 }
 
+/// Constructs a namespace from a value, as if by calling `Namespace(value)`.
+fn namespace_from_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<E4XNamespace<'gc>, Error<'gc>> {
+    if let Some(namespace) = value.as_object().and_then(|o| o.as_namespace_object()) {
+        return Ok(E4XNamespace::from_namespace_object(namespace));
+    }
+
+    let namespace = activation
+        .avm2()
+        .classes()
+        .namespace
+        .construct(activation, &[value])?;
+    Ok(E4XNamespace::from_namespace_object(
+        namespace
+            .as_namespace_object()
+            .expect("Namespace constructor should return a Namespace"),
+    ))
+}
+
 // namespace_internal_impl(hasPrefix:Boolean, prefix:String = null):*
 pub fn namespace_internal_impl<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "XML", "namespace");
+    let xml = this.as_xml_object().unwrap();
+    let node = *xml.node();
 
-    // FIXME:
     // 1. Let y = x
     // 2. Let inScopeNS = { }
     // 3. While (y is not null)
     //     a. For each ns in y.[[InScopeNamespaces]]
-    //     ....
-
-    let xml = this.as_xml_object().unwrap();
-    let node = xml.node();
+    //         i. If there exists no n ∈ inScopeNS, such that n.prefix == ns.prefix
+    //             1. Let inScopeNS = inScopeNS ∪ { ns }
+    //     b. Let y = y.[[Parent]]
 
     // 4. If prefix was not specified
     if args[0] == Value::Bool(false) {
@@ -205,38 +237,45 @@ pub fn namespace_internal_impl<'gc>(
         }
 
         // b. Return the result of calling the [[GetNamespace]] method of x.[[Name]] with argument inScopeNS
-        // FIXME: Use inScopeNS
-        let namespace = xml.namespace(activation);
-        Ok(NamespaceObject::from_namespace(activation, namespace)?.into())
+        Ok(node.get_namespace().as_namespace_object(activation)?.into())
     } else {
         // a. Let prefix = ToString(prefix)
         let prefix = args.get_string(activation, 1)?;
 
         // b. Find a Namespace ns ∈ inScopeNS, such that ns.prefix = prefix. If no such ns exists, let ns = undefined.
         // c. Return ns
-
-        // FIXME: Nodes currently either have zero or one namespace, which has the prefix "" (empty string)
-        Ok(match node.namespace() {
-            Some(ns) if prefix.is_empty() => {
-                let namespace = Namespace::package(
-                    ns,
-                    ApiVersion::AllVersions,
-                    &mut activation.context.borrow_gc(),
-                );
-                NamespaceObject::from_namespace(activation, namespace)?.into()
-            }
-            _ => Value::Undefined,
-        })
+        match node
+            .in_scope_namespaces()
+            .into_iter()
+            .find(|ns| ns.prefix == Some(prefix))
+        {
+            Some(ns) => Ok(ns.as_namespace_object(activation)?.into()),
+            None => Ok(Value::Undefined),
+        }
     }
 }
 
 pub fn in_scope_namespaces<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "XML", "inScopeNamespaces");
-    Ok(ArrayObject::empty(activation)?.into())
+    let xml = this.as_xml_object().unwrap();
+
+    // 1. - 3. Collect the in-scope namespaces, the closest declaration of each prefix winning.
+    let mut namespaces = xml.node().in_scope_namespaces();
+
+    // NOTE: avmplus returns the default namespace instead of an empty array.
+    if namespaces.is_empty() {
+        namespaces.push(E4XNamespace::default_namespace());
+    }
+
+    // 4. - 6. Return an array of the namespaces
+    let namespaces = namespaces
+        .into_iter()
+        .map(|ns| ns.as_namespace_object(activation).map(Value::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&namespaces))?.into())
 }
 
 pub fn namespace_declarations<'gc>(
@@ -253,11 +292,107 @@ pub fn namespace_declarations<'gc>(
         return Ok(ArrayObject::empty(activation)?.into());
     }
 
-    // TODO: (We are missing [[InScopeNamespaces]])
-    // Step 3. Let y = x.[[Parent]
-    // ....
-    avm2_stub_method!(activation, "XML", "namespaceDeclarations");
-    Ok(ArrayObject::empty(activation)?.into())
+    // 3. Let y = x.[[Parent]]
+    // 4. Let ancestorNS = { }, and collect the in-scope namespaces of all ancestors into it
+    let ancestor_namespaces = node
+        .parent()
+        .map(|parent| parent.in_scope_namespaces())
+        .unwrap_or_default();
+
+    // 5. Let declaredNS = { }
+    // 6. For each ns in x.[[InScopeNamespaces]]
+    //     a. If there exists no n ∈ ancestorNS, such that n.prefix == ns.prefix and n.uri == ns.uri
+    //         i. Let declaredNS = declaredNS ∪ { ns }
+    let declared_namespaces: Vec<_> = node
+        .declared_namespaces()
+        .iter()
+        .filter(|ns| {
+            !ancestor_namespaces
+                .iter()
+                .any(|ans| ans.prefix == ns.prefix && ans.uri == ns.uri)
+        })
+        .copied()
+        .collect();
+
+    // 7. - 9. Return an array of the declared namespaces
+    let declared_namespaces = declared_namespaces
+        .into_iter()
+        .map(|ns| ns.as_namespace_object(activation).map(Value::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&declared_namespaces))?
+            .into(),
+    )
+}
+
+pub fn add_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let xml = this.as_xml_object().unwrap();
+
+    // 1. Let ns a Namespace constructed as if by calling the function Namespace(namespace)
+    let namespace = namespace_from_value(activation, args.get_value(0))?;
+
+    // 2. Call the [[AddInScopeNamespace]] method of x with parameter ns
+    xml.node()
+        .add_in_scope_namespace(namespace, activation.gc());
+
+    // 3. Return x
+    Ok(xml.into())
+}
+
+pub fn remove_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let xml = this.as_xml_object().unwrap();
+
+    let namespace = namespace_from_value(activation, args.get_value(0))?;
+    xml.node().remove_namespace(namespace, activation.gc());
+
+    Ok(xml.into())
+}
+
+pub fn set_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let xml = this.as_xml_object().unwrap();
+    let node = *xml.node();
+
+    // 1. If x.[[Class]] ∈ {"text", "comment", "processing-instruction"}, return
+    if !node.is_element() && !node.is_attribute() {
+        return Ok(Value::Undefined);
+    }
+
+    // 2. Let ns2 = a new Namespace created as if by calling the constructor new Namespace(ns)
+    let namespace = namespace_from_value(activation, args.get_value(0))?;
+
+    // 3. Let x.[[Name]] = a new QName created as if by calling the constructor new QName(ns2, x.[[Name]])
+    node.set_namespace(
+        (!namespace.uri.is_empty()).then_some(namespace),
+        activation.gc(),
+    );
+
+    // 4. If x.[[Class]] == "attribute"
+    //     a. If x.[[Parent]] == null, return
+    //     b. Call x.[[Parent]].[[AddInScopeNamespace]](ns2)
+    // 5. If x.[[Class]] == "element"
+    //     a. Call x.[[AddInScopeNamespace]](ns2)
+    let target = if node.is_attribute() {
+        node.parent()
+    } else {
+        Some(node)
+    };
+    if let Some(target) = target {
+        target.add_in_scope_namespace(namespace, activation.gc());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn local_name<'gc>(
@@ -80,6 +80,7 @@ impl<'gc> ArrayObject<'gc> {
         instance.install_instance_slots(activation.context.gc_context);
 
         class.call_native_init(instance.into(), &[], activation)?;
+        activation.context.avm2.sample_allocation(instance, class);
 
         Ok(instance)
     }
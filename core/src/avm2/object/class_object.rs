@@ -895,6 +895,7 @@ impl<'gc> TObject<'gc> for ClassObject<'gc> {
         let instance = instance_allocator(self, activation)?;
 
         instance.install_instance_slots(activation.context.gc_context);
+        activation.context.avm2.sample_allocation(instance, self);
 
         self.call_init(instance.into(), arguments, activation)?;
 
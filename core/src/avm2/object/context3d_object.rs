@@ -13,8 +13,8 @@ use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc, GcCell, GcWeak, Mutation};
 use ruffle_render::backend::{
     BufferUsage, Context3D, Context3DBlendFactor, Context3DCommand, Context3DCompareMode,
-    Context3DTextureFormat, Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
-    Texture,
+    Context3DStencilAction, Context3DTextureFormat, Context3DTriangleFace,
+    Context3DVertexBufferFormat, ProgramType, ShaderModule, Texture,
 };
 use ruffle_render::commands::CommandHandler;
 use std::cell::{Cell, Ref, RefMut};
@@ -47,6 +47,7 @@ impl<'gc> Context3DObject<'gc> {
                 base: RefLock::new(ScriptObjectData::new(class)),
                 render_context: Cell::new(Some(context)),
                 stage3d,
+                disposed: Cell::new(false),
             },
         ))
         .into();
@@ -61,6 +62,18 @@ impl<'gc> Context3DObject<'gc> {
         self.0.stage3d
     }
 
+    pub fn is_disposed(&self) -> bool {
+        self.0.disposed.get()
+    }
+
+    /// Drops the underlying render context, which frees all of the GPU resources
+    /// created through it. Any further use of this object (or of the buffers,
+    /// textures and programs it created) throws an error.
+    pub fn dispose(&self) {
+        self.0.disposed.set(true);
+        drop(self.0.render_context.take());
+    }
+
     pub fn with_context_3d<R>(&self, f: impl FnOnce(&mut dyn Context3D) -> R) -> R {
         // Temporarily take ownership of the Context3D instance.
         let cell = &self.0.render_context;
@@ -309,6 +322,9 @@ impl<'gc> Context3DObject<'gc> {
 
     // Renders our finalized frame to the screen, as part of the Ruffle rendering process.
     pub fn render(&self, context: &mut RenderContext<'_, 'gc>) {
+        if self.is_disposed() {
+            return;
+        }
         self.with_context_3d(|context3d| {
             if context3d.should_render() {
                 let handle = context3d.bitmap_handle();
@@ -478,6 +494,67 @@ impl<'gc> Context3DObject<'gc> {
             ctx.process_command(Context3DCommand::SetScissorRectangle { rect })
         });
     }
+
+    pub(crate) fn set_stencil_actions(
+        &self,
+        triangle_face: Context3DTriangleFace,
+        compare_mode: Context3DCompareMode,
+        action_on_both_pass: Context3DStencilAction,
+        action_on_depth_fail: Context3DStencilAction,
+        action_on_depth_pass_stencil_fail: Context3DStencilAction,
+    ) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::SetStencilActions {
+                triangle_face,
+                compare_mode,
+                action_on_both_pass,
+                action_on_depth_fail,
+                action_on_depth_pass_stencil_fail,
+            })
+        });
+    }
+
+    pub(crate) fn set_stencil_reference_value(
+        &self,
+        reference_value: u32,
+        read_mask: u32,
+        write_mask: u32,
+    ) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::SetStencilReferenceValue {
+                reference_value,
+                read_mask,
+                write_mask,
+            })
+        });
+    }
+
+    pub(crate) fn dispose_index_buffer(&self, buffer: IndexBuffer3DObject<'gc>) {
+        let handle = buffer.handle();
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::DisposeIndexBuffer { buffer: &*handle })
+        });
+    }
+
+    pub(crate) fn dispose_vertex_buffer(&self, buffer: VertexBuffer3DObject<'gc>) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::DisposeVertexBuffer {
+                buffer: buffer.handle(),
+            })
+        });
+    }
+
+    pub(crate) fn dispose_texture(&self, texture: Rc<dyn Texture>) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::DisposeTexture { texture })
+        });
+    }
+
+    pub(crate) fn dispose_shaders(&self, module: Rc<dyn ShaderModule>) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::DisposeShaders { module })
+        });
+    }
 }
 
 #[derive(Collect)]
@@ -490,6 +567,8 @@ pub struct Context3DData<'gc> {
     render_context: Cell<Option<Box<dyn Context3D>>>,
 
     stage3d: Stage3DObject<'gc>,
+
+    disposed: Cell<bool>,
 }
 
 impl<'gc> TObject<'gc> for Context3DObject<'gc> {
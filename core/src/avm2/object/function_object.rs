@@ -104,6 +104,12 @@ impl<'gc> FunctionObject<'gc> {
 
         this.0.write(activation.context.gc_context).prototype = Some(es3_proto);
 
+        let function_class = activation.avm2().classes().function;
+        activation
+            .context
+            .avm2
+            .sample_allocation(this.into(), function_class);
+
         Ok(this)
     }
 
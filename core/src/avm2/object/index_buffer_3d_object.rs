@@ -36,6 +36,7 @@ impl<'gc> IndexBuffer3DObject<'gc> {
                 context3d,
                 handle: RefCell::new(handle),
                 count: Cell::new(0),
+                disposed: Cell::new(false),
             },
         ))
         .into();
@@ -61,6 +62,18 @@ impl<'gc> IndexBuffer3DObject<'gc> {
     pub fn context3d(&self) -> Context3DObject<'gc> {
         self.0.context3d
     }
+
+    /// Whether `dispose()` was called on this object, or on the `Context3D` that created it.
+    pub fn is_disposed(&self) -> bool {
+        self.0.disposed.get() || self.0.context3d.is_disposed()
+    }
+
+    pub fn dispose(&self) {
+        if !self.is_disposed() {
+            self.0.disposed.set(true);
+            self.0.context3d.dispose_index_buffer(*self);
+        }
+    }
 }
 
 #[derive(Collect)]
@@ -73,6 +86,8 @@ pub struct IndexBuffer3DObjectData<'gc> {
 
     count: Cell<usize>,
 
+    disposed: Cell<bool>,
+
     context3d: Context3DObject<'gc>,
 }
 
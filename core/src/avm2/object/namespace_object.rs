@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Namespace;
@@ -22,6 +23,7 @@ pub fn namespace_allocator<'gc>(
         NamespaceObjectData {
             base,
             namespace: activation.context.avm2.public_namespace_base_version,
+            prefix: Some(AvmString::default()),
         },
     ))
     .into())
@@ -52,20 +54,48 @@ pub struct NamespaceObjectData<'gc> {
 
     /// The namespace name this object is associated with.
     namespace: Namespace<'gc>,
+
+    /// The XML prefix bound to this namespace, if any.
+    ///
+    /// `None` represents an `undefined` prefix, which E4X uses for namespaces
+    /// that were created without one and must never be serialized with one.
+    prefix: Option<AvmString<'gc>>,
 }
 
 impl<'gc> NamespaceObject<'gc> {
     /// Box a namespace into an object.
+    ///
+    /// As with `new Namespace(uri)`, the empty namespace gets an empty prefix
+    /// and every other namespace is left without one.
     pub fn from_namespace(
         activation: &mut Activation<'_, 'gc>,
         namespace: Namespace<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let prefix = if namespace.as_uri().is_empty() {
+            Some(AvmString::default())
+        } else {
+            None
+        };
+
+        Self::from_ns_and_prefix(activation, namespace, prefix)
+    }
+
+    /// Box a namespace into an object, binding it to the given prefix.
+    pub fn from_ns_and_prefix(
+        activation: &mut Activation<'_, 'gc>,
+        namespace: Namespace<'gc>,
+        prefix: Option<AvmString<'gc>>,
     ) -> Result<Object<'gc>, Error<'gc>> {
         let class = activation.avm2().classes().namespace;
         let base = ScriptObjectData::new(class);
 
         let this: Object<'gc> = NamespaceObject(GcCell::new(
             activation.context.gc_context,
-            NamespaceObjectData { base, namespace },
+            NamespaceObjectData {
+                base,
+                namespace,
+                prefix,
+            },
         ))
         .into();
         this.install_instance_slots(activation.context.gc_context);
@@ -82,6 +112,14 @@ impl<'gc> NamespaceObject<'gc> {
     pub fn namespace(self) -> Namespace<'gc> {
         return self.0.read().namespace;
     }
+
+    pub fn set_prefix(&self, mc: &Mutation<'gc>, prefix: Option<AvmString<'gc>>) {
+        self.0.write(mc).prefix = prefix;
+    }
+
+    pub fn prefix(self) -> Option<AvmString<'gc>> {
+        self.0.read().prefix
+    }
 }
 
 impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
@@ -9,7 +9,7 @@ use gc_arena::barrier::unlock;
 use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc, GcWeak, Mutation};
 use ruffle_render::backend::ShaderModule;
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::rc::Rc;
 
 use super::Context3DObject;
@@ -36,6 +36,7 @@ impl<'gc> Program3DObject<'gc> {
                 base: RefLock::new(base),
                 context3d,
                 shader_module_handle: RefCell::new(None),
+                disposed: Cell::new(false),
             },
         ))
         .into();
@@ -53,6 +54,20 @@ impl<'gc> Program3DObject<'gc> {
     pub fn context3d(&self) -> Context3DObject<'gc> {
         self.0.context3d
     }
+
+    /// Whether `dispose()` was called on this object, or on the `Context3D` that created it.
+    pub fn is_disposed(&self) -> bool {
+        self.0.disposed.get() || self.0.context3d.is_disposed()
+    }
+
+    pub fn dispose(&self) {
+        if !self.is_disposed() {
+            self.0.disposed.set(true);
+            if let Some(module) = self.0.shader_module_handle.take() {
+                self.0.context3d.dispose_shaders(module);
+            }
+        }
+    }
 }
 
 #[derive(Collect)]
@@ -64,6 +79,8 @@ pub struct Program3DObjectData<'gc> {
     context3d: Context3DObject<'gc>,
 
     shader_module_handle: RefCell<Option<Rc<dyn ShaderModule>>>,
+
+    disposed: Cell<bool>,
 }
 
 impl<'gc> TObject<'gc> for Program3DObject<'gc> {
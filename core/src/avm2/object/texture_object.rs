@@ -9,7 +9,7 @@ use gc_arena::barrier::unlock;
 use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc, GcWeak, Mutation};
 use ruffle_render::backend::{Context3DTextureFormat, Texture};
-use std::cell::{Ref, RefMut};
+use std::cell::{Cell, Ref, RefMut};
 use std::rc::Rc;
 
 use super::{ClassObject, Context3DObject};
@@ -37,6 +37,7 @@ impl<'gc> TextureObject<'gc> {
                 context3d,
                 original_format,
                 handle,
+                disposed: Cell::new(false),
             },
         ))
         .into();
@@ -58,6 +59,18 @@ impl<'gc> TextureObject<'gc> {
     pub fn context3d(&self) -> Context3DObject<'gc> {
         self.0.context3d
     }
+
+    /// Whether `dispose()` was called on this object, or on the `Context3D` that created it.
+    pub fn is_disposed(&self) -> bool {
+        self.0.disposed.get() || self.0.context3d.is_disposed()
+    }
+
+    pub fn dispose(&self) {
+        if !self.is_disposed() {
+            self.0.disposed.set(true);
+            self.0.context3d.dispose_texture(self.handle());
+        }
+    }
 }
 
 #[derive(Collect)]
@@ -73,6 +86,8 @@ pub struct TextureObjectData<'gc> {
 
     #[collect(require_static)]
     handle: Rc<dyn Texture>,
+
+    disposed: Cell<bool>,
 }
 
 impl<'gc> TObject<'gc> for TextureObject<'gc> {
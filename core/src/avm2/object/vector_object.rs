@@ -80,6 +80,10 @@ impl<'gc> VectorObject<'gc> {
         .into();
 
         object.install_instance_slots(activation.context.gc_context);
+        activation
+            .context
+            .avm2
+            .sample_allocation(object, applied_class);
 
         Ok(object)
     }
@@ -9,7 +9,7 @@ use gc_arena::barrier::unlock;
 use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc, GcWeak, Mutation};
 use ruffle_render::backend::VertexBuffer;
-use std::cell::{Ref, RefMut};
+use std::cell::{Cell, Ref, RefMut};
 use std::rc::Rc;
 
 use super::Context3DObject;
@@ -38,6 +38,7 @@ impl<'gc> VertexBuffer3DObject<'gc> {
                 context3d,
                 handle,
                 data32_per_vertex,
+                disposed: Cell::new(false),
             },
         ))
         .into();
@@ -59,6 +60,18 @@ impl<'gc> VertexBuffer3DObject<'gc> {
     pub fn data32_per_vertex(&self) -> u8 {
         self.0.data32_per_vertex
     }
+
+    /// Whether `dispose()` was called on this object, or on the `Context3D` that created it.
+    pub fn is_disposed(&self) -> bool {
+        self.0.disposed.get() || self.0.context3d.is_disposed()
+    }
+
+    pub fn dispose(&self) {
+        if !self.is_disposed() {
+            self.0.disposed.set(true);
+            self.0.context3d.dispose_vertex_buffer(*self);
+        }
+    }
 }
 
 #[derive(Collect)]
@@ -76,6 +89,8 @@ pub struct VertexBuffer3DObjectData<'gc> {
     /// This is the number of 32-bit values associated with each vertex,
     /// and is at most 64
     data32_per_vertex: u8,
+
+    disposed: Cell<bool>,
 }
 
 impl<'gc> TObject<'gc> for VertexBuffer3DObject<'gc> {
@@ -2,7 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::api_version::ApiVersion;
-use crate::avm2::e4x::{string_to_multiname, E4XNode, E4XNodeKind};
+use crate::avm2::e4x::{string_to_multiname, E4XNamespace, E4XNode, E4XNodeKind};
 use crate::avm2::error::make_error_1087;
 use crate::avm2::multiname::NamespaceSet;
 use crate::avm2::object::script_object::ScriptObjectData;
@@ -130,7 +130,7 @@ impl<'gc> XmlObject<'gc> {
     pub fn namespace(&self, activation: &mut Activation<'_, 'gc>) -> Namespace<'gc> {
         match self.0.read().node.namespace() {
             Some(ns) => Namespace::package(
-                ns,
+                ns.uri,
                 ApiVersion::AllVersions,
                 &mut activation.context.borrow_gc(),
             ),
@@ -520,14 +520,16 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
                 // 12.b.iii. Create a new XML object y with y.[[Name]] = name, y.[[Class]] = "element" and y.[[Parent]] = x
                 let node = E4XNode::element(
                     activation.gc(),
-                    name.explicit_namespace(),
+                    name.explicit_namespace().map(E4XNamespace::new_uri),
                     name.local_name().unwrap(),
                     Some(*self_node),
                 );
                 // 12.b.v. Call the [[Replace]] method of x with arguments ToString(i) and y
                 self_node.replace(index, XmlObject::new(node, activation).into(), activation)?;
-                // FIXME: 12.b.iv. Let ns be the result of calling [[GetNamespace]] on name with no arguments
+                // 12.b.iv. Let ns be the result of calling [[GetNamespace]] on name with no arguments
                 // 12.b.vi. Call [[AddInScopeNamespace]] on y with argument ns
+                // NOTE: Without any in-scope namespaces, ns never has a prefix, so
+                // [[AddInScopeNamespace]] never declares it.
             }
 
             index
@@ -654,7 +656,8 @@ fn handle_input_multiname<'gc>(
         {
             // Copy the namespaces from the previous name,
             // but make sure to definitely include the public namespace.
-            if !new_name.is_any_namespace() {
+            // A name that was qualified with the `default xml namespace` is kept as-is.
+            if !new_name.is_any_namespace() && !new_name.has_explicit_namespace() {
                 let mut ns = Vec::new();
                 ns.extend(name.namespace_set());
                 if !name.contains_public_namespace() {
@@ -0,0 +1,1245 @@
+//! Optimization passes over verified method bodies.
+//!
+//! The passes only ever replace ops in place, most often with `Op::Nop`, so that jump offsets and
+//! exception ranges stay valid while they run. Once they are done, the `Nop`s are removed and all
+//! offsets are remapped to the compacted code.
+
+use crate::avm2::class::Class;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::object::ClassObject;
+use crate::avm2::op::Op;
+use crate::avm2::property::Property;
+use crate::avm2::verify::Exception;
+use crate::avm2::{Activation, Multiname};
+use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use gc_arena::{Gc, GcCell};
+use std::collections::HashSet;
+use swf::avm2::types::{Index, Multiname as AbcMultiname};
+
+/// Runs all optimization passes over the code of a verified method.
+///
+/// `jump_targets` holds the index of every op that may be reached from anywhere other than the
+/// op before it, and `byte_offsets` holds the original offset of every op, which is kept in sync
+/// with the code.
+pub fn optimize<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+    code: &mut Vec<Op>,
+    exceptions: &mut [Exception],
+    byte_offsets: &mut Vec<u32>,
+    jump_targets: &HashSet<i32>,
+) {
+    let mut block_starts: HashSet<usize> = jump_targets
+        .iter()
+        .filter_map(|target| usize::try_from(*target).ok())
+        .collect();
+    // Ops may not be merged across the boundaries of exception ranges either, as that could
+    // change which handler catches their errors.
+    for exception in exceptions.iter() {
+        block_starts.insert(exception.from_offset as usize);
+        block_starts.insert(exception.to_offset as usize);
+        block_starts.insert(exception.target_offset as usize);
+    }
+
+    propagate_types(activation, method, code, &block_starts);
+    peephole(code, &block_starts);
+    eliminate_dead_code(code, exceptions);
+    remove_nops(code, exceptions, byte_offsets);
+}
+
+/// A primitive value that is known while optimizing.
+///
+/// `Integer` follows the same rules as `Value::Integer`: numbers that are pushed as an `i32` are
+/// only represented by it if they fit into 29 bits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Constant {
+    Integer(i32),
+    Number(f64),
+    Bool(bool),
+    Null,
+    Undefined,
+}
+
+impl Constant {
+    fn from_i32(value: i32) -> Self {
+        if !(-(1 << 28)..(1 << 28)).contains(&value) {
+            Constant::Number(value as f64)
+        } else {
+            Constant::Integer(value)
+        }
+    }
+
+    fn from_u32(value: u32) -> Self {
+        if value >= (1 << 28) {
+            Constant::Number(value as f64)
+        } else {
+            Constant::Integer(value as i32)
+        }
+    }
+
+    fn to_number(self) -> f64 {
+        match self {
+            Constant::Integer(value) => value as f64,
+            Constant::Number(value) => value,
+            Constant::Bool(value) => value as u8 as f64,
+            Constant::Null => 0.0,
+            Constant::Undefined => f64::NAN,
+        }
+    }
+
+    fn to_i32(self) -> i32 {
+        match self {
+            Constant::Integer(value) => value,
+            Constant::Number(value) => f64_to_wrapping_i32(value),
+            Constant::Bool(value) => value as i32,
+            Constant::Null | Constant::Undefined => 0,
+        }
+    }
+
+    fn to_u32(self) -> u32 {
+        match self {
+            Constant::Integer(value) => value as u32,
+            Constant::Number(value) => f64_to_wrapping_u32(value),
+            Constant::Bool(value) => value as u32,
+            Constant::Null | Constant::Undefined => 0,
+        }
+    }
+
+    fn to_boolean(self) -> bool {
+        match self {
+            Constant::Integer(value) => value != 0,
+            Constant::Number(value) => !value.is_nan() && value != 0.0,
+            Constant::Bool(value) => value,
+            Constant::Null | Constant::Undefined => false,
+        }
+    }
+
+    /// The op that pushes this constant.
+    fn to_op(self) -> Op {
+        match self {
+            Constant::Integer(value) => match i8::try_from(value) {
+                Ok(value) => Op::PushByte { value: value as u8 },
+                Err(_) => match i16::try_from(value) {
+                    Ok(value) => Op::PushShort { value },
+                    Err(_) => Op::PushInt { value },
+                },
+            },
+            Constant::Number(value) if value.is_nan() => Op::PushNaN,
+            Constant::Number(value) => Op::PushDouble { value },
+            Constant::Bool(true) => Op::PushTrue,
+            Constant::Bool(false) => Op::PushFalse,
+            Constant::Null => Op::PushNull,
+            Constant::Undefined => Op::PushUndefined,
+        }
+    }
+
+    /// The constant pushed by an op, if it always pushes the same one.
+    fn pushed_by(op: &Op) -> Option<Self> {
+        Some(match op {
+            Op::PushByte { value } => Constant::Integer(*value as i8 as i32),
+            Op::PushShort { value } => Constant::Integer(*value as i32),
+            Op::PushInt { value } => Constant::from_i32(*value),
+            Op::PushUint { value } => Constant::from_u32(*value),
+            Op::PushDouble { value } => Constant::Number(*value),
+            Op::PushNaN => Constant::Number(f64::NAN),
+            Op::PushTrue => Constant::Bool(true),
+            Op::PushFalse => Constant::Bool(false),
+            Op::PushNull => Constant::Null,
+            Op::PushUndefined => Constant::Undefined,
+            _ => return None,
+        })
+    }
+}
+
+/// Computes the result of an op that takes one value, if it's known at this point.
+///
+/// This mirrors the implementations of the ops in `Activation`.
+fn fold_unary(op: &Op, value: Constant) -> Option<Constant> {
+    Some(match op {
+        Op::Not => Constant::Bool(!value.to_boolean()),
+        Op::CoerceB => Constant::Bool(value.to_boolean()),
+        Op::CoerceD => Constant::Number(value.to_number()),
+        Op::CoerceI => Constant::from_i32(value.to_i32()),
+        Op::CoerceU => Constant::from_u32(value.to_u32()),
+        Op::Negate => Constant::Number(-value.to_number()),
+        Op::Increment => Constant::Number(value.to_number() + 1.0),
+        Op::Decrement => Constant::Number(value.to_number() - 1.0),
+        Op::NegateI => Constant::from_i32(value.to_i32().wrapping_neg()),
+        Op::IncrementI => Constant::from_i32(value.to_i32().wrapping_add(1)),
+        Op::DecrementI => Constant::from_i32(value.to_i32().wrapping_sub(1)),
+        Op::BitNot => Constant::from_i32(!value.to_i32()),
+        _ => return None,
+    })
+}
+
+/// Computes the result of an op that takes two values, if it's known at this point.
+///
+/// This mirrors the implementations of the ops in `Activation`.
+fn fold_binary(op: &Op, value1: Constant, value2: Constant) -> Option<Constant> {
+    Some(match op {
+        Op::Add => match (value1, value2) {
+            (Constant::Integer(n1), Constant::Integer(n2)) => Constant::from_i32(n1 + n2),
+            _ => Constant::Number(value1.to_number() + value2.to_number()),
+        },
+        Op::Subtract => match (value1, value2) {
+            (Constant::Integer(n1), Constant::Integer(n2)) => Constant::from_i32(n1 - n2),
+            _ => Constant::Number(value1.to_number() - value2.to_number()),
+        },
+        Op::Multiply => Constant::Number(value1.to_number() * value2.to_number()),
+        Op::Divide => Constant::Number(value1.to_number() / value2.to_number()),
+        Op::Modulo => Constant::Number(value1.to_number() % value2.to_number()),
+        Op::AddI => Constant::from_i32(value1.to_i32().wrapping_add(value2.to_i32())),
+        Op::SubtractI => Constant::from_i32(value1.to_i32().wrapping_sub(value2.to_i32())),
+        Op::MultiplyI => Constant::from_i32(value1.to_i32().wrapping_mul(value2.to_i32())),
+        Op::BitAnd => Constant::from_i32(value1.to_i32() & value2.to_i32()),
+        Op::BitOr => Constant::from_i32(value1.to_i32() | value2.to_i32()),
+        Op::BitXor => Constant::from_i32(value1.to_i32() ^ value2.to_i32()),
+        Op::LShift => Constant::from_i32(value1.to_i32() << (value2.to_u32() & 0x1F)),
+        Op::RShift => Constant::from_i32(value1.to_i32() >> (value2.to_u32() & 0x1F)),
+        Op::URShift => Constant::from_u32(value1.to_u32() >> (value2.to_u32() & 0x1F)),
+        _ => return None,
+    })
+}
+
+/// What is known about a value on the stack or in a local register.
+#[derive(Clone, Copy, Default)]
+struct OptValue<'gc> {
+    /// The class of the value. Unless `not_null` is set, the value may also be `null`.
+    class: Option<GcCell<'gc, Class<'gc>>>,
+
+    /// The class object of the value, which is needed to look up its traits.
+    class_object: Option<ClassObject<'gc>>,
+
+    not_null: bool,
+
+    constant: Option<Constant>,
+
+    /// The op that pushed this value, if it only pushes a constant and can be removed once the
+    /// value is folded into the op that uses it.
+    producer: Option<usize>,
+}
+
+impl<'gc> OptValue<'gc> {
+    fn of_class(class: GcCell<'gc, Class<'gc>>) -> Self {
+        Self {
+            class: Some(class),
+            ..Default::default()
+        }
+    }
+
+    fn of_class_object(class_object: ClassObject<'gc>) -> Self {
+        Self {
+            class: Some(class_object.inner_class_definition()),
+            class_object: Some(class_object),
+            ..Default::default()
+        }
+    }
+
+    fn not_null(self) -> Self {
+        Self {
+            not_null: true,
+            ..self
+        }
+    }
+
+    fn is_class(&self, class: GcCell<'gc, Class<'gc>>) -> bool {
+        self.class.is_some_and(|own| GcCell::ptr_eq(own, class))
+    }
+}
+
+/// The builtin classes that the optimizer knows the behavior of.
+struct Types<'gc> {
+    int: GcCell<'gc, Class<'gc>>,
+    uint: GcCell<'gc, Class<'gc>>,
+    number: GcCell<'gc, Class<'gc>>,
+    boolean: GcCell<'gc, Class<'gc>>,
+    string: GcCell<'gc, Class<'gc>>,
+    void: GcCell<'gc, Class<'gc>>,
+    object: ClassObject<'gc>,
+    array: ClassObject<'gc>,
+    function: ClassObject<'gc>,
+}
+
+impl<'gc> Types<'gc> {
+    fn new(activation: &mut Activation<'_, 'gc>) -> Self {
+        let classes = activation.avm2().classes();
+        Self {
+            int: classes.int.inner_class_definition(),
+            uint: classes.uint.inner_class_definition(),
+            number: classes.number.inner_class_definition(),
+            boolean: classes.boolean.inner_class_definition(),
+            string: classes.string.inner_class_definition(),
+            void: classes.void.inner_class_definition(),
+            object: classes.object,
+            array: classes.array,
+            function: classes.function,
+        }
+    }
+
+    fn int(&self) -> OptValue<'gc> {
+        OptValue::of_class(self.int).not_null()
+    }
+
+    fn uint(&self) -> OptValue<'gc> {
+        OptValue::of_class(self.uint).not_null()
+    }
+
+    fn number(&self) -> OptValue<'gc> {
+        OptValue::of_class(self.number).not_null()
+    }
+
+    fn boolean(&self) -> OptValue<'gc> {
+        OptValue::of_class(self.boolean).not_null()
+    }
+
+    fn string(&self) -> OptValue<'gc> {
+        OptValue::of_class(self.string)
+    }
+
+    /// Whether coercing `null` to this class results in something other than `null`.
+    fn is_primitive(&self, class: GcCell<'gc, Class<'gc>>) -> bool {
+        [self.int, self.uint, self.number, self.boolean, self.void]
+            .into_iter()
+            .any(|primitive| GcCell::ptr_eq(primitive, class))
+    }
+
+    /// The value of a coercion to the given class.
+    fn coerced(&self, class: GcCell<'gc, Class<'gc>>) -> OptValue<'gc> {
+        if self.is_primitive(class) {
+            OptValue::of_class(class).not_null()
+        } else {
+            OptValue::of_class(class)
+        }
+    }
+
+    fn constant(&self, constant: Constant, producer: usize) -> OptValue<'gc> {
+        let value = match constant {
+            Constant::Integer(_) => self.int(),
+            Constant::Number(_) => self.number(),
+            Constant::Bool(_) => self.boolean(),
+            Constant::Null | Constant::Undefined => OptValue::default(),
+        };
+        OptValue {
+            constant: Some(constant),
+            producer: Some(producer),
+            ..value
+        }
+    }
+}
+
+/// A multiname operand of an op, with the number of runtime components it pops off the stack.
+pub fn pool_multiname<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+    index: Index<AbcMultiname>,
+) -> Option<(Gc<'gc, Multiname<'gc>>, usize)> {
+    let multiname = method
+        .translation_unit()
+        .pool_maybe_uninitialized_multiname(index, &mut activation.context)
+        .ok()?;
+    let runtime_components = multiname.has_lazy_name() as usize + multiname.has_lazy_ns() as usize;
+    Some((multiname, runtime_components))
+}
+
+/// Resolves a multiname operand that names a class, if it can be resolved ahead of time.
+fn pool_class<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+    index: Index<AbcMultiname>,
+) -> Option<GcCell<'gc, Class<'gc>>> {
+    let (multiname, runtime_components) = pool_multiname(activation, method, index)?;
+    if runtime_components != 0 || multiname.has_lazy_component() {
+        return None;
+    }
+    activation
+        .domain()
+        .get_class(&multiname, activation.context.gc_context)
+}
+
+/// Finds the type of every local register that holds the same value for the whole method: the
+/// receiver and the parameters, as long as no op assigns to them.
+fn local_types<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+    code: &[Op],
+    types: &Types<'gc>,
+) -> Vec<OptValue<'gc>> {
+    let num_locals = method
+        .body()
+        .expect("Cannot optimize non-native method without body!")
+        .num_locals;
+    let mut local_types = vec![OptValue::default(); num_locals as usize];
+
+    // This can probably be done better by recording the receiver in `Activation`,
+    // but this works since it's guaranteed to be set in `Activation::from_method`.
+    let this_value = activation.local_register(0);
+    if let Some(this_class) = activation.subclass_object() {
+        if this_value.is_of_type(activation, this_class.inner_class_definition()) {
+            if let Some(local) = local_types.get_mut(0) {
+                *local = OptValue::of_class_object(this_class).not_null();
+            }
+        }
+    }
+
+    // Parameters are coerced to their declared types by `Activation::resolve_parameters`.
+    for (i, param) in method.signature().iter().enumerate() {
+        if param.param_type_name.is_any_name() || param.param_type_name.has_lazy_component() {
+            continue;
+        }
+        let class = activation
+            .domain()
+            .get_class(&param.param_type_name, activation.context.gc_context);
+        if let (Some(class), Some(local)) = (class, local_types.get_mut(i + 1)) {
+            *local = types.coerced(class);
+        }
+    }
+
+    // Only keep the types we're absolutely sure about - invalidate the known type of a local
+    // register if any other register-modifying opcodes mention them anywhere else in the
+    // function.
+    let mut invalidate = |index: u32| {
+        if let Some(local) = local_types.get_mut(index as usize) {
+            *local = OptValue::default();
+        }
+    };
+    for op in code {
+        match op {
+            Op::SetLocal { index }
+            | Op::Kill { index }
+            | Op::IncLocal { index }
+            | Op::IncLocalI { index }
+            | Op::DecLocal { index }
+            | Op::DecLocalI { index } => invalidate(*index),
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => {
+                invalidate(*object_register);
+                invalidate(*index_register);
+            }
+            _ => {}
+        }
+    }
+
+    local_types
+}
+
+fn pop<'gc>(stack: &mut Vec<OptValue<'gc>>) -> OptValue<'gc> {
+    stack.pop().unwrap_or_default()
+}
+
+fn pop_n(stack: &mut Vec<OptValue<'_>>, count: usize) {
+    stack.truncate(stack.len().saturating_sub(count));
+}
+
+fn peek<'gc>(stack: &[OptValue<'gc>], depth: usize) -> OptValue<'gc> {
+    stack
+        .len()
+        .checked_sub(depth + 1)
+        .map(|index| stack[index])
+        .unwrap_or_default()
+}
+
+/// Tracks what is known about the values on the stack through every basic block, and uses it
+/// to fold constants and branches, to remove coercions that don't change their value, and to
+/// resolve property accesses on objects of known classes to slot accesses.
+fn propagate_types<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+    code: &mut [Op],
+    block_starts: &HashSet<usize>,
+) {
+    let types = Types::new(activation);
+    let local_types = local_types(activation, method, code, &types);
+
+    // The values pushed since the start of the current block. Anything below them is unknown.
+    let mut stack: Vec<OptValue<'gc>> = Vec::new();
+
+    for i in 0..code.len() {
+        if block_starts.contains(&i) {
+            stack.clear();
+        }
+
+        let op = code[i].clone();
+        if let Some(constant) = Constant::pushed_by(&op) {
+            stack.push(types.constant(constant, i));
+            continue;
+        }
+
+        match op {
+            Op::Nop
+            | Op::Debug { .. }
+            | Op::DebugFile { .. }
+            | Op::DebugLine { .. }
+            | Op::Bkpt
+            | Op::BkptLine { .. }
+            | Op::Timestamp
+            | Op::Kill { .. }
+            | Op::IncLocal { .. }
+            | Op::IncLocalI { .. }
+            | Op::DecLocal { .. }
+            | Op::DecLocalI { .. }
+            | Op::PopScope
+            | Op::Dxns { .. } => {}
+
+            Op::GetLocal { index } => {
+                let value = local_types.get(index as usize).copied().unwrap_or_default();
+                stack.push(value);
+            }
+
+            Op::PushString { .. } => stack.push(types.string().not_null()),
+
+            Op::ConvertS => {
+                let value = pop(&mut stack);
+                if value.is_class(types.string) && value.not_null {
+                    code[i] = Op::Nop;
+                    stack.push(value);
+                } else {
+                    stack.push(types.string().not_null());
+                }
+            }
+
+            Op::TypeOf => {
+                pop_n(&mut stack, 1);
+                stack.push(types.string().not_null());
+            }
+
+            Op::PushNamespace { .. }
+            | Op::GetGlobalScope
+            | Op::GetScopeObject { .. }
+            | Op::GetOuterScope { .. }
+            | Op::GetGlobalSlot { .. }
+            | Op::NewActivation
+            | Op::NewCatch { .. }
+            | Op::FindDef { .. }
+            | Op::GetLex { .. } => stack.push(OptValue::default()),
+
+            Op::NewFunction { .. } => {
+                stack.push(OptValue::of_class_object(types.function).not_null());
+            }
+
+            Op::HasNext2 { .. } => stack.push(types.boolean()),
+
+            Op::Dup => {
+                let value = pop(&mut stack);
+                let value = OptValue {
+                    producer: None,
+                    ..value
+                };
+                stack.push(value);
+                stack.push(value);
+            }
+
+            Op::Swap => {
+                let value2 = pop(&mut stack);
+                let value1 = pop(&mut stack);
+                stack.push(OptValue {
+                    producer: None,
+                    ..value2
+                });
+                stack.push(OptValue {
+                    producer: None,
+                    ..value1
+                });
+            }
+
+            Op::Pop
+            | Op::SetLocal { .. }
+            | Op::SetGlobalSlot { .. }
+            | Op::PushScope
+            | Op::PushWith
+            | Op::DxnsLate => pop_n(&mut stack, 1),
+
+            Op::SetSlot { .. }
+            | Op::Si8
+            | Op::Si16
+            | Op::Si32
+            | Op::Sf32
+            | Op::Sf64
+            | Op::IfEq { .. }
+            | Op::IfNe { .. }
+            | Op::IfGe { .. }
+            | Op::IfGt { .. }
+            | Op::IfLe { .. }
+            | Op::IfLt { .. }
+            | Op::IfNge { .. }
+            | Op::IfNgt { .. }
+            | Op::IfNle { .. }
+            | Op::IfNlt { .. }
+            | Op::IfStrictEq { .. }
+            | Op::IfStrictNe { .. } => pop_n(&mut stack, 2),
+
+            Op::IfTrue { offset } | Op::IfFalse { offset } => {
+                let value = pop(&mut stack);
+                if let (Some(constant), Some(producer)) = (value.constant, value.producer) {
+                    let jumps = constant.to_boolean() == matches!(op, Op::IfTrue { .. });
+                    code[producer] = Op::Nop;
+                    code[i] = if jumps {
+                        stack.clear();
+                        Op::Jump { offset }
+                    } else {
+                        Op::Nop
+                    };
+                }
+            }
+
+            Op::Jump { .. }
+            | Op::LookupSwitch(_)
+            | Op::ReturnValue
+            | Op::ReturnVoid
+            | Op::Throw => stack.clear(),
+
+            Op::Not
+            | Op::Negate
+            | Op::Increment
+            | Op::Decrement
+            | Op::NegateI
+            | Op::IncrementI
+            | Op::DecrementI
+            | Op::BitNot
+            | Op::CoerceB
+            | Op::CoerceD
+            | Op::CoerceI
+            | Op::CoerceU => {
+                let value = pop(&mut stack);
+                if let (Some(constant), Some(producer)) = (value.constant, value.producer) {
+                    if let Some(result) = fold_unary(&op, constant) {
+                        code[producer] = Op::Nop;
+                        code[i] = result.to_op();
+                        stack.push(types.constant(result, i));
+                        continue;
+                    }
+                }
+
+                let result = match op {
+                    Op::Not | Op::CoerceB => types.boolean(),
+                    Op::NegateI | Op::IncrementI | Op::DecrementI | Op::BitNot | Op::CoerceI => {
+                        types.int()
+                    }
+                    Op::CoerceU => types.uint(),
+                    _ => types.number(),
+                };
+                let is_redundant = match op {
+                    Op::CoerceB => value.is_class(types.boolean),
+                    Op::CoerceD => value.is_class(types.number),
+                    Op::CoerceI => value.is_class(types.int),
+                    Op::CoerceU => value.is_class(types.uint),
+                    _ => false,
+                };
+                if is_redundant {
+                    code[i] = Op::Nop;
+                    stack.push(value);
+                } else {
+                    stack.push(result);
+                }
+            }
+
+            Op::Add
+            | Op::Subtract
+            | Op::Multiply
+            | Op::Divide
+            | Op::Modulo
+            | Op::AddI
+            | Op::SubtractI
+            | Op::MultiplyI
+            | Op::BitAnd
+            | Op::BitOr
+            | Op::BitXor
+            | Op::LShift
+            | Op::RShift
+            | Op::URShift => {
+                let value2 = pop(&mut stack);
+                let value1 = pop(&mut stack);
+                if let (Some(constant1), Some(producer1), Some(constant2), Some(producer2)) = (
+                    value1.constant,
+                    value1.producer,
+                    value2.constant,
+                    value2.producer,
+                ) {
+                    if let Some(result) = fold_binary(&op, constant1, constant2) {
+                        code[producer1] = Op::Nop;
+                        code[producer2] = Op::Nop;
+                        code[i] = result.to_op();
+                        stack.push(types.constant(result, i));
+                        continue;
+                    }
+                }
+
+                stack.push(match op {
+                    // These may result in strings, or in either representation of numbers.
+                    Op::Add | Op::Subtract => OptValue::default(),
+                    Op::Multiply | Op::Divide | Op::Modulo => types.number(),
+                    Op::URShift => types.uint(),
+                    _ => types.int(),
+                });
+            }
+
+            Op::Equals
+            | Op::StrictEquals
+            | Op::LessThan
+            | Op::LessEquals
+            | Op::GreaterThan
+            | Op::GreaterEquals
+            | Op::In
+            | Op::InstanceOf
+            | Op::IsTypeLate => {
+                pop_n(&mut stack, 2);
+                stack.push(types.boolean());
+            }
+
+            Op::IsType { .. } => {
+                pop_n(&mut stack, 1);
+                stack.push(types.boolean());
+            }
+
+            Op::CoerceS => {
+                let value = pop(&mut stack);
+                if value.is_class(types.string) {
+                    code[i] = Op::Nop;
+                    stack.push(value);
+                } else {
+                    stack.push(OptValue {
+                        not_null: value.not_null,
+                        ..types.string()
+                    });
+                }
+            }
+
+            Op::CoerceA => {
+                code[i] = Op::Nop;
+            }
+
+            Op::Coerce { index } => {
+                let value = pop(&mut stack);
+                let Some(class) = pool_class(activation, method, index) else {
+                    stack.push(OptValue::default());
+                    continue;
+                };
+
+                let is_redundant = if value.is_class(class) {
+                    !types.is_primitive(class) || value.not_null
+                } else if value.constant == Some(Constant::Null) {
+                    // As long as this isn't coercing to one of the special primitive classes,
+                    // `null` stays `null`.
+                    !types.is_primitive(class)
+                } else if let Some(class_object) = value.class_object {
+                    !types.is_primitive(class) && class_object.has_class_in_chain(class)
+                } else {
+                    false
+                };
+
+                if is_redundant {
+                    code[i] = Op::Nop;
+                    stack.push(value);
+                } else {
+                    stack.push(types.coerced(class));
+                }
+            }
+
+            Op::AsType { type_name } => {
+                let value = pop(&mut stack);
+                if value.constant == Some(Constant::Null)
+                    && pool_class(activation, method, type_name).is_some()
+                {
+                    code[i] = Op::Nop;
+                    stack.push(value);
+                } else {
+                    stack.push(OptValue::default());
+                }
+            }
+
+            Op::GetProperty { index } => {
+                let Some((multiname, runtime_components)) =
+                    pool_multiname(activation, method, index)
+                else {
+                    stack.clear();
+                    continue;
+                };
+                pop_n(&mut stack, runtime_components);
+                let receiver = pop(&mut stack);
+
+                if runtime_components == 0 && !multiname.has_lazy_component() && receiver.not_null {
+                    if let Some(class_object) = receiver.class_object {
+                        match class_object.instance_vtable().get_trait(&multiname) {
+                            Some(Property::Slot { slot_id })
+                            | Some(Property::ConstSlot { slot_id }) => {
+                                code[i] = Op::GetSlot { index: slot_id };
+                            }
+                            Some(Property::Virtual { get: Some(get), .. }) => {
+                                code[i] = Op::CallMethod {
+                                    num_args: 0,
+                                    index: Index::new(get),
+                                };
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+
+                stack.push(OptValue::default());
+            }
+
+            Op::SetProperty { index } | Op::InitProperty { index } => {
+                let Some((multiname, runtime_components)) =
+                    pool_multiname(activation, method, index)
+                else {
+                    stack.clear();
+                    continue;
+                };
+                let receiver = peek(&stack, runtime_components + 1);
+                pop_n(&mut stack, runtime_components + 2);
+
+                if runtime_components == 0 && !multiname.has_lazy_component() && receiver.not_null {
+                    if let Some(class_object) = receiver.class_object {
+                        match class_object.instance_vtable().get_trait(&multiname) {
+                            Some(Property::Slot { slot_id }) => {
+                                code[i] = Op::SetSlot { index: slot_id };
+                            }
+                            Some(Property::ConstSlot { slot_id })
+                                if matches!(op, Op::InitProperty { .. }) =>
+                            {
+                                code[i] = Op::SetSlot { index: slot_id };
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            Op::GetSlot { .. }
+            | Op::Li8
+            | Op::Li16
+            | Op::Li32
+            | Op::Lf32
+            | Op::Lf64
+            | Op::Sxi1
+            | Op::Sxi8
+            | Op::Sxi16
+            | Op::EscXAttr
+            | Op::EscXElem
+            | Op::CheckFilter
+            | Op::ConvertO
+            | Op::CoerceO
+            | Op::NewClass { .. } => {
+                pop_n(&mut stack, 1);
+                stack.push(OptValue::default());
+            }
+
+            Op::AsTypeLate | Op::HasNext | Op::NextName | Op::NextValue => {
+                pop_n(&mut stack, 2);
+                stack.push(OptValue::default());
+            }
+
+            Op::DeleteProperty { index }
+            | Op::GetSuper { index }
+            | Op::GetDescendants { index }
+            | Op::FindProperty { index }
+            | Op::FindPropStrict { index }
+            | Op::SetSuper { index } => {
+                let Some((_, runtime_components)) = pool_multiname(activation, method, index)
+                else {
+                    stack.clear();
+                    continue;
+                };
+                let (pops, pushes) = match op {
+                    Op::FindProperty { .. } | Op::FindPropStrict { .. } => (0, 1),
+                    Op::SetSuper { .. } => (2, 0),
+                    _ => (1, 1),
+                };
+                pop_n(&mut stack, runtime_components + pops);
+                if pushes == 1 {
+                    stack.push(OptValue::default());
+                }
+            }
+
+            Op::CallProperty { index, num_args }
+            | Op::CallPropLex { index, num_args }
+            | Op::CallSuper { index, num_args }
+            | Op::ConstructProp { index, num_args }
+            | Op::CallPropVoid { index, num_args }
+            | Op::CallSuperVoid { index, num_args } => {
+                let Some((_, runtime_components)) = pool_multiname(activation, method, index)
+                else {
+                    stack.clear();
+                    continue;
+                };
+                pop_n(&mut stack, num_args as usize + runtime_components + 1);
+                if !matches!(op, Op::CallPropVoid { .. } | Op::CallSuperVoid { .. }) {
+                    stack.push(OptValue::default());
+                }
+            }
+
+            Op::CallMethod { num_args, .. }
+            | Op::CallStatic { num_args, .. }
+            | Op::Construct { num_args }
+            | Op::ApplyType {
+                num_types: num_args,
+            } => {
+                pop_n(&mut stack, num_args as usize + 1);
+                if matches!(op, Op::Construct { .. }) {
+                    stack.push(OptValue::default().not_null());
+                } else {
+                    stack.push(OptValue::default());
+                }
+            }
+
+            Op::Call { num_args } => {
+                pop_n(&mut stack, num_args as usize + 2);
+                stack.push(OptValue::default());
+            }
+
+            Op::ConstructSuper { num_args } => pop_n(&mut stack, num_args as usize + 1),
+
+            Op::NewArray { num_args } => {
+                pop_n(&mut stack, num_args as usize);
+                stack.push(OptValue::of_class_object(types.array).not_null());
+            }
+
+            Op::NewObject { num_args } => {
+                pop_n(&mut stack, 2 * num_args as usize);
+                stack.push(OptValue::of_class_object(types.object).not_null());
+            }
+
+            _ => stack.clear(),
+        }
+    }
+}
+
+/// Replaces short sequences of ops with cheaper equivalents.
+fn peephole(code: &mut [Op], block_starts: &HashSet<usize>) {
+    // Whether `count` ops starting at `start` always run one after another.
+    let len = code.len();
+    let is_sequence = |start: usize, count: usize| {
+        start + count <= len && (start + 1..start + count).all(|i| !block_starts.contains(&i))
+    };
+
+    for i in 0..len {
+        let replacement = match (&code[i], code.get(i + 1), code.get(i + 2)) {
+            // `x = x`
+            (Op::GetLocal { index }, Some(Op::SetLocal { index: set_index }), _)
+                if index == set_index && is_sequence(i, 2) =>
+            {
+                vec![Op::Nop, Op::Nop]
+            }
+
+            // A value that's pushed just to be discarded.
+            (
+                Op::GetLocal { .. }
+                | Op::PushByte { .. }
+                | Op::PushShort { .. }
+                | Op::PushInt { .. }
+                | Op::PushUint { .. }
+                | Op::PushDouble { .. }
+                | Op::PushNaN
+                | Op::PushTrue
+                | Op::PushFalse
+                | Op::PushNull
+                | Op::PushUndefined
+                | Op::PushString { .. }
+                | Op::Dup,
+                Some(Op::Pop),
+                _,
+            ) if is_sequence(i, 2) => vec![Op::Nop, Op::Nop],
+
+            // `x++` and `x--` on a local register.
+            (
+                Op::GetLocal { index },
+                Some(step @ (Op::Increment | Op::IncrementI | Op::Decrement | Op::DecrementI)),
+                Some(Op::SetLocal { index: set_index }),
+            ) if index == set_index && is_sequence(i, 3) => {
+                let index = *index;
+                let step = match step {
+                    Op::Increment => Op::IncLocal { index },
+                    Op::IncrementI => Op::IncLocalI { index },
+                    Op::Decrement => Op::DecLocal { index },
+                    _ => Op::DecLocalI { index },
+                };
+                vec![Op::Nop, Op::Nop, step]
+            }
+
+            // Negated conditions.
+            (Op::Not, Some(Op::IfTrue { offset }), _) if is_sequence(i, 2) => {
+                vec![Op::Nop, Op::IfFalse { offset: *offset }]
+            }
+            (Op::Not, Some(Op::IfFalse { offset }), _) if is_sequence(i, 2) => {
+                vec![Op::Nop, Op::IfTrue { offset: *offset }]
+            }
+
+            // A jump to the next op.
+            (Op::Jump { offset: 0 }, _, _) => vec![Op::Nop],
+
+            _ => continue,
+        };
+
+        for (j, op) in replacement.into_iter().enumerate() {
+            code[i + j] = op;
+        }
+    }
+}
+
+/// The indices of the ops that may run after the op at `index`.
+pub fn successors(code: &[Op], index: usize) -> Vec<usize> {
+    let target = |offset: i32| usize::try_from(index as i64 + 1 + offset as i64).ok();
+    let next = Some(index + 1);
+
+    let successors = match &code[index] {
+        Op::ReturnValue | Op::ReturnVoid | Op::Throw => vec![],
+        Op::Jump { offset } => vec![target(*offset)],
+        Op::IfEq { offset }
+        | Op::IfFalse { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNe { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfTrue { offset } => vec![next, target(*offset)],
+        Op::LookupSwitch(lookup_switch) => std::iter::once(lookup_switch.default_offset)
+            .chain(lookup_switch.case_offsets.iter().copied())
+            .map(target)
+            .collect(),
+        _ => vec![next],
+    };
+
+    successors
+        .into_iter()
+        .flatten()
+        .filter(|successor| *successor < code.len())
+        .collect()
+}
+
+/// Replaces every op that can't be reached from the start of the method or from an exception
+/// handler with `Nop`.
+fn eliminate_dead_code(code: &mut [Op], exceptions: &[Exception]) {
+    if code.is_empty() {
+        return;
+    }
+
+    let mut reachable = vec![false; code.len()];
+    let mut worklist = vec![0];
+    worklist.extend(
+        exceptions
+            .iter()
+            .map(|exception| exception.target_offset as usize)
+            .filter(|target| *target < code.len()),
+    );
+
+    while let Some(index) = worklist.pop() {
+        if std::mem::replace(&mut reachable[index], true) {
+            continue;
+        }
+        worklist.extend(
+            successors(code, index)
+                .into_iter()
+                .filter(|successor| !reachable[*successor]),
+        );
+    }
+
+    for (op, reachable) in code.iter_mut().zip(reachable) {
+        if !reachable {
+            *op = Op::Nop;
+        }
+    }
+}
+
+/// Removes all `Nop`s, adjusting jumps and exception ranges to the new positions of the ops.
+fn remove_nops(code: &mut Vec<Op>, exceptions: &mut [Exception], byte_offsets: &mut Vec<u32>) {
+    // The new position of every op, or of the next op that's kept if it's removed.
+    let mut new_positions = Vec::with_capacity(code.len() + 1);
+    let mut kept = 0;
+    for op in code.iter() {
+        new_positions.push(kept);
+        if !matches!(op, Op::Nop) {
+            kept += 1;
+        }
+    }
+    new_positions.push(kept);
+
+    if kept == code.len() || kept == 0 {
+        return;
+    }
+
+    let new_position = |index: i64| {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| new_positions.get(index))
+            .copied()
+            .unwrap_or(kept) as i64
+    };
+    for (index, op) in code.iter_mut().enumerate() {
+        let index = index as i64;
+        let adjust = |offset: &mut i32| {
+            let target = index + 1 + *offset as i64;
+            *offset = (new_position(target) - new_position(index) - 1) as i32;
+        };
+        match op {
+            Op::IfEq { offset }
+            | Op::IfFalse { offset }
+            | Op::IfGe { offset }
+            | Op::IfGt { offset }
+            | Op::IfLe { offset }
+            | Op::IfLt { offset }
+            | Op::IfNe { offset }
+            | Op::IfNge { offset }
+            | Op::IfNgt { offset }
+            | Op::IfNle { offset }
+            | Op::IfNlt { offset }
+            | Op::IfStrictEq { offset }
+            | Op::IfStrictNe { offset }
+            | Op::IfTrue { offset }
+            | Op::Jump { offset } => adjust(offset),
+            Op::LookupSwitch(lookup_switch) => {
+                adjust(&mut lookup_switch.default_offset);
+                for case in lookup_switch.case_offsets.iter_mut() {
+                    adjust(case);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for exception in exceptions.iter_mut() {
+        exception.from_offset = new_position(exception.from_offset as i64) as u32;
+        exception.to_offset = new_position(exception.to_offset as i64) as u32;
+        exception.target_offset = new_position(exception.target_offset as i64) as u32;
+    }
+
+    let mut is_kept = code.iter().map(|op| !matches!(op, Op::Nop));
+    byte_offsets.retain(|_| is_kept.next().unwrap_or(true));
+    code.retain(|op| !matches!(op, Op::Nop));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exception(from_offset: u32, to_offset: u32, target_offset: u32) -> Exception {
+        Exception {
+            from_offset,
+            to_offset,
+            target_offset,
+            variable_name: Index::new(0),
+            type_name: Index::new(0),
+        }
+    }
+
+    #[test]
+    fn folds_constants() {
+        let int = |value| Constant::Integer(value);
+        assert_eq!(fold_binary(&Op::Add, int(2), int(3)), Some(int(5)));
+        assert_eq!(
+            fold_binary(&Op::Add, int(1 << 27), int(1 << 27)),
+            Some(Constant::Number((1 << 28) as f64))
+        );
+        assert_eq!(
+            fold_binary(&Op::Add, int(1), Constant::Number(0.5)),
+            Some(Constant::Number(1.5))
+        );
+        assert_eq!(
+            fold_binary(&Op::Divide, int(1), int(0)),
+            Some(Constant::Number(f64::INFINITY))
+        );
+        assert_eq!(fold_binary(&Op::SubtractI, int(3), int(5)), Some(int(-2)));
+        assert_eq!(
+            fold_binary(&Op::URShift, int(-1), int(28)),
+            Some(Constant::Integer(15))
+        );
+        assert_eq!(
+            fold_unary(&Op::CoerceI, Constant::Number(4294967297.0)),
+            Some(int(1))
+        );
+        assert_eq!(
+            fold_unary(&Op::Not, Constant::Number(f64::NAN)),
+            Some(Constant::Bool(true))
+        );
+        assert_eq!(fold_unary(&Op::CoerceS, int(1)), None);
+        assert_eq!(fold_binary(&Op::Equals, int(1), int(1)), None);
+
+        assert_eq!(int(-1).to_op(), Op::PushByte { value: 255 });
+        assert_eq!(int(300).to_op(), Op::PushShort { value: 300 });
+        assert_eq!(int(100_000).to_op(), Op::PushInt { value: 100_000 });
+        assert_eq!(Constant::Number(f64::NAN).to_op(), Op::PushNaN);
+    }
+
+    #[test]
+    fn peephole_rewrites_locals() {
+        let mut code = vec![
+            Op::GetLocal { index: 1 },
+            Op::IncrementI,
+            Op::SetLocal { index: 1 },
+            Op::GetLocal { index: 2 },
+            Op::SetLocal { index: 2 },
+            Op::PushByte { value: 1 },
+            Op::Pop,
+            Op::ReturnVoid,
+        ];
+        peephole(&mut code, &HashSet::new());
+        assert_eq!(
+            code,
+            vec![
+                Op::Nop,
+                Op::Nop,
+                Op::IncLocalI { index: 1 },
+                Op::Nop,
+                Op::Nop,
+                Op::Nop,
+                Op::Nop,
+                Op::ReturnVoid,
+            ]
+        );
+
+        // Ops that can be jumped to must stay where they are.
+        let mut code = vec![
+            Op::GetLocal { index: 1 },
+            Op::SetLocal { index: 1 },
+            Op::ReturnVoid,
+        ];
+        peephole(&mut code, &HashSet::from([1]));
+        assert_eq!(code[0], Op::GetLocal { index: 1 });
+    }
+
+    #[test]
+    fn removes_dead_code_and_nops() {
+        let mut code = vec![
+            Op::Nop,                   // 0
+            Op::Jump { offset: 2 },    // 1 -> 4
+            Op::PushByte { value: 1 }, // 2, unreachable
+            Op::Pop,                   // 3, unreachable
+            Op::GetLocal { index: 0 }, // 4
+            Op::IfTrue { offset: -6 }, // 5 -> 0
+            Op::Nop,                   // 6
+            Op::ReturnVoid,            // 7
+            Op::PushNull,              // 8, exception handler
+            Op::Throw,                 // 9
+        ];
+        let mut exceptions = [exception(4, 6, 8)];
+        let mut byte_offsets = (0..10).map(|i| i * 10).collect();
+
+        eliminate_dead_code(&mut code, &exceptions);
+        assert_eq!(code[2], Op::Nop);
+        assert_eq!(code[3], Op::Nop);
+        assert_eq!(code[8], Op::PushNull);
+
+        remove_nops(&mut code, &mut exceptions, &mut byte_offsets);
+        assert_eq!(
+            code,
+            vec![
+                Op::Jump { offset: 0 },
+                Op::GetLocal { index: 0 },
+                Op::IfTrue { offset: -3 },
+                Op::ReturnVoid,
+                Op::PushNull,
+                Op::Throw,
+            ]
+        );
+        assert_eq!(byte_offsets, vec![10, 40, 50, 70, 80, 90]);
+        assert_eq!(exceptions[0].from_offset, 1);
+        assert_eq!(exceptions[0].to_offset, 3);
+        assert_eq!(exceptions[0].target_offset, 4);
+    }
+}
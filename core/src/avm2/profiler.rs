@@ -0,0 +1,262 @@
+//! Opt-in tracing of AVM2 method calls and frame processing, which can be saved in the Chrome
+//! Trace Event format and opened in Perfetto or `about:tracing`.
+
+use crate::profiler::{SpanStats, SpanTracker};
+use fnv::FnvHashMap;
+use serde_json::json;
+use web_time::{Duration, Instant};
+
+/// The number of spans that are kept before new ones are dropped.
+const MAX_EVENTS: usize = 1_000_000;
+
+/// What a span of time was spent on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TraceCategory {
+    /// A whole frame of the movie.
+    Frame,
+
+    /// One of the phases of a frame, such as `enterFrame` or frame scripts.
+    FramePhase,
+
+    /// An AVM2 method, or the initializer of a script.
+    Method,
+
+    /// The dispatch of an event to its handlers.
+    Event,
+
+    /// Rendering the stage.
+    Render,
+
+    /// Garbage collection.
+    Gc,
+}
+
+impl TraceCategory {
+    pub fn name(self) -> &'static str {
+        match self {
+            TraceCategory::Frame => "frame",
+            TraceCategory::FramePhase => "phase",
+            TraceCategory::Method => "method",
+            TraceCategory::Event => "event",
+            TraceCategory::Render => "render",
+            TraceCategory::Gc => "gc",
+        }
+    }
+}
+
+/// A span of time that has ended.
+#[derive(Clone, Debug)]
+pub struct TraceEvent {
+    pub name: String,
+    pub category: TraceCategory,
+
+    /// When the span started, relative to the start of profiling.
+    pub start: Duration,
+
+    pub duration: Duration,
+}
+
+/// Records nested spans of time spent running AVM2 code, dispatching events, rendering and
+/// collecting garbage.
+pub struct Profiler {
+    start: Instant,
+
+    /// The spans that are currently open.
+    open: SpanTracker<(String, TraceCategory)>,
+
+    events: Vec<TraceEvent>,
+
+    /// The number of spans that ended after `MAX_EVENTS` were recorded.
+    dropped: usize,
+
+    /// The statistics of every method and event, by name.
+    ///
+    /// The total time of recursive calls is counted every time.
+    totals: FnvHashMap<(TraceCategory, String), SpanStats>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            open: SpanTracker::new(),
+            events: Vec::new(),
+            dropped: 0,
+            totals: FnvHashMap::default(),
+        }
+    }
+
+    /// The recorded spans, in the order that they ended.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// The number of spans that were not recorded because too many already were.
+    pub fn dropped_events(&self) -> usize {
+        self.dropped
+    }
+
+    /// The statistics of every method and event handler dispatch, by name.
+    pub fn totals(&self) -> &FnvHashMap<(TraceCategory, String), SpanStats> {
+        &self.totals
+    }
+
+    /// Discards everything that was recorded, except for the spans that are currently open.
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.dropped = 0;
+        self.totals.clear();
+    }
+
+    /// Starts a span nested in the innermost open span.
+    pub fn begin(&mut self, name: impl Into<String>, category: TraceCategory) {
+        self.open.begin((name.into(), category));
+    }
+
+    /// Ends the innermost open span.
+    pub fn end(&mut self) {
+        let Some(span) = self.open.end() else {
+            return;
+        };
+        let (name, category) = span.key;
+        self.add_event(name, category, span.start, span.duration, span.exclusive);
+    }
+
+    /// Records a span that was timed by the caller, such as work done outside of the
+    /// garbage-collected arena.
+    pub fn record(
+        &mut self,
+        name: impl Into<String>,
+        category: TraceCategory,
+        start: Instant,
+        end: Instant,
+    ) {
+        let duration = self.open.record(start, end);
+        self.add_event(name.into(), category, start, duration, duration);
+    }
+
+    fn add_event(
+        &mut self,
+        name: String,
+        category: TraceCategory,
+        start: Instant,
+        duration: Duration,
+        exclusive: Duration,
+    ) {
+        if matches!(category, TraceCategory::Method | TraceCategory::Event) {
+            self.totals
+                .entry((category, name.clone()))
+                .or_default()
+                .add_call(duration, exclusive);
+        }
+
+        if self.events.len() >= MAX_EVENTS {
+            self.dropped += 1;
+            return;
+        }
+        self.events.push(TraceEvent {
+            name,
+            category,
+            start: start.saturating_duration_since(self.start),
+            duration,
+        });
+    }
+
+    /// Writes the recorded spans as complete events in the Chrome Trace Event format.
+    pub fn to_chrome_trace(&self) -> Vec<u8> {
+        let mut events = vec![
+            json!({
+                "name": "process_name",
+                "ph": "M",
+                "pid": 1,
+                "args": { "name": "Ruffle" },
+            }),
+            json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": 1,
+                "args": { "name": "AVM2" },
+            }),
+        ];
+        events.extend(self.events.iter().map(|event| {
+            json!({
+                "name": event.name,
+                "cat": event.category.name(),
+                "ph": "X",
+                "ts": micros(event.start),
+                "dur": micros(event.duration),
+                "pid": 1,
+                "tid": 1,
+            })
+        }));
+
+        let trace = json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        });
+        serde_json::to_vec(&trace).unwrap_or_default()
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_nested_spans() {
+        let mut profiler = Profiler::new();
+
+        profiler.begin("Frame", TraceCategory::Frame);
+        profiler.begin("Main/update", TraceCategory::Method);
+        std::thread::sleep(Duration::from_millis(2));
+        profiler.begin("Main/draw", TraceCategory::Method);
+        profiler.end();
+        profiler.end();
+        profiler.begin("Main/draw", TraceCategory::Method);
+        profiler.end();
+        profiler.end();
+        profiler.end();
+
+        let names: Vec<_> = profiler.events().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Main/draw", "Main/update", "Main/draw", "Frame"]);
+        assert!(profiler.events()[1].duration >= profiler.events()[0].duration);
+
+        let update = profiler.totals()[&(TraceCategory::Method, "Main/update".to_string())];
+        assert_eq!(update.calls, 1);
+        assert!(update.exclusive <= update.total);
+        assert!(update.exclusive >= Duration::from_millis(2));
+        let draw = profiler.totals()[&(TraceCategory::Method, "Main/draw".to_string())];
+        assert_eq!(draw.calls, 2);
+        assert_eq!(profiler.totals().len(), 2);
+    }
+
+    #[test]
+    fn writes_chrome_trace_events() {
+        let mut profiler = Profiler::new();
+        let start = Instant::now();
+        profiler.begin("enterFrame", TraceCategory::Event);
+        profiler.end();
+        profiler.record("Render", TraceCategory::Render, start, Instant::now());
+
+        let trace: serde_json::Value = serde_json::from_slice(&profiler.to_chrome_trace()).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0]["ph"], "M");
+        assert_eq!(events[2]["name"], "enterFrame");
+        assert_eq!(events[2]["cat"], "event");
+        assert_eq!(events[2]["ph"], "X");
+        assert!(events[2]["ts"].is_f64());
+        assert_eq!(events[3]["cat"], "render");
+    }
+}
@@ -0,0 +1,1367 @@
+//! Compilation of verified method bodies to a register-based representation, and the
+//! interpreter that runs it.
+//!
+//! The operand stack has the same depth every time an op runs, so every slot of it can be
+//! treated as a register. Values that are only moved around on the stack, like the results of
+//! `GetLocal` and `Dup`, are tracked during translation instead of being copied at runtime, and
+//! arithmetic, comparisons, branches and domain memory accesses operate on registers directly
+//! whenever their operands are numbers. Every other op runs through the regular interpreter on
+//! the real operand stack.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::op::Op;
+use crate::avm2::optimize::{pool_multiname, successors};
+use crate::avm2::value::Value;
+use crate::avm2::verify::Exception;
+use crate::avm2::Error;
+use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use gc_arena::Gc;
+use std::collections::HashSet;
+use swf::avm2::types::{Index, Multiname as AbcMultiname};
+
+/// A location that holds a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reg {
+    /// A local register of the activation.
+    Local(u32),
+
+    /// The slot at the given depth of the operand stack.
+    Stack(u32),
+}
+
+/// A constant that can be loaded into a register.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Immediate {
+    Undefined,
+    Null,
+    Bool(bool),
+    Int(i32),
+    Uint(u32),
+    Number(f64),
+}
+
+impl Immediate {
+    fn to_value<'gc>(self) -> Value<'gc> {
+        match self {
+            Immediate::Undefined => Value::Undefined,
+            Immediate::Null => Value::Null,
+            Immediate::Bool(value) => value.into(),
+            Immediate::Int(value) => value.into(),
+            Immediate::Uint(value) => value.into(),
+            Immediate::Number(value) => value.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Instruction {
+    Move {
+        dst: Reg,
+        src: Reg,
+    },
+
+    Load {
+        dst: Reg,
+        value: Immediate,
+    },
+
+    /// Applies a unary arithmetic, logic or conversion op.
+    Unary {
+        op: Op,
+        dst: Reg,
+        src: Reg,
+    },
+
+    /// Applies a binary arithmetic, logic or comparison op.
+    Binary {
+        op: Op,
+        dst: Reg,
+        left: Reg,
+        right: Reg,
+    },
+
+    Jump {
+        target: u32,
+    },
+
+    /// Jumps if the boolean value of a register is `jump_if`.
+    JumpIf {
+        value: Reg,
+        jump_if: bool,
+        target: u32,
+    },
+
+    /// Jumps if the result of the comparison `op` differs from `negate`.
+    JumpIfCompare {
+        op: Op,
+        negate: bool,
+        left: Reg,
+        right: Reg,
+        target: u32,
+    },
+
+    Switch {
+        index: Reg,
+        default: u32,
+        cases: Box<[u32]>,
+    },
+
+    /// Reads from domain memory with one of the `Li*` or `Lf*` ops.
+    LoadMemory {
+        op: Op,
+        dst: Reg,
+        address: Reg,
+    },
+
+    /// Writes to domain memory with one of the `Si*` or `Sf*` ops.
+    StoreMemory {
+        op: Op,
+        value: Reg,
+        address: Reg,
+    },
+
+    GetSlot {
+        dst: Reg,
+        object: Reg,
+        index: u32,
+    },
+
+    SetSlot {
+        object: Reg,
+        value: Reg,
+        index: u32,
+    },
+
+    Return {
+        value: Reg,
+    },
+
+    ReturnVoid,
+
+    /// Runs the op at the given index on the operand stack.
+    ///
+    /// Its inputs are taken from the stack registers starting at `base`, and its outputs are
+    /// stored back into them.
+    Stack {
+        op: u32,
+        base: u32,
+        pops: u32,
+        pushes: u32,
+    },
+}
+
+/// The register-based translation of a verified method body.
+#[derive(Debug)]
+pub struct RegisterCode {
+    pub instructions: Vec<Instruction>,
+
+    /// The index of the op each instruction was translated from.
+    pub op_indices: Vec<u32>,
+
+    /// The index of the first instruction translated from each op, followed by the number of
+    /// instructions.
+    pub op_starts: Vec<u32>,
+
+    pub num_stack_registers: u32,
+}
+
+/// Translates the code of a verified method, if every reachable op is supported.
+pub fn compile<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+    code: &[Op],
+    exceptions: &[Exception],
+) -> Option<RegisterCode> {
+    translate(code, exceptions, |index| {
+        pool_multiname(activation, method, index).map(|(_, components)| components as u32)
+    })
+}
+
+/// The number of values an op pops off and pushes onto the operand stack.
+///
+/// `runtime_components` gives the number of values a multiname operand pops off the stack.
+fn stack_effect(
+    op: &Op,
+    mut runtime_components: impl FnMut(Index<AbcMultiname>) -> Option<u32>,
+) -> Option<(u32, u32)> {
+    let effect = match op {
+        Op::Add
+        | Op::AddI
+        | Op::AsTypeLate
+        | Op::BitAnd
+        | Op::BitOr
+        | Op::BitXor
+        | Op::Divide
+        | Op::Equals
+        | Op::GreaterEquals
+        | Op::GreaterThan
+        | Op::HasNext
+        | Op::In
+        | Op::InstanceOf
+        | Op::IsTypeLate
+        | Op::LessEquals
+        | Op::LessThan
+        | Op::LShift
+        | Op::Modulo
+        | Op::Multiply
+        | Op::MultiplyI
+        | Op::NextName
+        | Op::NextValue
+        | Op::RShift
+        | Op::StrictEquals
+        | Op::Subtract
+        | Op::SubtractI
+        | Op::URShift => (2, 1),
+        Op::AsType { .. }
+        | Op::BitNot
+        | Op::CheckFilter
+        | Op::Coerce { .. }
+        | Op::CoerceA
+        | Op::CoerceB
+        | Op::CoerceD
+        | Op::CoerceI
+        | Op::CoerceO
+        | Op::CoerceS
+        | Op::CoerceU
+        | Op::ConvertO
+        | Op::ConvertS
+        | Op::Decrement
+        | Op::DecrementI
+        | Op::EscXAttr
+        | Op::EscXElem
+        | Op::GetSlot { .. }
+        | Op::Increment
+        | Op::IncrementI
+        | Op::IsType { .. }
+        | Op::Lf32
+        | Op::Lf64
+        | Op::Li16
+        | Op::Li32
+        | Op::Li8
+        | Op::Negate
+        | Op::NegateI
+        | Op::NewClass { .. }
+        | Op::Not
+        | Op::Sxi1
+        | Op::Sxi16
+        | Op::Sxi8
+        | Op::TypeOf => (1, 1),
+        Op::Bkpt
+        | Op::BkptLine { .. }
+        | Op::Debug { .. }
+        | Op::DebugFile { .. }
+        | Op::DebugLine { .. }
+        | Op::DecLocal { .. }
+        | Op::DecLocalI { .. }
+        | Op::Dxns { .. }
+        | Op::IncLocal { .. }
+        | Op::IncLocalI { .. }
+        | Op::Jump { .. }
+        | Op::Kill { .. }
+        | Op::Nop
+        | Op::PopScope
+        | Op::ReturnVoid
+        | Op::Timestamp => (0, 0),
+        Op::DxnsLate
+        | Op::IfFalse { .. }
+        | Op::IfTrue { .. }
+        | Op::LookupSwitch(_)
+        | Op::Pop
+        | Op::PushScope
+        | Op::PushWith
+        | Op::ReturnValue
+        | Op::SetGlobalSlot { .. }
+        | Op::SetLocal { .. }
+        | Op::Throw => (1, 0),
+        Op::FindDef { .. }
+        | Op::GetGlobalScope
+        | Op::GetGlobalSlot { .. }
+        | Op::GetLex { .. }
+        | Op::GetLocal { .. }
+        | Op::GetOuterScope { .. }
+        | Op::GetScopeObject { .. }
+        | Op::HasNext2 { .. }
+        | Op::NewActivation
+        | Op::NewCatch { .. }
+        | Op::NewFunction { .. }
+        | Op::PushByte { .. }
+        | Op::PushDouble { .. }
+        | Op::PushFalse
+        | Op::PushInt { .. }
+        | Op::PushNamespace { .. }
+        | Op::PushNaN
+        | Op::PushNull
+        | Op::PushShort { .. }
+        | Op::PushString { .. }
+        | Op::PushTrue
+        | Op::PushUint { .. }
+        | Op::PushUndefined => (0, 1),
+        Op::IfEq { .. }
+        | Op::IfGe { .. }
+        | Op::IfGt { .. }
+        | Op::IfLe { .. }
+        | Op::IfLt { .. }
+        | Op::IfNe { .. }
+        | Op::IfNge { .. }
+        | Op::IfNgt { .. }
+        | Op::IfNle { .. }
+        | Op::IfNlt { .. }
+        | Op::IfStrictEq { .. }
+        | Op::IfStrictNe { .. }
+        | Op::SetSlot { .. }
+        | Op::Si8
+        | Op::Si16
+        | Op::Si32
+        | Op::Sf32
+        | Op::Sf64 => (2, 0),
+        Op::Dup => (1, 2),
+        Op::Swap => (2, 2),
+        Op::ApplyType { num_types } => (num_types + 1, 1),
+        Op::Call { num_args } => (num_args + 2, 1),
+        Op::CallMethod { num_args, .. }
+        | Op::CallStatic { num_args, .. }
+        | Op::Construct { num_args } => (num_args + 1, 1),
+        Op::ConstructSuper { num_args } => (num_args + 1, 0),
+        Op::CallProperty { index, num_args }
+        | Op::CallPropLex { index, num_args }
+        | Op::CallSuper { index, num_args }
+        | Op::ConstructProp { index, num_args } => (num_args + 1 + runtime_components(*index)?, 1),
+        Op::CallPropVoid { index, num_args } | Op::CallSuperVoid { index, num_args } => {
+            (num_args + 1 + runtime_components(*index)?, 0)
+        }
+        Op::DeleteProperty { index }
+        | Op::GetDescendants { index }
+        | Op::GetProperty { index }
+        | Op::GetSuper { index } => (1 + runtime_components(*index)?, 1),
+        Op::InitProperty { index } | Op::SetProperty { index } | Op::SetSuper { index } => {
+            (2 + runtime_components(*index)?, 0)
+        }
+        Op::FindProperty { index } | Op::FindPropStrict { index } => {
+            (runtime_components(*index)?, 1)
+        }
+        Op::NewArray { num_args } => (*num_args, 1),
+        Op::NewObject { num_args } => (num_args * 2, 1),
+    };
+    Some(effect)
+}
+
+/// Whether the op after an op may run after it.
+fn falls_through(op: &Op) -> bool {
+    !matches!(
+        op,
+        Op::Jump { .. } | Op::LookupSwitch(_) | Op::ReturnValue | Op::ReturnVoid | Op::Throw
+    )
+}
+
+/// The comparison op that decides a conditional branch, and whether the branch is taken when
+/// the comparison is false.
+fn branch_comparison(op: &Op) -> Option<(Op, bool)> {
+    let comparison = match op {
+        Op::IfEq { .. } => (Op::Equals, false),
+        Op::IfNe { .. } => (Op::Equals, true),
+        Op::IfStrictEq { .. } => (Op::StrictEquals, false),
+        Op::IfStrictNe { .. } => (Op::StrictEquals, true),
+        Op::IfLt { .. } => (Op::LessThan, false),
+        Op::IfNlt { .. } => (Op::LessThan, true),
+        Op::IfLe { .. } => (Op::LessEquals, false),
+        Op::IfNle { .. } => (Op::LessEquals, true),
+        Op::IfGt { .. } => (Op::GreaterThan, false),
+        Op::IfNgt { .. } => (Op::GreaterThan, true),
+        Op::IfGe { .. } => (Op::GreaterEquals, false),
+        Op::IfNge { .. } => (Op::GreaterEquals, true),
+        _ => return None,
+    };
+    Some(comparison)
+}
+
+/// The index of the op a jump from the op at `index` lands on.
+fn jump_target(index: usize, offset: i32) -> u32 {
+    (index as i64 + 1 + offset as i64) as u32
+}
+
+struct Translator {
+    instructions: Vec<Instruction>,
+    op_indices: Vec<u32>,
+
+    /// The register each slot of the operand stack currently refers to.
+    ///
+    /// A slot either refers to its own stack register, to a local register, or to the stack
+    /// register of a slot below it which refers to its own register.
+    stack: Vec<Reg>,
+
+    /// The index of the op being translated.
+    index: u32,
+}
+
+impl Translator {
+    fn emit(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+        self.op_indices.push(self.index);
+    }
+
+    fn pop(&mut self) -> Option<Reg> {
+        self.stack.pop()
+    }
+
+    /// Pushes a slot that refers to its own stack register, and returns that register.
+    fn push(&mut self) -> Reg {
+        let reg = Reg::Stack(self.stack.len() as u32);
+        self.stack.push(reg);
+        reg
+    }
+
+    /// Resets the operand stack to the given depth, with every slot in its own register.
+    fn reset(&mut self, depth: u32) {
+        self.stack = (0..depth).map(Reg::Stack).collect();
+    }
+
+    /// Copies the value of every slot from the given depth up into its own stack register.
+    fn materialize(&mut self, from: usize) {
+        for depth in from..self.stack.len() {
+            let own = Reg::Stack(depth as u32);
+            let src = self.stack[depth];
+            if src != own {
+                self.emit(Instruction::Move { dst: own, src });
+                self.stack[depth] = own;
+            }
+        }
+    }
+
+    /// Copies the value of every slot that refers to a local register into its own stack
+    /// register, before that local is written to.
+    fn materialize_local(&mut self, local: u32) {
+        for depth in 0..self.stack.len() {
+            if self.stack[depth] == Reg::Local(local) {
+                let own = Reg::Stack(depth as u32);
+                self.emit(Instruction::Move {
+                    dst: own,
+                    src: Reg::Local(local),
+                });
+                self.stack[depth] = own;
+            }
+        }
+    }
+
+    fn unary(&mut self, op: &Op) -> Option<()> {
+        let src = self.pop()?;
+        let dst = self.push();
+        self.emit(Instruction::Unary {
+            op: op.clone(),
+            dst,
+            src,
+        });
+        Some(())
+    }
+
+    fn binary(&mut self, op: &Op) -> Option<()> {
+        let right = self.pop()?;
+        let left = self.pop()?;
+        let dst = self.push();
+        self.emit(Instruction::Binary {
+            op: op.clone(),
+            dst,
+            left,
+            right,
+        });
+        Some(())
+    }
+
+    fn load(&mut self, value: Immediate) {
+        let dst = self.push();
+        self.emit(Instruction::Load { dst, value });
+    }
+
+    /// Runs an op on the operand stack.
+    fn stack_op(&mut self, pops: u32, pushes: u32) -> Option<()> {
+        let base = self.stack.len().checked_sub(pops as usize)?;
+        self.materialize(base);
+        self.stack.truncate(base);
+        self.emit(Instruction::Stack {
+            op: self.index,
+            base: base as u32,
+            pops,
+            pushes,
+        });
+        for _ in 0..pushes {
+            self.push();
+        }
+        Some(())
+    }
+
+    /// Translates a single reachable op.
+    fn translate_op(&mut self, op: &Op, pops: u32, pushes: u32) -> Option<()> {
+        let index = self.index as usize;
+        match op {
+            // Debug information is only used for logging and by the debugger, which runs the
+            // original ops.
+            Op::Nop
+            | Op::CoerceA
+            | Op::Bkpt
+            | Op::BkptLine { .. }
+            | Op::Debug { .. }
+            | Op::DebugFile { .. }
+            | Op::DebugLine { .. } => {}
+            Op::PushByte { value } => self.load(Immediate::Int(*value as i8 as i32)),
+            Op::PushShort { value } => self.load(Immediate::Int(*value as i32)),
+            Op::PushInt { value } => self.load(Immediate::Int(*value)),
+            Op::PushUint { value } => self.load(Immediate::Uint(*value)),
+            Op::PushDouble { value } => self.load(Immediate::Number(*value)),
+            Op::PushNaN => self.load(Immediate::Number(f64::NAN)),
+            Op::PushTrue => self.load(Immediate::Bool(true)),
+            Op::PushFalse => self.load(Immediate::Bool(false)),
+            Op::PushNull => self.load(Immediate::Null),
+            Op::PushUndefined => self.load(Immediate::Undefined),
+            Op::GetLocal { index } => self.stack.push(Reg::Local(*index)),
+            Op::SetLocal { index } => {
+                let src = self.pop()?;
+                self.materialize_local(*index);
+                if src != Reg::Local(*index) {
+                    self.emit(Instruction::Move {
+                        dst: Reg::Local(*index),
+                        src,
+                    });
+                }
+            }
+            Op::Kill { index } => {
+                self.materialize_local(*index);
+                self.emit(Instruction::Load {
+                    dst: Reg::Local(*index),
+                    value: Immediate::Undefined,
+                });
+            }
+            Op::IncLocal { index }
+            | Op::IncLocalI { index }
+            | Op::DecLocal { index }
+            | Op::DecLocalI { index } => {
+                let op = match op {
+                    Op::IncLocal { .. } => Op::Increment,
+                    Op::IncLocalI { .. } => Op::IncrementI,
+                    Op::DecLocal { .. } => Op::Decrement,
+                    _ => Op::DecrementI,
+                };
+                self.materialize_local(*index);
+                self.emit(Instruction::Unary {
+                    op,
+                    dst: Reg::Local(*index),
+                    src: Reg::Local(*index),
+                });
+            }
+            Op::Pop => {
+                self.pop()?;
+            }
+            Op::Dup => {
+                let top = *self.stack.last()?;
+                self.stack.push(top);
+            }
+            Op::Not
+            | Op::Negate
+            | Op::NegateI
+            | Op::Increment
+            | Op::IncrementI
+            | Op::Decrement
+            | Op::DecrementI
+            | Op::BitNot
+            | Op::CoerceB
+            | Op::CoerceD
+            | Op::CoerceI
+            | Op::CoerceU
+            | Op::Sxi1
+            | Op::Sxi8
+            | Op::Sxi16 => self.unary(op)?,
+            Op::Add
+            | Op::AddI
+            | Op::Subtract
+            | Op::SubtractI
+            | Op::Multiply
+            | Op::MultiplyI
+            | Op::Divide
+            | Op::Modulo
+            | Op::BitAnd
+            | Op::BitOr
+            | Op::BitXor
+            | Op::LShift
+            | Op::RShift
+            | Op::URShift
+            | Op::Equals
+            | Op::StrictEquals
+            | Op::LessThan
+            | Op::LessEquals
+            | Op::GreaterThan
+            | Op::GreaterEquals => self.binary(op)?,
+            Op::Li8 | Op::Li16 | Op::Li32 | Op::Lf32 | Op::Lf64 => {
+                let address = self.pop()?;
+                let dst = self.push();
+                self.emit(Instruction::LoadMemory {
+                    op: op.clone(),
+                    dst,
+                    address,
+                });
+            }
+            Op::Si8 | Op::Si16 | Op::Si32 | Op::Sf32 | Op::Sf64 => {
+                let address = self.pop()?;
+                let value = self.pop()?;
+                self.emit(Instruction::StoreMemory {
+                    op: op.clone(),
+                    value,
+                    address,
+                });
+            }
+            Op::GetSlot { index } => {
+                let object = self.pop()?;
+                let dst = self.push();
+                self.emit(Instruction::GetSlot {
+                    dst,
+                    object,
+                    index: *index,
+                });
+            }
+            Op::SetSlot { index } => {
+                let value = self.pop()?;
+                let object = self.pop()?;
+                self.emit(Instruction::SetSlot {
+                    object,
+                    value,
+                    index: *index,
+                });
+            }
+            Op::Jump { offset } => {
+                self.materialize(0);
+                self.emit(Instruction::Jump {
+                    target: jump_target(index, *offset),
+                });
+            }
+            Op::IfTrue { offset } | Op::IfFalse { offset } => {
+                let value = self.pop()?;
+                self.materialize(0);
+                self.emit(Instruction::JumpIf {
+                    value,
+                    jump_if: matches!(op, Op::IfTrue { .. }),
+                    target: jump_target(index, *offset),
+                });
+            }
+            Op::IfEq { offset }
+            | Op::IfNe { offset }
+            | Op::IfStrictEq { offset }
+            | Op::IfStrictNe { offset }
+            | Op::IfLt { offset }
+            | Op::IfNlt { offset }
+            | Op::IfLe { offset }
+            | Op::IfNle { offset }
+            | Op::IfGt { offset }
+            | Op::IfNgt { offset }
+            | Op::IfGe { offset }
+            | Op::IfNge { offset } => {
+                let (comparison, negate) = branch_comparison(op)?;
+                let right = self.pop()?;
+                let left = self.pop()?;
+                self.materialize(0);
+                self.emit(Instruction::JumpIfCompare {
+                    op: comparison,
+                    negate,
+                    left,
+                    right,
+                    target: jump_target(index, *offset),
+                });
+            }
+            Op::LookupSwitch(lookup_switch) => {
+                let value = self.pop()?;
+                self.materialize(0);
+                self.emit(Instruction::Switch {
+                    index: value,
+                    default: jump_target(index, lookup_switch.default_offset),
+                    cases: lookup_switch
+                        .case_offsets
+                        .iter()
+                        .map(|offset| jump_target(index, *offset))
+                        .collect(),
+                });
+            }
+            Op::ReturnValue => {
+                let value = self.pop()?;
+                self.emit(Instruction::Return { value });
+            }
+            Op::ReturnVoid => self.emit(Instruction::ReturnVoid),
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => {
+                self.materialize_local(*object_register);
+                self.materialize_local(*index_register);
+                self.stack_op(pops, pushes)?;
+            }
+            _ => self.stack_op(pops, pushes)?,
+        }
+        Some(())
+    }
+
+    /// Replaces the op indices in jump targets with the index of their first instruction.
+    fn resolve_targets(&mut self, op_starts: &[u32]) {
+        let resolve = |target: &mut u32| *target = op_starts[*target as usize];
+        for instruction in &mut self.instructions {
+            match instruction {
+                Instruction::Jump { target }
+                | Instruction::JumpIf { target, .. }
+                | Instruction::JumpIfCompare { target, .. } => resolve(target),
+                Instruction::Switch { default, cases, .. } => {
+                    resolve(default);
+                    cases.iter_mut().for_each(resolve);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Translates verified code to registers.
+///
+/// This fails if the depth of the operand stack at some op depends on the path taken to it, or
+/// if a reachable op can't be translated.
+fn translate(
+    code: &[Op],
+    exceptions: &[Exception],
+    mut runtime_components: impl FnMut(Index<AbcMultiname>) -> Option<u32>,
+) -> Option<RegisterCode> {
+    if code.is_empty() {
+        return None;
+    }
+
+    // Find the depth of the operand stack before every reachable op.
+    let mut effects = vec![None; code.len()];
+    let mut depths: Vec<Option<u32>> = vec![None; code.len()];
+    let mut num_stack_registers = 1;
+    let mut block_starts = HashSet::new();
+    let mut worklist = vec![(0, 0)];
+    for exception in exceptions {
+        let target = exception.target_offset as usize;
+        block_starts.insert(target);
+        // The error that was caught is the only value on the stack.
+        worklist.push((target, 1));
+    }
+    while let Some((index, depth)) = worklist.pop() {
+        let op = code.get(index)?;
+        match depths[index] {
+            Some(known) if known == depth => continue,
+            Some(_) => return None,
+            None => depths[index] = Some(depth),
+        }
+        let (pops, pushes) = stack_effect(op, &mut runtime_components)?;
+        effects[index] = Some((pops, pushes));
+        let after = depth.checked_sub(pops)? + pushes;
+        num_stack_registers = num_stack_registers.max(after).max(depth);
+
+        if falls_through(op) && index + 1 == code.len() {
+            return None;
+        }
+        let successors = successors(code, index);
+        let is_branch = !matches!(successors.as_slice(), [next] if *next == index + 1);
+        for successor in successors {
+            if is_branch {
+                block_starts.insert(successor);
+            }
+            worklist.push((successor, after));
+        }
+    }
+
+    let mut translator = Translator {
+        instructions: Vec::new(),
+        op_indices: Vec::new(),
+        stack: Vec::new(),
+        index: 0,
+    };
+    let mut op_starts = Vec::with_capacity(code.len() + 1);
+    let mut previous_falls_through = false;
+    for (index, op) in code.iter().enumerate() {
+        let (Some(depth), Some((pops, pushes))) = (depths[index], effects[index]) else {
+            // Unreachable ops don't need any instructions.
+            op_starts.push(translator.instructions.len() as u32);
+            previous_falls_through = false;
+            continue;
+        };
+
+        if !previous_falls_through {
+            translator.reset(depth);
+        } else if block_starts.contains(&index) {
+            translator.materialize(0);
+        }
+        if translator.stack.len() != depth as usize {
+            return None;
+        }
+
+        translator.index = index as u32;
+        op_starts.push(translator.instructions.len() as u32);
+        translator.translate_op(op, pops, pushes)?;
+        if translator.stack.len() != (depth - pops + pushes) as usize {
+            return None;
+        }
+        previous_falls_through = falls_through(op);
+    }
+    op_starts.push(translator.instructions.len() as u32);
+
+    translator.resolve_targets(&op_starts);
+    Some(RegisterCode {
+        instructions: translator.instructions,
+        op_indices: translator.op_indices,
+        op_starts,
+        num_stack_registers,
+    })
+}
+
+enum Flow<'gc> {
+    Continue,
+    Jump(u32),
+    Return(Value<'gc>),
+}
+
+/// Runs the register-based translation of a method.
+pub fn run<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    code: &[Op],
+    register_code: &RegisterCode,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut interpreter = Interpreter {
+        activation,
+        method,
+        code,
+        registers: vec![Value::Undefined; register_code.num_stack_registers as usize],
+        domain_memory: None,
+    };
+
+    let mut ip = 0;
+    loop {
+        let index = ip;
+        ip += 1;
+        let result = interpreter
+            .activation
+            .check_timeout()
+            .and_then(|()| interpreter.run_instruction(&register_code.instructions[index]));
+        match result {
+            Ok(Flow::Continue) => {}
+            Ok(Flow::Jump(target)) => ip = target as usize,
+            Ok(Flow::Return(value)) => return Ok(value),
+            Err(error) => {
+                // Exception handlers cover ranges of the original ops, so look up the handler
+                // of the op the failing instruction belongs to.
+                let op_index = register_code.op_indices[index] as usize;
+                let handler = interpreter
+                    .activation
+                    .catch_error(method, op_index, error)?;
+                ip = register_code.op_starts[handler] as usize;
+                interpreter.registers[0] = interpreter.activation.pop_stack();
+            }
+        }
+    }
+}
+
+struct Interpreter<'a, 'b, 'gc> {
+    activation: &'a mut Activation<'b, 'gc>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    code: &'a [Op],
+
+    /// The stack registers.
+    registers: Vec<Value<'gc>>,
+
+    /// The domain memory, which is looked up once and then reused until an op that may run
+    /// arbitrary code runs.
+    domain_memory: Option<ByteArrayObject<'gc>>,
+}
+
+impl<'a, 'b, 'gc> Interpreter<'a, 'b, 'gc> {
+    fn get(&self, reg: Reg) -> Value<'gc> {
+        match reg {
+            Reg::Local(index) => self.activation.local_register(index),
+            Reg::Stack(index) => self.registers[index as usize],
+        }
+    }
+
+    fn set(&mut self, reg: Reg, value: Value<'gc>) {
+        match reg {
+            Reg::Local(index) => self.activation.set_local_register(index, value),
+            Reg::Stack(index) => self.registers[index as usize] = value,
+        }
+    }
+
+    /// Runs an op through the regular interpreter, with its inputs already on the operand
+    /// stack.
+    fn run_op(&mut self, op: &Op) -> Result<(), Error<'gc>> {
+        self.domain_memory = None;
+        self.activation.do_op(self.method, op)?;
+        Ok(())
+    }
+
+    /// Runs an op with a single output through the regular interpreter.
+    fn run_op_with(&mut self, op: &Op, inputs: &[Value<'gc>]) -> Result<Value<'gc>, Error<'gc>> {
+        for input in inputs {
+            self.activation.push_stack(*input);
+        }
+        self.run_op(op)?;
+        Ok(self.activation.pop_stack())
+    }
+
+    fn domain_memory(&mut self) -> ByteArrayObject<'gc> {
+        *self
+            .domain_memory
+            .get_or_insert_with(|| self.activation.domain_memory())
+    }
+
+    fn run_instruction(&mut self, instruction: &Instruction) -> Result<Flow<'gc>, Error<'gc>> {
+        match instruction {
+            Instruction::Move { dst, src } => {
+                let value = self.get(*src);
+                self.set(*dst, value);
+            }
+            Instruction::Load { dst, value } => self.set(*dst, value.to_value()),
+            Instruction::Unary { op, dst, src } => {
+                let value = self.get(*src);
+                let result = match unary(op, value) {
+                    Some(result) => result,
+                    None => self.run_op_with(op, &[value])?,
+                };
+                self.set(*dst, result);
+            }
+            Instruction::Binary {
+                op,
+                dst,
+                left,
+                right,
+            } => {
+                let result = self.binary(op, *left, *right)?;
+                self.set(*dst, result);
+            }
+            Instruction::Jump { target } => return Ok(Flow::Jump(*target)),
+            Instruction::JumpIf {
+                value,
+                jump_if,
+                target,
+            } => {
+                if self.get(*value).coerce_to_boolean() == *jump_if {
+                    return Ok(Flow::Jump(*target));
+                }
+            }
+            Instruction::JumpIfCompare {
+                op,
+                negate,
+                left,
+                right,
+                target,
+            } => {
+                if self.binary(op, *left, *right)?.coerce_to_boolean() != *negate {
+                    return Ok(Flow::Jump(*target));
+                }
+            }
+            Instruction::Switch {
+                index,
+                default,
+                cases,
+            } => {
+                let index = match self.get(*index) {
+                    Value::Integer(index) => index,
+                    value => value.coerce_to_i32(self.activation).map_err(|_| {
+                        Error::from(
+                            "VerifyError: Invalid value type on stack (should have been int) for LookupSwitch!",
+                        )
+                    })?,
+                };
+                let target = cases.get(index as usize).copied().unwrap_or(*default);
+                return Ok(Flow::Jump(target));
+            }
+            Instruction::LoadMemory { op, dst, address } => {
+                let address = self.get(*address);
+                let value = match self.load(op, address) {
+                    Some(value) => value,
+                    None => self.run_op_with(op, &[address])?,
+                };
+                self.set(*dst, value);
+            }
+            Instruction::StoreMemory { op, value, address } => {
+                let value = self.get(*value);
+                let address = self.get(*address);
+                if self.store(op, value, address).is_none() {
+                    self.activation.push_stack(value);
+                    self.activation.push_stack(address);
+                    self.run_op(op)?;
+                }
+            }
+            Instruction::GetSlot { dst, object, index } => {
+                let value = match self.get(*object) {
+                    Value::Object(object) => object.get_slot(*index)?,
+                    object => self.run_op_with(&Op::GetSlot { index: *index }, &[object])?,
+                };
+                self.set(*dst, value);
+            }
+            Instruction::SetSlot {
+                object,
+                value,
+                index,
+            } => {
+                let value = self.get(*value);
+                match self.get(*object) {
+                    Value::Object(object) => object.set_slot(*index, value, self.activation)?,
+                    object => {
+                        self.activation.push_stack(object);
+                        self.activation.push_stack(value);
+                        self.run_op(&Op::SetSlot { index: *index })?;
+                    }
+                }
+            }
+            Instruction::Return { value } => {
+                let value = self
+                    .get(*value)
+                    .coerce_to_type_name(self.activation, &self.method.return_type)?;
+                return Ok(Flow::Return(value));
+            }
+            Instruction::ReturnVoid => return Ok(Flow::Return(Value::Undefined)),
+            Instruction::Stack {
+                op,
+                base,
+                pops,
+                pushes,
+            } => {
+                let base = *base as usize;
+                for value in &self.registers[base..base + *pops as usize] {
+                    self.activation.push_stack(*value);
+                }
+                let code = self.code;
+                self.run_op(&code[*op as usize])?;
+                for register in self.registers[base..base + *pushes as usize]
+                    .iter_mut()
+                    .rev()
+                {
+                    *register = self.activation.pop_stack();
+                }
+            }
+        }
+        Ok(Flow::Continue)
+    }
+
+    fn binary(&mut self, op: &Op, left: Reg, right: Reg) -> Result<Value<'gc>, Error<'gc>> {
+        let left = self.get(left);
+        let right = self.get(right);
+        match binary(op, left, right) {
+            Some(result) => Ok(result),
+            None => self.run_op_with(op, &[left, right]),
+        }
+    }
+
+    /// Reads from domain memory, if the address is a number within it.
+    fn load(&mut self, op: &Op, address: Value<'gc>) -> Option<Value<'gc>> {
+        let address = to_u32(address)? as usize;
+        let domain_memory = self.domain_memory();
+        let storage = domain_memory.as_bytearray()?;
+        let bytes = storage.bytes();
+        let value = match op {
+            Op::Li8 => (*bytes.get(address)?).into(),
+            Op::Li16 => u16::from_le_bytes(read(bytes, address)?).into(),
+            Op::Li32 => i32::from_le_bytes(read(bytes, address)?).into(),
+            Op::Lf32 => f32::from_le_bytes(read(bytes, address)?).into(),
+            Op::Lf64 => f64::from_le_bytes(read(bytes, address)?).into(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Writes a number to domain memory, if the address is a number within it.
+    fn store(&mut self, op: &Op, value: Value<'gc>, address: Value<'gc>) -> Option<()> {
+        let address = usize::try_from(to_i32(address)?).ok()?;
+        let domain_memory = self.domain_memory();
+        let mut storage = domain_memory.as_bytearray_mut(self.activation.context.gc_context)?;
+        let bytes = storage.bytes_mut();
+        match op {
+            Op::Si8 => write(bytes, address, (to_i32(value)? as i8).to_le_bytes()),
+            Op::Si16 => write(bytes, address, (to_i32(value)? as i16).to_le_bytes()),
+            Op::Si32 => write(bytes, address, to_i32(value)?.to_le_bytes()),
+            Op::Sf32 => write(bytes, address, (to_number(value)? as f32).to_le_bytes()),
+            Op::Sf64 => write(bytes, address, to_number(value)?.to_le_bytes()),
+            _ => None,
+        }
+    }
+}
+
+fn read<const N: usize>(bytes: &[u8], address: usize) -> Option<[u8; N]> {
+    bytes.get(address..address.checked_add(N)?)?.try_into().ok()
+}
+
+fn write<const N: usize>(bytes: &mut [u8], address: usize, value: [u8; N]) -> Option<()> {
+    bytes
+        .get_mut(address..address.checked_add(N)?)?
+        .copy_from_slice(&value);
+    Some(())
+}
+
+fn to_number(value: Value<'_>) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(value as f64),
+        Value::Number(value) => Some(value),
+        _ => None,
+    }
+}
+
+fn to_i32(value: Value<'_>) -> Option<i32> {
+    match value {
+        Value::Integer(value) => Some(value),
+        Value::Number(value) => Some(f64_to_wrapping_i32(value)),
+        _ => None,
+    }
+}
+
+fn to_u32(value: Value<'_>) -> Option<u32> {
+    match value {
+        Value::Integer(value) => Some(value as u32),
+        Value::Number(value) => Some(f64_to_wrapping_u32(value)),
+        _ => None,
+    }
+}
+
+/// Applies a unary op without calling into the interpreter, if its operand is a number.
+fn unary<'gc>(op: &Op, value: Value<'gc>) -> Option<Value<'gc>> {
+    let result = match op {
+        Op::Not => Value::Bool(!value.coerce_to_boolean()),
+        Op::CoerceB => Value::Bool(value.coerce_to_boolean()),
+        Op::Negate => Value::Number(-to_number(value)?),
+        Op::Increment => Value::Number(to_number(value)? + 1.0),
+        Op::Decrement => Value::Number(to_number(value)? - 1.0),
+        Op::CoerceD => Value::Number(to_number(value)?),
+        Op::NegateI => to_i32(value)?.wrapping_neg().into(),
+        Op::IncrementI => to_i32(value)?.wrapping_add(1).into(),
+        Op::DecrementI => to_i32(value)?.wrapping_sub(1).into(),
+        Op::BitNot => (!to_i32(value)?).into(),
+        Op::CoerceI => to_i32(value)?.into(),
+        Op::CoerceU => to_u32(value)?.into(),
+        Op::Sxi1 => Value::Integer(to_i32(value)?.wrapping_shl(31).wrapping_shr(31)),
+        Op::Sxi8 => Value::Integer(to_i32(value)? as i8 as i32),
+        Op::Sxi16 => Value::Integer(to_i32(value)? as i16 as i32),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Applies a binary op without calling into the interpreter, if both operands are numbers.
+fn binary<'gc>(op: &Op, left: Value<'gc>, right: Value<'gc>) -> Option<Value<'gc>> {
+    if let (Value::Integer(left), Value::Integer(right)) = (left, right) {
+        match op {
+            Op::Add => return Some((left + right).into()),
+            Op::Subtract => return Some((left - right).into()),
+            _ => {}
+        }
+    }
+
+    let l = to_number(left)?;
+    let r = to_number(right)?;
+    let result = match op {
+        Op::Add => Value::Number(l + r),
+        Op::Subtract => Value::Number(l - r),
+        Op::Multiply => Value::Number(l * r),
+        Op::Divide => Value::Number(l / r),
+        Op::Modulo => Value::Number(l % r),
+        Op::Equals | Op::StrictEquals => Value::Bool(l == r),
+        Op::LessThan => Value::Bool(l < r),
+        Op::LessEquals => Value::Bool(l <= r),
+        Op::GreaterThan => Value::Bool(l > r),
+        Op::GreaterEquals => Value::Bool(l >= r),
+        _ => {
+            let left = to_i32(left)?;
+            match op {
+                Op::AddI => left.wrapping_add(to_i32(right)?).into(),
+                Op::SubtractI => left.wrapping_sub(to_i32(right)?).into(),
+                Op::MultiplyI => left.wrapping_mul(to_i32(right)?).into(),
+                Op::BitAnd => (left & to_i32(right)?).into(),
+                Op::BitOr => (left | to_i32(right)?).into(),
+                Op::BitXor => (left ^ to_i32(right)?).into(),
+                Op::LShift => (left << (to_u32(right)? & 0x1F)).into(),
+                Op::RShift => (left >> (to_u32(right)? & 0x1F)).into(),
+                Op::URShift => ((left as u32) >> (to_u32(right)? & 0x1F)).into(),
+                _ => return None,
+            }
+        }
+    };
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate_plain(code: &[Op], exceptions: &[Exception]) -> Option<RegisterCode> {
+        translate(code, exceptions, |_| Some(0))
+    }
+
+    #[test]
+    fn locals_are_used_in_place() {
+        // local2 = local1 + 1; return local2
+        let code = [
+            Op::GetLocal { index: 1 },
+            Op::PushByte { value: 1 },
+            Op::AddI,
+            Op::SetLocal { index: 2 },
+            Op::GetLocal { index: 2 },
+            Op::ReturnValue,
+        ];
+        let register_code = translate_plain(&code, &[]).unwrap();
+        assert_eq!(
+            register_code.instructions,
+            vec![
+                Instruction::Load {
+                    dst: Reg::Stack(1),
+                    value: Immediate::Int(1),
+                },
+                Instruction::Binary {
+                    op: Op::AddI,
+                    dst: Reg::Stack(0),
+                    left: Reg::Local(1),
+                    right: Reg::Stack(1),
+                },
+                Instruction::Move {
+                    dst: Reg::Local(2),
+                    src: Reg::Stack(0),
+                },
+                Instruction::Return {
+                    value: Reg::Local(2),
+                },
+            ]
+        );
+        assert_eq!(register_code.op_starts, vec![0, 0, 1, 2, 3, 3, 4]);
+        assert_eq!(register_code.num_stack_registers, 2);
+    }
+
+    #[test]
+    fn stack_is_materialized_before_local_writes_and_branches() {
+        // loop: push local1; local1 = local1 - 1; if (local1) goto loop; return
+        let code = [
+            Op::GetLocal { index: 1 },
+            Op::DecLocalI { index: 1 },
+            Op::GetLocal { index: 1 },
+            Op::IfTrue { offset: -4 },
+            Op::ReturnValue,
+        ];
+        let register_code = translate_plain(&code, &[]);
+        // The loop grows the stack, so the depth at its start isn't fixed.
+        assert!(register_code.is_none());
+
+        let code = [
+            Op::GetLocal { index: 1 },
+            Op::DecLocalI { index: 1 },
+            Op::GetLocal { index: 1 },
+            Op::IfTrue { offset: 1 },
+            Op::Nop,
+            Op::ReturnValue,
+        ];
+        let register_code = translate_plain(&code, &[]).unwrap();
+        assert_eq!(
+            register_code.instructions,
+            vec![
+                Instruction::Move {
+                    dst: Reg::Stack(0),
+                    src: Reg::Local(1),
+                },
+                Instruction::Unary {
+                    op: Op::DecrementI,
+                    dst: Reg::Local(1),
+                    src: Reg::Local(1),
+                },
+                Instruction::JumpIf {
+                    value: Reg::Local(1),
+                    jump_if: true,
+                    target: 3,
+                },
+                Instruction::Return {
+                    value: Reg::Stack(0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_ops_run_on_the_stack() {
+        let code = [
+            Op::GetLocal { index: 0 },
+            Op::Dup,
+            Op::CallProperty {
+                index: Index::new(1),
+                num_args: 0,
+            },
+            Op::Swap,
+            Op::Pop,
+            Op::ReturnValue,
+        ];
+        let register_code = translate_plain(&code, &[]).unwrap();
+        assert_eq!(
+            register_code.instructions,
+            vec![
+                Instruction::Move {
+                    dst: Reg::Stack(1),
+                    src: Reg::Local(0),
+                },
+                Instruction::Stack {
+                    op: 2,
+                    base: 1,
+                    pops: 1,
+                    pushes: 1,
+                },
+                Instruction::Move {
+                    dst: Reg::Stack(0),
+                    src: Reg::Local(0),
+                },
+                Instruction::Stack {
+                    op: 3,
+                    base: 0,
+                    pops: 2,
+                    pushes: 2,
+                },
+                Instruction::Return {
+                    value: Reg::Stack(0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn exception_handlers_start_with_the_error() {
+        let code = [Op::GetLocal { index: 0 }, Op::Throw, Op::ReturnValue];
+        let exceptions = [Exception {
+            from_offset: 0,
+            to_offset: 2,
+            target_offset: 2,
+            variable_name: Index::new(0),
+            type_name: Index::new(0),
+        }];
+        let register_code = translate_plain(&code, &exceptions).unwrap();
+        assert_eq!(register_code.op_starts, vec![0, 0, 2, 3]);
+        assert_eq!(
+            register_code.instructions[2],
+            Instruction::Return {
+                value: Reg::Stack(0),
+            }
+        );
+    }
+
+    #[test]
+    fn numeric_fast_paths() {
+        assert_eq!(
+            binary(&Op::Add, Value::Integer(1), Value::Integer(2)),
+            Some(Value::Integer(3))
+        );
+        assert_eq!(
+            binary(&Op::AddI, Value::Integer(i32::MAX), Value::Integer(1)),
+            Some(Value::Number(i32::MIN as f64))
+        );
+        assert_eq!(
+            binary(&Op::URShift, Value::Integer(-1), Value::Integer(28)),
+            Some(Value::Integer(15))
+        );
+        assert_eq!(
+            binary(&Op::LessThan, Value::Number(f64::NAN), Value::Integer(1)),
+            Some(Value::Bool(false))
+        );
+        assert_eq!(binary(&Op::Add, Value::Null, Value::Integer(1)), None);
+        assert_eq!(
+            unary(&Op::Sxi8, Value::Integer(0xFF)),
+            Some(Value::Integer(-1))
+        );
+        assert_eq!(
+            unary(&Op::CoerceU, Value::Integer(-1)),
+            Some(Value::Number(u32::MAX as f64))
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Allocation and call stack sampling, as exposed by `flash.sampler`.
+
+use crate::avm2::call_stack::CallStack;
+use crate::avm2::dynamic_map::{DynamicKey, DynamicProperty};
+use crate::avm2::object::{ClassObject, Object, ScriptObjectData, TObject, WeakObject};
+use crate::avm2::value::Value;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Mutation};
+use std::mem::size_of;
+use web_time::{Duration, Instant};
+
+/// The minimum time between two samples of the call stack.
+const STACK_SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The estimated bookkeeping overhead of every allocation in the GC arena.
+pub const GC_HEADER_SIZE: usize = 4 * size_of::<usize>();
+
+/// The estimated size of a string, excluding its characters.
+const STRING_HEADER_SIZE: usize = GC_HEADER_SIZE + 4 * size_of::<usize>();
+
+/// A single call of a sampled call stack.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+pub struct SampledFrame {
+    /// The name of the function, as displayed in stack traces, without its arguments.
+    pub name: String,
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub enum SampleKind<'gc> {
+    /// A periodic sample of the call stack.
+    Stack,
+
+    /// An object was allocated.
+    NewObject { id: u64, class: ClassObject<'gc> },
+
+    /// A previously allocated object was garbage collected.
+    DeleteObject { id: u64, size: usize },
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct Sample<'gc> {
+    /// The time of this sample, in microseconds since sampling was started.
+    pub time: f64,
+
+    /// The call stack at the time of this sample, innermost call first.
+    #[collect(require_static)]
+    pub stack: Vec<SampledFrame>,
+
+    pub kind: SampleKind<'gc>,
+}
+
+/// An object whose allocation was sampled, which will produce a `DeleteObject` sample once
+/// it is collected.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct TrackedObject<'gc> {
+    object: WeakObject<'gc>,
+    size: usize,
+    deleted: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SamplerState {
+    Stopped,
+    Running,
+    Paused,
+}
+
+/// Collects samples of the call stack and of allocations while sampling is enabled.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    #[collect(require_static)]
+    state: SamplerState,
+
+    /// When sampling was started.
+    #[collect(require_static)]
+    start: Instant,
+
+    /// When the call stack was last sampled.
+    #[collect(require_static)]
+    last_stack_sample: Instant,
+
+    /// The samples collected since the samples were last cleared.
+    samples: Vec<Sample<'gc>>,
+
+    /// The objects whose allocation was sampled, by their sample ID.
+    objects: FnvHashMap<u64, TrackedObject<'gc>>,
+
+    next_id: u64,
+}
+
+impl<'gc> Sampler<'gc> {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            state: SamplerState::Stopped,
+            start: now,
+            last_stack_sample: now,
+            samples: Vec::new(),
+            objects: FnvHashMap::default(),
+            next_id: 1,
+        }
+    }
+
+    pub fn is_sampling(&self) -> bool {
+        self.state == SamplerState::Running
+    }
+
+    /// Starts sampling, or resumes it if it was paused.
+    pub fn start(&mut self) {
+        if self.state == SamplerState::Stopped {
+            self.start = Instant::now();
+            self.last_stack_sample = self.start;
+        }
+        self.state = SamplerState::Running;
+    }
+
+    /// Pauses sampling, keeping the samples collected so far.
+    pub fn pause(&mut self) {
+        if self.state == SamplerState::Running {
+            self.state = SamplerState::Paused;
+        }
+    }
+
+    /// Stops sampling and discards all samples.
+    pub fn stop(&mut self) {
+        self.state = SamplerState::Stopped;
+        self.samples.clear();
+        self.objects.clear();
+    }
+
+    /// Discards the samples collected so far.
+    ///
+    /// Objects that are still alive keep being tracked, so that their deletion is still sampled.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.objects.retain(|_, object| !object.deleted);
+    }
+
+    pub fn samples(&self) -> &[Sample<'gc>] {
+        &self.samples
+    }
+
+    /// Samples the call stack, if enough time has passed since the last sample.
+    pub fn sample_stack(&mut self, call_stack: &CallStack<'gc>) {
+        if !self.is_sampling() {
+            return;
+        }
+        let now = Instant::now();
+        if now.duration_since(self.last_stack_sample) < STACK_SAMPLE_INTERVAL {
+            return;
+        }
+        self.last_stack_sample = now;
+        self.push(now, call_stack.sample(), SampleKind::Stack);
+    }
+
+    /// Samples the allocation of an object.
+    pub fn sample_allocation(
+        &mut self,
+        object: Object<'gc>,
+        class: ClassObject<'gc>,
+        call_stack: &CallStack<'gc>,
+    ) {
+        if !self.is_sampling() {
+            return;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.objects.insert(
+            id,
+            TrackedObject {
+                object: object.downgrade(),
+                size: estimate_size(object.into()),
+                deleted: false,
+            },
+        );
+        self.push(
+            Instant::now(),
+            call_stack.sample(),
+            SampleKind::NewObject { id, class },
+        );
+    }
+
+    /// Samples the deletion of every tracked object that was garbage collected since the last
+    /// call.
+    pub fn sample_deletions(&mut self, mc: &Mutation<'gc>) {
+        if self.state == SamplerState::Stopped {
+            return;
+        }
+        let now = Instant::now();
+        let mut deleted: Vec<_> = self
+            .objects
+            .iter_mut()
+            .filter(|(_, object)| !object.deleted && object.object.upgrade(mc).is_none())
+            .map(|(id, object)| {
+                object.deleted = true;
+                (*id, object.size)
+            })
+            .collect();
+        deleted.sort_unstable();
+        for (id, size) in deleted {
+            self.push(now, Vec::new(), SampleKind::DeleteObject { id, size });
+        }
+    }
+
+    /// The object allocated in the `NewObject` sample with the given ID, if it is still alive.
+    pub fn object(&self, id: u64, mc: &Mutation<'gc>) -> Option<Object<'gc>> {
+        self.objects
+            .get(&id)
+            .and_then(|object| object.object.upgrade(mc))
+    }
+
+    /// The estimated size of the object allocated in the `NewObject` sample with the given ID.
+    pub fn object_size(&self, id: u64) -> Option<usize> {
+        self.objects.get(&id).map(|object| object.size)
+    }
+
+    fn push(&mut self, now: Instant, stack: Vec<SampledFrame>, kind: SampleKind<'gc>) {
+        let time = now.duration_since(self.start).as_secs_f64() * 1_000_000.0;
+        self.samples.push(Sample { time, stack, kind });
+    }
+}
+
+impl<'gc> Default for Sampler<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Estimates the memory used by a value, as reported by `flash.sampler.getSize`.
+///
+/// This counts the allocations made for the value in the GC arena and the storage owned by them,
+/// but not the values they refer to.
+pub fn estimate_size(value: Value<'_>) -> usize {
+    match value {
+        Value::Undefined | Value::Null => 0,
+        Value::Bool(_) | Value::Integer(_) => 4,
+        Value::Number(_) => 8,
+        Value::String(string) => {
+            let char_size = if string.is_wide() { 2 } else { 1 };
+            STRING_HEADER_SIZE + string.len() * char_size
+        }
+        Value::Object(object) => object_size(object),
+    }
+}
+
+fn object_size(object: Object<'_>) -> usize {
+    let base = object.base();
+    let slots = base
+        .vtable()
+        .map_or(0, |vtable| vtable.default_slots().len());
+    let dynamic_properties = base.values().as_hashmap().len();
+    drop(base);
+
+    let mut size = GC_HEADER_SIZE
+        + size_of::<ScriptObjectData<'_>>()
+        + slots * size_of::<Value<'_>>()
+        + dynamic_properties * size_of::<(DynamicKey<'_>, DynamicProperty<Value<'_>>)>();
+    if let Some(bytearray) = object.as_bytearray() {
+        size += bytearray.len();
+    }
+    if let Some(array) = object.as_array_storage() {
+        size += array.length() * size_of::<Option<Value<'_>>>();
+    }
+    if let Some(vector) = object.as_vector_storage() {
+        size += vector.length() * size_of::<Value<'_>>();
+    }
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sleep_past_interval() {
+        std::thread::sleep(STACK_SAMPLE_INTERVAL * 2);
+    }
+
+    #[test]
+    fn samples_stack_only_while_running() {
+        let call_stack = CallStack::new();
+        let mut sampler = Sampler::new();
+
+        sleep_past_interval();
+        sampler.sample_stack(&call_stack);
+        assert!(sampler.samples().is_empty());
+
+        sampler.start();
+        assert!(sampler.is_sampling());
+        sleep_past_interval();
+        sampler.sample_stack(&call_stack);
+        assert_eq!(sampler.samples().len(), 1);
+        assert!(matches!(sampler.samples()[0].kind, SampleKind::Stack));
+
+        sampler.pause();
+        assert!(!sampler.is_sampling());
+        sleep_past_interval();
+        sampler.sample_stack(&call_stack);
+        assert_eq!(sampler.samples().len(), 1);
+
+        sampler.start();
+        sleep_past_interval();
+        sampler.sample_stack(&call_stack);
+        assert_eq!(sampler.samples().len(), 2);
+        assert!(sampler.samples()[1].time > sampler.samples()[0].time);
+
+        sampler.stop();
+        assert!(!sampler.is_sampling());
+        assert!(sampler.samples().is_empty());
+    }
+
+    #[test]
+    fn limits_stack_sample_rate() {
+        let call_stack = CallStack::new();
+        let mut sampler = Sampler::new();
+        sampler.start();
+
+        sleep_past_interval();
+        for _ in 0..100 {
+            sampler.sample_stack(&call_stack);
+        }
+        assert_eq!(sampler.samples().len(), 1);
+
+        sleep_past_interval();
+        sampler.sample_stack(&call_stack);
+        assert_eq!(sampler.samples().len(), 2);
+    }
+
+    #[test]
+    fn clear_keeps_sampling() {
+        let call_stack = CallStack::new();
+        let mut sampler = Sampler::new();
+        sampler.start();
+
+        sleep_past_interval();
+        sampler.sample_stack(&call_stack);
+        sampler.clear();
+        assert!(sampler.samples().is_empty());
+        assert!(sampler.is_sampling());
+
+        sleep_past_interval();
+        sampler.sample_stack(&call_stack);
+        assert_eq!(sampler.samples().len(), 1);
+    }
+
+    #[test]
+    fn estimates_primitive_sizes() {
+        assert_eq!(estimate_size(Value::Undefined), 0);
+        assert_eq!(estimate_size(Value::Null), 0);
+        assert_eq!(estimate_size(Value::Bool(true)), 4);
+        assert_eq!(estimate_size(Value::Integer(5)), 4);
+        assert_eq!(estimate_size(Value::Number(0.5)), 8);
+    }
+}
@@ -3,10 +3,10 @@ use crate::avm2::error::{
 };
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::op::Op;
-use crate::avm2::property::Property;
+use crate::avm2::optimize::optimize;
+use crate::avm2::register_ir::{compile, RegisterCode};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::{Activation, Error};
-use gc_arena::GcCell;
 use std::collections::{HashMap, HashSet};
 use swf::avm2::read::Reader;
 use swf::avm2::types::{Index, MethodFlags as AbcMethodFlags, Multiname, Op as AbcOp};
@@ -15,6 +15,12 @@ use swf::error::Error as AbcReadError;
 pub struct VerifiedMethodInfo {
     pub parsed_code: Vec<Op>,
     pub exceptions: Vec<Exception>,
+
+    /// The offset of every op in the original method body, used by the debugger.
+    pub byte_offsets: Vec<u32>,
+
+    /// The translation of the code to registers, if it could be translated.
+    pub register_code: Option<RegisterCode>,
 }
 
 pub struct Exception {
@@ -251,16 +257,33 @@ pub fn verify_method<'gc>(
         verified_code.push(resolved_op);
     }
 
-    optimize(
-        activation,
-        method,
-        &mut verified_code,
-        potential_jump_targets,
-    );
+    let mut byte_offsets: Vec<u32> = idx_to_byte_offset[..verified_code.len()]
+        .iter()
+        .map(|offset| *offset as u32)
+        .collect();
+
+    if activation.avm2().optimizer_enabled {
+        optimize(
+            activation,
+            method,
+            &mut verified_code,
+            &mut new_exceptions,
+            &mut byte_offsets,
+            &potential_jump_targets,
+        );
+    }
+
+    let register_code = if activation.avm2().register_ir_enabled {
+        compile(activation, method, &verified_code, &new_exceptions)
+    } else {
+        None
+    };
 
     Ok(VerifiedMethodInfo {
         parsed_code: verified_code,
         exceptions: new_exceptions,
+        byte_offsets,
+        register_code,
     })
 }
 
@@ -517,326 +540,6 @@ fn verify_code_starting_from<'gc>(
     Ok(())
 }
 
-fn optimize<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    method: &BytecodeMethod<'gc>,
-    code: &mut Vec<Op>,
-    jump_targets: HashSet<i32>,
-) {
-    // These make the code less readable
-    #![allow(clippy::manual_filter)]
-    #![allow(clippy::single_match)]
-
-    let method_body = method
-        .body()
-        .expect("Cannot verify non-native method without body!");
-
-    // This can probably be done better by recording the receiver in `Activation`,
-    // but this works since it's guaranteed to be set in `Activation::from_method`.
-    let this_value = activation.local_register(0);
-
-    let this_class = if let Some(this_class) = activation.subclass_object() {
-        if this_value.is_of_type(activation, this_class.inner_class_definition()) {
-            Some(this_class)
-        } else {
-            None
-        }
-    } else {
-        None
-    };
-
-    // Initial set of local types
-    let mut local_types = vec![None; method_body.num_locals as usize];
-    local_types[0] = this_class;
-
-    // Logic to only allow for type-based optimizations on types that
-    // we're absolutely sure about- invalidate the local register's
-    // known type if any other register-modifying opcodes mention them
-    // anywhere else in the function.
-    for op in &*code {
-        match op {
-            Op::SetLocal { index }
-            | Op::Kill { index }
-            | Op::IncLocal { index }
-            | Op::IncLocalI { index }
-            | Op::DecLocal { index }
-            | Op::DecLocalI { index } => {
-                if (*index as usize) < local_types.len() {
-                    local_types[*index as usize] = None;
-                }
-            }
-            Op::HasNext2 {
-                object_register,
-                index_register,
-            } => {
-                if (*object_register as usize) < local_types.len() {
-                    local_types[*object_register as usize] = None;
-                }
-                if (*index_register as usize) < local_types.len() {
-                    local_types[*index_register as usize] = None;
-                }
-            }
-            _ => {}
-        }
-    }
-
-    let mut previous_op = None;
-    for (i, op) in code.iter_mut().enumerate() {
-        if let Some(previous_op_some) = previous_op {
-            if !jump_targets.contains(&(i as i32)) {
-                match op {
-                    Op::CoerceB => match previous_op_some {
-                        Op::CoerceB
-                        | Op::Equals
-                        | Op::GreaterEquals
-                        | Op::GreaterThan
-                        | Op::LessEquals
-                        | Op::LessThan
-                        | Op::PushTrue
-                        | Op::PushFalse
-                        | Op::Not
-                        | Op::IsType { .. }
-                        | Op::IsTypeLate => {
-                            previous_op = Some(op.clone());
-                            *op = Op::Nop;
-                            continue;
-                        }
-                        _ => {}
-                    },
-                    Op::CoerceD => match previous_op_some {
-                        Op::CoerceD
-                        | Op::PushDouble { .. }
-                        | Op::Multiply
-                        | Op::Divide
-                        | Op::Modulo
-                        | Op::Increment
-                        | Op::Decrement
-                        | Op::Negate => {
-                            previous_op = Some(op.clone());
-                            *op = Op::Nop;
-                            continue;
-                        }
-                        _ => {}
-                    },
-                    Op::CoerceI => match previous_op_some {
-                        Op::CoerceI | Op::PushByte { .. } | Op::PushShort { .. } => {
-                            previous_op = Some(op.clone());
-                            *op = Op::Nop;
-                            continue;
-                        }
-                        Op::PushInt { value } => {
-                            if value >= -(1 << 28) && value < (1 << 28) {
-                                previous_op = Some(op.clone());
-                                *op = Op::Nop;
-                                continue;
-                            }
-                        }
-                        _ => {}
-                    },
-                    Op::CoerceU => match previous_op_some {
-                        Op::CoerceU => {
-                            previous_op = Some(op.clone());
-                            *op = Op::Nop;
-                            continue;
-                        }
-                        Op::PushByte { value } => {
-                            if (value as i8) >= 0 {
-                                previous_op = Some(op.clone());
-                                *op = Op::Nop;
-                                continue;
-                            }
-                        }
-                        Op::PushShort { value } => {
-                            if value >= 0 {
-                                previous_op = Some(op.clone());
-                                *op = Op::Nop;
-                                continue;
-                            }
-                        }
-                        Op::PushInt { value } => {
-                            if value >= 0 && value < (1 << 28) {
-                                previous_op = Some(op.clone());
-                                *op = Op::Nop;
-                                continue;
-                            }
-                        }
-                        _ => {}
-                    },
-                    Op::GetProperty { index: name_index } => match previous_op_some {
-                        Op::GetLocal { index: local_index } => {
-                            let class = local_types[local_index as usize];
-                            if let Some(class) = class {
-                                let multiname = method
-                                    .translation_unit()
-                                    .pool_maybe_uninitialized_multiname(
-                                        *name_index,
-                                        &mut activation.context,
-                                    );
-
-                                if let Ok(multiname) = multiname {
-                                    if !multiname.has_lazy_component() {
-                                        match class.instance_vtable().get_trait(&multiname) {
-                                            Some(Property::Slot { slot_id })
-                                            | Some(Property::ConstSlot { slot_id }) => {
-                                                previous_op = Some(op.clone());
-                                                *op = Op::GetSlot { index: slot_id };
-                                                continue;
-                                            }
-                                            Some(Property::Virtual { get: Some(get), .. }) => {
-                                                previous_op = Some(op.clone());
-                                                *op = Op::CallMethod {
-                                                    num_args: 0,
-                                                    index: Index::new(get),
-                                                };
-                                                continue;
-                                            }
-                                            _ => {}
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        _ => {}
-                    },
-                    Op::AsType {
-                        type_name: name_index,
-                    } => {
-                        let multiname = method
-                            .translation_unit()
-                            .pool_maybe_uninitialized_multiname(
-                                *name_index,
-                                &mut activation.context,
-                            );
-
-                        let resolved_type = if let Ok(multiname) = multiname {
-                            if !multiname.has_lazy_component() {
-                                activation
-                                    .domain()
-                                    .get_class(&multiname, activation.context.gc_context)
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        };
-
-                        if resolved_type.is_some() {
-                            match previous_op_some {
-                                Op::PushNull => {
-                                    previous_op = Some(op.clone());
-                                    *op = Op::Nop;
-                                    continue;
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    Op::Coerce { index: name_index } => {
-                        let multiname = method
-                            .translation_unit()
-                            .pool_maybe_uninitialized_multiname(
-                                *name_index,
-                                &mut activation.context,
-                            );
-
-                        let resolved_type = if let Ok(multiname) = multiname {
-                            if !multiname.has_lazy_component() {
-                                activation
-                                    .domain()
-                                    .get_class(&multiname, activation.context.gc_context)
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        };
-
-                        if let Some(class) = resolved_type {
-                            match previous_op_some {
-                                Op::PushNull => {
-                                    // As long as this Coerce isn't coercing to one
-                                    // of these special classes, we can remove it.
-                                    if !GcCell::ptr_eq(
-                                        class,
-                                        activation.avm2().classes().int.inner_class_definition(),
-                                    ) && !GcCell::ptr_eq(
-                                        class,
-                                        activation.avm2().classes().uint.inner_class_definition(),
-                                    ) && !GcCell::ptr_eq(
-                                        class,
-                                        activation.avm2().classes().number.inner_class_definition(),
-                                    ) && !GcCell::ptr_eq(
-                                        class,
-                                        activation
-                                            .avm2()
-                                            .classes()
-                                            .boolean
-                                            .inner_class_definition(),
-                                    ) && !GcCell::ptr_eq(
-                                        class,
-                                        activation.avm2().classes().void.inner_class_definition(),
-                                    ) {
-                                        previous_op = Some(op.clone());
-                                        *op = Op::Nop;
-                                        continue;
-                                    }
-                                }
-                                Op::PushString { .. } => {
-                                    if GcCell::ptr_eq(
-                                        class,
-                                        activation.avm2().classes().string.inner_class_definition(),
-                                    ) {
-                                        previous_op = Some(op.clone());
-                                        *op = Op::Nop;
-                                        continue;
-                                    }
-                                }
-                                Op::NewArray { .. } => {
-                                    if GcCell::ptr_eq(
-                                        class,
-                                        activation.avm2().classes().array.inner_class_definition(),
-                                    ) {
-                                        previous_op = Some(op.clone());
-                                        *op = Op::Nop;
-                                        continue;
-                                    }
-                                }
-                                Op::NewFunction { .. } => {
-                                    if GcCell::ptr_eq(
-                                        class,
-                                        activation
-                                            .avm2()
-                                            .classes()
-                                            .function
-                                            .inner_class_definition(),
-                                    ) {
-                                        previous_op = Some(op.clone());
-                                        *op = Op::Nop;
-                                        continue;
-                                    }
-                                }
-                                Op::Coerce {
-                                    index: previous_name_index,
-                                } => {
-                                    if name_index.as_u30() == previous_name_index.as_u30() {
-                                        previous_op = Some(op.clone());
-                                        *op = Op::Nop;
-                                        continue;
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-
-        previous_op = Some(op.clone());
-    }
-}
-
 fn ops_can_throw_error(ops: &[AbcOp]) -> bool {
     for op in ops {
         match op {
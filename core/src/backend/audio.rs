@@ -373,6 +373,11 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// The sounds that are currently playing.
+    pub fn sounds(&self) -> &[SoundInstance<'gc>] {
+        &self.sounds
+    }
+
     /// Update state of active sounds. Should be called once per frame.
     pub fn update_sounds(context: &mut UpdateContext<'_, 'gc>) {
         // We can't use 'context' to construct an event inside the
@@ -752,6 +757,23 @@ pub struct SoundInstance<'gc> {
     stream_start_frame: Option<u16>,
 }
 
+impl<'gc> SoundInstance<'gc> {
+    /// The display object that this sound is playing in, if any.
+    pub fn display_object(&self) -> Option<DisplayObject<'gc>> {
+        self.display_object
+    }
+
+    /// The AVM1 `Sound` object associated with this sound, if any.
+    pub fn avm1_object(&self) -> Option<SoundObject<'gc>> {
+        self.avm1_object
+    }
+
+    /// The AVM2 `SoundChannel` object associated with this sound, if any.
+    pub fn avm2_object(&self) -> Option<SoundChannelObject<'gc>> {
+        self.avm2_object
+    }
+}
+
 /// A sound transform for a playing sound, for use by audio backends.
 /// This differs from `display_object::SoundTransform` by being
 /// already converted to `f32` and having `volume` baked in.
@@ -63,6 +63,18 @@ impl_downcast!(FileDialogResult);
 /// Future representing a file selection in process
 pub type DialogResultFuture = OwnedFuture<Box<dyn FileDialogResult>, DialogLoaderError>;
 
+/// The choice made by the user when asked what to do with a script that is running slowly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptTimeoutChoice {
+    /// Let the scripts of the movie keep running.
+    Continue,
+
+    /// Stop running any scripts in the movie.
+    Abort,
+}
+
+pub type ScriptTimeoutPromptFuture = OwnedFuture<ScriptTimeoutChoice, DialogLoaderError>;
+
 pub trait UiBackend: Downcast {
     fn mouse_visible(&self) -> bool;
 
@@ -90,6 +102,14 @@ pub trait UiBackend: Downcast {
     // Only used on web.
     fn open_virtual_keyboard(&self);
 
+    /// Enables or disables the input method editor (IME) of the platform.
+    /// This is called when an editable text field gains or loses focus,
+    /// and when a movie sets `IME.enabled`.
+    fn set_ime_enabled(&mut self, enabled: bool);
+
+    /// Whether the input method editor (IME) of the platform is enabled.
+    fn ime_enabled(&self) -> bool;
+
     fn language(&self) -> &LanguageIdentifier;
 
     fn display_unsupported_video(&self, url: Url);
@@ -125,6 +145,12 @@ pub trait UiBackend: Downcast {
 
     /// Mark that any previously open dialog has been closed
     fn close_file_dialog(&mut self);
+
+    /// Displays a prompt telling the user that a script is making the movie run slowly, and
+    /// asking whether to abort it. Returns None if the prompt cannot be displayed.
+    ///
+    /// The prompt must not block; the player is paused until the returned future completes.
+    fn display_script_timeout_prompt(&mut self) -> Option<ScriptTimeoutPromptFuture>;
 }
 impl_downcast!(UiBackend);
 
@@ -293,6 +319,12 @@ impl UiBackend for NullUiBackend {
 
     fn open_virtual_keyboard(&self) {}
 
+    fn set_ime_enabled(&mut self, _enabled: bool) {}
+
+    fn ime_enabled(&self) -> bool {
+        false
+    }
+
     fn language(&self) -> &LanguageIdentifier {
         &US_ENGLISH
     }
@@ -317,6 +349,10 @@ impl UiBackend for NullUiBackend {
     ) -> Option<DialogResultFuture> {
         None
     }
+
+    fn display_script_timeout_prompt(&mut self) -> Option<ScriptTimeoutPromptFuture> {
+        None
+    }
 }
 
 impl Default for NullUiBackend {
@@ -21,11 +21,13 @@ use crate::backend::{
     ui::{InputManager, UiBackend},
 };
 use crate::context_menu::ContextMenuState;
+use crate::debugger::Debugger;
 use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
+use crate::limits::ScriptTimeout;
 use crate::loader::LoadManager;
 use crate::local_connection::LocalConnections;
 use crate::net_connection::NetConnections;
@@ -40,6 +42,7 @@ use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
+use crate::worker::Workers;
 use core::fmt;
 use gc_arena::{Collect, Mutation};
 use rand::rngs::SmallRng;
@@ -233,6 +236,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// Amount of actions performed since the last timeout check
     pub actions_since_timeout_check: &'a mut u16,
 
+    /// Whether scripts have exceeded the time limit during the current update.
+    pub script_timeout: &'a mut ScriptTimeout,
+
     /// The current frame processing phase.
     ///
     /// If we are not doing frame processing, then this is `FramePhase::Enter`.
@@ -255,6 +261,12 @@ pub struct UpdateContext<'a, 'gc> {
     /// Currently, this is just used for handling `Loader.loadBytes`
     #[allow(clippy::type_complexity)]
     pub post_frame_callbacks: &'a mut Vec<PostFrameCallback<'gc>>,
+
+    /// The workers of this movie, as seen by the worker running this player.
+    pub workers: &'a Workers,
+
+    /// The script debugger, if a client is attached.
+    pub debugger: &'a mut Option<Debugger>,
 }
 
 /// Convenience methods for controlling audio.
@@ -521,6 +533,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             frame_rate: self.frame_rate,
             forced_frame_rate: self.forced_frame_rate,
             actions_since_timeout_check: self.actions_since_timeout_check,
+            script_timeout: self.script_timeout,
             frame_phase: self.frame_phase,
             stream_manager: self.stream_manager,
             sockets: self.sockets,
@@ -528,6 +541,8 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             local_connections: self.local_connections,
             dynamic_root: self.dynamic_root,
             post_frame_callbacks: self.post_frame_callbacks,
+            workers: self.workers,
+            debugger: self.debugger,
         }
     }
 
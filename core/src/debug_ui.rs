@@ -1,18 +1,25 @@
 mod avm1;
+mod avm1_profiler;
 mod avm2;
+mod avm2_profiler;
 mod display_object;
 mod domain;
 mod handle;
+mod heap;
 mod movie;
+mod stats_table;
 
 use crate::context::{RenderContext, UpdateContext};
 use crate::debug_ui::avm1::Avm1ObjectWindow;
+use crate::debug_ui::avm1_profiler::Avm1ProfilerWindow;
 use crate::debug_ui::avm2::Avm2ObjectWindow;
+use crate::debug_ui::avm2_profiler::Avm2ProfilerWindow;
 use crate::debug_ui::display_object::{DisplayObjectSearchWindow, DisplayObjectWindow};
 use crate::debug_ui::domain::DomainListWindow;
 use crate::debug_ui::handle::{
     AVM1ObjectHandle, AVM2ObjectHandle, DisplayObjectHandle, DomainHandle,
 };
+use crate::debug_ui::heap::HeapSnapshotWindow;
 use crate::debug_ui::movie::{MovieListWindow, MovieWindow};
 use crate::display_object::TDisplayObject;
 use crate::tag_utils::SwfMovie;
@@ -37,6 +44,9 @@ pub struct DebugUi {
     movie_list: Option<MovieListWindow>,
     domain_list: Option<DomainListWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
+    heap_snapshots: Option<HeapSnapshotWindow>,
+    avm1_profiler: Option<Avm1ProfilerWindow>,
+    avm2_profiler: Option<Avm2ProfilerWindow>,
 }
 
 #[derive(Debug)]
@@ -52,6 +62,9 @@ pub enum Message {
     ShowDomains,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
+    ShowHeapSnapshots,
+    ShowAvm1Profiler,
+    ShowAvm2Profiler,
 }
 
 impl DebugUi {
@@ -99,6 +112,24 @@ impl DebugUi {
             }
         }
 
+        if let Some(mut heap_snapshots) = self.heap_snapshots.take() {
+            if heap_snapshots.show(egui_ctx, context, &mut messages) {
+                self.heap_snapshots = Some(heap_snapshots);
+            }
+        }
+
+        if let Some(mut avm1_profiler) = self.avm1_profiler.take() {
+            if avm1_profiler.show(egui_ctx, context, &mut messages) {
+                self.avm1_profiler = Some(avm1_profiler);
+            }
+        }
+
+        if let Some(mut avm2_profiler) = self.avm2_profiler.take() {
+            if avm2_profiler.show(egui_ctx, context, &mut messages) {
+                self.avm2_profiler = Some(avm2_profiler);
+            }
+        }
+
         for message in messages {
             match message {
                 Message::TrackDisplayObject(object) => {
@@ -134,6 +165,15 @@ impl DebugUi {
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
+                Message::ShowHeapSnapshots => {
+                    self.heap_snapshots.get_or_insert_with(Default::default);
+                }
+                Message::ShowAvm1Profiler => {
+                    self.avm1_profiler.get_or_insert_with(Default::default);
+                }
+                Message::ShowAvm2Profiler => {
+                    self.avm2_profiler.get_or_insert_with(Default::default);
+                }
             }
         }
     }
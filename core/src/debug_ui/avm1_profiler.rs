@@ -0,0 +1,113 @@
+use crate::avm1::profiler::{FunctionId, Profiler};
+use crate::context::UpdateContext;
+use crate::debug_ui::stats_table::{format_duration, StatsTable};
+use crate::debug_ui::{ItemToSave, Message};
+use crate::profiler::SpanStats;
+use egui::{ComboBox, Ui, Window};
+use fnv::FnvHashMap;
+
+#[derive(Debug, Eq, PartialEq, Hash, Default, Copy, Clone)]
+enum Range {
+    #[default]
+    AllFrames,
+    SlowestFrame,
+    LastFrame,
+}
+
+impl Range {
+    fn name(self) -> &'static str {
+        match self {
+            Range::AllFrames => "All Frames",
+            Range::SlowestFrame => "Slowest Frame",
+            Range::LastFrame => "Last Frame",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Avm1ProfilerWindow {
+    range: Range,
+    table: StatsTable,
+}
+
+impl Avm1ProfilerWindow {
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+
+        Window::new("AVM1 Profiler")
+            .open(&mut keep_open)
+            .scroll2([true, true])
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let mut enabled = context.avm1.profiler().is_some();
+                    if ui.checkbox(&mut enabled, "Enable Profiling").changed() {
+                        context.avm1.set_profiling(enabled);
+                    }
+                    if let Some(profiler) = context.avm1.profiler_mut() {
+                        if ui.button("Clear").clicked() {
+                            profiler.clear();
+                        }
+                        if ui.button("Save Flamegraph...").clicked() {
+                            messages.push(Message::SaveFile(ItemToSave {
+                                suggested_name: "avm1-profile.folded".to_string(),
+                                data: profiler.to_folded_stacks().into_bytes(),
+                            }));
+                        }
+                    }
+                });
+                ui.separator();
+
+                match context.avm1.profiler() {
+                    Some(profiler) => self.show_profile(ui, profiler),
+                    None => {
+                        ui.label("Enable profiling to record the time spent in AVM1 functions.");
+                    }
+                }
+            });
+
+        keep_open
+    }
+
+    fn show_profile(&mut self, ui: &mut Ui, profiler: &Profiler) {
+        let slowest_frame = profiler.frames().iter().max_by_key(|frame| frame.time);
+        ui.horizontal(|ui| {
+            ui.label(format!("{} frames recorded", profiler.frames().len()));
+            if let Some(frame) = slowest_frame {
+                ui.label(format!(
+                    "(slowest: frame {} with {})",
+                    frame.frame,
+                    format_duration(frame.time)
+                ));
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source(ui.id().with("range"))
+                .selected_text(self.range.name())
+                .show_ui(ui, |ui| {
+                    for range in [Range::AllFrames, Range::SlowestFrame, Range::LastFrame] {
+                        ui.selectable_value(&mut self.range, range, range.name());
+                    }
+                });
+            self.table.show_search(ui);
+        });
+        ui.add_space(10.0);
+
+        let stats: Option<&FnvHashMap<FunctionId, SpanStats>> = match self.range {
+            Range::AllFrames => Some(profiler.totals()),
+            Range::SlowestFrame => slowest_frame.map(|frame| &frame.functions),
+            Range::LastFrame => profiler.frames().back().map(|frame| &frame.functions),
+        };
+        let rows = stats.into_iter().flatten().map(|(id, stats)| {
+            let function = &profiler.functions()[*id];
+            let location = format!("{}:{}", function.url, function.offset);
+            (vec![function.name.clone(), location], stats)
+        });
+        self.table.show(ui, &["Function", "Location"], rows);
+    }
+}
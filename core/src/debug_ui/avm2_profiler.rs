@@ -0,0 +1,109 @@
+use crate::avm2::profiler::{Profiler, TraceCategory};
+use crate::context::UpdateContext;
+use crate::debug_ui::stats_table::StatsTable;
+use crate::debug_ui::{ItemToSave, Message};
+use egui::{ComboBox, Ui, Window};
+
+#[derive(Debug, Eq, PartialEq, Hash, Default, Copy, Clone)]
+enum Source {
+    #[default]
+    Methods,
+    EventHandlers,
+}
+
+impl Source {
+    fn name(self) -> &'static str {
+        match self {
+            Source::Methods => "Methods",
+            Source::EventHandlers => "Event Handlers",
+        }
+    }
+
+    fn category(self) -> TraceCategory {
+        match self {
+            Source::Methods => TraceCategory::Method,
+            Source::EventHandlers => TraceCategory::Event,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Avm2ProfilerWindow {
+    source: Source,
+    table: StatsTable,
+}
+
+impl Avm2ProfilerWindow {
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+
+        Window::new("AVM2 Profiler")
+            .open(&mut keep_open)
+            .scroll2([true, true])
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let mut enabled = context.avm2.profiler().is_some();
+                    if ui.checkbox(&mut enabled, "Enable Profiling").changed() {
+                        context.avm2.set_profiling(enabled);
+                    }
+                    if let Some(profiler) = context.avm2.profiler_mut() {
+                        if ui.button("Clear").clicked() {
+                            profiler.clear();
+                        }
+                        if ui.button("Save Chrome Trace...").clicked() {
+                            messages.push(Message::SaveFile(ItemToSave {
+                                suggested_name: "avm2-trace.json".to_string(),
+                                data: profiler.to_chrome_trace(),
+                            }));
+                        }
+                    }
+                });
+                ui.separator();
+
+                match context.avm2.profiler() {
+                    Some(profiler) => self.show_profile(ui, profiler),
+                    None => {
+                        ui.label(
+                            "Enable profiling to record the time spent in AVM2 methods and frames.",
+                        );
+                    }
+                }
+            });
+
+        keep_open
+    }
+
+    fn show_profile(&mut self, ui: &mut Ui, profiler: &Profiler) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{} spans recorded", profiler.events().len()));
+            if profiler.dropped_events() > 0 {
+                ui.label(format!("({} dropped)", profiler.dropped_events()));
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source(ui.id().with("source"))
+                .selected_text(self.source.name())
+                .show_ui(ui, |ui| {
+                    for source in [Source::Methods, Source::EventHandlers] {
+                        ui.selectable_value(&mut self.source, source, source.name());
+                    }
+                });
+            self.table.show_search(ui);
+        });
+        ui.add_space(10.0);
+
+        let category = self.source.category();
+        let rows = profiler
+            .totals()
+            .iter()
+            .filter(|((c, _), _)| *c == category)
+            .map(|((_, name), stats)| (vec![name.clone()], stats));
+        self.table.show(ui, &["Name"], rows);
+    }
+}
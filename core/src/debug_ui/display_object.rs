@@ -742,7 +742,7 @@ fn summary_name(object: DisplayObject) -> Cow<'static, str> {
     }
 }
 
-fn display_object_type(object: DisplayObject) -> &'static str {
+pub fn display_object_type(object: DisplayObject) -> &'static str {
     match object {
         DisplayObject::Stage(_) => "Stage",
         DisplayObject::Bitmap(_) => "Bitmap",
@@ -0,0 +1,1125 @@
+use crate::avm1::{
+    Executable as Avm1Executable, Object as Avm1Object, TObject as _, Value as Avm1Value,
+};
+use crate::avm2::object::TObject as _;
+use crate::avm2::property::Property;
+use crate::avm2::sampler::{estimate_size, GC_HEADER_SIZE};
+use crate::avm2::{Domain, DynamicKey, Object as Avm2Object, Value as Avm2Value};
+use crate::context::UpdateContext;
+use crate::debug_ui::display_object::display_object_type;
+use crate::debug_ui::{ItemToSave, Message};
+use crate::display_object::{
+    DisplayObject, DisplayObjectBase, TDisplayObject, TDisplayObjectContainer,
+};
+use crate::loader::{Loader, MovieLoaderVMData};
+use crate::net_connection::NetConnectionObject;
+use crate::timer::TimerCallback;
+use egui::{Align, CollapsingHeader, ComboBox, Layout, TextEdit, Ui, Window};
+use egui_extras::{Column, TableBuilder};
+use fnv::{FnvHashMap, FnvHashSet};
+use gc_arena::Mutation;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+/// The maximum number of objects listed for a single type or as new objects in a comparison.
+const MAX_LISTED_OBJECTS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum HeapNodeKind {
+    /// A group of GC roots, such as the stage or the active timers.
+    Root,
+    Domain,
+    DisplayObject,
+    Avm1Object,
+    Avm2Object,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeapNode {
+    pub kind: HeapNodeKind,
+
+    /// The address of the object.
+    ///
+    /// Addresses identify objects across snapshots for as long as they are alive, but may be
+    /// reused once an object has been collected.
+    pub address: usize,
+
+    pub type_name: String,
+
+    /// The estimated size of this object, excluding the objects it refers to.
+    pub size: usize,
+
+    /// The node through which this node was first reached from the roots, and the name of the
+    /// reference. This is `None` for roots.
+    pub retainer: Option<(usize, String)>,
+
+    /// The number of references to this node that were found.
+    pub references: usize,
+}
+
+/// The objects reachable from the player's roots at a point in time.
+#[derive(Debug, Serialize)]
+pub struct HeapSnapshot {
+    pub name: String,
+    pub nodes: Vec<HeapNode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeSummary {
+    pub type_name: String,
+    pub count: usize,
+    pub size: usize,
+}
+
+impl HeapSnapshot {
+    /// Walks the heap from the stage, the AVM1 globals, the AVM2 domains, the active timers, the
+    /// shared objects, the orphan display objects, the loaders, the playing sounds and the net
+    /// connections, recording every object that is reached.
+    pub fn capture(context: &mut UpdateContext<'_, '_>, name: String) -> Self {
+        let mut walker = HeapWalker::new(context.gc_context);
+
+        let stage = walker.add_root("Stage");
+        walker.push(context.stage.into(), stage, "stage");
+
+        let globals = walker.add_root("AVM1 Globals");
+        walker.push(context.avm1.global_object().into(), globals, "_global");
+
+        let domains = walker.add_root("AVM2 Domains");
+        walker.push(
+            context.avm2.playerglobals_domain().into(),
+            domains,
+            "playerglobals",
+        );
+
+        let timers = walker.add_root("Timers");
+        for (id, callback) in context.timers.iter() {
+            let name = format!("timer {id}");
+            match callback {
+                TimerCallback::Avm1Function { func, params } => {
+                    walker.push((*func).into(), timers, &name);
+                    for param in params {
+                        walker.push_avm1_value(*param, timers, &name);
+                    }
+                }
+                TimerCallback::Avm1Method { this, params, .. } => {
+                    walker.push((*this).into(), timers, &name);
+                    for param in params {
+                        walker.push_avm1_value(*param, timers, &name);
+                    }
+                }
+                TimerCallback::Avm2Callback { closure, params } => {
+                    walker.push((*closure).into(), timers, &name);
+                    for param in params {
+                        walker.push_avm2_value(*param, timers, &name);
+                    }
+                }
+            }
+        }
+
+        let shared_objects = walker.add_root("Shared Objects");
+        for (name, object) in context.avm1_shared_objects.iter() {
+            walker.push((*object).into(), shared_objects, name);
+        }
+        for (name, object) in context.avm2_shared_objects.iter() {
+            walker.push((*object).into(), shared_objects, name);
+        }
+
+        let orphans = walker.add_root("Orphan Display Objects");
+        for orphan in context.avm2.iter_orphan_objs(context.gc_context) {
+            walker.push(orphan.into(), orphans, "orphan");
+        }
+
+        let loaders = walker.add_root("Loaders");
+        for loader in context.load_manager.iter() {
+            walker.push_loader(loader, loaders);
+        }
+
+        let sounds = walker.add_root("Sounds");
+        for sound in context.audio_manager.sounds() {
+            if let Some(display_object) = sound.display_object() {
+                walker.push(display_object.into(), sounds, "display object");
+            }
+            if let Some(object) = sound.avm1_object() {
+                walker.push(Avm1Object::from(object).into(), sounds, "Sound");
+            }
+            if let Some(object) = sound.avm2_object() {
+                walker.push(Avm2Object::from(object).into(), sounds, "SoundChannel");
+            }
+        }
+
+        let net_connections = walker.add_root("Net Connections");
+        for object in context.net_connections.iter_objects() {
+            match object {
+                NetConnectionObject::Avm2(object) => {
+                    walker.push(
+                        Avm2Object::from(object).into(),
+                        net_connections,
+                        "NetConnection",
+                    );
+                }
+            }
+        }
+
+        walker.walk();
+
+        Self {
+            name,
+            nodes: walker.nodes,
+        }
+    }
+
+    pub fn total_size(&self) -> usize {
+        self.nodes.iter().map(|node| node.size).sum()
+    }
+
+    /// The chain of references through which the given node was first reached, starting at a
+    /// root.
+    pub fn retention_path(&self, index: usize) -> String {
+        let mut path = vec![];
+        let mut current = index;
+        while let Some((retainer, edge)) = &self.nodes[current].retainer {
+            path.push(format!("{} ({})", edge, self.nodes[current].type_name));
+            current = *retainer;
+        }
+        path.push(self.nodes[current].type_name.clone());
+        path.reverse();
+        path.join(" → ")
+    }
+
+    /// The number and total size of the objects of every type, largest first.
+    pub fn type_summaries(&self) -> Vec<TypeSummary> {
+        let mut types: FnvHashMap<&str, TypeSummary> = FnvHashMap::default();
+        for node in &self.nodes {
+            let summary = types
+                .entry(node.type_name.as_str())
+                .or_insert_with(|| TypeSummary {
+                    type_name: node.type_name.clone(),
+                    count: 0,
+                    size: 0,
+                });
+            summary.count += 1;
+            summary.size += node.size;
+        }
+        let mut types: Vec<_> = types.into_values().collect();
+        types.sort_by(|a, b| b.size.cmp(&a.size).then(a.type_name.cmp(&b.type_name)));
+        types
+    }
+
+    pub fn to_json(&self) -> Vec<u8> {
+        serde_json::to_vec_pretty(self).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeDiff {
+    pub type_name: String,
+    pub count_before: usize,
+    pub count_after: usize,
+    pub size_before: usize,
+    pub size_after: usize,
+}
+
+impl TypeDiff {
+    fn empty(type_name: &str) -> Self {
+        Self {
+            type_name: type_name.to_string(),
+            count_before: 0,
+            count_after: 0,
+            size_before: 0,
+            size_after: 0,
+        }
+    }
+
+    pub fn count_delta(&self) -> isize {
+        self.count_after as isize - self.count_before as isize
+    }
+
+    pub fn size_delta(&self) -> isize {
+        self.size_after as isize - self.size_before as isize
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewObject {
+    pub type_name: String,
+    pub address: usize,
+    pub size: usize,
+    pub retention_path: String,
+}
+
+/// The changes between two snapshots, used to find objects that are leaked.
+#[derive(Debug, Serialize)]
+pub struct HeapSnapshotDiff {
+    pub before: String,
+    pub after: String,
+
+    /// The types whose number of objects changed, the ones that grew the most first.
+    pub types: Vec<TypeDiff>,
+
+    /// The objects in the later snapshot that weren't in the earlier one.
+    pub new_objects: Vec<NewObject>,
+}
+
+impl HeapSnapshotDiff {
+    pub fn new(before: &HeapSnapshot, after: &HeapSnapshot) -> Self {
+        let mut types: FnvHashMap<String, TypeDiff> = FnvHashMap::default();
+        for node in &before.nodes {
+            let diff = types
+                .entry(node.type_name.clone())
+                .or_insert_with(|| TypeDiff::empty(&node.type_name));
+            diff.count_before += 1;
+            diff.size_before += node.size;
+        }
+        for node in &after.nodes {
+            let diff = types
+                .entry(node.type_name.clone())
+                .or_insert_with(|| TypeDiff::empty(&node.type_name));
+            diff.count_after += 1;
+            diff.size_after += node.size;
+        }
+
+        let mut types: Vec<_> = types
+            .into_values()
+            .filter(|diff| diff.count_delta() != 0 || diff.size_delta() != 0)
+            .collect();
+        types.sort_by(|a, b| {
+            b.size_delta()
+                .cmp(&a.size_delta())
+                .then(a.type_name.cmp(&b.type_name))
+        });
+
+        let known: FnvHashSet<_> = before
+            .nodes
+            .iter()
+            .map(|node| (node.kind, node.address, node.type_name.as_str()))
+            .collect();
+        let new_objects = after
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| {
+                node.kind != HeapNodeKind::Root
+                    && !known.contains(&(node.kind, node.address, node.type_name.as_str()))
+            })
+            .map(|(index, node)| NewObject {
+                type_name: node.type_name.clone(),
+                address: node.address,
+                size: node.size,
+                retention_path: after.retention_path(index),
+            })
+            .collect();
+
+        Self {
+            before: before.name.clone(),
+            after: after.name.clone(),
+            types,
+            new_objects,
+        }
+    }
+
+    pub fn to_json(&self) -> Vec<u8> {
+        serde_json::to_vec_pretty(self).unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum HeapRef<'gc> {
+    Domain(Domain<'gc>),
+    DisplayObject(DisplayObject<'gc>),
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+}
+
+impl<'gc> From<Domain<'gc>> for HeapRef<'gc> {
+    fn from(domain: Domain<'gc>) -> Self {
+        Self::Domain(domain)
+    }
+}
+
+impl<'gc> From<DisplayObject<'gc>> for HeapRef<'gc> {
+    fn from(object: DisplayObject<'gc>) -> Self {
+        Self::DisplayObject(object)
+    }
+}
+
+impl<'gc> From<crate::display_object::Stage<'gc>> for HeapRef<'gc> {
+    fn from(stage: crate::display_object::Stage<'gc>) -> Self {
+        Self::DisplayObject(stage.into())
+    }
+}
+
+impl<'gc> From<Avm1Object<'gc>> for HeapRef<'gc> {
+    fn from(object: Avm1Object<'gc>) -> Self {
+        Self::Avm1(object)
+    }
+}
+
+impl<'gc> From<Avm2Object<'gc>> for HeapRef<'gc> {
+    fn from(object: Avm2Object<'gc>) -> Self {
+        Self::Avm2(object)
+    }
+}
+
+impl<'gc> HeapRef<'gc> {
+    fn key(self) -> (HeapNodeKind, usize) {
+        match self {
+            Self::Domain(domain) => (HeapNodeKind::Domain, domain.as_ptr() as usize),
+            Self::DisplayObject(object) => (HeapNodeKind::DisplayObject, object.as_ptr() as usize),
+            Self::Avm1(object) => (HeapNodeKind::Avm1Object, object.as_ptr() as usize),
+            Self::Avm2(object) => (HeapNodeKind::Avm2Object, object.as_ptr() as usize),
+        }
+    }
+}
+
+/// Walks the object graph breadth-first, so that the first retainer of every object is on one
+/// of its shortest paths from the roots.
+struct HeapWalker<'gc> {
+    mc: &'gc Mutation<'gc>,
+    nodes: Vec<HeapNode>,
+    visited: FnvHashMap<(HeapNodeKind, usize), usize>,
+    queue: VecDeque<(HeapRef<'gc>, usize, String)>,
+}
+
+impl<'gc> HeapWalker<'gc> {
+    fn new(mc: &'gc Mutation<'gc>) -> Self {
+        Self {
+            mc,
+            nodes: vec![],
+            visited: FnvHashMap::default(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn add_root(&mut self, name: &str) -> usize {
+        self.nodes.push(HeapNode {
+            kind: HeapNodeKind::Root,
+            address: 0,
+            type_name: name.to_string(),
+            size: 0,
+            retainer: None,
+            references: 0,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn push(&mut self, target: HeapRef<'gc>, retainer: usize, edge: &str) {
+        self.queue.push_back((target, retainer, edge.to_string()));
+    }
+
+    fn push_avm1_value(&mut self, value: Avm1Value<'gc>, retainer: usize, edge: &str) {
+        if let Avm1Value::Object(object) = value {
+            self.push(object.into(), retainer, edge);
+        }
+    }
+
+    fn push_avm2_value(&mut self, value: Avm2Value<'gc>, retainer: usize, edge: &str) {
+        if let Avm2Value::Object(object) = value {
+            self.push(object.into(), retainer, edge);
+        }
+    }
+
+    fn push_loader(&mut self, loader: &Loader<'gc>, retainer: usize) {
+        match loader {
+            Loader::Movie {
+                target_clip,
+                vm_data,
+                ..
+            } => {
+                self.push((*target_clip).into(), retainer, "target clip");
+                match vm_data {
+                    MovieLoaderVMData::Avm1 {
+                        broadcaster: Some(broadcaster),
+                    } => {
+                        self.push((*broadcaster).into(), retainer, "broadcaster");
+                    }
+                    MovieLoaderVMData::Avm1 { broadcaster: None } => {}
+                    MovieLoaderVMData::Avm2 {
+                        loader_info,
+                        context,
+                        default_domain,
+                    } => {
+                        self.push((*loader_info).into(), retainer, "loaderInfo");
+                        if let Some(context) = context {
+                            self.push((*context).into(), retainer, "context");
+                        }
+                        self.push((*default_domain).into(), retainer, "domain");
+                    }
+                }
+            }
+            Loader::MovieUnloader { target_clip, .. } => {
+                self.push((*target_clip).into(), retainer, "target clip");
+            }
+            Loader::Form { target_object, .. }
+            | Loader::LoadVars { target_object, .. }
+            | Loader::FileDialog { target_object, .. }
+            | Loader::DownloadFileDialog { target_object, .. } => {
+                self.push((*target_object).into(), retainer, "target");
+            }
+            Loader::SoundAvm1 { target_object, .. } => {
+                self.push(Avm1Object::from(*target_object).into(), retainer, "target");
+            }
+            Loader::LoadURLLoader { target_object, .. }
+            | Loader::SoundAvm2 { target_object, .. } => {
+                self.push((*target_object).into(), retainer, "target");
+            }
+            Loader::FileDialogAvm2 { target_object, .. }
+            | Loader::SaveFileDialog { target_object, .. } => {
+                self.push(Avm2Object::from(*target_object).into(), retainer, "target");
+            }
+            _ => {}
+        }
+    }
+
+    fn walk(&mut self) {
+        while let Some((target, retainer, edge)) = self.queue.pop_front() {
+            let key = target.key();
+            if let Some(&index) = self.visited.get(&key) {
+                self.nodes[index].references += 1;
+                continue;
+            }
+
+            let index = self.nodes.len();
+            let type_name = self.type_name(target);
+            self.visited.insert(key, index);
+            self.nodes.push(HeapNode {
+                kind: key.0,
+                address: key.1,
+                type_name,
+                size: Self::size(target),
+                retainer: Some((retainer, edge)),
+                references: 1,
+            });
+            self.visit(target, index);
+        }
+    }
+
+    fn type_name(&self, target: HeapRef<'gc>) -> String {
+        match target {
+            HeapRef::Domain(_) => "Domain".to_string(),
+            HeapRef::DisplayObject(object) => display_object_type(object).to_string(),
+            HeapRef::Avm1(object) => {
+                if object.as_executable().is_some() {
+                    "AVM1 Function".to_string()
+                } else if object.as_array_object().is_some() {
+                    "AVM1 Array".to_string()
+                } else if object.as_display_object().is_some() {
+                    "AVM1 StageObject".to_string()
+                } else {
+                    "AVM1 Object".to_string()
+                }
+            }
+            HeapRef::Avm2(object) => {
+                if let Some(class) = object.as_class_object() {
+                    let name = class.inner_class_definition().read().name();
+                    format!("Class {}", name.to_qualified_name(self.mc))
+                } else if let Some(class) = object.instance_of_class_definition() {
+                    class.read().name().to_qualified_name(self.mc).to_string()
+                } else {
+                    "Object".to_string()
+                }
+            }
+        }
+    }
+
+    fn size(target: HeapRef<'gc>) -> usize {
+        match target {
+            HeapRef::Domain(_) => GC_HEADER_SIZE,
+            HeapRef::DisplayObject(_) => GC_HEADER_SIZE + size_of::<DisplayObjectBase<'gc>>(),
+            HeapRef::Avm1(object) => {
+                let properties = object.raw_script_object().referenced_values().len();
+                GC_HEADER_SIZE + properties * size_of::<(Avm1Value<'gc>, usize)>()
+            }
+            HeapRef::Avm2(object) => estimate_size(object.into()),
+        }
+    }
+
+    fn visit(&mut self, target: HeapRef<'gc>, index: usize) {
+        match target {
+            HeapRef::Domain(domain) => self.visit_domain(domain, index),
+            HeapRef::DisplayObject(object) => self.visit_display_object(object, index),
+            HeapRef::Avm1(object) => self.visit_avm1_object(object, index),
+            HeapRef::Avm2(object) => self.visit_avm2_object(object, index),
+        }
+    }
+
+    fn visit_domain(&mut self, domain: Domain<'gc>, index: usize) {
+        let scripts: Vec<_> = domain
+            .defs()
+            .iter()
+            .map(|(name, _ns, script)| (name, *script))
+            .collect();
+        for (name, script) in scripts {
+            let (_, globals, _) = script.init();
+            self.push(globals.into(), index, &format!("script defining {name}"));
+        }
+
+        let classes: Vec<_> = domain
+            .classes()
+            .iter()
+            .map(|(name, _ns, class)| (name, *class))
+            .collect();
+        for (name, class) in classes {
+            for class_object in class.read().class_objects() {
+                self.push((*class_object).into(), index, &name.to_string());
+            }
+        }
+
+        for child in domain.children(self.mc) {
+            self.push(child.into(), index, "child domain");
+        }
+    }
+
+    fn visit_display_object(&mut self, object: DisplayObject<'gc>, index: usize) {
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                let name = child.name();
+                if name.is_empty() {
+                    self.push(child.into(), index, "child");
+                } else {
+                    self.push(child.into(), index, &format!("child \"{name}\""));
+                }
+            }
+        }
+        self.push_avm1_value(object.object(), index, "object");
+        self.push_avm2_value(object.object2(), index, "object");
+    }
+
+    fn visit_avm1_object(&mut self, object: Avm1Object<'gc>, index: usize) {
+        for (name, value) in object.raw_script_object().referenced_values() {
+            self.push_avm1_value(value, index, &name.to_string());
+        }
+        if let Some(display_object) = object.as_display_object() {
+            self.push(display_object.into(), index, "display object");
+        }
+        if let Some(Avm1Executable::Action(function)) = object.as_executable() {
+            self.push(function.base_clip().into(), index, "[base clip]");
+            let mut scope = Some(function.scope());
+            let mut depth = 0;
+            while let Some(current) = scope {
+                self.push(
+                    (*current.locals()).into(),
+                    index,
+                    &format!("[scope {depth}]"),
+                );
+                scope = current.parent();
+                depth += 1;
+            }
+        }
+    }
+
+    fn visit_avm2_object(&mut self, object: Avm2Object<'gc>, index: usize) {
+        let mut references = vec![];
+        {
+            let base = object.base();
+            if let Some(proto) = base.proto() {
+                references.push(("__proto__".to_string(), proto.into()));
+            }
+            for (key, property) in base.values().as_hashmap().iter() {
+                let name = match key {
+                    DynamicKey::String(name) => name.to_string(),
+                    DynamicKey::Uint(index) => index.to_string(),
+                    DynamicKey::Object(key) => {
+                        references.push(("[key]".to_string(), (*key).into()));
+                        "[value]".to_string()
+                    }
+                };
+                references.push((name, property.value));
+            }
+        }
+
+        if let Some(vtable) = object.vtable() {
+            for (name, _ns, property) in vtable.resolved_traits().iter() {
+                if let Property::Slot { slot_id } | Property::ConstSlot { slot_id } = property {
+                    if let Ok(value) = object.get_slot(*slot_id) {
+                        references.push((name.to_string(), value));
+                    }
+                }
+            }
+        }
+
+        if let Some(class) = object.instance_of() {
+            references.push(("[class]".to_string(), class.into()));
+        }
+        let mut scopes = vec![];
+        if let Some(class) = object.as_class_object() {
+            references.push(("prototype".to_string(), class.prototype().into()));
+            scopes.push(("[class scope]", class.class_scope()));
+            scopes.push(("[instance scope]", class.instance_scope()));
+        }
+        if let Some(executable) = object.as_executable() {
+            scopes.push(("[scope]", executable.scope()));
+            if let Some(receiver) = executable.bound_receiver() {
+                references.push(("[receiver]".to_string(), receiver.into()));
+            }
+            if let Some(superclass) = executable.bound_superclass() {
+                references.push(("[superclass]".to_string(), superclass.into()));
+            }
+        }
+        if let Some(array) = object.as_array_storage() {
+            for (i, value) in array.iter().enumerate() {
+                if let Some(value) = value {
+                    references.push((i.to_string(), value));
+                }
+            }
+        }
+        if let Some(vector) = object.as_vector_storage() {
+            for (i, value) in vector.iter().enumerate() {
+                references.push((i.to_string(), value));
+            }
+        }
+        if let Some(dispatch) = object.as_dispatch() {
+            for (event, handler) in dispatch.iter_all_event_handlers() {
+                references.push((format!("listener for \"{event}\""), handler.into()));
+            }
+        }
+
+        for (name, value) in references {
+            self.push_avm2_value(value, index, &name);
+        }
+        for (name, scope) in scopes {
+            for i in 0.. {
+                let Some(scope) = scope.get(i) else {
+                    break;
+                };
+                self.push(scope.values().into(), index, &format!("{name} {i}"));
+            }
+        }
+        if let Some(display_object) = object.as_display_object() {
+            self.push(display_object.into(), index, "display object");
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct HeapSnapshotWindow {
+    snapshots: Vec<HeapSnapshot>,
+    selected: usize,
+    compare_with: Option<usize>,
+    diff: Option<(usize, usize, HeapSnapshotDiff)>,
+    selected_type: Option<String>,
+    search: String,
+}
+
+impl HeapSnapshotWindow {
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+
+        Window::new("Heap Snapshots")
+            .open(&mut keep_open)
+            .scroll2([true, true])
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Take Snapshot").clicked() {
+                        let name = format!("Snapshot {}", self.snapshots.len() + 1);
+                        self.snapshots.push(HeapSnapshot::capture(context, name));
+                        self.selected = self.snapshots.len() - 1;
+                    }
+                    if !self.snapshots.is_empty() {
+                        self.show_snapshot_selection(ui);
+                        if ui.button("Save File...").clicked() {
+                            messages.push(Message::SaveFile(self.save()));
+                        }
+                    }
+                });
+                ui.separator();
+
+                if self.snapshots.is_empty() {
+                    ui.label("Take a snapshot to see the objects that are currently alive.");
+                    return;
+                }
+
+                TextEdit::singleline(&mut self.search)
+                    .hint_text("Search")
+                    .show(ui);
+                ui.add_space(10.0);
+                let search = self.search.to_ascii_lowercase();
+
+                match self.compare_with {
+                    Some(before) => self.show_diff(ui, before, &search),
+                    None => self.show_snapshot(ui, &search),
+                }
+            });
+
+        keep_open
+    }
+
+    fn show_snapshot_selection(&mut self, ui: &mut Ui) {
+        ComboBox::from_id_source(ui.id().with("snapshot"))
+            .selected_text(self.snapshots[self.selected].name.as_str())
+            .show_ui(ui, |ui| {
+                for (i, snapshot) in self.snapshots.iter().enumerate() {
+                    ui.selectable_value(&mut self.selected, i, snapshot.name.as_str());
+                }
+            });
+
+        ui.label("compared with");
+        ComboBox::from_id_source(ui.id().with("compare_with"))
+            .selected_text(
+                self.compare_with
+                    .map_or("Nothing", |i| self.snapshots[i].name.as_str()),
+            )
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.compare_with, None, "Nothing");
+                for (i, snapshot) in self.snapshots.iter().enumerate() {
+                    if i != self.selected {
+                        ui.selectable_value(
+                            &mut self.compare_with,
+                            Some(i),
+                            snapshot.name.as_str(),
+                        );
+                    }
+                }
+            });
+        if self.compare_with == Some(self.selected) {
+            self.compare_with = None;
+        }
+    }
+
+    fn diff(&mut self, before: usize) -> &HeapSnapshotDiff {
+        let after = self.selected;
+        if !matches!(self.diff, Some((b, a, _)) if b == before && a == after) {
+            let diff = HeapSnapshotDiff::new(&self.snapshots[before], &self.snapshots[after]);
+            self.diff = Some((before, after, diff));
+        }
+        &self.diff.as_ref().expect("Diff was just computed").2
+    }
+
+    fn save(&mut self) -> ItemToSave {
+        match self.compare_with {
+            Some(before) => {
+                let diff = self.diff(before);
+                ItemToSave {
+                    suggested_name: format!("{} vs {}.json", diff.after, diff.before),
+                    data: diff.to_json(),
+                }
+            }
+            None => {
+                let snapshot = &self.snapshots[self.selected];
+                ItemToSave {
+                    suggested_name: format!("{}.json", snapshot.name),
+                    data: snapshot.to_json(),
+                }
+            }
+        }
+    }
+
+    fn show_snapshot(&mut self, ui: &mut Ui, search: &str) {
+        let snapshot = &self.snapshots[self.selected];
+        ui.label(format!(
+            "{} objects, {}",
+            snapshot.nodes.len(),
+            format_size(snapshot.total_size() as isize)
+        ));
+
+        let types: Vec<_> = snapshot
+            .type_summaries()
+            .into_iter()
+            .filter(|summary| summary.type_name.to_ascii_lowercase().contains(search))
+            .collect();
+
+        ui.push_id("types", |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .column(Column::remainder())
+                .column(Column::auto())
+                .column(Column::auto())
+                .auto_shrink([true, true])
+                .max_scroll_height(300.0)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Type");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Count");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Size");
+                    });
+                })
+                .body(|mut body| {
+                    for summary in &types {
+                        body.row(18.0, |mut row| {
+                            row.col(|ui| {
+                                let selected =
+                                    self.selected_type.as_ref() == Some(&summary.type_name);
+                                if ui
+                                    .selectable_label(selected, summary.type_name.as_str())
+                                    .clicked()
+                                {
+                                    self.selected_type = Some(summary.type_name.clone());
+                                }
+                            });
+                            row.col(|ui| {
+                                ui.label(summary.count.to_string());
+                            });
+                            row.col(|ui| {
+                                ui.label(format_size(summary.size as isize));
+                            });
+                        });
+                    }
+                });
+        });
+
+        if let Some(type_name) = &self.selected_type {
+            ui.separator();
+            CollapsingHeader::new(format!("Objects of type {type_name}"))
+                .default_open(true)
+                .show(ui, |ui| {
+                    let objects = snapshot
+                        .nodes
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, node)| &node.type_name == type_name)
+                        .take(MAX_LISTED_OBJECTS);
+                    for (index, node) in objects {
+                        show_object(
+                            ui,
+                            node.address,
+                            node.size,
+                            node.references,
+                            &snapshot.retention_path(index),
+                        );
+                    }
+                });
+        }
+    }
+
+    fn show_diff(&mut self, ui: &mut Ui, before: usize, search: &str) {
+        let before_size = self.snapshots[before].total_size() as isize;
+        let after_size = self.snapshots[self.selected].total_size() as isize;
+        ui.label(format!(
+            "{} since {}",
+            format_size_delta(after_size - before_size),
+            self.snapshots[before].name
+        ));
+
+        let diff = self.diff(before);
+        ui.push_id("type_diffs", |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .column(Column::remainder())
+                .column(Column::auto())
+                .column(Column::auto())
+                .auto_shrink([true, true])
+                .max_scroll_height(300.0)
+                .cell_layout(Layout::left_to_right(Align::Center))
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Type");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Count");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Size");
+                    });
+                })
+                .body(|mut body| {
+                    for type_diff in &diff.types {
+                        if !type_diff.type_name.to_ascii_lowercase().contains(search) {
+                            continue;
+                        }
+                        body.row(18.0, |mut row| {
+                            row.col(|ui| {
+                                ui.label(type_diff.type_name.as_str());
+                            });
+                            row.col(|ui| {
+                                ui.label(format!(
+                                    "{} ({:+})",
+                                    type_diff.count_after,
+                                    type_diff.count_delta()
+                                ));
+                            });
+                            row.col(|ui| {
+                                ui.label(format_size_delta(type_diff.size_delta()));
+                            });
+                        });
+                    }
+                });
+        });
+
+        ui.separator();
+        CollapsingHeader::new(format!("New objects ({})", diff.new_objects.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                let objects = diff
+                    .new_objects
+                    .iter()
+                    .filter(|object| object.type_name.to_ascii_lowercase().contains(search))
+                    .take(MAX_LISTED_OBJECTS);
+                for object in objects {
+                    ui.label(object.type_name.as_str());
+                    show_object(ui, object.address, object.size, 1, &object.retention_path);
+                }
+            });
+    }
+}
+
+fn show_object(ui: &mut Ui, address: usize, size: usize, references: usize, path: &str) {
+    ui.horizontal(|ui| {
+        ui.monospace(format!("{address:#x}"));
+        ui.label(format_size(size as isize));
+        if references > 1 {
+            ui.label(format!("{references} references"));
+        }
+    });
+    ui.indent(address, |ui| {
+        ui.small(path);
+    });
+}
+
+fn format_size(size: isize) -> String {
+    let magnitude = size.unsigned_abs() as f64;
+    if magnitude >= 1024.0 * 1024.0 {
+        format!("{:.1} MiB", size as f64 / (1024.0 * 1024.0))
+    } else if magnitude >= 1024.0 {
+        format!("{:.1} KiB", size as f64 / 1024.0)
+    } else {
+        format!("{size} bytes")
+    }
+}
+
+fn format_size_delta(delta: isize) -> String {
+    if delta > 0 {
+        format!("+{}", format_size(delta))
+    } else {
+        format_size(delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(
+        kind: HeapNodeKind,
+        address: usize,
+        type_name: &str,
+        size: usize,
+        retainer: Option<(usize, &str)>,
+    ) -> HeapNode {
+        HeapNode {
+            kind,
+            address,
+            type_name: type_name.to_string(),
+            size,
+            retainer: retainer.map(|(index, edge)| (index, edge.to_string())),
+            references: 1,
+        }
+    }
+
+    fn snapshot(name: &str, objects: &[(usize, &str, usize)]) -> HeapSnapshot {
+        let mut nodes = vec![
+            node(HeapNodeKind::Root, 0, "Stage", 0, None),
+            node(
+                HeapNodeKind::DisplayObject,
+                0x10,
+                "Stage",
+                100,
+                Some((0, "stage")),
+            ),
+        ];
+        for (address, type_name, size) in objects {
+            nodes.push(node(
+                HeapNodeKind::Avm2Object,
+                *address,
+                type_name,
+                *size,
+                Some((1, "child")),
+            ));
+        }
+        HeapSnapshot {
+            name: name.to_string(),
+            nodes,
+        }
+    }
+
+    #[test]
+    fn retention_path_starts_at_root() {
+        let mut snapshot = snapshot("Snapshot 1", &[(0x20, "flash.display::Sprite", 50)]);
+        snapshot.nodes.push(node(
+            HeapNodeKind::Avm2Object,
+            0x30,
+            "Array",
+            10,
+            Some((2, "items")),
+        ));
+
+        assert_eq!(snapshot.retention_path(0), "Stage");
+        assert_eq!(
+            snapshot.retention_path(3),
+            "Stage → stage (Stage) → child (flash.display::Sprite) → items (Array)"
+        );
+    }
+
+    #[test]
+    fn diff_lists_changed_types_and_new_objects() {
+        let before = snapshot(
+            "Snapshot 1",
+            &[(0x20, "Array", 10), (0x30, "flash.display::Sprite", 50)],
+        );
+        let after = snapshot(
+            "Snapshot 2",
+            &[
+                (0x20, "Array", 10),
+                (0x40, "Array", 30),
+                (0x50, "flash.display::Sprite", 50),
+            ],
+        );
+        let diff = HeapSnapshotDiff::new(&before, &after);
+
+        assert_eq!(diff.before, "Snapshot 1");
+        assert_eq!(diff.after, "Snapshot 2");
+
+        // The sprite was replaced by one of the same size, so only the arrays changed.
+        assert_eq!(diff.types.len(), 1);
+        let arrays = &diff.types[0];
+        assert_eq!(arrays.type_name, "Array");
+        assert_eq!(arrays.count_delta(), 1);
+        assert_eq!(arrays.size_delta(), 30);
+
+        let new_objects: Vec<_> = diff
+            .new_objects
+            .iter()
+            .map(|object| (object.address, object.type_name.as_str()))
+            .collect();
+        assert_eq!(
+            new_objects,
+            [(0x40, "Array"), (0x50, "flash.display::Sprite")]
+        );
+        assert_eq!(
+            diff.new_objects[0].retention_path,
+            "Stage → stage (Stage) → child (Array)"
+        );
+    }
+
+    #[test]
+    fn diff_sorts_types_by_growth() {
+        let before = snapshot("Snapshot 1", &[(0x20, "Array", 10), (0x30, "Object", 40)]);
+        let after = snapshot(
+            "Snapshot 2",
+            &[
+                (0x40, "Array", 100),
+                (0x50, "Object", 20),
+                (0x60, "XML", 50),
+            ],
+        );
+        let diff = HeapSnapshotDiff::new(&before, &after);
+
+        let types: Vec<_> = diff
+            .types
+            .iter()
+            .map(|diff| (diff.type_name.as_str(), diff.size_delta()))
+            .collect();
+        assert_eq!(types, [("Array", 90), ("XML", 50), ("Object", -20)]);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::profiler::SpanStats;
+use egui::{Align, Layout, TextEdit, Ui};
+use egui_extras::{Column, TableBuilder};
+use web_time::Duration;
+
+/// A searchable table of profiled functions, methods or events, with the most time spent in
+/// them first.
+#[derive(Debug, Default)]
+pub struct StatsTable {
+    search: String,
+}
+
+impl StatsTable {
+    pub fn show_search(&mut self, ui: &mut Ui) {
+        TextEdit::singleline(&mut self.search)
+            .hint_text("Search")
+            .show(ui);
+    }
+
+    /// Shows the rows whose first label matches the search, with a column for each label
+    /// followed by the statistics.
+    pub fn show<'a>(
+        &self,
+        ui: &mut Ui,
+        label_headers: &[&str],
+        rows: impl IntoIterator<Item = (Vec<String>, &'a SpanStats)>,
+    ) {
+        let search = self.search.to_ascii_lowercase();
+        let mut rows: Vec<_> = rows
+            .into_iter()
+            .filter(|(labels, _)| {
+                labels
+                    .first()
+                    .is_some_and(|name| name.to_ascii_lowercase().contains(&search))
+            })
+            .collect();
+        rows.sort_by(|a, b| b.1.exclusive.cmp(&a.1.exclusive));
+
+        let mut table = TableBuilder::new(ui).striped(true).resizable(true);
+        for i in 0..label_headers.len() {
+            // The last label gets the remaining space.
+            table = table.column(if i + 1 == label_headers.len() {
+                Column::remainder()
+            } else {
+                Column::auto()
+            });
+        }
+        table
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .auto_shrink([true, true])
+            .cell_layout(Layout::left_to_right(Align::Center))
+            .header(20.0, |mut header| {
+                for name in label_headers
+                    .iter()
+                    .chain(&["Calls", "Self Time", "Total Time"])
+                {
+                    header.col(|ui| {
+                        ui.strong(*name);
+                    });
+                }
+            })
+            .body(|mut body| {
+                for (labels, stats) in rows {
+                    body.row(18.0, |mut row| {
+                        for label in labels {
+                            row.col(|ui| {
+                                ui.label(label);
+                            });
+                        }
+                        row.col(|ui| {
+                            ui.label(stats.calls.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(format_duration(stats.exclusive));
+                        });
+                        row.col(|ui| {
+                            ui.label(format_duration(stats.total));
+                        });
+                    });
+                }
+            });
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
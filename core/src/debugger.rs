@@ -0,0 +1,668 @@
+//! A script debugger, which lets a client set breakpoints, step through code and inspect values.
+//!
+//! The player talks to the client through a `DebuggerConnection`, which carries `DebugCommand`s
+//! in and `DebugMessage`s out; the frontend is responsible for translating them to a wire
+//! protocol. Every command is answered with exactly one message, in order. `Stopped` is the only
+//! message that is sent on its own, whenever a script pauses.
+//!
+//! While a script is paused, the debugger is taken out of the `UpdateContext`, so that code run
+//! by an evaluated expression can't hit any breakpoints.
+
+use std::fmt;
+
+/// How often, in executed instructions, pending commands are handled while scripts run.
+const POLL_INTERVAL: u32 = 10_000;
+
+/// A place where execution should pause.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BreakpointLocation {
+    /// An AVM1 action, given by the URL of its movie and its byte offset in the SWF.
+    Action { swf: String, offset: usize },
+
+    /// An AVM2 instruction, given by the name of its method (e.g. `Main/onEnterFrame`) and its
+    /// byte offset in the method body.
+    Method { method: String, offset: u32 },
+
+    /// A line of AVM2 source code, which is only known when compiled with debug information.
+    Line { file: String, line: u32 },
+}
+
+/// Why a script paused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    Breakpoint,
+    Step,
+    Pause,
+}
+
+impl StopReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StopReason::Breakpoint => "breakpoint",
+            StopReason::Step => "step",
+            StopReason::Pause => "pause",
+        }
+    }
+}
+
+/// A frame of the call stack of a paused script, innermost first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackFrame {
+    pub name: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+
+    /// The current instruction, as a location that a breakpoint can be set at. This is only
+    /// known for the innermost frame.
+    pub location: Option<BreakpointLocation>,
+}
+
+/// A named value, or a scope when listing scopes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    pub type_name: String,
+
+    /// The reference to pass to `DebugCommand::Variables` to list the children of this value,
+    /// or 0 if it has none. References are only valid until the script continues.
+    pub reference: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugCommand {
+    /// Replaces all breakpoints in a group, such as all line breakpoints of a single file.
+    SetBreakpoints {
+        group: String,
+        locations: Vec<BreakpointLocation>,
+    },
+    Continue,
+    Pause,
+    StepIn,
+    StepOver,
+    StepOut,
+    StackTrace,
+    Scopes,
+    Variables {
+        reference: u32,
+    },
+    Evaluate {
+        expression: String,
+    },
+
+    /// Removes all breakpoints and continues.
+    Disconnect,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugMessage {
+    Stopped { reason: StopReason },
+    Done,
+    StackTrace(Vec<StackFrame>),
+    Variables(Vec<Variable>),
+    Evaluated(Result<Variable, String>),
+}
+
+/// The channel between the player and a debugger client.
+pub trait DebuggerConnection {
+    /// Returns the next command, if one is pending.
+    fn try_receive(&mut self) -> Option<DebugCommand>;
+
+    /// Waits for the next command, or returns `None` if the client is gone.
+    fn receive(&mut self) -> Option<DebugCommand>;
+
+    fn send(&mut self, message: DebugMessage);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Running,
+    Pause,
+    StepIn,
+    StepOver { depth: usize },
+    StepOut { depth: usize },
+}
+
+pub struct Debugger {
+    connection: Box<dyn DebuggerConnection>,
+    breakpoints: Vec<(String, BreakpointLocation)>,
+    mode: Mode,
+    instructions_since_poll: u32,
+}
+
+impl fmt::Debug for Debugger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("breakpoints", &self.breakpoints)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+impl Debugger {
+    pub fn new(connection: Box<dyn DebuggerConnection>) -> Self {
+        Self {
+            connection,
+            breakpoints: Vec::new(),
+            mode: Mode::Running,
+            instructions_since_poll: 0,
+        }
+    }
+
+    /// Handles the commands that were sent while no script is paused.
+    pub fn poll(&mut self) {
+        while let Some(command) = self.connection.try_receive() {
+            let reply = match command {
+                DebugCommand::SetBreakpoints { group, locations } => {
+                    self.set_breakpoints(group, locations);
+                    DebugMessage::Done
+                }
+                DebugCommand::Pause => {
+                    self.mode = Mode::Pause;
+                    DebugMessage::Done
+                }
+                DebugCommand::Continue
+                | DebugCommand::StepIn
+                | DebugCommand::StepOver
+                | DebugCommand::StepOut => DebugMessage::Done,
+                DebugCommand::StackTrace => DebugMessage::StackTrace(Vec::new()),
+                DebugCommand::Scopes | DebugCommand::Variables { .. } => {
+                    DebugMessage::Variables(Vec::new())
+                }
+                DebugCommand::Evaluate { .. } => {
+                    DebugMessage::Evaluated(Err("No script is paused".to_string()))
+                }
+                DebugCommand::Disconnect => {
+                    self.disconnect();
+                    DebugMessage::Done
+                }
+            };
+            self.connection.send(reply);
+        }
+    }
+
+    /// Decides whether to pause before running an instruction.
+    ///
+    /// `depth` is the depth of the call stack, `is_statement` tells whether the instruction
+    /// starts a new statement (stepping stops on those only), and `hits` tells whether a
+    /// breakpoint location refers to the instruction.
+    pub(crate) fn check(
+        &mut self,
+        depth: usize,
+        is_statement: impl FnOnce() -> bool,
+        hits: impl FnMut(&BreakpointLocation) -> bool,
+    ) -> Option<StopReason> {
+        self.instructions_since_poll += 1;
+        if self.instructions_since_poll >= POLL_INTERVAL {
+            self.instructions_since_poll = 0;
+            self.poll();
+        }
+
+        let step = match self.mode {
+            Mode::Running => None,
+            Mode::Pause => return Some(StopReason::Pause),
+            Mode::StepIn => Some(StopReason::Step),
+            Mode::StepOver { depth: start } if depth <= start => Some(StopReason::Step),
+            Mode::StepOut { depth: start } if depth < start => Some(StopReason::Step),
+            Mode::StepOver { .. } | Mode::StepOut { .. } => None,
+        };
+        if step.is_some() && is_statement() {
+            return step;
+        }
+
+        self.breakpoints
+            .iter()
+            .map(|(_, location)| location)
+            .any(hits)
+            .then_some(StopReason::Breakpoint)
+    }
+
+    fn set_breakpoints(&mut self, group: String, locations: Vec<BreakpointLocation>) {
+        self.breakpoints.retain(|(g, _)| *g != group);
+        self.breakpoints
+            .extend(locations.into_iter().map(|l| (group.clone(), l)));
+    }
+
+    fn disconnect(&mut self) {
+        self.breakpoints.clear();
+        self.mode = Mode::Running;
+    }
+}
+
+/// A literal in an evaluated expression.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Literal {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+/// An evaluated expression, which may only read values: names, `this`, literals, member
+/// accesses and indexing.
+#[derive(Clone, Debug, PartialEq)]
+enum Expression {
+    Name(String),
+    This,
+    Literal(Literal),
+    Member(Box<Expression>, String),
+    Index(Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: source.chars().collect(),
+            pos: 0,
+        };
+        let expression = parser.expression()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(expression),
+            Some(c) => Err(format!("Unexpected '{c}'")),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(format!("Expected '{expected}', found '{c}'")),
+            None => Err(format!("Expected '{expected}'")),
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        {
+            self.pos += 1;
+        }
+        if start == self.pos || self.chars[start].is_ascii_digit() {
+            return Err("Expected a name".to_string());
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn expression(&mut self) -> Result<Expression, String> {
+        let mut expression = self.primary()?;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('.') => {
+                    self.pos += 1;
+                    expression = Expression::Member(Box::new(expression), self.identifier()?);
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    let index = self.expression()?;
+                    self.expect(']')?;
+                    expression = Expression::Index(Box::new(expression), Box::new(index));
+                }
+                _ => return Ok(expression),
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expression, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let expression = self.expression()?;
+                self.expect(')')?;
+                Ok(expression)
+            }
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let mut string = String::new();
+                loop {
+                    match self.peek() {
+                        Some(c) if c == quote => break,
+                        Some('\\') => {
+                            self.pos += 1;
+                            match self.peek() {
+                                Some('n') => string.push('\n'),
+                                Some('t') => string.push('\t'),
+                                Some(c) => string.push(c),
+                                None => return Err("Unterminated string".to_string()),
+                            }
+                        }
+                        Some(c) => string.push(c),
+                        None => return Err("Unterminated string".to_string()),
+                    }
+                    self.pos += 1;
+                }
+                self.pos += 1;
+                Ok(Expression::Literal(Literal::String(string)))
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' => {
+                let start = self.pos;
+                self.pos += 1;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '.')
+                {
+                    self.pos += 1;
+                }
+                let source: String = self.chars[start..self.pos].iter().collect();
+                let number = if let Some(hex) = source.strip_prefix("0x") {
+                    u32::from_str_radix(hex, 16).map(f64::from).ok()
+                } else {
+                    source.parse().ok()
+                };
+                number
+                    .map(|n| Expression::Literal(Literal::Number(n)))
+                    .ok_or_else(|| format!("Invalid number '{source}'"))
+            }
+            Some(_) => Ok(match self.identifier()?.as_str() {
+                "this" => Expression::This,
+                "undefined" => Expression::Literal(Literal::Undefined),
+                "null" => Expression::Literal(Literal::Null),
+                "true" => Expression::Literal(Literal::Bool(true)),
+                "false" => Expression::Literal(Literal::Bool(false)),
+                "NaN" => Expression::Literal(Literal::Number(f64::NAN)),
+                "Infinity" => Expression::Literal(Literal::Number(f64::INFINITY)),
+                name => Expression::Name(name.to_string()),
+            }),
+            None => Err("Expected an expression".to_string()),
+        }
+    }
+}
+
+/// Values with their names, such as the locals of a scope or the properties of an object.
+pub(crate) type NamedValues<V> = Vec<(String, V)>;
+
+/// A paused script, as seen by the debugger.
+pub(crate) trait Debuggee {
+    type Value: Copy;
+
+    /// The depth of the call stack, as passed to `Debugger::check`.
+    fn depth(&self) -> usize;
+
+    fn stack_trace(&mut self) -> Vec<StackFrame>;
+
+    /// The scopes of the innermost frame, each with its named values.
+    fn scopes(&mut self) -> Vec<(String, NamedValues<Self::Value>)>;
+
+    /// The named values inside of a value, or nothing for primitives.
+    fn children(&mut self, value: Self::Value) -> NamedValues<Self::Value>;
+
+    /// Returns how a value is displayed, its type name and whether it has children.
+    fn describe(&mut self, value: Self::Value) -> (String, String, bool);
+
+    /// Converts a value to a property name.
+    fn key(&mut self, value: Self::Value) -> String;
+
+    /// Looks up a name in the scope of the innermost frame.
+    fn lookup(&mut self, name: &str) -> Option<Self::Value>;
+
+    fn this(&mut self) -> Self::Value;
+
+    fn member(&mut self, value: Self::Value, name: &str) -> Option<Self::Value>;
+
+    fn literal(&mut self, literal: &Literal) -> Self::Value;
+}
+
+enum Handle<V> {
+    Scope(NamedValues<V>),
+    Value(V),
+}
+
+fn variable<D: Debuggee>(
+    debuggee: &mut D,
+    handles: &mut Vec<Handle<D::Value>>,
+    name: String,
+    value: D::Value,
+) -> Variable {
+    let (display, type_name, has_children) = debuggee.describe(value);
+    let reference = if has_children {
+        handles.push(Handle::Value(value));
+        handles.len() as u32
+    } else {
+        0
+    };
+    Variable {
+        name,
+        value: display,
+        type_name,
+        reference,
+    }
+}
+
+fn evaluate<D: Debuggee>(debuggee: &mut D, expression: &Expression) -> Result<D::Value, String> {
+    match expression {
+        Expression::Name(name) => debuggee
+            .lookup(name)
+            .ok_or_else(|| format!("{name} is not defined")),
+        Expression::This => Ok(debuggee.this()),
+        Expression::Literal(literal) => Ok(debuggee.literal(literal)),
+        Expression::Member(object, name) => {
+            let object = evaluate(debuggee, object)?;
+            debuggee
+                .member(object, name)
+                .ok_or_else(|| format!("Can't read property {name}"))
+        }
+        Expression::Index(object, index) => {
+            let object = evaluate(debuggee, object)?;
+            let index = evaluate(debuggee, index)?;
+            let name = debuggee.key(index);
+            debuggee
+                .member(object, &name)
+                .ok_or_else(|| format!("Can't read property {name}"))
+        }
+    }
+}
+
+/// Pauses a script and handles commands until the client continues or disconnects.
+pub(crate) fn pause<D: Debuggee>(debuggee: &mut D, debugger: &mut Debugger, reason: StopReason) {
+    debugger.connection.send(DebugMessage::Stopped { reason });
+
+    let mut handles: Vec<Handle<D::Value>> = Vec::new();
+    loop {
+        let Some(command) = debugger.connection.receive() else {
+            debugger.disconnect();
+            return;
+        };
+        let (reply, mode) = match command {
+            DebugCommand::SetBreakpoints { group, locations } => {
+                debugger.set_breakpoints(group, locations);
+                (DebugMessage::Done, None)
+            }
+            DebugCommand::Pause => (DebugMessage::Done, None),
+            DebugCommand::Continue => (DebugMessage::Done, Some(Mode::Running)),
+            DebugCommand::StepIn => (DebugMessage::Done, Some(Mode::StepIn)),
+            DebugCommand::StepOver => (
+                DebugMessage::Done,
+                Some(Mode::StepOver {
+                    depth: debuggee.depth(),
+                }),
+            ),
+            DebugCommand::StepOut => (
+                DebugMessage::Done,
+                Some(Mode::StepOut {
+                    depth: debuggee.depth(),
+                }),
+            ),
+            DebugCommand::Disconnect => {
+                debugger.disconnect();
+                (DebugMessage::Done, Some(Mode::Running))
+            }
+            DebugCommand::StackTrace => (DebugMessage::StackTrace(debuggee.stack_trace()), None),
+            DebugCommand::Scopes => {
+                let scopes = debuggee
+                    .scopes()
+                    .into_iter()
+                    .map(|(name, values)| {
+                        handles.push(Handle::Scope(values));
+                        Variable {
+                            name,
+                            value: String::new(),
+                            type_name: String::new(),
+                            reference: handles.len() as u32,
+                        }
+                    })
+                    .collect();
+                (DebugMessage::Variables(scopes), None)
+            }
+            DebugCommand::Variables { reference } => {
+                let values = match handles.get(reference.wrapping_sub(1) as usize) {
+                    Some(Handle::Scope(values)) => values.clone(),
+                    Some(Handle::Value(value)) => debuggee.children(*value),
+                    None => Vec::new(),
+                };
+                let variables = values
+                    .into_iter()
+                    .map(|(name, value)| variable(debuggee, &mut handles, name, value))
+                    .collect();
+                (DebugMessage::Variables(variables), None)
+            }
+            DebugCommand::Evaluate { expression } => {
+                let result = Expression::parse(&expression)
+                    .and_then(|parsed| evaluate(debuggee, &parsed))
+                    .map(|value| variable(debuggee, &mut handles, expression, value));
+                (DebugMessage::Evaluated(result), None)
+            }
+        };
+        debugger.connection.send(reply);
+        if let Some(mode) = mode {
+            debugger.mode = mode;
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    struct TestConnection(VecDeque<DebugCommand>);
+
+    impl DebuggerConnection for TestConnection {
+        fn try_receive(&mut self) -> Option<DebugCommand> {
+            self.0.pop_front()
+        }
+
+        fn receive(&mut self) -> Option<DebugCommand> {
+            self.0.pop_front()
+        }
+
+        fn send(&mut self, _message: DebugMessage) {}
+    }
+
+    fn debugger(commands: Vec<DebugCommand>) -> Debugger {
+        Debugger::new(Box::new(TestConnection(commands.into())))
+    }
+
+    #[test]
+    fn parse_expressions() {
+        assert_eq!(
+            Expression::parse("this.items[0].name"),
+            Ok(Expression::Member(
+                Box::new(Expression::Index(
+                    Box::new(Expression::Member(
+                        Box::new(Expression::This),
+                        "items".to_string()
+                    )),
+                    Box::new(Expression::Literal(Literal::Number(0.0)))
+                )),
+                "name".to_string()
+            ))
+        );
+        assert_eq!(
+            Expression::parse(" map [ 'a\\'b' ] "),
+            Ok(Expression::Index(
+                Box::new(Expression::Name("map".to_string())),
+                Box::new(Expression::Literal(Literal::String("a'b".to_string())))
+            ))
+        );
+        assert_eq!(
+            Expression::parse("(null)"),
+            Ok(Expression::Literal(Literal::Null))
+        );
+        assert!(Expression::parse("a +").is_err());
+        assert!(Expression::parse("a[0").is_err());
+        assert!(Expression::parse("a.1").is_err());
+    }
+
+    #[test]
+    fn breakpoints_replace_their_group() {
+        let location = |offset| BreakpointLocation::Action {
+            swf: "test.swf".to_string(),
+            offset,
+        };
+        let mut debugger = debugger(vec![
+            DebugCommand::SetBreakpoints {
+                group: "a".to_string(),
+                locations: vec![location(1), location(2)],
+            },
+            DebugCommand::SetBreakpoints {
+                group: "b".to_string(),
+                locations: vec![location(3)],
+            },
+            DebugCommand::SetBreakpoints {
+                group: "a".to_string(),
+                locations: vec![location(4)],
+            },
+        ]);
+        debugger.poll();
+
+        let hits = |debugger: &mut Debugger, offset| {
+            debugger.check(0, || true, |l| *l == location(offset)) == Some(StopReason::Breakpoint)
+        };
+        assert!(!hits(&mut debugger, 1));
+        assert!(!hits(&mut debugger, 2));
+        assert!(hits(&mut debugger, 3));
+        assert!(hits(&mut debugger, 4));
+    }
+
+    #[test]
+    fn stepping_respects_depth() {
+        let mut debugger = debugger(vec![]);
+        debugger.mode = Mode::StepOver { depth: 2 };
+        assert_eq!(debugger.check(3, || true, |_| false), None);
+        assert_eq!(debugger.check(2, || false, |_| false), None);
+        assert_eq!(
+            debugger.check(2, || true, |_| false),
+            Some(StopReason::Step)
+        );
+
+        debugger.mode = Mode::StepOut { depth: 2 };
+        assert_eq!(debugger.check(2, || true, |_| false), None);
+        assert_eq!(
+            debugger.check(1, || true, |_| false),
+            Some(StopReason::Step)
+        );
+
+        debugger.mode = Mode::Pause;
+        assert_eq!(
+            debugger.check(5, || false, |_| false),
+            Some(StopReason::Pause)
+        );
+    }
+}
@@ -34,6 +34,7 @@ mod interactive;
 mod loader_display;
 mod morph_shape;
 mod movie_clip;
+mod scaling_grid;
 mod stage;
 mod text;
 mod video;
@@ -58,11 +59,13 @@ use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
+use ruffle_render::matrix3d::Matrix3D;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
 pub use text::Text;
 pub use video::Video;
 
 use self::loader_display::LoaderDisplayWeak;
+use self::scaling_grid::ScalingGridSlices;
 
 /// If a `DisplayObject` is marked `cacheAsBitmap` (via tag or AS),
 /// this struct keeps the information required to uphold that cache.
@@ -185,6 +188,61 @@ impl BitmapCache {
     }
 }
 
+/// The additional transform properties of a display object that has been transformed in 3D,
+/// through `z`, `rotationX`, `rotationY`, `scaleZ` or `transform.matrix3D`.
+///
+/// The remaining components (`x`, `y`, `rotation`, `scaleX` and `scaleY`) are shared
+/// with the 2D transform.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform3D {
+    pub z: f64,
+    pub rotation_x: Degrees,
+    pub rotation_y: Degrees,
+    pub scale_z: f64,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from_radians(0.0),
+            rotation_y: Degrees::from_radians(0.0),
+            scale_z: 1.0,
+        }
+    }
+}
+
+/// The perspective projection used to display the 3D children of a display object
+/// (`transform.perspectiveProjection`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The field of view, in degrees.
+    pub field_of_view: f64,
+
+    /// The vanishing point, in pixels in the local space of the display object.
+    pub projection_center: (f64, f64),
+}
+
+impl PerspectiveProjection {
+    /// The default field of view, in degrees.
+    pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+    /// The projection used for display objects without a projection of their own,
+    /// centered on the stage.
+    pub fn for_stage(stage_size: (u32, u32)) -> Self {
+        Self {
+            field_of_view: Self::DEFAULT_FIELD_OF_VIEW,
+            projection_center: (f64::from(stage_size.0) / 2.0, f64::from(stage_size.1) / 2.0),
+        }
+    }
+
+    /// The distance between the viewer and the `z = 0` plane, in pixels.
+    /// Flash derives this from the field of view and the width of the stage.
+    pub fn focal_length(&self, stage_width: u32) -> f64 {
+        f64::from(stage_width) / 2.0 / (self.field_of_view.to_radians() / 2.0).tan()
+    }
+}
+
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
 pub struct DisplayObjectBase<'gc> {
@@ -210,6 +268,15 @@ pub struct DisplayObjectBase<'gc> {
 
     skew: f64,
 
+    /// The 3D transform properties of this object.
+    /// `None` means this object is only transformed in 2D.
+    #[collect(require_static)]
+    transform_3d: Option<Transform3D>,
+
+    /// The perspective projection used for the 3D children of this object.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
+
     /// The next display object in order of execution.
     ///
     /// `None` in an AVM2 movie.
@@ -279,6 +346,8 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             scale_x: Percent::from_unit(1.0),
             scale_y: Percent::from_unit(1.0),
             skew: 0.0,
+            transform_3d: None,
+            perspective_projection: None,
             next_avm1_clip: None,
             masker: None,
             maskee: None,
@@ -496,6 +565,102 @@ impl<'gc> DisplayObjectBase<'gc> {
         changed
     }
 
+    pub fn transform_3d(&self) -> Option<Transform3D> {
+        self.transform_3d
+    }
+
+    /// Updates the 3D transform properties, enabling the 3D transform if necessary.
+    fn update_transform_3d(&mut self, update: impl FnOnce(&mut Transform3D)) -> bool {
+        self.set_transformed_by_script(true);
+        let old = self.transform_3d;
+        update(self.transform_3d.get_or_insert_with(Default::default));
+        self.recheck_cache_as_bitmap();
+        old != self.transform_3d
+    }
+
+    /// The 3D transformation matrix of this object, if it has been transformed in 3D.
+    /// The translation is in pixels.
+    pub fn matrix3d(&mut self) -> Option<Matrix3D> {
+        let transform_3d = self.transform_3d?;
+        self.cache_scale_rotation();
+        Some(
+            Matrix3D::translate(self.x().to_pixels(), self.y().to_pixels(), transform_3d.z)
+                * Matrix3D::rotate_z(self.rotation.into_radians())
+                * Matrix3D::rotate_y(transform_3d.rotation_y.into_radians())
+                * Matrix3D::rotate_x(transform_3d.rotation_x.into_radians())
+                * Matrix3D::scale(
+                    self.scale_x.unit(),
+                    self.scale_y.unit(),
+                    transform_3d.scale_z,
+                ),
+        )
+    }
+
+    /// Sets the 3D transformation matrix of this object.
+    /// `None` turns this object back into a 2D object, keeping its 2D transform.
+    fn set_matrix3d(&mut self, matrix: Option<Matrix3D>) -> bool {
+        let Some(matrix) = matrix else {
+            let changed = self.transform_3d.is_some();
+            self.transform_3d = None;
+            self.recheck_cache_as_bitmap();
+            return changed;
+        };
+
+        // Decompose the matrix in the same way as `Matrix3D.decompose`.
+        let m = matrix.raw_data;
+        let scale_x = (m[0] * m[0] + m[1] * m[1] + m[2] * m[2]).sqrt();
+        let scale_y = (m[4] * m[4] + m[5] * m[5] + m[6] * m[6]).sqrt();
+        let mut scale_z = (m[8] * m[8] + m[9] * m[9] + m[10] * m[10]).sqrt();
+        let determinant = m[0] * (m[5] * m[10] - m[6] * m[9]) - m[1] * (m[4] * m[10] - m[6] * m[8])
+            + m[2] * (m[4] * m[9] - m[5] * m[8]);
+        if determinant < 0.0 {
+            scale_z = -scale_z;
+        }
+        let (r0, r1, r2) = (m[0] / scale_x, m[1] / scale_x, m[2] / scale_x);
+        let (r4, r5, r6) = (m[4] / scale_y, m[5] / scale_y, m[6] / scale_y);
+        let r10 = m[10] / scale_z;
+        let rotation_y = (-r2).asin();
+        let (rotation_x, rotation_z) = if r2 != 1.0 && r2 != -1.0 {
+            (r6.atan2(r10), r1.atan2(r0))
+        } else {
+            (r4.atan2(r5), 0.0)
+        };
+
+        let (sin, cos) = rotation_z.sin_cos();
+        self.transform.matrix = Matrix {
+            a: (scale_x * cos) as f32,
+            b: (scale_x * sin) as f32,
+            c: (scale_y * -sin) as f32,
+            d: (scale_y * cos) as f32,
+            tx: Twips::from_pixels(m[12]),
+            ty: Twips::from_pixels(m[13]),
+        };
+        self.rotation = Degrees::from_radians(rotation_z);
+        self.scale_x = Percent::from_unit(scale_x);
+        self.scale_y = Percent::from_unit(scale_y);
+        self.skew = 0.0;
+        self.set_scale_rotation_cached(true);
+        self.update_transform_3d(|transform_3d| {
+            *transform_3d = Transform3D {
+                z: m[14],
+                rotation_x: Degrees::from_radians(rotation_x),
+                rotation_y: Degrees::from_radians(rotation_y),
+                scale_z,
+            };
+        });
+        true
+    }
+
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.perspective_projection
+    }
+
+    fn set_perspective_projection(&mut self, projection: Option<PerspectiveProjection>) -> bool {
+        let changed = self.perspective_projection != projection;
+        self.perspective_projection = projection;
+        changed
+    }
+
     fn name(&self) -> Option<AvmString<'gc>> {
         self.name
     }
@@ -719,8 +884,20 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.remove(DisplayObjectFlags::CACHE_INVALIDATED);
     }
 
+    /// Whether the content of this object changed since it was last drawn.
+    /// This is only tracked for redraw regions.
+    fn redraw_invalidated(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::REDRAW_INVALIDATED)
+    }
+
+    fn set_redraw_invalidated(&mut self, value: bool) {
+        self.flags.set(DisplayObjectFlags::REDRAW_INVALIDATED, value);
+    }
+
     fn recheck_cache_as_bitmap(&mut self) {
-        let should_cache = self.is_bitmap_cached_preference() || !self.filters.is_empty();
+        let should_cache = self.is_bitmap_cached_preference()
+            || !self.filters.is_empty()
+            || self.transform_3d.is_some();
         if should_cache && self.cache.is_none() {
             self.cache = Some(Default::default());
         } else if !should_cache && self.cache.is_some() {
@@ -784,7 +961,35 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
-    context.transform_stack.push(this.base().transform());
+    if !context.is_offscreen && context.stage.redraw_regions_color().is_some() {
+        let parent_transform = context.transform_stack.transform();
+        let matrix = parent_transform.matrix * *this.base().matrix();
+        let color_transform = parent_transform.color_transform * *this.base().color_transform();
+        let invalidated = this.base().redraw_invalidated();
+        this.base_mut(context.gc_context).set_redraw_invalidated(false);
+        context.stage.record_redraw(
+            context.gc_context,
+            this,
+            matrix * this.self_bounds(),
+            color_transform,
+            invalidated,
+        );
+    }
+    // Objects with a 3D transform are drawn into their bitmap cache, which is then projected
+    // onto the parent. Their own transform is applied when projecting.
+    let matrix3d = if context.use_bitmap_cache && this.is_bitmap_cached() {
+        this.base_mut(context.gc_context).matrix3d()
+    } else {
+        None
+    };
+    if matrix3d.is_some() {
+        context.transform_stack.push(&Transform {
+            matrix: Matrix::IDENTITY,
+            color_transform: *this.base().color_transform(),
+        });
+    } else {
+        context.transform_stack.push(this.base().transform());
+    }
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
@@ -794,7 +999,21 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
 
     let cache_info = if context.use_bitmap_cache && this.is_bitmap_cached() {
         let mut cache_info: Option<DrawCacheInfo> = None;
-        let base_transform = context.transform_stack.transform();
+        let mut base_transform = context.transform_stack.transform();
+        if let Some(matrix3d) = &matrix3d {
+            // Rasterize the object unrotated, at roughly the scale it will be displayed at.
+            let parent = Matrix3D::from(base_transform.matrix);
+            let column_scale = |m: &Matrix3D, column: usize| {
+                (0..3)
+                    .map(|row| m.get(row, column).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            };
+            let scale = column_scale(&parent, 0).max(column_scale(&parent, 1))
+                * column_scale(matrix3d, 0).max(column_scale(matrix3d, 1));
+            let scale = if scale.is_finite() { scale as f32 } else { 0.0 };
+            base_transform.matrix = Matrix::scale(scale, scale);
+        }
         let bounds: Rectangle<Twips> = this.render_bounds_with_transform(
             &base_transform.matrix,
             false, // we want to do the filter growth for this object ourselves, to know the offsets
@@ -916,23 +1135,53 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
             });
         }
 
-        // When rendering it back, ensure we're only keeping the translation - scale/rotation is within the image already
-        apply_standard_mask_and_scroll(this, context, |context| {
-            context.commands.render_bitmap(
-                cache_info.handle,
-                Transform {
-                    matrix: Matrix {
-                        tx: context.transform_stack.transform().matrix.tx + offset_x,
-                        ty: context.transform_stack.transform().matrix.ty + offset_y,
-                        ..Default::default()
+        if let Some(matrix3d) = matrix3d {
+            // Map the cached image back into local space, transform it in 3D within the parent,
+            // then project it in stage space.
+            let raster_scale = f64::from(cache_info.base_transform.matrix.a);
+            let to_local = Matrix3D::scale(1.0 / raster_scale, 1.0 / raster_scale, 1.0)
+                * Matrix3D::translate(offset_x.to_pixels(), offset_y.to_pixels(), 0.0);
+            let view_matrix = context.stage.view_matrix();
+            let inverse_view_matrix = context.stage.inverse_view_matrix();
+            let projection = this.perspective_projection_matrix(context.stage);
+            apply_standard_mask_and_scroll(this, context, |context| {
+                let parent = context.transform_stack.transform().matrix;
+                let parent_in_stage = inverse_view_matrix * parent;
+                context.commands.render_bitmap_projected(
+                    cache_info.handle,
+                    Matrix3D::from(view_matrix)
+                        * projection
+                        * Matrix3D::from(parent_in_stage)
+                        * matrix3d
+                        * to_local,
+                    cache_info.base_transform.color_transform,
+                    true,
+                )
+            });
+        } else {
+            // When rendering it back, ensure we're only keeping the translation - scale/rotation is within the image already
+            apply_standard_mask_and_scroll(this, context, |context| {
+                context.commands.render_bitmap(
+                    cache_info.handle,
+                    Transform {
+                        matrix: Matrix {
+                            tx: context.transform_stack.transform().matrix.tx + offset_x,
+                            ty: context.transform_stack.transform().matrix.ty + offset_y,
+                            ..Default::default()
+                        },
+                        color_transform: cache_info.base_transform.color_transform,
                     },
-                    color_transform: cache_info.base_transform.color_transform,
-                },
-                true,
-                PixelSnapping::Always, // cacheAsBitmap forces pixel snapping
-            )
-        });
+                    true,
+                    PixelSnapping::Always, // cacheAsBitmap forces pixel snapping
+                )
+            });
+        }
     } else {
+        if matrix3d.is_some() {
+            // Without a cache to project, draw the object flat.
+            context.transform_stack.pop();
+            context.transform_stack.push(this.base().transform());
+        }
         if let Some(background) = this.opaque_background() {
             // This is intended for use with cacheAsBitmap, but can be set for non-cached objects too
             // It wants the entire bounding box to be cleared before any draws happen
@@ -1381,6 +1630,156 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The `z` position in pixels of this display object in local space.
+    /// Returned by the `z` ActionScript property.
+    fn z(&self) -> f64 {
+        self.base().transform_3d().map_or(0.0, |t| t.z)
+    }
+
+    /// Sets the `z` position in pixels of this display object in local space.
+    /// Set by the `z` ActionScript property, which enables the 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_z(&self, gc_context: &Mutation<'gc>, z: f64) {
+        let changed = self
+            .base_mut(gc_context)
+            .update_transform_3d(|transform_3d| transform_3d.z = z);
+        if changed {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees around the X axis of this display object in local space.
+    /// Returned by the `rotationX` ActionScript property.
+    fn rotation_x(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(Degrees::from_radians(0.0), |t| t.rotation_x)
+    }
+
+    /// Sets the rotation in degrees around the X axis of this display object in local space.
+    /// Set by the `rotationX` ActionScript property, which enables the 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_x(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        let changed = self
+            .base_mut(gc_context)
+            .update_transform_3d(|transform_3d| transform_3d.rotation_x = degrees);
+        if changed {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees around the Y axis of this display object in local space.
+    /// Returned by the `rotationY` ActionScript property.
+    fn rotation_y(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(Degrees::from_radians(0.0), |t| t.rotation_y)
+    }
+
+    /// Sets the rotation in degrees around the Y axis of this display object in local space.
+    /// Set by the `rotationY` ActionScript property, which enables the 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_y(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        let changed = self
+            .base_mut(gc_context)
+            .update_transform_3d(|transform_3d| transform_3d.rotation_y = degrees);
+        if changed {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The Z axis scale for this display object in local space.
+    /// Returned by the `scaleZ` ActionScript property.
+    fn scale_z(&self) -> f64 {
+        self.base().transform_3d().map_or(1.0, |t| t.scale_z)
+    }
+
+    /// Sets the Z axis scale for this display object in local space.
+    /// Set by the `scaleZ` ActionScript property, which enables the 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_scale_z(&self, gc_context: &Mutation<'gc>, value: f64) {
+        let changed = self
+            .base_mut(gc_context)
+            .update_transform_3d(|transform_3d| transform_3d.scale_z = value);
+        if changed {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The 3D transformation matrix of this display object, if it has been transformed in 3D.
+    /// Returned by the `transform.matrix3D` ActionScript property.
+    fn matrix3d(&self, gc_context: &Mutation<'gc>) -> Option<Matrix3D> {
+        self.base_mut(gc_context).matrix3d()
+    }
+
+    /// Sets the 3D transformation matrix of this display object.
+    /// `None` turns this object back into a 2D object.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_matrix3d(&self, gc_context: &Mutation<'gc>, matrix: Option<Matrix3D>) {
+        if self.base_mut(gc_context).set_matrix3d(matrix) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The perspective projection set on this display object, used for its 3D children.
+    /// Returned by the `transform.perspectiveProjection` ActionScript property.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection()
+    }
+
+    /// Sets the perspective projection used for the 3D children of this display object.
+    /// This invalidates this object's cacheAsBitmap automatically.
+    fn set_perspective_projection(
+        &self,
+        gc_context: &Mutation<'gc>,
+        projection: Option<PerspectiveProjection>,
+    ) {
+        if self
+            .base_mut(gc_context)
+            .set_perspective_projection(projection)
+        {
+            self.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// Returns the perspective projection applied to this object's 3D transform,
+    /// as a matrix operating on stage pixels.
+    ///
+    /// This is the projection of the closest ancestor that has one, or a projection
+    /// centered on the stage.
+    fn perspective_projection_matrix(&self, stage: Stage<'gc>) -> Matrix3D {
+        let stage_size = stage.stage_size();
+        let mut node = self.parent();
+        while let Some(ancestor) = node {
+            if let Some(projection) = ancestor.perspective_projection() {
+                let center = ancestor.local_to_global(Point::new(
+                    Twips::from_pixels(projection.projection_center.0),
+                    Twips::from_pixels(projection.projection_center.1),
+                ));
+                return Matrix3D::perspective_projection(
+                    projection.focal_length(stage_size.0),
+                    (center.x.to_pixels(), center.y.to_pixels()),
+                );
+            }
+            node = ancestor.parent();
+        }
+        let projection = PerspectiveProjection::for_stage(stage_size);
+        Matrix3D::perspective_projection(
+            projection.focal_length(stage_size.0),
+            projection.projection_center,
+        )
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -1667,6 +2066,21 @@ pub trait TDisplayObject<'gc>:
 
     fn set_scaling_grid(&self, gc_context: &Mutation<'gc>, rect: Rectangle<Twips>) {
         self.base_mut(gc_context).scaling_grid = rect;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// The regions to draw this object's shapes in to apply its `scale9Grid`,
+    /// or `None` if the grid currently has no effect.
+    fn scaling_grid_slices(&self) -> Option<ScalingGridSlices> {
+        let grid = self.scaling_grid();
+        if !grid.is_valid() {
+            return None;
+        }
+        ScalingGridSlices::new(
+            &grid,
+            &self.bounds_with_transform(&Matrix::IDENTITY),
+            self.base().matrix(),
+        )
     }
 
     /// Whether this object has been removed. Only applies to AVM1.
@@ -2471,10 +2885,18 @@ pub trait TDisplayObject<'gc>:
     /// Inform this object and its ancestors that it has visually changed and must be redrawn.
     /// If this object or any ancestor is marked as cacheAsBitmap, it will invalidate that cache.
     fn invalidate_cached_bitmap(&self, mc: &Mutation<'gc>) {
-        if self.base_mut(mc).invalidate_cached_bitmap() {
+        let mut base = self.base_mut(mc);
+        // Only this object needs to be redrawn, its ancestors merely contain it.
+        base.set_redraw_invalidated(true);
+        if base.invalidate_cached_bitmap() {
+            drop(base);
             // Don't inform ancestors if we've already done so this frame
-            if let Some(parent) = self.parent() {
-                parent.invalidate_cached_bitmap(mc);
+            let mut ancestor = self.parent();
+            while let Some(parent) = ancestor {
+                if !parent.base_mut(mc).invalidate_cached_bitmap() {
+                    break;
+                }
+                ancestor = parent.parent();
             }
         }
     }
@@ -2556,6 +2978,10 @@ bitflags! {
 
         /// If this AVM1 object is pending removal (will be removed on the next frame).
         const AVM1_PENDING_REMOVAL     = 1 << 13;
+
+        /// If the content of this object changed since it was last drawn.
+        /// Used to track redraw regions.
+        const REDRAW_INVALIDATED       = 1 << 14;
     }
 }
 
@@ -10,6 +10,7 @@ use crate::display_object::container::{dispatch_added_event, dispatch_removed_ev
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
+use crate::display_object::scaling_grid::is_sliced_child;
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, MovieClip, TDisplayObject};
 use crate::events::{ClipEvent, ClipEventResult};
 use crate::frame_lifecycle::catchup_display_object_to_frame;
@@ -578,7 +579,12 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
         let current_state = self.get_state_child(self.0.state.get().into());
 
         if let Some(state) = current_state {
-            state.render(context);
+            match self.scaling_grid_slices() {
+                Some(slices) if is_sliced_child(state) => {
+                    slices.render(context, |context| state.render(context))
+                }
+                _ => state.render(context),
+            }
         }
     }
 
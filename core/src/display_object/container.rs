@@ -9,6 +9,7 @@ use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::avm1_button::Avm1Button;
 use crate::display_object::loader_display::LoaderDisplay;
 use crate::display_object::movie_clip::MovieClip;
+use crate::display_object::scaling_grid::is_sliced_child;
 use crate::display_object::stage::Stage;
 use crate::display_object::{Depth, DisplayObject, TDisplayObject, TInteractiveObject};
 use crate::string::WStr;
@@ -477,6 +478,9 @@ pub trait TDisplayObjectContainer<'gc>:
 
     /// Renders the children of this container in render list order.
     fn render_children(self, context: &mut RenderContext<'_, 'gc>) {
+        // Shapes within an object with a `scale9Grid` are drawn sliced.
+        let this: DisplayObject<'_> = self.into();
+        let slices = this.scaling_grid_slices();
         let mut clip_depth = 0;
         let mut clip_depth_stack: Vec<(Depth, DisplayObject<'_>)> = vec![];
         for child in self.iter_render_list() {
@@ -506,7 +510,12 @@ pub trait TDisplayObjectContainer<'gc>:
                 // Either a normal visible child, or a descendant of a mask object
                 // that we're drawing. The 'visible' flag is ignored for all descendants
                 // of a mask.
-                child.render(context);
+                match &slices {
+                    Some(slices) if is_sliced_child(child) => {
+                        slices.render(context, |context| child.render(context))
+                    }
+                    _ => child.render(context),
+                }
             }
         }
 
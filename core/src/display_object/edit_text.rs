@@ -7,23 +7,28 @@ use crate::avm1::{
     Object as Avm1Object, StageObject as Avm1StageObject, TObject as Avm1TObject,
     Value as Avm1Value,
 };
+use crate::avm2::globals::flash::display::loader::load_request;
 use crate::avm2::Avm2;
 use crate::avm2::{
-    Activation as Avm2Activation, EventObject as Avm2EventObject, Object as Avm2Object,
-    StageObject as Avm2StageObject, TObject as _,
+    Activation as Avm2Activation, ClassObject as Avm2ClassObject, Error as Avm2Error,
+    EventObject as Avm2EventObject, Object as Avm2Object, StageObject as Avm2StageObject,
+    TObject as _,
 };
+use crate::backend::navigator::Request;
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
-use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, MovieClip, TDisplayObject};
 use crate::drawing::Drawing;
-use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
+use crate::events::{ClipEvent, ClipEventResult, ImeEvent, TextControlCode};
 use crate::font::{round_down_to_pixel, FontType, Glyph, TextRenderSettings};
 use crate::html::{
-    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, TextFormat,
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, Size, TextFormat,
+    TextImage,
 };
+use crate::loader::MovieLoaderVMData;
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -176,9 +181,39 @@ pub struct EditTextData<'gc> {
     /// Restrict what characters the user may input.
     #[collect(require_static)]
     restrict: EditTextRestrict,
+
+    /// The in-progress input method editor (IME) composition, if any.
+    ///
+    /// The composition text is only displayed while the user is composing,
+    /// and becomes part of the text once it is committed.
+    #[collect(require_static)]
+    ime_composition: Option<ImeComposition>,
+
+    /// The images embedded into the text with `<img>` tags, in the same
+    /// order as the images of `text_spans`.
+    images: Vec<EditTextImage<'gc>>,
+
+    /// The undo and redo history of the edits made by the user.
+    #[collect(require_static)]
+    history: TextHistory,
+
+    /// The last mouse press on the text, used to detect multiple clicks.
+    #[collect(require_static)]
+    last_click: Option<TextClick>,
 }
 
 impl<'gc> EditTextData<'gc> {
+    /// Replace the text and selection with a state from the undo history,
+    /// returning the current state.
+    fn restore_history_state(&mut self, state: TextHistoryState) -> TextHistoryState {
+        self.ime_composition = None;
+        self.history.break_merging();
+        TextHistoryState {
+            text_spans: std::mem::replace(&mut self.text_spans, state.text_spans),
+            selection: std::mem::replace(&mut self.selection, state.selection),
+        }
+    }
+
     fn vertical_scroll_offset(&self) -> Twips {
         if self.scroll > 1 {
             let line_data = &self.line_data;
@@ -261,6 +296,7 @@ impl<'gc> EditText<'gc> {
                 &text,
                 default_format,
                 swf_tag.is_multiline(),
+                false,
                 swf_movie.version(),
             )
         } else {
@@ -283,13 +319,15 @@ impl<'gc> EditText<'gc> {
             FontType::Device
         };
 
-        let (layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
+        // Embedded images are only loaded once the text field is laid out again.
+        let (layout, intrinsic_bounds, _) = LayoutBox::lower_from_text_spans(
             &text_spans,
             context,
             swf_movie.clone(),
             swf_tag.bounds().width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap(),
             font_type,
+            &[],
         );
         let line_data = get_line_data(&layout);
 
@@ -357,6 +395,10 @@ impl<'gc> EditText<'gc> {
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
                 is_tlf: false,
                 restrict: EditTextRestrict::allow_all(),
+                ime_composition: None,
+                images: Vec::new(),
+                history: Default::default(),
+                last_click: None,
             },
         ));
 
@@ -427,6 +469,8 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.ime_composition = None;
+        edit_text.history.clear();
         drop(edit_text);
 
         self.relayout(context);
@@ -449,8 +493,11 @@ impl<'gc> EditText<'gc> {
                 text,
                 default_format,
                 write.flags.contains(EditTextFlag::MULTILINE),
+                write.flags.contains(EditTextFlag::CONDENSE_WHITE),
                 write.static_data.swf.version(),
             );
+            write.ime_composition = None;
+            write.history.clear();
             drop(write);
 
             self.relayout(context);
@@ -459,6 +506,50 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Replace the text in the range [from, to) with the given HTML text,
+    /// keeping its formatting.
+    ///
+    /// If `paste` is set, the inserted text is subject to `restrict` and
+    /// `maxChars`, like text pasted by the user.
+    pub fn replace_html_text(
+        self,
+        from: usize,
+        to: usize,
+        html: &WStr,
+        paste: bool,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        let mut spans = FormatSpans::from_html(
+            html,
+            write.text_spans.default_format().clone(),
+            write.flags.contains(EditTextFlag::MULTILINE),
+            write.flags.contains(EditTextFlag::CONDENSE_WHITE),
+            write.static_data.swf.version(),
+        );
+        if paste {
+            write.restrict.restrict_spans(&mut spans);
+            if write.max_chars > 0 {
+                let kept_len = write
+                    .text_spans
+                    .text()
+                    .len()
+                    .saturating_sub(to.saturating_sub(from));
+                let max_len = (write.max_chars as usize).saturating_sub(kept_len);
+                let len = spans.text().len();
+                if len > max_len {
+                    spans.replace_text(max_len, len, WStr::empty(), None);
+                }
+            }
+        }
+        write.text_spans.replace_with_spans(from, to, &spans);
+        write.ime_composition = None;
+        write.history.clear();
+        drop(write);
+
+        self.relayout(context);
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -494,6 +585,32 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    pub fn condense_white(self) -> bool {
+        self.0.read().flags.contains(EditTextFlag::CONDENSE_WHITE)
+    }
+
+    pub fn set_condense_white(self, gc_context: &Mutation<'gc>, condense_white: bool) {
+        self.0
+            .write(gc_context)
+            .flags
+            .set(EditTextFlag::CONDENSE_WHITE, condense_white);
+    }
+
+    pub fn always_show_selection(self) -> bool {
+        self.0
+            .read()
+            .flags
+            .contains(EditTextFlag::ALWAYS_SHOW_SELECTION)
+    }
+
+    pub fn set_always_show_selection(self, gc_context: &Mutation<'gc>, value: bool) {
+        self.0
+            .write(gc_context)
+            .flags
+            .set(EditTextFlag::ALWAYS_SHOW_SELECTION, value);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
     pub fn is_editable(self) -> bool {
         !self.0.read().flags.contains(EditTextFlag::READ_ONLY)
     }
@@ -646,6 +763,9 @@ impl<'gc> EditText<'gc> {
         self.0.write(gc_context).is_tlf = is_tlf;
     }
 
+    /// Replace the text in the range [from, to) on behalf of a script.
+    ///
+    /// This forgets the undo history, as it doesn't apply to the new text.
     pub fn replace_text(
         self,
         from: usize,
@@ -653,10 +773,22 @@ impl<'gc> EditText<'gc> {
         text: &WStr,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .replace_text(from, to, text, None);
+        self.0.write(context.gc_context).history.clear();
+        self.replace_input_text(from, to, text, context);
+    }
+
+    /// Replace the text in the range [from, to) with the input of the user.
+    fn replace_input_text(
+        self,
+        from: usize,
+        to: usize,
+        text: &WStr,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        write.ime_composition = None;
+        write.text_spans.replace_text(from, to, text, None);
+        drop(write);
         self.relayout(context);
     }
 
@@ -785,6 +917,9 @@ impl<'gc> EditText<'gc> {
     /// Applies to each side.
     const INTERNAL_PADDING: f64 = 2.0;
 
+    /// Color of the selection box of an unfocused text field with `alwaysShowSelection` set.
+    const UNFOCUSED_SELECTION_COLOR: Color = Color::from_rgb(0xC0C0C0, 0xFF);
+
     /// Relayout the `EditText`.
     ///
     /// This function operates exclusively with the text-span representation of
@@ -792,13 +927,16 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.sync_images(context);
+
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.flags.contains(EditTextFlag::WORD_WRAP);
+        let is_password = edit_text.flags.contains(EditTextFlag::PASSWORD);
         let movie = edit_text.static_data.swf.clone();
         let padding = Twips::from_pixels(EditText::INTERNAL_PADDING) * 2;
 
-        if edit_text.flags.contains(EditTextFlag::PASSWORD) {
+        if is_password {
             // If the text is a password, hide the text
             edit_text.text_spans.hide_text();
         } else if edit_text.text_spans.has_displayed_text() {
@@ -821,15 +959,33 @@ impl<'gc> EditText<'gc> {
             FontType::Embedded
         };
 
-        let (new_layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
-            &edit_text.text_spans,
+        for image in edit_text.images.iter_mut() {
+            image.size = image.layout_size();
+        }
+        let image_sizes: Vec<_> = edit_text.images.iter().map(|image| image.size).collect();
+
+        // The layout includes the IME composition, although it isn't part of the text yet.
+        let composed_spans = edit_text
+            .ime_composition
+            .as_ref()
+            .map(|composition| composition.apply(&edit_text.text_spans, is_password));
+        let (new_layout, intrinsic_bounds, image_bounds) = LayoutBox::lower_from_text_spans(
+            composed_spans.as_ref().unwrap_or(&edit_text.text_spans),
             context,
             movie,
             content_width,
             is_word_wrap,
             font_type,
+            &image_sizes,
         );
 
+        for (image, bounds) in edit_text.images.iter_mut().zip(image_bounds) {
+            image.bounds = bounds;
+        }
+        if let (Some(composition), Some(spans)) = (&mut edit_text.ime_composition, composed_spans) {
+            composition.displayed_text = spans.displayed_text().to_owned();
+        }
+
         edit_text.line_data = get_line_data(&new_layout);
         edit_text.layout = new_layout;
         edit_text.intrinsic_bounds = intrinsic_bounds;
@@ -865,9 +1021,231 @@ impl<'gc> EditText<'gc> {
         }
         drop(edit_text);
         self.redraw_border(context.gc_context);
+        self.position_images(context.gc_context);
         self.invalidate_cached_bitmap(context.gc_context);
     }
 
+    /// Update the display objects of the embedded images to match the images
+    /// of the text, loading any images that are new.
+    fn sync_images(self, context: &mut UpdateContext<'_, 'gc>) {
+        let read = self.0.read();
+        if read.images.is_empty() && read.text_spans.images().is_empty() {
+            return;
+        }
+        let text_images = read.text_spans.images().to_vec();
+        drop(read);
+
+        let mut old_images = std::mem::take(&mut self.0.write(context.gc_context).images);
+        let mut images = Vec::with_capacity(text_images.len());
+        for image in text_images {
+            let display_object = match old_images
+                .iter()
+                .position(|old| old.image.is_same_content(&image))
+            {
+                Some(index) => old_images.remove(index).display_object,
+                None => self.load_image(context, &image),
+            };
+
+            images.push(EditTextImage {
+                image,
+                display_object,
+                size: Default::default(),
+                bounds: Default::default(),
+            });
+        }
+
+        for old_image in old_images {
+            if let Some(display_object) = old_image.display_object {
+                if !display_object.movie().is_action_script_3() {
+                    display_object.avm1_unload(context);
+                }
+                display_object.set_parent(context, None);
+            }
+        }
+
+        self.0.write(context.gc_context).images = images;
+    }
+
+    /// Start loading an embedded image.
+    ///
+    /// The source of the image is either the linkage identifier of a library
+    /// symbol (in AVM2, the name of its class), or the URL of a SWF or bitmap
+    /// file.
+    fn load_image(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        image: &TextImage,
+    ) -> Option<DisplayObject<'gc>> {
+        let movie = self.movie();
+        let library = context.library.library_for_movie(movie.clone())?;
+        let src = AvmString::new(context.gc_context, image.src.clone());
+
+        if movie.is_action_script_3() {
+            let domain = library.avm2_domain();
+            let mut activation = Avm2Activation::from_domain(context.reborrow(), domain);
+            let loader_class = activation.avm2().classes().loader;
+            let loader = match loader_class.construct(&mut activation, &[]) {
+                Ok(loader) => loader,
+                Err(e) => {
+                    tracing::error!("Failed to construct Loader for <img> tag: {e:?}");
+                    return None;
+                }
+            };
+
+            let symbol_class = domain
+                .get_defined_value_handling_vector(&mut activation, src)
+                .ok()
+                .and_then(|value| value.as_object())
+                .and_then(|object| object.as_class_object());
+            let result = if let Some(symbol_class) = symbol_class {
+                Self::instantiate_image_symbol(&mut activation, loader, symbol_class)
+            } else {
+                let request = Request::get(image.src.to_utf8_lossy().into_owned());
+                load_request(&mut activation, loader, request, None)
+            };
+            if let Err(e) = result {
+                tracing::warn!("Failed to load <img> \"{}\": {e:?}", image.src);
+            }
+
+            let display_object = loader.as_display_object()?;
+            display_object.set_parent(&mut activation.context, Some(self.into()));
+            Some(display_object)
+        } else {
+            let clip = match library.instantiate_by_export_name(src, context.gc_context) {
+                Ok(clip) => clip,
+                Err(_) => {
+                    let clip: DisplayObject<'gc> = MovieClip::new(movie, context.gc_context).into();
+                    let request = Request::get(image.src.to_utf8_lossy().into_owned());
+                    let future = context.load_manager.load_movie_into_clip(
+                        context.player.clone(),
+                        clip,
+                        request,
+                        None,
+                        MovieLoaderVMData::Avm1 { broadcaster: None },
+                    );
+                    context.navigator.spawn_future(future);
+                    clip
+                }
+            };
+
+            if let Some(id) = &image.id {
+                clip.set_name(
+                    context.gc_context,
+                    AvmString::new(context.gc_context, id.clone()),
+                );
+            }
+            clip.set_parent(context, Some(self.into()));
+            clip.post_instantiation(context, None, Instantiator::Movie, false);
+            Some(clip)
+        }
+    }
+
+    /// Instantiate the library symbol of an embedded image as the content of
+    /// the given `Loader`.
+    fn instantiate_image_symbol(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        loader: Avm2Object<'gc>,
+        symbol_class: Avm2ClassObject<'gc>,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let bitmapdata_class = activation.avm2().classes().bitmapdata;
+        let content = if symbol_class.has_class_in_chain(bitmapdata_class.inner_class_definition())
+        {
+            let bitmap_data = symbol_class.construct(activation, &[0.into(), 0.into()])?;
+            activation
+                .avm2()
+                .classes()
+                .bitmap
+                .construct(activation, &[bitmap_data.into()])?
+        } else {
+            symbol_class.construct(activation, &[])?
+        };
+
+        if let (Some(content), Some(loader)) = (
+            content.as_display_object(),
+            loader.as_display_object().and_then(|l| l.as_container()),
+        ) {
+            loader.insert_at_index(&mut activation.context, content, 0);
+        }
+
+        Ok(())
+    }
+
+    /// Move and scale the embedded images to where the layout placed them.
+    fn position_images(self, gc_context: &Mutation<'gc>) {
+        let edit_text = self.0.read();
+        let origin = Position::from((
+            edit_text.bounds.x_min + Twips::from_pixels(Self::INTERNAL_PADDING),
+            edit_text.bounds.y_min + Twips::from_pixels(Self::INTERNAL_PADDING),
+        ));
+
+        for image in &edit_text.images {
+            let Some(display_object) = image.display_object else {
+                continue;
+            };
+
+            let natural_bounds = image.natural_bounds();
+            let scale_x = if natural_bounds.width() > Twips::ZERO {
+                image.bounds.width().get() as f32 / natural_bounds.width().get() as f32
+            } else {
+                1.0
+            };
+            let scale_y = if natural_bounds.height() > Twips::ZERO {
+                image.bounds.height().get() as f32 / natural_bounds.height().get() as f32
+            } else {
+                1.0
+            };
+
+            display_object.set_matrix(
+                gc_context,
+                Matrix {
+                    a: scale_x,
+                    d: scale_y,
+                    tx: origin.x() + image.bounds.offset_x()
+                        - Twips::from_pixels(natural_bounds.x_min.to_pixels() * scale_x as f64),
+                    ty: origin.y() + image.bounds.offset_y()
+                        - Twips::from_pixels(natural_bounds.y_min.to_pixels() * scale_y as f64),
+                    ..Matrix::IDENTITY
+                },
+            );
+        }
+    }
+
+    /// Relayout the text if any embedded image changed its size, e.g.
+    /// because it finished loading.
+    fn update_image_sizes(self, context: &mut UpdateContext<'_, 'gc>) {
+        let needs_relayout = self
+            .0
+            .read()
+            .images
+            .iter()
+            .any(|image| image.layout_size() != image.size);
+
+        if needs_relayout {
+            self.relayout(context);
+        }
+    }
+
+    /// Returns the display object an embedded image with the given `id` was
+    /// loaded into.
+    pub fn image_by_id(self, id: &WStr) -> Option<DisplayObject<'gc>> {
+        self.0
+            .read()
+            .images
+            .iter()
+            .find(|image| image.image.id.as_deref() == Some(id))
+            .and_then(|image| image.display_object)
+    }
+
+    /// All display objects of the embedded images.
+    fn image_objects(self) -> Vec<DisplayObject<'gc>> {
+        self.0
+            .read()
+            .images
+            .iter()
+            .filter_map(|image| image.display_object)
+            .collect()
+    }
+
     /// Measure the width and height of the `EditText`'s current text load.
     ///
     /// The returned tuple should be interpreted as width, then height.
@@ -959,7 +1337,14 @@ impl<'gc> EditText<'gc> {
 
         let edit_text = self.0.read();
 
-        let visible_selection = if edit_text.flags.contains(EditTextFlag::HAS_FOCUS) {
+        let has_focus = edit_text.flags.contains(EditTextFlag::HAS_FOCUS);
+        let visible_selection = if let Some(composition) = &edit_text.ime_composition {
+            Some(composition.selection)
+        } else if has_focus
+            || edit_text
+                .flags
+                .contains(EditTextFlag::ALWAYS_SHOW_SELECTION)
+        {
             edit_text.selection
         } else {
             None
@@ -967,7 +1352,8 @@ impl<'gc> EditText<'gc> {
 
         let caret = if let LayoutContent::Text { start, end, .. } = &lbox.content() {
             if let Some(visible_selection) = visible_selection {
-                if visible_selection.is_caret()
+                if has_focus
+                    && visible_selection.is_caret()
                     && !edit_text.flags.contains(EditTextFlag::READ_ONLY)
                     && visible_selection.start() >= *start
                     && visible_selection.end() <= *end
@@ -990,13 +1376,17 @@ impl<'gc> EditText<'gc> {
             0
         };
 
+        let ime_composition = edit_text.ime_composition.as_ref();
+        let displayed_text: &WStr = match ime_composition {
+            Some(composition) => &composition.displayed_text,
+            None => edit_text.text_spans.displayed_text(),
+        };
+
         // If the font can't be found or has no glyph information, use the "device font" instead.
         // We're cheating a bit and not actually rendering text using the OS/web.
         // Instead, we embed an SWF version of Noto Sans to use as the "device font", and render
         // it the same as any other SWF outline text.
-        if let Some((text, _tf, font, params, color)) =
-            lbox.as_renderable_text(edit_text.text_spans.displayed_text())
-        {
+        if let Some((text, _tf, font, params, color)) = lbox.as_renderable_text(displayed_text) {
             let baseline = font.get_baseline_for_height(params.height());
             let descent = font.get_descent_for_height(params.height());
             let baseline_adjustment = baseline - params.height();
@@ -1010,14 +1400,20 @@ impl<'gc> EditText<'gc> {
                     if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
                         // If it's highlighted, override the color.
                         if matches!(visible_selection, Some(visible_selection) if visible_selection.contains(start + pos)) {
-                            // Draw black selection rect
-                            self.render_selection(context, x, advance, caret_height);
-
-                            // Set text color to white
-                            context.transform_stack.push(&Transform {
-                                matrix: transform.matrix,
-                                color_transform: ColorTransform::IDENTITY,
-                            });
+                            if has_focus {
+                                // Draw black selection rect
+                                self.render_selection(context, x, advance, caret_height, Color::BLACK);
+
+                                // Set text color to white
+                                context.transform_stack.push(&Transform {
+                                    matrix: transform.matrix,
+                                    color_transform: ColorTransform::IDENTITY,
+                                });
+                            } else {
+                                // Unfocused selections are drawn in gray, keeping the text color.
+                                self.render_selection(context, x, advance, caret_height, Self::UNFOCUSED_SELECTION_COLOR);
+                                context.transform_stack.push(transform);
+                            }
                         } else {
                             context.transform_stack.push(transform);
                         }
@@ -1029,6 +1425,11 @@ impl<'gc> EditText<'gc> {
                         context.transform_stack.pop();
                     }
 
+                    // Underline the text that is still being composed.
+                    if matches!(ime_composition, Some(composition) if composition.contains(start + pos)) {
+                        self.render_ime_underline(context, x, advance, baseline, color);
+                    }
+
                     // Update caret position
                     if let Some(caret) = caret {
                         if pos == caret {
@@ -1059,6 +1460,7 @@ impl<'gc> EditText<'gc> {
         x: Twips,
         width: Twips,
         height: Twips,
+        color: Color,
     ) {
         let selection_box = context.transform_stack.transform().matrix
             * Matrix::create_box(
@@ -1068,7 +1470,27 @@ impl<'gc> EditText<'gc> {
                 x,
                 Twips::ZERO,
             );
-        context.commands.draw_rect(Color::BLACK, selection_box);
+        context.commands.draw_rect(color, selection_box);
+    }
+
+    fn render_ime_underline(
+        self,
+        context: &mut RenderContext<'_, 'gc>,
+        x: Twips,
+        width: Twips,
+        baseline: Twips,
+        color: Color,
+    ) {
+        let thickness = Twips::from_pixels(1.0);
+        let underline = context.transform_stack.transform().matrix
+            * Matrix::create_box(
+                width.to_pixels() as f32,
+                thickness.to_pixels() as f32,
+                0.0,
+                x,
+                baseline + thickness,
+            );
+        context.commands.draw_rect(color, underline);
     }
 
     fn render_caret(
@@ -1369,10 +1791,21 @@ impl<'gc> EditText<'gc> {
             return;
         }
 
+        if !control_code.is_edit_input() {
+            // Moving the caret around starts a new undo step.
+            self.0.write(context.gc_context).history.break_merging();
+        }
+
         if let Some(selection) = self.selection() {
             let mut changed = false;
             let is_selectable = self.is_selectable();
             match control_code {
+                TextControlCode::Undo => {
+                    changed = self.undo(context);
+                }
+                TextControlCode::Redo => {
+                    changed = self.redo(context);
+                }
                 TextControlCode::Enter => {
                     self.text_input(Self::INPUT_NEWLINE, context);
                 }
@@ -1451,7 +1884,8 @@ impl<'gc> EditText<'gc> {
                     }
 
                     if text.len() <= self.available_chars() {
-                        self.replace_text(
+                        self.record_edit(TextEditKind::Other, context.gc_context);
+                        self.replace_input_text(
                             selection.start(),
                             selection.end(),
                             &WString::from_utf8(&text),
@@ -1477,7 +1911,8 @@ impl<'gc> EditText<'gc> {
                         let text = &self.text()[selection.start()..selection.end()];
                         context.ui.set_clipboard_content(text.to_string());
 
-                        self.replace_text(
+                        self.record_edit(TextEditKind::Other, context.gc_context);
+                        self.replace_input_text(
                             selection.start(),
                             selection.end(),
                             WStr::empty(),
@@ -1504,7 +1939,13 @@ impl<'gc> EditText<'gc> {
                     if !selection.is_caret() =>
                 {
                     // Backspace or delete with multiple characters selected
-                    self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
+                    self.record_edit(TextEditKind::Other, context.gc_context);
+                    self.replace_input_text(
+                        selection.start(),
+                        selection.end(),
+                        WStr::empty(),
+                        context,
+                    );
                     self.set_selection(
                         Some(TextSelection::for_position(selection.start())),
                         context.gc_context,
@@ -1516,7 +1957,8 @@ impl<'gc> EditText<'gc> {
                     if selection.start() > 0 {
                         // Delete previous character(s)
                         let start = self.find_new_position(control_code, selection.start());
-                        self.replace_text(start, selection.start(), WStr::empty(), context);
+                        self.record_edit(TextEditKind::Deletion, context.gc_context);
+                        self.replace_input_text(start, selection.start(), WStr::empty(), context);
                         self.set_selection(
                             Some(TextSelection::for_position(start)),
                             context.gc_context,
@@ -1529,7 +1971,8 @@ impl<'gc> EditText<'gc> {
                     if selection.end() < self.text_length() {
                         // Delete next character(s)
                         let end = self.find_new_position(control_code, selection.start());
-                        self.replace_text(selection.start(), end, WStr::empty(), context);
+                        self.record_edit(TextEditKind::Deletion, context.gc_context);
+                        self.replace_input_text(selection.start(), end, WStr::empty(), context);
                         // No need to change selection, reset it to prevent caret from blinking
                         self.reset_selection_blinking(context.gc_context);
                         changed = true;
@@ -1648,6 +2091,107 @@ impl<'gc> EditText<'gc> {
         self.text().get(pos).unwrap_or(0) == '\n' as u16
     }
 
+    /// Find the word around `pos`, as selected by a double click.
+    ///
+    /// This algorithm is based on [UAX #29](https://unicode.org/reports/tr29/).
+    fn find_word_at(self, pos: usize) -> (usize, usize) {
+        let text = self.text();
+        let to_utf8 = WStrToUtf8::new(&text);
+        WordBoundIndices::new(&to_utf8.to_utf8_lossy())
+            .filter_map(|(utf8_index, span)| {
+                let start = to_utf8.utf16_index(utf8_index)?;
+                let end = to_utf8.utf16_index(utf8_index + span.len())?;
+                Some((start, end))
+            })
+            .find(|&(_, end)| pos < end)
+            .unwrap_or((text.len(), text.len()))
+    }
+
+    /// Find the paragraph around `pos`, as selected by a triple click.
+    ///
+    /// The trailing paragraph break is not part of the paragraph.
+    fn find_paragraph_at(self, pos: usize) -> (usize, usize) {
+        let text = self.text();
+        let is_break = |c: u16| c == '\n' as u16 || c == '\r' as u16;
+        let pos = pos.min(text.len());
+
+        let start = text[..pos].rfind(is_break).map_or(0, |index| index + 1);
+        let end = text[pos..]
+            .find(is_break)
+            .map_or(text.len(), |index| pos + index);
+        (start, end)
+    }
+
+    /// Remember the current text before the user edits it, so that the edit can be undone.
+    fn record_edit(self, kind: TextEditKind, gc_context: &Mutation<'gc>) {
+        let mut write = self.0.write(gc_context);
+        let EditTextData {
+            history,
+            text_spans,
+            selection,
+            ..
+        } = &mut *write;
+        history.record(kind, || TextHistoryState {
+            text_spans: text_spans.clone(),
+            selection: *selection,
+        });
+    }
+
+    /// Revert the last edit made by the user.
+    ///
+    /// Returns `true` if the text has changed.
+    fn undo(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let mut write = self.0.write(context.gc_context);
+        let Some(state) = write.history.undo.pop() else {
+            return false;
+        };
+        let current = write.restore_history_state(state);
+        write.history.redo.push(current);
+        drop(write);
+
+        self.relayout(context);
+        true
+    }
+
+    /// Reapply the last edit reverted by [`Self::undo`].
+    ///
+    /// Returns `true` if the text has changed.
+    fn redo(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        let mut write = self.0.write(context.gc_context);
+        let Some(state) = write.history.redo.pop() else {
+            return false;
+        };
+        let current = write.restore_history_state(state);
+        write.history.undo.push(current);
+        drop(write);
+
+        self.relayout(context);
+        true
+    }
+
+    /// Register a mouse press at the given text position,
+    /// returning how many times in a row the text has been clicked there.
+    fn register_click(self, position: usize, context: &mut UpdateContext<'_, 'gc>) -> u8 {
+        let now = context.timers.current_time_ms();
+        let mut write = self.0.write(context.gc_context);
+        let count = match write.last_click {
+            Some(click)
+                if click.position == position
+                    && now.wrapping_sub(click.time) < TextClick::MULTI_CLICK_INTERVAL_MS =>
+            {
+                // Clicking again after a triple click starts over.
+                click.count % 3 + 1
+            }
+            _ => 1,
+        };
+        write.last_click = Some(TextClick {
+            time: now,
+            position,
+            count,
+        });
+        count
+    }
+
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc>) {
         if self.0.read().flags.contains(EditTextFlag::READ_ONLY)
             || (character.is_control() && character != Self::INPUT_NEWLINE)
@@ -1686,7 +2230,13 @@ impl<'gc> EditText<'gc> {
             }
         }
 
-        self.replace_text(
+        let kind = if selection.is_caret() {
+            TextEditKind::Typing
+        } else {
+            TextEditKind::Other
+        };
+        self.record_edit(kind, context.gc_context);
+        self.replace_input_text(
             selection.start(),
             selection.end(),
             &WString::from_char(character),
@@ -1707,6 +2257,149 @@ impl<'gc> EditText<'gc> {
         self.on_changed(&mut activation);
     }
 
+    /// Handle an event sent by the input method editor (IME).
+    pub fn ime(self, event: ImeEvent, context: &mut UpdateContext<'_, 'gc>) {
+        if !self.is_editable() {
+            return;
+        }
+
+        match event {
+            ImeEvent::Preedit(text, cursor) => {
+                let composition = WString::from_utf8(&text);
+                // The cursor is given as UTF-8 byte offsets into the composition.
+                let cursor = cursor.and_then(|(from, to)| {
+                    let from = WString::from_utf8(text.get(..from)?).len();
+                    let to = WString::from_utf8(text.get(..to)?).len();
+                    Some((from, to))
+                });
+                self.set_ime_composition(&composition, cursor, context);
+            }
+            ImeEvent::Commit(text) => {
+                self.remove_ime_composition(context);
+                self.ime_commit(&WString::from_utf8(&text), context);
+            }
+        }
+    }
+
+    /// Replace the in-progress IME composition with the given text.
+    ///
+    /// A composition is started at the current selection if there is none
+    /// yet. An empty text ends the composition without inserting anything.
+    /// `cursor` is the selection within the composition text.
+    fn set_ime_composition(
+        self,
+        text: &WStr,
+        cursor: Option<(usize, usize)>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        let (start, end) = match &write.ime_composition {
+            Some(composition) => (composition.start, composition.end),
+            None if text.is_empty() => return,
+            None => {
+                let Some(selection) = write.selection else {
+                    return;
+                };
+                (selection.start(), selection.end())
+            }
+        };
+
+        write.ime_composition = if text.is_empty() {
+            None
+        } else {
+            let (from, to) = cursor.unwrap_or((text.len(), text.len()));
+            let selection =
+                TextSelection::for_range(start + from.min(text.len()), start + to.min(text.len()));
+            Some(ImeComposition {
+                start,
+                end,
+                text: text.to_owned(),
+                selection,
+                displayed_text: WString::new(),
+            })
+        };
+        drop(write);
+        self.relayout(context);
+    }
+
+    /// Forget about the composition, restoring the selection it replaced,
+    /// and return its text.
+    fn remove_ime_composition(self, context: &mut UpdateContext<'_, 'gc>) -> Option<WString> {
+        let mut write = self.0.write(context.gc_context);
+        let composition = write.ime_composition.take()?;
+        write.selection = Some(TextSelection::for_range(composition.start, composition.end));
+        drop(write);
+        self.relayout(context);
+        Some(composition.text)
+    }
+
+    /// Cancel the in-progress IME composition, discarding its text.
+    pub fn abandon_ime_composition(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.remove_ime_composition(context);
+    }
+
+    /// Finish the in-progress IME composition, inserting its current text.
+    pub fn commit_ime_composition(self, context: &mut UpdateContext<'_, 'gc>) {
+        if let Some(text) = self.remove_ime_composition(context) {
+            self.ime_commit(&text, context);
+        }
+    }
+
+    /// Set the selection within the in-progress IME composition.
+    pub fn set_ime_composition_selection(self, from: usize, to: usize, gc_context: &Mutation<'gc>) {
+        let mut write = self.0.write(gc_context);
+        if let Some(composition) = &mut write.ime_composition {
+            let len = composition.text.len();
+            let start = composition.start;
+            composition.selection =
+                TextSelection::for_range(start + from.min(len), start + to.min(len));
+            drop(write);
+            self.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// Insert text committed by the IME as if it was typed by the user,
+    /// and notify the movie about the finished composition.
+    fn ime_commit(self, text: &WStr, context: &mut UpdateContext<'_, 'gc>) {
+        if text.is_empty() {
+            return;
+        }
+
+        for character in text.chars().flatten() {
+            self.text_input(character, context);
+        }
+
+        let text = AvmString::new(context.gc_context, text);
+        if self.movie().is_action_script_3() {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            if let Some(ime) = Avm2::ime_object(&mut activation) {
+                let ime_evt = Avm2EventObject::ime_event(&mut activation, "imeComposition", text);
+                Avm2::dispatch_event(&mut activation.context, ime_evt, ime);
+            }
+        } else {
+            let mut activation = Avm1Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[IME Composition]"),
+                self.into(),
+            );
+            let system = activation
+                .context
+                .avm1
+                .global_object()
+                .get("System", &mut activation);
+            if let Ok(Avm1Value::Object(system)) = system {
+                if let Ok(Avm1Value::Object(ime)) = system.get("IME", &mut activation) {
+                    let _ = ime.call_method(
+                        "broadcastMessage".into(),
+                        &["onIMEComposition".into(), text.into()],
+                        &mut activation,
+                        ExecutionReason::Special,
+                    );
+                }
+            }
+        }
+    }
+
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc>) {
         if let Avm1Value::Object(object) = self.object() {
             activation.context.avm1.broadcaster_functions().initialize(
@@ -1968,16 +2661,41 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.0.read().static_data.swf.clone()
     }
 
+    fn enter_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
+        for image in self.image_objects() {
+            image.enter_frame(context);
+        }
+
+        self.update_image_sizes(context);
+    }
+
     /// Construct objects placed on this frame.
     fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
         if self.movie().is_action_script_3() && matches!(self.object2(), Avm2Value::Null) {
             self.construct_as_avm2_object(context, (*self).into());
             self.on_construction_complete(context);
         }
+
+        for image in self.image_objects() {
+            image.construct_frame(context);
+        }
     }
 
-    fn run_frame_avm1(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame_avm1(&self, context: &mut UpdateContext<'_, 'gc>) {
+        // Embedded images run their own frames, but may have changed size.
+        self.update_image_sizes(context);
+    }
+
+    fn run_frame_scripts(self, context: &mut UpdateContext<'_, 'gc>) {
+        for image in self.image_objects() {
+            image.run_frame_scripts(context);
+        }
+    }
+
+    fn on_exit_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
+        for image in self.image_objects() {
+            image.on_exit_frame(context);
+        }
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -2006,6 +2724,11 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if !self.movie().is_action_script_3() {
             self.construct_as_avm1_object(context, run_frame);
         }
+
+        // Start loading any images embedded into the initial text.
+        if !self.0.read().text_spans.images().is_empty() {
+            self.relayout(context);
+        }
     }
 
     fn object(&self) -> Avm1Value<'gc> {
@@ -2176,6 +2899,22 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         context.transform_stack.pop();
 
+        // Embedded images are positioned relative to the text field itself,
+        // and only need to be scrolled along with the text.
+        context.transform_stack.push(&Transform {
+            matrix: Matrix::translate(
+                -edit_text.bounds.x_min - Twips::from_pixels(edit_text.hscroll),
+                -edit_text.bounds.y_min - scroll_offset,
+            ),
+            ..Default::default()
+        });
+        for image in &edit_text.images {
+            if let Some(display_object) = image.display_object {
+                display_object.render(context);
+            }
+        }
+        context.transform_stack.pop();
+
         context.commands.deactivate_mask();
         context.commands.draw_rect(
             Color::WHITE,
@@ -2229,6 +2968,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         focused: bool,
         other: Option<DisplayObject<'gc>>,
     ) {
+        if !focused {
+            self.abandon_ime_composition(context);
+        }
+
         let is_action_script_3 = self.movie().is_action_script_3();
         let mut text = self.0.write(context.gc_context);
         text.flags.set(EditTextFlag::HAS_FOCUS, focused);
@@ -2284,9 +3027,20 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         // We can't hold self as any link may end up modifying this object, so pull the info out
         let mut link_to_open = None;
 
+        // Clicking finishes the composition, so that the layout matches the text again.
+        self.commit_ime_composition(context);
+
+        // Clicking somewhere starts a new undo step.
+        self.0.write(context.gc_context).history.break_merging();
+
         if let Some(position) = self.screen_position_to_index(*context.mouse_position) {
-            self.0.write(context.gc_context).selection =
-                Some(TextSelection::for_position(position));
+            let click_count = self.register_click(position, context);
+            let (from, to) = match click_count {
+                2 if self.is_selectable() => self.find_word_at(position),
+                3 if self.is_selectable() => self.find_paragraph_at(position),
+                _ => (position, position),
+            };
+            self.0.write(context.gc_context).selection = Some(TextSelection::for_range(from, to));
 
             if let Some((span_index, _)) =
                 self.0.read().text_spans.resolve_position_as_span(position)
@@ -2395,6 +3149,8 @@ bitflags::bitflags! {
         const WAS_STATIC = 1 << 10;
         const BORDER = 1 << 11;
         const NO_SELECT = 1 << 12;
+        const CONDENSE_WHITE = 1 << 13;
+        const ALWAYS_SHOW_SELECTION = 1 << 14;
         const SWF_FLAGS = Self::READ_ONLY.bits() | Self::PASSWORD.bits() | Self::MULTILINE.bits() | Self::WORD_WRAP.bits() | Self::USE_OUTLINES.bits() |
                           Self::HTML.bits() | Self::WAS_STATIC.bits() | Self::BORDER.bits() | Self::NO_SELECT.bits();
     }
@@ -2419,6 +3175,170 @@ pub struct TextSelection {
     blink_epoch: DateTime<Utc>,
 }
 
+/// An image embedded into the text of an `EditText`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct EditTextImage<'gc> {
+    /// The `<img>` tag this image was created from.
+    #[collect(require_static)]
+    image: TextImage,
+
+    /// The display object the image is loaded into.
+    display_object: Option<DisplayObject<'gc>>,
+
+    /// The size of the image as of the last layout.
+    #[collect(require_static)]
+    size: Size<Twips>,
+
+    /// Where the image was placed by the last layout, relative to the text.
+    #[collect(require_static)]
+    bounds: BoxBounds<Twips>,
+}
+
+impl<'gc> EditTextImage<'gc> {
+    /// The bounds of the loaded content, before scaling it to the requested size.
+    fn natural_bounds(&self) -> Rectangle<Twips> {
+        self.display_object
+            .map(|display_object| display_object.bounds())
+            .filter(|bounds| bounds.is_valid())
+            .unwrap_or(Rectangle::ZERO)
+    }
+
+    /// The size this image takes up in the layout.
+    fn layout_size(&self) -> Size<Twips> {
+        let natural_bounds = self.natural_bounds();
+        let width = self
+            .image
+            .width
+            .map(Twips::from_pixels)
+            .unwrap_or_else(|| natural_bounds.width());
+        let height = self
+            .image
+            .height
+            .map(Twips::from_pixels)
+            .unwrap_or_else(|| natural_bounds.height());
+
+        Size::from((width, height))
+    }
+}
+
+/// The kind of an edit made by the user.
+///
+/// Consecutive edits of the same kind are undone together,
+/// so that e.g. a typed word is undone at once instead of letter by letter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum TextEditKind {
+    /// Typing characters at the caret.
+    Typing,
+
+    /// Deleting characters around the caret.
+    Deletion,
+
+    /// Any other edit, which is never merged with others.
+    Other,
+}
+
+/// The text and selection of an `EditText` before or after an edit.
+#[derive(Clone, Debug)]
+struct TextHistoryState {
+    text_spans: FormatSpans,
+    selection: Option<TextSelection>,
+}
+
+/// The undo and redo history of the edits made by the user.
+#[derive(Clone, Debug, Default)]
+struct TextHistory {
+    /// States to restore when undoing, the most recent last.
+    undo: Vec<TextHistoryState>,
+
+    /// States to restore when redoing, the most recent last.
+    redo: Vec<TextHistoryState>,
+
+    /// The kind of the last edit, if the next edit may be merged into it.
+    last_edit: Option<TextEditKind>,
+}
+
+impl TextHistory {
+    /// The maximum number of edits that can be undone.
+    const MAX_LENGTH: usize = 100;
+
+    /// Record an edit about to be made, unless it continues the last edit.
+    fn record(&mut self, kind: TextEditKind, state: impl FnOnce() -> TextHistoryState) {
+        self.redo.clear();
+
+        if kind != TextEditKind::Other && self.last_edit == Some(kind) {
+            return;
+        }
+
+        if self.undo.len() >= Self::MAX_LENGTH {
+            self.undo.remove(0);
+        }
+        self.undo.push(state());
+        self.last_edit = (kind != TextEditKind::Other).then_some(kind);
+    }
+
+    /// Make sure the next edit is recorded as a separate undo step.
+    fn break_merging(&mut self) {
+        self.last_edit = None;
+    }
+
+    fn clear(&mut self) {
+        *self = Default::default();
+    }
+}
+
+/// A mouse press on the text of an `EditText`.
+#[derive(Copy, Clone, Debug)]
+struct TextClick {
+    /// The time of the click on the player's clock, in milliseconds.
+    time: u64,
+    position: usize,
+
+    /// How many times in a row the text has been clicked at this position.
+    count: u8,
+}
+
+impl TextClick {
+    /// The maximum time between two clicks to be considered a multiple click.
+    const MULTI_CLICK_INTERVAL_MS: u64 = 500;
+}
+
+/// Text that is being composed using the input method editor.
+///
+/// The composition is displayed in place of the range [start, end) of the
+/// text, which it replaces once it is committed.
+#[derive(Clone, Debug)]
+struct ImeComposition {
+    start: usize,
+    end: usize,
+    text: WString,
+
+    /// The selection within the displayed text.
+    selection: TextSelection,
+
+    /// The displayed text of the field, including the composition.
+    displayed_text: WString,
+}
+
+impl ImeComposition {
+    /// Whether the given position of the displayed text is part of the composition.
+    fn contains(&self, pos: usize) -> bool {
+        pos >= self.start && pos < self.start + self.text.len()
+    }
+
+    /// The text spans as they are displayed while composing.
+    fn apply(&self, text_spans: &FormatSpans, is_password: bool) -> FormatSpans {
+        let mut text_spans = text_spans.clone();
+        text_spans.replace_text(self.start, self.end, &self.text, None);
+        if is_password {
+            text_spans.hide_text();
+        } else {
+            text_spans.clear_displayed_text();
+        }
+        text_spans
+    }
+}
+
 /// Information about the start and end y-coordinates of a given line of text
 #[derive(Copy, Clone, Debug)]
 pub struct LineData {
@@ -2705,6 +3625,30 @@ impl EditTextRestrict {
         }
     }
 
+    /// Remove the characters of `spans` that aren't allowed, changing
+    /// their case where that makes them allowed.
+    pub fn restrict_spans(&self, spans: &mut FormatSpans) {
+        let mut edits = Vec::new();
+        for (pos, character) in spans.text().char_indices() {
+            let Ok(character) = character else {
+                edits.push((pos, 1, None));
+                continue;
+            };
+            let len = character.len_utf16();
+            match self.to_allowed(character) {
+                Some(allowed) if allowed == character => {}
+                allowed => edits.push((pos, len, allowed)),
+            }
+        }
+
+        // Edit from the end, so that the positions of the remaining edits stay valid.
+        for (pos, len, allowed) in edits.into_iter().rev() {
+            let format = spans.get_text_format(pos, pos + len);
+            let replacement = allowed.map(WString::from_char).unwrap_or_default();
+            spans.replace_text(pos, pos + len, &replacement, Some(&format));
+        }
+    }
+
     pub fn filter_allowed(&self, text: &str) -> String {
         let mut filtered = String::with_capacity(text.len());
         for c in text.chars() {
@@ -182,7 +182,14 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext) {
-        if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
+        // Shapes sliced by their parent's `scale9Grid` aren't drawn where their bounds say.
+        let sliced = self
+            .parent()
+            .is_some_and(|parent| parent.scaling_grid().is_valid());
+        if !context.is_offscreen
+            && !sliced
+            && !self.world_bounds().intersects(&context.stage.view_bounds())
+        {
             // Off-screen; culled
             return;
         }
@@ -2806,7 +2806,11 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        self.0.read().drawing.render(context);
+        if let Some(slices) = self.scaling_grid_slices() {
+            slices.render(context, |context| self.0.read().drawing.render(context));
+        } else {
+            self.0.read().drawing.render(context);
+        }
         self.render_children(context);
     }
 
@@ -3571,12 +3575,16 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let id = reader.read_u16()?;
         let rect = reader.read_rectangle()?;
         let library = context.library.library_for_movie_mut(self.movie());
-        if let Some(character) = library.character_by_id(id) {
-            if let Character::MovieClip(clip) = character {
-                clip.set_scaling_grid(context.gc_context, rect);
-            } else {
-                tracing::warn!("DefineScalingGrid for invalid ID {}", id);
+        match library.character_by_id(id) {
+            Some(Character::MovieClip(clip)) => clip.set_scaling_grid(context.gc_context, rect),
+            Some(Character::Avm1Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect)
+            }
+            Some(Character::Avm2Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect)
             }
+            Some(_) => tracing::warn!("DefineScalingGrid for invalid ID {}", id),
+            None => {}
         }
         Ok(())
     }
@@ -0,0 +1,262 @@
+//! 9-slice scaling (`scale9Grid`)
+
+use crate::context::RenderContext;
+use crate::prelude::*;
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::transform::Transform;
+
+/// The pieces that content affected by a `scale9Grid` is drawn in.
+///
+/// The object's bounds are split into nine regions by the grid. When the object is scaled,
+/// the corners keep their original size, the edges only stretch along their length, and
+/// the center region takes up the remaining space.
+///
+/// Each region is drawn by rendering the content with the transform of that region,
+/// clipped to where the region ends up.
+pub struct ScalingGridSlices {
+    slices: Vec<Slice>,
+}
+
+struct Slice {
+    /// The transform from the original content into this region, in the object's local space.
+    matrix: Matrix,
+
+    /// The clipping rectangle of this region, in the object's local space.
+    clip: Matrix,
+}
+
+/// A single piece of one axis of the grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct AxisSlice {
+    /// The scale applied to content in this piece.
+    scale: f64,
+
+    /// The offset applied to content in this piece, after scaling.
+    offset: f64,
+
+    /// The start of this piece after slicing.
+    start: f64,
+
+    /// The end of this piece after slicing.
+    end: f64,
+}
+
+impl ScalingGridSlices {
+    /// Slices the content within `bounds` by `grid`, for an object with the given matrix.
+    ///
+    /// Returns `None` if the grid has no effect, such as when the object isn't scaled,
+    /// or when it's rotated or skewed (in which case Flash scales everything normally).
+    pub fn new(
+        grid: &Rectangle<Twips>,
+        bounds: &Rectangle<Twips>,
+        matrix: &Matrix,
+    ) -> Option<Self> {
+        if !grid.is_valid() || !bounds.is_valid() {
+            return None;
+        }
+        if matrix.b != 0.0 || matrix.c != 0.0 {
+            return None;
+        }
+        if matrix.a.abs() == 1.0 && matrix.d.abs() == 1.0 {
+            return None;
+        }
+
+        let columns = slice_axis(
+            bounds.x_min.to_pixels(),
+            grid.x_min.to_pixels(),
+            grid.x_max.to_pixels(),
+            bounds.x_max.to_pixels(),
+            f64::from(matrix.a.abs()),
+        )?;
+        let rows = slice_axis(
+            bounds.y_min.to_pixels(),
+            grid.y_min.to_pixels(),
+            grid.y_max.to_pixels(),
+            bounds.y_max.to_pixels(),
+            f64::from(matrix.d.abs()),
+        )?;
+
+        let mut slices = Vec::with_capacity(columns.len() * rows.len());
+        for row in &rows {
+            for column in &columns {
+                slices.push(Slice {
+                    matrix: Matrix {
+                        a: column.scale as f32,
+                        b: 0.0,
+                        c: 0.0,
+                        d: row.scale as f32,
+                        tx: Twips::from_pixels(column.offset),
+                        ty: Twips::from_pixels(row.offset),
+                    },
+                    clip: Matrix::create_box(
+                        (column.end - column.start) as f32,
+                        (row.end - row.start) as f32,
+                        0.0,
+                        Twips::from_pixels(column.start),
+                        Twips::from_pixels(row.start),
+                    ),
+                });
+            }
+        }
+
+        Some(Self { slices })
+    }
+
+    /// Renders content once for each region, using the current transform as the object's transform.
+    pub fn render<'gc>(
+        &self,
+        context: &mut RenderContext<'_, 'gc>,
+        mut draw: impl FnMut(&mut RenderContext<'_, 'gc>),
+    ) {
+        let transform = context.transform_stack.transform().matrix;
+        for slice in &self.slices {
+            let clip = transform * slice.clip;
+            context.commands.push_mask();
+            // The color doesn't matter, as this is a mask.
+            context.commands.draw_rect(Color::WHITE, clip);
+            context.commands.activate_mask();
+
+            context.transform_stack.push(&Transform {
+                matrix: slice.matrix,
+                color_transform: Default::default(),
+            });
+            draw(context);
+            context.transform_stack.pop();
+
+            context.commands.deactivate_mask();
+            context.commands.draw_rect(Color::WHITE, clip);
+            context.commands.pop_mask();
+        }
+    }
+}
+
+/// Whether a child of an object with a `scale9Grid` is sliced along with the object's own drawing.
+///
+/// Only shapes are sliced; anything else (nested clips, text, bitmaps) is scaled normally.
+pub fn is_sliced_child(child: DisplayObject<'_>) -> bool {
+    matches!(
+        child,
+        DisplayObject::Graphic(_) | DisplayObject::MorphShape(_)
+    )
+}
+
+/// Splits one axis of the bounds `min..max` at `grid_min` and `grid_max`,
+/// for an object with the given (positive) scale along this axis.
+///
+/// The outer pieces keep their size on screen, unless there isn't enough room for them,
+/// in which case they're shrunk proportionally and the middle piece disappears.
+/// Empty pieces are skipped.
+fn slice_axis(
+    min: f64,
+    grid_min: f64,
+    grid_max: f64,
+    max: f64,
+    scale: f64,
+) -> Option<Vec<AxisSlice>> {
+    if !(scale.is_finite() && scale > 0.0) {
+        return None;
+    }
+
+    let grid_min = grid_min.clamp(min, max);
+    let grid_max = grid_max.clamp(grid_min, max);
+
+    // Sizes on screen, relative to the object's parent.
+    let available = (max - min) * scale;
+    let (start, end) = (grid_min - min, max - grid_max);
+    let (start, end) = if start + end <= available {
+        (start, end)
+    } else {
+        let shrink = available / (start + end);
+        (start * shrink, end * shrink)
+    };
+    let middle = available - start - end;
+
+    let mut slices = Vec::with_capacity(3);
+    let mut position = min;
+    let pieces = [
+        (min, grid_min, start),
+        (grid_min, grid_max, middle),
+        (grid_max, max, end),
+    ];
+    for (source_start, source_end, size) in pieces {
+        let size = size / scale;
+        let slice_start = position;
+        position += size;
+        if source_end <= source_start || size <= 0.0 {
+            continue;
+        }
+
+        let slice_scale = size / (source_end - source_start);
+        slices.push(AxisSlice {
+            scale: slice_scale,
+            offset: slice_start - source_start * slice_scale,
+            start: slice_start,
+            end: position,
+        });
+    }
+
+    // The outer pieces are clipped generously, so that anti-aliasing and strokes at the
+    // very edges of the content aren't cut off.
+    let padding = max - min;
+    if let Some(first) = slices.first_mut() {
+        first.start -= padding;
+    }
+    if let Some(last) = slices.last_mut() {
+        last.end += padding;
+    }
+
+    Some(slices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corners_keep_their_size() {
+        // 100px wide, with 10px edges, scaled 2x.
+        let slices = slice_axis(0.0, 10.0, 90.0, 100.0, 2.0).unwrap();
+        assert_eq!(slices.len(), 3);
+
+        // On screen, the left edge is 10px wide, so it's 5px in local space.
+        assert_eq!(slices[0].scale, 0.5);
+        assert_eq!(slices[0].offset, 0.0);
+        assert_eq!(slices[0].end, 5.0);
+
+        // The middle covers the remaining 180px on screen.
+        assert_eq!(slices[1].start, 5.0);
+        assert_eq!(slices[1].end, 95.0);
+        assert_eq!(slices[1].scale, 90.0 / 80.0);
+        assert_eq!(slices[1].offset, 5.0 - 10.0 * 90.0 / 80.0);
+
+        // The right edge still ends at the same place.
+        assert_eq!(slices[2].scale, 0.5);
+        assert_eq!(slices[2].offset, 95.0 - 90.0 * 0.5);
+        assert_eq!(slices[2].start, 95.0);
+    }
+
+    #[test]
+    fn edges_shrink_when_too_small() {
+        // 100px wide, with 20px and 30px edges, scaled down to 25px.
+        let slices = slice_axis(0.0, 20.0, 70.0, 100.0, 0.25).unwrap();
+        assert_eq!(slices.len(), 2);
+
+        // 10px and 15px on screen, 40px and 60px in local space.
+        assert_eq!(slices[0].scale, 2.0);
+        assert_eq!(slices[0].end, 40.0);
+        assert_eq!(slices[1].start, 40.0);
+        assert_eq!(slices[1].scale, 2.0);
+        assert_eq!(slices[1].offset, 100.0 - 100.0 * 2.0);
+    }
+
+    #[test]
+    fn grid_outside_bounds() {
+        let slices = slice_axis(0.0, -10.0, 50.0, 100.0, 2.0).unwrap();
+        assert_eq!(slices.len(), 2);
+        assert_eq!(slices[0].start, -100.0);
+        assert_eq!(slices[0].end, 75.0);
+        assert_eq!(slices[1].scale, 0.5);
+
+        assert_eq!(slice_axis(0.0, 10.0, 90.0, 100.0, 0.0), None);
+    }
+}
@@ -20,6 +20,7 @@ use crate::display_object::{
 };
 use crate::events::{ClipEvent, ClipEventResult};
 use crate::prelude::*;
+use crate::redraw_regions::RedrawRegions;
 use crate::string::{FromWStr, WStr};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -33,6 +34,7 @@ use std::cell::{Ref, RefMut};
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 use std::sync::Arc;
+use swf::ColorTransform;
 
 /// The Stage is the root of the display object hierarchy. It contains all AVM1
 /// levels as well as AVM2 movies.
@@ -151,6 +153,10 @@ pub struct StageData<'gc> {
     /// identity matrix unless explicitly set from ActionScript)
     #[collect(require_static)]
     viewport_matrix: Matrix,
+
+    /// The areas of the stage that changed between frames, tracked for `showRedrawRegions`.
+    #[collect(require_static)]
+    redraw_regions: RedrawRegions,
 }
 
 impl<'gc> Stage<'gc> {
@@ -188,6 +194,7 @@ impl<'gc> Stage<'gc> {
                 stage3ds: vec![],
                 movie,
                 viewport_matrix: Matrix::IDENTITY,
+                redraw_regions: Default::default(),
             },
         ));
         stage.set_is_root(gc_context, true);
@@ -202,6 +209,38 @@ impl<'gc> Stage<'gc> {
         self.0.write(gc_context).background_color = color;
     }
 
+    /// The color that redraw regions are outlined with, if they are shown.
+    pub fn redraw_regions_color(self) -> Option<Color> {
+        self.0.read().redraw_regions.color()
+    }
+
+    /// Starts or stops outlining the areas of the stage that are redrawn each frame.
+    ///
+    /// Used by `flash.profiler.showRedrawRegions`.
+    pub fn set_redraw_regions_color(self, gc_context: &Mutation<'gc>, color: Option<Color>) {
+        self.0.write(gc_context).redraw_regions.set_color(color);
+    }
+
+    /// Records how a display object was drawn this frame, if redraw regions are tracked.
+    pub fn record_redraw(
+        self,
+        gc_context: &Mutation<'gc>,
+        object: DisplayObject<'gc>,
+        bounds: Rectangle<Twips>,
+        color_transform: ColorTransform,
+        invalidated: bool,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if write.redraw_regions.enabled() {
+            write.redraw_regions.record(
+                object.as_ptr() as usize,
+                bounds,
+                color_transform,
+                invalidated,
+            );
+        }
+    }
+
     pub fn inverse_view_matrix(self) -> Matrix {
         self.0
             .read()
@@ -846,6 +885,16 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
             self.draw_letterbox(context);
         }
 
+        if !context.is_offscreen {
+            let mut write = self.0.write(context.gc_context);
+            if write.redraw_regions.enabled() {
+                let regions = write.redraw_regions.finish_frame();
+                write
+                    .redraw_regions
+                    .draw_outlines(&mut context.commands, &regions);
+            }
+        }
+
         context.transform_stack.pop();
     }
 
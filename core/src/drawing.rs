@@ -4,6 +4,7 @@ use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::shape_utils::{
     cubic_curve_bounds, quadratic_curve_bounds, DistilledShape, DrawCommand, DrawPath, FillRule,
+    ShaderFill,
 };
 use std::cell::{Cell, RefCell};
 use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
@@ -90,6 +91,9 @@ impl Drawing {
                     winding_rule,
                 } => {
                     this.set_winding_rule(winding_rule);
+                    let ruffle_render::shape_utils::FillStyle::Swf(style) = style else {
+                        unreachable!("SWF shapes only contain SWF fill styles");
+                    };
                     this.set_fill_style(Some(style.clone()));
 
                     for command in commands {
@@ -126,6 +130,15 @@ impl Drawing {
     }
 
     pub fn set_fill_style(&mut self, style: Option<FillStyle>) {
+        self.set_fill(style.map(DrawingFillStyle::Swf));
+    }
+
+    /// Starts a new fill that's drawn by a Pixel Bender shader.
+    pub fn set_shader_fill(&mut self, fill: ShaderFill) {
+        self.set_fill(Some(DrawingFillStyle::Shader(fill)));
+    }
+
+    fn set_fill(&mut self, style: Option<DrawingFillStyle>) {
         self.close_path();
         if let Some(existing) = self.current_fill.take() {
             self.paths.push(DrawingPath::Fill(existing));
@@ -239,70 +252,109 @@ impl Drawing {
         id
     }
 
-    pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> ShapeHandle {
-        if self.dirty.get() || self.render_handle.borrow().is_none() {
-            self.dirty.set(false);
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                            winding_rule: FillRule::EvenOdd,
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
-                    }
+    /// The bitmaps used by the fills and strokes of this drawing, including the current ones.
+    pub fn used_bitmaps(&self) -> impl Iterator<Item = &BitmapHandle> {
+        let fills = self
+            .paths
+            .iter()
+            .filter_map(|path| match path {
+                DrawingPath::Fill(fill) => Some(&fill.style),
+                DrawingPath::Line(_) => None,
+            })
+            .chain(self.current_fill.as_ref().map(|fill| &fill.style))
+            .filter_map(|style| match style {
+                DrawingFillStyle::Swf(style) => Some(style),
+                DrawingFillStyle::Shader(_) => None,
+            });
+        let lines = self
+            .paths
+            .iter()
+            .filter_map(|path| match path {
+                DrawingPath::Fill(_) => None,
+                DrawingPath::Line(line) => Some(line),
+            })
+            .chain(&self.current_line)
+            .chain(&self.pending_lines)
+            .map(|line| line.style.fill_style());
+
+        fills
+            .chain(lines)
+            .filter_map(|style| match style {
+                FillStyle::Bitmap { id, .. } => self.bitmaps.get(*id as usize),
+                _ => None,
+            })
+            .map(|bitmap| &bitmap.handle)
+    }
+
+    /// The fills and strokes of this drawing, in the order that they're drawn.
+    pub fn paths(&self) -> Vec<DrawPath<'_>> {
+        let mut paths = Vec::with_capacity(self.paths.len());
+
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: fill.style.as_fill_style(),
+                        commands: fill.commands.to_owned(),
+                        winding_rule: FillRule::EvenOdd,
+                    });
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
                 }
             }
+        }
 
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                    winding_rule: FillRule::EvenOdd,
-                })
-            }
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: fill.style.as_fill_style(),
+                commands: fill.commands.to_owned(),
+                winding_rule: FillRule::EvenOdd,
+            })
+        }
 
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
 
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
 
+        paths
+    }
+
+    pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> ShapeHandle {
+        if self.dirty.get() || self.render_handle.borrow().is_none() {
+            self.dirty.set(false);
             let shape = DistilledShape {
-                paths,
+                paths: self.paths(),
                 shape_bounds: self.shape_bounds.clone(),
                 edge_bounds: self.edge_bounds.clone(),
                 id: 0,
@@ -431,10 +483,25 @@ impl BitmapSource for Drawing {
 
 #[derive(Debug, Clone)]
 struct DrawingFill {
-    style: FillStyle,
+    style: DrawingFillStyle,
     commands: Vec<DrawCommand>,
 }
 
+#[derive(Debug, Clone)]
+enum DrawingFillStyle {
+    Swf(FillStyle),
+    Shader(ShaderFill),
+}
+
+impl DrawingFillStyle {
+    fn as_fill_style(&self) -> ruffle_render::shape_utils::FillStyle<'_> {
+        match self {
+            DrawingFillStyle::Swf(style) => ruffle_render::shape_utils::FillStyle::Swf(style),
+            DrawingFillStyle::Shader(fill) => ruffle_render::shape_utils::FillStyle::Shader(fill),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct DrawingLine {
     style: LineStyle,
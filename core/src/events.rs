@@ -2,7 +2,7 @@ use crate::display_object::InteractiveObject;
 use serde::Deserialize;
 use swf::ClipEventFlag;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum PlayerEvent {
     KeyDown {
         key_code: KeyCode,
@@ -36,6 +36,21 @@ pub enum PlayerEvent {
     TextControl {
         code: TextControlCode,
     },
+    Ime(ImeEvent),
+}
+
+/// An event sent by the input method editor (IME) of the platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// The composition text has changed.
+    ///
+    /// The optional range is the cursor (or selection) inside of the
+    /// composition text, as UTF-8 byte indices. An empty composition text
+    /// means that the composition has been cleared.
+    Preedit(String, Option<(usize, usize)>),
+
+    /// The composition has finished and the given text should be inserted.
+    Commit(String),
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -362,6 +377,8 @@ pub enum TextControlCode {
     Enter,
     Delete,
     DeleteWord,
+    Undo,
+    Redo,
 }
 
 impl TextControlCode {
@@ -376,6 +393,8 @@ impl TextControlCode {
                 | Self::BackspaceWord
                 | Self::Delete
                 | Self::DeleteWord
+                | Self::Undo
+                | Self::Redo
         )
     }
 }
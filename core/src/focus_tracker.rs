@@ -1,23 +1,53 @@
 use crate::avm1::Avm1;
 use crate::avm1::Value;
 use crate::context::UpdateContext;
+use crate::display_object::EditText;
 pub use crate::display_object::{
     DisplayObject, TDisplayObject, TDisplayObjectContainer, TextSelection,
 };
-use gc_arena::lock::GcLock;
-use gc_arena::{Collect, Mutation};
+use gc_arena::barrier::unlock;
+use gc_arena::lock::Lock;
+use gc_arena::{Collect, Gc, Mutation};
+use std::cell::Cell;
 
 #[derive(Clone, Copy, Collect)]
 #[collect(no_drop)]
-pub struct FocusTracker<'gc>(GcLock<'gc, Option<DisplayObject<'gc>>>);
+pub struct FocusTracker<'gc>(Gc<'gc, FocusTrackerData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct FocusTrackerData<'gc> {
+    focus: Lock<Option<DisplayObject<'gc>>>,
+
+    /// Whether the movie enabled or disabled the IME itself, in which case focus changes don't
+    /// toggle it anymore.
+    ime_set_by_movie: Cell<bool>,
+}
 
 impl<'gc> FocusTracker<'gc> {
     pub fn new(mc: &Mutation<'gc>) -> Self {
-        Self(GcLock::new(mc, None.into()))
+        Self(Gc::new(
+            mc,
+            FocusTrackerData {
+                focus: Lock::new(None),
+                ime_set_by_movie: Cell::new(false),
+            },
+        ))
     }
 
     pub fn get(&self) -> Option<DisplayObject<'gc>> {
-        self.0.get()
+        self.0.focus.get()
+    }
+
+    /// The focused text field, which IME compositions are applied to, if any.
+    pub fn get_edit_text(&self) -> Option<EditText<'gc>> {
+        self.get().and_then(|o| o.as_edit_text())
+    }
+
+    /// Enables or disables the IME on behalf of the movie, through `IME.enabled`.
+    pub fn set_ime_enabled(&self, enabled: bool, context: &mut UpdateContext<'_, 'gc>) {
+        self.0.ime_set_by_movie.set(true);
+        context.ui.set_ime_enabled(enabled);
     }
 
     pub fn set(
@@ -25,11 +55,11 @@ impl<'gc> FocusTracker<'gc> {
         focused_element: Option<DisplayObject<'gc>>,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        let old = self.0.get();
+        let old = self.get();
 
         // Check if the focused element changed.
         if old.map(|o| o.as_ptr()) != focused_element.map(|o| o.as_ptr()) {
-            self.0.set(context.gc(), focused_element);
+            unlock!(Gc::write(context.gc(), self.0), FocusTrackerData, focus).set(focused_element);
 
             if let Some(old) = old {
                 old.on_focus_changed(context, false, focused_element);
@@ -55,6 +85,7 @@ impl<'gc> FocusTracker<'gc> {
         }
 
         // This applies even if the focused element hasn't changed.
+        let mut is_editing_text = false;
         if let Some(text_field) = focused_element.and_then(|e| e.as_edit_text()) {
             if text_field.is_editable() {
                 if !text_field.movie().is_action_script_3() {
@@ -63,7 +94,11 @@ impl<'gc> FocusTracker<'gc> {
                         .set_selection(Some(TextSelection::for_range(0, length)), context.gc());
                 }
                 context.ui.open_virtual_keyboard();
+                is_editing_text = true;
             }
         }
+        if !self.0.ime_set_by_movie.get() {
+            context.ui.set_ime_enabled(is_editing_text);
+        }
     }
 }
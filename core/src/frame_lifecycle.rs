@@ -11,6 +11,7 @@
 //! runs in one phase, with timeline operations executing with all phases
 //! inline in the order that clips were originally created.
 
+use crate::avm2::profiler::TraceCategory;
 use crate::avm2::Avm2;
 use crate::avm2_stub_method_context;
 use crate::context::UpdateContext;
@@ -63,6 +64,33 @@ pub enum FramePhase {
     Idle,
 }
 
+impl FramePhase {
+    fn name(self) -> &'static str {
+        match self {
+            FramePhase::Enter => "Enter Frame",
+            FramePhase::Construct => "Construct Frame",
+            FramePhase::FrameScripts => "Frame Scripts",
+            FramePhase::Exit => "Exit Frame",
+            FramePhase::Idle => "Idle",
+        }
+    }
+}
+
+/// Switches to `phase`, starting a span for it if AVM2 profiling is enabled.
+fn begin_phase(context: &mut UpdateContext<'_, '_>, phase: FramePhase) {
+    *context.frame_phase = phase;
+    if let Some(profiler) = context.avm2.profiler_mut() {
+        profiler.begin(phase.name(), TraceCategory::FramePhase);
+    }
+}
+
+/// Ends the span started by `begin_phase`.
+fn end_phase(context: &mut UpdateContext<'_, '_>) {
+    if let Some(profiler) = context.avm2.profiler_mut() {
+        profiler.end();
+    }
+}
+
 /// Run one frame according to AVM2 frame order.
 /// NOTE: The `each_orphan_movie` calls are in really odd places,
 /// but this is needed to match Flash Player's output. There may
@@ -76,30 +104,34 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_, '_>) {
         return;
     }
 
-    *context.frame_phase = FramePhase::Enter;
+    begin_phase(context, FramePhase::Enter);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.enter_frame(context);
     });
     stage.enter_frame(context);
+    end_phase(context);
 
-    *context.frame_phase = FramePhase::Construct;
+    begin_phase(context, FramePhase::Construct);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
     stage.frame_constructed(context);
+    end_phase(context);
 
-    *context.frame_phase = FramePhase::FrameScripts;
+    begin_phase(context, FramePhase::FrameScripts);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.run_frame_scripts(context);
     });
     stage.run_frame_scripts(context);
+    end_phase(context);
 
-    *context.frame_phase = FramePhase::Exit;
+    begin_phase(context, FramePhase::Exit);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.on_exit_frame(context);
     });
     stage.exit_frame(context);
+    end_phase(context);
 
     // We cannot easily remove dead `GcWeak` instances from the orphan list
     // inside `each_orphan_movie`, since the callback may modify the orphan list.
@@ -151,14 +183,15 @@ pub fn run_inner_goto_frame<'gc>(
 
     // Note - we do *not* call `enter_frame` or dispatch an `enterFrame` event
 
-    *context.frame_phase = FramePhase::Construct;
+    begin_phase(context, FramePhase::Construct);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
     stage.frame_constructed(context);
+    end_phase(context);
 
-    *context.frame_phase = FramePhase::FrameScripts;
+    begin_phase(context, FramePhase::FrameScripts);
     stage.run_frame_scripts(context);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.run_frame_scripts(context);
@@ -167,12 +200,14 @@ pub fn run_inner_goto_frame<'gc>(
     for child in removed_frame_scripts {
         child.run_frame_scripts(context);
     }
+    end_phase(context);
 
-    *context.frame_phase = FramePhase::Exit;
+    begin_phase(context, FramePhase::Exit);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.on_exit_frame(context);
     });
     stage.exit_frame(context);
+    end_phase(context);
 
     // We cannot easily remove dead `GcWeak` instances from the orphan list
     // inside `each_orphan_movie`, since the callback may modify the orphan list.
@@ -7,8 +7,9 @@ mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
+pub use dimensions::Size;
 pub use layout::{LayoutBox, LayoutContent, LayoutMetrics};
-pub use text_format::{FormatSpans, TextFormat, TextSpan};
+pub use text_format::{FormatSpans, TextFormat, TextImage, TextImageAlign, TextSpan};
 
 #[cfg(test)]
 mod test;
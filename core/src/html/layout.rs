@@ -4,7 +4,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font, FontType};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, TextFormat, TextImage, TextImageAlign, TextSpan};
 use crate::string::{utils as string_utils, WStr};
 use crate::tag_utils::SwfMovie;
 use crate::DefaultFont;
@@ -99,10 +99,29 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The images embedded into the text being laid out.
+    images: &'a [TextImage],
+
+    /// The size of each image in `images`.
+    image_sizes: &'a [Size<Twips>],
+
+    /// The bounds of each image placed so far, in the order of `images`.
+    image_bounds: Vec<BoxBounds<Twips>>,
+
+    /// The areas occupied by floating images, including the space around
+    /// them, and which side of the field they float to.
+    floats: Vec<(BoxBounds<Twips>, TextImageAlign)>,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
-    fn new(movie: Arc<SwfMovie>, max_bounds: Twips, text: &'a WStr) -> Self {
+    fn new(
+        movie: Arc<SwfMovie>,
+        max_bounds: Twips,
+        text: &'a WStr,
+        images: &'a [TextImage],
+        image_sizes: &'a [Size<Twips>],
+    ) -> Self {
         Self {
             movie,
             cursor: Default::default(),
@@ -116,6 +135,10 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             current_line: 0,
             current_line_span: Default::default(),
             max_bounds,
+            images,
+            image_sizes,
+            image_bounds: Vec::new(),
+            floats: Vec::new(),
         }
     }
 
@@ -279,9 +302,11 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_bounds = line_bounds.unwrap_or_default();
 
+        let (float_left, float_right) = self.float_margins();
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line) + float_left;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + float_right;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_bounds.width();
@@ -658,19 +683,91 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
-        let offset = Self::left_alignment_offset(current_span, self.is_first_line);
+        let (float_left, float_right) = self.float_margins();
+        let width =
+            self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin) - float_right;
+        let offset = Self::left_alignment_offset(current_span, self.is_first_line) + float_left;
 
         (width, offset + self.cursor.x())
     }
 
-    /// Destroy the layout context, returning the newly constructed layout list.
+    /// Calculate how far floating images push the current line in from the
+    /// left and right edges of the field.
+    fn float_margins(&self) -> (Twips, Twips) {
+        let line_y = self.cursor.y();
+        let mut left = Twips::ZERO;
+        let mut right = Twips::ZERO;
+
+        for (bounds, align) in &self.floats {
+            if bounds.offset_y() > line_y || bounds.extent_y() <= line_y {
+                continue;
+            }
+
+            match align {
+                TextImageAlign::Left => left = max(left, bounds.extent_x()),
+                TextImageAlign::Right => right = max(right, self.max_bounds - bounds.offset_x()),
+            }
+        }
+
+        (left, right)
+    }
+
+    /// Place all images anchored at or before the given text position that
+    /// have not been placed yet.
+    ///
+    /// Images are placed at the top of the current line, against the left or
+    /// right edge of the field, or against any image already floating there.
+    fn append_images(&mut self, position: usize) {
+        let images = self.images;
+        while let Some(image) = images.get(self.image_bounds.len()) {
+            if image.position > position {
+                break;
+            }
+
+            let size = self
+                .image_sizes
+                .get(self.image_bounds.len())
+                .copied()
+                .unwrap_or_default();
+            let hspace = Twips::from_pixels(image.hspace);
+            let vspace = Twips::from_pixels(image.vspace);
+            let float_size = Size::from((size.width() + hspace * 2, size.height() + vspace * 2));
+
+            let (float_left, float_right) = self.float_margins();
+            let x = match image.align {
+                TextImageAlign::Left => float_left,
+                TextImageAlign::Right => self.max_bounds - float_right - float_size.width(),
+            };
+            let float_bounds =
+                BoxBounds::from_position_and_size(Position::from((x, self.cursor.y())), float_size);
+            let image_bounds = BoxBounds::from_position_and_size(
+                Position::from((x + hspace, self.cursor.y() + vspace)),
+                size,
+            );
+
+            // Images that haven't loaded yet don't push any text away.
+            if size.width() > Twips::ZERO && size.height() > Twips::ZERO {
+                self.floats.push((float_bounds, image.align));
+
+                if let Some(eb) = &mut self.exterior_bounds {
+                    *eb += float_bounds;
+                } else {
+                    self.exterior_bounds = Some(float_bounds);
+                }
+            }
+
+            self.image_bounds.push(image_bounds);
+        }
+    }
+
+    /// Destroy the layout context, returning the newly constructed layout
+    /// list and the bounds of all placed images.
     fn end_layout(
         mut self,
         context: &mut UpdateContext<'_, 'gc>,
         fs: &'a FormatSpans,
         font_type: FontType,
-    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
+    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>, Vec<BoxBounds<Twips>>) {
         self.fixup_line(
             context,
             !self.has_line_break,
@@ -680,7 +777,14 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             font_type,
         );
 
-        (self.boxes, self.exterior_bounds.unwrap_or_default())
+        // Images anchored past the last laid-out text go on the last line.
+        self.append_images(usize::MAX);
+
+        (
+            self.boxes,
+            self.exterior_bounds.unwrap_or_default(),
+            self.image_bounds,
+        )
     }
 
     fn is_start_of_line(&self) -> bool {
@@ -812,6 +916,10 @@ impl<'gc> LayoutBox<'gc> {
     ///
     /// The returned bounds will include both the text bounds itself, as well
     /// as left and right margins on any of the lines.
+    ///
+    /// `image_sizes` holds the size of each of the images embedded into the
+    /// text spans. The bounds of each image, as placed by the layout, are
+    /// returned alongside the layout boxes.
     pub fn lower_from_text_spans(
         fs: &FormatSpans,
         context: &mut UpdateContext<'_, 'gc>,
@@ -819,8 +927,10 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         font_type: FontType,
-    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
-        let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
+        image_sizes: &[Size<Twips>],
+    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>, Vec<BoxBounds<Twips>>) {
+        let mut layout_context =
+            LayoutContext::new(movie, bounds, fs.displayed_text(), fs.images(), image_sizes);
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
             if let Some(font) = layout_context.resolve_font(context, span, font_type) {
@@ -849,6 +959,8 @@ impl<'gc> LayoutBox<'gc> {
 
                     let start = span_start + slice_start;
 
+                    layout_context.append_images(start + text.len());
+
                     let mut last_breakpoint = 0;
 
                     if is_word_wrap {
@@ -1,7 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, TextFormat, TextImageAlign, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
 
@@ -868,3 +868,72 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_from_html_condense_white() {
+    let html = WString::from_utf8("  hello \t\n  <b> world</b>  ");
+
+    let fs = FormatSpans::from_html(&html, TextFormat::default(), true, true, 8);
+    assert_eq!(WStr::from_units(b"hello world "), fs.text());
+
+    let html = WString::from_utf8("hello  <b>  world</b>");
+    let fs = FormatSpans::from_html(&html, TextFormat::default(), true, false, 8);
+    assert_eq!(WStr::from_units(b"hello    world"), fs.text());
+}
+
+#[test]
+fn formatspans_replace_with_spans() {
+    let tf1 = TextFormat {
+        font: Some(WString::from_utf8("same!")),
+        ..Default::default()
+    };
+
+    let tf2 = TextFormat {
+        font: Some(WString::from_utf8("difference!")),
+        ..Default::default()
+    };
+
+    let mut fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdefghi"),
+        &[TextSpan::with_length_and_format(9, &tf1)],
+    );
+
+    let with = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"123"),
+        &[TextSpan::with_length_and_format(3, &tf2)],
+    );
+
+    fs.replace_with_spans(3, 6, &with);
+
+    assert_eq!(WStr::from_units(b"abc123ghi"), fs.text());
+
+    assert_eq!((0, 1), fs.get_span_boundaries(0, 3));
+    assert_eq!((1, 2), fs.get_span_boundaries(3, 6));
+    assert_eq!((2, 3), fs.get_span_boundaries(6, 9));
+}
+
+#[test]
+fn formatspans_from_html_img() {
+    let html = WString::from_utf8(
+        "ab<img src='smile.png' id='smile' width='16' align='right' hspace='2'/>cd<img src='Icon'>",
+    );
+
+    let fs = FormatSpans::from_html(&html, TextFormat::default(), true, false, 8);
+    assert_eq!(WStr::from_units(b"abcd"), fs.text());
+
+    let images = fs.images();
+    assert_eq!(2, images.len());
+
+    assert_eq!(2, images[0].position);
+    assert_eq!(WStr::from_units(b"smile.png"), &images[0].src);
+    assert_eq!(Some(WString::from_utf8("smile")), images[0].id);
+    assert_eq!(Some(16.0), images[0].width);
+    assert_eq!(None, images[0].height);
+    assert_eq!(TextImageAlign::Right, images[0].align);
+    assert_eq!(2.0, images[0].hspace);
+    assert_eq!(8.0, images[0].vspace);
+
+    assert_eq!(4, images[1].position);
+    assert_eq!(WStr::from_units(b"Icon"), &images[1].src);
+    assert_eq!(TextImageAlign::Left, images[1].align);
+}
@@ -15,6 +15,37 @@ use std::sync::Arc;
 const ANY_NEWLINE: &[u8] = &[b'\n', b'\r'];
 const HTML_NEWLINE: u8 = b'\n';
 
+/// Whitespace characters collapsed by `TextField.condenseWhite`.
+const CONDENSED_WHITESPACE: &[u8] = &[b' ', b'\t', b'\n', b'\r'];
+
+/// Collapse every run of whitespace in `src` into a single space.
+///
+/// `preceding` is the text that has already been emitted; whitespace at the
+/// beginning of the text or right after other whitespace or a line break is
+/// removed entirely.
+fn condense_whitespace(src: &WStr, preceding: &WStr) -> WString {
+    let is_white = |ch: u16| CONDENSED_WHITESPACE.iter().any(|&w| u16::from(w) == ch);
+
+    let mut result_str = WString::with_capacity(src.len(), src.is_wide());
+    let mut last_was_white = preceding
+        .len()
+        .checked_sub(1)
+        .and_then(|i| preceding.get(i))
+        .map_or(true, is_white);
+    for ch in src.iter() {
+        if is_white(ch) {
+            if !last_was_white {
+                result_str.push_byte(b' ');
+            }
+            last_was_white = true;
+        } else {
+            result_str.push(ch);
+            last_was_white = false;
+        }
+    }
+    result_str
+}
+
 /// Replace HTML entities with their equivalent characters.
 ///
 /// Unknown entities will be ignored.
@@ -546,6 +577,88 @@ impl TextSpan {
     }
 }
 
+/// Which side of the text field an embedded image floats to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextImageAlign {
+    Left,
+    Right,
+}
+
+/// An image embedded into the text with an `<img>` tag.
+///
+/// Images do not occupy any characters of the text. Instead, they are
+/// anchored to a position in the text and float to the left or right side of
+/// the field, with the text wrapping around them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextImage {
+    /// The position in the text this image is anchored to.
+    pub position: usize,
+
+    /// The URL of the image, or the linkage identifier of a library symbol.
+    pub src: WString,
+
+    /// The identifier used to retrieve the loaded image from script.
+    pub id: Option<WString>,
+
+    /// The requested width of the image, in pixels.
+    ///
+    /// If absent, the natural width of the loaded image is used.
+    pub width: Option<f64>,
+
+    /// The requested height of the image, in pixels.
+    ///
+    /// If absent, the natural height of the loaded image is used.
+    pub height: Option<f64>,
+
+    /// Which side of the field the image floats to.
+    pub align: TextImageAlign,
+
+    /// The horizontal space around the image that no text flows into, in pixels.
+    pub hspace: f64,
+
+    /// The vertical space around the image that no text flows into, in pixels.
+    pub vspace: f64,
+}
+
+impl TextImage {
+    /// The default amount of space around an image, in pixels.
+    const DEFAULT_SPACE: f64 = 8.0;
+
+    fn from_attributes(
+        position: usize,
+        attribute: impl Fn(&'static [u8]) -> Option<WString>,
+    ) -> Option<Self> {
+        let src = attribute(b"src")?;
+        let align = match attribute(b"align") {
+            Some(align) if align.eq_ignore_case(WStr::from_units(b"right")) => {
+                TextImageAlign::Right
+            }
+            _ => TextImageAlign::Left,
+        };
+
+        Some(Self {
+            position,
+            src,
+            id: attribute(b"id"),
+            width: attribute(b"width").and_then(|v| v.parse().ok()),
+            height: attribute(b"height").and_then(|v| v.parse().ok()),
+            align,
+            hspace: attribute(b"hspace")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::DEFAULT_SPACE),
+            vspace: attribute(b"vspace")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(Self::DEFAULT_SPACE),
+        })
+    }
+
+    /// Whether or not both images refer to the same content, regardless of
+    /// their position and layout attributes.
+    pub fn is_same_content(&self, other: &TextImage) -> bool {
+        self.src == other.src && self.id == other.id
+    }
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug)]
 pub struct FormatSpans {
@@ -553,6 +666,7 @@ pub struct FormatSpans {
     displayed_text: WString,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+    images: Vec<TextImage>,
 }
 
 impl Default for FormatSpans {
@@ -568,6 +682,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: Vec::new(),
         }
     }
 
@@ -579,6 +694,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: Vec::new(),
         }
     }
 
@@ -589,6 +705,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::with_length_and_format(len, &format)],
             default_format: format,
+            images: Vec::new(),
         }
     }
 
@@ -602,6 +719,7 @@ impl FormatSpans {
         html: &WStr,
         default_format: TextFormat,
         is_multiline: bool,
+        condense_white: bool,
         swf_version: u8,
     ) -> Self {
         // For SWF version 6, the multiline property exists and may be changed,
@@ -611,6 +729,7 @@ impl FormatSpans {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
+        let mut images: Vec<TextImage> = Vec::new();
 
         // quick_xml::Reader requires a [u8] slice, but doesn't actually care about Unicode;
         // this means we can pass the raw buffer in the Latin1 case.
@@ -690,6 +809,14 @@ impl FormatSpans {
                             // Skip push to `format_stack`.
                             continue;
                         }
+                        b"img" => {
+                            if let Some(image) = TextImage::from_attributes(text.len(), attribute) {
+                                images.push(image);
+                            }
+
+                            // Skip push to `format_stack`.
+                            continue;
+                        }
                         b"p" => {
                             p_open = true;
                             if let Some(align) = attribute(b"align") {
@@ -815,6 +942,11 @@ impl FormatSpans {
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => 'text: {
                     let e = decode_to_wstr(&e.into_inner());
+                    let e = if condense_white {
+                        condense_whitespace(&e, &text)
+                    } else {
+                        e
+                    };
                     let e = process_html_entity(&e).unwrap_or(e);
                     let format = format_stack.last().unwrap().clone();
                     if swf_version <= 7 && e.trim().is_empty() {
@@ -842,7 +974,7 @@ impl FormatSpans {
                     }
 
                     match tag_name {
-                        b"br" | b"sbr" => {
+                        b"br" | b"sbr" | b"img" => {
                             // Skip pop from `format_stack`.
                             continue;
                         }
@@ -902,6 +1034,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans,
             default_format,
+            images,
         };
         ret.normalize();
         ret
@@ -911,6 +1044,11 @@ impl FormatSpans {
         &self.default_format
     }
 
+    /// All images embedded into the text, ordered by their position.
+    pub fn images(&self) -> &[TextImage] {
+        &self.images
+    }
+
     pub fn set_default_format(&mut self, tf: TextFormat) {
         self.default_format = tf.mix_with(self.default_format.clone());
     }
@@ -1228,11 +1366,57 @@ impl FormatSpans {
             new_string.push_str(text);
         }
 
+        // Images anchored within the replaced range stick to its start, and
+        // all images after it move along with the text.
+        let start = min(from, self.text.len());
+        let end = min(to, self.text.len());
+        for image in &mut self.images {
+            if image.position >= end {
+                image.position = image.position - end + start + with.len();
+            } else if image.position > start {
+                image.position = start;
+            }
+        }
+
         self.text = new_string;
 
         self.normalize();
     }
 
+    /// Replace the text in the range [from, to) with the text of `with`,
+    /// keeping the formatting of each of its spans.
+    ///
+    /// Attempts to remove degenerate ranges (e.g. [5, 2)) will fail silently.
+    pub fn replace_with_spans(&mut self, from: usize, to: usize, with: &FormatSpans) {
+        if to < from {
+            return;
+        }
+
+        let start = min(from, self.text.len());
+        self.replace_text(from, to, with.text(), Some(with.default_format()));
+
+        for (span_start, span_end, _, span) in with.iter_spans() {
+            self.set_text_format(
+                start + span_start,
+                start + span_end,
+                &span.get_text_format(),
+            );
+        }
+
+        let insert_at = self
+            .images
+            .iter()
+            .position(|image| image.position > start)
+            .unwrap_or(self.images.len());
+        self.images.splice(
+            insert_at..insert_at,
+            with.images.iter().map(|image| TextImage {
+                position: start + image.position,
+                ..image.clone()
+            }),
+        );
+    }
+
     /// Iterate over all text spans in the current list of format spans.
     ///
     /// The iterator returned by this function yields a tuple for each span,
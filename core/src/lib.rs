@@ -27,6 +27,7 @@ pub mod buffer;
 mod character;
 pub mod context;
 pub mod context_menu;
+pub mod debugger;
 mod drawing;
 mod ecma_conversions;
 pub(crate) mod either;
@@ -44,6 +45,8 @@ mod net_connection;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+mod profiler;
+mod redraw_regions;
 pub mod socket;
 mod streams;
 pub mod string;
@@ -51,6 +54,7 @@ pub mod tag_utils;
 pub mod timer;
 mod types;
 mod vminterface;
+mod worker;
 mod xml;
 
 pub mod backend;
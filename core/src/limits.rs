@@ -1,5 +1,6 @@
 use crate::context::UpdateContext;
 use std::time::Duration;
+use web_time::Instant;
 
 /// Indication of how long execution is allowed to take.
 ///
@@ -91,3 +92,98 @@ impl ExecutionLimit {
         false
     }
 }
+
+/// What should happen to a script that has been checked against the script time limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptTimeoutAction {
+    /// The script may keep running.
+    Continue,
+
+    /// A `ScriptTimeoutError` should be thrown into the script.
+    ThrowError,
+
+    /// The script must be aborted.
+    Abort,
+}
+
+/// The script time limit of a single update, as enforced by Flash Player.
+///
+/// Once scripts have run for longer than the maximum execution duration, a `ScriptTimeoutError`
+/// is thrown into AVM2 code, which it may catch. If the script then keeps running for as long
+/// again, it is aborted. AVM1 has no such error, so its scripts are aborted right away.
+#[derive(Debug, Default)]
+pub struct ScriptTimeout {
+    /// When the `ScriptTimeoutError` was thrown during this update.
+    error_thrown_at: Option<Instant>,
+
+    /// Whether a script was aborted during this update.
+    aborted: bool,
+}
+
+impl ScriptTimeout {
+    /// Checks whether scripts have exceeded the time limit of the update that started at
+    /// `update_start`.
+    ///
+    /// `can_throw` is whether the script that is running can be sent a `ScriptTimeoutError`.
+    pub fn check(
+        &mut self,
+        update_start: Instant,
+        max_execution_duration: Duration,
+        can_throw: bool,
+    ) -> ScriptTimeoutAction {
+        let now = Instant::now();
+        let started = self.error_thrown_at.unwrap_or(update_start);
+        if now.duration_since(started) < max_execution_duration {
+            return ScriptTimeoutAction::Continue;
+        }
+
+        if can_throw && self.error_thrown_at.is_none() {
+            self.error_thrown_at = Some(now);
+            ScriptTimeoutAction::ThrowError
+        } else {
+            self.aborted = true;
+            ScriptTimeoutAction::Abort
+        }
+    }
+
+    /// Returns whether a script was aborted since the last call, and starts tracking a new
+    /// update.
+    pub fn finish_update(&mut self) -> bool {
+        std::mem::take(self).aborted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_timeout_throws_once_before_aborting() {
+        let mut timeout = ScriptTimeout::default();
+        let start = Instant::now();
+        assert_eq!(
+            timeout.check(start, Duration::MAX, true),
+            ScriptTimeoutAction::Continue
+        );
+        assert_eq!(
+            timeout.check(start, Duration::ZERO, true),
+            ScriptTimeoutAction::ThrowError
+        );
+        assert_eq!(
+            timeout.check(start, Duration::ZERO, true),
+            ScriptTimeoutAction::Abort
+        );
+        assert!(timeout.finish_update());
+        assert!(!timeout.finish_update());
+    }
+
+    #[test]
+    fn script_timeout_aborts_scripts_that_cannot_catch_it() {
+        let mut timeout = ScriptTimeout::default();
+        assert_eq!(
+            timeout.check(Instant::now(), Duration::ZERO, false),
+            ScriptTimeoutAction::Abort
+        );
+        assert!(timeout.finish_update());
+    }
+}
@@ -277,6 +277,11 @@ impl<'gc> LoadManager<'gc> {
     }
 
     /// Retrieve a loader by handle.
+    /// Iterates over all loaders that are in progress.
+    pub fn iter(&self) -> impl Iterator<Item = &Loader<'gc>> {
+        self.0.iter().map(|(_, loader)| loader)
+    }
+
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
         self.0.get(handle)
     }
@@ -192,6 +192,13 @@ impl<'gc> NetConnections<'gc> {
         }
     }
 
+    /// Iterates over the objects of all open connections.
+    pub fn iter_objects(&self) -> impl Iterator<Item = NetConnectionObject<'gc>> + '_ {
+        self.connections
+            .iter()
+            .map(|(_, connection)| connection.object)
+    }
+
     pub fn update_connections(context: &mut UpdateContext<'_, 'gc>) {
         for (handle, connection) in context.net_connections.connections.iter_mut() {
             connection.update(handle, context.navigator, context.player.clone());
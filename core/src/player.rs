@@ -7,13 +7,14 @@ use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{TObject, Value};
 use crate::avm2::{
-    object::TObject as _, Activation as Avm2Activation, Avm2, CallStack, Object as Avm2Object,
+    object::TObject as _, profiler::TraceCategory, Activation as Avm2Activation, Avm2, CallStack,
+    Object as Avm2Object,
 };
-use crate::backend::ui::FontDefinition;
+use crate::backend::ui::{FontDefinition, ScriptTimeoutChoice};
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
     log::LogBackend,
-    navigator::{NavigatorBackend, Request},
+    navigator::{NavigatorBackend, OwnedFuture, Request},
     storage::StorageBackend,
     ui::{InputManager, MouseCursor, UiBackend},
 };
@@ -24,6 +25,7 @@ use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::debugger::{Debugger, DebuggerConnection};
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
     EditText, InteractiveObject, Stage, StageAlign, StageDisplayState, StageScaleMode,
@@ -35,8 +37,8 @@ use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::library::Library;
-use crate::limits::ExecutionLimit;
-use crate::loader::{LoadBehavior, LoadManager};
+use crate::limits::{ExecutionLimit, ScriptTimeout};
+use crate::loader::{self, LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::net_connection::NetConnections;
@@ -48,6 +50,7 @@ use crate::stub::StubCollection;
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
+use crate::worker::Workers;
 use crate::DefaultFont;
 use gc_arena::{Collect, DynamicRootSet, GcCell, Rootable};
 use rand::{rngs::SmallRng, SeedableRng};
@@ -295,6 +298,12 @@ pub struct Player {
     forced_frame_rate: bool,
     actions_since_timeout_check: u16,
 
+    /// Whether scripts have exceeded the time limit during the current update.
+    script_timeout: ScriptTimeout,
+
+    /// Whether the user is being asked what to do with a script that ran for too long.
+    script_timeout_prompt_open: bool,
+
     frame_phase: FramePhase,
 
     stub_tracker: StubCollection,
@@ -359,6 +368,12 @@ pub struct Player {
     /// Debug UI windows
     #[cfg(feature = "egui")]
     debug_ui: Rc<RefCell<crate::debug_ui::DebugUi>>,
+
+    /// The workers of this movie, as seen by the worker running this player.
+    workers: Workers,
+
+    /// The script debugger, if a client is attached.
+    debugger: Option<Debugger>,
 }
 
 impl Player {
@@ -479,6 +494,23 @@ impl Player {
             self.audio.tick();
         }
     }
+
+    /// Runs exactly one frame and advances time by the duration of a frame.
+    ///
+    /// Unlike `tick`, this ignores how much time actually passed and never adjusts the
+    /// playback speed to stay in sync with audio, so it can be used to render movies offline.
+    pub fn run_frame_offline(&mut self) {
+        let dt = 1000.0 / self.frame_rate;
+        self.run_frame();
+        self.update_sockets();
+        self.update_net_connections();
+        self.update_timers(dt);
+        self.update(|context| {
+            StreamManager::tick(context, dt);
+        });
+        self.audio.tick();
+    }
+
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
     }
@@ -821,6 +853,25 @@ impl Player {
         })
     }
 
+    /// Whether the areas of the stage that are redrawn each frame are outlined.
+    pub fn show_redraw_regions(&self) -> bool {
+        self.gc_arena
+            .borrow()
+            .mutate(|_, gc_root| gc_root.data.read().stage.redraw_regions_color().is_some())
+    }
+
+    /// Outline the areas of the stage that are redrawn each frame, like the
+    /// "Show Redraw Regions" option of the Flash Player debugger.
+    pub fn set_show_redraw_regions(&mut self, show: bool) {
+        self.mutate_with_update_context(|context| {
+            let color = show.then_some(Color::RED);
+            context
+                .stage
+                .set_redraw_regions_color(context.gc_context, color);
+        });
+        self.needs_render = true;
+    }
+
     pub fn set_quality(&mut self, quality: StageQuality) {
         self.mutate_with_update_context(|context| {
             context.stage.set_quality(context, quality);
@@ -1043,6 +1094,11 @@ impl Player {
                         text.text_control_input(code, context);
                     }
                 }
+                if let PlayerEvent::Ime(ime_event) = &event {
+                    if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                        text.ime(ime_event.clone(), context);
+                    }
+                }
             }
 
             // Propagate clip events.
@@ -1576,7 +1632,17 @@ impl Player {
             return;
         }
 
+        if let Some(debugger) = &mut self.debugger {
+            debugger.poll();
+        }
+
         self.update(|context| {
+            if let Some(profiler) = context.avm2.profiler_mut() {
+                profiler.begin("Frame", TraceCategory::Frame);
+            }
+
+            crate::avm2::globals::flash::system::worker::dispatch_events(context);
+
             // TODO: Is this order correct?
             run_all_phases_avm2(context);
             Avm1::run_frame(context);
@@ -1587,13 +1653,26 @@ impl Player {
             for cb in std::mem::take(context.post_frame_callbacks) {
                 (cb.callback)(context, cb.data);
             }
+
+            if let Some(profiler) = context.avm2.profiler_mut() {
+                profiler.end();
+            }
         });
 
+        // The other workers run between the frames of the primordial worker.
+        if self.workers.is_primordial() {
+            self.workers.run_others();
+            for message in self.workers.take_traces() {
+                self.log.avm_trace(&message);
+            }
+        }
+
         self.needs_render = true;
     }
 
     #[instrument(level = "debug", skip_all)]
     pub fn render(&mut self) {
+        let render_start = Instant::now();
         let invalidated = self
             .gc_arena
             .borrow()
@@ -1647,10 +1726,24 @@ impl Player {
 
         self.renderer
             .submit_frame(background_color, commands, cache_draws);
+        self.record_profile_span("Render", TraceCategory::Render, render_start);
 
         self.needs_render = false;
     }
 
+    /// Records a span that was timed outside of the arena in the AVM2 profiler, if profiling is
+    /// enabled.
+    fn record_profile_span(&self, name: &str, category: TraceCategory, start: Instant) {
+        let end = Instant::now();
+        self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+            if gc_root.data.read().avm2.profiler().is_some() {
+                if let Some(profiler) = gc_root.data.write(gc_context).avm2.profiler_mut() {
+                    profiler.record(name, category, start, end);
+                }
+            }
+        });
+    }
+
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     pub fn current_frame(&self) -> Option<u16> {
@@ -1803,7 +1896,7 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc>) -> R,
     {
-        self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+        let ret = self.gc_arena.borrow().mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.data.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
             let mouse_pressed_object = root_data.mouse_pressed_object;
@@ -1871,7 +1964,12 @@ impl Player {
                 external_interface,
                 start_time: self.start_time,
                 update_start: Instant::now(),
-                max_execution_duration: self.max_execution_duration,
+                // Scripts may stay paused in the debugger for any amount of time.
+                max_execution_duration: if self.debugger.is_some() {
+                    Duration::MAX
+                } else {
+                    self.max_execution_duration
+                },
                 focus_tracker,
                 times_get_time_called: 0,
                 time_offset: &mut self.time_offset,
@@ -1879,6 +1977,7 @@ impl Player {
                 frame_rate: &mut self.frame_rate,
                 forced_frame_rate: self.forced_frame_rate,
                 actions_since_timeout_check: &mut self.actions_since_timeout_check,
+                script_timeout: &mut self.script_timeout,
                 frame_phase: &mut self.frame_phase,
                 stub_tracker: &mut self.stub_tracker,
                 stream_manager,
@@ -1887,12 +1986,23 @@ impl Player {
                 local_connections,
                 dynamic_root,
                 post_frame_callbacks,
+                workers: &self.workers,
+                debugger: &mut self.debugger,
             };
 
             let prev_frame_rate = *update_context.frame_rate;
 
+            // Other workers may have changed shared memory since this worker last ran code.
+            update_context
+                .avm2
+                .reload_shared_memory(update_context.gc_context);
+
             let ret = f(&mut update_context);
 
+            update_context
+                .avm2
+                .flush_shared_memory(update_context.gc_context);
+
             // If we changed the framerate, let the audio handler now.
             #[allow(clippy::float_cmp)]
             if *update_context.frame_rate != prev_frame_rate {
@@ -1914,7 +2024,53 @@ impl Player {
             root_data.mouse_pressed_object = mouse_pressed_object;
 
             ret
-        })
+        });
+
+        if self.script_timeout.finish_update() {
+            self.on_script_timeout();
+        }
+
+        ret
+    }
+
+    /// Called after a script has been aborted for running too long.
+    ///
+    /// Like Flash Player, this asks the user whether to abort all scripts in the movie. The
+    /// player is paused until the user has answered. If the prompt can't be displayed, only AVM1
+    /// is halted, as it can't recover from a timeout.
+    fn on_script_timeout(&mut self) {
+        if self.script_timeout_prompt_open {
+            return;
+        }
+
+        let Some(prompt) = self.ui.display_script_timeout_prompt() else {
+            self.mutate_with_update_context(|context| context.avm1.halt());
+            return;
+        };
+
+        let was_playing = self.is_playing;
+        self.script_timeout_prompt_open = true;
+        self.set_is_playing(false);
+
+        let player = self
+            .self_reference
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+        let future: OwnedFuture<(), loader::Error> = Box::pin(async move {
+            let choice = prompt.await;
+
+            let mut player = player.lock().unwrap();
+            player.script_timeout_prompt_open = false;
+            if matches!(choice, Ok(ScriptTimeoutChoice::Abort)) {
+                player.mutate_with_update_context(|context| {
+                    context.avm1.halt();
+                    context.avm2.halt();
+                });
+            }
+            player.set_is_playing(was_playing);
+            Ok(())
+        });
+        self.navigator.spawn_future(future);
     }
 
     #[cfg(feature = "egui")]
@@ -1961,7 +2117,9 @@ impl Player {
         self.update_mouse_state(false, false);
 
         // GC
+        let gc_start = Instant::now();
         self.gc_arena.borrow_mut().collect_debt();
+        self.record_profile_span("Garbage Collection", TraceCategory::Gc, gc_start);
 
         rval
     }
@@ -2115,6 +2273,8 @@ pub struct PlayerBuilder {
     compatibility_rules: CompatibilityRules,
     player_version: Option<u8>,
     player_runtime: PlayerRuntime,
+    avm2_optimizer: bool,
+    avm2_register_ir: bool,
     quality: StageQuality,
     sandbox_type: SandboxType,
     page_url: Option<String>,
@@ -2123,6 +2283,8 @@ pub struct PlayerBuilder {
     fs_command_provider: Box<dyn FsCommandProvider>,
     #[cfg(feature = "known_stubs")]
     stub_report_output: Option<std::path::PathBuf>,
+    workers: Option<Workers>,
+    debugger: Option<Box<dyn DebuggerConnection>>,
 }
 
 impl PlayerBuilder {
@@ -2165,6 +2327,8 @@ impl PlayerBuilder {
             compatibility_rules: CompatibilityRules::default(),
             player_version: None,
             player_runtime: PlayerRuntime::default(),
+            avm2_optimizer: true,
+            avm2_register_ir: true,
             quality: StageQuality::High,
             sandbox_type: SandboxType::LocalTrusted,
             page_url: None,
@@ -2173,6 +2337,8 @@ impl PlayerBuilder {
             fs_command_provider: Box::new(NullFsCommandProvider),
             #[cfg(feature = "known_stubs")]
             stub_report_output: None,
+            workers: None,
+            debugger: None,
         }
     }
 
@@ -2332,6 +2498,24 @@ impl PlayerBuilder {
         self
     }
 
+    /// Configures whether AVM2 method bodies are optimized before they're run (default is `true`).
+    ///
+    /// Disabling the optimizer makes the executed code match the original bytecode, which can be
+    /// useful when debugging.
+    pub fn with_avm2_optimizer(mut self, enabled: bool) -> Self {
+        self.avm2_optimizer = enabled;
+        self
+    }
+
+    /// Configures whether AVM2 method bodies are compiled to registers and run by the register
+    /// interpreter (default is `true`).
+    ///
+    /// When disabled, all code is run by the slower stack interpreter.
+    pub fn with_avm2_register_ir(mut self, enabled: bool) -> Self {
+        self.avm2_register_ir = enabled;
+        self
+    }
+
     /// Configures the security sandbox type (default is `SandboxType::LocalTrusted`)
     pub fn with_sandbox_type(mut self, sandbox_type: SandboxType) -> Self {
         self.sandbox_type = sandbox_type;
@@ -2370,6 +2554,18 @@ impl PlayerBuilder {
         self
     }
 
+    /// Attaches a script debugger, which is controlled through the given connection.
+    pub fn with_debugger(mut self, connection: Box<dyn DebuggerConnection>) -> Self {
+        self.debugger = Some(connection);
+        self
+    }
+
+    /// Makes the player run a worker of another movie, rather than being a primordial worker.
+    pub(crate) fn with_workers(mut self, workers: Workers) -> Self {
+        self.workers = Some(workers);
+        self
+    }
+
     fn create_gc_root<'gc>(
         gc_context: &'gc gc_arena::Mutation<'gc>,
         player_version: u8,
@@ -2485,6 +2681,8 @@ impl PlayerBuilder {
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
                 actions_since_timeout_check: 0,
+                script_timeout: Default::default(),
+                script_timeout_prompt_open: false,
 
                 // Input
                 input: Default::default(),
@@ -2510,6 +2708,8 @@ impl PlayerBuilder {
                 stub_tracker: StubCollection::new(),
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
+                workers: self.workers.unwrap_or_default(),
+                debugger: self.debugger.map(Debugger::new),
 
                 // GC data
                 gc_arena: Rc::new(RefCell::new(GcArena::new(|gc_context| {
@@ -2551,6 +2751,8 @@ impl PlayerBuilder {
         }
 
         player_lock.mutate_with_update_context(|context| {
+            context.avm2.optimizer_enabled = self.avm2_optimizer;
+            context.avm2.register_ir_enabled = self.avm2_register_ir;
             Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
             let stage = context.stage;
             stage.set_align(context, self.align);
@@ -0,0 +1,152 @@
+//! Bookkeeping shared by the AVM1 and AVM2 profilers.
+
+use web_time::{Duration, Instant};
+
+/// The accumulated timing of a function, method or event.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpanStats {
+    pub calls: u64,
+
+    /// The time spent in the span, including the spans nested in it.
+    pub total: Duration,
+
+    /// The time spent in the span itself.
+    pub exclusive: Duration,
+}
+
+impl SpanStats {
+    /// Adds a single call to these statistics.
+    pub fn add_call(&mut self, total: Duration, exclusive: Duration) {
+        self.calls += 1;
+        self.total += total;
+        self.exclusive += exclusive;
+    }
+}
+
+/// A span that was ended by `SpanTracker::end`.
+#[derive(Clone, Debug)]
+pub struct ClosedSpan<K> {
+    pub key: K,
+    pub start: Instant,
+    pub duration: Duration,
+
+    /// The time spent in the span itself, excluding the spans nested in it.
+    pub exclusive: Duration,
+}
+
+struct OpenSpan<K> {
+    key: K,
+    start: Instant,
+
+    /// The time spent in the spans nested in this one.
+    children: Duration,
+}
+
+/// Keeps track of the nested spans that are currently open, and of how much of their time was
+/// spent in the spans nested in them.
+pub struct SpanTracker<K> {
+    /// The spans that are currently open, innermost last.
+    open: Vec<OpenSpan<K>>,
+}
+
+impl<K> Default for SpanTracker<K> {
+    fn default() -> Self {
+        Self { open: Vec::new() }
+    }
+}
+
+impl<K> SpanTracker<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The keys of the spans that are currently open, outermost first.
+    pub fn open_keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.open.iter().map(|span| &span.key)
+    }
+
+    /// Starts a span nested in the innermost open span.
+    pub fn begin(&mut self, key: K) {
+        self.open.push(OpenSpan {
+            key,
+            start: Instant::now(),
+            children: Duration::ZERO,
+        });
+    }
+
+    /// Ends the innermost open span, if there is one.
+    pub fn end(&mut self) -> Option<ClosedSpan<K>> {
+        let span = self.open.pop()?;
+        let duration = span.start.elapsed();
+        self.add_to_parent(duration);
+        Some(ClosedSpan {
+            key: span.key,
+            start: span.start,
+            duration,
+            exclusive: duration.saturating_sub(span.children),
+        })
+    }
+
+    /// Accounts for a span that was timed by the caller and nested in the innermost open span.
+    ///
+    /// Returns the duration of the span.
+    pub fn record(&mut self, start: Instant, end: Instant) -> Duration {
+        let duration = end.duration_since(start);
+        self.add_to_parent(duration);
+        duration
+    }
+
+    fn add_to_parent(&mut self, duration: Duration) {
+        if let Some(parent) = self.open.last_mut() {
+            parent.children += duration;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subtracts_nested_spans_from_exclusive_time() {
+        let mut tracker = SpanTracker::new();
+
+        tracker.begin("outer");
+        tracker.begin("inner");
+        std::thread::sleep(Duration::from_millis(2));
+        let inner = tracker.end().unwrap();
+        assert_eq!(tracker.open_keys().collect::<Vec<_>>(), [&"outer"]);
+        let outer = tracker.end().unwrap();
+
+        assert_eq!(inner.key, "inner");
+        assert_eq!(inner.exclusive, inner.duration);
+        assert!(outer.duration >= inner.duration);
+        assert!(outer.exclusive <= outer.duration - inner.duration);
+        assert!(tracker.end().is_none());
+    }
+
+    #[test]
+    fn recorded_spans_count_as_children() {
+        let mut tracker = SpanTracker::new();
+
+        tracker.begin("outer");
+        let start = Instant::now();
+        std::thread::sleep(Duration::from_millis(2));
+        let recorded = tracker.record(start, Instant::now());
+        let outer = tracker.end().unwrap();
+
+        assert!(recorded >= Duration::from_millis(2));
+        assert!(outer.exclusive <= outer.duration - recorded);
+    }
+
+    #[test]
+    fn stats_add_calls() {
+        let mut stats = SpanStats::default();
+        stats.add_call(Duration::from_millis(3), Duration::from_millis(1));
+        stats.add_call(Duration::ZERO, Duration::from_millis(2));
+
+        assert_eq!(stats.calls, 2);
+        assert_eq!(stats.total, Duration::from_millis(3));
+        assert_eq!(stats.exclusive, Duration::from_millis(3));
+    }
+}
@@ -0,0 +1,173 @@
+//! Tracking of the areas of the stage that changed between frames.
+
+use fnv::FnvHashMap;
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::matrix::Matrix;
+use swf::{Color, ColorTransform, Rectangle, Twips};
+
+/// How a display object was drawn in a frame.
+#[derive(Clone, PartialEq)]
+struct DrawnObject {
+    /// The bounds of the object's own content, in device space.
+    bounds: Rectangle<Twips>,
+
+    /// The color transform the object was drawn with.
+    color_transform: ColorTransform,
+}
+
+/// Tracks which areas of the stage need to be redrawn.
+///
+/// Every object drawn in a frame is compared against how it was drawn in the previous frame.
+/// Objects that moved, changed their appearance or were invalidated, as well as objects that
+/// were not drawn at all anymore, contribute their old and new bounds to the redraw regions.
+///
+/// This is used for `flash.profiler.showRedrawRegions`, which outlines these regions.
+#[derive(Clone, Default)]
+pub struct RedrawRegions {
+    /// The color of the outlines, or `None` if redraw regions are not tracked.
+    color: Option<Color>,
+
+    /// The objects drawn in the previous frame, keyed by their pointer.
+    previous: FnvHashMap<usize, DrawnObject>,
+
+    /// The objects drawn so far in the current frame, keyed by their pointer.
+    current: FnvHashMap<usize, DrawnObject>,
+
+    /// The areas that changed in the current frame. No two of these intersect.
+    regions: Vec<Rectangle<Twips>>,
+}
+
+impl RedrawRegions {
+    /// Whether redraw regions are being tracked.
+    pub fn enabled(&self) -> bool {
+        self.color.is_some()
+    }
+
+    /// The color that redraw regions are outlined with, if they are shown.
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Starts or stops showing redraw regions.
+    ///
+    /// When tracking starts, the whole stage is considered to have changed.
+    pub fn set_color(&mut self, color: Option<Color>) {
+        if color.is_none() {
+            self.previous.clear();
+            self.current.clear();
+            self.regions.clear();
+        }
+        self.color = color;
+    }
+
+    /// Records that an object with the given pointer was drawn.
+    ///
+    /// `invalidated` should be set when the content of the object changed since it was last drawn.
+    pub fn record(
+        &mut self,
+        ptr: usize,
+        bounds: Rectangle<Twips>,
+        color_transform: ColorTransform,
+        invalidated: bool,
+    ) {
+        let drawn = DrawnObject {
+            bounds: bounds.clone(),
+            color_transform,
+        };
+        let previous = self.previous.remove(&ptr);
+        if invalidated || previous.as_ref() != Some(&drawn) {
+            if let Some(previous) = previous {
+                self.add(previous.bounds);
+            }
+            self.add(bounds);
+        }
+        self.current.insert(ptr, drawn);
+    }
+
+    /// Marks an area of the stage as changed.
+    pub fn add(&mut self, mut bounds: Rectangle<Twips>) {
+        if !bounds.is_valid() || bounds.width() <= Twips::ZERO || bounds.height() <= Twips::ZERO {
+            return;
+        }
+        // Merge with all intersecting regions, which may in turn intersect further regions.
+        while let Some(index) = self.regions.iter().position(|r| r.intersects(&bounds)) {
+            bounds = bounds.union(&self.regions.swap_remove(index));
+        }
+        self.regions.push(bounds);
+    }
+
+    /// Ends the current frame, returning the areas that changed during it.
+    pub fn finish_frame(&mut self) -> Vec<Rectangle<Twips>> {
+        // Anything that was drawn before but not this frame has been removed or hidden.
+        let removed: Vec<_> = self
+            .previous
+            .drain()
+            .map(|(_, drawn)| drawn.bounds)
+            .collect();
+        for bounds in removed {
+            self.add(bounds);
+        }
+        std::mem::swap(&mut self.previous, &mut self.current);
+        std::mem::take(&mut self.regions)
+    }
+
+    /// Draws the outlines of the given regions.
+    pub fn draw_outlines(&self, commands: &mut CommandList, regions: &[Rectangle<Twips>]) {
+        let Some(color) = self.color else {
+            return;
+        };
+        let thickness = Twips::ONE;
+        for region in regions {
+            let width = region.width().to_pixels() as f32;
+            let height = region.height().to_pixels() as f32;
+            let edges = [
+                (width, 1.0, region.x_min, region.y_min),
+                (width, 1.0, region.x_min, region.y_max - thickness),
+                (1.0, height, region.x_min, region.y_min),
+                (1.0, height, region.x_max - thickness, region.y_min),
+            ];
+            for (width, height, x, y) in edges {
+                commands.draw_rect(color, Matrix::create_box(width, height, 0.0, x, y));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x_min: i32, y_min: i32, x_max: i32, y_max: i32) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::new(x_min),
+            y_min: Twips::new(y_min),
+            x_max: Twips::new(x_max),
+            y_max: Twips::new(y_max),
+        }
+    }
+
+    #[test]
+    fn intersecting_regions_are_merged() {
+        let mut regions = RedrawRegions::default();
+        regions.add(rect(0, 0, 10, 10));
+        regions.add(rect(20, 20, 30, 30));
+        regions.add(rect(5, 5, 25, 25));
+        assert_eq!(regions.finish_frame(), vec![rect(0, 0, 30, 30)]);
+    }
+
+    #[test]
+    fn only_changed_objects_are_redrawn() {
+        let mut regions = RedrawRegions::default();
+        let ct = ColorTransform::default();
+        regions.record(1, rect(0, 0, 10, 10), ct, false);
+        regions.record(2, rect(100, 100, 110, 110), ct, false);
+        assert_eq!(regions.finish_frame().len(), 2);
+
+        regions.record(1, rect(0, 0, 10, 10), ct, false);
+        regions.record(2, rect(100, 100, 110, 110), ct, true);
+        assert_eq!(regions.finish_frame(), vec![rect(100, 100, 110, 110)]);
+
+        regions.record(2, rect(100, 100, 110, 110), ct, false);
+        assert_eq!(regions.finish_frame(), vec![rect(0, 0, 10, 10)]);
+    }
+}
@@ -218,11 +218,23 @@ impl<'gc> Timers<'gc> {
         self.timers.is_empty()
     }
 
+    /// The time that has passed on the player's clock, in milliseconds.
+    ///
+    /// Unlike the wall clock, this only advances when the player is ticked.
+    pub fn current_time_ms(&self) -> u64 {
+        self.cur_time / Self::TIMER_SCALE as u64
+    }
+
     /// Whether a timer with the given ID exists.
     pub fn timer_exists(&self, id: i32) -> bool {
         self.timers.iter().any(|t| t.id == id)
     }
 
+    /// Iterates over the IDs and callbacks of all active timers.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &TimerCallback<'gc>)> {
+        self.timers.iter().map(|timer| (timer.id, &timer.callback))
+    }
+
     /// Registers a new timer and returns the timer ID.
     pub fn add_timer(
         &mut self,
@@ -0,0 +1,1059 @@
+//! Workers, which run other movies concurrently with the main one.
+//!
+//! Every worker is an isolated `Player` with its own GC arena, and every worker but the primordial
+//! one runs on a thread of its own. Only one worker runs code at a time, though: the workers take
+//! turns, which the primordial worker hands out after each of its own frames. A worker that blocks,
+//! like in `Mutex.lock` or `Condition.wait`, is suspended and gives its turn back, and is resumed
+//! on a later frame of the primordial worker once it can continue. The primordial worker can't be
+//! suspended, so it hands out turns to the other workers until it can continue instead.
+//!
+//! Values sent between workers are copied with AMF, except for workers, message channels, mutexes,
+//! conditions and shareable byte arrays, which are passed by reference.
+
+use crate::backend::log::LogBackend;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use web_time::{Duration, Instant};
+
+pub type WorkerId = u32;
+pub type ChannelId = u32;
+pub type MutexId = u32;
+pub type ConditionId = u32;
+
+/// The worker running the movie that was loaded into the player.
+pub const PRIMORDIAL_WORKER: WorkerId = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerState {
+    New,
+    Running,
+    Terminated,
+}
+
+impl WorkerState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WorkerState::New => "new",
+            WorkerState::Running => "running",
+            WorkerState::Terminated => "terminated",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelState {
+    Open,
+    Closing,
+    Closed,
+}
+
+impl ChannelState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChannelState::Open => "open",
+            ChannelState::Closing => "closing",
+            ChannelState::Closed => "closed",
+        }
+    }
+}
+
+/// The memory of a shareable `ByteArray`.
+///
+/// Each worker works on its own copy of the memory, which is written back whenever it stops
+/// running code and read again when it starts. As only one worker runs code at a time, this
+/// behaves just like sharing the memory directly.
+#[derive(Debug, Default)]
+pub struct SharedMemory {
+    bytes: Mutex<Vec<u8>>,
+
+    /// Incremented whenever the memory changes.
+    version: AtomicU64,
+}
+
+impl SharedMemory {
+    pub fn new(bytes: Vec<u8>) -> Arc<Self> {
+        Arc::new(Self {
+            bytes: Mutex::new(bytes),
+            version: AtomicU64::new(0),
+        })
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    pub fn read(&self) -> Vec<u8> {
+        lock(&self.bytes).clone()
+    }
+
+    /// Replaces the memory, returning its new version.
+    pub fn write(&self, bytes: &[u8]) -> u64 {
+        let mut own_bytes = lock(&self.bytes);
+        if own_bytes[..] != *bytes {
+            own_bytes.clear();
+            own_bytes.extend_from_slice(bytes);
+            self.version.fetch_add(1, Ordering::AcqRel);
+        }
+        self.version()
+    }
+}
+
+/// A value sent from one worker to another.
+#[derive(Clone, Debug)]
+pub enum SharedValue {
+    /// A value serialized as an AMF3 shared object with a single element.
+    Amf(Vec<u8>),
+    Worker(WorkerId),
+    Channel(ChannelId),
+    Mutex(MutexId),
+    Condition(ConditionId),
+    ByteArray(Arc<SharedMemory>),
+}
+
+/// Something that happened in another worker, to be dispatched as an event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerEvent {
+    /// The state of a worker changed.
+    State(WorkerId),
+
+    /// A message was sent through a channel.
+    ChannelMessage(ChannelId),
+
+    /// The state of a channel changed.
+    ChannelState(ChannelId),
+}
+
+/// The reason a blocking call couldn't continue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitError {
+    /// The current worker doesn't own the mutex it has to release.
+    NotOwner,
+
+    /// No other worker can run to let the primordial worker continue.
+    Deadlock,
+
+    /// The primordial worker waited for longer than its script may run.
+    Timeout,
+
+    /// The current worker was terminated while it was suspended.
+    Terminated,
+}
+
+/// What a blocked worker is waiting for.
+#[derive(Clone, Copy, Debug)]
+enum Block {
+    /// A mutex to become lockable.
+    Mutex(MutexId),
+
+    /// A condition to be notified, or the deadline to pass, and then its mutex to become lockable.
+    Condition {
+        condition: ConditionId,
+        mutex: MutexId,
+        deadline: Option<Instant>,
+    },
+
+    /// A message to arrive through a channel, or the channel to close.
+    Message(ChannelId),
+}
+
+struct WorkerData {
+    state: WorkerState,
+
+    /// Whether the thread of this worker is running. The primordial worker runs on the thread of
+    /// the frontend instead.
+    has_thread: bool,
+
+    /// What this worker is waiting for, if it's suspended in a blocking call.
+    blocked: Option<Block>,
+
+    shared_properties: HashMap<String, SharedValue>,
+    events: VecDeque<WorkerEvent>,
+}
+
+impl WorkerData {
+    fn new(state: WorkerState) -> Self {
+        Self {
+            state,
+            has_thread: false,
+            blocked: None,
+            shared_properties: HashMap::new(),
+            events: VecDeque::new(),
+        }
+    }
+}
+
+struct ChannelData {
+    sender: WorkerId,
+    receiver: WorkerId,
+    state: ChannelState,
+    messages: VecDeque<SharedValue>,
+}
+
+#[derive(Default)]
+struct MutexData {
+    owner: Option<WorkerId>,
+
+    /// The number of times the owner locked the mutex.
+    count: u32,
+}
+
+struct ConditionData {
+    mutex: MutexId,
+    waiting: Vec<WorkerId>,
+    notified: Vec<WorkerId>,
+}
+
+#[derive(Default)]
+struct Registry {
+    workers: Vec<WorkerData>,
+    channels: Vec<ChannelData>,
+    mutexes: Vec<MutexData>,
+    conditions: Vec<ConditionData>,
+
+    /// The worker whose turn it is to run code.
+    turn: WorkerId,
+
+    /// Messages traced by workers, to be logged by the primordial worker.
+    traces: Vec<String>,
+}
+
+impl Registry {
+    fn broadcast(&mut self, event: WorkerEvent) {
+        for worker in &mut self.workers {
+            if worker.state != WorkerState::Terminated {
+                worker.events.push_back(event);
+            }
+        }
+    }
+
+    fn notify_channel_state(&mut self, channel: ChannelId) {
+        let data = &self.channels[channel as usize];
+        let (sender, receiver) = (data.sender, data.receiver);
+        for worker in [sender, receiver] {
+            self.workers[worker as usize]
+                .events
+                .push_back(WorkerEvent::ChannelState(channel));
+        }
+    }
+
+    fn is_lockable(&self, worker: WorkerId, mutex: MutexId) -> bool {
+        let owner = self.mutexes[mutex as usize].owner;
+        owner.is_none() || owner == Some(worker)
+    }
+
+    /// Whether a worker that is blocked can continue.
+    fn can_continue(&self, worker: WorkerId, block: Block) -> bool {
+        match block {
+            Block::Mutex(mutex) => self.is_lockable(worker, mutex),
+            Block::Condition {
+                condition,
+                mutex,
+                deadline,
+            } => {
+                let notified = self.conditions[condition as usize]
+                    .notified
+                    .contains(&worker);
+                let timed_out = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+                (notified || timed_out) && self.is_lockable(worker, mutex)
+            }
+            Block::Message(channel) => {
+                let data = &self.channels[channel as usize];
+                !data.messages.is_empty() || data.state == ChannelState::Closed
+            }
+        }
+    }
+
+    /// Whether the thread of a worker can be given a turn.
+    fn is_runnable(&self, worker: WorkerId) -> bool {
+        let data = &self.workers[worker as usize];
+        if !data.has_thread {
+            return false;
+        }
+        match data.state {
+            WorkerState::New => false,
+            WorkerState::Running => match data.blocked {
+                Some(block) => self.can_continue(worker, block),
+                None => true,
+            },
+            // The thread has to run to unwind and end.
+            WorkerState::Terminated => true,
+        }
+    }
+}
+
+/// State shared by all workers of a movie, across threads.
+#[derive(Default)]
+struct Shared {
+    registry: Mutex<Registry>,
+
+    /// Notified whenever the turn changes.
+    turn_changed: Condvar,
+}
+
+/// The view of one worker on all workers of a movie.
+pub struct Workers {
+    shared: Arc<Shared>,
+    id: WorkerId,
+}
+
+impl Workers {
+    /// Creates the primordial worker of a new movie.
+    pub fn new() -> Self {
+        let registry = Registry {
+            workers: vec![WorkerData::new(WorkerState::Running)],
+            turn: PRIMORDIAL_WORKER,
+            ..Default::default()
+        };
+        Self {
+            shared: Arc::new(Shared {
+                registry: Mutex::new(registry),
+                turn_changed: Condvar::new(),
+            }),
+            id: PRIMORDIAL_WORKER,
+        }
+    }
+
+    fn registry(&self) -> MutexGuard<'_, Registry> {
+        lock(&self.shared.registry)
+    }
+
+    /// The worker that this view belongs to.
+    pub fn id(&self) -> WorkerId {
+        self.id
+    }
+
+    pub fn is_primordial(&self) -> bool {
+        self.id == PRIMORDIAL_WORKER
+    }
+
+    /// Creates a new worker, which won't run until its thread is spawned and it's started.
+    pub fn create_worker(&self) -> WorkerId {
+        let mut registry = self.registry();
+        registry.workers.push(WorkerData::new(WorkerState::New));
+        (registry.workers.len() - 1) as WorkerId
+    }
+
+    /// Whether workers other than the primordial one can run on this platform.
+    ///
+    /// Every worker needs a thread of its own, which can't be spawned on the web.
+    pub fn is_supported() -> bool {
+        cfg!(not(target_family = "wasm"))
+    }
+
+    /// Spawns the thread of a new worker.
+    ///
+    /// `build` is called on that thread once the worker first runs, with the view of the worker on
+    /// all workers, and returns the function that runs one frame of it. Everything it returns stays
+    /// on the thread of the worker.
+    ///
+    /// If the thread can't be spawned, the worker is terminated right away.
+    pub fn spawn<F, R>(&self, worker: WorkerId, build: F) -> std::io::Result<()>
+    where
+        F: FnOnce(Workers) -> R + Send + 'static,
+        R: FnMut(),
+    {
+        let workers = Workers {
+            shared: self.shared.clone(),
+            id: worker,
+        };
+        self.registry().workers[worker as usize].has_thread = true;
+        let result = thread::Builder::new()
+            .name(format!("Worker {worker}"))
+            .spawn(move || workers.run_thread(build));
+        if let Err(e) = result {
+            let mut registry = self.registry();
+            let data = &mut registry.workers[worker as usize];
+            data.has_thread = false;
+            data.state = WorkerState::Terminated;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn run_thread<F, R>(self, build: F)
+    where
+        F: FnOnce(Workers) -> R,
+        R: FnMut(),
+    {
+        /// Hands the turn back when the thread ends, even if it panics.
+        struct ThreadGuard<'a>(&'a Workers);
+
+        impl Drop for ThreadGuard<'_> {
+            fn drop(&mut self) {
+                let mut registry = self.0.registry();
+                registry.workers[self.0.id as usize].has_thread = false;
+                registry.turn = PRIMORDIAL_WORKER;
+                self.0.shared.turn_changed.notify_all();
+            }
+        }
+
+        let _guard = ThreadGuard(&self);
+        let mut build = Some(build);
+        let mut run_frame = None;
+        while self.wait_for_turn() {
+            if let Some(build) = build.take() {
+                run_frame = Some(build(Workers {
+                    shared: self.shared.clone(),
+                    id: self.id,
+                }));
+            }
+            if let Some(run_frame) = &mut run_frame {
+                run_frame();
+            }
+            self.end_turn();
+        }
+        // The player may run code of its own while being dropped, so it's dropped during a turn.
+        drop(run_frame);
+    }
+
+    /// Waits until it's the turn of this worker, returning whether it's still alive.
+    fn wait_for_turn(&self) -> bool {
+        let mut registry = self.registry();
+        while registry.turn != self.id {
+            registry = self
+                .shared
+                .turn_changed
+                .wait(registry)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        registry.workers[self.id as usize].state != WorkerState::Terminated
+    }
+
+    /// Gives the turn back to the primordial worker.
+    fn end_turn(&self) {
+        self.registry().turn = PRIMORDIAL_WORKER;
+        self.shared.turn_changed.notify_all();
+    }
+
+    /// Gives a worker a turn, and waits until it gives it back.
+    fn give_turn(&self, worker: WorkerId) -> bool {
+        let mut registry = self.registry();
+        if !registry.is_runnable(worker) {
+            return false;
+        }
+        registry.turn = worker;
+        self.shared.turn_changed.notify_all();
+        while registry.turn != self.id {
+            registry = self
+                .shared
+                .turn_changed
+                .wait(registry)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        true
+    }
+
+    /// Returns a log backend that passes the traces of a worker on to the primordial worker.
+    pub fn log_backend(&self) -> impl LogBackend {
+        WorkerLogBackend {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Takes the messages that other workers traced since the last call.
+    pub fn take_traces(&self) -> Vec<String> {
+        std::mem::take(&mut self.registry().traces)
+    }
+
+    pub fn state(&self, worker: WorkerId) -> WorkerState {
+        self.registry().workers[worker as usize].state
+    }
+
+    /// Returns all workers that weren't terminated.
+    pub fn list(&self) -> Vec<WorkerId> {
+        let registry = self.registry();
+        (0..registry.workers.len() as WorkerId)
+            .filter(|&id| registry.workers[id as usize].state != WorkerState::Terminated)
+            .collect()
+    }
+
+    /// Starts a new worker, returning whether it was new.
+    pub fn start(&self, worker: WorkerId) -> bool {
+        let mut registry = self.registry();
+        if registry.workers[worker as usize].state != WorkerState::New {
+            return false;
+        }
+        registry.workers[worker as usize].state = WorkerState::Running;
+        registry.broadcast(WorkerEvent::State(worker));
+        true
+    }
+
+    /// Terminates a worker, returning whether it was running.
+    ///
+    /// The thread of the worker ends the next time it gets a turn.
+    pub fn terminate(&self, worker: WorkerId) -> bool {
+        let mut registry = self.registry();
+        let data = &mut registry.workers[worker as usize];
+        if worker == PRIMORDIAL_WORKER || data.state == WorkerState::Terminated {
+            return false;
+        }
+        let was_running = data.state == WorkerState::Running;
+        data.state = WorkerState::Terminated;
+        data.events.clear();
+        for mutex in &mut registry.mutexes {
+            if mutex.owner == Some(worker) {
+                *mutex = MutexData::default();
+            }
+        }
+        for condition in &mut registry.conditions {
+            condition.waiting.retain(|&id| id != worker);
+            condition.notified.retain(|&id| id != worker);
+        }
+        registry.broadcast(WorkerEvent::State(worker));
+        was_running
+    }
+
+    pub fn set_shared_property(&self, worker: WorkerId, key: String, value: SharedValue) {
+        self.registry().workers[worker as usize]
+            .shared_properties
+            .insert(key, value);
+    }
+
+    pub fn shared_property(&self, worker: WorkerId, key: &str) -> Option<SharedValue> {
+        self.registry().workers[worker as usize]
+            .shared_properties
+            .get(key)
+            .cloned()
+    }
+
+    /// Takes the events that happened for this worker since the last call.
+    pub fn take_events(&self) -> Vec<WorkerEvent> {
+        self.registry().workers[self.id as usize]
+            .events
+            .drain(..)
+            .collect()
+    }
+
+    /// Creates a channel from one worker to another.
+    pub fn create_channel(&self, sender: WorkerId, receiver: WorkerId) -> ChannelId {
+        let mut registry = self.registry();
+        registry.channels.push(ChannelData {
+            sender,
+            receiver,
+            state: ChannelState::Open,
+            messages: VecDeque::new(),
+        });
+        (registry.channels.len() - 1) as ChannelId
+    }
+
+    pub fn channel_state(&self, channel: ChannelId) -> ChannelState {
+        self.registry().channels[channel as usize].state
+    }
+
+    pub fn message_available(&self, channel: ChannelId) -> bool {
+        !self.registry().channels[channel as usize]
+            .messages
+            .is_empty()
+    }
+
+    /// Sends a message through a channel, returning whether it's still open.
+    pub fn send(&self, channel: ChannelId, value: SharedValue) -> bool {
+        let mut registry = self.registry();
+        let data = &mut registry.channels[channel as usize];
+        if data.state != ChannelState::Open {
+            return false;
+        }
+        data.messages.push_back(value);
+        let receiver = data.receiver;
+        registry.workers[receiver as usize]
+            .events
+            .push_back(WorkerEvent::ChannelMessage(channel));
+        true
+    }
+
+    /// Takes the next message from a channel, if there is one.
+    pub fn receive(&self, channel: ChannelId) -> Option<SharedValue> {
+        let mut registry = self.registry();
+        let data = &mut registry.channels[channel as usize];
+        let message = data.messages.pop_front();
+        if data.state == ChannelState::Closing && data.messages.is_empty() {
+            data.state = ChannelState::Closed;
+            registry.notify_channel_state(channel);
+        }
+        message
+    }
+
+    /// Blocks until a message arrives through a channel, or the channel is closed.
+    pub fn wait_for_message(&self, channel: ChannelId, limit: Instant) -> Result<(), WaitError> {
+        self.block(Block::Message(channel), limit)
+    }
+
+    /// Closes a channel once all messages in it are received.
+    pub fn close(&self, channel: ChannelId) {
+        let mut registry = self.registry();
+        let data = &mut registry.channels[channel as usize];
+        if data.state != ChannelState::Open {
+            return;
+        }
+        data.state = if data.messages.is_empty() {
+            ChannelState::Closed
+        } else {
+            ChannelState::Closing
+        };
+        registry.notify_channel_state(channel);
+    }
+
+    pub fn create_mutex(&self) -> MutexId {
+        let mut registry = self.registry();
+        registry.mutexes.push(MutexData::default());
+        (registry.mutexes.len() - 1) as MutexId
+    }
+
+    /// Locks a mutex if no other worker owns it. Mutexes can be locked multiple times by their owner.
+    pub fn try_lock(&self, mutex: MutexId) -> bool {
+        let mut registry = self.registry();
+        let data = &mut registry.mutexes[mutex as usize];
+        match data.owner {
+            Some(owner) if owner != self.id => false,
+            _ => {
+                data.owner = Some(self.id);
+                data.count += 1;
+                true
+            }
+        }
+    }
+
+    /// Locks a mutex, blocking until it's released.
+    pub fn lock(&self, mutex: MutexId, limit: Instant) -> Result<(), WaitError> {
+        if !self.try_lock(mutex) {
+            self.block(Block::Mutex(mutex), limit)?;
+            self.try_lock(mutex);
+        }
+        Ok(())
+    }
+
+    /// Unlocks a mutex, returning whether it was owned by this worker.
+    pub fn unlock(&self, mutex: MutexId) -> bool {
+        let mut registry = self.registry();
+        let data = &mut registry.mutexes[mutex as usize];
+        if data.owner != Some(self.id) {
+            return false;
+        }
+        data.count -= 1;
+        if data.count == 0 {
+            data.owner = None;
+        }
+        true
+    }
+
+    fn owns(&self, mutex: MutexId) -> bool {
+        self.registry().mutexes[mutex as usize].owner == Some(self.id)
+    }
+
+    pub fn create_condition(&self, mutex: MutexId) -> ConditionId {
+        let mut registry = self.registry();
+        registry.conditions.push(ConditionData {
+            mutex,
+            waiting: vec![],
+            notified: vec![],
+        });
+        (registry.conditions.len() - 1) as ConditionId
+    }
+
+    pub fn condition_mutex(&self, condition: ConditionId) -> MutexId {
+        self.registry().conditions[condition as usize].mutex
+    }
+
+    /// Wakes up one (or all) of the workers waiting for a condition.
+    pub fn notify(&self, condition: ConditionId, all: bool) -> Result<(), WaitError> {
+        if !self.owns(self.condition_mutex(condition)) {
+            return Err(WaitError::NotOwner);
+        }
+        let mut registry = self.registry();
+        let data = &mut registry.conditions[condition as usize];
+        let count = if all { data.waiting.len() } else { 1 };
+        let count = count.min(data.waiting.len());
+        let woken: Vec<_> = data.waiting.drain(..count).collect();
+        data.notified.extend(woken);
+        Ok(())
+    }
+
+    /// Releases the mutex of a condition and blocks until the condition is notified, or until
+    /// `timeout` passed. The mutex is then locked again.
+    ///
+    /// Returns whether the condition was notified.
+    pub fn wait(
+        &self,
+        condition: ConditionId,
+        timeout: Option<Duration>,
+        limit: Instant,
+    ) -> Result<bool, WaitError> {
+        let mutex = self.condition_mutex(condition);
+        if !self.owns(mutex) {
+            return Err(WaitError::NotOwner);
+        }
+
+        let count = {
+            let mut registry = self.registry();
+            registry.conditions[condition as usize]
+                .waiting
+                .push(self.id);
+            let data = &mut registry.mutexes[mutex as usize];
+            data.owner = None;
+            std::mem::take(&mut data.count)
+        };
+
+        let block = Block::Condition {
+            condition,
+            mutex,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+        };
+        let result = self.block(block, limit);
+
+        let mut registry = self.registry();
+        let data = &mut registry.conditions[condition as usize];
+        let notified = data.notified.contains(&self.id);
+        data.waiting.retain(|&id| id != self.id);
+        data.notified.retain(|&id| id != self.id);
+
+        // The mutex must be owned again before returning, even if the wait failed, so that
+        // `finally` blocks can unlock it.
+        if registry.is_lockable(self.id, mutex) {
+            let data = &mut registry.mutexes[mutex as usize];
+            data.owner = Some(self.id);
+            data.count = count;
+        }
+        result.map(|()| notified)
+    }
+
+    /// Blocks until this worker can continue.
+    ///
+    /// Other workers are suspended, giving their turn back to the primordial worker until a later
+    /// frame of it finds that they can continue. The primordial worker can't be suspended, so it
+    /// gives turns to the other workers instead, until it can continue, or until `limit` passed.
+    fn block(&self, block: Block, limit: Instant) -> Result<(), WaitError> {
+        if self.registry().can_continue(self.id, block) {
+            return Ok(());
+        }
+
+        if !self.is_primordial() {
+            let mut registry = self.registry();
+            registry.workers[self.id as usize].blocked = Some(block);
+            registry.turn = PRIMORDIAL_WORKER;
+            self.shared.turn_changed.notify_all();
+            while registry.turn != self.id {
+                registry = self
+                    .shared
+                    .turn_changed
+                    .wait(registry)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+            let data = &mut registry.workers[self.id as usize];
+            data.blocked = None;
+            return match data.state {
+                WorkerState::Terminated => Err(WaitError::Terminated),
+                _ => Ok(()),
+            };
+        }
+
+        loop {
+            let ran = self.run_others();
+            if self.registry().can_continue(self.id, block) {
+                return Ok(());
+            }
+            if Instant::now() >= limit {
+                return Err(WaitError::Timeout);
+            }
+            if !ran {
+                return match block {
+                    // Nobody else can notify the condition, so waiting for the timeout to pass
+                    // wouldn't change anything.
+                    Block::Condition {
+                        deadline: Some(_), ..
+                    } => Ok(()),
+                    _ => Err(WaitError::Deadlock),
+                };
+            }
+        }
+    }
+
+    /// Gives a turn to every other worker that can run, to run a frame or to continue the frame
+    /// it was suspended in, returning whether any did.
+    pub fn run_others(&self) -> bool {
+        let count = self.registry().workers.len() as WorkerId;
+        let mut ran = false;
+        for worker in (0..count).filter(|&worker| worker != self.id) {
+            ran |= self.give_turn(worker);
+        }
+        ran
+    }
+}
+
+impl Default for Workers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Workers {
+    fn drop(&mut self) {
+        if self.is_primordial() {
+            // Terminate all other workers, and give their threads turns until they ended.
+            {
+                let mut registry = self.registry();
+                for worker in registry.workers.iter_mut().skip(1) {
+                    worker.state = WorkerState::Terminated;
+                    worker.events.clear();
+                }
+            }
+            while self.run_others() {}
+        }
+    }
+}
+
+/// Locks a mutex, ignoring whether another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+struct WorkerLogBackend {
+    shared: Arc<Shared>,
+}
+
+impl LogBackend for WorkerLogBackend {
+    fn avm_trace(&self, message: &str) {
+        lock(&self.shared.registry).traces.push(message.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit() -> Instant {
+        Instant::now() + Duration::from_secs(10)
+    }
+
+    impl Workers {
+        fn view(&self, worker: WorkerId) -> Workers {
+            Workers {
+                shared: self.shared.clone(),
+                id: worker,
+            }
+        }
+    }
+
+    #[test]
+    fn channel_closes_once_drained() {
+        let primordial = Workers::new();
+        let worker = primordial.view(primordial.create_worker());
+        let channel = primordial.create_channel(PRIMORDIAL_WORKER, worker.id());
+
+        assert!(primordial.send(channel, SharedValue::Worker(PRIMORDIAL_WORKER)));
+        primordial.close(channel);
+        assert_eq!(primordial.channel_state(channel), ChannelState::Closing);
+        assert!(!primordial.send(channel, SharedValue::Worker(PRIMORDIAL_WORKER)));
+        assert_eq!(
+            worker.take_events(),
+            vec![
+                WorkerEvent::ChannelMessage(channel),
+                WorkerEvent::ChannelState(channel)
+            ]
+        );
+
+        assert!(matches!(
+            worker.receive(channel),
+            Some(SharedValue::Worker(PRIMORDIAL_WORKER))
+        ));
+        assert_eq!(worker.channel_state(channel), ChannelState::Closed);
+        assert!(worker.receive(channel).is_none());
+    }
+
+    #[test]
+    fn mutex_is_reentrant_and_exclusive() {
+        let primordial = Workers::new();
+        let worker = primordial.view(primordial.create_worker());
+        let mutex = primordial.create_mutex();
+
+        assert!(primordial.try_lock(mutex));
+        assert!(primordial.try_lock(mutex));
+        assert!(!worker.try_lock(mutex));
+        assert!(!worker.unlock(mutex));
+        assert!(primordial.unlock(mutex));
+        assert!(!worker.try_lock(mutex));
+        assert!(primordial.unlock(mutex));
+        assert!(worker.try_lock(mutex));
+    }
+
+    #[test]
+    fn blocking_without_other_workers_deadlocks() {
+        let primordial = Workers::new();
+        let worker = primordial.view(primordial.create_worker());
+        let mutex = primordial.create_mutex();
+        let condition = primordial.create_condition(mutex);
+
+        assert_eq!(
+            primordial.wait(condition, None, limit()),
+            Err(WaitError::NotOwner)
+        );
+        assert!(worker.try_lock(mutex));
+        // The worker has no thread, so it can never release the mutex.
+        assert_eq!(primordial.lock(mutex, limit()), Err(WaitError::Deadlock));
+        assert!(worker.unlock(mutex));
+        assert_eq!(primordial.lock(mutex, limit()), Ok(()));
+
+        // Nobody else can notify the condition, but the mutex is owned again after timing out.
+        assert_eq!(
+            primordial.wait(condition, Some(Duration::from_millis(1)), limit()),
+            Ok(false)
+        );
+        assert_eq!(
+            primordial.wait(condition, None, limit()),
+            Err(WaitError::Deadlock)
+        );
+        assert!(primordial.unlock(mutex));
+        assert!(worker.try_lock(mutex));
+    }
+
+    #[test]
+    fn notify_wakes_waiting_workers() {
+        let primordial = Workers::new();
+        let worker = primordial.create_worker();
+        let mutex = primordial.create_mutex();
+        let condition = primordial.create_condition(mutex);
+
+        assert_eq!(
+            primordial.notify(condition, false),
+            Err(WaitError::NotOwner)
+        );
+        // Simulate the worker waiting from within its own frame.
+        primordial.registry().conditions[condition as usize]
+            .waiting
+            .push(worker);
+        assert!(primordial.try_lock(mutex));
+        assert_eq!(primordial.notify(condition, true), Ok(()));
+        let block = Block::Condition {
+            condition,
+            mutex,
+            deadline: None,
+        };
+        assert!(!primordial.registry().can_continue(worker, block));
+        assert!(primordial.unlock(mutex));
+        assert!(primordial.registry().can_continue(worker, block));
+    }
+
+    #[test]
+    fn terminated_workers_are_not_listed() {
+        let primordial = Workers::new();
+        let worker = primordial.create_worker();
+        assert_eq!(primordial.state(worker), WorkerState::New);
+        assert!(primordial.start(worker));
+        assert!(!primordial.start(worker));
+        assert_eq!(primordial.list(), vec![PRIMORDIAL_WORKER, worker]);
+        assert!(primordial.terminate(worker));
+        assert!(!primordial.terminate(PRIMORDIAL_WORKER));
+        assert_eq!(primordial.list(), vec![PRIMORDIAL_WORKER]);
+        assert_eq!(
+            primordial.take_events(),
+            vec![WorkerEvent::State(worker), WorkerEvent::State(worker)]
+        );
+    }
+
+    /// Spawns a worker whose frames lock `mutex`, logging what they do.
+    fn spawn_locking_worker(
+        primordial: &Workers,
+        mutex: MutexId,
+        log: &Arc<Mutex<Vec<String>>>,
+    ) -> WorkerId {
+        let worker = primordial.create_worker();
+        let log = log.clone();
+        primordial
+            .spawn(worker, move |workers| {
+                move || {
+                    log.lock().unwrap().push("locking".into());
+                    let result = workers.lock(mutex, limit());
+                    log.lock().unwrap().push(format!("{result:?}"));
+                    if result.is_ok() {
+                        workers.unlock(mutex);
+                    }
+                }
+            })
+            .unwrap();
+        worker
+    }
+
+    #[test]
+    fn blocked_worker_is_suspended_until_it_can_continue() {
+        let primordial = Workers::new();
+        let mutex = primordial.create_mutex();
+        let log = Arc::new(Mutex::new(vec![]));
+        let worker = spawn_locking_worker(&primordial, mutex, &log);
+
+        // New workers don't run.
+        assert!(!primordial.run_others());
+        assert!(primordial.start(worker));
+
+        assert!(primordial.try_lock(mutex));
+        assert!(primordial.run_others());
+        assert_eq!(*log.lock().unwrap(), ["locking"]);
+
+        // The worker stays suspended while the mutex is locked.
+        assert!(!primordial.run_others());
+        assert_eq!(*log.lock().unwrap(), ["locking"]);
+
+        assert!(primordial.unlock(mutex));
+        assert!(primordial.run_others());
+        assert_eq!(*log.lock().unwrap(), ["locking", "Ok(())"]);
+        assert!(!primordial.owns(mutex));
+        assert!(primordial.try_lock(mutex));
+    }
+
+    #[test]
+    fn primordial_worker_runs_others_until_it_can_continue() {
+        let primordial = Workers::new();
+        let mutex = primordial.create_mutex();
+        let condition = primordial.create_condition(mutex);
+        let worker = primordial.create_worker();
+        primordial
+            .spawn(worker, move |workers| {
+                move || {
+                    workers.lock(mutex, limit()).unwrap();
+                    workers.notify(condition, false).unwrap();
+                    workers.unlock(mutex);
+                }
+            })
+            .unwrap();
+        assert!(primordial.start(worker));
+
+        assert!(primordial.try_lock(mutex));
+        assert_eq!(primordial.wait(condition, None, limit()), Ok(true));
+        assert!(primordial.owns(mutex));
+    }
+
+    #[test]
+    fn primordial_worker_deadlocks_on_suspended_workers() {
+        let primordial = Workers::new();
+        let mutex = primordial.create_mutex();
+        let log = Arc::new(Mutex::new(vec![]));
+        let worker = spawn_locking_worker(&primordial, mutex, &log);
+        assert!(primordial.start(worker));
+
+        let other_mutex = primordial.create_mutex();
+        assert!(primordial.view(worker).try_lock(other_mutex));
+        assert!(primordial.try_lock(mutex));
+        assert_eq!(
+            primordial.lock(other_mutex, limit()),
+            Err(WaitError::Deadlock)
+        );
+        assert_eq!(*log.lock().unwrap(), ["locking"]);
+    }
+
+    #[test]
+    fn terminated_worker_threads_end() {
+        let primordial = Workers::new();
+        let mutex = primordial.create_mutex();
+        let log = Arc::new(Mutex::new(vec![]));
+        let worker = spawn_locking_worker(&primordial, mutex, &log);
+        assert!(primordial.start(worker));
+
+        assert!(primordial.try_lock(mutex));
+        assert!(primordial.run_others());
+        assert!(primordial.terminate(worker));
+        while primordial.run_others() {}
+
+        assert_eq!(*log.lock().unwrap(), ["locking", "Err(Terminated)"]);
+        assert!(!primordial.registry().workers[worker as usize].has_thread);
+    }
+}
@@ -32,4 +32,8 @@ debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-open-heap-snapshots = Heap Snapshots
+debug-menu-open-avm1-profiler = AVM1 Profiler
+debug-menu-open-avm2-profiler = AVM2 Profiler
+debug-menu-show-redraw-regions = Show Redraw Regions
 
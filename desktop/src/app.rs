@@ -1,5 +1,6 @@
 use crate::cli::Opt;
 use crate::custom_event::RuffleEvent;
+use crate::debugger::DebugServer;
 use crate::gui::{GuiController, MENU_HEIGHT};
 use crate::player::{PlayerController, PlayerOptions};
 use crate::util::{
@@ -14,7 +15,7 @@ use std::rc::Rc;
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
-use winit::event::{ElementState, KeyEvent, Modifiers, WindowEvent};
+use winit::event::{ElementState, Ime, KeyEvent, Modifiers, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop, EventLoopBuilder};
 use winit::keyboard::{Key, NamedKey};
 use winit::window::{Fullscreen, Icon, Window, WindowBuilder};
@@ -55,11 +56,22 @@ impl App {
 
         let mut gui = GuiController::new(window.clone(), &event_loop, &opt, &font_database)?;
 
+        let debug_server = opt
+            .debug_port
+            .and_then(|port| match DebugServer::start(port) {
+                Ok(server) => Some(server),
+                Err(e) => {
+                    tracing::error!("Couldn't listen for debuggers on port {port}: {e}");
+                    None
+                }
+            });
+
         let mut player = PlayerController::new(
             event_loop.create_proxy(),
             window.clone(),
             gui.descriptors().clone(),
             font_database,
+            debug_server,
         );
 
         if let Some(movie_url) = movie_url {
@@ -337,6 +349,16 @@ impl App {
                             };
                             check_redraw = true;
                         }
+                        WindowEvent::Ime(ime) => {
+                            use ruffle_core::events::ImeEvent;
+                            let event = match ime {
+                                Ime::Preedit(text, cursor) => ImeEvent::Preedit(text, cursor),
+                                Ime::Commit(text) => ImeEvent::Commit(text),
+                                Ime::Enabled | Ime::Disabled => return,
+                            };
+                            self.player.handle_event(PlayerEvent::Ime(event));
+                            check_redraw = true;
+                        }
                         _ => (),
                     }
                 }
@@ -3,12 +3,14 @@ use arboard::Clipboard;
 use chrono::{DateTime, Utc};
 use fontdb::Family;
 use rfd::{
-    AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
+    AsyncFileDialog, AsyncMessageDialog, FileHandle, MessageButtons, MessageDialog,
+    MessageDialogResult, MessageLevel,
 };
 use ruffle_core::backend::navigator::OpenURLMode;
 use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    FullscreenError, LanguageIdentifier, MouseCursor, ScriptTimeoutChoice,
+    ScriptTimeoutPromptFuture, UiBackend, US_ENGLISH,
 };
 use std::rc::Rc;
 use sys_locale::get_locale;
@@ -124,6 +126,8 @@ pub struct DesktopUiBackend {
     font_database: Rc<fontdb::Database>,
     /// Is a dialog currently open
     dialog_open: bool,
+    /// Is the platform input method editor currently allowed
+    ime_enabled: bool,
 }
 
 impl DesktopUiBackend {
@@ -145,6 +149,7 @@ impl DesktopUiBackend {
             open_url_mode,
             dialog_open: false,
             font_database,
+            ime_enabled: false,
         })
     }
 
@@ -164,6 +169,10 @@ impl DesktopUiBackend {
 
 const DOWNLOAD_FAILED_MESSAGE: &str = "Ruffle failed to open or download this file.";
 
+const SCRIPT_TIMEOUT_MESSAGE: &str = "A script in this movie is causing Ruffle to run slowly. \
+    If it continues to run, your computer may become unresponsive.\n\n\
+    Do you want to abort the script?";
+
 impl UiBackend for DesktopUiBackend {
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
@@ -309,6 +318,17 @@ impl UiBackend for DesktopUiBackend {
     // Unused on desktop
     fn open_virtual_keyboard(&self) {}
 
+    fn set_ime_enabled(&mut self, enabled: bool) {
+        if self.ime_enabled != enabled {
+            self.ime_enabled = enabled;
+            self.window.set_ime_allowed(enabled);
+        }
+    }
+
+    fn ime_enabled(&self) -> bool {
+        self.ime_enabled
+    }
+
     fn language(&self) -> &LanguageIdentifier {
         &self.language
     }
@@ -374,4 +394,23 @@ impl UiBackend for DesktopUiBackend {
     fn close_file_dialog(&mut self) {
         self.dialog_open = false;
     }
+
+    fn display_script_timeout_prompt(&mut self) -> Option<ScriptTimeoutPromptFuture> {
+        Some(Box::pin(async move {
+            let result = AsyncMessageDialog::new()
+                .set_level(MessageLevel::Warning)
+                .set_title("Ruffle - Script timeout")
+                .set_description(SCRIPT_TIMEOUT_MESSAGE)
+                .set_buttons(MessageButtons::YesNo)
+                .show()
+                .await;
+
+            let choice = if result == MessageDialogResult::Yes {
+                ScriptTimeoutChoice::Abort
+            } else {
+                ScriptTimeoutChoice::Continue
+            };
+            Ok(choice)
+        }))
+    }
 }
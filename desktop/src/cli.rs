@@ -137,6 +137,20 @@ pub struct Opt {
     #[clap(long)]
     pub dummy_external_interface: bool,
 
+    /// Listen on this local port for debuggers that use the Debug Adapter Protocol, such as IDEs.
+    #[clap(long)]
+    pub debug_port: Option<u16>,
+
+    /// Run AVM2 code exactly as it was compiled, without optimizing it first.
+    /// This is slower, but can help when debugging scripts or the optimizer itself.
+    #[clap(long)]
+    pub no_avm2_optimizer: bool,
+
+    /// Run AVM2 code with the stack interpreter instead of compiling it to registers first.
+    /// This is slower, but can help when the register interpreter misbehaves.
+    #[clap(long)]
+    pub no_avm2_register_ir: bool,
+
     /// Hides the menu bar (the bar at the top of the window).
     #[clap(long)]
     pub no_gui: bool,
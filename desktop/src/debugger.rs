@@ -0,0 +1,512 @@
+//! A Debug Adapter Protocol server, which lets IDEs debug the scripts of the running movie.
+//!
+//! A single client can attach at a time. It keeps its session when another movie is opened, but
+//! its breakpoints need to be set again, as every player has its own debugger.
+
+use ruffle_core::debugger::{
+    BreakpointLocation, DebugCommand, DebugMessage, DebuggerConnection, StackFrame, Variable,
+};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The only thread that is reported to clients, as all scripts run on the same one.
+const THREAD_ID: u64 = 1;
+
+/// How long to wait for messages from the player before checking whether the session ended.
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The ends of the channels to the current player.
+#[derive(Default)]
+struct Shared {
+    commands: Mutex<Option<Sender<DebugCommand>>>,
+    messages: Mutex<Option<Receiver<DebugMessage>>>,
+
+    /// Increases whenever another player is connected, as requests sent to the previous one
+    /// will never be answered.
+    generation: AtomicU64,
+}
+
+pub struct DebugServer {
+    shared: Arc<Shared>,
+}
+
+impl DebugServer {
+    /// Starts listening for clients on a local port.
+    pub fn start(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let shared = Arc::new(Shared::default());
+        let server_shared = shared.clone();
+        thread::Builder::new()
+            .name("Debug server".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    let result = stream.and_then(|stream| Session::run(&server_shared, stream));
+                    if let Err(e) = result {
+                        tracing::warn!("Debugger session ended with an error: {e}");
+                    }
+                }
+            })?;
+        tracing::info!("Listening for debuggers on port {port}");
+        Ok(Self { shared })
+    }
+
+    /// Creates the connection of a new player, which replaces the previous one.
+    pub fn connect_player(&self) -> Box<dyn DebuggerConnection> {
+        let (command_sender, command_receiver) = mpsc::channel();
+        let (message_sender, message_receiver) = mpsc::channel();
+        *self
+            .shared
+            .commands
+            .lock()
+            .expect("Lock must not be poisoned") = Some(command_sender);
+        *self
+            .shared
+            .messages
+            .lock()
+            .expect("Lock must not be poisoned") = Some(message_receiver);
+        self.shared.generation.fetch_add(1, Ordering::SeqCst);
+        Box::new(PlayerConnection {
+            commands: command_receiver,
+            messages: message_sender,
+        })
+    }
+}
+
+struct PlayerConnection {
+    commands: Receiver<DebugCommand>,
+    messages: Sender<DebugMessage>,
+}
+
+impl DebuggerConnection for PlayerConnection {
+    fn try_receive(&mut self) -> Option<DebugCommand> {
+        self.commands.try_recv().ok()
+    }
+
+    fn receive(&mut self) -> Option<DebugCommand> {
+        self.commands.recv().ok()
+    }
+
+    fn send(&mut self, message: DebugMessage) {
+        let _ = self.messages.send(message);
+    }
+}
+
+/// A request that was forwarded to the player, and waits for its reply.
+struct Pending {
+    request: Value,
+    generation: u64,
+}
+
+struct Session {
+    shared: Arc<Shared>,
+    writer: Mutex<TcpStream>,
+    seq: AtomicU64,
+    pending: Mutex<VecDeque<Pending>>,
+    closed: AtomicBool,
+}
+
+impl Session {
+    fn run(shared: &Arc<Shared>, stream: TcpStream) -> io::Result<()> {
+        // Drop any replies that were meant for the previous client.
+        if let Some(messages) = &*shared.messages.lock().expect("Lock must not be poisoned") {
+            while messages.try_recv().is_ok() {}
+        }
+
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let session = Arc::new(Session {
+            shared: shared.clone(),
+            writer: Mutex::new(stream),
+            seq: AtomicU64::new(1),
+            pending: Mutex::new(VecDeque::new()),
+            closed: AtomicBool::new(false),
+        });
+
+        let replies = {
+            let session = session.clone();
+            thread::Builder::new()
+                .name("Debug server replies".to_string())
+                .spawn(move || session.forward_messages())?
+        };
+
+        let result = session.handle_requests(&mut reader);
+        session.closed.store(true, Ordering::SeqCst);
+        let _ = replies.join();
+        result
+    }
+
+    fn handle_requests(&self, reader: &mut impl BufRead) -> io::Result<()> {
+        while let Some(request) = read_message(reader)? {
+            let disconnect = request["command"] == "disconnect";
+            self.handle_request(request)?;
+            if disconnect {
+                break;
+            }
+        }
+
+        // Don't leave the movie paused when the client goes away.
+        self.forward_command(DebugCommand::Disconnect);
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Value) -> io::Result<()> {
+        let arguments = &request["arguments"];
+        let command = match request["command"].as_str().unwrap_or_default() {
+            "initialize" => {
+                self.respond(
+                    &request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsFunctionBreakpoints": true,
+                        "supportsInstructionBreakpoints": true,
+                        "supportsEvaluateForHovers": true,
+                    }),
+                )?;
+                return self.send_event("initialized", json!({}));
+            }
+            "launch" | "attach" | "configurationDone" => {
+                return self.respond(&request, Value::Null);
+            }
+            "threads" => {
+                return self.respond(
+                    &request,
+                    json!({ "threads": [{ "id": THREAD_ID, "name": "Main" }] }),
+                );
+            }
+            "setBreakpoints" => {
+                let source = &arguments["source"];
+                let file = source["path"]
+                    .as_str()
+                    .or_else(|| source["name"].as_str())
+                    .unwrap_or_default();
+                let locations = array(&arguments["breakpoints"])
+                    .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                    .map(|line| BreakpointLocation::Line {
+                        file: file.to_string(),
+                        line: line as u32,
+                    })
+                    .collect();
+                DebugCommand::SetBreakpoints {
+                    group: format!("source:{file}"),
+                    locations,
+                }
+            }
+            "setFunctionBreakpoints" => {
+                let locations = array(&arguments["breakpoints"])
+                    .filter_map(|breakpoint| breakpoint["name"].as_str())
+                    .map(|name| BreakpointLocation::Method {
+                        method: name.to_string(),
+                        offset: 0,
+                    })
+                    .collect();
+                DebugCommand::SetBreakpoints {
+                    group: "function".to_string(),
+                    locations,
+                }
+            }
+            "setInstructionBreakpoints" => {
+                let locations = array(&arguments["breakpoints"])
+                    .filter_map(|breakpoint| {
+                        parse_instruction_reference(
+                            breakpoint["instructionReference"].as_str()?,
+                            breakpoint["offset"].as_i64().unwrap_or_default(),
+                        )
+                    })
+                    .collect();
+                DebugCommand::SetBreakpoints {
+                    group: "instruction".to_string(),
+                    locations,
+                }
+            }
+            "continue" => DebugCommand::Continue,
+            "pause" => DebugCommand::Pause,
+            "next" => DebugCommand::StepOver,
+            "stepIn" => DebugCommand::StepIn,
+            "stepOut" => DebugCommand::StepOut,
+            "stackTrace" => DebugCommand::StackTrace,
+            // Only the innermost frame can be inspected.
+            "scopes" => DebugCommand::Scopes,
+            "variables" => DebugCommand::Variables {
+                reference: arguments["variablesReference"].as_u64().unwrap_or_default() as u32,
+            },
+            "evaluate" => DebugCommand::Evaluate {
+                expression: arguments["expression"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            },
+            "disconnect" => DebugCommand::Disconnect,
+            command => {
+                return self.respond_error(&request, &format!("Unsupported request '{command}'"));
+            }
+        };
+
+        let generation = self.shared.generation.load(Ordering::SeqCst);
+        let mut pending = self.pending.lock().expect("Lock must not be poisoned");
+        pending.push_back(Pending {
+            request,
+            generation,
+        });
+        if !self.forward_command(command) {
+            let request = pending.pop_back().expect("Request was just queued").request;
+            drop(pending);
+            return self.respond_error(&request, "No movie is running");
+        }
+        Ok(())
+    }
+
+    /// Sends a command to the current player, and returns whether there is one.
+    fn forward_command(&self, command: DebugCommand) -> bool {
+        let commands = self
+            .shared
+            .commands
+            .lock()
+            .expect("Lock must not be poisoned");
+        commands
+            .as_ref()
+            .is_some_and(|commands| commands.send(command).is_ok())
+    }
+
+    /// Turns messages from the player into responses and events, until the session ends.
+    fn forward_messages(&self) {
+        while !self.closed.load(Ordering::SeqCst) {
+            let messages = self
+                .shared
+                .messages
+                .lock()
+                .expect("Lock must not be poisoned");
+            let message = match messages.as_ref().map(|m| m.recv_timeout(POLL_TIMEOUT)) {
+                Some(Ok(message)) => message,
+                Some(Err(RecvTimeoutError::Timeout)) => continue,
+                Some(Err(RecvTimeoutError::Disconnected)) | None => {
+                    drop(messages);
+                    thread::sleep(POLL_TIMEOUT);
+                    continue;
+                }
+            };
+            drop(messages);
+
+            if let Err(e) = self.forward_message(message) {
+                tracing::warn!("Couldn't send message to debugger: {e}");
+            }
+        }
+    }
+
+    fn forward_message(&self, message: DebugMessage) -> io::Result<()> {
+        if let DebugMessage::Stopped { reason } = message {
+            return self.send_event(
+                "stopped",
+                json!({
+                    "reason": reason.as_str(),
+                    "threadId": THREAD_ID,
+                    "allThreadsStopped": true,
+                }),
+            );
+        }
+
+        let generation = self.shared.generation.load(Ordering::SeqCst);
+        let request = loop {
+            let Some(pending) = self
+                .pending
+                .lock()
+                .expect("Lock must not be poisoned")
+                .pop_front()
+            else {
+                return Ok(());
+            };
+            if pending.generation == generation {
+                break pending.request;
+            }
+            self.respond_error(&pending.request, "The movie was closed")?;
+        };
+
+        match message {
+            DebugMessage::Stopped { .. } => unreachable!(),
+            DebugMessage::Done => {
+                if request["command"] == "setBreakpoints" {
+                    let breakpoints: Vec<_> = array(&request["arguments"]["breakpoints"])
+                        .map(|breakpoint| json!({ "verified": true, "line": breakpoint["line"] }))
+                        .collect();
+                    self.respond(&request, json!({ "breakpoints": breakpoints }))
+                } else if request["command"]
+                    .as_str()
+                    .is_some_and(|c| c.ends_with("Breakpoints"))
+                {
+                    let breakpoints: Vec<_> = array(&request["arguments"]["breakpoints"])
+                        .map(|_| json!({ "verified": true }))
+                        .collect();
+                    self.respond(&request, json!({ "breakpoints": breakpoints }))
+                } else if request["command"] == "continue" {
+                    self.respond(&request, json!({ "allThreadsContinued": true }))
+                } else {
+                    self.respond(&request, Value::Null)
+                }
+            }
+            DebugMessage::StackTrace(frames) => {
+                let frames: Vec<_> = frames.iter().enumerate().map(stack_frame).collect();
+                let total = frames.len();
+                self.respond(
+                    &request,
+                    json!({ "stackFrames": frames, "totalFrames": total }),
+                )
+            }
+            DebugMessage::Variables(variables) if request["command"] == "scopes" => {
+                let scopes: Vec<_> = variables
+                    .iter()
+                    .map(|scope| {
+                        json!({
+                            "name": scope.name,
+                            "variablesReference": scope.reference,
+                            "expensive": false,
+                        })
+                    })
+                    .collect();
+                self.respond(&request, json!({ "scopes": scopes }))
+            }
+            DebugMessage::Variables(variables) => {
+                let variables: Vec<_> = variables.iter().map(variable).collect();
+                self.respond(&request, json!({ "variables": variables }))
+            }
+            DebugMessage::Evaluated(Ok(result)) => self.respond(
+                &request,
+                json!({
+                    "result": result.value,
+                    "type": result.type_name,
+                    "variablesReference": result.reference,
+                }),
+            ),
+            DebugMessage::Evaluated(Err(e)) => self.respond_error(&request, &e),
+        }
+    }
+
+    fn respond(&self, request: &Value, body: Value) -> io::Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": true,
+            "body": body,
+        }))
+    }
+
+    fn respond_error(&self, request: &Value, message: &str) -> io::Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": false,
+            "message": message,
+        }))
+    }
+
+    fn send_event(&self, event: &str, body: Value) -> io::Result<()> {
+        self.send(json!({
+            "type": "event",
+            "event": event,
+            "body": body,
+        }))
+    }
+
+    fn send(&self, mut message: Value) -> io::Result<()> {
+        message["seq"] = self.seq.fetch_add(1, Ordering::SeqCst).into();
+        let content = message.to_string();
+        let mut writer = self.writer.lock().expect("Lock must not be poisoned");
+        write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+        writer.flush()
+    }
+}
+
+/// Reads a message with its `Content-Length` header, or returns `None` at the end of the stream.
+fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Missing Content-Length header",
+        ));
+    };
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn array(value: &Value) -> impl Iterator<Item = &Value> {
+    value.as_array().into_iter().flatten()
+}
+
+/// Instruction references are `avm1:<movie URL>@<offset>` or `avm2:<method name>@<offset>`.
+fn instruction_reference(location: &BreakpointLocation) -> Option<String> {
+    match location {
+        BreakpointLocation::Action { swf, offset } => Some(format!("avm1:{swf}@{offset}")),
+        BreakpointLocation::Method { method, offset } => Some(format!("avm2:{method}@{offset}")),
+        BreakpointLocation::Line { .. } => None,
+    }
+}
+
+fn parse_instruction_reference(reference: &str, extra_offset: i64) -> Option<BreakpointLocation> {
+    let (location, offset) = reference.rsplit_once('@')?;
+    let offset = offset.parse::<i64>().ok()?.checked_add(extra_offset)?;
+    if let Some(swf) = location.strip_prefix("avm1:") {
+        Some(BreakpointLocation::Action {
+            swf: swf.to_string(),
+            offset: offset.try_into().ok()?,
+        })
+    } else {
+        Some(BreakpointLocation::Method {
+            method: location.strip_prefix("avm2:")?.to_string(),
+            offset: offset.try_into().ok()?,
+        })
+    }
+}
+
+fn stack_frame((id, frame): (usize, &StackFrame)) -> Value {
+    let mut value = json!({
+        "id": id,
+        "name": frame.name,
+        "line": frame.line.unwrap_or_default(),
+        "column": 0,
+    });
+    if let Some(file) = &frame.file {
+        // AVM2 debug information separates the source root and the package with `;`.
+        let path = file.replace(';', std::path::MAIN_SEPARATOR_STR);
+        let name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+        value["source"] = json!({ "name": name, "path": path });
+    }
+    if let Some(reference) = frame.location.as_ref().and_then(instruction_reference) {
+        value["instructionPointerReference"] = reference.into();
+    }
+    value
+}
+
+fn variable(variable: &Variable) -> Value {
+    json!({
+        "name": variable.name,
+        "value": variable.value,
+        "type": variable.type_name,
+        "variablesReference": variable.reference,
+    })
+}
@@ -313,6 +313,31 @@ impl RuffleGui {
                                 player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
                             }
                         }
+                        if Button::new(text(&self.locale, "debug-menu-open-heap-snapshots")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowHeapSnapshots);
+                            }
+                        }
+                        if Button::new(text(&self.locale, "debug-menu-open-avm1-profiler")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowAvm1Profiler);
+                            }
+                        }
+                        if Button::new(text(&self.locale, "debug-menu-open-avm2-profiler")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowAvm2Profiler);
+                            }
+                        }
+                        let mut show_redraw_regions = player.as_ref().is_some_and(|player| player.show_redraw_regions());
+                        if ui.checkbox(&mut show_redraw_regions, text(&self.locale, "debug-menu-show-redraw-regions")).changed() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.set_show_redraw_regions(show_redraw_regions);
+                            }
+                        }
                     });
                 });
                 menu::menu_button(ui, text(&self.locale, "help-menu"), |ui| {
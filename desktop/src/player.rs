@@ -4,12 +4,14 @@ use crate::backends::{
 };
 use crate::cli::Opt;
 use crate::custom_event::RuffleEvent;
+use crate::debugger::DebugServer;
 use crate::executor::WinitAsyncExecutor;
 use crate::gui::MovieView;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
 use ruffle_core::config::Letterbox;
+use ruffle_core::debugger::DebuggerConnection;
 use ruffle_core::{
     DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent, PlayerRuntime, StageAlign,
     StageScaleMode,
@@ -49,6 +51,8 @@ pub struct PlayerOptions {
     pub spoof_url: Option<Url>,
     pub player_version: u8,
     pub player_runtime: PlayerRuntime,
+    pub avm2_optimizer: bool,
+    pub avm2_register_ir: bool,
     pub frame_rate: Option<f64>,
     pub open_url_mode: OpenURLMode,
     pub dummy_external_interface: bool,
@@ -74,6 +78,8 @@ impl From<&Opt> for PlayerOptions {
             spoof_url: value.spoof_url.clone(),
             player_version: value.player_version.unwrap_or(32),
             player_runtime: value.player_runtime,
+            avm2_optimizer: !value.no_avm2_optimizer,
+            avm2_register_ir: !value.no_avm2_register_ir,
             frame_rate: value.frame_rate,
             open_url_mode: value.open_url_mode,
             dummy_external_interface: value.dummy_external_interface,
@@ -99,6 +105,7 @@ impl ActivePlayer {
         descriptors: Arc<Descriptors>,
         movie_view: MovieView,
         font_database: Rc<fontdb::Database>,
+        debugger: Option<Box<dyn DebuggerConnection>>,
     ) -> Self {
         let mut builder = PlayerBuilder::new();
 
@@ -139,6 +146,10 @@ impl ActivePlayer {
             }));
         }
 
+        if let Some(debugger) = debugger {
+            builder = builder.with_debugger(debugger);
+        }
+
         let max_execution_duration = if opt.max_execution_duration == f64::INFINITY {
             Duration::MAX
         } else {
@@ -169,6 +180,8 @@ impl ActivePlayer {
             .with_page_url(opt.spoof_url.clone().map(|url| url.to_string()))
             .with_player_version(Some(opt.player_version))
             .with_player_runtime(opt.player_runtime)
+            .with_avm2_optimizer(opt.avm2_optimizer)
+            .with_avm2_register_ir(opt.avm2_register_ir)
             .with_frame_rate(opt.frame_rate);
         let player = builder.build();
 
@@ -265,6 +278,7 @@ pub struct PlayerController {
     window: Rc<Window>,
     descriptors: Arc<Descriptors>,
     font_database: Rc<fontdb::Database>,
+    debug_server: Option<DebugServer>,
 }
 
 impl PlayerController {
@@ -273,6 +287,7 @@ impl PlayerController {
         window: Rc<Window>,
         descriptors: Arc<Descriptors>,
         font_database: fontdb::Database,
+        debug_server: Option<DebugServer>,
     ) -> Self {
         Self {
             player: None,
@@ -280,6 +295,7 @@ impl PlayerController {
             window,
             descriptors,
             font_database: Rc::new(font_database),
+            debug_server,
         }
     }
 
@@ -292,6 +308,7 @@ impl PlayerController {
             self.descriptors.clone(),
             movie_view,
             self.font_database.clone(),
+            self.debug_server.as_ref().map(DebugServer::connect_player),
         ));
     }
 
@@ -24,6 +24,9 @@ pub fn winit_to_ruffle_text_control(
         Key::Character("c") if ctrl_cmd => Some(TextControlCode::Copy),
         Key::Character("v") if ctrl_cmd => Some(TextControlCode::Paste),
         Key::Character("x") if ctrl_cmd => Some(TextControlCode::Cut),
+        Key::Character("z" | "Z") if ctrl_cmd && shift => Some(TextControlCode::Redo),
+        Key::Character("z") if ctrl_cmd => Some(TextControlCode::Undo),
+        Key::Character("y") if ctrl_cmd => Some(TextControlCode::Redo),
         Key::Named(NamedKey::Backspace) if ctrl_cmd => Some(TextControlCode::BackspaceWord),
         Key::Named(NamedKey::Backspace) => Some(TextControlCode::Backspace),
         Key::Named(NamedKey::Delete) if ctrl_cmd => Some(TextControlCode::DeleteWord),
@@ -0,0 +1,201 @@
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
+    SoundStreamInfo, SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+use std::io::{self, Write};
+
+/// An audio backend that mixes audio in lockstep with the frames of the movie,
+/// recording it instead of playing it.
+pub struct CaptureAudioBackend {
+    mixer: AudioMixer,
+    frame_rate: f64,
+    /// The number of frames mixed since the frame rate was last set.
+    frames_mixed: u64,
+    /// The number of samples per channel mixed since the frame rate was last set.
+    samples_mixed: u64,
+    buffer: Vec<i16>,
+    /// The recorded samples, interleaved by channel.
+    samples: Vec<i16>,
+}
+
+impl Default for CaptureAudioBackend {
+    fn default() -> Self {
+        Self {
+            mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
+            frame_rate: 1.0,
+            frames_mixed: 0,
+            samples_mixed: 0,
+            buffer: vec![],
+            samples: vec![],
+        }
+    }
+}
+
+impl CaptureAudioBackend {
+    const NUM_CHANNELS: u8 = 2;
+    const SAMPLE_RATE: u32 = 44100;
+
+    /// Discards the audio recorded so far.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Writes the recorded audio as a 16-bit PCM WAV file.
+    pub fn write_wav(&self, mut writer: impl Write) -> io::Result<()> {
+        let channels = u16::from(Self::NUM_CHANNELS);
+        let block_align = channels * 2;
+        let data_len = (self.samples.len() * 2) as u32;
+
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(36 + data_len).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?;
+        writer.write_all(&1u16.to_le_bytes())?; // PCM
+        writer.write_all(&channels.to_le_bytes())?;
+        writer.write_all(&Self::SAMPLE_RATE.to_le_bytes())?;
+        writer.write_all(&(Self::SAMPLE_RATE * u32::from(block_align)).to_le_bytes())?;
+        writer.write_all(&block_align.to_le_bytes())?;
+        writer.write_all(&16u16.to_le_bytes())?;
+
+        writer.write_all(b"data")?;
+        writer.write_all(&data_len.to_le_bytes())?;
+        for sample in &self.samples {
+            writer.write_all(&sample.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl AudioBackend for CaptureAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = frame_rate;
+        self.frames_mixed = 0;
+        self.samples_mixed = 0;
+    }
+
+    fn tick(&mut self) {
+        // Count samples from the start of the frame rate rather than adding up fractions,
+        // so that rounding errors never make the audio drift away from the frames.
+        self.frames_mixed += 1;
+        let total_samples = (self.frames_mixed as f64 * f64::from(Self::SAMPLE_RATE)
+            / self.frame_rate)
+            .round() as u64;
+        let samples = total_samples.saturating_sub(self.samples_mixed);
+        self.samples_mixed += samples;
+
+        self.buffer
+            .resize(samples as usize * usize::from(Self::NUM_CHANNELS), 0);
+        self.mixer.mix::<i16>(&mut self.buffer);
+        self.samples.extend_from_slice(&self.buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The number of samples per channel recorded by `backend`.
+    fn recorded_samples(backend: &CaptureAudioBackend) -> usize {
+        backend.samples.len() / usize::from(CaptureAudioBackend::NUM_CHANNELS)
+    }
+
+    #[test]
+    fn write_wav_header() {
+        let mut backend = CaptureAudioBackend::default();
+        backend.samples = vec![1, -2, 3, -4, 5, -6];
+
+        let mut wav = vec![];
+        backend.write_wav(&mut wav).unwrap();
+
+        let u16_at = |i: usize| u16::from_le_bytes([wav[i], wav[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes([wav[i], wav[i + 1], wav[i + 2], wav[i + 3]]);
+        assert_eq!(wav.len(), 44 + 12);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32_at(4), 36 + 12);
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(&wav[12..16], b"fmt ");
+        assert_eq!(u32_at(16), 16);
+        assert_eq!(u16_at(20), 1, "format should be PCM");
+        assert_eq!(u16_at(22), 2, "channels");
+        assert_eq!(u32_at(24), 44100, "sample rate");
+        assert_eq!(u32_at(28), 44100 * 4, "byte rate");
+        assert_eq!(u16_at(32), 4, "block align");
+        assert_eq!(u16_at(34), 16, "bits per sample");
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(40), 12, "data length");
+        assert_eq!(u16_at(44) as i16, 1);
+        assert_eq!(u16_at(46) as i16, -2);
+        assert_eq!(u16_at(54) as i16, -6);
+    }
+
+    #[test]
+    fn write_wav_without_samples() {
+        let backend = CaptureAudioBackend::default();
+
+        let mut wav = vec![];
+        backend.write_wav(&mut wav).unwrap();
+
+        assert_eq!(wav.len(), 44);
+        assert_eq!(&wav[4..8], &36u32.to_le_bytes());
+        assert_eq!(&wav[40..44], &0u32.to_le_bytes());
+    }
+
+    #[test]
+    fn tick_records_every_sample_without_drifting() {
+        // None of these frame rates divide the sample rate evenly.
+        for frame_rate in [11.0, 24.0, 29.97, 31.0, 60.0] {
+            let mut backend = CaptureAudioBackend::default();
+            backend.set_frame_rate(frame_rate);
+
+            let seconds = 10;
+            let frames = (frame_rate * seconds as f64).round() as usize;
+            for _ in 0..frames {
+                backend.tick();
+            }
+
+            let expected = (frames as f64 * 44100.0 / frame_rate).round() as usize;
+            assert_eq!(
+                recorded_samples(&backend),
+                expected,
+                "{frame_rate} fps for {frames} frames"
+            );
+            if frame_rate.fract() == 0.0 {
+                assert_eq!(recorded_samples(&backend), 44100 * seconds);
+            }
+        }
+    }
+
+    #[test]
+    fn tick_spreads_partial_samples_across_frames() {
+        let mut backend = CaptureAudioBackend::default();
+        backend.set_frame_rate(24.0);
+
+        // 44100 / 24 = 1837.5 samples per frame.
+        let mut per_frame = vec![];
+        for _ in 0..4 {
+            let before = recorded_samples(&backend);
+            backend.tick();
+            per_frame.push(recorded_samples(&backend) - before);
+        }
+        assert_eq!(per_frame.iter().sum::<usize>(), 1837 * 2 + 1838 * 2);
+        assert!(per_frame.iter().all(|n| *n == 1837 || *n == 1838));
+    }
+
+    #[test]
+    fn clear_discards_samples() {
+        let mut backend = CaptureAudioBackend::default();
+        backend.set_frame_rate(30.0);
+        backend.tick();
+        assert_eq!(recorded_samples(&backend), 1470);
+
+        backend.clear();
+        assert_eq!(recorded_samples(&backend), 0);
+    }
+}
@@ -1,3 +1,7 @@
+mod audio;
+mod svg;
+mod video;
+
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use image::RgbaImage;
@@ -5,7 +9,9 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerBuilder;
+use ruffle_core::{Player, PlayerBuilder};
+use ruffle_render_software::backend::SoftwareRenderBackend;
+use ruffle_render_svg::backend::SvgRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -15,7 +21,7 @@ use std::fs::create_dir_all;
 use std::io::{self, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use walkdir::{DirEntry, WalkDir};
 
 #[derive(Parser, Debug, Copy, Clone)]
@@ -31,6 +37,10 @@ struct SizeOpt {
     /// Optionally override the output height
     #[clap(long = "height")]
     height: Option<u32>,
+
+    /// Render at this many times the output size and downscale the result, for smoother edges
+    #[clap(long = "supersample", default_value = "1")]
+    supersample: u32,
 }
 
 #[derive(Parser, Debug)]
@@ -81,45 +91,131 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Render on the CPU instead of using a graphics device.
+    /// This is also used if no graphics device is available.
+    #[clap(long, action)]
+    software: bool,
+
+    /// Render a video: every frame from `--skipframes` on, for `--frames` frames,
+    /// running at the exact frame rate of the movie, along with a WAV mixdown of its audio.
+    /// Frames are written to the output directory as a numbered PNG sequence.
+    #[clap(long, action)]
+    video: bool,
+
+    /// A command to pipe the raw RGBA frames of a video to, instead of writing images.
+    /// `{width}`, `{height}` and `{fps}` are replaced with the properties of the video,
+    /// for example `ffmpeg -f rawvideo -pix_fmt rgba -s {width}x{height} -r {fps} -i - out.mp4`
+    #[clap(long, requires = "video")]
+    encoder: Option<String>,
+
+    /// Export frames as SVG vector graphics instead of PNG images.
+    /// Filters, blend modes and masks are converted to their SVG equivalents where possible.
+    #[clap(long, action, conflicts_with = "video")]
+    svg: bool,
 }
 
-/// Captures a screenshot. The resulting image uses straight alpha
-fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+/// The device that movies are rendered with.
+#[derive(Clone)]
+enum RenderDevice {
+    Wgpu(Arc<Descriptors>),
+    Software,
+    /// Writes frames as SVG documents, which can't be captured as images.
+    Svg,
+}
+
+/// Loads a movie and creates a player for it, rendering with the given device.
+fn build_player(
+    device: RenderDevice,
     swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
-    progress: &Option<ProgressBar>,
     size: SizeOpt,
     skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
+    configure: impl FnOnce(PlayerBuilder) -> PlayerBuilder,
+) -> Result<Arc<Mutex<Player>>> {
     let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
 
     if movie.is_action_script_3() && skip_unsupported {
         return Err(anyhow!("Skipping unsupported movie"));
     }
 
+    let (width, height) = render_size(&movie, size);
+    let supersample = size.supersample.max(1);
+    let (width, height) = (width * supersample, height * supersample);
+
+    let builder = match device {
+        RenderDevice::Wgpu(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height))
+                .map_err(|e| anyhow!(e.to_string()))?;
+            PlayerBuilder::new().with_renderer(
+                WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
+            )
+        }
+        RenderDevice::Software => {
+            PlayerBuilder::new().with_renderer(SoftwareRenderBackend::new(width, height))
+        }
+        RenderDevice::Svg => {
+            PlayerBuilder::new().with_renderer(SvgRenderBackend::new(width, height))
+        }
+    };
+    Ok(configure(builder)
+        .with_movie(movie)
+        .with_viewport_dimensions(width, height, size.scale * f64::from(supersample))
+        .build())
+}
+
+/// The size of the images captured from a movie.
+fn render_size(movie: &SwfMovie, size: SizeOpt) -> (u32, u32) {
     let width = size
         .width
         .map(f64::from)
         .unwrap_or_else(|| movie.width().to_pixels());
     let width = (width * size.scale).round() as u32;
-
     let height = size
         .height
         .map(f64::from)
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
+    (width, height)
+}
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
-        )
-        .with_movie(movie)
-        .with_viewport_dimensions(width, height, size.scale)
-        .build();
+/// Renders the current frame of the player. The resulting image uses straight alpha
+fn capture_frame(player: &Mutex<Player>, size: SizeOpt) -> Option<RgbaImage> {
+    player.lock().unwrap().render();
+    let mut player = player.lock().unwrap();
+    let renderer = player.renderer_mut();
+    let image = if let Some(renderer) = renderer.downcast_mut::<SoftwareRenderBackend>() {
+        Some(renderer.capture_frame())
+    } else {
+        renderer
+            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+            .unwrap()
+            .capture_frame()
+    }?;
+
+    let supersample = size.supersample.max(1);
+    if supersample > 1 {
+        Some(image::imageops::resize(
+            &image,
+            image.width() / supersample,
+            image.height() / supersample,
+            image::imageops::FilterType::Triangle,
+        ))
+    } else {
+        Some(image)
+    }
+}
+
+/// Captures a screenshot. The resulting image uses straight alpha
+fn take_screenshot(
+    device: RenderDevice,
+    swf_path: &Path,
+    frames: u32,
+    skipframes: u32,
+    progress: &Option<ProgressBar>,
+    size: SizeOpt,
+    skip_unsupported: bool,
+) -> Result<Vec<RgbaImage>> {
+    let player = build_player(device, swf_path, size, skip_unsupported, |builder| builder)?;
 
     let mut result = Vec::new();
     let totalframes = frames + skipframes;
@@ -137,16 +233,7 @@ fn take_screenshot(
 
         player.lock().unwrap().run_frame();
         if i >= skipframes {
-            let image = || {
-                player.lock().unwrap().render();
-                let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
-            };
-            match catch_unwind(image) {
+            match catch_unwind(|| capture_frame(&player, size)) {
                 Ok(Some(image)) => result.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
                 Err(e) => {
@@ -197,7 +284,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(device: RenderDevice, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -226,7 +313,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
     };
 
     let frames = take_screenshot(
-        descriptors,
+        device,
         &opt.swf,
         opt.frames,
         opt.skipframes,
@@ -294,7 +381,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(device: RenderDevice, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -323,7 +410,7 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
             );
         }
         if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
+            device.clone(),
             file.path(),
             opt.frames,
             opt.skipframes,
@@ -400,29 +487,56 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
-fn main() -> Result<()> {
-    let opt: Opt = Opt::parse();
+fn create_render_device(opt: &Opt) -> RenderDevice {
+    if opt.software {
+        return RenderDevice::Software;
+    }
+
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
         backends: opt.graphics.into(),
         ..Default::default()
     });
-    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+    match futures::executor::block_on(request_adapter_and_device(
         opt.graphics.into(),
         &instance,
         None,
         opt.power.into(),
-        trace_path(&opt),
-    ))
-    .map_err(|e| anyhow!(e.to_string()))?;
+        trace_path(opt),
+    )) {
+        Ok((adapter, device, queue)) => {
+            RenderDevice::Wgpu(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+        }
+        Err(e) => {
+            eprintln!(
+                "Unable to create a graphics device ({e}), falling back to software rendering"
+            );
+            RenderDevice::Software
+        }
+    }
+}
 
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+fn main() -> Result<()> {
+    let opt: Opt = Opt::parse();
+    if opt.svg {
+        if !opt.swf.is_file() {
+            return Err(anyhow!("SVGs can only be exported from a single file."));
+        }
+        return svg::capture_svg(&opt);
+    }
+
+    let device = create_render_device(&opt);
 
-    if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+    if opt.video {
+        if !opt.swf.is_file() {
+            return Err(anyhow!("Videos can only be rendered from a single file."));
+        }
+        video::capture_video(device, &opt)?;
+    } else if opt.swf.is_file() {
+        capture_single_swf(device, &opt)?;
     } else if !opt.swf.is_dir() {
         return Err(anyhow!("Given path is not a file or directory."));
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(device, &opt)?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
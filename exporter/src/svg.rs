@@ -0,0 +1,77 @@
+use crate::{build_player, Opt, RenderDevice};
+use anyhow::Result;
+use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::Player;
+use ruffle_render_svg::backend::SvgRenderBackend;
+use std::fs::create_dir_all;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Captures frames of a movie as SVG documents instead of images.
+pub fn capture_svg(opt: &Opt) -> Result<()> {
+    let player = build_player(
+        RenderDevice::Svg,
+        &opt.swf,
+        opt.size,
+        opt.skip_unsupported,
+        |builder| builder,
+    )?;
+
+    let mut frames = Vec::new();
+    for i in 0..opt.skipframes + opt.frames {
+        let mut player = player.lock().unwrap();
+        player.preload(&mut ExecutionLimit::none());
+        player.run_frame();
+        if i >= opt.skipframes {
+            frames.push(capture_svg_frame(&mut player));
+        }
+    }
+
+    if let [frame] = &frames[..] {
+        if opt.output_path == Some(PathBuf::from("-")) {
+            io::stdout().write_all(frame.as_bytes())?;
+            return Ok(());
+        }
+        let output = opt.output_path.clone().unwrap_or_else(|| {
+            let mut result = PathBuf::from(opt.swf.file_stem().unwrap());
+            result.set_extension("svg");
+            result
+        });
+        std::fs::write(&output, frame)?;
+        if !opt.silent {
+            println!(
+                "Saved first frame of {} to {}",
+                opt.swf.to_string_lossy(),
+                output.to_string_lossy()
+            );
+        }
+    } else {
+        let output = opt
+            .output_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(opt.swf.file_stem().unwrap()));
+        create_dir_all(&output)?;
+        for (frame, contents) in frames.iter().enumerate() {
+            std::fs::write(output.join(format!("{frame}.svg")), contents)?;
+        }
+        if !opt.silent {
+            println!(
+                "Saved first {} frames of {} to {}",
+                frames.len(),
+                opt.swf.to_string_lossy(),
+                output.to_string_lossy()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn capture_svg_frame(player: &mut Player) -> String {
+    player.render();
+    player
+        .renderer_mut()
+        .downcast_mut::<SvgRenderBackend>()
+        .expect("SVG players must use an SVG renderer")
+        .capture_frame()
+}
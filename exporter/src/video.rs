@@ -0,0 +1,165 @@
+use crate::audio::CaptureAudioBackend;
+use crate::{build_player, capture_frame, Opt, RenderDevice};
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::Player;
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::panic::catch_unwind;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+/// Renders a movie frame by frame at its exact frame rate, writing the frames as an image
+/// sequence (or to an encoder) and the audio as a WAV file.
+pub fn capture_video(device: RenderDevice, opt: &Opt) -> Result<()> {
+    let output = opt
+        .output_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(opt.swf.file_stem().unwrap()));
+    create_dir_all(&output)?;
+
+    let player = build_player(
+        device,
+        &opt.swf,
+        opt.size,
+        opt.skip_unsupported,
+        |builder| builder.with_audio(CaptureAudioBackend::default()),
+    )?;
+
+    let progress = if !opt.silent {
+        let progress = ProgressBar::new((opt.skipframes + opt.frames) as u64);
+        progress.set_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {bar:40.cyan/blue} [{eta_precise}] {pos:>7}/{len:7} {msg}",
+            )
+            .unwrap()
+            .progress_chars("##-"),
+        );
+        Some(progress)
+    } else {
+        None
+    };
+
+    let mut encoder = None;
+    for i in 0..opt.skipframes + opt.frames {
+        if let Some(progress) = &progress {
+            progress.set_message(format!(
+                "{} frame {}",
+                opt.swf.file_stem().unwrap().to_string_lossy(),
+                i
+            ));
+        }
+
+        {
+            let mut player = player.lock().unwrap();
+            player.preload(&mut ExecutionLimit::none());
+            player.run_frame_offline();
+            if i < opt.skipframes {
+                // The audio track starts at the first rendered frame.
+                capture_audio(&mut player).clear();
+            }
+        }
+
+        if i >= opt.skipframes {
+            let image = match catch_unwind(|| capture_frame(&player, opt.size)) {
+                Ok(Some(image)) => image,
+                Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, opt.swf)),
+                Err(e) => {
+                    return Err(anyhow!(
+                        "Unable to capture frame {} of {:?}: {:?}",
+                        i,
+                        opt.swf,
+                        e
+                    ))
+                }
+            };
+
+            if let Some(command) = &opt.encoder {
+                if encoder.is_none() {
+                    let frame_rate = player.lock().unwrap().frame_rate();
+                    encoder = Some(spawn_encoder(
+                        command,
+                        image.width(),
+                        image.height(),
+                        frame_rate,
+                    )?);
+                }
+                encoder
+                    .as_mut()
+                    .unwrap()
+                    .stdin
+                    .as_mut()
+                    .unwrap()
+                    .write_all(image.as_raw())
+                    .map_err(|e| anyhow!("Unable to write frame {} to the encoder: {}", i, e))?;
+            } else {
+                let mut path = output.clone();
+                let frame = i - opt.skipframes;
+                path.push(format!("{frame:05}.png"));
+                image.save(&path)?;
+            }
+        }
+
+        if let Some(progress) = &progress {
+            progress.inc(1);
+        }
+    }
+
+    if let Some(mut encoder) = encoder {
+        // Closing stdin signals the end of the video to the encoder.
+        drop(encoder.stdin.take());
+        let status = encoder.wait()?;
+        if !status.success() {
+            return Err(anyhow!("Encoder exited with {}", status));
+        }
+    }
+
+    let audio_path = output.join("audio.wav");
+    let mut writer = BufWriter::new(File::create(&audio_path)?);
+    capture_audio(&mut player.lock().unwrap()).write_wav(&mut writer)?;
+    writer.flush()?;
+
+    let message = format!(
+        "Saved {} frames of {} to {}",
+        opt.frames,
+        opt.swf.to_string_lossy(),
+        output.to_string_lossy()
+    );
+    if let Some(progress) = progress {
+        progress.finish_with_message(message);
+    } else {
+        println!("{message}");
+    }
+
+    Ok(())
+}
+
+fn capture_audio(player: &mut Player) -> &mut CaptureAudioBackend {
+    player
+        .audio_mut()
+        .downcast_mut::<CaptureAudioBackend>()
+        .expect("Video players must use a capture audio backend")
+}
+
+/// Starts the user-supplied encoder command, which reads raw RGBA frames from its stdin.
+fn spawn_encoder(command: &str, width: u32, height: u32, frame_rate: f64) -> Result<Child> {
+    let command = command
+        .replace("{width}", &width.to_string())
+        .replace("{height}", &height.to_string())
+        .replace("{fps}", &frame_rate.to_string());
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell
+        .arg(&command)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Unable to start encoder `{}`: {}", command, e))
+}
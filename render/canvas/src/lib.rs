@@ -13,9 +13,11 @@ use ruffle_render::bitmap::{
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
+use ruffle_render::utils::premultiply_alpha_rgba;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -142,6 +144,15 @@ struct CanvasBitmap {
     smoothed: bool,
 }
 
+/// An offscreen render, whose result stays in the canvas of the bitmap until it's read back.
+#[derive(Debug)]
+struct CanvasSyncHandle {
+    handle: BitmapHandle,
+    region: PixelRegion,
+}
+
+impl SyncHandle for CanvasSyncHandle {}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 struct BitmapData {
@@ -410,6 +421,25 @@ impl WebCanvasRenderBackend {
         self.mask_state = MaskState::DrawContent;
     }
 
+    /// Draws `commands` into the canvas of `bitmap`, over its current contents.
+    fn render_to_bitmap(&mut self, bitmap: &BitmapHandle, commands: CommandList) {
+        let bitmap_context = as_bitmap_data(bitmap).context.clone();
+        let context = std::mem::replace(&mut self.context, bitmap_context);
+        let mask_state = std::mem::replace(&mut self.mask_state, MaskState::DrawContent);
+        let blend_modes = std::mem::replace(
+            &mut self.blend_modes,
+            vec![RenderBlendMode::Builtin(BlendMode::Normal)],
+        );
+
+        self.context.reset_transform().warn_on_error();
+        commands.execute(self);
+        self.clear_color_filter();
+
+        self.context = context;
+        self.mask_state = mask_state;
+        self.blend_modes = blend_modes;
+    }
+
     fn push_blend_mode(&mut self, blend: RenderBlendMode) {
         if !same_blend_mode(self.blend_modes.last(), &blend) {
             self.apply_blend_mode(blend.clone());
@@ -456,12 +486,18 @@ impl RenderBackend for WebCanvasRenderBackend {
 
     fn render_offscreen(
         &mut self,
-        _handle: BitmapHandle,
-        _commands: CommandList,
+        handle: BitmapHandle,
+        commands: CommandList,
         _quality: StageQuality,
-        _bounds: PixelRegion,
+        bounds: PixelRegion,
     ) -> Option<Box<dyn SyncHandle>> {
-        None
+        // Only `BitmapData.draw` renders offscreen here. Cached bitmaps and filters stay
+        // unsupported, as `is_offscreen_supported` is false.
+        self.render_to_bitmap(&handle, commands);
+        Some(Box::new(CanvasSyncHandle {
+            handle,
+            region: bounds,
+        }))
     }
 
     fn submit_frame(
@@ -531,10 +567,33 @@ impl RenderBackend for WebCanvasRenderBackend {
 
     fn resolve_sync_handle(
         &mut self,
-        _handle: Box<dyn SyncHandle>,
-        _with_rgba: RgbaBufRead,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
     ) -> Result<(), Error> {
-        Err(Error::Unimplemented("Sync handle resolution".into()))
+        let handle = handle
+            .downcast::<CanvasSyncHandle>()
+            .expect("Sync handle must be a canvas sync handle");
+        let data = as_bitmap_data(&handle.handle);
+
+        // Read back whole rows, as the callback expects.
+        let (width, height) = (data.canvas.width(), data.canvas.height());
+        let y_min = handle.region.y_min.min(height);
+        let y_max = handle.region.y_max.min(height).max(y_min);
+        if y_min == y_max {
+            with_rgba(&[], width * 4);
+            return Ok(());
+        }
+
+        let image_data = data
+            .context
+            .get_image_data(0.0, y_min.into(), width.into(), (y_max - y_min).into())
+            .map_err(Error::JavascriptError)?;
+        // Canvas pixels aren't premultiplied, unlike those of other backends.
+        let mut rgba = image_data.data().0;
+        premultiply_alpha_rgba(&mut rgba);
+
+        with_rgba(&rgba, width * 4);
+        Ok(())
     }
 
     fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
@@ -566,6 +625,35 @@ impl CommandHandler for WebCanvasRenderBackend {
         self.clear_color_filter();
     }
 
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        // Canvas only supports affine transforms, so approximate the projection
+        // with the affine transform that matches three of the projected corners.
+        let data = as_bitmap_data(&bitmap);
+        let width = data.canvas.width().max(1) as f64;
+        let height = data.canvas.height().max(1) as f64;
+        let origin = matrix.transform_point(0.0, 0.0, 0.0);
+        let right = matrix.transform_point(width, 0.0, 0.0);
+        let bottom = matrix.transform_point(0.0, height, 0.0);
+        let transform = Transform {
+            matrix: Matrix {
+                a: ((right.0 - origin.0) / width) as f32,
+                b: ((right.1 - origin.1) / width) as f32,
+                c: ((bottom.0 - origin.0) / height) as f32,
+                d: ((bottom.1 - origin.1) / height) as f32,
+                tx: Twips::from_pixels(origin.0),
+                ty: Twips::from_pixels(origin.1),
+            },
+            color_transform,
+        };
+        self.render_bitmap(bitmap, transform, smoothing, PixelSnapping::Never);
+    }
+
     fn render_stage3d(&mut self, _bitmap: BitmapHandle, _transform: Transform) {
         panic!("Stage3D should not have been created on canvas backend")
     }
@@ -888,6 +976,17 @@ fn swf_shape_to_canvas_commands(
             DrawPath::Fill {
                 commands, style, ..
             } => {
+                let style = match style {
+                    ruffle_render::shape_utils::FillStyle::Swf(style) => *style,
+                    // Canvas can't run Pixel Bender shaders.
+                    ruffle_render::shape_utils::FillStyle::Shader(_) => {
+                        log::warn!(
+                            "Shader fills aren't supported by the canvas renderer, skipping"
+                        );
+                        continue;
+                    }
+                };
+
                 let canvas_path = Path2d::new().expect("Path2d constructor must succeed");
                 canvas_path.add_path_with_transformation(
                     &draw_commands_to_path2d(commands, false),
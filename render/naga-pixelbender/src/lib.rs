@@ -118,7 +118,12 @@ pub const SHADER_INT_PARAMETERS_INDEX: u32 = 4;
 // how the shader is being invoked.
 pub const ZEROED_OUT_OF_RANGE_MODE_INDEX: u32 = 5;
 
-pub const TEXTURE_START_BIND_INDEX: u32 = 6;
+// A mat4x4f uniform that transforms the position of the pixel being processed
+// (with the z and w components set to 0.0 and 1.0) into the value of `outCoord()`.
+// This is the identity matrix unless the shader is used to fill a shape.
+pub const OUT_COORD_MATRIX_INDEX: u32 = 6;
+
+pub const TEXTURE_START_BIND_INDEX: u32 = 7;
 
 impl<'a> ShaderBuilder<'a> {
     pub fn build(shader: &PixelBenderShader) -> Result<NagaModules> {
@@ -653,10 +658,49 @@ impl<'a> ShaderBuilder<'a> {
         // (it may use te same register as another parameter, but with different components)
 
         if let Some(coord_reg) = out_coord {
-            let coord_val = self
+            let position = self
                 .func
                 .expressions
                 .append(Expression::FunctionArgument(0), Span::UNDEFINED);
+            let position_x = self.evaluate_expr(Expression::AccessIndex {
+                base: position,
+                index: 0,
+            });
+            let position_y = self.evaluate_expr(Expression::AccessIndex {
+                base: position,
+                index: 1,
+            });
+            let position = self.evaluate_expr(Expression::Compose {
+                ty: self.vec4f,
+                components: vec![position_x, position_y, self.zerof32, self.onef32],
+            });
+
+            let out_coord_matrix_global = self.module.global_variables.append(
+                GlobalVariable {
+                    name: Some("out_coord_matrix".to_string()),
+                    space: naga::AddressSpace::Uniform,
+                    binding: Some(naga::ResourceBinding {
+                        group: 0,
+                        binding: OUT_COORD_MATRIX_INDEX,
+                    }),
+                    ty: self.mat4x4f,
+                    init: None,
+                },
+                Span::UNDEFINED,
+            );
+            let out_coord_matrix = self.func.expressions.append(
+                Expression::GlobalVariable(out_coord_matrix_global),
+                Span::UNDEFINED,
+            );
+            let out_coord_matrix = self.evaluate_expr(Expression::Load {
+                pointer: out_coord_matrix,
+            });
+
+            let coord_val = self.evaluate_expr(Expression::Binary {
+                op: BinaryOperator::Multiply,
+                left: out_coord_matrix,
+                right: position,
+            });
             self.emit_dest_store(coord_val, coord_reg)?;
         }
 
@@ -0,0 +1,281 @@
+use crate::bitmap::{as_bitmap, SoftwareBitmap, SoftwareSyncHandle};
+use crate::commands::CommandRenderer;
+use crate::filters::{apply_filter, is_filter_supported};
+use crate::mesh::Mesh;
+use crate::surface::{premultiply, Surface};
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+/// A render backend that draws everything on the CPU, without needing a GPU.
+pub struct SoftwareRenderBackend {
+    dimensions: ViewportDimensions,
+    target: Surface,
+    quality: StageQuality,
+    shape_tessellator: ShapeTessellator,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        Self {
+            dimensions: ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            },
+            target: Surface::new(width, height),
+            quality: StageQuality::High,
+            shape_tessellator: ShapeTessellator::new(),
+        }
+    }
+
+    /// Returns the last submitted frame, with straight alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut pixels = self.target.pixels().to_vec();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut pixels);
+        image::RgbaImage::from_raw(self.target.width(), self.target.height(), pixels)
+            .expect("Surface must be a valid RgbaImage")
+    }
+
+    /// Renders `commands` onto `surface` using supersampling for anti-aliasing.
+    fn render(surface: &mut Surface, commands: CommandList, quality: StageQuality) {
+        let factor = supersampling_factor(quality);
+        if factor == 1 {
+            commands.execute(&mut CommandRenderer::new(surface, 1, quality));
+        } else {
+            let mut upscaled = surface.upscale(factor);
+            commands.execute(&mut CommandRenderer::new(&mut upscaled, factor, quality));
+            *surface = upscaled.downscale(factor);
+        }
+    }
+}
+
+/// The number of subpixels rendered per pixel along each axis for the given quality.
+fn supersampling_factor(quality: StageQuality) -> u32 {
+    match quality.sample_count() {
+        0..=1 => 1,
+        2..=4 => 2,
+        5..=9 => 3,
+        _ => 4,
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        // Avoid empty surfaces, like the other backends.
+        let width = dimensions.width.max(1);
+        let height = dimensions.height.max(1);
+        self.dimensions = ViewportDimensions {
+            width,
+            height,
+            scale_factor: dimensions.scale_factor,
+        };
+        self.target = Surface::new(width, height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        ShapeHandle(Arc::new(Mesh::new(mesh, bitmap_source, self)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let bitmap = as_bitmap(&handle);
+        let mut surface = bitmap.to_surface();
+        Self::render(&mut surface, commands, quality);
+        bitmap.write(surface);
+        Some(Box::new(SoftwareSyncHandle {
+            handle,
+            region: bounds,
+        }))
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let source_region = as_bitmap(&source).surface().region(
+            source_point.0,
+            source_point.1,
+            source_size.0,
+            source_size.1,
+        );
+        let filtered = apply_filter(&source_region, &filter);
+
+        let destination_bitmap = as_bitmap(&destination);
+        let mut surface = destination_bitmap.to_surface();
+        surface.blit(&filtered, dest_point.0, dest_point.1);
+        destination_bitmap.write(surface);
+
+        let mut region =
+            PixelRegion::for_region(dest_point.0, dest_point.1, source_size.0, source_size.1);
+        region.clamp(destination_bitmap.width(), destination_bitmap.height());
+        Some(Box::new(SoftwareSyncHandle {
+            handle: destination,
+            region,
+        }))
+    }
+
+    fn is_filter_supported(&self, filter: &Filter) -> bool {
+        is_filter_supported(filter)
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            let bitmap = as_bitmap(&entry.handle);
+            let mut surface =
+                Surface::filled(bitmap.width(), bitmap.height(), premultiply(entry.clear));
+            Self::render(&mut surface, entry.commands, self.quality);
+            for filter in &entry.filters {
+                surface = apply_filter(&surface, filter);
+            }
+            bitmap.write(surface);
+        }
+
+        let mut target = Surface::filled(
+            self.target.width(),
+            self.target.height(),
+            premultiply(clear),
+        );
+        Self::render(&mut target, commands, self.quality);
+        self.target = target;
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(Surface::new(
+            width, height,
+        )))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        let surface = Surface::from_pixels(bitmap.width(), bitmap.height(), bitmap.data().to_vec());
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(surface))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let bitmap = bitmap.to_rgba();
+        as_bitmap(handle).update_region(bitmap.data(), region);
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        let mut result = vec![];
+        result.push("Renderer: software".to_string());
+        result.push(format!("Surface quality: {}", self.quality));
+        result.push(format!(
+            "Surface supersampling: {}x",
+            supersampling_factor(self.quality)
+        ));
+        result.push(format!(
+            "Surface size: {}x{}",
+            self.target.width(),
+            self.target.height()
+        ));
+        Cow::Owned(result.join("\n"))
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        let handle = handle
+            .downcast::<SoftwareSyncHandle>()
+            .expect("Sync handle must be a software sync handle");
+        as_bitmap(&handle.handle).read_region(handle.region, with_rgba);
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use crate::surface::Surface;
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, PixelRegion, SyncHandle};
+use std::cell::{Ref, RefCell};
+use std::fmt::{Debug, Formatter};
+
+/// A bitmap owned by the software backend, stored as premultiplied RGBA.
+pub struct SoftwareBitmap {
+    width: u32,
+    height: u32,
+    surface: RefCell<Surface>,
+}
+
+impl SoftwareBitmap {
+    pub fn new(surface: Surface) -> Self {
+        Self {
+            width: surface.width(),
+            height: surface.height(),
+            surface: RefCell::new(surface),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Borrows the contents of this bitmap, to draw it without copying it.
+    pub fn surface(&self) -> Ref<'_, Surface> {
+        self.surface.borrow()
+    }
+
+    /// Returns a copy of the contents of this bitmap.
+    pub fn to_surface(&self) -> Surface {
+        self.surface.borrow().clone()
+    }
+
+    /// Replaces the contents of this bitmap, which must be of the same size.
+    pub fn write(&self, surface: Surface) {
+        debug_assert_eq!(
+            (surface.width(), surface.height()),
+            (self.width, self.height)
+        );
+        *self.surface.borrow_mut() = surface;
+    }
+
+    /// Copies the given region of `pixels`, a full image of the same size as this bitmap.
+    pub fn update_region(&self, pixels: &[u8], region: PixelRegion) {
+        let mut surface = self.surface.borrow_mut();
+        let own_pixels = surface.pixels_mut();
+        let row_length = self.width as usize * 4;
+        for y in region.y_min..region.y_max.min(self.height) {
+            let start = y as usize * row_length + region.x_min as usize * 4;
+            let end = y as usize * row_length + region.x_max.min(self.width) as usize * 4;
+            if let (Some(target), Some(source)) =
+                (own_pixels.get_mut(start..end), pixels.get(start..end))
+            {
+                target.copy_from_slice(source);
+            }
+        }
+    }
+
+    /// Calls `with_rgba` with the rows of the given region.
+    pub fn read_region(&self, region: PixelRegion, with_rgba: impl FnOnce(&[u8], u32)) {
+        let surface = self.surface.borrow();
+        let pixels = surface.pixels();
+        let row_length = self.width as usize * 4;
+        let start = region.y_min as usize * row_length;
+        let end = (region.y_max.min(self.height) as usize * row_length).max(start);
+        with_rgba(&pixels[start..end], row_length as u32);
+    }
+}
+
+impl Debug for SoftwareBitmap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoftwareBitmap")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+pub fn as_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a software bitmap")
+}
+
+/// A completed offscreen render or filter, ready to be read back.
+#[derive(Debug)]
+pub struct SoftwareSyncHandle {
+    pub handle: BitmapHandle,
+    pub region: PixelRegion,
+}
+
+impl SyncHandle for SoftwareSyncHandle {}
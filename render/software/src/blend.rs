@@ -0,0 +1,131 @@
+use crate::surface::{source_over, Rgba};
+use swf::BlendMode;
+
+/// Composites a pixel of a blend layer (`src`) onto its parent (`dst`).
+///
+/// These mirror the blend shaders of the wgpu backend.
+pub fn blend(mode: BlendMode, src: Rgba, dst: Rgba) -> Rgba {
+    if src[3] <= 0.0 {
+        return dst;
+    }
+
+    match mode {
+        BlendMode::Normal | BlendMode::Layer => source_over(src, dst),
+        BlendMode::Add => with_over_alpha(
+            [src[0] + dst[0], src[1] + dst[1], src[2] + dst[2]],
+            src,
+            dst,
+        ),
+        BlendMode::Subtract => with_over_alpha(
+            [dst[0] - src[0], dst[1] - src[1], dst[2] - src[2]],
+            src,
+            dst,
+        ),
+        BlendMode::Screen => with_over_alpha(
+            [
+                src[0] + dst[0] * (1.0 - src[0]),
+                src[1] + dst[1] * (1.0 - src[1]),
+                src[2] + dst[2] * (1.0 - src[2]),
+            ],
+            src,
+            dst,
+        ),
+        BlendMode::Multiply => {
+            if dst[3] > 0.0 {
+                separable(src, dst, |s, d| s * d)
+            } else {
+                src
+            }
+        }
+        BlendMode::Lighten => separable(src, dst, f32::max),
+        BlendMode::Darken => separable(src, dst, f32::min),
+        BlendMode::Difference => separable(src, dst, |s, d| (d - s).abs()),
+        BlendMode::Invert => separable(src, dst, |_, d| 1.0 - d),
+        BlendMode::Overlay => separable(src, dst, |s, d| hard_light(d, s)),
+        BlendMode::HardLight => separable(src, dst, hard_light),
+        BlendMode::Alpha => [
+            dst[0] * src[3],
+            dst[1] * src[3],
+            dst[2] * src[3],
+            dst[3] * src[3],
+        ],
+        BlendMode::Erase => {
+            let inverse_alpha = 1.0 - src[3];
+            [
+                dst[0] * inverse_alpha,
+                dst[1] * inverse_alpha,
+                dst[2] * inverse_alpha,
+                dst[3] * inverse_alpha,
+            ]
+        }
+    }
+}
+
+/// Combines the given color with the alpha of drawing `src` over `dst`.
+fn with_over_alpha(rgb: [f32; 3], src: Rgba, dst: Rgba) -> Rgba {
+    [
+        rgb[0].clamp(0.0, 1.0),
+        rgb[1].clamp(0.0, 1.0),
+        rgb[2].clamp(0.0, 1.0),
+        src[3] + dst[3] * (1.0 - src[3]),
+    ]
+}
+
+/// Applies a separable blend function, which operates on unmultiplied color components.
+fn separable(src: Rgba, dst: Rgba, blend_func: impl Fn(f32, f32) -> f32) -> Rgba {
+    let unmultiply = |color: Rgba, i: usize| {
+        if color[3] > 0.0 {
+            color[i] / color[3]
+        } else {
+            0.0
+        }
+    };
+    let mut result = [0.0; 4];
+    for i in 0..3 {
+        let blended = blend_func(unmultiply(src, i), unmultiply(dst, i));
+        result[i] = src[i] * (1.0 - dst[3]) + dst[i] * (1.0 - src[3]) + src[3] * dst[3] * blended;
+    }
+    result[3] = src[3] + dst[3] * (1.0 - src[3]);
+    result
+}
+
+fn hard_light(src: f32, dst: f32) -> f32 {
+    if src <= 0.5 {
+        2.0 * src * dst
+    } else {
+        1.0 - 2.0 * (1.0 - dst) * (1.0 - src)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::blend;
+    use swf::BlendMode;
+
+    #[test]
+    fn transparent_source_keeps_destination() {
+        let dst = [0.2, 0.4, 0.6, 1.0];
+        for mode in [BlendMode::Normal, BlendMode::Multiply, BlendMode::Erase] {
+            assert_eq!(blend(mode, [0.0; 4], dst), dst);
+        }
+    }
+
+    #[test]
+    fn multiply_onto_transparent_replaces() {
+        let src = [0.5, 0.0, 0.0, 0.5];
+        assert_eq!(blend(BlendMode::Multiply, src, [0.0; 4]), src);
+    }
+
+    #[test]
+    fn opaque_blends() {
+        let src = [1.0, 0.5, 0.0, 1.0];
+        let dst = [0.5, 0.5, 0.5, 1.0];
+        assert_eq!(blend(BlendMode::Multiply, src, dst), [0.5, 0.25, 0.0, 1.0]);
+        assert_eq!(blend(BlendMode::Lighten, src, dst), [1.0, 0.5, 0.5, 1.0]);
+        assert_eq!(blend(BlendMode::Darken, src, dst), [0.5, 0.5, 0.0, 1.0]);
+        assert_eq!(blend(BlendMode::Add, src, dst), [1.0, 1.0, 0.5, 1.0]);
+        assert_eq!(blend(BlendMode::Subtract, src, dst), [0.0, 0.0, 0.5, 1.0]);
+        assert_eq!(blend(BlendMode::Invert, src, dst), [0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(blend(BlendMode::Erase, src, dst), [0.0; 4]);
+    }
+}
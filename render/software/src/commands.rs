@@ -0,0 +1,485 @@
+use crate::bitmap::as_bitmap;
+use crate::blend::blend;
+use crate::mesh::{DrawType, Mesh};
+use crate::paint::{sample_bitmap, solid_color, transform_color, GradientPaint};
+use crate::raster::fill_triangle;
+use crate::surface::{source_over, Rgba, Surface};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Color, ColorTransform};
+
+/// A 2D affine transform, operating on pixels.
+#[derive(Clone, Copy, Debug)]
+struct Affine {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    tx: f32,
+    ty: f32,
+}
+
+impl Affine {
+    /// Converts a display object matrix into one that outputs pixels of the render target.
+    fn from_matrix(matrix: &Matrix, scale: f32) -> Self {
+        Self {
+            a: matrix.a * scale,
+            b: matrix.b * scale,
+            c: matrix.c * scale,
+            d: matrix.d * scale,
+            tx: matrix.tx.to_pixels() as f32 * scale,
+            ty: matrix.ty.to_pixels() as f32 * scale,
+        }
+    }
+
+    /// Converts a column-major matrix used by the tessellator.
+    fn from_columns(matrix: &[[f32; 3]; 3]) -> Self {
+        Self {
+            a: matrix[0][0],
+            b: matrix[0][1],
+            c: matrix[1][0],
+            d: matrix[1][1],
+            tx: matrix[2][0],
+            ty: matrix[2][1],
+        }
+    }
+
+    fn scale(x: f32, y: f32) -> Self {
+        Self {
+            a: x,
+            b: 0.0,
+            c: 0.0,
+            d: y,
+            tx: 0.0,
+            ty: 0.0,
+        }
+    }
+
+    fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    /// Returns the transform that applies `other` first, then `self`.
+    fn then(&self, other: &Affine) -> Affine {
+        Affine {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            tx: self.a * other.tx + self.c * other.ty + self.tx,
+            ty: self.b * other.tx + self.d * other.ty + self.ty,
+        }
+    }
+
+    fn inverse(&self) -> Option<Affine> {
+        let determinant = self.a * self.d - self.b * self.c;
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+        let a = self.d / determinant;
+        let b = -self.b / determinant;
+        let c = -self.c / determinant;
+        let d = self.a / determinant;
+        Some(Affine {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + c * self.ty),
+            ty: -(b * self.tx + d * self.ty),
+        })
+    }
+}
+
+enum MaskState {
+    /// The mask is being drawn; draws only add to its coverage.
+    Drawing,
+    /// The masked content is being drawn; draws are clipped to the coverage.
+    Active,
+    /// The mask is being cleared; draws are discarded.
+    Clearing,
+}
+
+struct Mask {
+    coverage: Vec<bool>,
+    state: MaskState,
+}
+
+/// Rasterizes commands onto a surface.
+pub struct CommandRenderer<'a> {
+    target: &'a mut Surface,
+    /// The number of target pixels per stage pixel along each axis, used for supersampling.
+    scale: f32,
+    quality: StageQuality,
+    masks: Vec<Mask>,
+}
+
+impl<'a> CommandRenderer<'a> {
+    pub fn new(target: &'a mut Surface, scale: u32, quality: StageQuality) -> Self {
+        Self {
+            target,
+            scale: scale as f32,
+            quality,
+            masks: Vec::new(),
+        }
+    }
+
+    fn drawing_mask(&self) -> bool {
+        matches!(
+            self.masks.last(),
+            Some(Mask {
+                state: MaskState::Drawing,
+                ..
+            })
+        )
+    }
+
+    /// Fills a triangle given in target pixels, where `paint` returns the color at a pixel center.
+    fn fill(&mut self, vertices: [(f32, f32); 3], paint: impl Fn(f32, f32) -> Rgba) {
+        let width = self.target.width();
+        let height = self.target.height();
+        let index = |x: u32, y: u32| y as usize * width as usize + x as usize;
+
+        match self.masks.split_last_mut() {
+            Some((mask, parents)) => match mask.state {
+                MaskState::Drawing => {
+                    let parent = parents.last().and_then(|parent| match parent.state {
+                        MaskState::Active => Some(&parent.coverage),
+                        _ => None,
+                    });
+                    fill_triangle(width, height, vertices, |x, y| {
+                        let i = index(x, y);
+                        if parent.map(|parent| parent[i]).unwrap_or(true) {
+                            mask.coverage[i] = true;
+                        }
+                    });
+                }
+                MaskState::Active => {
+                    let target = &mut *self.target;
+                    fill_triangle(width, height, vertices, |x, y| {
+                        if mask.coverage[index(x, y)] {
+                            let color = paint(x as f32 + 0.5, y as f32 + 0.5);
+                            target.set(x, y, source_over(color, target.get(x, y)));
+                        }
+                    });
+                }
+                MaskState::Clearing => {}
+            },
+            None => {
+                let target = &mut *self.target;
+                fill_triangle(width, height, vertices, |x, y| {
+                    let color = paint(x as f32 + 0.5, y as f32 + 0.5);
+                    target.set(x, y, source_over(color, target.get(x, y)));
+                });
+            }
+        }
+    }
+
+    /// Fills a parallelogram, given as the unit square transformed by `transform`.
+    fn fill_quad(&mut self, transform: &Affine, paint: impl Fn(f32, f32) -> Rgba) {
+        let corners = [
+            transform.apply(0.0, 0.0),
+            transform.apply(1.0, 0.0),
+            transform.apply(1.0, 1.0),
+            transform.apply(0.0, 1.0),
+        ];
+        self.fill([corners[0], corners[1], corners[2]], &paint);
+        self.fill([corners[0], corners[2], corners[3]], &paint);
+    }
+
+    fn draw_bitmap(
+        &mut self,
+        bitmap: &BitmapHandle,
+        transform: &Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let bitmap = as_bitmap(bitmap);
+        let (width, height) = (bitmap.width() as f32, bitmap.height() as f32);
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        let to_target =
+            Affine::from_matrix(&matrix, self.scale).then(&Affine::scale(width, height));
+        let Some(to_bitmap) = to_target.inverse() else {
+            return;
+        };
+
+        let texture = bitmap.surface();
+        let color_transform = transform.color_transform;
+        let smoothed = smoothing && self.quality != StageQuality::Low;
+        self.fill_quad(&to_target, |x, y| {
+            let (u, v) = to_bitmap.apply(x, y);
+            let color = sample_bitmap(&texture, u * width, v * height, smoothed, false);
+            transform_color(color, &color_transform)
+        });
+    }
+
+    fn draw_bitmap_projected(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        let bitmap = as_bitmap(bitmap);
+        let (width, height) = (f64::from(bitmap.width()), f64::from(bitmap.height()));
+        let scale = f64::from(self.scale);
+        let to_target = (Matrix3D::scale(scale, scale, 1.0) * matrix).to_homography();
+        let Some(to_bitmap) = invert_homography(&to_target) else {
+            return;
+        };
+
+        let project = |u: f64, v: f64| {
+            let [x, y, w] = to_target.map(|row| row[0] * u + row[1] * v + row[2]);
+            (w > 0.0).then(|| ((x / w) as f32, (y / w) as f32))
+        };
+        // Anything reaching behind the viewer is not drawn, as we don't clip against the near plane.
+        let (Some(top_left), Some(top_right), Some(bottom_right), Some(bottom_left)) = (
+            project(0.0, 0.0),
+            project(width, 0.0),
+            project(width, height),
+            project(0.0, height),
+        ) else {
+            return;
+        };
+
+        let texture = bitmap.surface();
+        let smoothed = smoothing && self.quality != StageQuality::Low;
+        let paint = |x: f32, y: f32| {
+            let [u, v, w] =
+                to_bitmap.map(|row| row[0] * f64::from(x) + row[1] * f64::from(y) + row[2]);
+            let color = sample_bitmap(&texture, (u / w) as f32, (v / w) as f32, smoothed, false);
+            transform_color(color, color_transform)
+        };
+        self.fill([top_left, top_right, bottom_right], paint);
+        self.fill([top_left, bottom_right, bottom_left], paint);
+    }
+}
+
+/// Inverts a 3x3 matrix given as rows.
+fn invert_homography(m: &[[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    let adjugate = [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ];
+    let determinant =
+        m[0][0] * adjugate[0][0] + m[0][1] * adjugate[1][0] + m[0][2] * adjugate[2][0];
+    if determinant == 0.0 || !determinant.is_finite() {
+        return None;
+    }
+    Some(adjugate.map(|row| row.map(|value| value / determinant)))
+}
+
+impl<'a> CommandHandler for CommandRenderer<'a> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        self.draw_bitmap(&bitmap, &transform, smoothing, pixel_snapping);
+    }
+
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        self.draw_bitmap_projected(&bitmap, matrix, &color_transform, smoothing);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.draw_bitmap(&bitmap, &transform, false, PixelSnapping::Never);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let mesh: &Mesh = <dyn ruffle_render::backend::ShapeHandleImpl>::downcast_ref(&*shape.0)
+            .expect("Shape handle must be a software mesh");
+        let to_target = Affine::from_matrix(&transform.matrix, self.scale);
+        let Some(from_target) = to_target.inverse() else {
+            return;
+        };
+        let color_transform = transform.color_transform;
+        let drawing_mask = self.drawing_mask();
+
+        for draw in &mesh.draws {
+            let index_count = if drawing_mask {
+                draw.mask_index_count as usize
+            } else {
+                draw.indices.len()
+            };
+            let vertex = |index: u32| {
+                let vertex = &draw.vertices[index as usize];
+                to_target.apply(vertex.x, vertex.y)
+            };
+
+            match &draw.draw_type {
+                DrawType::Color => {
+                    for triangle in draw.indices[..index_count].chunks_exact(3) {
+                        let color = solid_color(
+                            draw.vertices[triangle[0] as usize].color,
+                            &color_transform,
+                        );
+                        let vertices = [
+                            vertex(triangle[0]),
+                            vertex(triangle[1]),
+                            vertex(triangle[2]),
+                        ];
+                        self.fill(vertices, |_, _| color);
+                    }
+                }
+                DrawType::Gradient { matrix, gradient } => {
+                    let paint = GradientPaint::new(gradient, &color_transform);
+                    let to_gradient = Affine::from_columns(matrix).then(&from_target);
+                    for triangle in draw.indices[..index_count].chunks_exact(3) {
+                        let vertices = [
+                            vertex(triangle[0]),
+                            vertex(triangle[1]),
+                            vertex(triangle[2]),
+                        ];
+                        self.fill(vertices, |x, y| {
+                            let (u, v) = to_gradient.apply(x, y);
+                            paint.color_at(u, v)
+                        });
+                    }
+                }
+                DrawType::Bitmap {
+                    matrix,
+                    handle,
+                    is_smoothed,
+                    is_repeating,
+                } => {
+                    let Some(handle) = handle else {
+                        continue;
+                    };
+                    let bitmap = as_bitmap(handle);
+                    let (width, height) = (bitmap.width() as f32, bitmap.height() as f32);
+                    let texture = bitmap.surface();
+                    let smoothed = *is_smoothed && self.quality != StageQuality::Low;
+                    let to_bitmap = Affine::from_columns(matrix).then(&from_target);
+                    for triangle in draw.indices[..index_count].chunks_exact(3) {
+                        let vertices = [
+                            vertex(triangle[0]),
+                            vertex(triangle[1]),
+                            vertex(triangle[2]),
+                        ];
+                        self.fill(vertices, |x, y| {
+                            let (u, v) = to_bitmap.apply(x, y);
+                            let color = sample_bitmap(
+                                &texture,
+                                u * width,
+                                v * height,
+                                smoothed,
+                                *is_repeating,
+                            );
+                            transform_color(color, &color_transform)
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let color = solid_color(color, &ColorTransform::IDENTITY);
+        let transform = Affine::from_matrix(&matrix, self.scale);
+        self.fill_quad(&transform, |_, _| color);
+    }
+
+    fn push_mask(&mut self) {
+        let size = self.target.width() as usize * self.target.height() as usize;
+        self.masks.push(Mask {
+            coverage: vec![false; size],
+            state: MaskState::Drawing,
+        });
+    }
+
+    fn activate_mask(&mut self) {
+        if let Some(mask) = self.masks.last_mut() {
+            mask.state = MaskState::Active;
+        }
+    }
+
+    fn deactivate_mask(&mut self) {
+        if let Some(mask) = self.masks.last_mut() {
+            mask.state = MaskState::Clearing;
+        }
+    }
+
+    fn pop_mask(&mut self) {
+        self.masks.pop();
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let clip = match self.masks.last() {
+            Some(Mask {
+                state: MaskState::Drawing,
+                ..
+            }) => {
+                // Blend modes have no effect on the shape of a mask.
+                commands.execute(self);
+                return;
+            }
+            Some(Mask {
+                state: MaskState::Clearing,
+                ..
+            }) => return,
+            Some(mask) => Some(&mask.coverage),
+            None => None,
+        };
+
+        let blend_mode = match blend_mode {
+            RenderBlendMode::Builtin(blend_mode) => blend_mode,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend modes are not supported by the software renderer");
+                BlendMode::Normal
+            }
+        };
+
+        let mut layer = Surface::new(self.target.width(), self.target.height());
+        commands.execute(&mut CommandRenderer::new(
+            &mut layer,
+            self.scale as u32,
+            self.quality,
+        ));
+
+        let width = self.target.width();
+        for y in 0..self.target.height() {
+            for x in 0..width {
+                if clip
+                    .map(|clip| clip[y as usize * width as usize + x as usize])
+                    .unwrap_or(true)
+                {
+                    let color = blend(blend_mode, layer.get(x, y), self.target.get(x, y));
+                    self.target.set(x, y, color);
+                }
+            }
+        }
+    }
+}
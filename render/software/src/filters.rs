@@ -0,0 +1,511 @@
+use crate::bitmap::as_bitmap;
+use crate::paint::sample_bitmap;
+use crate::surface::{premultiply, Rgba, Surface};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use swf::{BevelFilter, BlurFilter, ColorMatrixFilter, ConvolutionFilter, GlowFilter};
+
+pub fn is_filter_supported(filter: &Filter) -> bool {
+    matches!(
+        filter,
+        Filter::BlurFilter(_)
+            | Filter::ColorMatrixFilter(_)
+            | Filter::GlowFilter(_)
+            | Filter::DropShadowFilter(_)
+            | Filter::BevelFilter(_)
+            | Filter::ConvolutionFilter(_)
+            | Filter::DisplacementMapFilter(_)
+    )
+}
+
+/// Applies a filter to a surface, producing a surface of the same size.
+///
+/// The filters mirror the shaders of the wgpu backend.
+pub fn apply_filter(source: &Surface, filter: &Filter) -> Surface {
+    match filter {
+        Filter::BlurFilter(filter) => blur(source, filter),
+        Filter::ColorMatrixFilter(filter) => color_matrix(source, filter),
+        Filter::GlowFilter(filter) => glow(source, filter, (0.0, 0.0)),
+        Filter::DropShadowFilter(filter) => {
+            let distance = filter.distance.to_f32();
+            let angle = filter.angle.to_f32();
+            glow(
+                source,
+                &filter.inner_glow_filter(),
+                (-angle.cos() * distance, -angle.sin() * distance),
+            )
+        }
+        Filter::BevelFilter(filter) => bevel(source, filter),
+        Filter::ConvolutionFilter(filter) => convolution(source, filter),
+        Filter::DisplacementMapFilter(filter) => displacement_map(source, filter),
+        filter => {
+            tracing::warn!("Unsupported filter {filter:?}");
+            source.clone()
+        }
+    }
+}
+
+fn blur(source: &Surface, filter: &BlurFilter) -> Surface {
+    let (width, height) = (source.width() as usize, source.height() as usize);
+    let mut pixels: Vec<Rgba> = (0..source.height())
+        .flat_map(|y| (0..source.width()).map(move |x| (x, y)))
+        .map(|(x, y)| source.get(x, y))
+        .collect();
+
+    let mut line = Vec::new();
+    for _ in 0..filter.num_passes() {
+        let strength_x = filter.blur_x.to_f32();
+        for y in 0..height {
+            blur_line(
+                &mut pixels[y * width..(y + 1) * width],
+                strength_x,
+                &mut line,
+            );
+        }
+
+        let strength_y = filter.blur_y.to_f32();
+        let mut column = Vec::with_capacity(height);
+        for x in 0..width {
+            column.clear();
+            column.extend((0..height).map(|y| pixels[y * width + x]));
+            blur_line(&mut column, strength_y, &mut line);
+            for (y, pixel) in column.iter().enumerate() {
+                pixels[y * width + x] = *pixel;
+            }
+        }
+    }
+
+    let mut result = Surface::new(source.width(), source.height());
+    for (i, pixel) in pixels.into_iter().enumerate() {
+        result.set((i % width) as u32, (i / width) as u32, pixel);
+    }
+    result
+}
+
+/// Blurs a line of pixels in place with a box blur of the given size,
+/// where the pixels outside of the line are transparent.
+fn blur_line(pixels: &mut [Rgba], strength: f32, scratch: &mut Vec<Rgba>) {
+    let full_size = strength.min(255.0);
+    if full_size <= 1.0 {
+        return;
+    }
+
+    // The kernel covers `full_size` pixels: `2 * m + 1` pixels of full weight in the center,
+    // and one partially weighted pixel on either side.
+    let radius = (full_size - 1.0) / 2.0;
+    let m = radius.ceil() as isize - 1;
+    let edge_weight = ((radius - m as f32) * 255.0).floor() / 255.0;
+
+    scratch.clear();
+    scratch.extend_from_slice(pixels);
+    let len = scratch.len() as isize;
+    let at = |i: isize| {
+        if (0..len).contains(&i) {
+            scratch[i as usize]
+        } else {
+            [0.0; 4]
+        }
+    };
+
+    let mut sum = [0.0f32; 4];
+    for i in -m..m {
+        let pixel = at(i);
+        for c in 0..4 {
+            sum[c] += pixel[c];
+        }
+    }
+    for (i, target) in pixels.iter_mut().enumerate() {
+        let i = i as isize;
+        let entering = at(i + m);
+        for c in 0..4 {
+            sum[c] += entering[c];
+        }
+        let (left, right) = (at(i - m - 1), at(i + m + 1));
+        for c in 0..4 {
+            let value = (sum[c] + (left[c] + right[c]) * edge_weight) / full_size;
+            target[c] = (value * 255.0).floor() / 255.0;
+        }
+        let leaving = at(i - m);
+        for c in 0..4 {
+            sum[c] -= leaving[c];
+        }
+    }
+}
+
+/// Returns the alpha of `blurred` at the given pixel position, or 0 outside of it.
+fn blurred_alpha(blurred: &Surface, x: f32, y: f32) -> f32 {
+    if x < 0.0 || y < 0.0 || x > blurred.width() as f32 || y > blurred.height() as f32 {
+        return 0.0;
+    }
+    sample_bitmap(blurred, x, y, true, false)[3]
+}
+
+fn glow(source: &Surface, filter: &GlowFilter, offset: (f32, f32)) -> Surface {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let strength = filter.strength.to_f32();
+    let color = filter.color;
+    let rgb = [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+    ];
+    let color_alpha = f32::from(color.a) / 255.0;
+
+    let mut result = Surface::new(source.width(), source.height());
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let blur = blurred_alpha(
+                &blurred,
+                x as f32 + 0.5 + offset.0,
+                y as f32 + 0.5 + offset.1,
+            );
+            let dest = source.get(x, y);
+            let glow = |alpha: f32| [rgb[0] * alpha, rgb[1] * alpha, rgb[2] * alpha, alpha];
+
+            let pixel = if filter.is_inner() {
+                let alpha = color_alpha * ((1.0 - blur) * strength).clamp(0.0, 1.0);
+                let glow = glow(alpha * dest[3]);
+                if !filter.is_knockout() && filter.composite_source() {
+                    std::array::from_fn(|c| glow[c] + dest[c] * (1.0 - alpha))
+                } else {
+                    glow
+                }
+            } else {
+                let alpha = color_alpha * (blur * strength).clamp(0.0, 1.0);
+                if filter.is_knockout() {
+                    glow(alpha * (1.0 - dest[3]))
+                } else if filter.composite_source() {
+                    let glow = glow(alpha * (1.0 - dest[3]));
+                    std::array::from_fn(|c| glow[c] + dest[c])
+                } else {
+                    glow(alpha)
+                }
+            };
+            result.set(x, y, pixel);
+        }
+    }
+    result
+}
+
+fn bevel(source: &Surface, filter: &BevelFilter) -> Surface {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let strength = filter.strength.to_f32();
+    let distance = filter.distance.to_f32();
+    let angle = filter.angle.to_f32();
+    let offset = (angle.cos() * distance, angle.sin() * distance);
+    let highlight = premultiply(filter.highlight_color);
+    let shadow = premultiply(filter.shadow_color);
+    let (inner, outer) = if filter.is_on_top() {
+        (true, true)
+    } else {
+        (filter.is_inner(), !filter.is_inner())
+    };
+
+    let mut result = Surface::new(source.width(), source.height());
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let (center_x, center_y) = (x as f32 + 0.5, y as f32 + 0.5);
+            let left = blurred_alpha(&blurred, center_x + offset.0, center_y + offset.1);
+            let right = blurred_alpha(&blurred, center_x - offset.0, center_y - offset.1);
+            let highlight_alpha = ((left - right) * strength).clamp(0.0, 1.0);
+            let shadow_alpha = ((right - left) * strength).clamp(0.0, 1.0);
+            let glow: Rgba =
+                std::array::from_fn(|c| highlight[c] * highlight_alpha + shadow[c] * shadow_alpha);
+            let dest = source.get(x, y);
+
+            let pixel: Rgba = if inner && outer {
+                if filter.is_knockout() {
+                    glow
+                } else {
+                    std::array::from_fn(|c| dest[c] - dest[c] * glow[3] + glow[c])
+                }
+            } else if inner {
+                if filter.is_knockout() {
+                    std::array::from_fn(|c| glow[c] * dest[3])
+                } else {
+                    std::array::from_fn(|c| glow[c] * dest[3] + dest[c] * (1.0 - glow[3]))
+                }
+            } else if filter.is_knockout() {
+                std::array::from_fn(|c| glow[c] - glow[c] * dest[3])
+            } else {
+                std::array::from_fn(|c| dest[c] + glow[c] - glow[c] * dest[3])
+            };
+            result.set(x, y, pixel);
+        }
+    }
+    result
+}
+
+fn color_matrix(source: &Surface, filter: &ColorMatrixFilter) -> Surface {
+    let m = &filter.matrix;
+    let mut result = Surface::new(source.width(), source.height());
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let color = source.get(x, y);
+            let alpha = color[3];
+            let (r, g, b) = if alpha > 0.0 {
+                (color[0] / alpha, color[1] / alpha, color[2] / alpha)
+            } else {
+                (0.0, 0.0, 0.0)
+            };
+            let row = |i: usize| {
+                (m[i] * r + m[i + 1] * g + m[i + 2] * b + m[i + 3] * alpha + m[i + 4] / 255.0)
+                    .clamp(0.0, 1.0)
+            };
+            let alpha = row(15);
+            result.set(
+                x,
+                y,
+                [row(0) * alpha, row(5) * alpha, row(10) * alpha, alpha],
+            );
+        }
+    }
+    result
+}
+
+/// Returns the straight alpha components of a premultiplied color, in the range `0.0..=255.0`.
+fn unpremultiply(color: Rgba) -> [f32; 4] {
+    let alpha = color[3];
+    if alpha > 0.0 {
+        [
+            color[0] / alpha * 255.0,
+            color[1] / alpha * 255.0,
+            color[2] / alpha * 255.0,
+            alpha * 255.0,
+        ]
+    } else {
+        [0.0; 4]
+    }
+}
+
+fn convolution(source: &Surface, filter: &ConvolutionFilter) -> Surface {
+    let (cols, rows) = (
+        i64::from(filter.num_matrix_cols),
+        i64::from(filter.num_matrix_rows),
+    );
+    let (width, height) = (i64::from(source.width()), i64::from(source.height()));
+    let divisor = if filter.divisor == 0.0 {
+        1.0
+    } else {
+        filter.divisor
+    };
+    let default_color = filter.default_color;
+    let default_color = [
+        f32::from(default_color.r),
+        f32::from(default_color.g),
+        f32::from(default_color.b),
+        f32::from(default_color.a),
+    ];
+
+    let mut result = Surface::new(source.width(), source.height());
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = [0.0f32; 4];
+            for row in 0..rows {
+                for col in 0..cols {
+                    let weight = filter
+                        .matrix
+                        .get((row * cols + col) as usize)
+                        .copied()
+                        .unwrap_or(0.0);
+                    let (sample_x, sample_y) = (x + col - cols / 2, y + row - rows / 2);
+                    let color = if (0..width).contains(&sample_x) && (0..height).contains(&sample_y)
+                    {
+                        unpremultiply(source.get(sample_x as u32, sample_y as u32))
+                    } else if filter.is_clamped() {
+                        unpremultiply(source.get(
+                            sample_x.clamp(0, width - 1) as u32,
+                            sample_y.clamp(0, height - 1) as u32,
+                        ))
+                    } else {
+                        default_color
+                    };
+                    for c in 0..4 {
+                        sum[c] += color[c] * weight;
+                    }
+                }
+            }
+
+            let channel = |c: usize| ((sum[c] / divisor + filter.bias).clamp(0.0, 255.0)) / 255.0;
+            let alpha = if filter.is_preserve_alpha() {
+                source.get(x as u32, y as u32)[3]
+            } else {
+                channel(3)
+            };
+            result.set(
+                x as u32,
+                y as u32,
+                [
+                    channel(0) * alpha,
+                    channel(1) * alpha,
+                    channel(2) * alpha,
+                    alpha,
+                ],
+            );
+        }
+    }
+    result
+}
+
+/// Returns the displacement of a channel of the map, where 128 means no displacement.
+fn displacement_component(map: Rgba, component: u8) -> f32 {
+    match component {
+        1 => map[0] * 255.0,
+        2 => map[1] * 255.0,
+        4 => map[2] * 255.0,
+        8 => map[3] * 255.0,
+        _ => 128.0,
+    }
+}
+
+fn displacement_map(source: &Surface, filter: &DisplacementMapFilter) -> Surface {
+    let Some(map) = filter
+        .map_bitmap
+        .as_ref()
+        .map(|map| as_bitmap(map).surface())
+    else {
+        return source.clone();
+    };
+    let (width, height) = (source.width() as f32, source.height() as f32);
+    let scale_x = filter.scale_x * filter.viewscale_x / 256.0;
+    let scale_y = filter.scale_y * filter.viewscale_y / 256.0;
+    let color = premultiply(filter.color);
+
+    let mut result = Surface::new(source.width(), source.height());
+    for y in 0..source.height() {
+        for x in 0..source.width() {
+            let (source_x, source_y) = (x as f32 + 0.5, y as f32 + 0.5);
+            let map_x = (source_x - filter.map_point.0 as f32) / filter.viewscale_x;
+            let map_y = (source_y - filter.map_point.1 as f32) / filter.viewscale_y;
+            let displacement = if map_x < 0.0
+                || map_y < 0.0
+                || map_x > map.width() as f32
+                || map_y > map.height() as f32
+            {
+                [0.5; 4]
+            } else {
+                sample_bitmap(&map, map_x, map_y, false, false)
+            };
+
+            let displaced_x = source_x
+                + (displacement_component(displacement, filter.component_x) - 128.0) * scale_x;
+            let displaced_y = source_y
+                + (displacement_component(displacement, filter.component_y) - 128.0) * scale_y;
+            let out_of_bounds = displaced_x < 0.0
+                || displaced_y < 0.0
+                || displaced_x > width
+                || displaced_y > height;
+
+            let pixel = match filter.mode {
+                DisplacementMapFilterMode::Wrap => {
+                    sample_bitmap(source, displaced_x, displaced_y, false, true)
+                }
+                DisplacementMapFilterMode::Clamp => {
+                    sample_bitmap(source, displaced_x, displaced_y, false, false)
+                }
+                DisplacementMapFilterMode::Ignore if out_of_bounds => source.get(x, y),
+                DisplacementMapFilterMode::Color if out_of_bounds => color,
+                DisplacementMapFilterMode::Ignore | DisplacementMapFilterMode::Color => {
+                    sample_bitmap(source, displaced_x, displaced_y, false, false)
+                }
+            };
+            result.set(x, y, pixel);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitmap::SoftwareBitmap;
+    use ruffle_render::bitmap::BitmapHandle;
+    use std::sync::Arc;
+    use swf::{Color, ConvolutionFilterFlags};
+
+    fn gradient() -> Surface {
+        let mut surface = Surface::new(3, 3);
+        for y in 0..3 {
+            for x in 0..3 {
+                let value = (x + y * 3) as f32 / 8.0;
+                surface.set(x, y, [value, value, value, 1.0]);
+            }
+        }
+        surface
+    }
+
+    #[test]
+    fn blur_spreads_evenly() {
+        let mut pixels = vec![[0.0; 4]; 9];
+        pixels[4] = [1.0; 4];
+        blur_line(&mut pixels, 3.0, &mut Vec::new());
+        for (i, pixel) in pixels.iter().enumerate() {
+            let expected = if (3..=5).contains(&i) {
+                85.0 / 255.0
+            } else {
+                0.0
+            };
+            assert_eq!(pixel[3], expected, "pixel {i}");
+        }
+    }
+
+    #[test]
+    fn small_blurs_are_ignored() {
+        let mut pixels = vec![[0.0; 4], [1.0; 4], [0.0; 4]];
+        blur_line(&mut pixels, 1.0, &mut Vec::new());
+        assert_eq!(pixels, vec![[0.0; 4], [1.0; 4], [0.0; 4]]);
+    }
+
+    #[test]
+    fn identity_convolution_keeps_pixels() {
+        let source = gradient();
+        let filter = ConvolutionFilter {
+            num_matrix_rows: 3,
+            num_matrix_cols: 3,
+            matrix: vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+            divisor: 1.0,
+            bias: 0.0,
+            default_color: Color::from_rgba(0),
+            flags: ConvolutionFilterFlags::CLAMP,
+        };
+        assert_eq!(convolution(&source, &filter).pixels(), source.pixels());
+    }
+
+    #[test]
+    fn convolution_uses_default_color_outside_of_source() {
+        let source = Surface::filled(2, 1, [1.0; 4]);
+        let filter = ConvolutionFilter {
+            num_matrix_rows: 1,
+            num_matrix_cols: 3,
+            matrix: vec![1.0, 1.0, 1.0],
+            divisor: 3.0,
+            bias: 0.0,
+            default_color: Color::from_rgba(0),
+            flags: ConvolutionFilterFlags::PRESERVE_ALPHA,
+        };
+        let result = convolution(&source, &filter);
+        assert_eq!(result.get(0, 0), result.get(1, 0));
+        assert_eq!(result.get(0, 0)[3], 1.0);
+        assert!((result.get(0, 0)[0] - 2.0 / 3.0).abs() < 1.0 / 255.0);
+    }
+
+    #[test]
+    fn displacement_map_moves_pixels() {
+        let source = gradient();
+        // A red channel of 255 moves every pixel by `127 * 256 / 256` pixels to the right.
+        let map = Surface::filled(3, 3, [1.0, 0.5, 0.5, 1.0]);
+        let filter = DisplacementMapFilter {
+            component_x: 1,
+            component_y: 2,
+            map_bitmap: Some(BitmapHandle(Arc::new(SoftwareBitmap::new(map)))),
+            mode: DisplacementMapFilterMode::Color,
+            scale_x: 256.0 / 127.0,
+            scale_y: 0.0,
+            color: Color::from_rgba(0xFF00FF00),
+            ..Default::default()
+        };
+        let result = displacement_map(&source, &filter);
+        assert_eq!(result.get(0, 1), source.get(1, 1));
+        assert_eq!(result.get(1, 1), source.get(2, 1));
+        assert_eq!(result.get(2, 1), premultiply(Color::from_rgba(0xFF00FF00)));
+    }
+}
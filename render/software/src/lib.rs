@@ -0,0 +1,17 @@
+//! A render backend that rasterizes everything on the CPU.
+//!
+//! This doesn't need any graphics device or driver, which makes it useful for
+//! headless environments such as the exporter and the test framework.
+
+// Bitmaps are shared through `Arc`s by the `RenderBackend` API, but are never sent across threads.
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+mod bitmap;
+mod blend;
+mod commands;
+mod filters;
+mod mesh;
+mod paint;
+mod raster;
+mod surface;
@@ -0,0 +1,76 @@
+use ruffle_render::backend::{RenderBackend, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::tessellator::{self, Gradient, Vertex};
+
+/// A tessellated shape, ready to be rasterized.
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub draw_type: DrawType,
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    pub mask_index_count: u32,
+}
+
+#[derive(Debug)]
+pub enum DrawType {
+    Color,
+    Gradient {
+        matrix: [[f32; 3]; 3],
+        gradient: Gradient,
+    },
+    Bitmap {
+        matrix: [[f32; 3]; 3],
+        handle: Option<BitmapHandle>,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+impl Mesh {
+    pub fn new(
+        mesh: tessellator::Mesh,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Self {
+        let draws = mesh
+            .draws
+            .into_iter()
+            .filter_map(|draw| {
+                let draw_type = match draw.draw_type {
+                    tessellator::DrawType::Color => DrawType::Color,
+                    tessellator::DrawType::Gradient { matrix, gradient } => DrawType::Gradient {
+                        matrix,
+                        gradient: mesh.gradients[gradient].clone(),
+                    },
+                    tessellator::DrawType::Bitmap(bitmap) => DrawType::Bitmap {
+                        matrix: bitmap.matrix,
+                        handle: bitmap_source.bitmap_handle(bitmap.bitmap_id, backend),
+                        is_smoothed: bitmap.is_smoothed,
+                        is_repeating: bitmap.is_repeating,
+                    },
+                    // Pixel Bender shaders can't be compiled by this backend.
+                    tessellator::DrawType::Shader(_) => {
+                        tracing::warn!(
+                            "Shader fills aren't supported by the software renderer, skipping"
+                        );
+                        return None;
+                    }
+                };
+                Some(Draw {
+                    draw_type,
+                    vertices: draw.vertices,
+                    indices: draw.indices,
+                    mask_index_count: draw.mask_index_count,
+                })
+            })
+            .collect();
+        Self { draws }
+    }
+}
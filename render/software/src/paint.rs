@@ -0,0 +1,189 @@
+use crate::surface::{premultiply, Rgba, Surface, TRANSPARENT};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::Gradient;
+use swf::{Color, ColorTransform, GradientInterpolation, GradientSpread};
+
+/// Applies a color transform to a premultiplied color.
+pub fn transform_color(color: Rgba, color_transform: &ColorTransform) -> Rgba {
+    if color[3] <= 0.0 || *color_transform == ColorTransform::IDENTITY {
+        return color;
+    }
+
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    let alpha = (color[3] * mult[3] + add[3]).clamp(0.0, 1.0);
+    let mut result = [0.0, 0.0, 0.0, alpha];
+    for i in 0..3 {
+        let straight = color[i] / color[3];
+        result[i] = (straight * mult[i] + add[i]).clamp(0.0, 1.0) * alpha;
+    }
+    result
+}
+
+/// A gradient, resolved into a lookup table of premultiplied colors.
+pub struct GradientPaint {
+    gradient_type: GradientType,
+    spread: GradientSpread,
+    focal_point: f32,
+    colors: Box<[Rgba; 256]>,
+}
+
+impl GradientPaint {
+    pub fn new(gradient: &Gradient, color_transform: &ColorTransform) -> Self {
+        let records: Vec<(f32, [f32; 4])> = gradient
+            .records
+            .iter()
+            .map(|record| {
+                let color = color_transform * record.color;
+                let mut color = straight(color);
+                if gradient.interpolation == GradientInterpolation::LinearRgb {
+                    for component in &mut color[..3] {
+                        *component = srgb_to_linear(*component);
+                    }
+                }
+                (f32::from(record.ratio) / 255.0, color)
+            })
+            .collect();
+
+        let mut colors = Box::new([TRANSPARENT; 256]);
+        if let (Some(first), Some(last)) = (records.first(), records.last()) {
+            for (i, entry) in colors.iter_mut().enumerate() {
+                let t = i as f32 / 255.0;
+                let mut color = if t <= first.0 {
+                    first.1
+                } else if t >= last.0 {
+                    last.1
+                } else {
+                    let next = records.iter().position(|record| record.0 >= t).unwrap_or(0);
+                    let (start, end) = (records[next.saturating_sub(1)], records[next]);
+                    let span = end.0 - start.0;
+                    let amount = if span > 0.0 {
+                        (t - start.0) / span
+                    } else {
+                        1.0
+                    };
+                    std::array::from_fn(|c| start.1[c] + (end.1[c] - start.1[c]) * amount)
+                };
+                if gradient.interpolation == GradientInterpolation::LinearRgb {
+                    for component in &mut color[..3] {
+                        *component = linear_to_srgb(*component);
+                    }
+                }
+                *entry = [
+                    color[0] * color[3],
+                    color[1] * color[3],
+                    color[2] * color[3],
+                    color[3],
+                ];
+            }
+        }
+
+        Self {
+            gradient_type: gradient.gradient_type,
+            spread: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+            colors,
+        }
+    }
+
+    /// Returns the color at the given gradient coordinates, in the range `0.0..=1.0`.
+    pub fn color_at(&self, u: f32, v: f32) -> Rgba {
+        let t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => (u * 2.0 - 1.0).hypot(v * 2.0 - 1.0),
+            GradientType::Focal => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                let focal = self.focal_point;
+                let (dx, dy) = (focal - x, -y);
+                let length = dx.hypot(dy);
+                if length > 0.0 {
+                    let (dx, dy) = (dx / length, dy / length);
+                    length / ((1.0 - focal * focal * dy * dy).sqrt() + focal * dx)
+                } else {
+                    0.0
+                }
+            }
+        };
+        let t = match self.spread {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Repeat => t.rem_euclid(1.0),
+            GradientSpread::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        };
+        if t.is_nan() {
+            return self.colors[0];
+        }
+        self.colors[(t * 255.0).round() as usize]
+    }
+}
+
+/// Samples a premultiplied bitmap at the given pixel coordinates.
+pub fn sample_bitmap(bitmap: &Surface, x: f32, y: f32, smoothed: bool, repeating: bool) -> Rgba {
+    if bitmap.width() == 0 || bitmap.height() == 0 || !x.is_finite() || !y.is_finite() {
+        return TRANSPARENT;
+    }
+
+    let texel = |x: i64, y: i64| {
+        let (width, height) = (i64::from(bitmap.width()), i64::from(bitmap.height()));
+        let (x, y) = if repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        bitmap.get(x as u32, y as u32)
+    };
+
+    if !smoothed {
+        return texel(x.floor() as i64, y.floor() as i64);
+    }
+
+    let (x, y) = (x - 0.5, y - 0.5);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as i64, y0 as i64);
+    let top_left = texel(x0, y0);
+    let top_right = texel(x0 + 1, y0);
+    let bottom_left = texel(x0, y0 + 1);
+    let bottom_right = texel(x0 + 1, y0 + 1);
+    std::array::from_fn(|c| {
+        let top = top_left[c] + (top_right[c] - top_left[c]) * fx;
+        let bottom = bottom_left[c] + (bottom_right[c] - bottom_left[c]) * fx;
+        top + (bottom - top) * fy
+    })
+}
+
+/// The premultiplied color of a solid fill.
+pub fn solid_color(color: Color, color_transform: &ColorTransform) -> Rgba {
+    premultiply(color_transform * color)
+}
+
+fn straight(color: Color) -> [f32; 4] {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
+
+fn srgb_to_linear(component: f32) -> f32 {
+    if component <= 0.04045 {
+        component / 12.92
+    } else {
+        ((component + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(component: f32) -> f32 {
+    if component <= 0.0031308 {
+        component * 12.92
+    } else {
+        1.055 * component.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,164 @@
+type Point = (f64, f64);
+
+/// Calls `plot` for every pixel of a `width` x `height` surface whose center lies inside the given triangle.
+///
+/// Pixel centers lying exactly on an edge are only plotted for one of the two triangles sharing
+/// that edge, so that no pixel is drawn twice when filling the triangles of a mesh.
+pub fn fill_triangle(
+    width: u32,
+    height: u32,
+    vertices: [(f32, f32); 3],
+    mut plot: impl FnMut(u32, u32),
+) {
+    let [mut a, mut b, c] = vertices.map(|(x, y)| (f64::from(x), f64::from(y)));
+    if [a, b, c]
+        .iter()
+        .any(|p| !p.0.is_finite() || !p.1.is_finite())
+    {
+        return;
+    }
+
+    // Make sure that the inside of the triangle is on the positive side of every edge.
+    let area = edge_function(a, b, c);
+    if area == 0.0 {
+        return;
+    }
+    if area < 0.0 {
+        std::mem::swap(&mut a, &mut b);
+    }
+    let edges = [(a, b), (b, c), (c, a)];
+
+    let min_y = a.1.min(b.1).min(c.1);
+    let max_y = a.1.max(b.1).max(c.1);
+    let min_x = a.0.min(b.0).min(c.0);
+    let max_x = a.0.max(b.0).max(c.0);
+    let Some((y_start, y_end)) = pixel_range(min_y, max_y, height) else {
+        return;
+    };
+    let Some((x_start, x_end)) = pixel_range(min_x, max_x, width) else {
+        return;
+    };
+
+    for y in y_start..=y_end {
+        let center_y = f64::from(y) + 0.5;
+
+        // Narrow down the span of this row, so that we don't test every pixel of the bounding box.
+        let mut span_start = x_start;
+        let mut span_end = x_end;
+        for &(from, to) in &edges {
+            let dy = to.1 - from.1;
+            if dy == 0.0 {
+                continue;
+            }
+            let crossing = from.0 + (center_y - from.1) * (to.0 - from.0) / dy - 0.5;
+            if dy < 0.0 {
+                // The inside lies to the right of this edge.
+                let start = (crossing.floor() - 1.0).max(0.0);
+                if start > f64::from(span_end) {
+                    span_end = 0;
+                    span_start = 1;
+                    break;
+                }
+                span_start = span_start.max(start as u32);
+            } else {
+                // The inside lies to the left of this edge.
+                let end = crossing.ceil() + 1.0;
+                if end < f64::from(span_start) {
+                    span_end = 0;
+                    span_start = 1;
+                    break;
+                }
+                span_end = span_end.min(end as u32);
+            }
+        }
+
+        for x in span_start..=span_end {
+            let center = (f64::from(x) + 0.5, center_y);
+            if edges.iter().all(|&(from, to)| is_inside(from, to, center)) {
+                plot(x, y);
+            }
+        }
+    }
+}
+
+/// The range of pixels whose centers lie between `min` and `max`, clamped to `0..size`.
+fn pixel_range(min: f64, max: f64, size: u32) -> Option<(u32, u32)> {
+    let start = (min - 0.5).ceil().max(0.0);
+    let end = (max - 0.5).floor().min(f64::from(size) - 1.0);
+    if start > end {
+        None
+    } else {
+        Some((start as u32, end as u32))
+    }
+}
+
+#[inline]
+fn edge_function(from: Point, to: Point, point: Point) -> f64 {
+    (to.0 - from.0) * (point.1 - from.1) - (to.1 - from.1) * (point.0 - from.0)
+}
+
+#[inline]
+fn is_inside(from: Point, to: Point, point: Point) -> bool {
+    let value = edge_function(from, to, point);
+    if value != 0.0 {
+        return value > 0.0;
+    }
+    // Break ties consistently: a shared edge is walked in opposite directions
+    // by the two triangles, so exactly one of them will claim the point.
+    let dx = to.0 - from.0;
+    let dy = to.1 - from.1;
+    dy > 0.0 || (dy == 0.0 && dx < 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fill_triangle;
+
+    fn coverage(width: u32, height: u32, triangles: &[[(f32, f32); 3]]) -> Vec<u32> {
+        let mut counts = vec![0; (width * height) as usize];
+        for triangle in triangles {
+            fill_triangle(width, height, *triangle, |x, y| {
+                counts[(y * width + x) as usize] += 1;
+            });
+        }
+        counts
+    }
+
+    #[test]
+    fn fills_pixel_centers() {
+        let counts = coverage(4, 4, &[[(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]]);
+        #[rustfmt::skip]
+        assert_eq!(counts, [
+            1, 1, 1, 1,
+            1, 1, 1, 0,
+            1, 1, 0, 0,
+            1, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn shared_edges_are_filled_once() {
+        // Two triangles of a quad, with opposite windings.
+        let counts = coverage(
+            4,
+            4,
+            &[
+                [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)],
+                [(0.0, 0.0), (0.0, 4.0), (4.0, 4.0)],
+            ],
+        );
+        assert_eq!(counts, [1; 16]);
+    }
+
+    #[test]
+    fn clips_to_surface() {
+        let counts = coverage(2, 2, &[[(-10.0, -10.0), (12.5, -10.0), (-10.0, 12.5)]]);
+        assert_eq!(counts, [1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn ignores_degenerate_triangles() {
+        let counts = coverage(2, 2, &[[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]]);
+        assert_eq!(counts, [0; 4]);
+    }
+}
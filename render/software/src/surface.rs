@@ -0,0 +1,229 @@
+use swf::Color;
+
+/// A premultiplied RGBA color, with every component in the range `0.0..=1.0`.
+pub type Rgba = [f32; 4];
+
+pub const TRANSPARENT: Rgba = [0.0; 4];
+
+/// Converts a straight alpha color into a premultiplied one.
+pub fn premultiply(color: Color) -> Rgba {
+    let alpha = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * alpha,
+        f32::from(color.g) / 255.0 * alpha,
+        f32::from(color.b) / 255.0 * alpha,
+        alpha,
+    ]
+}
+
+/// Draws `src` over `dst`.
+#[inline]
+pub fn source_over(src: Rgba, dst: Rgba) -> Rgba {
+    let inverse_alpha = 1.0 - src[3];
+    [
+        src[0] + dst[0] * inverse_alpha,
+        src[1] + dst[1] * inverse_alpha,
+        src[2] + dst[2] * inverse_alpha,
+        src[3] + dst[3] * inverse_alpha,
+    ]
+}
+
+/// A buffer of premultiplied RGBA pixels, stored with 8 bits per channel.
+#[derive(Clone)]
+pub struct Surface {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Surface {
+    /// Creates a fully transparent surface.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Creates a surface filled with the given color.
+    pub fn filled(width: u32, height: u32, color: Rgba) -> Self {
+        let mut surface = Self::new(width, height);
+        let pixel = to_bytes(color);
+        for chunk in surface.pixels.chunks_exact_mut(4) {
+            chunk.copy_from_slice(&pixel);
+        }
+        surface
+    }
+
+    pub fn from_pixels(width: u32, height: u32, mut pixels: Vec<u8>) -> Self {
+        pixels.resize(width as usize * height as usize * 4, 0);
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        &mut self.pixels
+    }
+
+    #[inline]
+    fn index(&self, x: u32, y: u32) -> usize {
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> Rgba {
+        let i = self.index(x, y);
+        from_bytes(&self.pixels[i..i + 4])
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: u32, y: u32, color: Rgba) {
+        let i = self.index(x, y);
+        self.pixels[i..i + 4].copy_from_slice(&to_bytes(color));
+    }
+
+    /// Returns a copy of this surface that is `factor` times larger in both dimensions.
+    pub fn upscale(&self, factor: u32) -> Surface {
+        let mut result = Surface::new(self.width * factor, self.height * factor);
+        for y in 0..result.height {
+            let source_row = self.index(0, y / factor);
+            for x in 0..result.width {
+                let i = source_row + (x / factor) as usize * 4;
+                let j = result.index(x, y);
+                result.pixels[j..j + 4].copy_from_slice(&self.pixels[i..i + 4]);
+            }
+        }
+        result
+    }
+
+    /// Returns a copy of this surface that is `factor` times smaller in both dimensions,
+    /// averaging every block of `factor` x `factor` pixels into a single pixel.
+    pub fn downscale(&self, factor: u32) -> Surface {
+        let mut result = Surface::new(self.width / factor, self.height / factor);
+        let samples = (factor * factor) as f32;
+        for y in 0..result.height {
+            for x in 0..result.width {
+                let mut sum = TRANSPARENT;
+                for sample_y in y * factor..(y + 1) * factor {
+                    for sample_x in x * factor..(x + 1) * factor {
+                        let sample = self.get(sample_x, sample_y);
+                        for (sum, sample) in sum.iter_mut().zip(sample) {
+                            *sum += sample;
+                        }
+                    }
+                }
+                result.set(x, y, sum.map(|c| c / samples));
+            }
+        }
+        result
+    }
+
+    /// Copies out the given region of this surface.
+    /// Any part of the region outside of this surface is left transparent.
+    pub fn region(&self, x: u32, y: u32, width: u32, height: u32) -> Surface {
+        let mut result = Surface::new(width, height);
+        result.copy_from(self, x, y, 0, 0, width, height);
+        result
+    }
+
+    /// Copies the entirety of `source` into this surface at the given position.
+    pub fn blit(&mut self, source: &Surface, x: u32, y: u32) {
+        self.copy_from(source, 0, 0, x, y, source.width, source.height);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn copy_from(
+        &mut self,
+        source: &Surface,
+        source_x: u32,
+        source_y: u32,
+        dest_x: u32,
+        dest_y: u32,
+        width: u32,
+        height: u32,
+    ) {
+        let width = width
+            .min(source.width.saturating_sub(source_x))
+            .min(self.width.saturating_sub(dest_x));
+        let height = height
+            .min(source.height.saturating_sub(source_y))
+            .min(self.height.saturating_sub(dest_y));
+        let row_length = width as usize * 4;
+        for row in 0..height {
+            let i = source.index(source_x, source_y + row);
+            let j = self.index(dest_x, dest_y + row);
+            self.pixels[j..j + row_length].copy_from_slice(&source.pixels[i..i + row_length]);
+        }
+    }
+}
+
+#[inline]
+fn from_bytes(bytes: &[u8]) -> Rgba {
+    [
+        f32::from(bytes[0]) / 255.0,
+        f32::from(bytes[1]) / 255.0,
+        f32::from(bytes[2]) / 255.0,
+        f32::from(bytes[3]) / 255.0,
+    ]
+}
+
+#[inline]
+fn to_bytes(color: Rgba) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Surface, TRANSPARENT};
+
+    #[test]
+    fn downscale_averages_samples() {
+        let mut surface = Surface::new(4, 2);
+        surface.set(0, 0, [1.0, 0.0, 0.0, 1.0]);
+        surface.set(1, 1, [1.0, 0.0, 0.0, 1.0]);
+        surface.set(2, 0, [0.0, 0.0, 1.0, 1.0]);
+
+        let result = surface.downscale(2);
+        assert_eq!((result.width(), result.height()), (2, 1));
+        assert_eq!(result.pixels(), &[128, 0, 0, 128, 0, 0, 64, 64]);
+    }
+
+    #[test]
+    fn upscale_then_downscale_is_lossless() {
+        let mut surface = Surface::new(3, 2);
+        surface.set(1, 0, [0.2, 0.4, 0.6, 0.8]);
+        surface.set(2, 1, [0.0, 0.0, 0.0, 1.0]);
+
+        let result = surface.upscale(3).downscale(3);
+        assert_eq!(result.pixels(), surface.pixels());
+    }
+
+    #[test]
+    fn region_outside_is_transparent() {
+        let surface = Surface::filled(2, 2, [1.0, 1.0, 1.0, 1.0]);
+        let region = surface.region(1, 1, 2, 2);
+        assert_eq!(region.get(0, 0), [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(region.get(1, 0), TRANSPARENT);
+        assert_eq!(region.get(1, 1), TRANSPARENT);
+    }
+}
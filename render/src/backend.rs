@@ -250,14 +250,6 @@ pub trait Context3D: Downcast {
     // as part of stage rendering
     fn should_render(&self) -> bool;
 
-    // Get a 'disposed' handle - this is what we store in all IndexBuffer3D
-    // objects after dispose() has been called.
-    fn disposed_index_buffer_handle(&self) -> Rc<dyn IndexBuffer>;
-
-    // Get a 'disposed' handle - this is what we store in all VertexBuffer3D
-    // objects after dispose() has been called.
-    fn disposed_vertex_buffer_handle(&self) -> Rc<dyn VertexBuffer>;
-
     fn create_index_buffer(&mut self, usage: BufferUsage, num_indices: u32)
         -> Box<dyn IndexBuffer>;
     fn create_vertex_buffer(
@@ -304,6 +296,22 @@ pub enum Context3DTriangleFace {
     FrontAndBack,
 }
 
+impl Context3DTriangleFace {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"none" {
+            Some(Context3DTriangleFace::None)
+        } else if s == b"back" {
+            Some(Context3DTriangleFace::Back)
+        } else if s == b"front" {
+            Some(Context3DTriangleFace::Front)
+        } else if s == b"frontAndBack" {
+            Some(Context3DTriangleFace::FrontAndBack)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Context3DProfile {
     Baseline,
@@ -370,6 +378,42 @@ impl Context3DCompareMode {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub enum Context3DStencilAction {
+    DecrementSaturate,
+    DecrementWrap,
+    IncrementSaturate,
+    IncrementWrap,
+    Invert,
+    Keep,
+    Set,
+    Zero,
+}
+
+impl Context3DStencilAction {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"decrementSaturate" {
+            Some(Context3DStencilAction::DecrementSaturate)
+        } else if s == b"decrementWrap" {
+            Some(Context3DStencilAction::DecrementWrap)
+        } else if s == b"incrementSaturate" {
+            Some(Context3DStencilAction::IncrementSaturate)
+        } else if s == b"incrementWrap" {
+            Some(Context3DStencilAction::IncrementWrap)
+        } else if s == b"invert" {
+            Some(Context3DStencilAction::Invert)
+        } else if s == b"keep" {
+            Some(Context3DStencilAction::Keep)
+        } else if s == b"set" {
+            Some(Context3DStencilAction::Set)
+        } else if s == b"zero" {
+            Some(Context3DStencilAction::Zero)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Context3DWrapMode {
     Clamp,
@@ -525,6 +569,33 @@ pub enum Context3DCommand<'a> {
     SetScissorRectangle {
         rect: Option<Rectangle<Twips>>,
     },
+    SetStencilActions {
+        triangle_face: Context3DTriangleFace,
+        compare_mode: Context3DCompareMode,
+        action_on_both_pass: Context3DStencilAction,
+        action_on_depth_fail: Context3DStencilAction,
+        action_on_depth_pass_stencil_fail: Context3DStencilAction,
+    },
+    SetStencilReferenceValue {
+        reference_value: u32,
+        read_mask: u32,
+        write_mask: u32,
+    },
+
+    // The `Dispose*` commands free the GPU resources backing an object.
+    // The handle must not be used in any other command afterwards.
+    DisposeIndexBuffer {
+        buffer: &'a dyn IndexBuffer,
+    },
+    DisposeVertexBuffer {
+        buffer: Rc<dyn VertexBuffer>,
+    },
+    DisposeTexture {
+        texture: Rc<dyn Texture>,
+    },
+    DisposeShaders {
+        module: Rc<dyn ShaderModule>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,321 @@
+use crate::matrix::Matrix;
+
+/// A 4x4 transformation matrix, used to render display objects with 3D transforms.
+///
+/// The values are stored in column-major order, matching `flash.geom.Matrix3D.rawData`.
+/// Unlike `Matrix`, the translation is stored in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ],
+    };
+
+    pub fn translate(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[12] = x;
+        matrix.raw_data[13] = y;
+        matrix.raw_data[14] = z;
+        matrix
+    }
+
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[0] = x;
+        matrix.raw_data[5] = y;
+        matrix.raw_data[10] = z;
+        matrix
+    }
+
+    /// A rotation around the X axis, in radians.
+    pub fn rotate_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[5] = cos;
+        matrix.raw_data[6] = sin;
+        matrix.raw_data[9] = -sin;
+        matrix.raw_data[10] = cos;
+        matrix
+    }
+
+    /// A rotation around the Y axis, in radians.
+    pub fn rotate_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[0] = cos;
+        matrix.raw_data[2] = -sin;
+        matrix.raw_data[8] = sin;
+        matrix.raw_data[10] = cos;
+        matrix
+    }
+
+    /// A rotation around the Z axis, in radians.
+    pub fn rotate_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[0] = cos;
+        matrix.raw_data[1] = sin;
+        matrix.raw_data[4] = -sin;
+        matrix.raw_data[5] = cos;
+        matrix
+    }
+
+    /// The perspective projection used by Flash to display 3D objects.
+    ///
+    /// Points are projected towards `center` (in pixels) onto the `z = 0` plane,
+    /// as seen from a viewer at `z = -focal_length`.
+    pub fn perspective_projection(focal_length: f64, center: (f64, f64)) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.raw_data[8] = center.0 / focal_length;
+        matrix.raw_data[9] = center.1 / focal_length;
+        matrix.raw_data[11] = 1.0 / focal_length;
+        matrix
+    }
+
+    /// Returns the element at the given row and column.
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    /// Transforms a point, dividing the result by its `w` component.
+    pub fn transform_point(&self, x: f64, y: f64, z: f64) -> (f64, f64, f64) {
+        let m = |row| {
+            self.get(row, 0) * x + self.get(row, 1) * y + self.get(row, 2) * z + self.get(row, 3)
+        };
+        let w = m(3);
+        (m(0) / w, m(1) / w, m(2) / w)
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.raw_data;
+        let mut inv = [0.0; 16];
+        inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15]
+            + m[9] * m[7] * m[14]
+            + m[13] * m[6] * m[11]
+            - m[13] * m[7] * m[10];
+        inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15]
+            - m[8] * m[7] * m[14]
+            - m[12] * m[6] * m[11]
+            + m[12] * m[7] * m[10];
+        inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15]
+            + m[8] * m[7] * m[13]
+            + m[12] * m[5] * m[11]
+            - m[12] * m[7] * m[9];
+        inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14]
+            - m[8] * m[6] * m[13]
+            - m[12] * m[5] * m[10]
+            + m[12] * m[6] * m[9];
+        inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15]
+            - m[9] * m[3] * m[14]
+            - m[13] * m[2] * m[11]
+            + m[13] * m[3] * m[10];
+        inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15]
+            + m[8] * m[3] * m[14]
+            + m[12] * m[2] * m[11]
+            - m[12] * m[3] * m[10];
+        inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15]
+            - m[8] * m[3] * m[13]
+            - m[12] * m[1] * m[11]
+            + m[12] * m[3] * m[9];
+        inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14]
+            + m[8] * m[2] * m[13]
+            + m[12] * m[1] * m[10]
+            - m[12] * m[2] * m[9];
+        inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15]
+            + m[5] * m[3] * m[14]
+            + m[13] * m[2] * m[7]
+            - m[13] * m[3] * m[6];
+        inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15]
+            - m[4] * m[3] * m[14]
+            - m[12] * m[2] * m[7]
+            + m[12] * m[3] * m[6];
+        inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15]
+            + m[4] * m[3] * m[13]
+            + m[12] * m[1] * m[7]
+            - m[12] * m[3] * m[5];
+        inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14]
+            - m[4] * m[2] * m[13]
+            - m[12] * m[1] * m[6]
+            + m[12] * m[2] * m[5];
+        inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11]
+            - m[5] * m[3] * m[10]
+            - m[9] * m[2] * m[7]
+            + m[9] * m[3] * m[6];
+        inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11]
+            + m[4] * m[3] * m[10]
+            + m[8] * m[2] * m[7]
+            - m[8] * m[3] * m[6];
+        inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11]
+            - m[4] * m[3] * m[9]
+            - m[8] * m[1] * m[7]
+            + m[8] * m[3] * m[5];
+        inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10]
+            + m[4] * m[2] * m[9]
+            + m[8] * m[1] * m[6]
+            - m[8] * m[2] * m[5];
+
+        let determinant = m[0] * inv[0] + m[1] * inv[4] + m[2] * inv[8] + m[3] * inv[12];
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+        Some(Self {
+            raw_data: inv.map(|value| value / determinant),
+        })
+    }
+
+    /// Returns the 3x3 matrix (as rows) mapping homogeneous 2D points `(x, y, 1)` on the `z = 0`
+    /// plane to homogeneous 2D points `(x', y', w')`, discarding the resulting depth.
+    pub fn to_homography(&self) -> [[f64; 3]; 3] {
+        [0, 1, 3].map(|row| [self.get(row, 0), self.get(row, 1), self.get(row, 3)])
+    }
+
+    /// Returns this matrix for use as a shader's world matrix, discarding the resulting depth.
+    pub fn to_world_matrix(&self) -> [[f32; 4]; 4] {
+        let column = |c: usize| {
+            [
+                self.get(0, c) as f32,
+                self.get(1, c) as f32,
+                0.0,
+                self.get(3, c) as f32,
+            ]
+        };
+        [column(0), column(1), [0.0, 0.0, 1.0, 0.0], column(3)]
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Matrix> for Matrix3D {
+    fn from(matrix: Matrix) -> Self {
+        Self {
+            raw_data: [
+                matrix.a.into(),
+                matrix.b.into(),
+                0.0,
+                0.0,
+                matrix.c.into(),
+                matrix.d.into(),
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+                0.0,
+                matrix.tx.to_pixels(),
+                matrix.ty.to_pixels(),
+                0.0,
+                1.0,
+            ],
+        }
+    }
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut raw_data = [0.0; 16];
+        for column in 0..4 {
+            for row in 0..4 {
+                raw_data[column * 4 + row] =
+                    (0..4).map(|i| self.get(row, i) * rhs.get(i, column)).sum();
+            }
+        }
+        Self { raw_data }
+    }
+}
+
+impl std::ops::MulAssign for Matrix3D {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::Twips;
+
+    fn assert_point_eq(actual: (f64, f64, f64), expected: (f64, f64, f64)) {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(
+            close(actual.0, expected.0)
+                && close(actual.1, expected.1)
+                && close(actual.2, expected.2),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn from_matrix() {
+        let matrix = Matrix {
+            a: 2.0,
+            b: 0.5,
+            c: -1.0,
+            d: 3.0,
+            tx: Twips::from_pixels(10.0),
+            ty: Twips::from_pixels(-20.0),
+        };
+        assert_point_eq(
+            Matrix3D::from(matrix).transform_point(4.0, 5.0, 0.0),
+            (2.0 * 4.0 - 5.0 + 10.0, 0.5 * 4.0 + 3.0 * 5.0 - 20.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn rotations_match_flash() {
+        // A positive `rotationZ` turns the X axis towards the Y axis, like `rotation`.
+        assert_point_eq(
+            Matrix3D::rotate_z(std::f64::consts::FRAC_PI_2).transform_point(1.0, 0.0, 0.0),
+            (0.0, 1.0, 0.0),
+        );
+        // A positive `rotationY` turns the X axis away from the viewer.
+        assert_point_eq(
+            Matrix3D::rotate_y(std::f64::consts::FRAC_PI_2).transform_point(1.0, 0.0, 0.0),
+            (0.0, 0.0, -1.0),
+        );
+        // A positive `rotationX` turns the Y axis towards the viewer.
+        assert_point_eq(
+            Matrix3D::rotate_x(std::f64::consts::FRAC_PI_2).transform_point(0.0, 1.0, 0.0),
+            (0.0, 0.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn perspective_projection() {
+        let projection = Matrix3D::perspective_projection(100.0, (50.0, 50.0));
+        // Points on the projection plane are unaffected.
+        assert_point_eq(
+            projection.transform_point(10.0, 20.0, 0.0),
+            (10.0, 20.0, 0.0),
+        );
+        // Points further away are pulled towards the center.
+        let (x, y, _) = projection.transform_point(150.0, 50.0, 100.0);
+        assert_point_eq((x, y, 0.0), (100.0, 50.0, 0.0));
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Matrix3D::translate(1.0, 2.0, 3.0)
+            * Matrix3D::rotate_y(0.3)
+            * Matrix3D::rotate_x(-0.7)
+            * Matrix3D::scale(2.0, 3.0, 4.0);
+        let inverse = matrix.inverse().expect("Matrix must be invertible");
+        let (x, y, z) = matrix.transform_point(5.0, 6.0, 7.0);
+        assert_point_eq(inverse.transform_point(x, y, z), (5.0, 6.0, 7.0));
+        assert_eq!(Matrix3D::scale(0.0, 1.0, 1.0).inverse(), None);
+    }
+}
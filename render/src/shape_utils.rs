@@ -1,7 +1,8 @@
 use crate::matrix::Matrix;
+use crate::pixel_bender::{PixelBenderShaderArgument, PixelBenderShaderHandle};
 use enum_map::Enum;
 use smallvec::SmallVec;
-use swf::{CharacterId, FillStyle, LineStyle, Rectangle, Shape, ShapeRecord, Twips};
+use swf::{CharacterId, LineStyle, Rectangle, Shape, ShapeRecord, Twips};
 
 /// Controls the accuracy of the approximated quadratic curve, when splitting up a cubic curve
 const CUBIC_CURVE_TOLERANCE: f64 = 0.01;
@@ -78,6 +79,28 @@ pub fn calculate_shape_bounds(shape_records: &[swf::ShapeRecord]) -> swf::Rectan
     bounds
 }
 
+/// The style used to paint the inside of a `DrawPath::Fill`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillStyle<'a> {
+    /// A fill that can be defined by a SWF: a solid color, a gradient or a bitmap.
+    Swf(&'a swf::FillStyle),
+
+    /// A fill painted by a Pixel Bender shader (`Graphics.beginShaderFill`).
+    Shader(&'a ShaderFill),
+}
+
+/// A fill that runs a Pixel Bender shader for every pixel it covers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShaderFill {
+    pub shader: PixelBenderShaderHandle,
+
+    /// The inputs and parameters of the shader, as they were when the fill was created.
+    pub arguments: Vec<PixelBenderShaderArgument<'static>>,
+
+    /// The transform from the shader's coordinate space into the shape's coordinate space.
+    pub matrix: Matrix,
+}
+
 /// `DrawPath` represents a solid fill or a stroke.
 /// Fills are always closed paths, while strokes may be open or closed.
 /// Closed paths will have the first point equal to the last point.
@@ -89,7 +112,7 @@ pub enum DrawPath<'a> {
         commands: Vec<DrawCommand>,
     },
     Fill {
-        style: &'a FillStyle,
+        style: FillStyle<'a>,
         commands: Vec<DrawCommand>,
         winding_rule: FillRule,
     },
@@ -513,7 +536,7 @@ impl<'a> ShapeConverter<'a> {
             }
             let style = unsafe { self.fill_styles.get_unchecked(i) };
             self.commands.push(DrawPath::Fill {
-                style,
+                style: FillStyle::Swf(style),
                 commands: path.to_draw_commands().collect(),
                 winding_rule: self.winding_rule,
             });
@@ -1325,7 +1348,7 @@ mod tests {
     use super::*;
     use swf::PointDelta;
 
-    const FILL_STYLES: [FillStyle; 1] = [FillStyle::Color(swf::Color {
+    const FILL_STYLES: [swf::FillStyle; 1] = [swf::FillStyle::Color(swf::Color {
         r: 255,
         g: 0,
         b: 0,
@@ -1377,7 +1400,7 @@ mod tests {
         ]);
         let commands = ShapeConverter::from_shape(&shape).into_commands();
         let expected = vec![DrawPath::Fill {
-            style: &FILL_STYLES[0],
+            style: FillStyle::Swf(&FILL_STYLES[0]),
             commands: vec![
                 DrawCommand::MoveTo(swf::Point::from_pixels(100.0, 100.0)),
                 DrawCommand::LineTo(swf::Point::from_pixels(200.0, 100.0)),
@@ -1423,7 +1446,7 @@ mod tests {
         ]);
         let commands = ShapeConverter::from_shape(&shape).into_commands();
         let expected = vec![DrawPath::Fill {
-            style: &FILL_STYLES[0],
+            style: FillStyle::Swf(&FILL_STYLES[0]),
             commands: vec![
                 DrawCommand::MoveTo(swf::Point::from_pixels(100.0, 100.0)),
                 DrawCommand::LineTo(swf::Point::from_pixels(200.0, 100.0)),
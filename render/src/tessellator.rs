@@ -1,5 +1,6 @@
 use crate::bitmap::BitmapSource;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType};
+use crate::pixel_bender::{PixelBenderShaderArgument, PixelBenderShaderHandle};
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillStyle, GradientType};
 use indexmap::IndexSet;
 use lyon::path::Path;
 use lyon::tessellation::{
@@ -56,15 +57,15 @@ impl ShapeTessellator {
                     commands,
                     is_closed,
                 } => (
-                    style.fill_style(),
+                    FillStyle::Swf(style.fill_style()),
                     ruffle_path_to_lyon_path(commands, *is_closed),
                     true,
                 ),
             };
 
             let (draw, color, needs_flush) = match fill_style {
-                swf::FillStyle::Color(color) => (DrawType::Color, *color, false),
-                swf::FillStyle::LinearGradient(gradient) => {
+                FillStyle::Swf(swf::FillStyle::Color(color)) => (DrawType::Color, *color, false),
+                FillStyle::Swf(swf::FillStyle::LinearGradient(gradient)) => {
                     let uniform =
                         swf_gradient_to_uniforms(GradientType::Linear, gradient, swf::Fixed8::ZERO);
                     let (gradient_index, _) = self.gradients.insert_full(uniform);
@@ -78,7 +79,7 @@ impl ShapeTessellator {
                         true,
                     )
                 }
-                swf::FillStyle::RadialGradient(gradient) => {
+                FillStyle::Swf(swf::FillStyle::RadialGradient(gradient)) => {
                     let uniform =
                         swf_gradient_to_uniforms(GradientType::Radial, gradient, swf::Fixed8::ZERO);
                     let (gradient_index, _) = self.gradients.insert_full(uniform);
@@ -91,10 +92,10 @@ impl ShapeTessellator {
                         true,
                     )
                 }
-                swf::FillStyle::FocalGradient {
+                FillStyle::Swf(swf::FillStyle::FocalGradient {
                     gradient,
                     focal_point,
-                } => {
+                }) => {
                     let uniform =
                         swf_gradient_to_uniforms(GradientType::Focal, gradient, *focal_point);
                     let (gradient_index, _) = self.gradients.insert_full(uniform);
@@ -107,12 +108,12 @@ impl ShapeTessellator {
                         true,
                     )
                 }
-                swf::FillStyle::Bitmap {
+                FillStyle::Swf(swf::FillStyle::Bitmap {
                     id,
                     matrix,
                     is_smoothed,
                     is_repeating,
-                } => {
+                }) => {
                     if let Some(bitmap) = bitmap_source.bitmap_size(*id) {
                         (
                             DrawType::Bitmap(Bitmap {
@@ -133,6 +134,22 @@ impl ShapeTessellator {
                         continue;
                     }
                 }
+                FillStyle::Shader(fill) => {
+                    if let Some(matrix) = fill.matrix.inverse() {
+                        (
+                            DrawType::Shader(Shader {
+                                matrix: shader_to_gl_matrix(matrix),
+                                shader: fill.shader.clone(),
+                                arguments: fill.arguments.clone(),
+                            }),
+                            swf::Color::WHITE,
+                            true,
+                        )
+                    } else {
+                        // A degenerate matrix collapses the shader into nothing.
+                        continue;
+                    }
+                }
             };
 
             if needs_flush || (self.is_stroke && !next_is_stroke) {
@@ -264,6 +281,7 @@ pub enum DrawType {
         gradient: usize,
     },
     Bitmap(Bitmap),
+    Shader(Shader),
 }
 
 impl DrawType {
@@ -272,6 +290,7 @@ impl DrawType {
             Self::Color => "Color",
             Self::Gradient { .. } => "Gradient",
             Self::Bitmap { .. } => "Bitmap",
+            Self::Shader { .. } => "Shader",
         }
     }
 }
@@ -300,6 +319,14 @@ pub struct Bitmap {
     pub is_repeating: bool,
 }
 
+#[derive(Clone, Debug)]
+pub struct Shader {
+    /// Transforms points in the shape (in pixels) into the shader's coordinate space.
+    pub matrix: [[f32; 3]; 3],
+    pub shader: PixelBenderShaderHandle,
+    pub arguments: Vec<PixelBenderShaderArgument<'static>>,
+}
+
 #[allow(clippy::many_single_char_names)]
 fn swf_to_gl_matrix(m: crate::matrix::Matrix) -> [[f32; 3]; 3] {
     let tx = m.tx.get() as f32;
@@ -324,6 +351,16 @@ fn swf_to_gl_matrix(m: crate::matrix::Matrix) -> [[f32; 3]; 3] {
     [[a, d, 0.0], [b, e, 0.0], [c, f, 1.0]]
 }
 
+/// Converts the inverse of a shader fill's matrix into a column-major matrix
+/// that maps shape pixels to shader pixels.
+fn shader_to_gl_matrix(m: crate::matrix::Matrix) -> [[f32; 3]; 3] {
+    [
+        [m.a, m.b, 0.0],
+        [m.c, m.d, 0.0],
+        [m.tx.to_pixels() as f32, m.ty.to_pixels() as f32, 1.0],
+    ]
+}
+
 #[allow(clippy::many_single_char_names)]
 fn swf_bitmap_to_gl_matrix(
     m: crate::matrix::Matrix,
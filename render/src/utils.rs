@@ -399,7 +399,7 @@ fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
 }
 
 /// Converts standard RBGA to premultiplied alpha.
-fn premultiply_alpha_rgba(rgba: &mut [u8]) {
+pub fn premultiply_alpha_rgba(rgba: &mut [u8]) {
     rgba.chunks_exact_mut(4).for_each(|rgba| {
         let a = f32::from(rgba[3]) / 255.0;
         rgba[0] = (f32::from(rgba[0]) * a) as u8;
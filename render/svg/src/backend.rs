@@ -0,0 +1,225 @@
+use crate::bitmap::{as_bitmap, SvgBitmap};
+use crate::shape::SvgShape;
+use crate::writer::{handle_key, CachedContents, SvgWriter};
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::error::Error;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::{Color, Twips};
+
+/// A render backend that writes each frame as an SVG document.
+///
+/// Objects cached as bitmaps (including those with filters) are kept as vectors, and the
+/// filters are converted into their SVG equivalents. Drawing into a `BitmapData` isn't supported.
+pub struct SvgRenderBackend {
+    dimensions: ViewportDimensions,
+
+    /// The vector contents of every bitmap that was drawn by `cacheAsBitmap`.
+    cached: CachedContents,
+
+    /// The last id used for a definition, shared by all frames so that they never clash.
+    next_id: u32,
+
+    /// The last submitted frame.
+    frame: String,
+}
+
+impl SvgRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            dimensions: ViewportDimensions {
+                width: width.max(1),
+                height: height.max(1),
+                scale_factor: 1.0,
+            },
+            cached: CachedContents::new(),
+            next_id: 0,
+            frame: String::new(),
+        }
+    }
+
+    /// Returns the last submitted frame as an SVG document.
+    pub fn capture_frame(&self) -> String {
+        self.frame.clone()
+    }
+
+    /// Writes `commands` on top of a rectangle of the `clear` color, unless it's transparent.
+    fn write(
+        &mut self,
+        clear: Color,
+        width: u32,
+        height: u32,
+        commands: CommandList,
+    ) -> SvgWriter<'_> {
+        let mut writer = SvgWriter::new(&self.cached, &mut self.next_id);
+        if clear.a > 0 {
+            writer.draw_rect(
+                clear,
+                Matrix::create_box(width as f32, height as f32, 0.0, Twips::ZERO, Twips::ZERO),
+            );
+        }
+        commands.execute(&mut writer);
+        writer
+    }
+}
+
+impl RenderBackend for SvgRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.dimensions = ViewportDimensions {
+            width: dimensions.width.max(1),
+            height: dimensions.height.max(1),
+            scale_factor: dimensions.scale_factor,
+        };
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        ShapeHandle(Arc::new(SvgShape::new(shape, bitmap_source, self)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        _handle: BitmapHandle,
+        _commands: CommandList,
+        _quality: StageQuality,
+        _bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        None
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        // Required for `cacheAsBitmap`, which is how filters get applied.
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            let bitmap = as_bitmap(&entry.handle);
+            let (width, height) = (bitmap.width(), bitmap.height());
+            let mut writer = self.write(entry.clear, width, height, entry.commands);
+            writer.apply_filters(&entry.filters);
+            let contents = writer.finish();
+            self.cached.insert(
+                handle_key(&entry.handle),
+                (Arc::downgrade(&entry.handle.0), contents),
+            );
+        }
+        // Forget bitmaps that no longer exist, as new ones may reuse their addresses.
+        self.cached
+            .retain(|_, (handle, _)| handle.strong_count() > 0);
+
+        let (width, height) = (self.dimensions.width, self.dimensions.height);
+        let body = self.write(clear, width, height, commands).finish();
+        self.frame = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{width}" height="{height}" viewBox="0 0 {width} {height}">{body}</svg>"#
+        );
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        let pixels = vec![0; width as usize * height as usize * 4];
+        Ok(BitmapHandle(Arc::new(SvgBitmap::new(
+            width, height, pixels,
+        ))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        Ok(BitmapHandle(Arc::new(SvgBitmap::new(
+            bitmap.width(),
+            bitmap.height(),
+            bitmap.data().to_vec(),
+        ))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let bitmap = bitmap.to_rgba();
+        as_bitmap(handle).update_region(bitmap.data(), region);
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "Renderer: svg\nSurface size: {}x{}",
+            self.dimensions.width, self.dimensions.height
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "svg"
+    }
+
+    fn set_quality(&mut self, _quality: StageQuality) {
+        // Vectors have no quality to choose.
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        _handle: Box<dyn SyncHandle>,
+        _with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        // `render_offscreen` never returns a sync handle.
+        Err(Error::Unimplemented("Sync handle resolution".into()))
+    }
+}
@@ -0,0 +1,86 @@
+use base64::Engine;
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, PixelRegion};
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::io::Cursor;
+
+/// A bitmap owned by the SVG backend, stored as premultiplied RGBA.
+pub struct SvgBitmap {
+    width: u32,
+    height: u32,
+    pixels: RefCell<Vec<u8>>,
+
+    /// The bitmap encoded as a PNG data URI, cleared whenever the pixels change.
+    data_uri: RefCell<Option<String>>,
+}
+
+impl SvgBitmap {
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        Self {
+            width,
+            height,
+            pixels: RefCell::new(pixels),
+            data_uri: RefCell::new(None),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Copies the given region of `pixels`, a full image of the same size as this bitmap.
+    pub fn update_region(&self, pixels: &[u8], region: PixelRegion) {
+        let mut own_pixels = self.pixels.borrow_mut();
+        let row_length = self.width as usize * 4;
+        for y in region.y_min..region.y_max.min(self.height) {
+            let start = y as usize * row_length + region.x_min as usize * 4;
+            let end = y as usize * row_length + region.x_max.min(self.width) as usize * 4;
+            if let (Some(target), Some(source)) =
+                (own_pixels.get_mut(start..end), pixels.get(start..end))
+            {
+                target.copy_from_slice(source);
+            }
+        }
+        *self.data_uri.borrow_mut() = None;
+    }
+
+    /// Returns the contents of this bitmap as a `data:` URI of a PNG image.
+    pub fn data_uri(&self) -> String {
+        self.data_uri
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                let mut pixels = self.pixels.borrow().clone();
+                ruffle_render::utils::unmultiply_alpha_rgba(&mut pixels);
+                let image = image::RgbaImage::from_raw(self.width, self.height, pixels)
+                    .expect("Bitmap must be a valid RgbaImage");
+                let mut png = Vec::new();
+                image
+                    .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+                    .expect("Encoding to memory can't fail");
+                format!(
+                    "data:image/png;base64,{}",
+                    base64::engine::general_purpose::STANDARD.encode(png)
+                )
+            })
+            .clone()
+    }
+}
+
+impl Debug for SvgBitmap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SvgBitmap")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+impl BitmapHandleImpl for SvgBitmap {}
+
+pub fn as_bitmap(handle: &BitmapHandle) -> &SvgBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0).expect("Bitmap handle must be an SVG bitmap")
+}
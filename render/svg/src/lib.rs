@@ -0,0 +1,12 @@
+//! A render backend that writes frames as SVG documents instead of rasterizing them.
+//!
+//! Shapes and text keep their vector outlines, gradients and bitmap fills become SVG paint
+//! servers, and masks and blend modes are mapped to their SVG counterparts where possible.
+
+// Bitmaps are shared through `Arc`s by the `RenderBackend` API, but are never sent across threads.
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+mod bitmap;
+mod shape;
+mod writer;
@@ -0,0 +1,186 @@
+use ruffle_render::backend::{RenderBackend, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule, FillStyle};
+use std::fmt::Write;
+use swf::{Color, Gradient, LineCapStyle, LineJoinStyle, Point, Twips};
+
+/// A shape converted into SVG path data, ready to be written with any transform.
+#[derive(Debug)]
+pub struct SvgShape {
+    pub paths: Vec<SvgPath>,
+}
+
+impl ShapeHandleImpl for SvgShape {}
+
+#[derive(Debug)]
+pub struct SvgPath {
+    /// The `d` attribute of the path, in pixels.
+    pub data: String,
+    pub paint: Paint,
+    pub kind: PathKind,
+}
+
+#[derive(Debug)]
+pub enum PathKind {
+    Fill { even_odd: bool },
+    Stroke(Stroke),
+}
+
+#[derive(Debug)]
+pub struct Stroke {
+    /// The width of the stroke in pixels. Hairlines have a width of zero.
+    pub width: f64,
+
+    /// Whether the width of the stroke scales along with the shape.
+    pub is_scaled: bool,
+
+    pub cap: LineCapStyle,
+    pub join: LineJoinStyle,
+}
+
+#[derive(Debug)]
+pub enum Paint {
+    Color(Color),
+    LinearGradient(Gradient),
+    RadialGradient {
+        gradient: Gradient,
+        focal_point: f64,
+    },
+    Bitmap {
+        handle: BitmapHandle,
+        matrix: swf::Matrix,
+        is_smoothed: bool,
+    },
+}
+
+impl Paint {
+    fn new(
+        style: &swf::FillStyle,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Option<Self> {
+        Some(match style {
+            swf::FillStyle::Color(color) => Paint::Color(*color),
+            swf::FillStyle::LinearGradient(gradient) => Paint::LinearGradient(gradient.clone()),
+            swf::FillStyle::RadialGradient(gradient) => Paint::RadialGradient {
+                gradient: gradient.clone(),
+                focal_point: 0.0,
+            },
+            swf::FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            } => Paint::RadialGradient {
+                gradient: gradient.clone(),
+                focal_point: focal_point.to_f64(),
+            },
+            swf::FillStyle::Bitmap {
+                id,
+                matrix,
+                is_smoothed,
+                ..
+            } => Paint::Bitmap {
+                handle: bitmap_source.bitmap_handle(*id, backend)?,
+                matrix: *matrix,
+                is_smoothed: *is_smoothed,
+            },
+        })
+    }
+}
+
+impl SvgShape {
+    pub fn new(
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Self {
+        let paths = shape
+            .paths
+            .iter()
+            .filter_map(|path| match path {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => {
+                    // Pixel Bender shaders can't be expressed in SVG.
+                    let FillStyle::Swf(style) = style else {
+                        return None;
+                    };
+                    Some(SvgPath {
+                        data: path_data(commands, false),
+                        paint: Paint::new(style, bitmap_source, backend)?,
+                        kind: PathKind::Fill {
+                            even_odd: *winding_rule == FillRule::EvenOdd,
+                        },
+                    })
+                }
+                DrawPath::Stroke {
+                    style,
+                    is_closed,
+                    commands,
+                } => Some(SvgPath {
+                    data: path_data(commands, *is_closed),
+                    paint: Paint::new(style.fill_style(), bitmap_source, backend)?,
+                    kind: PathKind::Stroke(Stroke {
+                        width: style.width().to_pixels(),
+                        is_scaled: style.allow_scale_x() || style.allow_scale_y(),
+                        cap: style.start_cap(),
+                        join: style.join_style(),
+                    }),
+                }),
+            })
+            .collect();
+        Self { paths }
+    }
+}
+
+/// Converts draw commands into the `d` attribute of an SVG path, in pixels.
+fn path_data(commands: &[DrawCommand], is_closed: bool) -> String {
+    fn push(data: &mut String, command: char, points: &[Point<Twips>]) {
+        data.push(command);
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                data.push(' ');
+            }
+            let _ = write!(data, "{} {}", point.x.to_pixels(), point.y.to_pixels());
+        }
+    }
+
+    let mut data = String::new();
+    for command in commands {
+        match command {
+            DrawCommand::MoveTo(point) => push(&mut data, 'M', &[*point]),
+            DrawCommand::LineTo(point) => push(&mut data, 'L', &[*point]),
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                push(&mut data, 'Q', &[*control, *anchor])
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => push(&mut data, 'C', &[*control_a, *control_b, *anchor]),
+        }
+    }
+    if is_closed {
+        data.push('Z');
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_data_is_in_pixels() {
+        let commands = [
+            DrawCommand::MoveTo(Point::new(Twips::new(0), Twips::new(10))),
+            DrawCommand::LineTo(Point::new(Twips::new(200), Twips::new(10))),
+            DrawCommand::QuadraticCurveTo {
+                control: Point::new(Twips::new(300), Twips::new(-20)),
+                anchor: Point::new(Twips::new(400), Twips::new(10)),
+            },
+        ];
+        assert_eq!(path_data(&commands, true), "M0 0.5L10 0.5Q15 -1 20 0.5Z");
+    }
+}
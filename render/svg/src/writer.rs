@@ -0,0 +1,595 @@
+use crate::bitmap::as_bitmap;
+use crate::shape::{Paint, PathKind, SvgShape};
+use ruffle_render::backend::{ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::filters::Filter;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::transform::Transform;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Weak};
+use swf::{
+    BlendMode, BlurFilter, Color, ColorTransform, GlowFilter, Gradient, GradientInterpolation,
+    GradientSpread, LineCapStyle, LineJoinStyle, Twips,
+};
+
+/// Half the size of the square that SWF gradients are defined in, in pixels.
+const GRADIENT_SIZE: f64 = 16384.0 / 20.0;
+
+/// The region that masks and filters apply to. SVG clips them to the bounds of their element
+/// by default, which would cut off glows and masks that extend past their content.
+const EFFECT_REGION: &str = r#"x="-100000" y="-100000" width="200000" height="200000""#;
+
+/// The vector contents of bitmaps drawn for `cacheAsBitmap`, keyed by the address of their handle.
+pub type CachedContents = HashMap<usize, (Weak<dyn BitmapHandleImpl>, String)>;
+
+pub fn handle_key(handle: &BitmapHandle) -> usize {
+    Arc::as_ptr(&handle.0) as *const () as usize
+}
+
+enum MaskState {
+    /// The commands draw the mask itself.
+    DrawingMask,
+
+    /// The commands draw the content that is masked by the mask with the given id.
+    DrawingMaskee(String),
+
+    /// The commands erase the mask again, which isn't needed in SVG.
+    ClearingMask,
+}
+
+/// Writes a command list as SVG elements.
+pub struct SvgWriter<'a> {
+    cached: &'a CachedContents,
+    next_id: &'a mut u32,
+    defs: String,
+
+    /// The elements being written. Masks and blends write their contents into new buffers.
+    buffers: Vec<String>,
+    masks: Vec<MaskState>,
+}
+
+impl<'a> SvgWriter<'a> {
+    pub fn new(cached: &'a CachedContents, next_id: &'a mut u32) -> Self {
+        Self {
+            cached,
+            next_id,
+            defs: String::new(),
+            buffers: vec![String::new()],
+            masks: vec![],
+        }
+    }
+
+    /// Returns the written elements, preceded by the definitions they use.
+    pub fn finish(mut self) -> String {
+        let body = self.buffers.swap_remove(0);
+        if self.defs.is_empty() {
+            body
+        } else {
+            format!("<defs>{}</defs>{}", self.defs, body)
+        }
+    }
+
+    /// Applies filters to everything written so far.
+    pub fn apply_filters(&mut self, filters: &[Filter]) {
+        let mut primitives = String::new();
+        let mut input = "SourceGraphic".to_string();
+        for filter in filters {
+            let result = self.new_id("result");
+            let written = match filter {
+                Filter::BlurFilter(filter) => {
+                    write_blur(&mut primitives, filter, &input, &result);
+                    true
+                }
+                Filter::ColorMatrixFilter(filter) => {
+                    let values: Vec<String> = filter
+                        .matrix
+                        .iter()
+                        .enumerate()
+                        .map(|(i, value)| {
+                            if i % 5 == 4 {
+                                (value / 255.0).to_string()
+                            } else {
+                                value.to_string()
+                            }
+                        })
+                        .collect();
+                    let _ = write!(
+                        primitives,
+                        r#"<feColorMatrix in="{input}" type="matrix" values="{}" result="{result}"/>"#,
+                        values.join(" ")
+                    );
+                    true
+                }
+                Filter::GlowFilter(filter) => {
+                    write_glow(&mut primitives, filter, (0.0, 0.0), &input, &result);
+                    true
+                }
+                Filter::DropShadowFilter(filter) => {
+                    let distance = filter.distance.to_f64();
+                    let angle = filter.angle.to_f64();
+                    let offset = (angle.cos() * distance, angle.sin() * distance);
+                    write_glow(
+                        &mut primitives,
+                        &filter.inner_glow_filter(),
+                        offset,
+                        &input,
+                        &result,
+                    );
+                    true
+                }
+                filter => {
+                    tracing::warn!("Filter {filter:?} can't be expressed in SVG");
+                    false
+                }
+            };
+            if written {
+                input = result;
+            }
+        }
+        if primitives.is_empty() {
+            return;
+        }
+
+        let id = self.new_id("filter");
+        let _ = write!(
+            self.defs,
+            r#"<filter id="{id}" filterUnits="userSpaceOnUse" {EFFECT_REGION} color-interpolation-filters="sRGB">{primitives}</filter>"#
+        );
+        let contents = std::mem::take(self.out());
+        let _ = write!(self.out(), r#"<g filter="url(#{id})">{contents}</g>"#);
+    }
+
+    fn out(&mut self) -> &mut String {
+        self.buffers
+            .last_mut()
+            .expect("SVG writer must have a buffer")
+    }
+
+    fn new_id(&mut self, prefix: &str) -> String {
+        *self.next_id += 1;
+        format!("{prefix}{}", self.next_id)
+    }
+
+    /// Returns the attributes that paint a fill or stroke (depending on `attribute`).
+    fn paint(
+        &mut self,
+        attribute: &str,
+        paint: &Paint,
+        color_transform: &ColorTransform,
+    ) -> String {
+        match paint {
+            Paint::Color(color) => color_attributes(attribute, color, color_transform),
+            Paint::LinearGradient(gradient) => {
+                let id = self.gradient(gradient, None, color_transform);
+                format!(r#"{attribute}="url(#{id})""#)
+            }
+            Paint::RadialGradient {
+                gradient,
+                focal_point,
+            } => {
+                let id = self.gradient(gradient, Some(*focal_point), color_transform);
+                format!(r#"{attribute}="url(#{id})""#)
+            }
+            Paint::Bitmap {
+                handle,
+                matrix,
+                is_smoothed,
+            } => {
+                let id = self.pattern(handle, matrix, *is_smoothed, color_transform);
+                format!(r#"{attribute}="url(#{id})""#)
+            }
+        }
+    }
+
+    /// Defines a linear gradient, or a radial gradient if `focal_point` is given.
+    fn gradient(
+        &mut self,
+        gradient: &Gradient,
+        focal_point: Option<f64>,
+        color_transform: &ColorTransform,
+    ) -> String {
+        let id = self.new_id("gradient");
+        let spread = match gradient.spread {
+            GradientSpread::Pad => "pad",
+            GradientSpread::Reflect => "reflect",
+            GradientSpread::Repeat => "repeat",
+        };
+        let interpolation = if gradient.interpolation == GradientInterpolation::LinearRgb {
+            r#" color-interpolation="linearRGB""#
+        } else {
+            ""
+        };
+        let m = &gradient.matrix;
+        let attributes = format!(
+            r#"id="{id}" gradientUnits="userSpaceOnUse" gradientTransform="matrix({} {} {} {} {} {})" spreadMethod="{spread}"{interpolation}"#,
+            m.a.to_f64(),
+            m.b.to_f64(),
+            m.c.to_f64(),
+            m.d.to_f64(),
+            m.tx.to_pixels(),
+            m.ty.to_pixels()
+        );
+        let element = match focal_point {
+            None => {
+                let _ = write!(
+                    self.defs,
+                    r#"<linearGradient {attributes} x1="{}" y1="0" x2="{GRADIENT_SIZE}" y2="0">"#,
+                    -GRADIENT_SIZE
+                );
+                "linearGradient"
+            }
+            Some(focal_point) => {
+                let _ = write!(
+                    self.defs,
+                    r#"<radialGradient {attributes} cx="0" cy="0" r="{GRADIENT_SIZE}" fx="{}" fy="0">"#,
+                    focal_point * GRADIENT_SIZE
+                );
+                "radialGradient"
+            }
+        };
+        for record in &gradient.records {
+            let [r, g, b, a] = transform_color(&record.color, color_transform);
+            let _ = write!(
+                self.defs,
+                r#"<stop offset="{}" stop-color="{}" stop-opacity="{a}"/>"#,
+                f32::from(record.ratio) / 255.0,
+                hex_color(r, g, b)
+            );
+        }
+        let _ = write!(self.defs, "</{element}>");
+        id
+    }
+
+    /// Defines a repeating pattern of a bitmap fill.
+    /// Bitmap fills that clamp instead of repeating can't be expressed in SVG, and repeat as well.
+    fn pattern(
+        &mut self,
+        handle: &BitmapHandle,
+        matrix: &swf::Matrix,
+        is_smoothed: bool,
+        color_transform: &ColorTransform,
+    ) -> String {
+        let bitmap = as_bitmap(handle);
+        let id = self.new_id("pattern");
+        let image = self.image_element(handle, &Matrix::IDENTITY, color_transform, is_smoothed);
+        // The matrix maps bitmap pixels to twips.
+        let _ = write!(
+            self.defs,
+            r#"<pattern id="{id}" patternUnits="userSpaceOnUse" width="{}" height="{}" patternTransform="matrix({} {} {} {} {} {})">{image}</pattern>"#,
+            bitmap.width(),
+            bitmap.height(),
+            m_to_pixels(matrix.a.to_f64()),
+            m_to_pixels(matrix.b.to_f64()),
+            m_to_pixels(matrix.c.to_f64()),
+            m_to_pixels(matrix.d.to_f64()),
+            matrix.tx.to_pixels(),
+            matrix.ty.to_pixels()
+        );
+        id
+    }
+
+    /// Defines a filter that applies a color transform, unless it wouldn't change anything.
+    fn color_transform_filter(&mut self, color_transform: &ColorTransform) -> Option<String> {
+        if *color_transform == ColorTransform::default() {
+            return None;
+        }
+        let id = self.new_id("colortransform");
+        let [r_mult, g_mult, b_mult, a_mult] = color_transform.mult_rgba_normalized();
+        let [r_add, g_add, b_add, a_add] = color_transform.add_rgba_normalized();
+        let _ = write!(
+            self.defs,
+            r#"<filter id="{id}" color-interpolation-filters="sRGB"><feColorMatrix type="matrix" values="{r_mult} 0 0 0 {r_add} 0 {g_mult} 0 0 {g_add} 0 0 {b_mult} 0 {b_add} 0 0 0 {a_mult} {a_add}"/></filter>"#
+        );
+        Some(id)
+    }
+
+    fn image_element(
+        &mut self,
+        bitmap: &BitmapHandle,
+        matrix: &Matrix,
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) -> String {
+        let filter = self
+            .color_transform_filter(color_transform)
+            .map(|id| format!(r#" filter="url(#{id})""#))
+            .unwrap_or_default();
+        let rendering = if smoothing {
+            ""
+        } else {
+            r#" style="image-rendering:pixelated""#
+        };
+        let svg_bitmap = as_bitmap(bitmap);
+        format!(
+            r#"<image width="{}" height="{}" transform="{}" xlink:href="{}"{rendering}{filter}/>"#,
+            svg_bitmap.width(),
+            svg_bitmap.height(),
+            matrix_transform(matrix),
+            svg_bitmap.data_uri()
+        )
+    }
+}
+
+impl CommandHandler for SvgWriter<'_> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        if let Some((_, contents)) = self.cached.get(&handle_key(&bitmap)) {
+            // Objects cached as bitmaps are drawn with their vector contents instead.
+            let filter = self
+                .color_transform_filter(&transform.color_transform)
+                .map(|id| format!(r#" filter="url(#{id})""#))
+                .unwrap_or_default();
+            let _ = write!(
+                self.out(),
+                r#"<g transform="{}"{filter}>{contents}</g>"#,
+                matrix_transform(&matrix)
+            );
+        } else {
+            let image = self.image_element(&bitmap, &matrix, &transform.color_transform, smoothing);
+            self.out().push_str(&image);
+        }
+    }
+
+    /// Draws a projected bitmap without perspective, which SVG can't express.
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        let w = matrix.get(3, 3);
+        let affine = Matrix {
+            a: (matrix.get(0, 0) / w) as f32,
+            b: (matrix.get(1, 0) / w) as f32,
+            c: (matrix.get(0, 1) / w) as f32,
+            d: (matrix.get(1, 1) / w) as f32,
+            tx: Twips::from_pixels(matrix.get(0, 3) / w),
+            ty: Twips::from_pixels(matrix.get(1, 3) / w),
+        };
+        let image = self.image_element(&bitmap, &affine, &color_transform, smoothing);
+        self.out().push_str(&image);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        let image = self.image_element(
+            &bitmap,
+            &transform.matrix,
+            &transform.color_transform,
+            false,
+        );
+        self.out().push_str(&image);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let shape: &SvgShape = <dyn ShapeHandleImpl>::downcast_ref(&*shape.0)
+            .expect("Shape handle must be an SVG shape");
+        let mut elements = String::new();
+        for path in &shape.paths {
+            match &path.kind {
+                PathKind::Fill { even_odd } => {
+                    let paint = self.paint("fill", &path.paint, &transform.color_transform);
+                    let fill_rule = if *even_odd { "evenodd" } else { "nonzero" };
+                    let _ = write!(
+                        elements,
+                        r#"<path d="{}" {paint} fill-rule="{fill_rule}"/>"#,
+                        path.data
+                    );
+                }
+                PathKind::Stroke(stroke) => {
+                    let paint = self.paint("stroke", &path.paint, &transform.color_transform);
+                    let cap = match stroke.cap {
+                        LineCapStyle::Round => "round",
+                        LineCapStyle::None => "butt",
+                        LineCapStyle::Square => "square",
+                    };
+                    let join = match stroke.join {
+                        LineJoinStyle::Round => "round".to_string(),
+                        LineJoinStyle::Bevel => "bevel".to_string(),
+                        LineJoinStyle::Miter(limit) => {
+                            format!(r#"miter" stroke-miterlimit="{}"#, limit.to_f32())
+                        }
+                    };
+                    // Hairlines are always one pixel wide, no matter the scale.
+                    let (width, scaling) = if stroke.width <= 0.0 {
+                        (1.0, false)
+                    } else {
+                        (stroke.width, stroke.is_scaled)
+                    };
+                    let vector_effect = if scaling {
+                        ""
+                    } else {
+                        r#" vector-effect="non-scaling-stroke""#
+                    };
+                    let _ = write!(
+                        elements,
+                        r#"<path d="{}" fill="none" {paint} stroke-width="{width}" stroke-linecap="{cap}" stroke-linejoin="{join}"{vector_effect}/>"#,
+                        path.data
+                    );
+                }
+            }
+        }
+        if !elements.is_empty() {
+            let _ = write!(
+                self.out(),
+                r#"<g transform="{}">{elements}</g>"#,
+                matrix_transform(&transform.matrix)
+            );
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let paint = color_attributes("fill", &color, &ColorTransform::default());
+        let _ = write!(
+            self.out(),
+            r#"<rect width="1" height="1" transform="{}" {paint}/>"#,
+            matrix_transform(&matrix)
+        );
+    }
+
+    fn push_mask(&mut self) {
+        self.masks.push(MaskState::DrawingMask);
+        self.buffers.push(String::new());
+    }
+
+    fn activate_mask(&mut self) {
+        let contents = self.buffers.pop().unwrap_or_default();
+        let id = self.new_id("mask");
+        let _ = write!(
+            self.defs,
+            r#"<mask id="{id}" maskUnits="userSpaceOnUse" {EFFECT_REGION} style="mask-type:alpha">{contents}</mask>"#
+        );
+        if let Some(state) = self.masks.last_mut() {
+            *state = MaskState::DrawingMaskee(id);
+        }
+        self.buffers.push(String::new());
+    }
+
+    fn deactivate_mask(&mut self) {
+        let contents = self.buffers.pop().unwrap_or_default();
+        if let Some(MaskState::DrawingMaskee(id)) = self.masks.pop() {
+            let _ = write!(self.out(), r#"<g mask="url(#{id})">{contents}</g>"#);
+        }
+        self.masks.push(MaskState::ClearingMask);
+        // The commands erasing the mask are written here and discarded.
+        self.buffers.push(String::new());
+    }
+
+    fn pop_mask(&mut self) {
+        if let Some(MaskState::ClearingMask) = self.masks.pop() {
+            self.buffers.pop();
+        }
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let style = match blend_mode {
+            RenderBlendMode::Builtin(mode) => blend_style(mode),
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend modes can't be expressed in SVG");
+                None
+            }
+        };
+        self.buffers.push(String::new());
+        commands.execute(self);
+        let contents = self.buffers.pop().unwrap_or_default();
+        match style {
+            Some(style) => {
+                let _ = write!(self.out(), r#"<g style="{style}">{contents}</g>"#);
+            }
+            None => self.out().push_str(&contents),
+        }
+    }
+}
+
+/// Returns the CSS style that blends a group like the given blend mode.
+fn blend_style(mode: BlendMode) -> Option<&'static str> {
+    Some(match mode {
+        BlendMode::Normal => return None,
+        BlendMode::Layer => "isolation:isolate",
+        BlendMode::Multiply => "mix-blend-mode:multiply",
+        BlendMode::Screen => "mix-blend-mode:screen",
+        BlendMode::Lighten => "mix-blend-mode:lighten",
+        BlendMode::Darken => "mix-blend-mode:darken",
+        BlendMode::Difference => "mix-blend-mode:difference",
+        BlendMode::Add => "mix-blend-mode:plus-lighter",
+        BlendMode::Overlay => "mix-blend-mode:overlay",
+        BlendMode::HardLight => "mix-blend-mode:hard-light",
+        BlendMode::Subtract | BlendMode::Invert | BlendMode::Alpha | BlendMode::Erase => {
+            tracing::warn!("Blend mode {mode:?} can't be expressed in SVG");
+            return None;
+        }
+    })
+}
+
+/// Writes a blur approximating the box blurs of Flash with a gaussian blur.
+fn write_blur(primitives: &mut String, filter: &BlurFilter, input: &str, result: &str) {
+    // `n` box blurs of width `w` have a standard deviation of `w * sqrt(n / 12)`.
+    let passes = f64::from(filter.num_passes()).max(1.0);
+    let deviation = |size: f64| size * (passes / 12.0).sqrt();
+    let _ = write!(
+        primitives,
+        r#"<feGaussianBlur in="{input}" stdDeviation="{} {}" result="{result}"/>"#,
+        deviation(filter.blur_x.to_f64()),
+        deviation(filter.blur_y.to_f64())
+    );
+}
+
+/// Writes an outer glow, offset by the given distance for drop shadows.
+/// Inner and knockout glows are drawn as outer glows.
+fn write_glow(
+    primitives: &mut String,
+    filter: &GlowFilter,
+    offset: (f64, f64),
+    input: &str,
+    result: &str,
+) {
+    let blurred = format!("{result}-blur");
+    write_blur(primitives, &filter.inner_blur_filter(), input, &blurred);
+    let [r, g, b, a] = transform_color(&filter.color, &ColorTransform::default());
+    let _ = write!(
+        primitives,
+        concat!(
+            r#"<feFlood flood-color="{color}" flood-opacity="{a}"/>"#,
+            r#"<feComposite in2="{blurred}" operator="in"/>"#,
+            r#"<feComponentTransfer><feFuncA type="linear" slope="{strength}"/></feComponentTransfer>"#,
+            r#"<feOffset dx="{dx}" dy="{dy}" result="{result}-glow"/>"#,
+            r#"<feMerge result="{result}"><feMergeNode in="{result}-glow"/><feMergeNode in="{input}"/></feMerge>"#,
+        ),
+        color = hex_color(r, g, b),
+        a = a,
+        blurred = blurred,
+        strength = filter.strength.to_f32(),
+        dx = offset.0,
+        dy = offset.1,
+        result = result,
+        input = input,
+    );
+}
+
+/// Converts a scale factor of a matrix that maps to twips into one that maps to pixels.
+fn m_to_pixels(value: f64) -> f64 {
+    value / 20.0
+}
+
+fn matrix_transform(matrix: &Matrix) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        matrix.a,
+        matrix.b,
+        matrix.c,
+        matrix.d,
+        matrix.tx.to_pixels(),
+        matrix.ty.to_pixels()
+    )
+}
+
+fn transform_color(color: &Color, color_transform: &ColorTransform) -> [f32; 4] {
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    let rgba = [color.r, color.g, color.b, color.a];
+    std::array::from_fn(|i| (f32::from(rgba[i]) / 255.0 * mult[i] + add[i]).clamp(0.0, 1.0))
+}
+
+fn hex_color(r: f32, g: f32, b: f32) -> String {
+    let byte = |value: f32| (value * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", byte(r), byte(g), byte(b))
+}
+
+fn color_attributes(attribute: &str, color: &Color, color_transform: &ColorTransform) -> String {
+    let [r, g, b, a] = transform_color(color, color_transform);
+    let mut attributes = format!(r#"{attribute}="{}""#, hex_color(r, g, b));
+    if a < 1.0 {
+        let _ = write!(attributes, r#" {attribute}-opacity="{a}""#);
+    }
+    attributes
+}
@@ -0,0 +1,66 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform sampler2D u_texture;
+uniform sampler2D u_blurred;
+
+// Premultiplied highlight and shadow colors.
+uniform vec4 u_highlight_color;
+uniform vec4 u_shadow_color;
+
+uniform float u_strength;
+uniform bool u_knockout;
+
+// 0 outer, 1 inner, 2 full
+uniform int u_bevel_type;
+
+// The offset of the highlight in the blurred texture, in texture coordinates.
+// The shadow is offset in the opposite direction.
+uniform vec2 u_offset;
+
+varying vec2 frag_uv;
+
+float blurred_alpha(vec2 uv) {
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        return 0.0;
+    }
+    return texture2D(u_blurred, uv).a;
+}
+
+void main() {
+    float blur_left = blurred_alpha(frag_uv + u_offset);
+    float blur_right = blurred_alpha(frag_uv - u_offset);
+    vec4 dest = texture2D(u_texture, frag_uv);
+
+    bool outer = u_bevel_type == 0 || u_bevel_type == 2;
+    bool inner = u_bevel_type == 1 || u_bevel_type == 2;
+
+    float highlight_alpha = clamp((blur_left - blur_right) * u_strength, 0.0, 1.0);
+    float shadow_alpha = clamp((blur_right - blur_left) * u_strength, 0.0, 1.0);
+    vec4 glow = u_highlight_color * highlight_alpha + u_shadow_color * shadow_alpha;
+
+    if (inner && outer) {
+        if (u_knockout) {
+            gl_FragColor = glow;
+        } else {
+            gl_FragColor = dest - dest * glow.a + glow;
+        }
+    } else if (inner) {
+        if (u_knockout) {
+            gl_FragColor = glow * dest.a;
+        } else {
+            gl_FragColor = glow * dest.a + dest * (1.0 - glow.a);
+        }
+    } else {
+        if (u_knockout) {
+            gl_FragColor = glow - glow * dest.a;
+        } else {
+            gl_FragColor = dest + glow - glow * dest.a;
+        }
+    }
+}
@@ -0,0 +1,50 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform sampler2D u_texture;
+
+// The size of one texel in the direction of the blur.
+uniform vec2 u_direction;
+
+// Full size of the blur kernel, in pixels.
+uniform float u_size;
+
+varying vec2 frag_uv;
+
+// Loops need constant bounds in GLSL ES 1.0; blurs are at most 255 pixels wide.
+const int MAX_RADIUS = 128;
+
+vec4 sample_texel(vec2 uv) {
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        return vec4(0.0);
+    }
+    return texture2D(u_texture, uv);
+}
+
+void main() {
+    // The kernel covers `u_size` pixels: `2 * m + 1` pixels of full weight in the center,
+    // and one partially weighted pixel on either side.
+    float radius = (u_size - 1.0) / 2.0;
+    float m = ceil(radius) - 1.0;
+    float edge_weight = floor((radius - m) * 255.0) / 255.0;
+
+    vec4 total = sample_texel(frag_uv);
+    for (int i = 1; i <= MAX_RADIUS; i++) {
+        float offset = float(i);
+        if (offset > m) {
+            break;
+        }
+        total += sample_texel(frag_uv + u_direction * offset);
+        total += sample_texel(frag_uv - u_direction * offset);
+    }
+    vec2 edge = u_direction * (m + 1.0);
+    total += (sample_texel(frag_uv - edge) + sample_texel(frag_uv + edge)) * edge_weight;
+
+    // This rounding imitates the fixed-point computations of FP, improving emulation accuracy.
+    gl_FragColor = floor(total / u_size * 255.0) / 255.0;
+}
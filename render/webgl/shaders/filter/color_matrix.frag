@@ -0,0 +1,27 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform sampler2D u_texture;
+
+// The first four columns of the 4x5 color matrix, and its offsets (normalized to 0-1).
+uniform mat4 u_color_matrix;
+uniform vec4 u_color_offset;
+
+varying vec2 frag_uv;
+
+void main() {
+    vec4 color = texture2D(u_texture, frag_uv);
+
+    // The matrix operates on unmultiplied colors.
+    if (color.a > 0.0) {
+        color.rgb /= color.a;
+    }
+    color = clamp(u_color_matrix * color + u_color_offset, 0.0, 1.0);
+
+    gl_FragColor = vec4(color.rgb * color.a, color.a);
+}
@@ -0,0 +1,72 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform sampler2D u_texture;
+
+// The convolution matrix, row by row. Every row is padded to 16 columns (4 elements).
+uniform vec4 u_kernel[60];
+
+// The number of columns and rows of the matrix.
+uniform vec2 u_kernel_size;
+
+uniform vec2 u_texel_size;
+uniform float u_divisor;
+uniform float u_bias;
+uniform bool u_preserve_alpha;
+uniform bool u_clamp;
+
+// Unmultiplied color used for pixels outside of the source, unless clamping.
+uniform vec4 u_color;
+
+varying vec2 frag_uv;
+
+const int MAX_ROWS = 15;
+
+vec4 sample_unmultiplied(vec2 uv) {
+    vec4 color;
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0) {
+        if (!u_clamp) {
+            return u_color;
+        }
+        color = texture2D(u_texture, clamp(uv, 0.0, 1.0));
+    } else {
+        color = texture2D(u_texture, uv);
+    }
+    if (color.a > 0.0) {
+        color.rgb /= color.a;
+    }
+    return color;
+}
+
+void main() {
+    vec2 origin = frag_uv - floor(u_kernel_size / 2.0) * u_texel_size;
+
+    vec4 total = vec4(0.0);
+    for (int row = 0; row < MAX_ROWS; row++) {
+        if (float(row) >= u_kernel_size.y) {
+            break;
+        }
+        for (int group = 0; group < 4; group++) {
+            if (float(group * 4) >= u_kernel_size.x) {
+                break;
+            }
+            vec4 weights = u_kernel[row * 4 + group];
+            for (int i = 0; i < 4; i++) {
+                vec2 uv = origin + vec2(float(group * 4 + i), float(row)) * u_texel_size;
+                total += sample_unmultiplied(uv) * weights[i];
+            }
+        }
+    }
+
+    vec4 color = clamp(total / u_divisor + u_bias, 0.0, 1.0);
+    if (u_preserve_alpha) {
+        color.a = texture2D(u_texture, frag_uv).a;
+    }
+
+    gl_FragColor = vec4(color.rgb * color.a, color.a);
+}
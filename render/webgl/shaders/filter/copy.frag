@@ -0,0 +1,15 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform sampler2D u_texture;
+
+varying vec2 frag_uv;
+
+void main() {
+    gl_FragColor = texture2D(u_texture, frag_uv);
+}
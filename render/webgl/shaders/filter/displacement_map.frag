@@ -0,0 +1,65 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform sampler2D u_texture;
+uniform sampler2D u_map;
+
+// Maps texture coordinates of the source onto texture coordinates of the map.
+uniform mat3 u_map_matrix;
+
+// Selects the channel of the map used for each axis. All zero for no displacement.
+uniform vec4 u_component_x;
+uniform vec4 u_component_y;
+
+// The displacement of one step of a map channel, in texture coordinates.
+uniform vec2 u_scale;
+
+// 0 wrap, 1 clamp, 2 ignore, 3 color
+uniform int u_mode;
+
+// Premultiplied color used for out of bounds pixels in color mode.
+uniform vec4 u_color;
+
+varying vec2 frag_uv;
+
+float get_component(vec4 map, vec4 component) {
+    if (dot(component, vec4(1.0)) == 0.0) {
+        return 128.0; // here this means zero displacement
+    }
+    return dot(map, component) * 255.0;
+}
+
+void main() {
+    vec2 map_uv = vec2(u_map_matrix * vec3(frag_uv, 1.0));
+    vec4 map = texture2D(u_map, map_uv);
+    if (map_uv.x < 0.0 || map_uv.x > 1.0 || map_uv.y < 0.0 || map_uv.y > 1.0) {
+        map = vec4(0.5, 0.5, 0.5, 0.5);
+    }
+
+    vec2 displacement = vec2(
+        get_component(map, u_component_x) - 128.0,
+        get_component(map, u_component_y) - 128.0
+    );
+    vec2 displaced_uv = frag_uv + displacement * u_scale;
+    bool out_of_bounds = displaced_uv.x < 0.0 || displaced_uv.x > 1.0 || displaced_uv.y < 0.0 || displaced_uv.y > 1.0;
+
+    if (u_mode == 0) { // wrap
+        // Done here because WebGL1 can't repeat textures that aren't a power of 2.
+        displaced_uv = fract(displaced_uv);
+    } else if (u_mode == 1) { // clamp
+        displaced_uv = clamp(displaced_uv, 0.0, 1.0);
+    } else if (u_mode == 2 && out_of_bounds) { // ignore
+        displaced_uv = frag_uv;
+    }
+    vec4 result = texture2D(u_texture, displaced_uv);
+    if (u_mode == 3 && out_of_bounds) { // color
+        result = u_color;
+    }
+
+    gl_FragColor = result;
+}
@@ -0,0 +1,19 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform mat3 u_matrix;
+
+attribute vec2 position;
+
+varying vec2 frag_uv;
+
+void main() {
+    // Filters always cover the whole viewport of their target.
+    frag_uv = vec2(u_matrix * vec3(position, 1.0));
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
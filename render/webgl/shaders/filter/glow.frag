@@ -0,0 +1,52 @@
+#version 100
+
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+    precision highp float;
+#else
+    precision mediump float;
+#endif
+
+uniform sampler2D u_texture;
+uniform sampler2D u_blurred;
+
+uniform vec4 u_color;
+uniform float u_strength;
+uniform bool u_inner;
+uniform bool u_knockout;
+uniform bool u_composite_source;
+
+// The offset of the blurred texture (used by drop shadows), in texture coordinates.
+uniform vec2 u_offset;
+
+varying vec2 frag_uv;
+
+void main() {
+    vec2 blur_uv = frag_uv + u_offset;
+    float blur = texture2D(u_blurred, blur_uv).a;
+    if (blur_uv.x < 0.0 || blur_uv.x > 1.0 || blur_uv.y < 0.0 || blur_uv.y > 1.0) {
+        blur = 0.0;
+    }
+    vec4 dest = texture2D(u_texture, frag_uv);
+
+    // Start with 1 alpha because we'll be multiplying the whole thing
+    vec4 color = vec4(u_color.rgb, 1.0);
+    if (u_inner) {
+        float alpha = u_color.a * clamp((1.0 - blur) * u_strength, 0.0, 1.0);
+        if (!u_knockout && u_composite_source) {
+            color = color * alpha * dest.a + dest * (1.0 - alpha);
+        } else {
+            color = color * alpha * dest.a;
+        }
+    } else {
+        float alpha = u_color.a * clamp(blur * u_strength, 0.0, 1.0);
+        if (u_knockout) {
+            color = color * alpha * (1.0 - dest.a);
+        } else if (u_composite_source) {
+            color = color * alpha * (1.0 - dest.a) + dest;
+        } else {
+            color = color * alpha;
+        }
+    }
+
+    gl_FragColor = color;
+}
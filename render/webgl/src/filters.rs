@@ -0,0 +1,800 @@
+//! Filters for the WebGL backend.
+//!
+//! Every filter pass draws a quad covering the whole of a temporary render target.
+//! The shaders mirror the filters of the wgpu backend.
+
+use crate::{
+    as_registry_data, Draw, Error, GlExt, ShaderProgram, ShaderUniform, WebGlRenderBackend,
+};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use ruffle_web_common::JsResult;
+use swf::{
+    BevelFilter, BlurFilter, Color, ColorMatrixFilter, ConvolutionFilter, DropShadowFilter,
+    GlowFilter,
+};
+use web_sys::{WebGlFramebuffer, WebGlRenderingContext as Gl, WebGlTexture};
+
+const FILTER_VERTEX_GLSL: &str = include_str!("../shaders/filter/filter.vert");
+const COPY_FRAGMENT_GLSL: &str = include_str!("../shaders/filter/copy.frag");
+const BLUR_FRAGMENT_GLSL: &str = include_str!("../shaders/filter/blur.frag");
+const COLOR_MATRIX_FRAGMENT_GLSL: &str = include_str!("../shaders/filter/color_matrix.frag");
+const CONVOLUTION_FRAGMENT_GLSL: &str = include_str!("../shaders/filter/convolution.frag");
+const GLOW_FRAGMENT_GLSL: &str = include_str!("../shaders/filter/glow.frag");
+const BEVEL_FRAGMENT_GLSL: &str = include_str!("../shaders/filter/bevel.frag");
+const DISPLACEMENT_MAP_FRAGMENT_GLSL: &str =
+    include_str!("../shaders/filter/displacement_map.frag");
+
+/// The largest convolution matrix supported by Flash is 15x15.
+/// Each row is padded to 16 columns, so that it fits in 4 `vec4`s.
+const MAX_CONVOLUTION_SIZE: usize = 15;
+const CONVOLUTION_ROW_LENGTH: usize = 16;
+
+const IDENTITY_MATRIX: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+pub fn is_filter_supported(filter: &Filter) -> bool {
+    matches!(
+        filter,
+        Filter::BlurFilter(_)
+            | Filter::ColorMatrixFilter(_)
+            | Filter::ConvolutionFilter(_)
+            | Filter::DisplacementMapFilter(_)
+            | Filter::DropShadowFilter(_)
+            | Filter::GlowFilter(_)
+            | Filter::BevelFilter(_)
+    )
+}
+
+struct FilterProgram {
+    program: ShaderProgram,
+    quad: Vec<Draw>,
+}
+
+pub struct Filters {
+    copy: FilterProgram,
+    blur: FilterProgram,
+    color_matrix: FilterProgram,
+    convolution: FilterProgram,
+    glow: FilterProgram,
+    bevel: FilterProgram,
+    displacement_map: FilterProgram,
+}
+
+impl Filters {
+    pub fn new(renderer: &WebGlRenderBackend) -> Result<Self, Error> {
+        let gl = &renderer.gl;
+        let vertex = WebGlRenderBackend::compile_shader(gl, Gl::VERTEX_SHADER, FILTER_VERTEX_GLSL)?;
+        let program = |fragment_glsl| -> Result<FilterProgram, Error> {
+            let fragment =
+                WebGlRenderBackend::compile_shader(gl, Gl::FRAGMENT_SHADER, fragment_glsl)?;
+            let program = ShaderProgram::new(gl, &vertex, &fragment)?;
+            let quad = renderer.build_quad_mesh(&program)?;
+            Ok(FilterProgram { program, quad })
+        };
+
+        Ok(Self {
+            copy: program(COPY_FRAGMENT_GLSL)?,
+            blur: program(BLUR_FRAGMENT_GLSL)?,
+            color_matrix: program(COLOR_MATRIX_FRAGMENT_GLSL)?,
+            convolution: program(CONVOLUTION_FRAGMENT_GLSL)?,
+            glow: program(GLOW_FRAGMENT_GLSL)?,
+            bevel: program(BEVEL_FRAGMENT_GLSL)?,
+            displacement_map: program(DISPLACEMENT_MAP_FRAGMENT_GLSL)?,
+        })
+    }
+}
+
+/// A texture that filters can render into.
+pub struct RenderTexture {
+    gl: Gl,
+    texture: WebGlTexture,
+    framebuffer: WebGlFramebuffer,
+    width: u32,
+    height: u32,
+}
+
+impl RenderTexture {
+    pub fn new(gl: &Gl, width: u32, height: u32) -> Result<Self, Error> {
+        let texture = gl.create_texture().ok_or(Error::UnableToCreateTexture)?;
+        gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            Gl::TEXTURE_2D,
+            0,
+            Gl::RGBA as i32,
+            width as i32,
+            height as i32,
+            0,
+            Gl::RGBA,
+            Gl::UNSIGNED_BYTE,
+            None,
+        )
+        .into_js_result()?;
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE as i32);
+
+        let framebuffer = gl
+            .create_framebuffer()
+            .ok_or(Error::UnableToCreateFrameBuffer)?;
+        gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+        gl.check_error("framebuffer_texture_2d (filter target)")?;
+
+        Ok(Self {
+            gl: gl.clone(),
+            texture,
+            framebuffer,
+            width,
+            height,
+        })
+    }
+}
+
+impl Drop for RenderTexture {
+    fn drop(&mut self) {
+        self.gl.delete_framebuffer(Some(&self.framebuffer));
+        self.gl.delete_texture(Some(&self.texture));
+    }
+}
+
+impl WebGlRenderBackend {
+    /// Applies `filter` to the `size` region at `point` of `source`,
+    /// returning the result in a new texture of that size.
+    ///
+    /// The caller is responsible for disabling blending and the stencil test.
+    pub(crate) fn run_filter(
+        &self,
+        filters: &Filters,
+        source: &WebGlTexture,
+        source_dimensions: (u32, u32),
+        point: (u32, u32),
+        size: (u32, u32),
+        filter: &Filter,
+    ) -> Result<RenderTexture, Error> {
+        // Copy the region out first, so that every filter sees a whole texture
+        // with nothing but transparency around it.
+        let input = RenderTexture::new(&self.gl, size.0, size.1)?;
+        self.draw_filter_pass(
+            &filters.copy,
+            &input,
+            &region_matrix(source_dimensions, point, size),
+            |gl, program| {
+                bind_texture(gl, program, ShaderUniform::BitmapTexture, 0, source, false);
+            },
+        );
+
+        match filter {
+            Filter::BlurFilter(filter) => self.blur(filters, input, filter),
+            Filter::ColorMatrixFilter(filter) => self.color_matrix(filters, &input, filter),
+            Filter::ConvolutionFilter(filter) => self.convolution(filters, &input, filter),
+            Filter::GlowFilter(filter) => self.glow(filters, &input, filter, (0.0, 0.0)),
+            Filter::DropShadowFilter(filter) => self.glow(
+                filters,
+                &input,
+                &filter.inner_glow_filter(),
+                drop_shadow_offset(filter),
+            ),
+            Filter::BevelFilter(filter) => self.bevel(filters, &input, filter),
+            Filter::DisplacementMapFilter(filter) => self.displacement_map(filters, &input, filter),
+            filter => {
+                log::warn!("Unsupported filter {filter:?}");
+                Ok(input)
+            }
+        }
+    }
+
+    /// Draws `source` into the `target` framebuffer, replacing the pixels at `point`.
+    ///
+    /// The caller is responsible for disabling blending and the stencil test.
+    pub(crate) fn copy_filter_result(
+        &self,
+        filters: &Filters,
+        source: &RenderTexture,
+        target: &WebGlFramebuffer,
+        point: (u32, u32),
+    ) {
+        let program = &filters.copy;
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, Some(target));
+        self.gl.viewport(
+            point.0 as i32,
+            point.1 as i32,
+            source.width as i32,
+            source.height as i32,
+        );
+        self.gl.use_program(Some(&program.program.program));
+        program
+            .program
+            .uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, &IDENTITY_MATRIX);
+        bind_texture(
+            &self.gl,
+            &program.program,
+            ShaderUniform::BitmapTexture,
+            0,
+            &source.texture,
+            false,
+        );
+        self.draw_filter_quad(program);
+    }
+
+    fn draw_filter_pass(
+        &self,
+        program: &FilterProgram,
+        target: &RenderTexture,
+        texture_matrix: &[[f32; 3]; 3],
+        set_uniforms: impl FnOnce(&Gl, &ShaderProgram),
+    ) {
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, Some(&target.framebuffer));
+        self.gl
+            .viewport(0, 0, target.width as i32, target.height as i32);
+        self.gl.use_program(Some(&program.program.program));
+        program
+            .program
+            .uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, texture_matrix);
+        set_uniforms(&self.gl, &program.program);
+        self.draw_filter_quad(program);
+    }
+
+    fn draw_filter_quad(&self, program: &FilterProgram) {
+        let quad = &program.quad[0];
+        self.bind_vertex_array(Some(&quad.vao));
+        self.gl
+            .draw_elements_with_i32(Gl::TRIANGLES, quad.num_indices, Gl::UNSIGNED_INT, 0);
+    }
+
+    fn blur(
+        &self,
+        filters: &Filters,
+        mut source: RenderTexture,
+        filter: &BlurFilter,
+    ) -> Result<RenderTexture, Error> {
+        let (width, height) = (source.width, source.height);
+        let strength_x = filter.blur_x.to_f32().min(255.0);
+        let strength_y = filter.blur_y.to_f32().min(255.0);
+        let directions = [
+            (strength_x, [1.0 / width as f32, 0.0]),
+            (strength_y, [0.0, 1.0 / height as f32]),
+        ];
+
+        for _ in 0..filter.num_passes() {
+            for (strength, direction) in directions {
+                // Blurs of a single pixel or less don't change anything.
+                if strength <= 1.0 {
+                    continue;
+                }
+                let target = RenderTexture::new(&self.gl, width, height)?;
+                self.draw_filter_pass(&filters.blur, &target, &IDENTITY_MATRIX, |gl, program| {
+                    bind_texture(
+                        gl,
+                        program,
+                        ShaderUniform::BitmapTexture,
+                        0,
+                        &source.texture,
+                        false,
+                    );
+                    program.uniform2f(gl, ShaderUniform::BlurDirection, direction);
+                    program.uniform1f(gl, ShaderUniform::BlurSize, strength);
+                });
+                source = target;
+            }
+        }
+
+        Ok(source)
+    }
+
+    fn color_matrix(
+        &self,
+        filters: &Filters,
+        source: &RenderTexture,
+        filter: &ColorMatrixFilter,
+    ) -> Result<RenderTexture, Error> {
+        let (matrix, offset) = color_matrix_uniforms(filter);
+        let target = RenderTexture::new(&self.gl, source.width, source.height)?;
+        self.draw_filter_pass(
+            &filters.color_matrix,
+            &target,
+            &IDENTITY_MATRIX,
+            |gl, program| {
+                bind_texture(
+                    gl,
+                    program,
+                    ShaderUniform::BitmapTexture,
+                    0,
+                    &source.texture,
+                    false,
+                );
+                program.uniform_matrix4fv(gl, ShaderUniform::ColorMatrix, &matrix);
+                program.uniform4fv(gl, ShaderUniform::ColorOffset, &offset);
+            },
+        );
+        Ok(target)
+    }
+
+    fn convolution(
+        &self,
+        filters: &Filters,
+        source: &RenderTexture,
+        filter: &ConvolutionFilter,
+    ) -> Result<RenderTexture, Error> {
+        let (kernel, columns, rows) = convolution_kernel(filter);
+        let divisor = if filter.divisor == 0.0 {
+            1.0
+        } else {
+            filter.divisor
+        };
+
+        let target = RenderTexture::new(&self.gl, source.width, source.height)?;
+        self.draw_filter_pass(
+            &filters.convolution,
+            &target,
+            &IDENTITY_MATRIX,
+            |gl, program| {
+                bind_texture(
+                    gl,
+                    program,
+                    ShaderUniform::BitmapTexture,
+                    0,
+                    &source.texture,
+                    false,
+                );
+                program.uniform4fv(gl, ShaderUniform::ConvolutionKernel, &kernel);
+                program.uniform2f(
+                    gl,
+                    ShaderUniform::ConvolutionKernelSize,
+                    [columns as f32, rows as f32],
+                );
+                program.uniform2f(
+                    gl,
+                    ShaderUniform::TexelSize,
+                    [1.0 / source.width as f32, 1.0 / source.height as f32],
+                );
+                program.uniform1f(gl, ShaderUniform::ConvolutionDivisor, divisor);
+                program.uniform1f(gl, ShaderUniform::ConvolutionBias, filter.bias / 255.0);
+                program.uniform1i(
+                    gl,
+                    ShaderUniform::ConvolutionPreserveAlpha,
+                    filter.is_preserve_alpha() as i32,
+                );
+                program.uniform1i(
+                    gl,
+                    ShaderUniform::ConvolutionClamp,
+                    filter.is_clamped() as i32,
+                );
+                program.uniform4fv(
+                    gl,
+                    ShaderUniform::FilterColor,
+                    &normalized(filter.default_color),
+                );
+            },
+        );
+        Ok(target)
+    }
+
+    fn glow(
+        &self,
+        filters: &Filters,
+        source: &RenderTexture,
+        filter: &GlowFilter,
+        offset: (f32, f32),
+    ) -> Result<RenderTexture, Error> {
+        let blurred = self.blur(
+            filters,
+            self.duplicate(filters, source)?,
+            &filter.inner_blur_filter(),
+        )?;
+
+        let target = RenderTexture::new(&self.gl, source.width, source.height)?;
+        self.draw_filter_pass(&filters.glow, &target, &IDENTITY_MATRIX, |gl, program| {
+            bind_texture(
+                gl,
+                program,
+                ShaderUniform::BitmapTexture,
+                0,
+                &source.texture,
+                false,
+            );
+            bind_texture(
+                gl,
+                program,
+                ShaderUniform::BlurredTexture,
+                1,
+                &blurred.texture,
+                true,
+            );
+            program.uniform4fv(gl, ShaderUniform::FilterColor, &normalized(filter.color));
+            program.uniform1f(gl, ShaderUniform::FilterStrength, filter.strength.to_f32());
+            program.uniform1i(gl, ShaderUniform::FilterInner, filter.is_inner() as i32);
+            program.uniform1i(
+                gl,
+                ShaderUniform::FilterKnockout,
+                filter.is_knockout() as i32,
+            );
+            program.uniform1i(
+                gl,
+                ShaderUniform::FilterCompositeSource,
+                filter.composite_source() as i32,
+            );
+            program.uniform2f(
+                gl,
+                ShaderUniform::FilterOffset,
+                [
+                    offset.0 / source.width as f32,
+                    offset.1 / source.height as f32,
+                ],
+            );
+        });
+        Ok(target)
+    }
+
+    fn bevel(
+        &self,
+        filters: &Filters,
+        source: &RenderTexture,
+        filter: &BevelFilter,
+    ) -> Result<RenderTexture, Error> {
+        let blurred = self.blur(
+            filters,
+            self.duplicate(filters, source)?,
+            &filter.inner_blur_filter(),
+        )?;
+        let distance = filter.distance.to_f32();
+        let angle = filter.angle.to_f32();
+        let offset = [
+            angle.cos() * distance / source.width as f32,
+            angle.sin() * distance / source.height as f32,
+        ];
+        let bevel_type = if filter.is_on_top() {
+            2
+        } else if filter.is_inner() {
+            1
+        } else {
+            0
+        };
+
+        let target = RenderTexture::new(&self.gl, source.width, source.height)?;
+        self.draw_filter_pass(&filters.bevel, &target, &IDENTITY_MATRIX, |gl, program| {
+            bind_texture(
+                gl,
+                program,
+                ShaderUniform::BitmapTexture,
+                0,
+                &source.texture,
+                false,
+            );
+            bind_texture(
+                gl,
+                program,
+                ShaderUniform::BlurredTexture,
+                1,
+                &blurred.texture,
+                true,
+            );
+            program.uniform4fv(
+                gl,
+                ShaderUniform::BevelHighlightColor,
+                &premultiplied(filter.highlight_color),
+            );
+            program.uniform4fv(
+                gl,
+                ShaderUniform::BevelShadowColor,
+                &premultiplied(filter.shadow_color),
+            );
+            program.uniform1f(gl, ShaderUniform::FilterStrength, filter.strength.to_f32());
+            program.uniform1i(
+                gl,
+                ShaderUniform::FilterKnockout,
+                filter.is_knockout() as i32,
+            );
+            program.uniform1i(gl, ShaderUniform::BevelType, bevel_type);
+            program.uniform2f(gl, ShaderUniform::FilterOffset, offset);
+        });
+        Ok(target)
+    }
+
+    fn displacement_map(
+        &self,
+        filters: &Filters,
+        source: &RenderTexture,
+        filter: &DisplacementMapFilter,
+    ) -> Result<RenderTexture, Error> {
+        let Some(map) = &filter.map_bitmap else {
+            return self.duplicate(filters, source);
+        };
+        let map = as_registry_data(map);
+
+        let (width, height) = (source.width as f32, source.height as f32);
+        let map_width = filter.viewscale_x * map.width as f32;
+        let map_height = filter.viewscale_y * map.height as f32;
+        let map_matrix = [
+            [width / map_width, 0.0, 0.0],
+            [0.0, height / map_height, 0.0],
+            [
+                -filter.map_point.0 as f32 / map_width,
+                -filter.map_point.1 as f32 / map_height,
+                1.0,
+            ],
+        ];
+        let scale = [
+            filter.viewscale_x * filter.scale_x / 256.0 / width,
+            filter.viewscale_y * filter.scale_y / 256.0 / height,
+        ];
+        let mode = match filter.mode {
+            DisplacementMapFilterMode::Wrap => 0,
+            DisplacementMapFilterMode::Clamp => 1,
+            DisplacementMapFilterMode::Ignore => 2,
+            DisplacementMapFilterMode::Color => 3,
+        };
+
+        let target = RenderTexture::new(&self.gl, source.width, source.height)?;
+        self.draw_filter_pass(
+            &filters.displacement_map,
+            &target,
+            &IDENTITY_MATRIX,
+            |gl, program| {
+                bind_texture(
+                    gl,
+                    program,
+                    ShaderUniform::BitmapTexture,
+                    0,
+                    &source.texture,
+                    true,
+                );
+                bind_texture(
+                    gl,
+                    program,
+                    ShaderUniform::MapTexture,
+                    1,
+                    &map.texture,
+                    false,
+                );
+                program.uniform_matrix3fv(gl, ShaderUniform::DisplacementMapMatrix, &map_matrix);
+                program.uniform4fv(
+                    gl,
+                    ShaderUniform::DisplacementComponentX,
+                    &component_mask(filter.component_x),
+                );
+                program.uniform4fv(
+                    gl,
+                    ShaderUniform::DisplacementComponentY,
+                    &component_mask(filter.component_y),
+                );
+                program.uniform2f(gl, ShaderUniform::DisplacementScale, scale);
+                program.uniform1i(gl, ShaderUniform::DisplacementMode, mode);
+                program.uniform4fv(gl, ShaderUniform::FilterColor, &premultiplied(filter.color));
+            },
+        );
+        Ok(target)
+    }
+
+    /// Copies `source` into a new texture, so that it can be filtered while still being read.
+    fn duplicate(&self, filters: &Filters, source: &RenderTexture) -> Result<RenderTexture, Error> {
+        let target = RenderTexture::new(&self.gl, source.width, source.height)?;
+        self.draw_filter_pass(&filters.copy, &target, &IDENTITY_MATRIX, |gl, program| {
+            bind_texture(
+                gl,
+                program,
+                ShaderUniform::BitmapTexture,
+                0,
+                &source.texture,
+                false,
+            );
+        });
+        Ok(target)
+    }
+}
+
+/// The texture matrix that maps a target of `size` onto the region at `point`
+/// of a texture with the given dimensions.
+fn region_matrix(dimensions: (u32, u32), point: (u32, u32), size: (u32, u32)) -> [[f32; 3]; 3] {
+    let (width, height) = (dimensions.0 as f32, dimensions.1 as f32);
+    [
+        [size.0 as f32 / width, 0.0, 0.0],
+        [0.0, size.1 as f32 / height, 0.0],
+        [point.0 as f32 / width, point.1 as f32 / height, 1.0],
+    ]
+}
+
+/// Splits a color matrix filter into a GLSL (column-major) matrix and an offset,
+/// with the offset normalized to `0.0..=1.0`.
+fn color_matrix_uniforms(filter: &ColorMatrixFilter) -> ([[f32; 4]; 4], [f32; 4]) {
+    let m = &filter.matrix;
+    let matrix =
+        std::array::from_fn(|column| [m[column], m[5 + column], m[10 + column], m[15 + column]]);
+    let offset = [m[4] / 255.0, m[9] / 255.0, m[14] / 255.0, m[19] / 255.0];
+    (matrix, offset)
+}
+
+/// Lays out the matrix of a convolution filter in padded rows, as the shader expects.
+/// Returns the kernel with its number of columns and rows.
+fn convolution_kernel(
+    filter: &ConvolutionFilter,
+) -> (
+    [f32; MAX_CONVOLUTION_SIZE * CONVOLUTION_ROW_LENGTH],
+    usize,
+    usize,
+) {
+    let columns = (filter.num_matrix_cols as usize).min(MAX_CONVOLUTION_SIZE);
+    let rows = (filter.num_matrix_rows as usize).min(MAX_CONVOLUTION_SIZE);
+    let mut kernel = [0.0; MAX_CONVOLUTION_SIZE * CONVOLUTION_ROW_LENGTH];
+    for row in 0..rows {
+        for column in 0..columns {
+            kernel[row * CONVOLUTION_ROW_LENGTH + column] = filter
+                .matrix
+                .get(row * filter.num_matrix_cols as usize + column)
+                .copied()
+                .unwrap_or_default();
+        }
+    }
+    (kernel, columns, rows)
+}
+
+/// The offset in pixels to sample the shadow of a drop shadow filter from.
+fn drop_shadow_offset(filter: &DropShadowFilter) -> (f32, f32) {
+    let distance = filter.distance.to_f32();
+    let angle = filter.angle.to_f32();
+    (-angle.cos() * distance, -angle.sin() * distance)
+}
+
+/// Binds `texture` to the given texture unit and sampler uniform.
+fn bind_texture(
+    gl: &Gl,
+    program: &ShaderProgram,
+    uniform: ShaderUniform,
+    unit: u32,
+    texture: &WebGlTexture,
+    smoothing: bool,
+) {
+    gl.active_texture(Gl::TEXTURE0 + unit);
+    gl.bind_texture(Gl::TEXTURE_2D, Some(texture));
+    let filter = if smoothing {
+        Gl::LINEAR as i32
+    } else {
+        Gl::NEAREST as i32
+    };
+    gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
+    gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
+    program.uniform1i(gl, uniform, unit as i32);
+}
+
+fn normalized(color: Color) -> [f32; 4] {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
+
+fn premultiplied(color: Color) -> [f32; 4] {
+    let [r, g, b, a] = normalized(color);
+    [r * a, g * a, b * a, a]
+}
+
+/// Selects the channel of a displacement map, as a mask over RGBA.
+fn component_mask(component: u8) -> [f32; 4] {
+    match component {
+        1 => [1.0, 0.0, 0.0, 0.0],
+        2 => [0.0, 1.0, 0.0, 0.0],
+        4 => [0.0, 0.0, 1.0, 0.0],
+        8 => [0.0, 0.0, 0.0, 1.0],
+        _ => [0.0; 4],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{ConvolutionFilterFlags, DropShadowFilterFlags, Fixed16, Fixed8};
+
+    #[test]
+    fn region_matrix_maps_onto_source_region() {
+        let matrix = region_matrix((200, 100), (50, 20), (100, 40));
+        let map = |x: f32, y: f32| {
+            (
+                matrix[0][0] * x + matrix[1][0] * y + matrix[2][0],
+                matrix[0][1] * x + matrix[1][1] * y + matrix[2][1],
+            )
+        };
+        assert_eq!(map(0.0, 0.0), (0.25, 0.2));
+        assert_eq!(map(1.0, 1.0), (0.75, 0.6));
+    }
+
+    #[test]
+    fn color_matrix_uniforms_are_column_major() {
+        let mut matrix = [0.0; 20];
+        for (i, value) in matrix.iter_mut().enumerate() {
+            *value = i as f32;
+        }
+        let (matrix, offset) = color_matrix_uniforms(&ColorMatrixFilter { matrix });
+        assert_eq!(matrix[0], [0.0, 5.0, 10.0, 15.0]);
+        assert_eq!(matrix[3], [3.0, 8.0, 13.0, 18.0]);
+        assert_eq!(
+            offset,
+            [4.0 / 255.0, 9.0 / 255.0, 14.0 / 255.0, 19.0 / 255.0]
+        );
+    }
+
+    #[test]
+    fn convolution_kernel_pads_rows() {
+        let filter = ConvolutionFilter {
+            num_matrix_rows: 2,
+            num_matrix_cols: 3,
+            matrix: vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            divisor: 1.0,
+            bias: 0.0,
+            default_color: Color::TRANSPARENT,
+            flags: ConvolutionFilterFlags::empty(),
+        };
+        let (kernel, columns, rows) = convolution_kernel(&filter);
+        assert_eq!((columns, rows), (3, 2));
+        assert_eq!(kernel[..4], [1.0, 2.0, 3.0, 0.0]);
+        assert_eq!(
+            kernel[CONVOLUTION_ROW_LENGTH..CONVOLUTION_ROW_LENGTH + 4],
+            [4.0, 5.0, 6.0, 0.0]
+        );
+        assert!(kernel[2 * CONVOLUTION_ROW_LENGTH..]
+            .iter()
+            .all(|value| *value == 0.0));
+    }
+
+    #[test]
+    fn convolution_kernel_ignores_missing_values() {
+        let filter = ConvolutionFilter {
+            num_matrix_rows: 2,
+            num_matrix_cols: 2,
+            matrix: vec![1.0, 2.0, 3.0],
+            divisor: 1.0,
+            bias: 0.0,
+            default_color: Color::TRANSPARENT,
+            flags: ConvolutionFilterFlags::empty(),
+        };
+        let (kernel, _, _) = convolution_kernel(&filter);
+        assert_eq!(
+            kernel[CONVOLUTION_ROW_LENGTH..CONVOLUTION_ROW_LENGTH + 2],
+            [3.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn drop_shadow_offset_points_away_from_the_light() {
+        let filter = DropShadowFilter {
+            color: Color::BLACK,
+            blur_x: Fixed16::from_f32(4.0),
+            blur_y: Fixed16::from_f32(4.0),
+            angle: Fixed16::ZERO,
+            distance: Fixed16::from_f32(4.0),
+            strength: Fixed8::ONE,
+            flags: DropShadowFilterFlags::empty(),
+        };
+        assert_eq!(drop_shadow_offset(&filter), (-4.0, -0.0));
+    }
+
+    #[test]
+    fn colors_are_normalized_and_premultiplied() {
+        let color = Color {
+            r: 0,
+            g: 51,
+            b: 255,
+            a: 255,
+        };
+        assert_eq!(normalized(color), [0.0, 0.2, 1.0, 1.0]);
+        let half = Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 51,
+        };
+        assert_eq!(premultiplied(half), [0.2, 0.0, 0.0, 0.2]);
+    }
+
+    #[test]
+    fn component_masks_select_one_channel() {
+        assert_eq!(component_mask(1), [1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(component_mask(8), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(component_mask(3), [0.0; 4]);
+    }
+}
@@ -2,6 +2,7 @@
 // Remove this when we start using `Rc` when compiling for wasm
 #![allow(clippy::arc_with_non_send_sync)]
 
+use crate::filters::{is_filter_supported, Filters};
 use bytemuck::{Pod, Zeroable};
 use ruffle_render::backend::{
     BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
@@ -13,6 +14,8 @@ use ruffle_render::bitmap::{
 };
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error as BitmapError;
+use ruffle_render::filters::Filter;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
@@ -22,7 +25,7 @@ use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
-use swf::{BlendMode, Color};
+use swf::{BlendMode, Color, ColorTransform};
 use thiserror::Error;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -31,6 +34,8 @@ use web_sys::{
     WebGlTexture, WebGlUniformLocation, WebGlVertexArrayObject, WebglDebugRendererInfo,
 };
 
+mod filters;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Couldn't create GL context")]
@@ -127,6 +132,9 @@ pub struct WebGlRenderBackend {
     bitmap_program: ShaderProgram,
     gradient_program: ShaderProgram,
 
+    // The programs used for filters, if they could be compiled on this device.
+    filters: Option<Filters>,
+
     shape_tessellator: ShapeTessellator,
 
     color_quad_draws: Vec<Draw>,
@@ -167,6 +175,15 @@ impl Drop for RegistryData {
 
 impl BitmapHandleImpl for RegistryData {}
 
+/// An offscreen render or filter, whose result stays in the texture until it's read back.
+#[derive(Debug)]
+struct WebGlSyncHandle {
+    handle: BitmapHandle,
+    region: PixelRegion,
+}
+
+impl SyncHandle for WebGlSyncHandle {}
+
 fn as_registry_data(handle: &BitmapHandle) -> &RegistryData {
     <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
         .expect("Bitmap handle must be webgl RegistryData")
@@ -298,6 +315,8 @@ impl WebGlRenderBackend {
             gradient_program,
             bitmap_program,
 
+            filters: None,
+
             shape_tessellator: ShapeTessellator::new(),
 
             color_quad_draws: vec![],
@@ -326,6 +345,11 @@ impl WebGlRenderBackend {
         renderer.color_quad_draws.append(&mut color_quad_mesh);
         renderer.bitmap_quad_draws.append(&mut bitmap_quad_mesh);
 
+        // Filters aren't essential, so don't fail if a device can't handle their shaders.
+        renderer.filters = Filters::new(&renderer)
+            .map_err(|e| log::warn!("Couldn't create filter programs: {}", e))
+            .ok();
+
         renderer.set_viewport_dimensions(ViewportDimensions {
             width: 1,
             height: 1,
@@ -576,6 +600,12 @@ impl WebGlRenderBackend {
 
         let mut draws = Vec::with_capacity(lyon_mesh.draws.len());
         for draw in lyon_mesh.draws {
+            if let TessDrawType::Shader(_) = draw.draw_type {
+                // WebGL can't run Pixel Bender shaders.
+                log::warn!("Shader fills aren't supported by the WebGL renderer, skipping");
+                continue;
+            }
+
             let num_indices = draw.indices.len() as i32;
             let num_mask_indices = draw.mask_index_count as i32;
 
@@ -603,6 +633,7 @@ impl WebGlRenderBackend {
                 TessDrawType::Color => &self.color_program,
                 TessDrawType::Gradient { .. } => &self.gradient_program,
                 TessDrawType::Bitmap(_) => &self.bitmap_program,
+                TessDrawType::Shader(_) => unreachable!("Shader draws are skipped"),
             };
 
             // Unfortunately it doesn't seem to be possible to ensure that vertex attributes will be in
@@ -688,6 +719,7 @@ impl WebGlRenderBackend {
                     num_indices,
                     num_mask_indices,
                 },
+                TessDrawType::Shader(_) => unreachable!("Shader draws are skipped"),
             });
 
             self.bind_vertex_array(None);
@@ -898,6 +930,222 @@ impl WebGlRenderBackend {
         }
     }
 
+    /// Binds the stage as the render target again, after rendering elsewhere.
+    fn bind_stage_framebuffer(&self) {
+        let framebuffer = self
+            .msaa_buffers
+            .as_ref()
+            .map(|msaa_buffers| &msaa_buffers.render_framebuffer);
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, framebuffer);
+        self.gl
+            .viewport(0, 0, self.renderbuffer_width, self.renderbuffer_height);
+    }
+
+    /// Creates and binds a framebuffer that renders into `texture`.
+    fn texture_framebuffer(&self, texture: &WebGlTexture) -> Option<WebGlFramebuffer> {
+        let framebuffer = self.gl.create_framebuffer()?;
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        self.gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(texture),
+            0,
+        );
+        Some(framebuffer)
+    }
+
+    /// Renders `commands` into the texture of `bitmap`.
+    /// The texture is cleared to `clear` first, otherwise the commands are drawn over its contents.
+    fn render_to_texture(
+        &mut self,
+        bitmap: &BitmapHandle,
+        commands: CommandList,
+        clear: Option<Color>,
+    ) -> Result<(), Error> {
+        let entry = as_registry_data(bitmap);
+        let (width, height) = (entry.width as i32, entry.height as i32);
+
+        let stencil_renderbuffer = self
+            .gl
+            .create_renderbuffer()
+            .ok_or(Error::UnableToCreateRenderBuffer)?;
+        self.gl
+            .bind_renderbuffer(Gl::RENDERBUFFER, Some(&stencil_renderbuffer));
+        self.gl
+            .renderbuffer_storage(Gl::RENDERBUFFER, Gl::STENCIL_INDEX8, width, height);
+        let framebuffer = self
+            .texture_framebuffer(&entry.texture)
+            .ok_or(Error::UnableToCreateFrameBuffer)?;
+        self.gl.framebuffer_renderbuffer(
+            Gl::FRAMEBUFFER,
+            Gl::STENCIL_ATTACHMENT,
+            Gl::RENDERBUFFER,
+            Some(&stencil_renderbuffer),
+        );
+
+        let view_matrix =
+            std::mem::replace(&mut self.view_matrix, texture_view_matrix(width, height));
+        let (mask_state, num_masks) = (self.mask_state, self.num_masks);
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.mask_state_dirty = true;
+        self.active_program = std::ptr::null();
+
+        self.gl.viewport(0, 0, width, height);
+        self.set_stencil_state();
+        self.gl.stencil_mask(0xff);
+        if let Some(clear) = clear {
+            self.gl.clear_color(
+                clear.r as f32 / 255.0,
+                clear.g as f32 / 255.0,
+                clear.b as f32 / 255.0,
+                clear.a as f32 / 255.0,
+            );
+            self.gl.clear(Gl::COLOR_BUFFER_BIT | Gl::STENCIL_BUFFER_BIT);
+        } else {
+            self.gl.clear(Gl::STENCIL_BUFFER_BIT);
+        }
+
+        commands.execute(self);
+
+        self.view_matrix = view_matrix;
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+        self.mask_state_dirty = true;
+        self.active_program = std::ptr::null();
+        self.bind_stage_framebuffer();
+
+        self.gl.delete_framebuffer(Some(&framebuffer));
+        self.gl.delete_renderbuffer(Some(&stencil_renderbuffer));
+        Ok(())
+    }
+
+    /// Applies `filter` to a region of `source`, writing the result at `dest_point` of `destination`.
+    fn apply_filter_to_texture(
+        &mut self,
+        source: &BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: &BitmapHandle,
+        dest_point: (u32, u32),
+        filter: &Filter,
+    ) -> Result<(), Error> {
+        let Some(filters) = &self.filters else {
+            return Ok(());
+        };
+
+        // Filters replace the pixels of their targets.
+        self.gl.disable(Gl::BLEND);
+        self.gl.disable(Gl::STENCIL_TEST);
+        self.gl.color_mask(true, true, true, true);
+
+        let source = as_registry_data(source);
+        let destination = as_registry_data(destination);
+        let result = self
+            .run_filter(
+                filters,
+                &source.texture,
+                (source.width, source.height),
+                source_point,
+                source_size,
+                filter,
+            )
+            .and_then(|output| {
+                let framebuffer = self
+                    .texture_framebuffer(&destination.texture)
+                    .ok_or(Error::UnableToCreateFrameBuffer)?;
+                self.copy_filter_result(filters, &output, &framebuffer, dest_point);
+                self.gl.delete_framebuffer(Some(&framebuffer));
+                Ok(())
+            });
+
+        self.gl.enable(Gl::BLEND);
+        self.mask_state_dirty = true;
+        self.active_program = std::ptr::null();
+        self.bind_stage_framebuffer();
+        result
+    }
+
+    /// Draws `bitmap` as a unit quad transformed by `world_matrix`.
+    fn draw_bitmap_quad(
+        &mut self,
+        bitmap: &BitmapHandle,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: &ColorTransform,
+        smoothing: bool,
+    ) {
+        self.set_stencil_state();
+        let entry = as_registry_data(bitmap);
+        // Adjust the quad draw to use the target bitmap.
+        let quad = &self.bitmap_quad_draws;
+        let draw = &quad[0];
+        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
+            matrix
+        } else {
+            unreachable!()
+        };
+
+        let mult_color = color_transform.mult_rgba_normalized();
+        let add_color = color_transform.add_rgba_normalized();
+
+        self.bind_vertex_array(Some(&draw.vao));
+
+        let program = &self.bitmap_program;
+
+        // Set common render state, while minimizing unnecessary state changes.
+        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
+        // are in the same location between shaders, and avoid changing them unless necessary.
+        if program as *const ShaderProgram != self.active_program {
+            self.gl.use_program(Some(&program.program));
+            self.active_program = program as *const ShaderProgram;
+
+            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
+
+            self.mult_color = None;
+            self.add_color = None;
+        }
+
+        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
+        if Some(mult_color) != self.mult_color {
+            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
+            self.mult_color = Some(mult_color);
+        }
+        if Some(add_color) != self.add_color {
+            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
+            self.add_color = Some(add_color);
+        }
+
+        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
+
+        // Bind texture.
+        self.gl.active_texture(Gl::TEXTURE0);
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
+        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
+
+        // Set texture parameters.
+        let filter = if smoothing {
+            Gl::LINEAR as i32
+        } else {
+            Gl::NEAREST as i32
+        };
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
+
+        let wrap = Gl::CLAMP_TO_EDGE as i32;
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
+
+        // Draw the triangles.
+        self.gl
+            .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_INT, 0);
+    }
+
     fn push_blend_mode(&mut self, blend: RenderBlendMode) {
         if !same_blend_mode(self.blend_modes.last(), &blend) {
             self.apply_blend_mode(blend.clone());
@@ -917,6 +1165,27 @@ impl WebGlRenderBackend {
     }
 }
 
+/// The view matrix used when rendering into a texture of the given size.
+///
+/// Textures store their top row first, so unlike the stage this isn't flipped vertically.
+fn texture_view_matrix(width: i32, height: i32) -> [[f32; 4]; 4] {
+    [
+        [2.0 / width as f32, 0.0, 0.0, 0.0],
+        [0.0, 2.0 / height as f32, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [-1.0, -1.0, 0.0, 1.0],
+    ]
+}
+
+/// The rows of a texture with the given height to read back for `region`.
+///
+/// Whole rows are read, as the callback of `resolve_sync_handle` expects.
+fn rows_to_read(region: &PixelRegion, height: u32) -> (u32, u32) {
+    let y_min = region.y_min.min(height);
+    let y_max = region.y_max.min(height).max(y_min);
+    (y_min, y_max)
+}
+
 fn same_blend_mode(first: Option<&RenderBlendMode>, second: &RenderBlendMode) -> bool {
     match (first, second) {
         (Some(RenderBlendMode::Builtin(old)), RenderBlendMode::Builtin(new)) => old == new,
@@ -927,12 +1196,56 @@ fn same_blend_mode(first: Option<&RenderBlendMode>, second: &RenderBlendMode) ->
 impl RenderBackend for WebGlRenderBackend {
     fn render_offscreen(
         &mut self,
-        _handle: BitmapHandle,
-        _commands: CommandList,
+        handle: BitmapHandle,
+        commands: CommandList,
         _quality: StageQuality,
-        _bounds: PixelRegion,
+        bounds: PixelRegion,
     ) -> Option<Box<dyn SyncHandle>> {
-        None
+        if let Err(e) = self.render_to_texture(&handle, commands, None) {
+            log::error!("Couldn't render offscreen: {}", e);
+            return None;
+        }
+        Some(Box::new(WebGlSyncHandle {
+            handle,
+            region: bounds,
+        }))
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        if let Err(e) = self.apply_filter_to_texture(
+            &source,
+            source_point,
+            source_size,
+            &destination,
+            dest_point,
+            &filter,
+        ) {
+            log::error!("Couldn't apply filter: {}", e);
+            return None;
+        }
+
+        let entry = as_registry_data(&destination);
+        let region = PixelRegion::for_whole_size(entry.width, entry.height);
+        Some(Box::new(WebGlSyncHandle {
+            handle: destination,
+            region,
+        }))
+    }
+
+    fn is_filter_supported(&self, filter: &Filter) -> bool {
+        self.filters.is_some() && is_filter_supported(filter)
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
     }
 
     fn viewport_dimensions(&self) -> ViewportDimensions {
@@ -997,9 +1310,30 @@ impl RenderBackend for WebGlRenderBackend {
         commands: CommandList,
         cache_entries: Vec<BitmapCacheEntry>,
     ) {
-        if !cache_entries.is_empty() {
-            panic!("Bitmap caching is unavailable on the webgl backend");
+        for entry in cache_entries {
+            if let Err(e) = self.render_to_texture(&entry.handle, entry.commands, Some(entry.clear))
+            {
+                log::error!("Couldn't render cached bitmap: {}", e);
+                continue;
+            }
+            let (width, height) = {
+                let texture = as_registry_data(&entry.handle);
+                (texture.width, texture.height)
+            };
+            for filter in entry.filters {
+                if let Err(e) = self.apply_filter_to_texture(
+                    &entry.handle,
+                    (0, 0),
+                    (width, height),
+                    &entry.handle,
+                    (0, 0),
+                    &filter,
+                ) {
+                    log::error!("Couldn't apply filter to cached bitmap: {}", e);
+                }
+            }
         }
+
         self.begin_frame(clear);
         commands.execute(self);
         self.end_frame();
@@ -1140,12 +1474,36 @@ impl RenderBackend for WebGlRenderBackend {
 
     fn resolve_sync_handle(
         &mut self,
-        _handle: Box<dyn SyncHandle>,
-        _with_rgba: RgbaBufRead,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
     ) -> Result<(), ruffle_render::error::Error> {
-        Err(ruffle_render::error::Error::Unimplemented(
-            "Sync handle resolution".into(),
-        ))
+        let handle = handle
+            .downcast::<WebGlSyncHandle>()
+            .expect("Sync handle must be a WebGL sync handle");
+        let entry = as_registry_data(&handle.handle);
+
+        let (y_min, y_max) = rows_to_read(&handle.region, entry.height);
+        let row_length = entry.width * 4;
+        let mut buffer = vec![0; (row_length * (y_max - y_min)) as usize];
+
+        let framebuffer = self
+            .texture_framebuffer(&entry.texture)
+            .ok_or_else(|| BitmapError::JavascriptError("Unable to create framebuffer".into()))?;
+        let result = self.gl.read_pixels_with_opt_u8_array(
+            0,
+            y_min as i32,
+            entry.width as i32,
+            (y_max - y_min) as i32,
+            Gl::RGBA,
+            Gl::UNSIGNED_BYTE,
+            Some(&mut buffer),
+        );
+        self.gl.delete_framebuffer(Some(&framebuffer));
+        self.bind_stage_framebuffer();
+        result.map_err(BitmapError::JavascriptError)?;
+
+        with_rgba(&buffer, row_length);
+        Ok(())
     }
 
     fn run_pixelbender_shader(
@@ -1168,6 +1526,22 @@ impl RenderBackend for WebGlRenderBackend {
             .ok_or_else(|| BitmapError::JavascriptError("Unable to create texture".into()))?;
         self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
 
+        // Allocate storage, so that the texture can be rendered to.
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                Gl::TEXTURE_2D,
+                0,
+                Gl::RGBA as i32,
+                width as i32,
+                height as i32,
+                0,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                None,
+            )
+            .into_js_result()
+            .map_err(|e| BitmapError::JavascriptError(e.into()))?;
+
         // You must set the texture parameters for non-power-of-2 textures to function in WebGL1.
         self.gl
             .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
@@ -1195,16 +1569,7 @@ impl CommandHandler for WebGlRenderBackend {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     ) {
-        self.set_stencil_state();
         let entry = as_registry_data(&bitmap);
-        // Adjust the quad draw to use the target bitmap.
-        let quad = &self.bitmap_quad_draws;
-        let draw = &quad[0];
-        let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type {
-            matrix
-        } else {
-            unreachable!()
-        };
 
         // Scale the quad to the bitmap's dimensions.
         let mut matrix = transform.matrix;
@@ -1222,64 +1587,24 @@ impl CommandHandler for WebGlRenderBackend {
                 1.0,
             ],
         ];
+        self.draw_bitmap_quad(&bitmap, world_matrix, &transform.color_transform, smoothing);
+    }
 
-        let mult_color = transform.color_transform.mult_rgba_normalized();
-        let add_color = transform.color_transform.add_rgba_normalized();
-
-        self.bind_vertex_array(Some(&draw.vao));
-
-        let program = &self.bitmap_program;
-
-        // Set common render state, while minimizing unnecessary state changes.
-        // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
-        // are in the same location between shaders, and avoid changing them unless necessary.
-        if program as *const ShaderProgram != self.active_program {
-            self.gl.use_program(Some(&program.program));
-            self.active_program = program as *const ShaderProgram;
-
-            program.uniform_matrix4fv(&self.gl, ShaderUniform::ViewMatrix, &self.view_matrix);
-
-            self.mult_color = None;
-            self.add_color = None;
-        }
-
-        program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
-        if Some(mult_color) != self.mult_color {
-            program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
-            self.mult_color = Some(mult_color);
-        }
-        if Some(add_color) != self.add_color {
-            program.uniform4fv(&self.gl, ShaderUniform::AddColor, &add_color);
-            self.add_color = Some(add_color);
-        }
-
-        program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, bitmap_matrix);
-
-        // Bind texture.
-        self.gl.active_texture(Gl::TEXTURE0);
-        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&entry.texture));
-        program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
-
-        // Set texture parameters.
-        let filter = if smoothing {
-            Gl::LINEAR as i32
-        } else {
-            Gl::NEAREST as i32
-        };
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
-
-        let wrap = Gl::CLAMP_TO_EDGE as i32;
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
-
-        // Draw the triangles.
-        self.gl
-            .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_INT, 0);
+    fn render_bitmap_projected(
+        &mut self,
+        bitmap: BitmapHandle,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+        smoothing: bool,
+    ) {
+        let entry = as_registry_data(&bitmap);
+        let matrix = matrix * Matrix3D::scale(entry.width.into(), entry.height.into(), 1.0);
+        self.draw_bitmap_quad(
+            &bitmap,
+            matrix.to_world_matrix(),
+            &color_transform,
+            smoothing,
+        );
     }
 
     fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
@@ -1676,7 +2001,7 @@ struct ShaderProgram {
 }
 
 // These should match the uniform names in the shaders.
-const NUM_UNIFORMS: usize = 12;
+const NUM_UNIFORMS: usize = 39;
 const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "world_matrix",
     "view_matrix",
@@ -1690,6 +2015,33 @@ const UNIFORM_NAMES: [&str; NUM_UNIFORMS] = [
     "u_focal_point",
     "u_interpolation",
     "u_texture",
+    "u_blurred",
+    "u_map",
+    "u_direction",
+    "u_size",
+    "u_color_matrix",
+    "u_color_offset",
+    "u_kernel",
+    "u_kernel_size",
+    "u_texel_size",
+    "u_divisor",
+    "u_bias",
+    "u_preserve_alpha",
+    "u_clamp",
+    "u_color",
+    "u_strength",
+    "u_offset",
+    "u_inner",
+    "u_knockout",
+    "u_composite_source",
+    "u_highlight_color",
+    "u_shadow_color",
+    "u_bevel_type",
+    "u_map_matrix",
+    "u_component_x",
+    "u_component_y",
+    "u_scale",
+    "u_mode",
 ];
 
 enum ShaderUniform {
@@ -1705,6 +2057,33 @@ enum ShaderUniform {
     GradientFocalPoint,
     GradientInterpolation,
     BitmapTexture,
+    BlurredTexture,
+    MapTexture,
+    BlurDirection,
+    BlurSize,
+    ColorMatrix,
+    ColorOffset,
+    ConvolutionKernel,
+    ConvolutionKernelSize,
+    TexelSize,
+    ConvolutionDivisor,
+    ConvolutionBias,
+    ConvolutionPreserveAlpha,
+    ConvolutionClamp,
+    FilterColor,
+    FilterStrength,
+    FilterOffset,
+    FilterInner,
+    FilterKnockout,
+    FilterCompositeSource,
+    BevelHighlightColor,
+    BevelShadowColor,
+    BevelType,
+    DisplacementMapMatrix,
+    DisplacementComponentX,
+    DisplacementComponentY,
+    DisplacementScale,
+    DisplacementMode,
 }
 
 impl ShaderProgram {
@@ -1732,10 +2111,8 @@ impl ShaderProgram {
         }
 
         // Find uniforms.
-        let mut uniforms: [Option<WebGlUniformLocation>; NUM_UNIFORMS] = Default::default();
-        for i in 0..NUM_UNIFORMS {
-            uniforms[i] = gl.get_uniform_location(&program, UNIFORM_NAMES[i]);
-        }
+        let uniforms: [Option<WebGlUniformLocation>; NUM_UNIFORMS] =
+            std::array::from_fn(|i| gl.get_uniform_location(&program, UNIFORM_NAMES[i]));
 
         let vertex_position_location = gl.get_attrib_location(&program, "position") as u32;
         let vertex_color_location = gl.get_attrib_location(&program, "color") as u32;
@@ -1770,6 +2147,14 @@ impl ShaderProgram {
         gl.uniform1i(self.uniforms[uniform as usize].as_ref(), value);
     }
 
+    fn uniform2f(&self, gl: &Gl, uniform: ShaderUniform, values: [f32; 2]) {
+        gl.uniform2f(
+            self.uniforms[uniform as usize].as_ref(),
+            values[0],
+            values[1],
+        );
+    }
+
     fn uniform4fv(&self, gl: &Gl, uniform: ShaderUniform, values: &[f32]) {
         gl.uniform4fv_with_f32_array(self.uniforms[uniform as usize].as_ref(), values);
     }
@@ -1827,3 +2212,32 @@ fn srgb_to_linear(color: &mut [f32; 4]) {
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Transforms a point in pixels with a column-major view matrix.
+    fn transform(matrix: &[[f32; 4]; 4], x: f32, y: f32) -> (f32, f32) {
+        (
+            matrix[0][0] * x + matrix[1][0] * y + matrix[3][0],
+            matrix[0][1] * x + matrix[1][1] * y + matrix[3][1],
+        )
+    }
+
+    #[test]
+    fn texture_view_matrix_maps_texture_to_clip_space() {
+        let matrix = texture_view_matrix(200, 100);
+        assert_eq!(transform(&matrix, 0.0, 0.0), (-1.0, -1.0));
+        assert_eq!(transform(&matrix, 200.0, 100.0), (1.0, 1.0));
+        assert_eq!(transform(&matrix, 100.0, 25.0), (0.0, -0.5));
+    }
+
+    #[test]
+    fn rows_to_read_are_clamped_to_the_texture() {
+        let region = PixelRegion::for_region(0, 10, 50, 20);
+        assert_eq!(rows_to_read(&region, 100), (10, 30));
+        assert_eq!(rows_to_read(&region, 25), (10, 25));
+        assert_eq!(rows_to_read(&region, 5), (5, 5));
+    }
+}
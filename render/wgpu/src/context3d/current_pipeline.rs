@@ -65,6 +65,11 @@ pub struct CurrentPipeline {
     depth_mask: bool,
     pass_compare_mode: wgpu::CompareFunction,
 
+    stencil_front: StencilFaceState,
+    stencil_back: StencilFaceState,
+    stencil_read_mask: u32,
+    stencil_write_mask: u32,
+
     color_component: wgpu::BlendComponent,
     alpha_component: wgpu::BlendComponent,
 
@@ -149,6 +154,12 @@ impl CurrentPipeline {
 
             depth_mask: true,
             pass_compare_mode: wgpu::CompareFunction::LessEqual,
+
+            stencil_front: StencilFaceState::IGNORE,
+            stencil_back: StencilFaceState::IGNORE,
+            stencil_read_mask: 0xFF,
+            stencil_write_mask: 0xFF,
+
             color_component: wgpu::BlendComponent::REPLACE,
             alpha_component: wgpu::BlendComponent::REPLACE,
             sample_count: 1,
@@ -171,6 +182,17 @@ impl CurrentPipeline {
         }
     }
 
+    pub fn remove_shaders(&mut self, shaders: &Rc<ShaderPairAgal>) {
+        if self
+            .shaders
+            .as_ref()
+            .is_some_and(|current| Rc::ptr_eq(current, shaders))
+        {
+            self.dirty.set(true);
+            self.shaders = None;
+        }
+    }
+
     pub fn update_texture_at(&mut self, index: usize, texture: Option<BoundTextureData>) {
         // FIXME - determine if the texture actually changed
         self.dirty.set(true);
@@ -211,6 +233,32 @@ impl CurrentPipeline {
         self.pass_compare_mode = pass_compare_mode;
     }
 
+    pub fn update_stencil_actions(
+        &mut self,
+        triangle_face: Context3DTriangleFace,
+        state: StencilFaceState,
+    ) {
+        let (front, back) = match triangle_face {
+            Context3DTriangleFace::None => return,
+            Context3DTriangleFace::Front => (state, self.stencil_back),
+            Context3DTriangleFace::Back => (self.stencil_front, state),
+            Context3DTriangleFace::FrontAndBack => (state, state),
+        };
+        if self.stencil_front != front || self.stencil_back != back {
+            self.dirty.set(true);
+        }
+        self.stencil_front = front;
+        self.stencil_back = back;
+    }
+
+    pub fn update_stencil_masks(&mut self, read_mask: u32, write_mask: u32) {
+        if self.stencil_read_mask != read_mask || self.stencil_write_mask != write_mask {
+            self.dirty.set(true);
+        }
+        self.stencil_read_mask = read_mask;
+        self.stencil_write_mask = write_mask;
+    }
+
     pub fn update_has_depth_texture(&mut self, has_depth_texture: bool) {
         if self.has_depth_texture != has_depth_texture {
             self.dirty.set(true);
@@ -462,12 +510,11 @@ impl CurrentPipeline {
                 format: TextureFormat::Depth24PlusStencil8,
                 depth_write_enabled: self.depth_mask,
                 depth_compare: self.pass_compare_mode,
-                // FIXME - implement this
                 stencil: wgpu::StencilState {
-                    front: StencilFaceState::IGNORE,
-                    back: StencilFaceState::IGNORE,
-                    read_mask: !0,
-                    write_mask: !0,
+                    front: self.stencil_front,
+                    back: self.stencil_back,
+                    read_mask: self.stencil_read_mask,
+                    write_mask: self.stencil_write_mask,
                 },
                 bias: Default::default(),
             })
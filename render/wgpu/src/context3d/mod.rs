@@ -1,6 +1,7 @@
 use ruffle_render::backend::{
     Context3D, Context3DBlendFactor, Context3DCommand, Context3DCompareMode, Context3DProfile,
-    Context3DTextureFormat, Context3DVertexBufferFormat, IndexBuffer, ProgramType, VertexBuffer,
+    Context3DStencilAction, Context3DTextureFormat, Context3DVertexBufferFormat, IndexBuffer,
+    ProgramType, VertexBuffer,
 };
 use ruffle_render::bitmap::BitmapHandle;
 use ruffle_render::error::Error;
@@ -20,7 +21,7 @@ use crate::utils::supported_sample_count;
 use crate::Texture;
 
 use std::num::NonZeroU64;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::sync::Arc;
 
 mod current_pipeline;
@@ -96,6 +97,16 @@ pub struct WgpuContext3D {
     seen_clear_command: bool,
 
     scissor_rectangle: Option<Rectangle<Twips>>,
+    stencil_reference: u32,
+
+    // The texture we're currently rendering to, if `setRenderToTexture` was called.
+    render_to_texture: Option<Rc<dyn ruffle_render::backend::Texture>>,
+
+    // Every buffer and texture that we've handed out. ActionScript objects can outlive
+    // the `Context3D`, so we explicitly destroy anything that's still alive when
+    // we're dropped (e.g. from `Context3D.dispose`) to release the GPU memory immediately.
+    created_buffers: Vec<Weak<wgpu::Buffer>>,
+    created_textures: Vec<Weak<wgpu::Texture>>,
 }
 
 impl WgpuContext3D {
@@ -170,9 +181,47 @@ impl WgpuContext3D {
             clear_color: None,
             seen_clear_command: false,
             scissor_rectangle: None,
+            stencil_reference: 0,
+            render_to_texture: None,
+            created_buffers: Vec::new(),
+            created_textures: Vec::new(),
         }
     }
 
+    /// Submits any pending buffer uploads. This needs to happen before we destroy
+    /// a resource, since the pending commands may still reference it.
+    fn submit_buffer_commands(&mut self) {
+        self.buffer_staging_belt.finish();
+        let new_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: create_debug_label!("Buffer command encoder").as_deref(),
+                });
+        let finished_buffer_command_encoder =
+            std::mem::replace(&mut self.buffer_command_encoder, new_encoder);
+        self.descriptors
+            .queue
+            .submit([finished_buffer_command_encoder.finish()]);
+        self.buffer_staging_belt.recall();
+    }
+
+    fn track_buffer(&mut self, buffer: wgpu::Buffer) -> Rc<wgpu::Buffer> {
+        let buffer = Rc::new(buffer);
+        self.created_buffers
+            .retain(|buffer| buffer.strong_count() > 0);
+        self.created_buffers.push(Rc::downgrade(&buffer));
+        buffer
+    }
+
+    fn track_texture(&mut self, texture: wgpu::Texture) -> Rc<wgpu::Texture> {
+        let texture = Rc::new(texture);
+        self.created_textures
+            .retain(|texture| texture.strong_count() > 0);
+        self.created_textures.push(Rc::downgrade(&texture));
+        texture
+    }
+
     fn create_depth_texture(
         &mut self,
         width: u32,
@@ -204,6 +253,7 @@ impl WgpuContext3D {
     // from ActionScript via Context3D.setRenderToBackBuffer(), or automatically
     // when calling Context3D.present()
     fn set_render_to_back_buffer(&mut self) {
+        self.render_to_texture = None;
         self.current_texture_size = self.back_buffer_size;
         self.current_texture_view = self.back_buffer_texture_view.clone();
         self.current_texture_resolve_view = self.back_buffer_resolve_texture_view.clone();
@@ -292,6 +342,9 @@ impl WgpuContext3D {
             ..Default::default()
         });
         pass.set_bind_group(0, self.bind_group.as_ref().unwrap(), &[]);
+        if self.current_depth_texture_view.is_some() {
+            pass.set_stencil_reference(self.stencil_reference);
+        }
         pass.set_pipeline(
             self.compiled_pipeline
                 .as_ref()
@@ -340,7 +393,7 @@ impl WgpuContext3D {
 }
 
 pub struct IndexBufferWrapper {
-    pub buffer: wgpu::Buffer,
+    pub buffer: Rc<wgpu::Buffer>,
     /// A cpu-side copy of the buffer data. This is used to allow us to
     /// perform unaligned writes to the GPU buffer, which is required by ActionScript.
     pub data: Vec<u8>,
@@ -348,13 +401,13 @@ pub struct IndexBufferWrapper {
 
 #[derive(Debug)]
 pub struct VertexBufferWrapper {
-    pub buffer: wgpu::Buffer,
+    pub buffer: Rc<wgpu::Buffer>,
     pub data_32_per_vertex: u8,
 }
 
 #[derive(Debug)]
 pub struct TextureWrapper {
-    texture: wgpu::Texture,
+    texture: Rc<wgpu::Texture>,
 }
 
 impl IndexBuffer for IndexBufferWrapper {}
@@ -404,7 +457,7 @@ impl Context3D for WgpuContext3D {
             mapped_at_creation: false,
         });
         Box::new(IndexBufferWrapper {
-            buffer,
+            buffer: self.track_buffer(buffer),
             data: vec![0; size as usize],
         })
     }
@@ -423,19 +476,11 @@ impl Context3D for WgpuContext3D {
             mapped_at_creation: false,
         });
         Rc::new(VertexBufferWrapper {
-            buffer,
+            buffer: self.track_buffer(buffer),
             data_32_per_vertex,
         })
     }
 
-    fn disposed_index_buffer_handle(&self) -> Rc<dyn IndexBuffer> {
-        todo!()
-    }
-
-    fn disposed_vertex_buffer_handle(&self) -> Rc<dyn VertexBuffer> {
-        todo!()
-    }
-
     fn create_texture(
         &mut self,
         width: u32,
@@ -474,7 +519,9 @@ impl Context3D for WgpuContext3D {
             view_formats: &[format],
             usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | render_attachment,
         });
-        Ok(Rc::new(TextureWrapper { texture }))
+        Ok(Rc::new(TextureWrapper {
+            texture: self.track_texture(texture),
+        }))
     }
 
     fn create_cube_texture(
@@ -512,7 +559,9 @@ impl Context3D for WgpuContext3D {
                 | TextureUsages::COPY_DST
                 | TextureUsages::RENDER_ATTACHMENT,
         });
-        Ok(Rc::new(TextureWrapper { texture }))
+        Ok(Rc::new(TextureWrapper {
+            texture: self.track_texture(texture),
+        }))
     }
 
     fn process_command(&mut self, command: Context3DCommand<'_>) {
@@ -850,6 +899,7 @@ impl Context3D for WgpuContext3D {
                 self.current_pipeline
                     .update_has_depth_texture(enable_depth_and_stencil);
                 self.current_pipeline.remove_texture(&texture);
+                self.render_to_texture = Some(texture);
                 self.current_pipeline.update_sample_count(sample_count);
                 self.current_pipeline
                     .update_target_format(texture_wrapper.texture.format());
@@ -1124,17 +1174,8 @@ impl Context3D for WgpuContext3D {
                 depth_mask,
                 pass_compare_mode,
             } => {
-                let function = match pass_compare_mode {
-                    Context3DCompareMode::Always => wgpu::CompareFunction::Always,
-                    Context3DCompareMode::Equal => wgpu::CompareFunction::Equal,
-                    Context3DCompareMode::Greater => wgpu::CompareFunction::Greater,
-                    Context3DCompareMode::GreaterEqual => wgpu::CompareFunction::GreaterEqual,
-                    Context3DCompareMode::Less => wgpu::CompareFunction::Less,
-                    Context3DCompareMode::LessEqual => wgpu::CompareFunction::LessEqual,
-                    Context3DCompareMode::Never => wgpu::CompareFunction::Never,
-                    Context3DCompareMode::NotEqual => wgpu::CompareFunction::NotEqual,
-                };
-                self.current_pipeline.update_depth(depth_mask, function);
+                self.current_pipeline
+                    .update_depth(depth_mask, convert_compare_mode(pass_compare_mode));
             }
             Context3DCommand::SetBlendFactors {
                 source_factor,
@@ -1210,6 +1251,86 @@ impl Context3D for WgpuContext3D {
             Context3DCommand::SetScissorRectangle { rect } => {
                 self.scissor_rectangle = rect;
             }
+            Context3DCommand::SetStencilActions {
+                triangle_face,
+                compare_mode,
+                action_on_both_pass,
+                action_on_depth_fail,
+                action_on_depth_pass_stencil_fail,
+            } => {
+                let state = wgpu::StencilFaceState {
+                    compare: convert_compare_mode(compare_mode),
+                    fail_op: convert_stencil_action(action_on_depth_pass_stencil_fail),
+                    depth_fail_op: convert_stencil_action(action_on_depth_fail),
+                    pass_op: convert_stencil_action(action_on_both_pass),
+                };
+                self.current_pipeline
+                    .update_stencil_actions(triangle_face, state);
+            }
+            Context3DCommand::SetStencilReferenceValue {
+                reference_value,
+                read_mask,
+                write_mask,
+            } => {
+                // The reference value is dynamic render pass state, so it doesn't
+                // require rebuilding the pipeline.
+                self.stencil_reference = reference_value;
+                self.current_pipeline
+                    .update_stencil_masks(read_mask, write_mask);
+            }
+            Context3DCommand::DisposeIndexBuffer { buffer } => {
+                let buffer = buffer
+                    .as_any()
+                    .downcast_ref::<IndexBufferWrapper>()
+                    .unwrap();
+                self.submit_buffer_commands();
+                buffer.buffer.destroy();
+            }
+            Context3DCommand::DisposeVertexBuffer { buffer } => {
+                let buffer = buffer
+                    .into_any_rc()
+                    .downcast::<VertexBufferWrapper>()
+                    .unwrap();
+                for (index, attribute) in self.vertex_attributes.iter_mut().enumerate() {
+                    if attribute
+                        .as_ref()
+                        .is_some_and(|attr| Rc::ptr_eq(&attr.buffer, &buffer))
+                    {
+                        *attribute = None;
+                        self.current_pipeline.update_vertex_buffer_at(index);
+                    }
+                }
+                self.submit_buffer_commands();
+                buffer.buffer.destroy();
+            }
+            Context3DCommand::DisposeTexture { texture } => {
+                self.current_pipeline.remove_texture(&texture);
+                if self
+                    .render_to_texture
+                    .as_ref()
+                    .is_some_and(|target| Rc::ptr_eq(target, &texture))
+                {
+                    self.set_render_to_back_buffer();
+                }
+                let texture = texture.as_any().downcast_ref::<TextureWrapper>().unwrap();
+                self.submit_buffer_commands();
+                texture.texture.destroy();
+            }
+            Context3DCommand::DisposeShaders { module } => {
+                let module = module.into_any_rc().downcast::<ShaderPairAgal>().unwrap();
+                self.current_pipeline.remove_shaders(&module);
+            }
+        }
+    }
+}
+
+impl Drop for WgpuContext3D {
+    fn drop(&mut self) {
+        for buffer in self.created_buffers.iter().filter_map(Weak::upgrade) {
+            buffer.destroy();
+        }
+        for texture in self.created_textures.iter().filter_map(Weak::upgrade) {
+            texture.destroy();
         }
     }
 }
@@ -1222,6 +1343,32 @@ pub struct ClearColor {
     mask: u32,
 }
 
+fn convert_compare_mode(mode: Context3DCompareMode) -> wgpu::CompareFunction {
+    match mode {
+        Context3DCompareMode::Always => wgpu::CompareFunction::Always,
+        Context3DCompareMode::Equal => wgpu::CompareFunction::Equal,
+        Context3DCompareMode::Greater => wgpu::CompareFunction::Greater,
+        Context3DCompareMode::GreaterEqual => wgpu::CompareFunction::GreaterEqual,
+        Context3DCompareMode::Less => wgpu::CompareFunction::Less,
+        Context3DCompareMode::LessEqual => wgpu::CompareFunction::LessEqual,
+        Context3DCompareMode::Never => wgpu::CompareFunction::Never,
+        Context3DCompareMode::NotEqual => wgpu::CompareFunction::NotEqual,
+    }
+}
+
+fn convert_stencil_action(action: Context3DStencilAction) -> wgpu::StencilOperation {
+    match action {
+        Context3DStencilAction::DecrementSaturate => wgpu::StencilOperation::DecrementClamp,
+        Context3DStencilAction::DecrementWrap => wgpu::StencilOperation::DecrementWrap,
+        Context3DStencilAction::IncrementSaturate => wgpu::StencilOperation::IncrementClamp,
+        Context3DStencilAction::IncrementWrap => wgpu::StencilOperation::IncrementWrap,
+        Context3DStencilAction::Invert => wgpu::StencilOperation::Invert,
+        Context3DStencilAction::Keep => wgpu::StencilOperation::Keep,
+        Context3DStencilAction::Set => wgpu::StencilOperation::Replace,
+        Context3DStencilAction::Zero => wgpu::StencilOperation::Zero,
+    }
+}
+
 fn convert_texture_format(input: Context3DTextureFormat) -> Result<wgpu::TextureFormat, Error> {
     match input {
         // Some of these formats are unsupported by wgpu to various degrees:
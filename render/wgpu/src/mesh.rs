@@ -4,12 +4,18 @@ use crate::{
     as_texture, Descriptors, GradientUniforms, PosColorVertex, PosVertex, TextureTransforms,
 };
 use std::ops::Range;
+use std::sync::{Arc, Mutex};
 use wgpu::util::DeviceExt;
 
 use crate::buffer_builder::BufferBuilder;
+use crate::filters::FilterSource;
+use crate::pixel_bender::{run_pixelbender_shader_impl, ShaderMode};
 use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
 use ruffle_render::bitmap::BitmapSource;
-use ruffle_render::tessellator::{Bitmap, Draw as LyonDraw, DrawType as TessDrawType, Gradient};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::tessellator::{
+    Bitmap, Draw as LyonDraw, DrawType as TessDrawType, Gradient, Shader,
+};
 use swf::{CharacterId, GradientInterpolation};
 
 /// How big to make gradient textures. Larger will keep more detail, but be slower and use more memory.
@@ -24,6 +30,23 @@ pub struct Mesh {
 
 impl ShapeHandleImpl for Mesh {}
 
+impl Mesh {
+    /// Renders the shader fills of this mesh at the scale of `matrix`, in the order of their draws.
+    pub fn shader_fills(
+        &self,
+        descriptors: &Descriptors,
+        matrix: &Matrix,
+    ) -> Vec<Option<Arc<BitmapBinds>>> {
+        self.draws
+            .iter()
+            .filter_map(|draw| match &draw.draw_type {
+                DrawType::Shader { fill } => Some(fill.binds(descriptors, matrix)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
     <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be a WGPU ShapeData")
 }
@@ -77,6 +100,17 @@ impl PendingDraw {
         vertex_buffer: &mut BufferBuilder,
         index_buffer: &mut BufferBuilder,
     ) -> Option<Self> {
+        // Shader fills are rendered into a texture covering the draw.
+        let bounds = draw.vertices.iter().fold(None, |bounds, vertex| {
+            let (x0, y0, x1, y1) = bounds.unwrap_or((vertex.x, vertex.y, vertex.x, vertex.y));
+            Some((
+                x0.min(vertex.x),
+                y0.min(vertex.y),
+                x1.max(vertex.x),
+                y1.max(vertex.y),
+            ))
+        });
+
         let vertices = if matches!(draw.draw_type, TessDrawType::Color) {
             let vertices: Vec<_> = draw
                 .vertices
@@ -106,6 +140,9 @@ impl PendingDraw {
             TessDrawType::Bitmap(bitmap) => {
                 PendingDrawType::bitmap(bitmap, shape_id, draw_id, source, backend, uniform_buffer)?
             }
+            TessDrawType::Shader(shader) => {
+                PendingDrawType::shader(shader, bounds?, shape_id, draw_id, backend.descriptors())
+            }
         };
         Some(PendingDraw {
             draw_type,
@@ -133,6 +170,9 @@ pub enum PendingDrawType {
         is_smoothed: bool,
         bind_group_label: Option<String>,
     },
+    Shader {
+        fill: ShaderFill,
+    },
 }
 
 /// Converts an RGBA color from sRGB space to linear color space.
@@ -195,6 +235,52 @@ impl PendingDrawType {
         })
     }
 
+    pub fn shader(
+        shader: Shader,
+        (x_min, y_min, x_max, y_max): (f32, f32, f32, f32),
+        shape_id: CharacterId,
+        draw_id: usize,
+        descriptors: &Descriptors,
+    ) -> Self {
+        // The area of the shape covered by this draw, in whole pixels.
+        let origin_x = x_min.floor();
+        let origin_y = y_min.floor();
+        let width = (x_max.ceil() - origin_x).max(1.0);
+        let height = (y_max.ceil() - origin_y).max(1.0);
+
+        // The texture is drawn like a bitmap fill, from shape space to UV space.
+        let texture_transforms = TextureTransforms {
+            u_matrix: [
+                [1.0 / width, 0.0, 0.0, 0.0],
+                [0.0, 1.0 / height, 0.0, 0.0],
+                [-origin_x / width, -origin_y / height, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+            ],
+        };
+        let texture_transforms_label =
+            create_debug_label!("Shape {} (shader) draw {} transforms", shape_id, draw_id);
+        let texture_transforms =
+            descriptors
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: texture_transforms_label.as_deref(),
+                    contents: bytemuck::cast_slice(&[texture_transforms]),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+
+        PendingDrawType::Shader {
+            fill: ShaderFill {
+                shader,
+                origin: (origin_x, origin_y),
+                size: (width, height),
+                texture_transforms,
+                shape_id,
+                draw_id,
+                rendered: Mutex::new(None),
+            },
+        }
+    }
+
     pub fn finish(
         self,
         descriptors: &Descriptors,
@@ -270,6 +356,7 @@ impl PendingDrawType {
 
                 DrawType::Bitmap { binds }
             }
+            PendingDrawType::Shader { fill } => DrawType::Shader { fill },
         }
     }
 }
@@ -280,6 +367,151 @@ pub enum DrawType {
     Color,
     Gradient { bind_group: wgpu::BindGroup },
     Bitmap { binds: BitmapBinds },
+    Shader { fill: ShaderFill },
+}
+
+/// A Pixel Bender shader fill, drawn like a bitmap fill from a texture the shader is rendered into.
+///
+/// The shader is rendered again whenever the shape is drawn at a different scale,
+/// so that it's evaluated at the resolution it is displayed at.
+#[derive(Debug)]
+pub struct ShaderFill {
+    shader: Shader,
+
+    /// The top left corner and size of the area covered by the fill, in shape pixels.
+    origin: (f32, f32),
+    size: (f32, f32),
+
+    /// The transform from the shape into the UV space of the texture.
+    texture_transforms: wgpu::Buffer,
+
+    shape_id: CharacterId,
+    draw_id: usize,
+
+    /// The last rendering of the shader, and the scale it was rendered at.
+    rendered: Mutex<Option<((f32, f32), Arc<BitmapBinds>)>>,
+}
+
+impl ShaderFill {
+    /// Returns the shader rendered at the scale of `matrix`, rendering it again if the scale changed.
+    pub fn binds(&self, descriptors: &Descriptors, matrix: &Matrix) -> Option<Arc<BitmapBinds>> {
+        let scale = (
+            (matrix.a * matrix.a + matrix.b * matrix.b).sqrt(),
+            (matrix.c * matrix.c + matrix.d * matrix.d).sqrt(),
+        );
+
+        let mut rendered = self
+            .rendered
+            .lock()
+            .expect("Shader fill lock must not be poisoned");
+        if let Some((rendered_scale, binds)) = &*rendered {
+            if *rendered_scale == scale {
+                return Some(binds.clone());
+            }
+        }
+
+        let binds = Arc::new(self.render(descriptors, scale)?);
+        *rendered = Some((scale, binds.clone()));
+        Some(binds)
+    }
+
+    fn render(
+        &self,
+        descriptors: &Descriptors,
+        (scale_x, scale_y): (f32, f32),
+    ) -> Option<BitmapBinds> {
+        let (shape_id, draw_id) = (self.shape_id, self.draw_id);
+        let (origin_x, origin_y) = self.origin;
+        let (width, height) = self.size;
+
+        // The texture has as many pixels as the fill covers on the screen,
+        // limited to what fits into a texture.
+        let max_size = descriptors.limits.max_texture_dimension_2d as f32;
+        let texture_width = (width * scale_x).ceil().clamp(1.0, max_size) as u32;
+        let texture_height = (height * scale_y).ceil().clamp(1.0, max_size) as u32;
+        let pixel_width = width / texture_width as f32;
+        let pixel_height = height / texture_height as f32;
+
+        let size = wgpu::Extent3d {
+            width: texture_width,
+            height: texture_height,
+            depth_or_array_layers: 1,
+        };
+        let texture_label =
+            create_debug_label!("Shape {} (shader) draw {} texture", shape_id, draw_id);
+        let texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+            label: texture_label.as_deref(),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
+        });
+        let texture_view = texture.create_view(&Default::default());
+
+        // Maps each pixel of the texture to the shader's coordinate space.
+        let [[a, b, _], [c, d, _], [tx, ty, _]] = self.shader.matrix;
+        let coord_matrix = [
+            [a * pixel_width, b * pixel_width, 0.0, 0.0],
+            [c * pixel_height, d * pixel_height, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [
+                a * origin_x + c * origin_y + tx,
+                b * origin_x + d * origin_y + ty,
+                0.0,
+                1.0,
+            ],
+        ];
+
+        let mut encoder =
+            descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: create_debug_label!(
+                        "Shape {} (shader) draw {} encoder",
+                        shape_id,
+                        draw_id
+                    )
+                    .as_deref(),
+                });
+        if let Err(e) = run_pixelbender_shader_impl(
+            descriptors,
+            self.shader.shader.clone(),
+            ShaderMode::Fill(coord_matrix),
+            &self.shader.arguments,
+            &texture,
+            &mut encoder,
+            Some(wgpu::RenderPassColorAttachment {
+                view: &texture_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            }),
+            1,
+            &FilterSource::for_entire_texture(&texture),
+        ) {
+            tracing::error!("Failed to render shader fill: {e}");
+            return None;
+        }
+        descriptors.queue.submit(Some(encoder.finish()));
+
+        Some(BitmapBinds::new(
+            &descriptors.device,
+            &descriptors.bind_layouts.bitmap,
+            descriptors.bitmap_samplers.get_sampler(false, true),
+            &self.texture_transforms,
+            0,
+            texture_view,
+            create_debug_label!("Shape {} (shader) draw {} bindgroup", shape_id, draw_id),
+        ))
+    }
 }
 
 #[derive(Debug)]
@@ -40,6 +40,7 @@ pub struct PixelBenderWgpuShader {
     int_parameters_buffer: wgpu::Buffer,
     int_parameters_buffer_size: u64,
     zeroed_out_of_range_mode: wgpu::Buffer,
+    out_coord_matrix: wgpu::Buffer,
     staging_belt: RefCell<StagingBelt>,
 }
 
@@ -158,6 +159,16 @@ impl PixelBenderWgpuShader {
                 },
                 count: None,
             },
+            wgpu::BindGroupLayoutEntry {
+                binding: naga_pixelbender::OUT_COORD_MATRIX_INDEX,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
         ];
 
         for param in &shader.params {
@@ -228,6 +239,13 @@ impl PixelBenderWgpuShader {
             mapped_at_creation: false,
         });
 
+        let out_coord_matrix = descriptors.device.create_buffer(&BufferDescriptor {
+            label: create_debug_label!("PixelBender out_coord_matrix parameter buffer").as_deref(),
+            size: std::mem::size_of::<[[f32; 4]; 4]>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         let vertex_shader = descriptors
             .device
             .create_shader_module(ShaderModuleDescriptor {
@@ -254,6 +272,7 @@ impl PixelBenderWgpuShader {
             int_parameters_buffer,
             int_parameters_buffer_size: shaders.int_parameters_buffer_size,
             zeroed_out_of_range_mode,
+            out_coord_matrix,
             // FIXME - come up with a good chunk size
             staging_belt: RefCell::new(StagingBelt::new(8)),
         }
@@ -374,6 +393,9 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 pub enum ShaderMode {
     ShaderJob,
     Filter,
+    /// The shader is filling a shape. The matrix transforms the position of each
+    /// target pixel into the shader's coordinate space.
+    Fill([[f32; 4]; 4]),
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -432,6 +454,14 @@ pub(super) fn run_pixelbender_shader_impl(
                 size: Some(NonZeroU64::new(std::mem::size_of::<f32>() as u64 * 4).unwrap()),
             }),
         },
+        BindGroupEntry {
+            binding: naga_pixelbender::OUT_COORD_MATRIX_INDEX,
+            resource: BindingResource::Buffer(wgpu::BufferBinding {
+                buffer: &compiled_shader.out_coord_matrix,
+                offset: 0,
+                size: Some(NonZeroU64::new(std::mem::size_of::<[[f32; 4]; 4]>() as u64).unwrap()),
+            }),
+        },
     ];
 
     let mut zeroed_out_of_range_mode_slice = staging_belt.write_buffer(
@@ -446,7 +476,7 @@ pub(super) fn run_pixelbender_shader_impl(
     zeroed_out_of_range_mode_slice.copy_from_slice(bytemuck::cast_slice(&[match mode {
         // When a Shader is run via a ShaderJob, out-of-range texture sample coordinates
         // seem to be clamped to the edge of the texture (despite what the docs describe)
-        ShaderMode::ShaderJob => [0.0f32, 0.0f32, 0.0f32, 0.0f32],
+        ShaderMode::ShaderJob | ShaderMode::Fill(_) => [0.0f32, 0.0f32, 0.0f32, 0.0f32],
         // When a Shader is run through a ShaderFilter, out-of-range texture sample coordinates
         // return transparent black (0.0, 0.0, 0.0, 0.0). This is easiest to observe with
         // BitmapData.applyFilter when the BitampData destination is larger than the source.
@@ -454,6 +484,24 @@ pub(super) fn run_pixelbender_shader_impl(
     }]));
     drop(zeroed_out_of_range_mode_slice);
 
+    let mut out_coord_matrix_slice = staging_belt.write_buffer(
+        render_command_encoder,
+        &compiled_shader.out_coord_matrix,
+        0,
+        NonZeroU64::new(std::mem::size_of::<[[f32; 4]; 4]>() as u64).unwrap(),
+        &descriptors.device,
+    );
+    out_coord_matrix_slice.copy_from_slice(bytemuck::cast_slice(&match mode {
+        ShaderMode::Fill(matrix) => matrix,
+        ShaderMode::ShaderJob | ShaderMode::Filter => [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    }));
+    drop(out_coord_matrix_slice);
+
     let mut texture_views: IndexMap<u8, TextureView> = Default::default();
 
     let mut target_clone = None;
@@ -4,7 +4,7 @@ use crate::blend::{BlendType, ComplexBlend};
 use crate::buffer_builder::BufferBuilder;
 use crate::buffer_pool::TexturePool;
 use crate::dynamic_transforms::DynamicTransforms;
-use crate::mesh::{as_mesh, DrawType, Mesh};
+use crate::mesh::{as_mesh, BitmapBinds, DrawType, Mesh};
 use crate::surface::target::CommandTarget;
 use crate::surface::Surface;
 use crate::{as_texture, Descriptors, MaskState, Pipelines, Transforms};
@@ -12,9 +12,11 @@ use ruffle_render::backend::ShapeHandle;
 use ruffle_render::bitmap::BitmapHandle;
 use ruffle_render::commands::{Command, RenderBlendMode};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
+use std::sync::Arc;
 use swf::{BlendMode, ColorTransform, Fixed8};
 
 use super::target::PoolOrArcTexture;
@@ -90,7 +92,8 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
             DrawCommand::RenderShape {
                 shape,
                 transform_buffer,
-            } => self.render_shape(shape, *transform_buffer),
+                shader_fills,
+            } => self.render_shape(shape, *transform_buffer, shader_fills),
             DrawCommand::DrawRect { transform_buffer } => self.draw_rect(*transform_buffer),
             DrawCommand::PushMask => self.push_mask(),
             DrawCommand::ActivateMask => self.activate_mask(),
@@ -232,13 +235,20 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         &mut self,
         shape: &'frame ShapeHandle,
         transform_buffer: wgpu::DynamicOffset,
+        shader_fills: &'frame [Option<Arc<BitmapBinds>>],
     ) {
         if cfg!(feature = "render_debug_labels") {
             self.render_pass.push_debug_group("render_shape");
         }
 
         let mesh = as_mesh(shape);
+        let mut shader_fills = shader_fills.iter();
         for draw in &mesh.draws {
+            let shader_fill = match &draw.draw_type {
+                DrawType::Shader { .. } => shader_fills.next(),
+                _ => None,
+            };
+
             let num_indices = if self.mask_state != MaskState::DrawMaskStencil
                 && self.mask_state != MaskState::ClearMaskStencil
             {
@@ -261,6 +271,13 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 DrawType::Bitmap { binds, .. } => {
                     self.prep_bitmap(&binds.bind_group, TrivialBlend::Normal, false);
                 }
+                DrawType::Shader { .. } => {
+                    // The shader couldn't be rendered.
+                    let Some(Some(binds)) = shader_fill else {
+                        continue;
+                    };
+                    self.prep_bitmap(&binds.bind_group, TrivialBlend::Normal, false);
+                }
             }
             self.render_pass.set_bind_group(
                 1,
@@ -371,6 +388,8 @@ pub enum DrawCommand {
     RenderShape {
         shape: ShapeHandle,
         transform_buffer: wgpu::DynamicOffset,
+        /// The shader fills of the shape, rendered at the scale it's drawn at.
+        shader_fills: Vec<Option<Arc<BitmapBinds>>>,
     },
     DrawRect {
         transform_buffer: wgpu::DynamicOffset,
@@ -419,22 +438,12 @@ pub fn chunk_blends<'a>(
         dynamic_transforms: &DynamicTransforms,
         needs_stencil: bool,
         descriptors: &Descriptors,
-        matrix: Matrix,
+        matrix: Matrix3D,
         color_transform: ColorTransform,
         command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
     ) {
         let transform = Transforms {
-            world_matrix: [
-                [matrix.a, matrix.b, 0.0, 0.0],
-                [matrix.c, matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    matrix.tx.to_pixels() as f32,
-                    matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
-            ],
+            world_matrix: matrix.to_world_matrix(),
             mult_color: color_transform.mult_rgba_normalized(),
             add_color: color_transform.add_rgba_normalized(),
         };
@@ -535,7 +544,7 @@ pub fn chunk_blends<'a>(
                             dynamic_transforms,
                             needs_stencil,
                             descriptors,
-                            transform.matrix,
+                            transform.matrix.into(),
                             transform.color_transform,
                             |transform_buffer| DrawCommand::RenderTexture {
                                 _texture: texture,
@@ -593,7 +602,7 @@ pub fn chunk_blends<'a>(
                     dynamic_transforms,
                     needs_stencil,
                     descriptors,
-                    matrix,
+                    matrix.into(),
                     transform.color_transform,
                     |transform_buffer| DrawCommand::RenderBitmap {
                         bitmap,
@@ -604,6 +613,39 @@ pub fn chunk_blends<'a>(
                     },
                 );
             }
+            Command::RenderBitmapProjected {
+                bitmap,
+                matrix,
+                color_transform,
+                smoothing,
+            } => {
+                let matrix = {
+                    let texture = as_texture(&bitmap);
+                    matrix
+                        * Matrix3D::scale(
+                            texture.texture.width().into(),
+                            texture.texture.height().into(),
+                            1.0,
+                        )
+                };
+                add_to_current(
+                    &mut result,
+                    &mut current,
+                    &mut transforms,
+                    dynamic_transforms,
+                    needs_stencil,
+                    descriptors,
+                    matrix,
+                    color_transform,
+                    |transform_buffer| DrawCommand::RenderBitmap {
+                        bitmap,
+                        transform_buffer,
+                        smoothing,
+                        blend_mode: TrivialBlend::Normal,
+                        render_stage3d: false,
+                    },
+                );
+            }
             Command::RenderStage3D { bitmap, transform } => {
                 let mut matrix = transform.matrix;
                 {
@@ -620,7 +662,7 @@ pub fn chunk_blends<'a>(
                     dynamic_transforms,
                     needs_stencil,
                     descriptors,
-                    matrix,
+                    matrix.into(),
                     transform.color_transform,
                     |transform_buffer| DrawCommand::RenderBitmap {
                         bitmap,
@@ -631,20 +673,24 @@ pub fn chunk_blends<'a>(
                     },
                 );
             }
-            Command::RenderShape { shape, transform } => add_to_current(
-                &mut result,
-                &mut current,
-                &mut transforms,
-                dynamic_transforms,
-                needs_stencil,
-                descriptors,
-                transform.matrix,
-                transform.color_transform,
-                |transform_buffer| DrawCommand::RenderShape {
-                    shape,
-                    transform_buffer,
-                },
-            ),
+            Command::RenderShape { shape, transform } => {
+                let shader_fills = as_mesh(&shape).shader_fills(descriptors, &transform.matrix);
+                add_to_current(
+                    &mut result,
+                    &mut current,
+                    &mut transforms,
+                    dynamic_transforms,
+                    needs_stencil,
+                    descriptors,
+                    transform.matrix.into(),
+                    transform.color_transform,
+                    |transform_buffer| DrawCommand::RenderShape {
+                        shape,
+                        transform_buffer,
+                        shader_fills,
+                    },
+                )
+            }
             Command::DrawRect { color, matrix } => add_to_current(
                 &mut result,
                 &mut current,
@@ -652,7 +698,7 @@ pub fn chunk_blends<'a>(
                 dynamic_transforms,
                 needs_stencil,
                 descriptors,
-                matrix,
+                matrix.into(),
                 ColorTransform {
                     r_multiply: Fixed8::from_f32(f32::from(color.r) / 255.0),
                     g_multiply: Fixed8::from_f32(f32::from(color.g) / 255.0),
@@ -3,7 +3,8 @@ use chrono::{DateTime, Utc};
 use image::EncodableLayout;
 use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    FullscreenError, LanguageIdentifier, MouseCursor, ScriptTimeoutPromptFuture, UiBackend,
+    US_ENGLISH,
 };
 use url::Url;
 
@@ -83,6 +84,7 @@ impl FileDialogResult for TestFileDialogResult {
 pub struct TestUiBackend {
     fonts: Vec<Font>,
     clipboard: String,
+    ime_enabled: bool,
 }
 
 impl TestUiBackend {
@@ -90,6 +92,7 @@ impl TestUiBackend {
         Self {
             fonts,
             clipboard: "".to_string(),
+            ime_enabled: false,
         }
     }
 }
@@ -121,6 +124,14 @@ impl UiBackend for TestUiBackend {
 
     fn open_virtual_keyboard(&self) {}
 
+    fn set_ime_enabled(&mut self, enabled: bool) {
+        self.ime_enabled = enabled;
+    }
+
+    fn ime_enabled(&self) -> bool {
+        self.ime_enabled
+    }
+
     fn language(&self) -> &LanguageIdentifier {
         &US_ENGLISH
     }
@@ -189,4 +200,8 @@ impl UiBackend for TestUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn display_script_timeout_prompt(&mut self) -> Option<ScriptTimeoutPromptFuture> {
+        None
+    }
 }
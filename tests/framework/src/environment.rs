@@ -26,6 +26,7 @@ pub trait Environment {
     /// If [Self::is_render_supported] returned false, this won't be attempted.
     fn create_renderer(
         &self,
+        _requirements: &RenderOptions,
         _width: u32,
         _height: u32,
     ) -> Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)> {
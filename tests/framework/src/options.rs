@@ -150,6 +150,7 @@ pub struct PlayerOptions {
     with_audio: bool,
     with_video: bool,
     runtime: PlayerRuntime,
+    avm2_register_ir: Option<bool>,
 }
 
 impl PlayerOptions {
@@ -174,6 +175,10 @@ impl PlayerOptions {
 
         player_builder = player_builder.with_player_runtime(self.runtime);
 
+        if let Some(avm2_register_ir) = self.avm2_register_ir {
+            player_builder = player_builder.with_avm2_register_ir(avm2_register_ir);
+        }
+
         #[cfg(feature = "ruffle_video_software")]
         if self.with_video {
             use ruffle_video_software::backend::SoftwareVideoBackend;
@@ -183,6 +188,12 @@ impl PlayerOptions {
         Ok(player_builder)
     }
 
+    /// Runs AVM2 code with the register interpreter or with the stack interpreter, unless the
+    /// test itself chose one.
+    pub fn set_default_avm2_register_ir(&mut self, enabled: bool) {
+        self.avm2_register_ir.get_or_insert(enabled);
+    }
+
     pub fn can_run(&self, check_renderer: bool, environment: &impl Environment) -> bool {
         if let Some(render) = &self.with_renderer {
             // If we don't actually want to check the renderer (ie we're just listing potential tests),
@@ -208,12 +219,19 @@ impl PlayerOptions {
         environment: &impl Environment,
         dimensions: ViewportDimensions,
     ) -> Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)> {
-        if self.with_renderer.is_some() {
-            environment.create_renderer(dimensions.width, dimensions.height)
+        if let Some(render_options) = &self.with_renderer {
+            environment.create_renderer(render_options, dimensions.width, dimensions.height)
         } else {
             None
         }
     }
+
+    /// Whether images are rendered with the software backend.
+    pub fn uses_software_renderer(&self) -> bool {
+        self.with_renderer
+            .as_ref()
+            .is_some_and(|render_options| render_options.software)
+    }
 }
 
 #[derive(Deserialize, Default, Clone, Debug)]
@@ -374,6 +392,9 @@ pub struct RenderOptions {
     optional: bool,
     pub sample_count: u32,
     pub exclude_warp: bool,
+
+    /// Renders with the software backend instead of the GPU, which has its own expected images.
+    pub software: bool,
 }
 
 impl Default for RenderOptions {
@@ -382,6 +403,7 @@ impl Default for RenderOptions {
             optional: false,
             sample_count: 1,
             exclude_warp: false,
+            software: false,
         }
     }
 }
@@ -188,6 +188,7 @@ impl TestRunner {
                             image_comparison,
                             self.options.known_failure,
                             self.render_interface.as_deref(),
+                            self.options.player_options.uses_software_renderer(),
                         )?;
                     } else {
                         return Err(anyhow!("Encountered fscommand to capture and compare image '{name}', but no [image_comparison] was set up for this."));
@@ -244,8 +245,12 @@ impl TestRunner {
                         InputTextControlCode::Paste => RuffleTextControlCode::Paste,
                         InputTextControlCode::Cut => RuffleTextControlCode::Cut,
                         InputTextControlCode::Backspace => RuffleTextControlCode::Backspace,
+                        InputTextControlCode::BackspaceWord => RuffleTextControlCode::BackspaceWord,
                         InputTextControlCode::Enter => RuffleTextControlCode::Enter,
                         InputTextControlCode::Delete => RuffleTextControlCode::Delete,
+                        InputTextControlCode::DeleteWord => RuffleTextControlCode::DeleteWord,
+                        InputTextControlCode::Undo => RuffleTextControlCode::Undo,
+                        InputTextControlCode::Redo => RuffleTextControlCode::Redo,
                     },
                 },
                 AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => unreachable!(),
@@ -271,6 +276,7 @@ impl TestRunner {
                 image_comparison,
                 self.options.known_failure,
                 self.render_interface.as_deref(),
+                self.options.player_options.uses_software_renderer(),
             )?;
         }
 
@@ -295,6 +301,7 @@ impl TestRunner {
                     image_comparison,
                     self.options.known_failure,
                     self.render_interface.as_deref(),
+                    self.options.player_options.uses_software_renderer(),
                 )?;
             }
 
@@ -426,6 +433,7 @@ fn capture_and_compare_image(
     image_comparison: ImageComparison,
     known_failure: bool,
     render_interface: Option<&dyn RenderInterface>,
+    software: bool,
 ) -> Result<()> {
     use anyhow::Context;
 
@@ -435,7 +443,12 @@ fn capture_and_compare_image(
 
         let actual_image = render_interface.capture(player_lock.renderer_mut());
 
-        let expected_image_path = base_path.join(format!("{name}.expected.png"))?;
+        // The software renderer doesn't match the GPU exactly, so it has its own expected images.
+        let expected_image_path = if software {
+            base_path.join(format!("{name}.expected-software.png"))?
+        } else {
+            base_path.join(format!("{name}.expected.png"))?
+        };
         if expected_image_path.is_file()? {
             let expected_image = image::load_from_memory(&read_bytes(&expected_image_path)?)
                 .context("Failed to open expected image")?
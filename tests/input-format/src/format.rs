@@ -32,8 +32,12 @@ pub enum TextControlCode {
     Paste,
     Cut,
     Backspace,
+    BackspaceWord,
     Enter,
     Delete,
+    DeleteWord,
+    Undo,
+    Redo,
 }
 
 /// All automated event types supported by FlashTAS.
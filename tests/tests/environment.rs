@@ -14,19 +14,25 @@ impl Environment for NativeEnvironment {
     #[cfg(feature = "imgtests")]
     fn create_renderer(
         &self,
+        requirements: &ruffle_test_framework::options::RenderOptions,
         width: u32,
         height: u32,
     ) -> Option<(
         Box<dyn ruffle_test_framework::environment::RenderInterface>,
         Box<dyn ruffle_test_framework::environment::RenderBackend>,
     )> {
-        renderer::NativeRenderInterface::create_pair(width, height)
+        if requirements.software {
+            renderer::SoftwareRenderInterface::create_pair(width, height)
+        } else {
+            renderer::NativeRenderInterface::create_pair(width, height)
+        }
     }
 }
 
 #[cfg(feature = "imgtests")]
 mod renderer {
     use image::RgbaImage;
+    use ruffle_render_software::backend::SoftwareRenderBackend;
     use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
     use ruffle_render_wgpu::descriptors::Descriptors;
     use ruffle_render_wgpu::target::TextureTarget;
@@ -76,7 +82,39 @@ mod renderer {
         }
     }
 
+    /// Renders on the CPU, for the tests that opt into it.
+    pub struct SoftwareRenderInterface;
+
+    impl SoftwareRenderInterface {
+        pub fn create_pair(
+            width: u32,
+            height: u32,
+        ) -> Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)> {
+            Some((
+                Box::new(Self),
+                Box::new(SoftwareRenderBackend::new(width, height)),
+            ))
+        }
+    }
+
+    impl RenderInterface for SoftwareRenderInterface {
+        fn name(&self) -> String {
+            format!("{}-software", std::env::consts::OS)
+        }
+
+        fn capture(&self, backend: &mut Box<dyn RenderBackend>) -> RgbaImage {
+            let renderer = backend.downcast_mut::<SoftwareRenderBackend>().unwrap();
+
+            renderer.capture_frame()
+        }
+    }
+
     pub fn is_supported(requirements: &RenderOptions) -> bool {
+        if requirements.software {
+            // The software renderer is always available.
+            return true;
+        }
+
         if let Some(descriptors) = descriptors() {
             let adapter_info = descriptors.adapter.get_info();
             let is_warp =
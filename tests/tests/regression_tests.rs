@@ -62,20 +62,31 @@ fn main() {
         .into_iter()
         .map(Result::unwrap)
         .filter(|entry| entry.file_type().is_file() && entry.file_name() == "test.toml")
-        .filter_map(|file| {
-            let name = file
-                .path()
-                .parent()?
+        .flat_map(|file| {
+            let mut trials = Vec::new();
+            let Some(parent) = file.path().parent() else {
+                return trials;
+            };
+            let name = parent
                 .strip_prefix(root)
                 .context("Couldn't strip root prefix from test dir")
                 .unwrap()
                 .to_string_lossy()
                 .replace('\\', "/");
             if is_candidate(&args, &name) {
-                Some(run_test(&args, file, name))
-            } else {
-                None
+                // AVM2 tests also run on the stack interpreter, so that both interpreters are
+                // checked against the same expected output.
+                if name.starts_with("avm2/") {
+                    trials.push(run_test(
+                        &args,
+                        &file,
+                        format!("{name} (stack interpreter)"),
+                        false,
+                    ));
+                }
+                trials.push(run_test(&args, &file, name, true));
             }
+            trials
         })
         .collect();
 
@@ -101,17 +112,17 @@ fn main() {
     libtest_mimic::run(&args, tests).exit()
 }
 
-fn run_test(args: &Arguments, file: DirEntry, name: String) -> Trial {
+fn run_test(args: &Arguments, file: &DirEntry, name: String, avm2_register_ir: bool) -> Trial {
     let root = VfsPath::new(PhysicalFS::new(file.path().parent().unwrap()));
-    let test = Test::from_options(
-        TestOptions::read(&root.join("test.toml").unwrap())
-            .context("Couldn't load test options")
-            .unwrap(),
-        root,
-        name.clone(),
-    )
-    .with_context(|| format!("Couldn't create test {name}"))
-    .unwrap();
+    let mut options = TestOptions::read(&root.join("test.toml").unwrap())
+        .context("Couldn't load test options")
+        .unwrap();
+    options
+        .player_options
+        .set_default_avm2_register_ir(avm2_register_ir);
+    let test = Test::from_options(options, root, name.clone())
+        .with_context(|| format!("Couldn't create test {name}"))
+        .unwrap();
 
     let ignore = !test.should_run(!args.list, &NativeEnvironment);
 
@@ -0,0 +1,69 @@
+package {
+    import flash.display.Sprite;
+    import flash.geom.Matrix3D;
+    import flash.geom.PerspectiveProjection;
+    import flash.geom.Point;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var s:Sprite = new Sprite();
+            s.x = 10;
+            s.y = 20;
+            addChild(s);
+
+            trace("// 2D object");
+            trace("matrix3D: " + s.transform.matrix3D);
+            trace("matrix is null: " + (s.transform.matrix == null));
+            trace("z: " + s.z);
+
+            trace("// s.z = 100");
+            s.z = 100;
+            trace("z: " + s.z);
+            trace("matrix: " + s.transform.matrix);
+            trace("matrix3D.rawData: " + s.transform.matrix3D.rawData);
+
+            trace("// s.rotationX = 270, s.rotationY = 30");
+            s.rotationX = 270;
+            s.rotationY = 30;
+            trace("rotationX: " + s.rotationX);
+            trace("rotationY: " + s.rotationY);
+
+            trace("// s.transform.matrix3D = translation(5, 6, 7)");
+            var m:Matrix3D = new Matrix3D();
+            m.appendTranslation(5, 6, 7);
+            s.transform.matrix3D = m;
+            trace("x, y, z: " + s.x + ", " + s.y + ", " + s.z);
+            trace("rotationX, rotationY, rotation: " + s.rotationX + ", " + s.rotationY + ", " + s.rotation);
+
+            trace("// s.transform.matrix3D = null");
+            s.transform.matrix3D = null;
+            trace("matrix3D: " + s.transform.matrix3D);
+            trace("matrix: " + s.transform.matrix);
+            trace("z: " + s.z);
+
+            trace("// Perspective projections");
+            var root:PerspectiveProjection = transform.perspectiveProjection;
+            trace("root fieldOfView: " + root.fieldOfView);
+            trace("root focalLength: " + Math.round(root.focalLength * 100) / 100);
+            trace("root projectionCenter: " + root.projectionCenter);
+            trace("child perspectiveProjection: " + s.transform.perspectiveProjection);
+
+            var pp:PerspectiveProjection = new PerspectiveProjection();
+            pp.fieldOfView = 90;
+            pp.projectionCenter = new Point(100, 50);
+            trace("focalLength at 90 degrees: " + Math.round(pp.focalLength));
+            s.transform.perspectiveProjection = pp;
+            trace("child fieldOfView: " + s.transform.perspectiveProjection.fieldOfView);
+            trace("child projectionCenter: " + s.transform.perspectiveProjection.projectionCenter);
+
+            pp.focalLength = 480.2455317427915;
+            trace("fieldOfView from focalLength: " + Math.round(pp.fieldOfView));
+
+            try {
+                pp.fieldOfView = 180;
+            } catch (e:ArgumentError) {
+                trace("fieldOfView = 180: " + e.errorID);
+            }
+        }
+    }
+}
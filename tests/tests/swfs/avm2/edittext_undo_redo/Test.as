@@ -0,0 +1,42 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.MouseEvent;
+    import flash.events.TextEvent;
+    import flash.text.TextField;
+    import flash.text.TextFieldType;
+    import flash.text.TextFormat;
+
+    public class Test extends Sprite {
+        private var field:TextField = new TextField();
+
+        public function Test() {
+            field.type = TextFieldType.INPUT;
+            field.multiline = true;
+            field.width = 300;
+            field.height = 100;
+            field.defaultTextFormat = new TextFormat("Tinos", 20);
+            addChild(field);
+
+            field.addEventListener(Event.CHANGE, function(event:Event):void {
+                trace("change: " + field.text.replace(/[\r\n]/g, "|") +
+                      " [" + field.selectionBeginIndex + "," + field.selectionEndIndex + "]");
+            });
+            // Typing "!" edits the text from the script instead, which forgets the undo history.
+            field.addEventListener(TextEvent.TEXT_INPUT, function(event:TextEvent):void {
+                if (event.text == "!") {
+                    event.preventDefault();
+                    field.replaceText(0, 0, ">");
+                    trace("replaceText: " + field.text.replace(/[\r\n]/g, "|") +
+                          " [" + field.selectionBeginIndex + "," + field.selectionEndIndex + "]");
+                }
+            });
+            stage.addEventListener(MouseEvent.MOUSE_UP, function(event:MouseEvent):void {
+                trace("click: '" + field.text.substring(field.selectionBeginIndex, field.selectionEndIndex) + "'");
+            });
+
+            stage.focus = field;
+            field.setSelection(0, 0);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.DateTimeFormatter;
+	import flash.globalization.NationalDigitsType;
+	import flash.globalization.NumberFormatter;
+	import flash.globalization.NumberParseResult;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var nf:NumberFormatter = new NumberFormatter("en-US");
+			trace("// NumberFormatter en-US");
+			trace(nf.actualLocaleIDName + " " + nf.lastOperationStatus);
+			trace(nf.formatNumber(1234567.891));
+			trace(nf.formatNumber(-1234.5));
+			trace(nf.formatInt(-42));
+			trace(nf.formatUint(4000000000));
+
+			trace("// ARABIC_INDIC digits");
+			nf.digitsType = NationalDigitsType.ARABIC_INDIC;
+			trace(nf.digitsType + " " + nf.lastOperationStatus);
+			trace(nf.formatNumber(12.5));
+			trace(nf.parseNumber("١٢.٥"));
+
+			trace("// Unknown digits type");
+			nf.digitsType = 5;
+			trace(nf.digitsType);
+			trace(nf.formatNumber(12.5));
+			nf.digitsType = 0xFFFFFFFF;
+			trace(nf.formatNumber(12.5));
+			nf.digitsType = NationalDigitsType.EUROPEAN;
+
+			trace("// Parsing");
+			var result:NumberParseResult = nf.parse("total: -1,234.5 items");
+			trace(result.value + " " + result.startIndex + " " + result.endIndex);
+			trace(nf.parseNumber("abc") + " " + nf.lastOperationStatus);
+
+			trace("// NumberFormatter de-DE");
+			var de:NumberFormatter = new NumberFormatter("de-DE");
+			trace(de.formatNumber(-1234567.891));
+
+			trace("// DateTimeFormatter en-US");
+			var date:Date = new Date(Date.UTC(2009, 4, 3, 7, 5, 9));
+			var df:DateTimeFormatter = new DateTimeFormatter("en-US");
+			trace(df.getDateTimePattern());
+			trace(df.formatUTC(date));
+			trace(df.getFirstWeekday());
+			df.setDateTimePattern("yyyy-MM-dd HH:mm");
+			trace(df.formatUTC(date) + " " + df.lastOperationStatus);
+			df.setDateTimePattern("yyyy-xx");
+			trace(df.getDateTimePattern() + " " + df.lastOperationStatus);
+		}
+	}
+}
@@ -0,0 +1,49 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Shader;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        // donut.pbj, compiled from donut.pbk.
+        private static const DONUT:String = "a501000000a40500446f6e7574a00c6e616d65737061636500386269746176656e756500a00c76656e646f7200386269746176656e756500a00876657273696f6e000100a1010200000c5f4f7574436f6f726400a10101000002426c6f636b436f756e7400a2016d696e56616c7565003f800000a2016d617856616c75650042c80000a20164656661756c7456616c75650040a00000a101010000014d696e00a2016d696e56616c75650000000000a2016d617856616c7565003f800000a20164656661756c7456616c7565003e800000a101010100084d617800a2016d696e56616c75650000000000a2016d617856616c7565003f800000a20164656661756c7456616c7565003ee66666a10101010004576964746800a2016d696e56616c7565003f800000a2016d617856616c756500447a0000a20164656661756c7456616c75650042c80000a1010101000248656967687400a2016d696e56616c7565003f800000a2016d617856616c756500447a0000a20164656661756c7456616c75650042c80000a1010402000f636f6c6f7200a2046d696e56616c75650000000000000000000000000000000000a2046d617856616c7565003f8000003f8000003f8000003f800000a20464656661756c7456616c7565003e4ccccd3e4ccccd3e4ccccd3f800000a3000473726300a1020403000f647374003201001040a00000040400800100c00003040080000080001d01001004000000320400803f800000040400400100c00003040040040000001d040080040040001d04006100001000040400100100400003040010040040001d0400400400c000040400100100800003040010040080001d0400200400c0001d0500c104006000030500c10100f0001a050031050010001d0500c10500b0001d050031050010000305003104000000320400103f0000001d06008004000000030600800400c0001d0600610500b00001060061060000001d050031060060001d0600c104006000020600c10500b00024040011060010001d0600800400c000030600800100c0001d040010060000002a0400100000c0001d018080008000002a0100800400c0001d018040008000001d018020018000002e0180200180400034000000018080001d0300f302001b0035000000000000001d0600800500800003060080010040001d050020060000001d0600800500c00003060080010080001d05001006000000300600f10500b0001d0300f306001b003600000000000000";
+
+        public function Test() {
+            var bytes:ByteArray = new ByteArray();
+            for (var i:int = 0; i < DONUT.length; i += 2) {
+                bytes.writeByte(parseInt(DONUT.substr(i, 2), 16));
+            }
+
+            var shader:Shader = new Shader(bytes);
+            shader.data.BlockCount.value = [10.0];
+            shader.data.Min.value = [0.2];
+            shader.data.Max.value = [0.45];
+            shader.data.Width.value = [100.0];
+            shader.data.Height.value = [100.0];
+            shader.data.color.value = [0.2, 0.2, 0.2, 1.0];
+            shader.data.src.input = new BitmapData(100, 100, false, 0xFF8800);
+
+            var shape:Shape = new Shape();
+            shape.graphics.beginShaderFill(shader);
+            shape.graphics.drawRect(0, 0, 100, 100);
+            shape.graphics.endFill();
+            shape.x = 10;
+            shape.y = 10;
+            addChild(shape);
+
+            // The shader is evaluated at the scale the shape is displayed at.
+            var scaled:Shape = new Shape();
+            scaled.graphics.beginShaderFill(shader);
+            scaled.graphics.drawRect(0, 0, 100, 100);
+            scaled.graphics.endFill();
+            scaled.x = 120;
+            scaled.y = 10;
+            scaled.scaleX = 1.8;
+            scaled.scaleY = 1.8;
+            addChild(scaled);
+
+            trace("Done");
+        }
+    }
+}
@@ -0,0 +1,64 @@
+package {
+    import flash.display.GraphicsPath;
+    import flash.display.IGraphicsData;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.geom.Rectangle;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var shape:Shape = new Shape();
+            shape.graphics.beginFill(0xFF0000);
+            shape.graphics.drawRoundRectComplex(10, 20, 100, 50, 10, 0, 20, 5);
+            shape.graphics.endFill();
+            addChild(shape);
+
+            trace("// bounds");
+            var bounds:Rectangle = shape.getBounds(shape);
+            trace(shape.width + " " + shape.height + " " + bounds.x + " " + bounds.y);
+
+            trace("// path");
+            describePath(shape);
+
+            trace("// hit testing");
+            trace("inside the top-left corner: " + shape.hitTestPoint(11, 21, true));
+            trace("inside the square top-right corner: " + shape.hitTestPoint(109, 21, true));
+            trace("inside the bottom-left corner: " + shape.hitTestPoint(12, 68, true));
+            trace("center: " + shape.hitTestPoint(60, 45, true));
+
+            trace("// radii clamped to half the smaller side");
+            shape = new Shape();
+            shape.graphics.beginFill(0x00FF00);
+            shape.graphics.drawRoundRectComplex(0, 0, 80, 100, 50, 60, 70, 80);
+            shape.graphics.endFill();
+            describePath(shape);
+            trace(shape.width + " " + shape.height);
+
+            trace("// negative radii");
+            shape = new Shape();
+            shape.graphics.beginFill(0x0000FF);
+            shape.graphics.drawRoundRectComplex(0, 0, 80, 100, -5, -5, -5, -5);
+            shape.graphics.endFill();
+            describePath(shape);
+            trace(shape.width + " " + shape.height);
+        }
+
+        private function describePath(shape:Shape):void {
+            var data:Vector.<IGraphicsData> = shape.graphics.readGraphicsData();
+            for each (var item:IGraphicsData in data) {
+                var path:GraphicsPath = item as GraphicsPath;
+                if (path == null) {
+                    continue;
+                }
+                var curves:int = 0;
+                for each (var command:int in path.commands) {
+                    if (command == 3) {
+                        curves++;
+                    }
+                }
+                var last:int = path.data.length;
+                trace("commands=" + path.commands.length + " curves=" + curves + " to (" + path.data[last - 2] + "," + path.data[last - 1] + ")");
+            }
+        }
+    }
+}
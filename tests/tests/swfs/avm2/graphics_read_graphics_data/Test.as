@@ -0,0 +1,163 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.GraphicsBitmapFill;
+    import flash.display.GraphicsEndFill;
+    import flash.display.GraphicsGradientFill;
+    import flash.display.GraphicsPath;
+    import flash.display.GraphicsSolidFill;
+    import flash.display.GraphicsStroke;
+    import flash.display.IGraphicsData;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.geom.Matrix;
+
+    public class Test extends Sprite {
+        private var bitmapData:BitmapData = new BitmapData(20, 20, false, 0xFF00FF);
+
+        public function Test() {
+            var shape:Shape;
+            var gradientBox:Matrix = new Matrix();
+            gradientBox.createGradientBox(100, 50, 0, 0, 0);
+
+            trace("// solid fill");
+            shape = new Shape();
+            shape.graphics.beginFill(0xFF0000, 0.5);
+            shape.graphics.drawRect(10, 20, 30, 40);
+            shape.graphics.endFill();
+            roundTrip(shape);
+
+            trace("// linear gradient fill");
+            shape = new Shape();
+            shape.graphics.beginGradientFill("linear", [0xFF0000, 0x0000FF], [1, 0.5], [0, 255], gradientBox, "reflect", "linearRGB");
+            shape.graphics.drawRect(0, 0, 100, 50);
+            shape.graphics.endFill();
+            roundTrip(shape);
+
+            trace("// radial gradient fill with focal point");
+            shape = new Shape();
+            shape.graphics.beginGradientFill("radial", [0xFFFFFF, 0x000000], [1, 1], [0, 128], gradientBox, "repeat", "rgb", 0.5);
+            shape.graphics.drawRect(0, 0, 100, 50);
+            shape.graphics.endFill();
+            roundTrip(shape);
+
+            trace("// bitmap fill");
+            shape = new Shape();
+            shape.graphics.beginBitmapFill(bitmapData, new Matrix(2, 0, 0, 2, 5, 5), true, false);
+            shape.graphics.drawRect(0, 0, 20, 20);
+            shape.graphics.endFill();
+            roundTrip(shape);
+
+            trace("// solid stroke");
+            shape = new Shape();
+            shape.graphics.lineStyle(4, 0x00FF00, 0.25, true, "none", "square", "bevel");
+            shape.graphics.lineTo(50, 0);
+            roundTrip(shape);
+
+            trace("// gradient stroke");
+            shape = new Shape();
+            var data:Vector.<IGraphicsData> = new Vector.<IGraphicsData>();
+            data.push(new GraphicsStroke(2, false, "normal", "round", "miter", 5,
+                new GraphicsGradientFill("linear", [0xFF0000, 0x0000FF], [1, 1], [0, 255], gradientBox)));
+            data.push(new GraphicsPath(Vector.<int>([1, 2]), Vector.<Number>([0, 0, 50, 0])));
+            shape.graphics.drawGraphicsData(data);
+            roundTrip(shape);
+
+            trace("// curves");
+            shape = new Shape();
+            shape.graphics.beginFill(0x000000);
+            shape.graphics.moveTo(0, 0);
+            shape.graphics.curveTo(50, 50, 100, 0);
+            shape.graphics.cubicCurveTo(75, 25, 25, 25, 0, 0);
+            shape.graphics.endFill();
+            roundTrip(shape);
+
+            trace("// children");
+            var parent:Sprite = new Sprite();
+            parent.graphics.beginFill(0x0000FF);
+            parent.graphics.drawRect(0, 0, 10, 10);
+            parent.graphics.endFill();
+            var child:Shape = new Shape();
+            child.x = 100;
+            child.y = 200;
+            child.graphics.beginFill(0x00FF00);
+            child.graphics.drawRect(0, 0, 10, 10);
+            child.graphics.endFill();
+            parent.addChild(child);
+            trace(describe(parent.graphics.readGraphicsData(false)));
+            trace(describe(parent.graphics.readGraphicsData(true)));
+
+            trace("// cleared");
+            shape.graphics.clear();
+            trace(shape.graphics.readGraphicsData().length);
+        }
+
+        private function roundTrip(shape:Shape):void {
+            var original:String = describe(shape.graphics.readGraphicsData());
+            trace(original);
+
+            var copy:Shape = new Shape();
+            copy.graphics.drawGraphicsData(shape.graphics.readGraphicsData());
+            trace("round trip matches: " + (describe(copy.graphics.readGraphicsData()) == original));
+        }
+
+        private function describe(data:Vector.<IGraphicsData>):String {
+            var lines:Array = [];
+            for each (var item:IGraphicsData in data) {
+                lines.push(describeItem(item));
+            }
+            return lines.join("\n");
+        }
+
+        private function describeItem(item:Object):String {
+            if (item is GraphicsSolidFill) {
+                return "GraphicsSolidFill color=" + item.color.toString(16) + " alpha=" + round(item.alpha, 100);
+            }
+            if (item is GraphicsGradientFill) {
+                var colors:Array = [];
+                for each (var color:uint in item.colors) {
+                    colors.push(color.toString(16));
+                }
+                var alphas:Array = [];
+                for each (var alpha:Number in item.alphas) {
+                    alphas.push(round(alpha, 100));
+                }
+                return "GraphicsGradientFill type=" + item.type + " colors=" + colors + " alphas=" + alphas
+                    + " ratios=" + item.ratios + " matrix=" + describeMatrix(item.matrix)
+                    + " spreadMethod=" + item.spreadMethod + " interpolationMethod=" + item.interpolationMethod
+                    + " focalPointRatio=" + item.focalPointRatio;
+            }
+            if (item is GraphicsBitmapFill) {
+                return "GraphicsBitmapFill sameBitmapData=" + (item.bitmapData === bitmapData)
+                    + " matrix=" + describeMatrix(item.matrix) + " repeat=" + item.repeat + " smooth=" + item.smooth;
+            }
+            if (item is GraphicsStroke) {
+                return "GraphicsStroke thickness=" + item.thickness + " pixelHinting=" + item.pixelHinting
+                    + " scaleMode=" + item.scaleMode + " caps=" + item.caps + " joints=" + item.joints
+                    + " miterLimit=" + item.miterLimit + " fill=(" + (item.fill ? describeItem(item.fill) : "null") + ")";
+            }
+            if (item is GraphicsPath) {
+                var data:Vector.<Number> = item.data;
+                var curves:int = 0;
+                for each (var command:int in item.commands) {
+                    if (command == 3 || command == 6) {
+                        curves++;
+                    }
+                }
+                return "GraphicsPath to (" + data[data.length - 2] + "," + data[data.length - 1] + ") curves=" + curves;
+            }
+            if (item is GraphicsEndFill) {
+                return "GraphicsEndFill";
+            }
+            return "unexpected " + item;
+        }
+
+        private function describeMatrix(matrix:Matrix):String {
+            return "(" + [round(matrix.a, 10000), round(matrix.b, 10000), round(matrix.c, 10000),
+                round(matrix.d, 10000), round(matrix.tx, 100), round(matrix.ty, 100)] + ")";
+        }
+
+        private function round(value:Number, precision:Number):Number {
+            return Math.round(value * precision) / precision;
+        }
+    }
+}
@@ -0,0 +1,73 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.IMEEvent;
+    import flash.system.IME;
+    import flash.system.System;
+    import flash.text.TextField;
+    import flash.text.TextFieldType;
+
+    public class Test extends Sprite {
+        private var field: TextField;
+
+        public function Test() {
+            field = new TextField();
+            field.type = TextFieldType.INPUT;
+            field.text = "ab";
+            addChild(field);
+
+            field.addEventListener(Event.CHANGE, function(event: Event): void {
+                trace("change: " + field.text);
+            });
+            System.ime.addEventListener(IMEEvent.IME_COMPOSITION, function(event: IMEEvent): void {
+                trace("imeComposition: " + event.text);
+            });
+
+            stage.focus = field;
+            trace("/// Focusing an input field enables the IME");
+            trace("IME.enabled: " + IME.enabled);
+
+            field.setSelection(1, 1);
+            trace("/// setCompositionString shows the composition at the caret, without changing the text");
+            IME.setCompositionString("xyz");
+            dump();
+
+            trace("/// compositionSelectionChanged selects within the composition");
+            IME.compositionSelectionChanged(0, 1);
+            dump();
+
+            trace("/// setCompositionString replaces the composition");
+            IME.setCompositionString("q");
+            dump();
+
+            trace("/// doConversion commits the composition");
+            IME.doConversion();
+            dump();
+
+            trace("/// compositionAbandoned removes the composition");
+            IME.setCompositionString("zz");
+            dump();
+            IME.compositionAbandoned();
+            dump();
+
+            trace("/// An empty composition does nothing");
+            IME.setCompositionString("");
+            dump();
+            IME.doConversion();
+            dump();
+
+            trace("/// Focus changes don't override IME.enabled once it's set");
+            IME.enabled = false;
+            stage.focus = null;
+            stage.focus = field;
+            trace("IME.enabled: " + IME.enabled);
+            IME.enabled = true;
+            stage.focus = null;
+            trace("IME.enabled: " + IME.enabled);
+        }
+
+        private function dump(): void {
+            trace("text: " + field.text + ", length: " + field.length + ", selection: " + field.selectionBeginIndex + "-" + field.selectionEndIndex);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+package {
+    import flash.display.Sprite;
+    import flash.sampler.*;
+
+    public class Test extends Sprite {
+        public function Test() {
+            trace("// getSize of primitives");
+            trace(getSize(undefined));
+            trace(getSize(null));
+            trace(getSize(true));
+            trace(getSize(1));
+            trace(getSize(0.5));
+
+            run();
+        }
+
+        private function run():void {
+            startSampling();
+            var foo:Foo = new Foo();
+            var object:Object = {a: 1};
+            var array:Array = [1, 2, 3];
+            var f:Function = function():void {};
+            var constructed:Array = new Array(2);
+            pauseSampling();
+
+            var samples:Array = [];
+            for each (var sample:Sample in getSamples()) {
+                if (sample is NewObjectSample) {
+                    samples.push(sample);
+                }
+            }
+
+            trace("// NewObjectSample types");
+            for each (var newObject:NewObjectSample in samples) {
+                trace(newObject.type);
+            }
+
+            trace("// NewObjectSample objects");
+            trace(samples[0].object === foo);
+            trace(samples[1].object === object);
+            trace(samples[2].object === array);
+            trace(samples[3].object === f);
+            trace(samples[4].object === constructed);
+
+            trace("// NewObjectSample sizes");
+            trace(samples[0].size > 0);
+            trace(samples[0].size == getSize(foo));
+            trace(getSize(array) > getSize([]));
+
+            trace("// NewObjectSample ids and stacks");
+            trace(samples[1].id > samples[0].id);
+            trace(samples[0].stack.length > 0);
+            trace(samples[0].time <= samples[4].time);
+
+            trace("// sample count");
+            trace(getSampleCount() >= samples.length);
+
+            stopSampling();
+            trace("// after stopSampling");
+            trace(getSampleCount());
+        }
+    }
+}
+
+class Foo {
+    public var x:int = 5;
+}
@@ -0,0 +1,38 @@
+package {
+    import flash.display.Sprite;
+    import flash.errors.ScriptTimeoutError;
+    import flash.events.Event;
+
+    public class Test extends Sprite {
+        public function Test() {
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+            trace("Looping until the first timeout");
+            try {
+                while (true) {}
+            } catch (e:Error) {
+                trace("Caught: " + (e is ScriptTimeoutError) + " " + e.errorID);
+            }
+
+            trace("Looping until the second timeout");
+            try {
+                while (true) {}
+            } catch (e:Error) {
+                trace("Caught the second timeout (should not happen)");
+            }
+            trace("Still running (should not happen)");
+        }
+
+        private function onEnterFrame(event:Event):void {
+            removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+            // The time limit starts over on every frame.
+            trace("Next frame, looping until the first timeout");
+            try {
+                while (true) {}
+            } catch (e:ScriptTimeoutError) {
+                trace("Caught: " + e.errorID);
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Sprite;
+    import flash.display.Stage3D;
+    import flash.display3D.Context3D;
+    import flash.display3D.IndexBuffer3D;
+    import flash.display3D.Program3D;
+    import flash.display3D.VertexBuffer3D;
+    import flash.display3D.textures.CubeTexture;
+    import flash.display3D.textures.RectangleTexture;
+    import flash.display3D.textures.Texture;
+    import flash.events.Event;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var stage3d:Stage3D = stage.stage3Ds[0];
+            stage3d.addEventListener(Event.CONTEXT3D_CREATE, function(event:Event):void {
+                run(stage3d);
+            });
+            stage3d.requestContext3D();
+        }
+
+        private function attempt(name:String, f:Function):void {
+            try {
+                f();
+                trace(name + ": ok");
+            } catch (e:Error) {
+                trace(name + ": " + e);
+            }
+        }
+
+        private function run(stage3d:Stage3D):void {
+            var context:Context3D = stage3d.context3D;
+            var bitmap:BitmapData = new BitmapData(4, 4, true, 0xFF00FF00);
+
+            trace("// setStencilActions");
+            attempt("valid actions", function():void {
+                context.setStencilActions("frontAndBack", "equal", "incrementSaturate", "keep", "invert");
+            });
+            attempt("bad triangleFace", function():void {
+                context.setStencilActions("sideways", "equal");
+            });
+            attempt("bad compareMode", function():void {
+                context.setStencilActions("front", "sometimes");
+            });
+            attempt("bad actionOnBothPass", function():void {
+                context.setStencilActions("front", "always", "explode");
+            });
+            attempt("setStencilReferenceValue", function():void {
+                context.setStencilReferenceValue(1, 0xFF, 0xFF);
+            });
+
+            trace("// IndexBuffer3D");
+            var indexBuffer:IndexBuffer3D = context.createIndexBuffer(3);
+            attempt("upload before dispose", function():void {
+                indexBuffer.uploadFromVector(Vector.<uint>([0, 1, 2]), 0, 3);
+            });
+            indexBuffer.dispose();
+            attempt("uploadFromVector", function():void {
+                indexBuffer.uploadFromVector(Vector.<uint>([0, 1, 2]), 0, 3);
+            });
+            attempt("uploadFromByteArray", function():void {
+                indexBuffer.uploadFromByteArray(new ByteArray(), 0, 0, 0);
+            });
+            attempt("drawTriangles", function():void {
+                context.drawTriangles(indexBuffer);
+            });
+            attempt("dispose again", function():void {
+                indexBuffer.dispose();
+            });
+
+            trace("// VertexBuffer3D");
+            var vertexBuffer:VertexBuffer3D = context.createVertexBuffer(3, 2);
+            attempt("upload before dispose", function():void {
+                vertexBuffer.uploadFromVector(Vector.<Number>([0, 0, 1, 0, 0, 1]), 0, 3);
+            });
+            vertexBuffer.dispose();
+            attempt("uploadFromVector", function():void {
+                vertexBuffer.uploadFromVector(Vector.<Number>([0, 0, 1, 0, 0, 1]), 0, 3);
+            });
+            attempt("uploadFromByteArray", function():void {
+                vertexBuffer.uploadFromByteArray(new ByteArray(), 0, 0, 0);
+            });
+            attempt("setVertexBufferAt", function():void {
+                context.setVertexBufferAt(0, vertexBuffer, 0, "float2");
+            });
+            attempt("setVertexBufferAt null", function():void {
+                context.setVertexBufferAt(0, null);
+            });
+
+            trace("// Program3D");
+            var program:Program3D = context.createProgram();
+            program.dispose();
+            attempt("upload", function():void {
+                program.upload(new ByteArray(), new ByteArray());
+            });
+            attempt("setProgram", function():void {
+                context.setProgram(program);
+            });
+            attempt("setProgram null", function():void {
+                context.setProgram(null);
+            });
+
+            trace("// Texture");
+            var texture:Texture = context.createTexture(4, 4, "bgra", false);
+            attempt("upload before dispose", function():void {
+                texture.uploadFromBitmapData(bitmap);
+            });
+            texture.dispose();
+            attempt("uploadFromBitmapData", function():void {
+                texture.uploadFromBitmapData(bitmap);
+            });
+            attempt("uploadFromByteArray", function():void {
+                texture.uploadFromByteArray(new ByteArray(), 0);
+            });
+            attempt("setTextureAt", function():void {
+                context.setTextureAt(0, texture);
+            });
+
+            trace("// RectangleTexture");
+            var rectangleTexture:RectangleTexture = context.createRectangleTexture(4, 4, "bgra", false);
+            rectangleTexture.dispose();
+            attempt("uploadFromBitmapData", function():void {
+                rectangleTexture.uploadFromBitmapData(bitmap);
+            });
+            attempt("setTextureAt", function():void {
+                context.setTextureAt(0, rectangleTexture);
+            });
+
+            trace("// CubeTexture");
+            var cubeTexture:CubeTexture = context.createCubeTexture(4, "bgra", false);
+            cubeTexture.dispose();
+            attempt("uploadFromBitmapData", function():void {
+                cubeTexture.uploadFromBitmapData(bitmap, 0);
+            });
+            attempt("setTextureAt", function():void {
+                context.setTextureAt(0, cubeTexture);
+            });
+
+            trace("// Context3D");
+            var liveIndexBuffer:IndexBuffer3D = context.createIndexBuffer(3);
+            var liveVertexBuffer:VertexBuffer3D = context.createVertexBuffer(3, 2);
+            var liveProgram:Program3D = context.createProgram();
+            var liveTexture:Texture = context.createTexture(4, 4, "bgra", false);
+            context.dispose(false);
+            trace("stage3d.context3D: " + stage3d.context3D);
+            attempt("clear", function():void {
+                context.clear();
+            });
+            attempt("createVertexBuffer", function():void {
+                context.createVertexBuffer(3, 2);
+            });
+            attempt("createTexture", function():void {
+                context.createTexture(4, 4, "bgra", false);
+            });
+            attempt("setStencilActions", function():void {
+                context.setStencilActions("front", "always");
+            });
+            attempt("setStencilReferenceValue", function():void {
+                context.setStencilReferenceValue(1);
+            });
+            attempt("present", function():void {
+                context.present();
+            });
+            attempt("index buffer of disposed context", function():void {
+                liveIndexBuffer.uploadFromVector(Vector.<uint>([0, 1, 2]), 0, 3);
+            });
+            attempt("vertex buffer of disposed context", function():void {
+                liveVertexBuffer.uploadFromVector(Vector.<Number>([0, 0, 1, 0, 0, 1]), 0, 3);
+            });
+            attempt("program of disposed context", function():void {
+                liveProgram.upload(new ByteArray(), new ByteArray());
+            });
+            attempt("texture of disposed context", function():void {
+                liveTexture.uploadFromBitmapData(bitmap);
+            });
+            attempt("dispose buffer of disposed context", function():void {
+                liveIndexBuffer.dispose();
+            });
+        }
+    }
+}
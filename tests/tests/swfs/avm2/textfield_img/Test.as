@@ -0,0 +1,54 @@
+package {
+    import flash.display.Bitmap;
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.text.TextField;
+
+    public class Test extends Sprite {
+        private var field:TextField;
+
+        public function Test() {
+            field = new TextField();
+            field.width = 300;
+            field.height = 300;
+            field.multiline = true;
+            field.wordWrap = true;
+            field.htmlText = '<img src="image.png" id="left" width="40" height="30" hspace="5" vspace="5">' +
+                '<img src="image.png" id="right" align="right" width="20" height="20">' +
+                'Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor ' +
+                'incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud ' +
+                'exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute ' +
+                'irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla ' +
+                'pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia.';
+            addChild(field);
+
+            var left:Loader = field.getImageReference("left") as Loader;
+            trace("getImageReference(\"left\") is Loader: " + (left != null));
+            trace("getImageReference(\"right\") is Loader: " + (field.getImageReference("right") is Loader));
+            trace("getImageReference(\"missing\"): " + field.getImageReference("missing"));
+            trace("Text length: " + field.length);
+
+            left.contentLoaderInfo.addEventListener(Event.COMPLETE, onComplete);
+        }
+
+        private function onComplete(event:Event):void {
+            var left:Loader = field.getImageReference("left") as Loader;
+            var right:Loader = field.getImageReference("right") as Loader;
+            trace("Loaded, content is Bitmap: " + (left.content is Bitmap));
+            trace("left: " + left.x + ", " + left.y + ", " + left.width + "x" + left.height);
+            trace("right is on the right: " + (right.x > 200));
+
+            // Text flows around the images, and goes back to the left edge below them.
+            var firstLine:int = 0;
+            var lastLine:int = field.numLines - 1;
+            var firstX:Number = field.getCharBoundaries(field.getLineOffset(firstLine)).x;
+            var lastX:Number = field.getCharBoundaries(field.getLineOffset(lastLine)).x;
+            var firstEnd:int = field.getLineOffset(firstLine) + field.getLineLength(firstLine) - 2;
+            var firstRight:Number = field.getCharBoundaries(firstEnd).right;
+            trace("first line starts right of the left image: " + (firstX >= 50));
+            trace("first line ends left of the right image: " + (firstRight <= right.x));
+            trace("last line starts at the left edge: " + (lastX < 10));
+        }
+    }
+}
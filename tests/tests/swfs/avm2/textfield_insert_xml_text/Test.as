@@ -0,0 +1,40 @@
+package {
+    import flash.display.Sprite;
+    import flash.text.TextField;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var field:TextField = new TextField();
+            field.text = "Hello world";
+            addChild(field);
+
+            trace("/// alwaysShowSelection");
+            trace(field.alwaysShowSelection);
+            field.alwaysShowSelection = true;
+            trace(field.alwaysShowSelection);
+
+            trace("/// insertXMLText replaces a range with formatted text");
+            field.insertXMLText(6, 11, "<b>there</b>");
+            trace(field.text);
+            trace("bold: " + field.getTextFormat(0, 5).bold + " " + field.getTextFormat(6, 11).bold);
+
+            trace("/// The flashrichtext root element is ignored");
+            field.insertXMLText(0, 0, '<flashrichtext version="1"><font color="#FF0000">Oh, </font></flashrichtext>');
+            trace(field.text);
+            trace("color: " + field.getTextFormat(0, 4).color + " " + field.getTextFormat(4, 9).color);
+
+            trace("/// Pasted text is subject to restrict and maxChars");
+            field.restrict = "a-z ";
+            field.maxChars = 20;
+            field.insertXMLText(field.length, field.length, " <i>Big News</i>!", true);
+            trace(field.text);
+            trace("length: " + field.length);
+            trace("italic: " + field.getTextFormat(15, 16).italic + " " + field.getTextFormat(16, 19).italic);
+
+            trace("/// Text that isn't pasted is inserted as is");
+            field.insertXMLText(0, 0, "!!");
+            trace(field.text);
+            trace("length: " + field.length);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+package {
+    import flash.concurrent.Condition;
+    import flash.concurrent.Mutex;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.system.MessageChannel;
+    import flash.system.Worker;
+    import flash.system.WorkerDomain;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        public function Test() {
+            if (Worker.current.isPrimordial) {
+                runPrimordial();
+            } else {
+                runBackground();
+            }
+        }
+
+        private function runPrimordial():void {
+            trace("Worker.isSupported: " + Worker.isSupported);
+            trace("WorkerDomain.isSupported: " + WorkerDomain.isSupported);
+            trace("primordial: " + Worker.current.isPrimordial + " " + Worker.current.state);
+
+            var worker:Worker = WorkerDomain.current.createWorker(loaderInfo.bytes);
+            trace("created: " + worker.state + " " + worker.isPrimordial);
+            trace("workers: " + WorkerDomain.current.listWorkers().length);
+
+            var toWorker:MessageChannel = Worker.current.createMessageChannel(worker);
+            var fromWorker:MessageChannel = worker.createMessageChannel(Worker.current);
+            var bytes:ByteArray = new ByteArray();
+            bytes.shareable = true;
+            bytes.writeByte(1);
+            var mutex:Mutex = new Mutex();
+            var condition:Condition = new Condition(mutex);
+            worker.setSharedProperty("toWorker", toWorker);
+            worker.setSharedProperty("fromWorker", fromWorker);
+            worker.setSharedProperty("bytes", bytes);
+            worker.setSharedProperty("condition", condition);
+            worker.setSharedProperty("number", 42);
+
+            worker.addEventListener(Event.WORKER_STATE, function(event:Event):void {
+                trace("workerState: " + worker.state);
+            });
+            fromWorker.addEventListener(Event.CHANNEL_MESSAGE, function(event:Event):void {
+                trace("channelMessage: " + fromWorker.receive());
+                trace("terminate: " + worker.terminate());
+                trace("after terminate: " + worker.state);
+            });
+
+            toWorker.send("hello");
+            toWorker.send({name: "object", values: [1, 2]});
+            trace("messageAvailable: " + toWorker.messageAvailable);
+
+            worker.start();
+            trace("started: " + worker.state);
+
+            try {
+                condition.notify();
+            } catch (e:Error) {
+                trace("notify without the mutex: " + e.errorID);
+            }
+
+            // The worker runs while the primordial worker waits, and notifies the condition.
+            mutex.lock();
+            trace("waiting");
+            trace("notified: " + condition.wait());
+            trace("bytes after wait: " + bytes[0]);
+            mutex.unlock();
+
+            try {
+                mutex.unlock();
+            } catch (e:Error) {
+                trace("unlock without the mutex: " + e.errorID);
+            }
+        }
+
+        private function runBackground():void {
+            var current:Worker = Worker.current;
+            trace("background: " + current.isPrimordial + " " + current.state);
+            trace("number: " + current.getSharedProperty("number"));
+
+            var toWorker:MessageChannel = current.getSharedProperty("toWorker");
+            var fromWorker:MessageChannel = current.getSharedProperty("fromWorker");
+            var bytes:ByteArray = current.getSharedProperty("bytes");
+            var condition:Condition = current.getSharedProperty("condition");
+
+            trace("received: " + toWorker.receive());
+            var object:* = toWorker.receive();
+            trace("received: " + object.name + " " + object.values);
+            trace("messageAvailable: " + toWorker.messageAvailable);
+
+            trace("shareable: " + bytes.shareable + " bytes: " + bytes[0]);
+            bytes[0] = 2;
+
+            // The primordial worker released the mutex while waiting.
+            trace("tryLock: " + condition.mutex.tryLock());
+            condition.notify();
+            condition.mutex.unlock();
+
+            fromWorker.send("done");
+        }
+    }
+}
@@ -0,0 +1,54 @@
+package {
+    import flash.display.Sprite;
+
+    public class Test extends Sprite {
+        public function Test() {
+            trace("// before");
+            trace(new XML("<a/>").name().uri);
+            trace(new QName("a").uri);
+
+            withDefault();
+
+            trace("// after return");
+            trace(new XML("<a/>").name().uri);
+            trace(new QName("a").uri);
+        }
+
+        private function withDefault():void {
+            default xml namespace = "http://example.com/a";
+
+            trace("// with default");
+            trace(new XML("<a/>").name().uri);
+            trace(new XML("<p:a xmlns:p='http://example.com/p'/>").name().uri);
+            trace(new QName("a").uri);
+
+            var x:XML = <root><child/></root>;
+            trace(x.name().uri);
+            trace(x.children()[0].name().uri);
+
+            callee();
+            nested();
+
+            trace("// after nested");
+            trace(new XML("<a/>").name().uri);
+
+            var renamed:XML = new XML("<b/>");
+            renamed.setName("renamed");
+            trace(renamed.name().uri);
+            trace(renamed.localName());
+        }
+
+        private function callee():void {
+            trace("// callee");
+            trace(new XML("<a/>").name().uri);
+        }
+
+        private function nested():void {
+            default xml namespace = new Namespace("http://example.com/b");
+
+            trace("// nested");
+            trace(new XML("<a/>").name().uri);
+            trace(new QName("a").uri);
+        }
+    }
+}
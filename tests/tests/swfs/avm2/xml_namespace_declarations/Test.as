@@ -0,0 +1,55 @@
+package {
+    import flash.display.Sprite;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var x:XML = <root xmlns="http://example.com/default" xmlns:a="http://example.com/a"><a:child xmlns:b="http://example.com/b"><leaf xmlns:a="http://example.com/a2"/><same xmlns:b="http://example.com/b"/></a:child></root>;
+            var child:XML = x.children()[0];
+            var leaf:XML = child.children()[0];
+            var same:XML = child.children()[1];
+
+            trace("// root.inScopeNamespaces()");
+            dump(x.inScopeNamespaces());
+            trace("// child.inScopeNamespaces()");
+            dump(child.inScopeNamespaces());
+            trace("// leaf.inScopeNamespaces()");
+            dump(leaf.inScopeNamespaces());
+
+            trace("// root.namespaceDeclarations()");
+            dump(x.namespaceDeclarations());
+            trace("// child.namespaceDeclarations()");
+            dump(child.namespaceDeclarations());
+            trace("// leaf.namespaceDeclarations()");
+            dump(leaf.namespaceDeclarations());
+            trace("// same.namespaceDeclarations()");
+            dump(same.namespaceDeclarations());
+
+            trace("// without namespaces");
+            var plain:XML = <plain attr="value">text</plain>;
+            dump(plain.inScopeNamespaces());
+            dump(plain.namespaceDeclarations());
+            trace(plain.attributes()[0].namespaceDeclarations().length);
+            trace(plain.children()[0].namespaceDeclarations().length);
+
+            trace("// addNamespace");
+            plain.addNamespace(new Namespace("c", "http://example.com/c"));
+            dump(plain.namespaceDeclarations());
+            plain.addNamespace(new Namespace("c", "http://example.com/c2"));
+            dump(plain.namespaceDeclarations());
+
+            trace("// removeNamespace");
+            plain.removeNamespace(new Namespace("c", "http://example.com/c2"));
+            dump(plain.namespaceDeclarations());
+            x.removeNamespace(new Namespace("http://example.com/b"));
+            dump(child.namespaceDeclarations());
+            dump(same.namespaceDeclarations());
+        }
+
+        private function dump(namespaces:Array):void {
+            trace(namespaces.length + " namespace(s)");
+            for each (var ns:* in namespaces) {
+                trace("  " + ns.prefix + " = " + ns.uri);
+            }
+        }
+    }
+}
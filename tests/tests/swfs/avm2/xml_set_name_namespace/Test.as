@@ -0,0 +1,66 @@
+package {
+    import flash.display.Sprite;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var x:XML = <p:root xmlns:p="http://example.com/p" attr="value"><child/></p:root>;
+            trace("// original");
+            trace(x.name().uri);
+            trace(x.localName());
+
+            trace("// setName(String)");
+            x.setName("renamed");
+            trace(x.name().uri);
+            trace(x.localName());
+
+            trace("// setName(QName)");
+            x.setName(new QName("http://example.com/q", "qualified"));
+            trace(x.name().uri);
+            trace(x.localName());
+
+            trace("// setName(QName with Namespace)");
+            x.setName(new QName(new Namespace("n", "http://example.com/n"), "withNamespace"));
+            trace(x.name().uri);
+            trace(x.localName());
+
+            trace("// setName(QName with null uri)");
+            x.setName(new QName(null, "anyName"));
+            trace(x.localName());
+
+            trace("// setName on attribute");
+            x.@attr.setName(new QName("http://example.com/attr", "attr2"));
+            trace(x.attributes()[0].name().uri);
+            trace(x.attributes()[0].localName());
+
+            trace("// setName on text");
+            var text:XML = new XML("<a>hello</a>").children()[0];
+            text.setName("ignored");
+            trace(text.localName());
+
+            trace("// invalid names");
+            try {
+                x.setName("1invalid");
+            } catch (e:TypeError) {
+                trace(e.errorID);
+            }
+            try {
+                x.setName(new QName("http://example.com/q", "has space"));
+            } catch (e:TypeError) {
+                trace(e.errorID);
+            }
+            trace(x.localName());
+
+            withDefault();
+        }
+
+        private function withDefault():void {
+            default xml namespace = "http://example.com/default";
+
+            trace("// setName(String) with default xml namespace");
+            var x:XML = new XML("<a/>");
+            x.setName("b");
+            trace(x.name().uri);
+            trace(x.localName());
+        }
+    }
+}
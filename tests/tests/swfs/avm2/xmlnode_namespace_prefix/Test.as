@@ -0,0 +1,58 @@
+package {
+    import flash.display.Sprite;
+    import flash.xml.XMLDocument;
+    import flash.xml.XMLNode;
+    import flash.xml.XMLNodeType;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var doc:XMLDocument = new XMLDocument('<root xmlns="http://example.com/default" xmlns:a="http://example.com/a"><a:child xmlns:b="http://example.com/b"><leaf/></a:child><plain/></root>');
+            var root:XMLNode = doc.firstChild;
+            var child:XMLNode = root.firstChild;
+            var leaf:XMLNode = child.firstChild;
+            var plain:XMLNode = root.lastChild;
+
+            trace("// getNamespaceForPrefix");
+            trace(leaf.getNamespaceForPrefix("b"));
+            trace(leaf.getNamespaceForPrefix("a"));
+            trace(leaf.getNamespaceForPrefix(""));
+            trace(leaf.getNamespaceForPrefix("missing"));
+            trace(plain.getNamespaceForPrefix("b"));
+            trace(root.getNamespaceForPrefix("a"));
+
+            trace("// getPrefixForNamespace");
+            trace(leaf.getPrefixForNamespace("http://example.com/b"));
+            trace(leaf.getPrefixForNamespace("http://example.com/a"));
+            trace(leaf.getPrefixForNamespace("http://example.com/default") === "");
+            trace(leaf.getPrefixForNamespace("http://example.com/missing"));
+            trace(plain.getPrefixForNamespace("http://example.com/b"));
+
+            trace("// namespaceURI");
+            trace(root.namespaceURI);
+            trace(child.namespaceURI);
+            trace(leaf.namespaceURI);
+
+            trace("// created nodes");
+            var item:XMLNode = new XMLNode(XMLNodeType.ELEMENT_NODE, "x:item");
+            trace(item.getNamespaceForPrefix("x"));
+            trace(item.namespaceURI);
+            item.attributes["xmlns:x"] = "http://example.com/x";
+            trace(item.getNamespaceForPrefix("x"));
+            trace(item.namespaceURI);
+            trace(item.getPrefixForNamespace("http://example.com/x"));
+            leaf.appendChild(item);
+            trace(item.getNamespaceForPrefix("b"));
+            trace(item.getPrefixForNamespace("http://example.com/a"));
+
+            var text:XMLNode = new XMLNode(XMLNodeType.TEXT_NODE, "text");
+            item.appendChild(text);
+            trace(text.namespaceURI);
+            trace(text.getNamespaceForPrefix("x"));
+
+            trace("// overridden prefix");
+            leaf.attributes["xmlns:a"] = "http://example.com/a2";
+            trace(item.getNamespaceForPrefix("a"));
+            trace(child.getNamespaceForPrefix("a"));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+package {
+    import flash.display.Bitmap;
+    import flash.display.BitmapData;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.geom.Rectangle;
+
+    public class Test extends Sprite {
+        public function Test() {
+            // Unscaled, for reference.
+            addChild(panel(10, 10, 1, 1));
+
+            // Scaled non-uniformly: the corners keep their size, the edges stretch along one axis.
+            addChild(panel(120, 10, 2.5, 1.5));
+
+            // Scaled down below the size of the corners.
+            addChild(panel(10, 180, 0.5, 0.5));
+
+            // Rotated objects ignore the grid and scale normally.
+            var rotated:Sprite = panel(150, 250, 1.5, 1);
+            rotated.rotation = 10;
+            addChild(rotated);
+
+            trace("Done");
+        }
+
+        private static function panel(x:Number, y:Number, scaleX:Number, scaleY:Number):Sprite {
+            var panel:Sprite = new Sprite();
+
+            // The sprite's own drawing.
+            panel.graphics.lineStyle(4, 0x000000);
+            panel.graphics.beginFill(0x8080FF);
+            panel.graphics.drawRoundRect(0, 0, 100, 100, 40, 40);
+            panel.graphics.endFill();
+
+            // A child shape is sliced along with the drawing.
+            var shape:Shape = new Shape();
+            shape.graphics.beginFill(0xFF0000);
+            shape.graphics.drawCircle(15, 15, 8);
+            shape.graphics.drawCircle(85, 85, 8);
+            shape.graphics.endFill();
+            shape.graphics.lineStyle(2, 0x008000);
+            shape.graphics.moveTo(30, 50);
+            shape.graphics.lineTo(70, 50);
+            panel.addChild(shape);
+
+            // Children that aren't shapes are scaled normally.
+            var nested:Sprite = new Sprite();
+            nested.graphics.beginFill(0xFFFF00);
+            nested.graphics.drawRoundRect(0, 0, 20, 20, 10, 10);
+            nested.graphics.endFill();
+            nested.x = 70;
+            nested.y = 10;
+            panel.addChild(nested);
+
+            var bitmap:Bitmap = new Bitmap(new BitmapData(20, 20, false, 0x00C000));
+            bitmap.x = 10;
+            bitmap.y = 70;
+            panel.addChild(bitmap);
+
+            panel.scale9Grid = new Rectangle(25, 25, 50, 50);
+            panel.x = x;
+            panel.y = y;
+            panel.scaleX = scaleX;
+            panel.scaleY = scaleY;
+            return panel;
+        }
+    }
+}
@@ -0,0 +1,70 @@
+package {
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.geom.Matrix3D;
+    import flash.geom.PerspectiveProjection;
+    import flash.geom.Point;
+    import flash.geom.Vector3D;
+
+    public class Test extends Sprite {
+        public function Test() {
+            // Flat, for reference.
+            addChild(square(0xFF0000, 75, 75));
+
+            var rotatedY:Shape = square(0x00FF00, 200, 75);
+            rotatedY.rotationY = 45;
+            addChild(rotatedY);
+
+            var rotatedX:Shape = square(0x0000FF, 325, 75);
+            rotatedX.rotationX = 60;
+            addChild(rotatedX);
+
+            var far:Shape = square(0xFF00FF, 75, 200);
+            far.z = 300;
+            addChild(far);
+
+            var near:Shape = square(0x00FFFF, 200, 200);
+            near.z = -100;
+            addChild(near);
+
+            var matrix:Shape = square(0xFFFF00, 325, 200);
+            var m:Matrix3D = new Matrix3D();
+            m.appendRotation(30, Vector3D.X_AXIS);
+            m.appendRotation(30, Vector3D.Y_AXIS);
+            m.appendTranslation(325, 200, 50);
+            matrix.transform.matrix3D = m;
+            addChild(matrix);
+
+            // A container with its own, off-center vanishing point and a wide field of view.
+            var container:Sprite = new Sprite();
+            container.y = 275;
+            var projection:PerspectiveProjection = new PerspectiveProjection();
+            projection.fieldOfView = 100;
+            projection.projectionCenter = new Point(0, 0);
+            container.transform.perspectiveProjection = projection;
+            addChild(container);
+
+            for (var i:int = 0; i < 3; i++) {
+                var child:Shape = square(0x808080 + i * 0x202020, 75 + i * 125, 50);
+                child.rotationY = -30;
+                child.z = i * 100;
+                container.addChild(child);
+            }
+
+            trace("Done");
+        }
+
+        private static function square(color:uint, x:Number, y:Number):Shape {
+            var shape:Shape = new Shape();
+            shape.graphics.beginFill(color);
+            shape.graphics.drawRect(-40, -40, 80, 80);
+            shape.graphics.endFill();
+            shape.graphics.lineStyle(2, 0x000000);
+            shape.graphics.moveTo(-40, 0);
+            shape.graphics.lineTo(40, 0);
+            shape.x = x;
+            shape.y = y;
+            return shape;
+        }
+    }
+}
@@ -0,0 +1,34 @@
+const { jsApiBefore, playAndMonitor } = require("../utils");
+const { expect, use } = require("chai");
+const chaiHtml = require("chai-html");
+
+use(chaiHtml);
+
+describe("WebGL renderer", () => {
+    jsApiBefore();
+
+    it("draws offscreen, filters and cached bitmaps", async () => {
+        const player = await browser.$("<ruffle-player>");
+        await browser.execute((player) => {
+            player.load({
+                url: "/test_assets/webgl_offscreen.swf",
+                preferredRenderer: "webgl",
+            });
+        }, player);
+        await playAndMonitor(
+            browser,
+            player,
+            `draw: ffff0000 0
+applyFilter: 0 ffff0000
+blur spreads: true true
+rendered cached bitmaps
+`,
+        );
+
+        const renderer = await browser.execute(
+            (player) => player.instance.renderer_name(),
+            player,
+        );
+        expect(renderer).to.eql("webgl");
+    });
+});
@@ -0,0 +1,80 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.filters.BlurFilter;
+    import flash.filters.ColorMatrixFilter;
+    import flash.filters.GlowFilter;
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+
+    // Exercises offscreen rendering, filters and cached bitmaps, for renderer tests.
+    public class WebGlOffscreen extends Sprite {
+        private var frames:int = 0;
+        private var output:Array = [];
+
+        public function WebGlOffscreen() {
+            // BitmapData.draw renders offscreen.
+            var shape:Shape = new Shape();
+            shape.graphics.beginFill(0xFF0000);
+            shape.graphics.drawRect(0, 0, 10, 20);
+            shape.graphics.endFill();
+            var drawn:BitmapData = new BitmapData(20, 20, true, 0);
+            drawn.draw(shape);
+            output.push("draw: " + hex(drawn.getPixel32(5, 5)) + " " + hex(drawn.getPixel32(15, 5)));
+
+            // BitmapData.applyFilter filters one texture into another.
+            var green:BitmapData = new BitmapData(20, 20, true, 0xFF00FF00);
+            var swapped:BitmapData = new BitmapData(20, 20, true, 0);
+            swapped.applyFilter(green, new Rectangle(0, 0, 10, 20), new Point(10, 0), new ColorMatrixFilter([
+                0, 1, 0, 0, 0,
+                1, 0, 0, 0, 0,
+                0, 0, 1, 0, 0,
+                0, 0, 0, 1, 0
+            ]));
+            output.push("applyFilter: " + hex(swapped.getPixel32(5, 5)) + " " + hex(swapped.getPixel32(15, 5)));
+
+            var dot:BitmapData = new BitmapData(20, 20, true, 0);
+            dot.setPixel32(10, 10, 0xFFFFFFFF);
+            var blurred:BitmapData = new BitmapData(20, 20, true, 0);
+            blurred.applyFilter(dot, dot.rect, new Point(), new BlurFilter(4, 4, 1));
+            output.push("blur spreads: " + ((blurred.getPixel32(11, 10) >>> 24) > 0) + " " + ((blurred.getPixel32(10, 10) >>> 24) < 0xFF));
+
+            // Filters on display objects are drawn through their cached bitmaps.
+            var cached:Sprite = new Sprite();
+            cached.graphics.beginFill(0x0000FF);
+            cached.graphics.drawRect(0, 0, 50, 50);
+            cached.graphics.endFill();
+            cached.x = 25;
+            cached.y = 25;
+            cached.filters = [new GlowFilter(0xFFFF00, 1, 8, 8)];
+            addChild(cached);
+
+            var plain:Sprite = new Sprite();
+            plain.graphics.beginFill(0x00FF00);
+            plain.graphics.drawCircle(0, 0, 20);
+            plain.graphics.endFill();
+            plain.x = 150;
+            plain.y = 50;
+            plain.cacheAsBitmap = true;
+            addChild(plain);
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(event:Event):void {
+            // Let the cached bitmaps render a few times before reporting.
+            if (++frames < 3) {
+                return;
+            }
+            removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            output.push("rendered cached bitmaps");
+            trace(output.join("\n"));
+        }
+
+        private static function hex(color:uint):String {
+            return color.toString(16);
+        }
+    }
+}
@@ -1949,6 +1949,9 @@ pub fn web_to_ruffle_text_control(
                 Some('c') => Some(TextControlCode::Copy),
                 Some('v') => Some(TextControlCode::Paste),
                 Some('x') => Some(TextControlCode::Cut),
+                Some('z' | 'Z') if shift_key => Some(TextControlCode::Redo),
+                Some('z') => Some(TextControlCode::Undo),
+                Some('y') => Some(TextControlCode::Redo),
                 _ => None,
             }
         } else {
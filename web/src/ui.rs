@@ -4,7 +4,8 @@ use ruffle_core::backend::ui::{
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
 };
 use ruffle_core::backend::ui::{
-    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, ScriptTimeoutPromptFuture,
+    UiBackend, US_ENGLISH,
 };
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
@@ -253,6 +254,14 @@ impl UiBackend for WebUiBackend {
         self.js_player.open_virtual_keyboard()
     }
 
+    // Composition happens in the browser's own text input, which
+    // delivers the final text through the regular input events.
+    fn set_ime_enabled(&mut self, _enabled: bool) {}
+
+    fn ime_enabled(&self) -> bool {
+        false
+    }
+
     fn language(&self) -> &LanguageIdentifier {
         &self.language
     }
@@ -341,4 +350,8 @@ impl UiBackend for WebUiBackend {
         }))
         */
     }
+
+    fn display_script_timeout_prompt(&mut self) -> Option<ScriptTimeoutPromptFuture> {
+        None
+    }
 }